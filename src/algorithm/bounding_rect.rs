@@ -2,8 +2,9 @@ use crate::geo_traits::{
     LineStringTrait, MultiLineStringTrait, MultiPointTrait, MultiPolygonTrait, PointTrait,
     PolygonTrait,
 };
-use crate::{LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
+use crate::{GeometryArrayTrait, RectArray};
 use geo::{coord, Rect};
+use rstar::{RTreeObject, AABB};
 
 #[derive(Debug, Clone, Copy)]
 struct BoundingRect {
@@ -40,6 +41,32 @@ impl BoundingRect {
     }
 }
 
+impl BoundingRect {
+    /// Expand this box to include `envelope`.
+    ///
+    /// The envelope of an empty geometry spans infinite bounds and is skipped.
+    fn add_envelope(&mut self, envelope: &AABB<[f64; 2]>) {
+        let [minx, miny] = envelope.lower();
+        let [maxx, maxy] = envelope.upper();
+        if ![minx, miny, maxx, maxy]
+            .iter()
+            .all(|value| value.is_finite())
+        {
+            return;
+        }
+
+        self.minx = self.minx.min(minx);
+        self.miny = self.miny.min(miny);
+        self.maxx = self.maxx.max(maxx);
+        self.maxy = self.maxy.max(maxy);
+    }
+
+    /// Whether no coordinate has been added to this box.
+    fn is_empty(&self) -> bool {
+        self.minx > self.maxx || self.miny > self.maxy
+    }
+}
+
 impl From<BoundingRect> for Rect {
     fn from(value: BoundingRect) -> Self {
         let min_coord = coord! { x: value.minx, y: value.miny };
@@ -54,81 +81,173 @@ impl From<BoundingRect> for ([f64; 2], [f64; 2]) {
     }
 }
 
-pub fn bounding_rect_point(geom: &'_ Point) -> ([f64; 2], [f64; 2]) {
+impl BoundingRect {
+    fn add_line_string<'a>(&mut self, geom: &impl LineStringTrait<'a>) {
+        for coord_idx in 0..geom.num_points() {
+            self.update(geom.point(coord_idx).unwrap());
+        }
+    }
+
+    fn add_polygon<'a>(&mut self, geom: &impl PolygonTrait<'a>) {
+        self.add_line_string(&geom.exterior());
+        for interior_idx in 0..geom.num_interiors() {
+            self.add_line_string(&geom.interior(interior_idx).unwrap());
+        }
+    }
+}
+
+/// The bounding box of a point, as `(lower, upper)` corners.
+pub fn bounding_rect_point(geom: impl PointTrait) -> ([f64; 2], [f64; 2]) {
     let mut rect = BoundingRect::new();
     rect.update(geom);
     rect.into()
 }
 
-pub fn bounding_rect_multipoint(geom: &'_ MultiPoint) -> ([f64; 2], [f64; 2]) {
+/// The bounding box of a multi point, as `(lower, upper)` corners.
+pub fn bounding_rect_multipoint<'a>(geom: &impl MultiPointTrait<'a>) -> ([f64; 2], [f64; 2]) {
     let mut rect = BoundingRect::new();
     for geom_idx in 0..geom.num_points() {
-        let point = geom.point(geom_idx).unwrap();
-        rect.update(point);
+        rect.update(geom.point(geom_idx).unwrap());
     }
     rect.into()
 }
 
-pub fn bounding_rect_linestring(geom: &'_ LineString) -> ([f64; 2], [f64; 2]) {
+/// The bounding box of a line string, as `(lower, upper)` corners.
+pub fn bounding_rect_linestring<'a>(geom: &impl LineStringTrait<'a>) -> ([f64; 2], [f64; 2]) {
     let mut rect = BoundingRect::new();
-    for geom_idx in 0..geom.num_points() {
-        let point = geom.point(geom_idx).unwrap();
-        rect.update(point);
-    }
+    rect.add_line_string(geom);
     rect.into()
 }
 
-pub fn bounding_rect_multilinestring(geom: &'_ MultiLineString) -> ([f64; 2], [f64; 2]) {
+/// The bounding box of a multi line string, as `(lower, upper)` corners.
+pub fn bounding_rect_multilinestring<'a>(
+    geom: &impl MultiLineStringTrait<'a>,
+) -> ([f64; 2], [f64; 2]) {
     let mut rect = BoundingRect::new();
     for geom_idx in 0..geom.num_lines() {
-        let linestring = geom.line(geom_idx).unwrap();
-        for coord_idx in 0..linestring.num_points() {
-            let point = linestring.point(coord_idx).unwrap();
-            rect.update(point);
-        }
+        rect.add_line_string(&geom.line(geom_idx).unwrap());
     }
     rect.into()
 }
 
-pub fn bounding_rect_polygon(geom: &'_ Polygon) -> ([f64; 2], [f64; 2]) {
+/// The bounding box of a polygon, as `(lower, upper)` corners.
+pub fn bounding_rect_polygon<'a>(geom: &impl PolygonTrait<'a>) -> ([f64; 2], [f64; 2]) {
     let mut rect = BoundingRect::new();
-    let exterior_ring = geom.exterior();
-    for coord_idx in 0..exterior_ring.num_points() {
-        let point = exterior_ring.point(coord_idx).unwrap();
-        rect.update(point);
-    }
+    rect.add_polygon(geom);
+    rect.into()
+}
 
-    for interior_idx in 0..geom.num_interiors() {
-        let linestring = geom.interior(interior_idx).unwrap();
-        for coord_idx in 0..linestring.num_points() {
-            let point = linestring.point(coord_idx).unwrap();
-            rect.update(point);
-        }
+/// The bounding box of a multi polygon, as `(lower, upper)` corners.
+pub fn bounding_rect_multipolygon<'a>(geom: &impl MultiPolygonTrait<'a>) -> ([f64; 2], [f64; 2]) {
+    let mut rect = BoundingRect::new();
+    for geom_idx in 0..geom.num_polygons() {
+        rect.add_polygon(&geom.polygon(geom_idx).unwrap());
     }
     rect.into()
 }
 
-pub fn bounding_rect_multipolygon(geom: &'_ MultiPolygon) -> ([f64; 2], [f64; 2]) {
+/// The bounding box of the members of a geometry collection, from their envelopes, as
+/// `(lower, upper)` corners. Empty members are skipped.
+pub fn bounding_rect_envelopes(
+    envelopes: impl IntoIterator<Item = AABB<[f64; 2]>>,
+) -> ([f64; 2], [f64; 2]) {
     let mut rect = BoundingRect::new();
-    for geom_idx in 0..geom.num_polygons() {
-        let polygon = geom.polygon(geom_idx).unwrap();
+    for envelope in envelopes {
+        rect.add_envelope(&envelope);
+    }
+    rect.into()
+}
 
-        let exterior_ring = polygon.exterior();
-        for coord_idx in 0..exterior_ring.num_points() {
-            let point = exterior_ring.point(coord_idx).unwrap();
-            rect.update(point);
-        }
+/// The bounding box of each geometry in an array.
+///
+/// Null and empty geometries have a null bounding box.
+pub fn bounding_rect<'a, A>(array: &'a A) -> RectArray
+where
+    A: GeometryArrayTrait<'a>,
+    A::Scalar: RTreeObject<Envelope = AABB<[f64; 2]>>,
+{
+    let rects: Vec<Option<Rect>> = (0..array.len())
+        .map(|geom_idx| {
+            let mut rect = BoundingRect::new();
+            rect.add_envelope(&array.get(geom_idx)?.envelope());
+            (!rect.is_empty()).then(|| rect.into())
+        })
+        .collect();
+    rects.into()
+}
 
-        for interior_idx in 0..polygon.num_interiors() {
-            let linestring = polygon.interior(interior_idx).unwrap();
-            for coord_idx in 0..linestring.num_points() {
-                let point = linestring.point(coord_idx).unwrap();
-                rect.update(point);
-            }
+/// The bounding box of all geometries in an array.
+///
+/// Returns `None` if the array has no non-null, non-empty geometries.
+pub fn total_bounds<'a, A>(array: &'a A) -> Option<Rect>
+where
+    A: GeometryArrayTrait<'a>,
+    A::Scalar: RTreeObject<Envelope = AABB<[f64; 2]>>,
+{
+    let mut rect = BoundingRect::new();
+    for geom_idx in 0..array.len() {
+        if let Some(geom) = array.get(geom_idx) {
+            rect.add_envelope(&geom.envelope());
         }
     }
-
-    rect.into()
+    (!rect.is_empty()).then(|| rect.into())
 }
 
 // TODO: add tests from geo
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArray, LineStringArray, PointArray, PolygonArray};
+    use geo::{line_string, point, polygon};
+
+    #[test]
+    fn bounding_rect_linestrings() {
+        let arr: LineStringArray = vec![
+            Some(line_string![(x: 0., y: 1.), (x: 3., y: -2.)]),
+            None,
+            Some(line_string![]),
+        ]
+        .into();
+        let rects = bounding_rect(&arr);
+        assert_eq!(rects.len(), 3);
+        assert_eq!(
+            rects.get_as_geo(0),
+            Some(Rect::new(coord! { x: 0., y: -2. }, coord! { x: 3., y: 1. }))
+        );
+        assert_eq!(rects.get_as_geo(1), None);
+        assert_eq!(rects.get_as_geo(2), None);
+    }
+
+    #[test]
+    fn total_bounds_geometry_array() {
+        let arr: PointArray = vec![
+            Some(point!(x: 1., y: 5.)),
+            None,
+            Some(point!(x: -2., y: 3.)),
+        ]
+        .into();
+        let arr = GeometryArray::Point(arr);
+        assert_eq!(
+            total_bounds(&arr),
+            Some(Rect::new(coord! { x: -2., y: 3. }, coord! { x: 1., y: 5. }))
+        );
+
+        let empty: PointArray = vec![None].into();
+        assert_eq!(total_bounds(&empty), None);
+    }
+
+    #[test]
+    fn scalar_and_geo_agree() {
+        let polygon = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 3.), (x: 0., y: 0.)],
+            interiors: [[(x: 1., y: 0.5), (x: 2., y: 0.5), (x: 2., y: 1.), (x: 1., y: 0.5)]],
+        );
+        let arr: PolygonArray = vec![None, Some(polygon.clone())].into();
+        assert_eq!(
+            bounding_rect_polygon(&arr.value(1)),
+            bounding_rect_polygon(&&polygon)
+        );
+        assert_eq!(bounding_rect_polygon(&&polygon), ([0., 0.], [4., 3.]));
+    }
+}
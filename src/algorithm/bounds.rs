@@ -0,0 +1,352 @@
+//! The bounds of whole arrays and chunked arrays, as `[minx, miny, maxx, maxy]`.
+//!
+//! Native arrays are bounded by scanning the coordinate buffers spanned by their valid rows,
+//! without reading geometries one by one, so that the inner loops vectorize.
+
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use arrow2::bitmap::Bitmap;
+use arrow2::offset::OffsetsBuffer;
+use arrow2::types::Index;
+use geo::BoundingRect;
+use rstar::RTreeObject;
+
+/// Number of independent accumulators in [`min_max`], so that the comparisons of consecutive
+/// values do not depend on each other.
+const LANES: usize = 8;
+
+/// The minimum and maximum of `values`, ignoring NaNs, or infinite bounds if there are none.
+fn min_max(values: &[f64]) -> (f64, f64) {
+    let mut min = [f64::INFINITY; LANES];
+    let mut max = [f64::NEG_INFINITY; LANES];
+    let chunks = values.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        for lane in 0..LANES {
+            min[lane] = if chunk[lane] < min[lane] {
+                chunk[lane]
+            } else {
+                min[lane]
+            };
+            max[lane] = if chunk[lane] > max[lane] {
+                chunk[lane]
+            } else {
+                max[lane]
+            };
+        }
+    }
+
+    let min = remainder
+        .iter()
+        .chain(&min)
+        .fold(f64::INFINITY, |a, &b| a.min(b));
+    let max = remainder
+        .iter()
+        .chain(&max)
+        .fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    (min, max)
+}
+
+/// Whether two `[minx, miny, maxx, maxy]` boxes intersect, including along their boundaries.
+pub fn bounds_intersect(left: &[f64; 4], right: &[f64; 4]) -> bool {
+    left[0] <= right[2] && right[0] <= left[2] && left[1] <= right[3] && right[1] <= left[3]
+}
+
+/// A running `[minx, miny, maxx, maxy]` box.
+#[derive(Debug, Clone, Copy)]
+struct Bounds([f64; 4]);
+
+impl Bounds {
+    fn new() -> Self {
+        Bounds([
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        ])
+    }
+
+    /// Expand to include the coordinates `start..end` of the given buffers.
+    fn add_coords(&mut self, x: &[f64], y: &[f64], start: usize, end: usize) {
+        let (minx, maxx) = min_max(&x[start..end]);
+        let (miny, maxy) = min_max(&y[start..end]);
+        self.add(&[minx, miny, maxx, maxy]);
+    }
+
+    /// Expand to include a box, which is skipped unless it is finite.
+    fn add(&mut self, bounds: &[f64; 4]) {
+        if !bounds.iter().all(|value| value.is_finite()) {
+            return;
+        }
+        self.0[0] = self.0[0].min(bounds[0]);
+        self.0[1] = self.0[1].min(bounds[1]);
+        self.0[2] = self.0[2].max(bounds[2]);
+        self.0[3] = self.0[3].max(bounds[3]);
+    }
+
+    fn finish(self) -> Option<[f64; 4]> {
+        (self.0[0] <= self.0[2] && self.0[1] <= self.0[3]).then_some(self.0)
+    }
+}
+
+/// The runs of consecutive valid rows in `0..len`, as `(start, end)` ranges.
+fn valid_runs(len: usize, validity: Option<&Bitmap>) -> Vec<(usize, usize)> {
+    let validity = match validity {
+        Some(validity) if validity.unset_bits() > 0 => validity,
+        _ => return if len == 0 { vec![] } else { vec![(0, len)] },
+    };
+
+    let mut runs = vec![];
+    let mut run_start = None;
+    for (i, is_valid) in validity.iter().enumerate() {
+        match (is_valid, run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(start)) => {
+                runs.push((start, i));
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = run_start {
+        runs.push((start, len));
+    }
+    runs
+}
+
+/// The bounds of the coordinates of the valid rows of a list array, where `offsets` holds the
+/// offsets of each level of lists, outermost first.
+fn nested_bounds(
+    x: &[f64],
+    y: &[f64],
+    offsets: &[&OffsetsBuffer<i64>],
+    len: usize,
+    validity: Option<&Bitmap>,
+) -> Option<[f64; 4]> {
+    let mut bounds = Bounds::new();
+    for (start, end) in valid_runs(len, validity) {
+        let (start, end) = offsets.iter().fold((start, end), |(start, end), offsets| {
+            let buffer = offsets.buffer();
+            (buffer[start].to_usize(), buffer[end].to_usize())
+        });
+        bounds.add_coords(x, y, start, end);
+    }
+    bounds.finish()
+}
+
+/// The bounds of all geometries in an array or chunked array.
+pub trait TotalBounds {
+    /// The `[minx, miny, maxx, maxy]` box containing every geometry, or `None` if there are no
+    /// non-null, non-empty geometries.
+    ///
+    /// Null rows are skipped, as are coordinates that are NaN.
+    fn total_bounds(&self) -> Option<[f64; 4]>;
+}
+
+impl TotalBounds for PointArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        let mut bounds = Bounds::new();
+        for (start, end) in valid_runs(self.len(), self.validity()) {
+            bounds.add_coords(self.values_x(), self.values_y(), start, end);
+        }
+        bounds.finish()
+    }
+}
+
+impl TotalBounds for LineStringArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        nested_bounds(
+            self.values_x(),
+            self.values_y(),
+            &[self.geom_offsets()],
+            self.len(),
+            self.validity(),
+        )
+    }
+}
+
+impl TotalBounds for PolygonArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        nested_bounds(
+            self.values_x(),
+            self.values_y(),
+            &[self.geom_offsets(), self.ring_offsets()],
+            self.len(),
+            self.validity(),
+        )
+    }
+}
+
+impl TotalBounds for MultiPointArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        nested_bounds(
+            self.values_x(),
+            self.values_y(),
+            &[self.geom_offsets()],
+            self.len(),
+            self.validity(),
+        )
+    }
+}
+
+impl TotalBounds for MultiLineStringArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        nested_bounds(
+            self.values_x(),
+            self.values_y(),
+            &[self.geom_offsets(), self.ring_offsets()],
+            self.len(),
+            self.validity(),
+        )
+    }
+}
+
+impl TotalBounds for MultiPolygonArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        nested_bounds(
+            self.values_x(),
+            self.values_y(),
+            &[
+                self.geom_offsets(),
+                self.polygon_offsets(),
+                self.ring_offsets(),
+            ],
+            self.len(),
+            self.validity(),
+        )
+    }
+}
+
+impl TotalBounds for RectArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        let mut bounds = Bounds::new();
+        for (start, end) in valid_runs(self.len(), self.validity()) {
+            bounds.add_coords(self.values_minx(), self.values_miny(), start, end);
+            bounds.add_coords(self.values_maxx(), self.values_maxy(), start, end);
+        }
+        bounds.finish()
+    }
+}
+
+/// WKB geometries are parsed one by one.
+impl TotalBounds for WKBArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        let mut bounds = Bounds::new();
+        for geom_idx in 0..self.len() {
+            if let Some(rect) = self
+                .get_as_geo(geom_idx)
+                .and_then(|geom| geom.bounding_rect())
+            {
+                bounds.add(&[rect.min().x, rect.min().y, rect.max().x, rect.max().y]);
+            }
+        }
+        bounds.finish()
+    }
+}
+
+impl TotalBounds for MixedGeometryArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        let mut bounds = Bounds::new();
+        for geom_idx in 0..self.len() {
+            if let Some(geom) = self.get(geom_idx) {
+                let envelope = geom.envelope();
+                let ([minx, miny], [maxx, maxy]) = (envelope.lower(), envelope.upper());
+                bounds.add(&[minx, miny, maxx, maxy]);
+            }
+        }
+        bounds.finish()
+    }
+}
+
+impl TotalBounds for GeometryArray {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        match self {
+            GeometryArray::Point(arr) => arr.total_bounds(),
+            GeometryArray::LineString(arr) => arr.total_bounds(),
+            GeometryArray::Polygon(arr) => arr.total_bounds(),
+            GeometryArray::MultiPoint(arr) => arr.total_bounds(),
+            GeometryArray::MultiLineString(arr) => arr.total_bounds(),
+            GeometryArray::MultiPolygon(arr) => arr.total_bounds(),
+            GeometryArray::WKB(arr) => arr.total_bounds(),
+            GeometryArray::Rect(arr) => arr.total_bounds(),
+            GeometryArray::Mixed(arr) => arr.total_bounds(),
+        }
+    }
+}
+
+/// The bounds of a chunked array: the box containing the bounds of every chunk.
+impl TotalBounds for [GeometryArray] {
+    fn total_bounds(&self) -> Option<[f64; 4]> {
+        let mut bounds = Bounds::new();
+        for chunk_bounds in chunk_bounds(self).into_iter().flatten() {
+            bounds.add(&chunk_bounds);
+        }
+        bounds.finish()
+    }
+}
+
+/// The bounds of each chunk of a chunked array, with `None` for chunks without any non-null,
+/// non-empty geometry.
+///
+/// These are the statistics stored as the `bbox` of GeoParquet column metadata (for the whole
+/// column) and used to skip chunks that cannot match a bounding box query.
+pub fn chunk_bounds(chunks: &[GeometryArray]) -> Vec<Option<[f64; 4]>> {
+    chunks.iter().map(TotalBounds::total_bounds).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point, polygon};
+
+    #[test]
+    fn min_max_lanes() {
+        let values: Vec<f64> = (0..21).map(|i| ((i * 8) % 21) as f64 - 10.).collect();
+        assert_eq!(min_max(&values), (-10., 10.));
+        assert_eq!(min_max(&[f64::NAN, 2.]), (2., 2.));
+        assert_eq!(min_max(&[]), (f64::INFINITY, f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn skips_nulls_and_sliced_rows() {
+        let arr: LineStringArray = vec![
+            Some(line_string![(x: -100., y: -100.), (x: 0., y: 0.)]),
+            Some(line_string![(x: 1., y: 2.), (x: 3., y: -4.)]),
+            None,
+            Some(line_string![(x: 5., y: 0.)]),
+        ]
+        .into();
+        assert_eq!(arr.total_bounds(), Some([-100., -100., 5., 2.]));
+
+        let sliced = arr.sliced_range(1..4);
+        assert_eq!(sliced.total_bounds(), Some([1., -4., 5., 2.]));
+
+        let nulled = sliced.with_validity(Some(Bitmap::from([true, false, false])));
+        assert_eq!(nulled.total_bounds(), Some([1., -4., 3., 2.]));
+
+        let empty: LineStringArray = vec![None, Some(line_string![])].into();
+        assert_eq!(empty.total_bounds(), None);
+    }
+
+    #[test]
+    fn chunked() {
+        let points: PointArray = vec![point!(x: 1., y: 1.), point!(x: 2., y: 3.)].into();
+        let polygons: PolygonArray =
+            vec![polygon![(x: -1., y: 0.), (x: 0., y: 0.), (x: 0., y: 1.), (x: -1., y: 0.)]].into();
+        let wkb: WKBArray = polygons.clone().into();
+        let chunks = vec![
+            GeometryArray::Point(points),
+            GeometryArray::Polygon(polygons),
+            GeometryArray::Point(vec![None].into()),
+        ];
+        assert_eq!(
+            chunk_bounds(&chunks),
+            vec![Some([1., 1., 2., 3.]), Some([-1., 0., 0., 1.]), None]
+        );
+        assert_eq!(chunks.total_bounds(), Some([-1., 0., 2., 3.]));
+        assert_eq!(wkb.total_bounds(), Some([-1., 0., 0., 1.]));
+        assert!(!bounds_intersect(&[5., 5., 6., 6.], &[-1., 0., 2., 3.]));
+    }
+}
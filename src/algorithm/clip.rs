@@ -0,0 +1,765 @@
+//! Clip geometries to an axis-aligned rectangle.
+//!
+//! Clipping to a rectangle is much simpler than a general intersection, so these kernels work
+//! directly on the coordinate buffers: line strings are clipped segment by segment with the
+//! Liang–Barsky algorithm and polygon rings with the Sutherland–Hodgman algorithm. Geometries that
+//! lie entirely within the rectangle are copied unchanged, which is the common case when cutting
+//! vector tiles.
+//!
+//! m values are kept, and interpolated linearly where segments cross the rectangle.
+//!
+//! Like with GEOS' `ClipByRect`, the output is not always valid: a polygon leaving and
+//! re-entering the rectangle stays one polygon, joined by edges running along the boundary of the
+//! rectangle.
+
+use crate::error::GeoArrowError;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MultiLineStringArray, MultiPointArray,
+    MultiPolygonArray, PointArray, PolygonArray, RectArray,
+};
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::buffer::Buffer;
+use arrow2::offset::{Offsets, OffsetsBuffer};
+
+/// Clip each geometry to a rectangle.
+pub trait ClipByRect {
+    type Output;
+
+    /// The part of each geometry within `rect`, boundary included.
+    ///
+    /// Geometries outside `rect` become empty, except points, which become null. Line strings
+    /// are split into a multi line string wherever they leave `rect`, and polygon rings clipped to
+    /// nothing are dropped.
+    fn clip_by_rect(&self, rect: &geo::Rect) -> Self::Output;
+}
+
+/// A coordinate as `(x, y, m)`, with a `NaN` m value if the array has none.
+type Xym = (f64, f64, f64);
+
+/// Whether two coordinates are at the same position, whatever their m values.
+fn same_xy(a: Xym, b: Xym) -> bool {
+    a.0 == b.0 && a.1 == b.1
+}
+
+/// The coordinate a fraction `t` of the way from `a` to `b`.
+fn lerp(a: Xym, b: Xym, t: f64) -> Xym {
+    (
+        a.0 + t * (b.0 - a.0),
+        a.1 + t * (b.1 - a.1),
+        a.2 + t * (b.2 - a.2),
+    )
+}
+
+/// The clipping rectangle.
+#[derive(Debug, Clone, Copy)]
+struct Clip {
+    min_x: f64,
+    min_y: f64,
+    max_x: f64,
+    max_y: f64,
+}
+
+/// One of the four edges of the clipping rectangle.
+#[derive(Debug, Clone, Copy)]
+enum Edge {
+    Left,
+    Right,
+    Bottom,
+    Top,
+}
+
+/// A range of the coordinate buffers of an array to clip.
+#[derive(Debug, Clone, Copy)]
+struct CoordSlice<'a> {
+    x: &'a [f64],
+    y: &'a [f64],
+    m: Option<&'a [f64]>,
+}
+
+impl<'a> CoordSlice<'a> {
+    fn new(x: &'a Buffer<f64>, y: &'a Buffer<f64>, m: Option<&'a Buffer<f64>>) -> Self {
+        Self {
+            x,
+            y,
+            m: m.map(|m| m.as_slice()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.x.is_empty()
+    }
+
+    fn get(&self, i: usize) -> Xym {
+        (self.x[i], self.y[i], self.m.map_or(f64::NAN, |m| m[i]))
+    }
+
+    fn slice(&self, (start, end): (usize, usize)) -> Self {
+        Self {
+            x: &self.x[start..end],
+            y: &self.y[start..end],
+            m: self.m.map(|m| &m[start..end]),
+        }
+    }
+}
+
+/// The coordinates of a clipped array.
+#[derive(Debug)]
+struct Coords {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    m: Option<Vec<f64>>,
+}
+
+impl Coords {
+    /// Empty coordinates, with m values if `has_m`.
+    fn new(has_m: bool) -> Self {
+        Self {
+            x: Vec::new(),
+            y: Vec::new(),
+            m: has_m.then(Vec::new),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.x.len()
+    }
+
+    fn push(&mut self, (x, y, m): Xym) {
+        self.x.push(x);
+        self.y.push(y);
+        if let Some(values) = &mut self.m {
+            values.push(m);
+        }
+    }
+
+    fn extend(&mut self, coords: CoordSlice) {
+        self.x.extend_from_slice(coords.x);
+        self.y.extend_from_slice(coords.y);
+        if let (Some(values), Some(m)) = (&mut self.m, coords.m) {
+            values.extend_from_slice(m);
+        }
+    }
+
+    /// The x, y and m buffers.
+    fn into_buffers(self) -> (Buffer<f64>, Buffer<f64>, Option<Buffer<f64>>) {
+        (self.x.into(), self.y.into(), self.m.map(Into::into))
+    }
+}
+
+fn push_len(offsets: &mut Offsets<i64>, len: usize) {
+    offsets
+        .try_push_usize(len)
+        .expect("clipped offsets fit in i64");
+}
+
+impl Clip {
+    fn new(rect: &geo::Rect) -> Self {
+        Self {
+            min_x: rect.min().x,
+            min_y: rect.min().y,
+            max_x: rect.max().x,
+            max_y: rect.max().y,
+        }
+    }
+
+    fn contains(&self, x: f64, y: f64) -> bool {
+        self.min_x <= x && x <= self.max_x && self.min_y <= y && y <= self.max_y
+    }
+
+    fn contains_all(&self, coords: CoordSlice) -> bool {
+        coords
+            .x
+            .iter()
+            .zip(coords.y)
+            .all(|(x, y)| self.contains(*x, *y))
+    }
+
+    /// The part of the segment from `start` to `end` within the rectangle, and whether the
+    /// segment leaves the rectangle after it, with the Liang–Barsky algorithm.
+    fn clip_segment(&self, start: Xym, end: Xym) -> Option<(Xym, Xym, bool)> {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let mut t_start = 0.0_f64;
+        let mut t_end = 1.0_f64;
+        for (p, q) in [
+            (-dx, start.0 - self.min_x),
+            (dx, self.max_x - start.0),
+            (-dy, start.1 - self.min_y),
+            (dy, self.max_y - start.1),
+        ] {
+            if p == 0.0 {
+                // Parallel to this edge, and entirely outside it
+                if q < 0.0 {
+                    return None;
+                }
+            } else {
+                let t = q / p;
+                if p < 0.0 {
+                    if t > t_end {
+                        return None;
+                    }
+                    t_start = t_start.max(t);
+                } else {
+                    if t < t_start {
+                        return None;
+                    }
+                    t_end = t_end.min(t);
+                }
+            }
+        }
+
+        // Keep the original coordinates of the end points within the rectangle exactly
+        let clipped_start = if t_start == 0.0 {
+            start
+        } else {
+            lerp(start, end, t_start)
+        };
+        let clipped_end = if t_end == 1.0 {
+            end
+        } else {
+            lerp(start, end, t_end)
+        };
+        Some((clipped_start, clipped_end, t_end < 1.0))
+    }
+
+    /// Push the parts of a line string within the rectangle to `coords`, and the length of each
+    /// part to `parts`.
+    fn clip_line_string(&self, line: CoordSlice, coords: &mut Coords, parts: &mut Offsets<i64>) {
+        if self.contains_all(line) {
+            if !line.is_empty() {
+                coords.extend(line);
+                push_len(parts, line.len());
+            }
+            return;
+        }
+
+        let mut part_start = coords.len();
+        let mut open = false;
+        for i in 1..line.len() {
+            match self.clip_segment(line.get(i - 1), line.get(i)) {
+                Some((start, end, leaves)) => {
+                    if !open {
+                        // A segment only touching a corner of the rectangle is not a part
+                        if leaves && same_xy(start, end) {
+                            continue;
+                        }
+                        if coords.len() > part_start {
+                            push_len(parts, coords.len() - part_start);
+                        }
+                        part_start = coords.len();
+                        coords.push(start);
+                    }
+                    coords.push(end);
+                    open = !leaves;
+                }
+                None => open = false,
+            }
+        }
+        if coords.len() > part_start {
+            push_len(parts, coords.len() - part_start);
+        }
+    }
+
+    fn inside(&self, edge: Edge, (x, y, _): Xym) -> bool {
+        match edge {
+            Edge::Left => x >= self.min_x,
+            Edge::Right => x <= self.max_x,
+            Edge::Bottom => y >= self.min_y,
+            Edge::Top => y <= self.max_y,
+        }
+    }
+
+    /// Where the segment from `a` to `b`, which crosses the line through `edge`, crosses it.
+    fn crossing(&self, edge: Edge, a: Xym, b: Xym) -> Xym {
+        match edge {
+            Edge::Left | Edge::Right => {
+                let x = if let Edge::Left = edge {
+                    self.min_x
+                } else {
+                    self.max_x
+                };
+                let (_, y, m) = lerp(a, b, (x - a.0) / (b.0 - a.0));
+                (x, y, m)
+            }
+            Edge::Bottom | Edge::Top => {
+                let y = if let Edge::Bottom = edge {
+                    self.min_y
+                } else {
+                    self.max_y
+                };
+                let (x, _, m) = lerp(a, b, (y - a.1) / (b.1 - a.1));
+                (x, y, m)
+            }
+        }
+    }
+
+    /// Push a ring clipped to the rectangle to `coords` and its length to `rings`, with the
+    /// Sutherland–Hodgman algorithm. Returns whether anything was left of the ring.
+    fn clip_ring(&self, ring: CoordSlice, coords: &mut Coords, rings: &mut Offsets<i64>) -> bool {
+        if self.contains_all(ring) {
+            if ring.is_empty() {
+                return false;
+            }
+            coords.extend(ring);
+            push_len(rings, ring.len());
+            return true;
+        }
+
+        // Clip the ring without its closing coordinate against each edge in turn
+        let len = if ring.len() > 1 && same_xy(ring.get(0), ring.get(ring.len() - 1)) {
+            ring.len() - 1
+        } else {
+            ring.len()
+        };
+        let mut ring: Vec<Xym> = (0..len).map(|i| ring.get(i)).collect();
+        let mut clipped = Vec::with_capacity(ring.len());
+        for edge in [Edge::Left, Edge::Right, Edge::Bottom, Edge::Top] {
+            clipped.clear();
+            for i in 0..ring.len() {
+                let previous = ring[(i + ring.len() - 1) % ring.len()];
+                let current = ring[i];
+                match (self.inside(edge, previous), self.inside(edge, current)) {
+                    (true, true) => clipped.push(current),
+                    (true, false) => clipped.push(self.crossing(edge, previous, current)),
+                    (false, true) => {
+                        clipped.push(self.crossing(edge, previous, current));
+                        clipped.push(current);
+                    }
+                    (false, false) => {}
+                }
+            }
+            std::mem::swap(&mut ring, &mut clipped);
+        }
+
+        ring.dedup_by(|a, b| same_xy(*a, *b));
+        if ring.len() > 1 && same_xy(ring[0], ring[ring.len() - 1]) {
+            ring.pop();
+        }
+        if ring.len() < 3 {
+            return false;
+        }
+        for &coord in ring.iter().chain(ring.first()) {
+            coords.push(coord);
+        }
+        push_len(rings, ring.len() + 1);
+        true
+    }
+
+    /// Push the rings of a polygon clipped to the rectangle, or nothing if the exterior is clipped
+    /// to nothing. Returns the number of rings pushed.
+    fn clip_polygon(
+        &self,
+        all_coords: CoordSlice,
+        ring_offsets: &OffsetsBuffer<i64>,
+        rings: std::ops::Range<usize>,
+        coords: &mut Coords,
+        ring_lengths: &mut Offsets<i64>,
+    ) -> usize {
+        let mut num_rings = 0;
+        for ring in rings {
+            let ring = all_coords.slice(ring_offsets.start_end(ring));
+            if self.clip_ring(ring, coords, ring_lengths) {
+                num_rings += 1;
+            } else if num_rings == 0 {
+                return 0;
+            }
+        }
+        num_rings
+    }
+}
+
+impl ClipByRect for PointArray {
+    type Output = PointArray;
+
+    fn clip_by_rect(&self, rect: &geo::Rect) -> Self::Output {
+        let clip = Clip::new(rect);
+        let validity: MutableBitmap = (0..self.len())
+            .map(|i| self.is_valid(i) && clip.contains(self.values_x()[i], self.values_y()[i]))
+            .collect();
+        let validity: Bitmap = validity.into();
+        PointArray::new(
+            self.values_x().clone(),
+            self.values_y().clone(),
+            (validity.unset_bits() > 0).then_some(validity),
+        )
+        .with_crs(self.crs())
+        .with_optional_m(self.values_m().cloned())
+    }
+}
+
+impl ClipByRect for MultiPointArray {
+    type Output = MultiPointArray;
+
+    fn clip_by_rect(&self, rect: &geo::Rect) -> Self::Output {
+        let clip = Clip::new(rect);
+        let all_coords = CoordSlice::new(self.values_x(), self.values_y(), self.values_m());
+        let mut coords = Coords::new(self.has_m());
+        let mut geom_offsets = Offsets::<i64>::with_capacity(self.len());
+        for i in 0..self.len() {
+            let start = coords.len();
+            if self.is_valid(i) {
+                let (first, last) = self.geom_offsets().start_end(i);
+                for j in first..last {
+                    let coord = all_coords.get(j);
+                    if clip.contains(coord.0, coord.1) {
+                        coords.push(coord);
+                    }
+                }
+            }
+            push_len(&mut geom_offsets, coords.len() - start);
+        }
+        let (x, y, m) = coords.into_buffers();
+        MultiPointArray::new(x, y, geom_offsets.into(), self.validity().cloned())
+            .with_crs(self.crs())
+            .with_optional_m(m)
+    }
+}
+
+impl ClipByRect for LineStringArray {
+    type Output = MultiLineStringArray;
+
+    fn clip_by_rect(&self, rect: &geo::Rect) -> Self::Output {
+        let clip = Clip::new(rect);
+        let all_coords = CoordSlice::new(self.values_x(), self.values_y(), self.values_m());
+        let mut coords = Coords::new(self.has_m());
+        let mut geom_offsets = Offsets::<i64>::with_capacity(self.len());
+        let mut part_offsets = Offsets::<i64>::new();
+        for i in 0..self.len() {
+            let num_parts = part_offsets.len_proxy();
+            if self.is_valid(i) {
+                let line_string = all_coords.slice(self.geom_offsets().start_end(i));
+                clip.clip_line_string(line_string, &mut coords, &mut part_offsets);
+            }
+            push_len(&mut geom_offsets, part_offsets.len_proxy() - num_parts);
+        }
+        let (x, y, m) = coords.into_buffers();
+        MultiLineStringArray::new(
+            x,
+            y,
+            geom_offsets.into(),
+            part_offsets.into(),
+            self.validity().cloned(),
+        )
+        .with_crs(self.crs())
+        .with_optional_m(m)
+    }
+}
+
+impl ClipByRect for MultiLineStringArray {
+    type Output = MultiLineStringArray;
+
+    fn clip_by_rect(&self, rect: &geo::Rect) -> Self::Output {
+        let clip = Clip::new(rect);
+        let all_coords = CoordSlice::new(self.values_x(), self.values_y(), self.values_m());
+        let mut coords = Coords::new(self.has_m());
+        let mut geom_offsets = Offsets::<i64>::with_capacity(self.len());
+        let mut part_offsets = Offsets::<i64>::new();
+        for i in 0..self.len() {
+            let num_parts = part_offsets.len_proxy();
+            if self.is_valid(i) {
+                let (first, last) = self.geom_offsets().start_end(i);
+                for line_string in first..last {
+                    let line_string = all_coords.slice(self.ring_offsets().start_end(line_string));
+                    clip.clip_line_string(line_string, &mut coords, &mut part_offsets);
+                }
+            }
+            push_len(&mut geom_offsets, part_offsets.len_proxy() - num_parts);
+        }
+        let (x, y, m) = coords.into_buffers();
+        MultiLineStringArray::new(
+            x,
+            y,
+            geom_offsets.into(),
+            part_offsets.into(),
+            self.validity().cloned(),
+        )
+        .with_crs(self.crs())
+        .with_optional_m(m)
+    }
+}
+
+impl ClipByRect for PolygonArray {
+    type Output = PolygonArray;
+
+    fn clip_by_rect(&self, rect: &geo::Rect) -> Self::Output {
+        let clip = Clip::new(rect);
+        let all_coords = CoordSlice::new(self.values_x(), self.values_y(), self.values_m());
+        let mut coords = Coords::new(self.has_m());
+        let mut geom_offsets = Offsets::<i64>::with_capacity(self.len());
+        let mut ring_offsets = Offsets::<i64>::new();
+        for i in 0..self.len() {
+            let mut num_rings = 0;
+            if self.is_valid(i) {
+                let (first, last) = self.geom_offsets().start_end(i);
+                num_rings = clip.clip_polygon(
+                    all_coords,
+                    self.ring_offsets(),
+                    first..last,
+                    &mut coords,
+                    &mut ring_offsets,
+                );
+            }
+            push_len(&mut geom_offsets, num_rings);
+        }
+        let (x, y, m) = coords.into_buffers();
+        PolygonArray::new(
+            x,
+            y,
+            geom_offsets.into(),
+            ring_offsets.into(),
+            self.validity().cloned(),
+        )
+        .with_crs(self.crs())
+        .with_optional_m(m)
+    }
+}
+
+impl ClipByRect for MultiPolygonArray {
+    type Output = MultiPolygonArray;
+
+    fn clip_by_rect(&self, rect: &geo::Rect) -> Self::Output {
+        let clip = Clip::new(rect);
+        let all_coords = CoordSlice::new(self.values_x(), self.values_y(), self.values_m());
+        let mut coords = Coords::new(self.has_m());
+        let mut geom_offsets = Offsets::<i64>::with_capacity(self.len());
+        let mut polygon_offsets = Offsets::<i64>::new();
+        let mut ring_offsets = Offsets::<i64>::new();
+        for i in 0..self.len() {
+            let mut num_polygons = 0;
+            if self.is_valid(i) {
+                let (first, last) = self.geom_offsets().start_end(i);
+                for polygon in first..last {
+                    let (first_ring, last_ring) = self.polygon_offsets().start_end(polygon);
+                    let num_rings = clip.clip_polygon(
+                        all_coords,
+                        self.ring_offsets(),
+                        first_ring..last_ring,
+                        &mut coords,
+                        &mut ring_offsets,
+                    );
+                    if num_rings > 0 {
+                        push_len(&mut polygon_offsets, num_rings);
+                        num_polygons += 1;
+                    }
+                }
+            }
+            push_len(&mut geom_offsets, num_polygons);
+        }
+        let (x, y, m) = coords.into_buffers();
+        MultiPolygonArray::new(
+            x,
+            y,
+            geom_offsets.into(),
+            polygon_offsets.into(),
+            ring_offsets.into(),
+            self.validity().cloned(),
+        )
+        .with_crs(self.crs())
+        .with_optional_m(m)
+    }
+}
+
+impl ClipByRect for RectArray {
+    type Output = RectArray;
+
+    /// The intersection of each rectangle with `rect`, or null if they do not intersect.
+    fn clip_by_rect(&self, rect: &geo::Rect) -> Self::Output {
+        let clip = Clip::new(rect);
+        let min_x: Vec<f64> = self
+            .values_minx()
+            .iter()
+            .map(|v| v.max(clip.min_x))
+            .collect();
+        let min_y: Vec<f64> = self
+            .values_miny()
+            .iter()
+            .map(|v| v.max(clip.min_y))
+            .collect();
+        let max_x: Vec<f64> = self
+            .values_maxx()
+            .iter()
+            .map(|v| v.min(clip.max_x))
+            .collect();
+        let max_y: Vec<f64> = self
+            .values_maxy()
+            .iter()
+            .map(|v| v.min(clip.max_y))
+            .collect();
+        let validity: MutableBitmap = (0..self.len())
+            .map(|i| self.is_valid(i) && min_x[i] <= max_x[i] && min_y[i] <= max_y[i])
+            .collect();
+        let validity: Bitmap = validity.into();
+        RectArray::new(
+            min_x.into(),
+            min_y.into(),
+            max_x.into(),
+            max_y.into(),
+            (validity.unset_bits() > 0).then_some(validity),
+        )
+        .with_crs(self.crs())
+    }
+}
+
+/// Clip each geometry in a [`GeometryArray`] to a rectangle.
+///
+/// Line string arrays become multi line string arrays; other arrays keep their type. See
+/// [`ClipByRect`].
+/// # Errors
+/// Errors for WKB and mixed geometry arrays.
+pub fn clip_by_rect(
+    array: &GeometryArray,
+    rect: &geo::Rect,
+) -> Result<GeometryArray, GeoArrowError> {
+    match array {
+        GeometryArray::Point(arr) => Ok(GeometryArray::Point(arr.clip_by_rect(rect))),
+        GeometryArray::LineString(arr) => {
+            Ok(GeometryArray::MultiLineString(arr.clip_by_rect(rect)))
+        }
+        GeometryArray::Polygon(arr) => Ok(GeometryArray::Polygon(arr.clip_by_rect(rect))),
+        GeometryArray::MultiPoint(arr) => Ok(GeometryArray::MultiPoint(arr.clip_by_rect(rect))),
+        GeometryArray::MultiLineString(arr) => {
+            Ok(GeometryArray::MultiLineString(arr.clip_by_rect(rect)))
+        }
+        GeometryArray::MultiPolygon(arr) => Ok(GeometryArray::MultiPolygon(arr.clip_by_rect(rect))),
+        GeometryArray::Rect(arr) => Ok(GeometryArray::Rect(arr.clip_by_rect(rect))),
+        GeometryArray::WKB(_) => Err(GeoArrowError::NotYetImplemented(
+            "clip_by_rect on WKB arrays".to_string(),
+        )),
+        GeometryArray::Mixed(_) => Err(GeoArrowError::NotYetImplemented(
+            "clip_by_rect on mixed geometry arrays".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point, polygon, Coord, LineString, MultiLineString, MultiPolygon};
+
+    fn unit_square() -> geo::Rect {
+        geo::Rect::new(Coord { x: 0., y: 0. }, Coord { x: 10., y: 10. })
+    }
+
+    #[test]
+    fn points() {
+        let arr: PointArray = vec![
+            Some(point!(x: 1., y: 1.)),
+            Some(point!(x: 11., y: 1.)),
+            None,
+            Some(point!(x: 10., y: 0.)),
+        ]
+        .into();
+        let clipped = arr.clip_by_rect(&unit_square());
+        assert!(clipped.is_valid(0));
+        assert!(clipped.is_null(1));
+        assert!(clipped.is_null(2));
+        assert_eq!(clipped.get_as_geo(3), Some(point!(x: 10., y: 0.)));
+    }
+
+    #[test]
+    fn line_strings() {
+        let inside = line_string![(x: 1., y: 1.), (x: 2., y: 2.)];
+        let leaves_and_returns = line_string![
+            (x: 5., y: 5.),
+            (x: 15., y: 5.),
+            (x: 15., y: 8.),
+            (x: 5., y: 8.),
+        ];
+        let outside = line_string![(x: 20., y: 20.), (x: 30., y: 30.)];
+        let arr: LineStringArray = vec![
+            Some(inside.clone()),
+            Some(leaves_and_returns),
+            Some(outside),
+            None,
+        ]
+        .into();
+        let clipped = arr.clip_by_rect(&unit_square());
+        assert_eq!(
+            clipped.get_as_geo(0),
+            Some(MultiLineString::new(vec![inside]))
+        );
+        assert_eq!(
+            clipped.get_as_geo(1),
+            Some(MultiLineString::new(vec![
+                line_string![(x: 5., y: 5.), (x: 10., y: 5.)],
+                line_string![(x: 10., y: 8.), (x: 5., y: 8.)],
+            ]))
+        );
+        assert_eq!(clipped.get_as_geo(2), Some(MultiLineString::new(vec![])));
+        assert!(clipped.is_null(3));
+    }
+
+    #[test]
+    fn polygons() {
+        let overlapping = polygon![
+            (x: 5., y: 5.), (x: 15., y: 5.), (x: 15., y: 15.), (x: 5., y: 15.), (x: 5., y: 5.)
+        ];
+        let outside = polygon![
+            (x: 20., y: 20.), (x: 30., y: 20.), (x: 30., y: 30.), (x: 20., y: 20.)
+        ];
+        let arr: PolygonArray = vec![Some(overlapping), Some(outside), None].into();
+        let clipped = arr.clip_by_rect(&unit_square());
+        assert_eq!(
+            clipped.get_as_geo(0),
+            Some(polygon![
+                (x: 5., y: 10.), (x: 5., y: 5.), (x: 10., y: 5.), (x: 10., y: 10.), (x: 5., y: 10.)
+            ])
+        );
+        assert_eq!(clipped.geom_offsets().start_end(1), (1, 1));
+        assert!(clipped.is_null(2));
+
+        let multi: MultiPolygonArray = vec![MultiPolygon::new(vec![
+            polygon![(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 1.)],
+            polygon![(x: 20., y: 20.), (x: 30., y: 20.), (x: 30., y: 30.), (x: 20., y: 20.)],
+        ])]
+        .into();
+        let clipped = multi.clip_by_rect(&unit_square());
+        assert_eq!(clipped.value_as_geo(0).0.len(), 1);
+    }
+
+    #[test]
+    fn m_values() {
+        let line_string = line_string![(x: 5., y: 5.), (x: 15., y: 5.), (x: 15., y: 8.)];
+        let arr: LineStringArray = vec![line_string].into();
+        let arr = arr.with_m(vec![0., 100., 200.].into()).unwrap();
+        let clipped = arr.clip_by_rect(&unit_square());
+        // The crossing is halfway along the first segment
+        assert_eq!(clipped.values_m().unwrap().as_slice(), &[0., 50.]);
+
+        let square = polygon![
+            (x: 5., y: 5.), (x: 15., y: 5.), (x: 15., y: 15.), (x: 5., y: 15.), (x: 5., y: 5.)
+        ];
+        let arr: PolygonArray = vec![square].into();
+        let arr = arr.with_m(vec![0., 1., 2., 3., 0.].into()).unwrap();
+        let clipped = arr.clip_by_rect(&unit_square());
+        assert_eq!(
+            clipped.values_m().unwrap().as_slice(),
+            &[1.5, 0., 0.5, 1.5, 1.5]
+        );
+
+        let points: PointArray = vec![point!(x: 1., y: 1.), point!(x: 11., y: 1.)].into();
+        let points = points.with_m(vec![7., 8.].into()).unwrap();
+        assert_eq!(points.clip_by_rect(&unit_square()).value(0).m(), Some(7.));
+
+        let multi_points = MultiPointArray::from(points);
+        let clipped = multi_points.clip_by_rect(&unit_square());
+        assert_eq!(clipped.values_m().unwrap().as_slice(), &[7.]);
+    }
+
+    #[test]
+    fn geometry_array() {
+        let line_string = LineString::from(vec![(0., 0.), (20., 0.)]);
+        let arr: LineStringArray = vec![line_string].into();
+        let clipped = clip_by_rect(&GeometryArray::LineString(arr), &unit_square()).unwrap();
+        assert!(matches!(clipped, GeometryArray::MultiLineString(_)));
+        assert_eq!(
+            clipped.get_as_geo(0),
+            Some(geo::Geometry::MultiLineString(MultiLineString::new(vec![
+                line_string![(x: 0., y: 0.), (x: 10., y: 0.)]
+            ])))
+        );
+    }
+}
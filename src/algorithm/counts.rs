@@ -0,0 +1,177 @@
+//! Per-row counts of the coordinates, parts and rings of geometries.
+//!
+//! Counts of native geometries are read from their offsets alone, in `O(1)` per row, without
+//! touching their coordinates. WKB geometries are counted by reading their headers and skipping
+//! over their coordinates. Null geometries give null counts.
+
+use crate::binary::wkb_counts;
+use crate::coords::values_range;
+use crate::enum_::Geometry;
+use crate::error::GeoArrowError;
+use crate::{GeometryArray, GeometryArrayTrait};
+use arrow2::array::PrimitiveArray;
+
+/// The number of coordinates of the polygon rings of a rect.
+const RECT_COORDS: usize = 5;
+
+/// The count `count` of each geometry of `array`.
+fn counts(
+    array: &GeometryArray,
+    count: impl Fn(&Geometry<'_>) -> Result<usize, String>,
+) -> Result<PrimitiveArray<u32>, GeoArrowError> {
+    let counts = (0..array.len())
+        .map(|row| {
+            array
+                .get(row)
+                .map(|geom| count(&geom).map(|count| count as u32))
+                .transpose()
+                .map_err(|message| GeoArrowError::InvalidWkb { row, message })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(PrimitiveArray::from(counts))
+}
+
+/// The number of coordinates of each geometry.
+///
+/// Rects count the five coordinates of their polygon ring.
+/// # Errors
+/// Errors with [`GeoArrowError::InvalidWkb`] if a WKB geometry is not well-formed.
+pub fn num_coords(array: &GeometryArray) -> Result<PrimitiveArray<u32>, GeoArrowError> {
+    counts(array, geometry_num_coords)
+}
+
+fn geometry_num_coords(geom: &Geometry<'_>) -> Result<usize, String> {
+    Ok(match geom {
+        Geometry::Point(_) => 1,
+        Geometry::LineString(geom) => values_range(geom.geom_offsets, row(geom.geom_index)).len(),
+        Geometry::Polygon(geom) => {
+            let rings = values_range(geom.geom_offsets, row(geom.geom_index));
+            values_range(geom.ring_offsets, rings).len()
+        }
+        Geometry::MultiPoint(geom) => values_range(geom.geom_offsets, row(geom.geom_index)).len(),
+        Geometry::MultiLineString(geom) => {
+            let lines = values_range(geom.geom_offsets, row(geom.geom_index));
+            values_range(geom.ring_offsets, lines).len()
+        }
+        Geometry::MultiPolygon(geom) => {
+            let polygons = values_range(geom.geom_offsets, row(geom.geom_index));
+            let rings = values_range(geom.polygon_offsets, polygons);
+            values_range(geom.ring_offsets, rings).len()
+        }
+        Geometry::WKB(geom) => wkb_counts(geom.as_slice())?.num_coords,
+        Geometry::Rect(_) => RECT_COORDS,
+        Geometry::GeometryCollection(geom) => geom
+            .geometries()
+            .map(|geom| geometry_num_coords(&geom))
+            .sum::<Result<_, _>>()?,
+    })
+}
+
+/// The number of parts of each geometry: the points of a multi point, the lines of a multi line
+/// string, the polygons of a multi polygon and the members of a geometry collection. Single
+/// geometries have one part.
+/// # Errors
+/// Errors with [`GeoArrowError::InvalidWkb`] if a WKB geometry is not well-formed.
+pub fn num_parts(array: &GeometryArray) -> Result<PrimitiveArray<u32>, GeoArrowError> {
+    counts(array, |geom| {
+        Ok(match geom {
+            Geometry::MultiPoint(geom) => {
+                values_range(geom.geom_offsets, row(geom.geom_index)).len()
+            }
+            Geometry::MultiLineString(geom) => {
+                values_range(geom.geom_offsets, row(geom.geom_index)).len()
+            }
+            Geometry::MultiPolygon(geom) => {
+                values_range(geom.geom_offsets, row(geom.geom_index)).len()
+            }
+            Geometry::WKB(geom) => wkb_counts(geom.as_slice())?.num_parts,
+            Geometry::GeometryCollection(geom) => geom.num_geometries(),
+            _ => 1,
+        })
+    })
+}
+
+/// The number of rings of each geometry, exterior and interior: those of a polygon, or of all
+/// the polygons of a multi polygon or of the members of a geometry collection. Rects have one
+/// ring, and other geometries none.
+/// # Errors
+/// Errors with [`GeoArrowError::InvalidWkb`] if a WKB geometry is not well-formed.
+pub fn num_rings(array: &GeometryArray) -> Result<PrimitiveArray<u32>, GeoArrowError> {
+    counts(array, geometry_num_rings)
+}
+
+fn geometry_num_rings(geom: &Geometry<'_>) -> Result<usize, String> {
+    Ok(match geom {
+        Geometry::Polygon(geom) => values_range(geom.geom_offsets, row(geom.geom_index)).len(),
+        Geometry::MultiPolygon(geom) => {
+            let polygons = values_range(geom.geom_offsets, row(geom.geom_index));
+            values_range(geom.polygon_offsets, polygons).len()
+        }
+        Geometry::WKB(geom) => wkb_counts(geom.as_slice())?.num_rings,
+        Geometry::Rect(_) => 1,
+        Geometry::GeometryCollection(geom) => geom
+            .geometries()
+            .map(|geom| geometry_num_rings(&geom))
+            .sum::<Result<_, _>>()?,
+        _ => 0,
+    })
+}
+
+/// The range of rows holding only row `i`.
+fn row(i: usize) -> std::ops::Range<usize> {
+    i..i + 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{MultiPolygonArray, PointArray, WKBArray};
+    use arrow2::array::Array;
+    use geo::{line_string, point, polygon};
+
+    #[test]
+    fn native_and_wkb() {
+        let square = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 0.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 1.)]],
+        );
+        let multi_polygons: MultiPolygonArray = vec![
+            Some(geo::MultiPolygon(vec![square.clone(), square.clone()])),
+            None,
+            Some(geo::MultiPolygon(vec![square.clone()])),
+        ]
+        .into();
+        let array = GeometryArray::MultiPolygon(multi_polygons.clone());
+        assert_eq!(num_coords(&array).unwrap().values().as_slice()[..1], [16]);
+        assert_eq!(num_parts(&array).unwrap().values().as_slice()[..1], [2]);
+        let rings = num_rings(&array).unwrap();
+        assert!(rings.is_null(1));
+        assert_eq!(rings.value(2), 2);
+
+        // Slices only count their own rows
+        let sliced = GeometryArray::MultiPolygon(multi_polygons.sliced_range(2..3));
+        assert_eq!(num_coords(&sliced).unwrap().value(0), 8);
+
+        // WKB gives the same counts, read from the headers
+        let wkb = GeometryArray::WKB(WKBArray::from(&multi_polygons));
+        assert_eq!(num_coords(&wkb).unwrap(), num_coords(&array).unwrap());
+        assert_eq!(num_parts(&wkb).unwrap(), num_parts(&array).unwrap());
+        assert_eq!(num_rings(&wkb).unwrap(), num_rings(&array).unwrap());
+
+        let geoms: WKBArray = vec![
+            Some(geo::Geometry::Point(point!(x: 0., y: 1.))),
+            Some(line_string![(x: 0., y: 0.), (x: 1., y: 1.)].into()),
+        ]
+        .into();
+        let array = GeometryArray::WKB(geoms);
+        assert_eq!(num_coords(&array).unwrap().values().as_slice(), &[1, 2]);
+        assert_eq!(num_parts(&array).unwrap().values().as_slice(), &[1, 1]);
+        assert_eq!(num_rings(&array).unwrap().values().as_slice(), &[0, 0]);
+
+        let points: PointArray = vec![point!(x: 0., y: 1.)].into();
+        assert_eq!(
+            num_rings(&GeometryArray::Point(points)).unwrap().value(0),
+            0
+        );
+    }
+}
@@ -0,0 +1,581 @@
+//! Delaunay triangulations and Voronoi diagrams of points.
+//!
+//! The Delaunay triangulation of a set of points joins them into triangles whose circumcircles
+//! contain no other point. Its dual, the Voronoi diagram, assigns every location to the nearest
+//! point. Both are built with the Bowyer-Watson algorithm on exact [`robust`] predicates, inserting
+//! points in Hilbert order so that each insertion starts next to the previous one. Each edge of
+//! the convex hull borders a ghost triangle joining it to a vertex at infinity, so that points
+//! outside the hull are inserted like points inside it and no hull edge is lost.
+//!
+//! Null points are skipped and repeated points are triangulated once, under the index of their
+//! first occurrence. The outputs carry index arrays mapping them back to the input points.
+
+use crate::algorithm::hilbert::hilbert_index;
+use crate::parallel::map_rows;
+use crate::{GeometryArrayTrait, PointArray, PolygonArray};
+use arrow2::array::PrimitiveArray;
+use std::collections::{HashMap, HashSet};
+
+/// The vertex at infinity, shared by the ghost triangles outside each edge of the convex hull.
+const INFINITE: usize = 0;
+
+/// A counterclockwise triangle.
+#[derive(Debug, Clone)]
+struct Triangle {
+    vertices: [usize; 3],
+
+    /// The triangle across the edge opposite each vertex.
+    neighbors: [usize; 3],
+
+    /// Whether this triangle is part of the triangulation, rather than replaced by an insertion.
+    alive: bool,
+}
+
+/// An incremental Delaunay triangulation, whose vertex 0 is the vertex at infinity.
+struct Triangulation {
+    vertices: Vec<[f64; 2]>,
+    triangles: Vec<Triangle>,
+
+    /// The last triangle created, where the search for the next point starts.
+    last: usize,
+
+    /// The vertices inserted while all are collinear and there are no triangles yet, by location.
+    collinear: HashMap<[u64; 2], usize>,
+}
+
+fn robust_coord([x, y]: [f64; 2]) -> robust::Coord<f64> {
+    robust::Coord { x, y }
+}
+
+/// A key of the location of `point`, the same for positive and negative zeros.
+fn location_key([x, y]: [f64; 2]) -> [u64; 2] {
+    [(x + 0.0).to_bits(), (y + 0.0).to_bits()]
+}
+
+impl Triangulation {
+    /// A triangulation without vertices.
+    fn new() -> Self {
+        Self {
+            vertices: vec![[f64::NAN; 2]],
+            triangles: vec![],
+            last: 0,
+            collinear: HashMap::new(),
+        }
+    }
+
+    /// Positive if `a`, `b` and `c` are counterclockwise, negative if clockwise.
+    fn orient(&self, a: usize, b: usize, c: [f64; 2]) -> f64 {
+        robust::orient2d(
+            robust_coord(self.vertices[a]),
+            robust_coord(self.vertices[b]),
+            robust_coord(c),
+        )
+    }
+
+    /// The position of the vertex at infinity in `triangle`, if it is a ghost triangle.
+    fn infinite_position(&self, triangle: usize) -> Option<usize> {
+        self.triangles[triangle]
+            .vertices
+            .iter()
+            .position(|vertex| *vertex == INFINITE)
+    }
+
+    /// Whether `point`, collinear with `from` and `to`, lies strictly between them.
+    fn between(&self, from: usize, to: usize, point: [f64; 2]) -> bool {
+        let (from, to) = (self.vertices[from], self.vertices[to]);
+        let axis = if from[0] != to[0] { 0 } else { 1 };
+        (from[axis] < point[axis] && point[axis] < to[axis])
+            || (to[axis] < point[axis] && point[axis] < from[axis])
+    }
+
+    /// Whether `point` is strictly inside the circumcircle of `triangle`.
+    ///
+    /// The circumcircle of a ghost triangle is the open half-plane outside its hull edge, together
+    /// with the inside of that edge.
+    fn in_circumcircle(&self, triangle: usize, point: [f64; 2]) -> bool {
+        let vertices = self.triangles[triangle].vertices;
+        match self.infinite_position(triangle) {
+            Some(i) => {
+                let (from, to) = (vertices[(i + 1) % 3], vertices[(i + 2) % 3]);
+                let side = self.orient(from, to, point);
+                side > 0.0 || (side == 0.0 && self.between(from, to, point))
+            }
+            None => {
+                let [a, b, c] = vertices.map(|vertex| robust_coord(self.vertices[vertex]));
+                robust::incircle(a, b, c, robust_coord(point)) > 0.0
+            }
+        }
+    }
+
+    /// The triangle containing `point`, or the ghost triangle outside the hull edge facing it,
+    /// walking from the last triangle created.
+    fn locate(&self, point: [f64; 2]) -> usize {
+        let mut current = self.last;
+        if let Some(i) = self.infinite_position(current) {
+            current = self.triangles[current].neighbors[i];
+        }
+        'walk: loop {
+            let [a, b, c] = self.triangles[current].vertices;
+            for (i, (from, to)) in [(b, c), (c, a), (a, b)].into_iter().enumerate() {
+                if self.orient(from, to, point) < 0.0 {
+                    current = self.triangles[current].neighbors[i];
+                    if self.infinite_position(current).is_some() {
+                        return current;
+                    }
+                    continue 'walk;
+                }
+            }
+            return current;
+        }
+    }
+
+    /// Insert `point`, returning its vertex, or the vertex already at its location.
+    fn insert(&mut self, point: [f64; 2]) -> usize {
+        if self.triangles.is_empty() {
+            if let Some(&existing) = self.collinear.get(&location_key(point)) {
+                return existing;
+            }
+            let vertex = self.vertices.len();
+            self.vertices.push(point);
+            self.collinear.insert(location_key(point), vertex);
+            if vertex >= 3 && self.orient(1, 2, point) != 0.0 {
+                self.start(vertex);
+            }
+            return vertex;
+        }
+
+        let start = self.locate(point);
+        if let Some(&existing) = self.triangles[start]
+            .vertices
+            .iter()
+            .find(|vertex| self.vertices[**vertex] == point)
+        {
+            return existing;
+        }
+        let vertex = self.vertices.len();
+        self.vertices.push(point);
+        self.connect(vertex, start);
+        vertex
+    }
+
+    /// Start the triangulation with the triangle of vertices 1, 2 and `apex`, then connect the
+    /// vertices between them, which are collinear with vertices 1 and 2.
+    fn start(&mut self, apex: usize) {
+        let [a, b] = if self.orient(1, 2, self.vertices[apex]) > 0.0 {
+            [1, 2]
+        } else {
+            [2, 1]
+        };
+        let triangle = |vertices, neighbors| Triangle {
+            vertices,
+            neighbors,
+            alive: true,
+        };
+        // The triangle, then the ghost triangles outside its edges opposite a, b and apex
+        self.triangles = vec![
+            triangle([a, b, apex], [1, 2, 3]),
+            triangle([apex, b, INFINITE], [3, 2, 0]),
+            triangle([a, apex, INFINITE], [1, 3, 0]),
+            triangle([b, a, INFINITE], [2, 1, 0]),
+        ];
+        self.last = 0;
+        self.collinear.clear();
+
+        for vertex in 3..apex {
+            let start = self.locate(self.vertices[vertex]);
+            self.connect(vertex, start);
+        }
+    }
+
+    /// Join `vertex` to the edges of the cavity of triangles whose circumcircle contains it,
+    /// starting from the triangle `start` containing it.
+    fn connect(&mut self, vertex: usize, start: usize) {
+        let point = self.vertices[vertex];
+        let mut cavity = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(triangle) = stack.pop() {
+            for neighbor in self.triangles[triangle].neighbors {
+                if !cavity.contains(&neighbor) && self.in_circumcircle(neighbor, point) {
+                    cavity.insert(neighbor);
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        // Join each edge of the boundary of the cavity to the new vertex
+        let mut by_start = HashMap::new();
+        let mut by_end = HashMap::new();
+        let mut created = vec![];
+        for &triangle in &cavity {
+            let Triangle {
+                vertices: [a, b, c],
+                neighbors,
+                ..
+            } = self.triangles[triangle];
+            for (i, (from, to)) in [(b, c), (c, a), (a, b)].into_iter().enumerate() {
+                let outside = neighbors[i];
+                if cavity.contains(&outside) {
+                    continue;
+                }
+                let new = self.triangles.len();
+                self.triangles.push(Triangle {
+                    vertices: [from, to, vertex],
+                    neighbors: [0, 0, outside],
+                    alive: true,
+                });
+                let across = &mut self.triangles[outside].neighbors;
+                *across.iter_mut().find(|n| **n == triangle).unwrap() = new;
+                by_start.insert(from, new);
+                by_end.insert(to, new);
+                created.push(new);
+            }
+        }
+        for &triangle in &cavity {
+            self.triangles[triangle].alive = false;
+        }
+
+        // The new triangles around the vertex are neighbors of each other
+        for &new in &created {
+            let [from, to, _] = self.triangles[new].vertices;
+            self.triangles[new].neighbors[0] = by_start[&to];
+            self.triangles[new].neighbors[1] = by_end[&from];
+        }
+        self.last = *created.last().unwrap();
+    }
+
+    /// The triangles between inserted vertices.
+    fn inner_triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        self.triangles
+            .iter()
+            .filter(|triangle| triangle.alive && !triangle.vertices.contains(&INFINITE))
+            .map(|triangle| triangle.vertices)
+    }
+
+    /// The vertices joined to each vertex by an edge, indexed by vertex.
+    fn neighbors(&self) -> Vec<Vec<usize>> {
+        let mut neighbors = vec![vec![]; self.vertices.len()];
+        if self.triangles.is_empty() {
+            // Collinear vertices are joined to the next along their line
+            let mut order: Vec<usize> = (1..self.vertices.len()).collect();
+            order.sort_by(|a, b| {
+                let ([ax, ay], [bx, by]) = (self.vertices[*a], self.vertices[*b]);
+                ax.total_cmp(&bx).then(ay.total_cmp(&by))
+            });
+            for pair in order.windows(2) {
+                neighbors[pair[0]].push(pair[1]);
+                neighbors[pair[1]].push(pair[0]);
+            }
+            return neighbors;
+        }
+
+        for triangle in self.triangles.iter().filter(|triangle| triangle.alive) {
+            let [a, b, c] = triangle.vertices;
+            // Each edge is in two triangles; keep it in the one where it runs upwards in index
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                if from != INFINITE && from < to {
+                    neighbors[from].push(to);
+                    neighbors[to].push(from);
+                }
+            }
+        }
+        neighbors
+    }
+}
+
+/// The triangulation of the non-null points of `points`, and the index of the input point of
+/// each of its vertices.
+fn triangulate(points: &PointArray) -> (Triangulation, Vec<u32>) {
+    let valid: Vec<usize> = (0..points.len()).filter(|i| points.is_valid(*i)).collect();
+    let coords: Vec<geo::Coord> = valid.iter().map(|i| points.value_as_geo(*i).0).collect();
+
+    let mut bounds = [
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    ];
+    for coord in &coords {
+        bounds = [
+            bounds[0].min(coord.x),
+            bounds[1].min(coord.y),
+            bounds[2].max(coord.x),
+            bounds[3].max(coord.y),
+        ];
+    }
+    let rect = geo::Rect::new((bounds[0], bounds[1]), (bounds[2], bounds[3]));
+    let mut order: Vec<usize> = (0..coords.len()).collect();
+    // Repeated points have the same key, so the first occurrence is inserted first
+    order.sort_by_key(|i| (hilbert_index(coords[*i], &rect), valid[*i]));
+
+    let mut triangulation = Triangulation::new();
+    let mut sources = vec![u32::MAX];
+    for i in order {
+        let vertex = triangulation.insert([coords[i].x, coords[i].y]);
+        if vertex == sources.len() {
+            sources.push(valid[i] as u32);
+        }
+    }
+    (triangulation, sources)
+}
+
+/// The Delaunay triangulation of `points`.
+///
+/// Returns the triangles and an array of three times their length, holding the indices of the
+/// points at the vertices of triangle `i` in slots `3 * i..3 * i + 3`. Triangles are
+/// counterclockwise, and points that are all collinear have no triangles. The triangles have the
+/// CRS of `points`.
+pub fn delaunay_triangulation(points: &PointArray) -> (PolygonArray, PrimitiveArray<u32>) {
+    let (triangulation, sources) = triangulate(points);
+    let mut triangles = vec![];
+    let mut indices = vec![];
+    for vertices in triangulation.inner_triangles() {
+        let ring: Vec<geo::Coord> = vertices
+            .iter()
+            .map(|v| triangulation.vertices[*v].into())
+            .collect();
+        triangles.push(geo::Polygon::new(ring.into(), vec![]));
+        indices.extend(vertices.iter().map(|v| sources[*v]));
+    }
+    (
+        PolygonArray::from(triangles).with_crs(points.crs()),
+        PrimitiveArray::from_vec(indices),
+    )
+}
+
+/// Clip the convex `ring` to the points at least as close to `point` as to `other`.
+fn clip_to_half_plane(
+    ring: &[geo::Coord],
+    point: geo::Coord,
+    other: geo::Coord,
+) -> Vec<geo::Coord> {
+    let midpoint = (point + other) / 2.0;
+    let normal = other - point;
+    let side = |coord: geo::Coord| {
+        let offset = coord - midpoint;
+        offset.x * normal.x + offset.y * normal.y
+    };
+
+    let mut clipped = vec![];
+    for (i, &current) in ring.iter().enumerate() {
+        let next = ring[(i + 1) % ring.len()];
+        let (current_side, next_side) = (side(current), side(next));
+        if current_side <= 0.0 {
+            clipped.push(current);
+        }
+        if (current_side < 0.0 && next_side > 0.0) || (current_side > 0.0 && next_side < 0.0) {
+            let t = current_side / (current_side - next_side);
+            clipped.push(current + (next - current) * t);
+        }
+    }
+    clipped
+}
+
+/// The Voronoi cells of `points`, clipped to `clip_bbox`.
+///
+/// Returns the cells and the index of the point of each cell. Cells are counterclockwise, and
+/// points whose cell lies outside `clip_bbox` have none. The cells have the CRS of `points`.
+pub fn voronoi_polygons(
+    points: &PointArray,
+    clip_bbox: &geo::Rect,
+) -> (PolygonArray, PrimitiveArray<u32>) {
+    let (min, max) = (clip_bbox.min(), clip_bbox.max());
+    let (triangulation, sources) = triangulate(points);
+    let neighbors = triangulation.neighbors();
+    let box_ring = [
+        min,
+        geo::coord! { x: max.x, y: min.y },
+        max,
+        geo::coord! { x: min.x, y: max.y },
+    ];
+
+    let vertices = triangulation.vertices.len();
+    let cells = map_rows(vertices - 1, |i| {
+        let vertex = i + 1;
+        let point: geo::Coord = triangulation.vertices[vertex].into();
+        let mut ring = box_ring.to_vec();
+        for neighbor in &neighbors[vertex] {
+            if ring.is_empty() {
+                break;
+            }
+            ring = clip_to_half_plane(&ring, point, triangulation.vertices[*neighbor].into());
+        }
+        (ring.len() >= 3).then(|| geo::Polygon::new(ring.into(), vec![]))
+    });
+
+    let mut polygons = vec![];
+    let mut indices = vec![];
+    for (vertex, cell) in (1..vertices).zip(cells) {
+        if let Some(cell) = cell {
+            polygons.push(cell);
+            indices.push(sources[vertex]);
+        }
+    }
+    (
+        PolygonArray::from(polygons).with_crs(points.crs()),
+        PrimitiveArray::from_vec(indices),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{point, Area, ConvexHull};
+
+    #[test]
+    fn predicates() {
+        let mut triangulation = Triangulation::new();
+        for point in [[0., 0.], [1., 0.], [0., 1.]] {
+            triangulation.insert(point);
+        }
+        assert_eq!(triangulation.inner_triangles().count(), 1);
+        assert!(triangulation.orient(1, 2, [0., 1.]) > 0.0);
+        assert!(triangulation.in_circumcircle(0, [0.5, 0.5]));
+        assert!(!triangulation.in_circumcircle(0, [1e12, 1e12]));
+
+        // The ghost triangle below the edge from (0, 0) to (1, 0) holds the open half-plane below
+        // it and the inside of the edge
+        let ghost = (0..4)
+            .find(|t| triangulation.triangles[*t].vertices == [2, 1, INFINITE])
+            .unwrap();
+        assert!(triangulation.in_circumcircle(ghost, [1e12, -1e-12]));
+        assert!(triangulation.in_circumcircle(ghost, [0.5, 0.]));
+        assert!(!triangulation.in_circumcircle(ghost, [2., 0.]));
+        assert!(!triangulation.in_circumcircle(ghost, [0.5, 1e-12]));
+    }
+
+    #[test]
+    fn triangulation() {
+        let points: PointArray = vec![
+            Some(point!(x: 0., y: 0.)),
+            Some(point!(x: 2., y: 0.)),
+            None,
+            Some(point!(x: 2., y: 2.)),
+            Some(point!(x: 0., y: 2.)),
+            Some(point!(x: 1., y: 1.)),
+            Some(point!(x: 2., y: 0.)),
+        ]
+        .into();
+        let (triangles, indices) = delaunay_triangulation(&points.with_crs(Some("EPSG:3857")));
+        assert_eq!(triangles.len(), 4);
+        assert_eq!(indices.len(), 12);
+        assert_eq!(triangles.crs(), Some("EPSG:3857"));
+
+        // The center point is a vertex of every triangle, and repeats use the first index
+        for i in 0..4 {
+            let vertices = &indices.values()[3 * i..3 * i + 3];
+            assert!(vertices.contains(&5));
+            assert!(!vertices.contains(&2) && !vertices.contains(&6));
+            let triangle = triangles.value_as_geo(i);
+            assert_eq!(triangle.signed_area(), 1.0);
+        }
+
+        let collinear: PointArray = vec![point!(x: 0., y: 0.), point!(x: 1., y: 1.)].into();
+        assert_eq!(delaunay_triangulation(&collinear).0.len(), 0);
+    }
+
+    #[test]
+    fn grid() {
+        let points: Vec<geo::Point> = (0..400)
+            .map(|i| point!(x: (i % 20) as f64, y: (i / 20) as f64))
+            .collect();
+        let points: PointArray = points.into();
+        let (triangles, _) = delaunay_triangulation(&points);
+        // A triangulation of n points with h on the convex hull has 2n - h - 2 triangles
+        assert_eq!(triangles.len(), 2 * 400 - 76 - 2);
+        let area: f64 = (0..triangles.len())
+            .map(|i| triangles.value_as_geo(i).signed_area())
+            .sum();
+        assert!((area - 361.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn near_collinear_hull() {
+        // The triangle on the bottom edge of the hull is nearly flat, with a circumcircle far
+        // wider than the points' extent, but is still part of the triangulation
+        let points = vec![
+            point!(x: 0., y: 0.),
+            point!(x: 10., y: 0.),
+            point!(x: 5., y: 1e-9),
+            point!(x: 5., y: 10.),
+        ];
+        let (triangles, indices) = delaunay_triangulation(&points.clone().into());
+        assert_eq!(triangles.len(), 3);
+        assert!(indices
+            .values()
+            .chunks(3)
+            .any(|vertices| vertices.contains(&0) && vertices.contains(&1)));
+
+        let area: f64 = (0..triangles.len())
+            .map(|i| triangles.value_as_geo(i).signed_area())
+            .sum();
+        let hull = geo::MultiPoint(points).convex_hull().unsigned_area();
+        assert!((area - hull).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_circumcircles() {
+        // Pseudorandom points from a linear congruential generator
+        let mut state: u64 = 42;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 100.0
+        };
+        let points: Vec<geo::Point> = (0..300).map(|_| point!(x: next(), y: next())).collect();
+        let (triangles, indices) = delaunay_triangulation(&points.clone().into());
+        assert!(triangles.len() > 500);
+
+        for (i, vertices) in indices.values().chunks(3).enumerate() {
+            let [a, b, c] = [0, 1, 2].map(|j| robust_coord(points[vertices[j] as usize].into()));
+            for (k, point) in points.iter().enumerate() {
+                if !vertices.contains(&(k as u32)) {
+                    let inside = robust::incircle(a, b, c, robust_coord((*point).into()));
+                    assert!(inside <= 0.0, "point {k} is inside triangle {i}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn voronoi() {
+        let points: PointArray = vec![
+            Some(point!(x: 1., y: 1.)),
+            None,
+            Some(point!(x: 3., y: 1.)),
+            Some(point!(x: 20., y: 20.)),
+        ]
+        .into();
+        let bbox = geo::Rect::new((0., 0.), (4., 2.));
+        let (cells, indices) = voronoi_polygons(&points, &bbox);
+        assert_eq!(indices.values().as_slice(), &[0, 2]);
+        assert_eq!(cells.len(), 2);
+        assert!(indices.validity().is_none());
+        assert_eq!(cells.value_as_geo(0).unsigned_area(), 4.0);
+        assert_eq!(cells.value_as_geo(1).unsigned_area(), 4.0);
+
+        // Collinear points have no triangles, but their cells split the box
+        let points: PointArray = vec![
+            point!(x: 1., y: 1.),
+            point!(x: 3., y: 1.),
+            point!(x: 2., y: 1.),
+        ]
+        .into();
+        let (cells, indices) = voronoi_polygons(&points, &bbox);
+        assert_eq!(cells.len(), 3);
+        for (i, index) in indices.values().iter().enumerate() {
+            let area = if *index == 2 { 2.0 } else { 3.0 };
+            assert_eq!(cells.value_as_geo(i).unsigned_area(), area);
+        }
+
+        // The areas of the cells of a grid add up to the clipping box
+        let points: Vec<geo::Point> = (0..100)
+            .map(|i| point!(x: (i % 10) as f64 + 0.5, y: (i / 10) as f64 + 0.5))
+            .collect();
+        let points: PointArray = points.into();
+        let bbox = geo::Rect::new((0., 0.), (10., 10.));
+        let (cells, _) = voronoi_polygons(&points, &bbox);
+        assert_eq!(cells.len(), 100);
+        for i in 0..100 {
+            assert!((cells.value_as_geo(i).signed_area() - 1.0).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,411 @@
+//! Split multi geometries into their parts, and collect single geometries back into multi
+//! geometries.
+//!
+//! Like GeoPandas' `explode`, [`Explode`] makes every part of a multi geometry its own row of the
+//! corresponding single-geometry array, and an index array maps each output row back to the
+//! input row it came from, so that other columns can be repeated with [`Take`] or Arrow's `take`
+//! kernel. Null geometries produce a single null row, while empty multi geometries produce no
+//! rows.
+//!
+//! [`CollectByGroup`] is the inverse: it gathers the geometries sharing a group key into one
+//! multi geometry per key, so collecting the output of [`Explode`] by its index array rebuilds
+//! the non-empty input rows.
+
+use super::take::Take;
+use crate::error::GeoArrowError;
+use crate::{
+    GeometryArrayTrait, LineStringArray, MultiLineStringArray, MultiPointArray, MultiPolygonArray,
+    PointArray, PolygonArray,
+};
+use arrow2::array::PrimitiveArray;
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::offset::{Offsets, OffsetsBuffer};
+use arrow2::types::Index;
+use std::collections::HashMap;
+
+/// Split multi geometries into their parts.
+pub trait Explode {
+    type Output;
+
+    /// The parts of every geometry, one per row, and the index of the input row of each part.
+    fn explode(&self) -> (Self::Output, PrimitiveArray<i64>);
+}
+
+/// Collect single geometries into multi geometries by group key.
+pub trait CollectByGroup {
+    type Output;
+
+    /// One multi geometry per distinct key of `groups`, holding the geometries with that key in
+    /// their input order, together with the key of each output row.
+    ///
+    /// Groups are output in order of the first appearance of their key. Geometries with a null
+    /// key are dropped, and null geometries are skipped, so a group of only null geometries is
+    /// null.
+    /// # Errors
+    /// Errors if `groups` has a different length than the array, or if the offsets of the result
+    /// would overflow.
+    fn collect_by_group(
+        &self,
+        groups: &PrimitiveArray<i64>,
+    ) -> Result<(Self::Output, PrimitiveArray<i64>), GeoArrowError>;
+}
+
+/// The rows of each group in order of first appearance of their key, as the rows to take, the
+/// offsets of each group into those rows, the validity of each group and the key of each group.
+fn group_rows(
+    groups: &PrimitiveArray<i64>,
+    validity: Option<&Bitmap>,
+) -> (
+    PrimitiveArray<i64>,
+    OffsetsBuffer<i64>,
+    Option<Bitmap>,
+    PrimitiveArray<i64>,
+) {
+    let mut group_index: HashMap<i64, usize> = HashMap::new();
+    let mut members: Vec<Vec<i64>> = vec![];
+    let mut keys = vec![];
+    for (row, key) in groups.iter().enumerate() {
+        let key = match key {
+            Some(key) => *key,
+            None => continue,
+        };
+        let group = *group_index.entry(key).or_insert_with(|| {
+            members.push(vec![]);
+            keys.push(key);
+            members.len() - 1
+        });
+        if validity.map_or(true, |validity| validity.get_bit(row)) {
+            members[group].push(row as i64);
+        }
+    }
+
+    let mut offsets = Offsets::<i64>::with_capacity(members.len());
+    let mut output_validity = MutableBitmap::with_capacity(members.len());
+    for group in &members {
+        offsets
+            .try_push_usize(group.len())
+            .expect("the rows of a group fit in i64");
+        output_validity.push(!group.is_empty());
+    }
+    let output_validity: Bitmap = output_validity.into();
+    (
+        PrimitiveArray::from_vec(members.concat()),
+        offsets.into(),
+        (output_validity.unset_bits() > 0).then_some(output_validity),
+        PrimitiveArray::from_vec(keys),
+    )
+}
+
+/// Check that `groups` has one key per geometry of an array of length `len`.
+fn check_groups_len(groups: &PrimitiveArray<i64>, len: usize) -> Result<(), GeoArrowError> {
+    if groups.len() != len {
+        return Err(GeoArrowError::General(
+            "groups must have the same length as the array".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl CollectByGroup for PointArray {
+    type Output = MultiPointArray;
+
+    fn collect_by_group(
+        &self,
+        groups: &PrimitiveArray<i64>,
+    ) -> Result<(Self::Output, PrimitiveArray<i64>), GeoArrowError> {
+        check_groups_len(groups, self.len())?;
+        let (rows, geom_offsets, validity, keys) = group_rows(groups, self.validity());
+        let parts = self.take(&rows)?;
+        let multi_points = MultiPointArray::new(
+            parts.values_x().clone(),
+            parts.values_y().clone(),
+            geom_offsets,
+            validity,
+        );
+        Ok((multi_points.with_crs(self.crs()), keys))
+    }
+}
+
+impl CollectByGroup for LineStringArray {
+    type Output = MultiLineStringArray;
+
+    fn collect_by_group(
+        &self,
+        groups: &PrimitiveArray<i64>,
+    ) -> Result<(Self::Output, PrimitiveArray<i64>), GeoArrowError> {
+        check_groups_len(groups, self.len())?;
+        let (rows, geom_offsets, validity, keys) = group_rows(groups, self.validity());
+        let parts = self.take(&rows)?;
+        let multi_line_strings = MultiLineStringArray::new(
+            parts.values_x().clone(),
+            parts.values_y().clone(),
+            geom_offsets,
+            parts.geom_offsets().clone(),
+            validity,
+        );
+        Ok((multi_line_strings.with_crs(self.crs()), keys))
+    }
+}
+
+impl CollectByGroup for PolygonArray {
+    type Output = MultiPolygonArray;
+
+    fn collect_by_group(
+        &self,
+        groups: &PrimitiveArray<i64>,
+    ) -> Result<(Self::Output, PrimitiveArray<i64>), GeoArrowError> {
+        check_groups_len(groups, self.len())?;
+        let (rows, geom_offsets, validity, keys) = group_rows(groups, self.validity());
+        let parts = self.take(&rows)?;
+        let multi_polygons = MultiPolygonArray::new(
+            parts.values_x().clone(),
+            parts.values_y().clone(),
+            geom_offsets,
+            parts.geom_offsets().clone(),
+            parts.ring_offsets().clone(),
+            validity,
+        );
+        Ok((multi_polygons.with_crs(self.crs()), keys))
+    }
+}
+
+/// The parts of each row of a list array with the given geometry offsets, as the output's
+/// offsets into the children of the parts, its validity and the index of the input row of each
+/// output row.
+///
+/// Each null row is given one null output row, spanning the children of whatever parts it has so
+/// that the output offsets stay contiguous.
+fn explode_offsets(
+    geom_offsets: &OffsetsBuffer<i64>,
+    validity: Option<&Bitmap>,
+    part_offsets: &OffsetsBuffer<i64>,
+) -> (OffsetsBuffer<i64>, Option<Bitmap>, PrimitiveArray<i64>) {
+    let len = geom_offsets.len_proxy();
+    let part_offsets = part_offsets.buffer();
+    let mut offsets = Vec::with_capacity(len + 1);
+    offsets.push(part_offsets[geom_offsets.first().to_usize()]);
+    let mut output_validity = MutableBitmap::with_capacity(len);
+    let mut indices = Vec::with_capacity(len);
+    for geom_idx in 0..len {
+        let (start, end) = geom_offsets.start_end(geom_idx);
+        if validity.map_or(false, |validity| !validity.get_bit(geom_idx)) {
+            offsets.push(part_offsets[end]);
+            output_validity.push(false);
+            indices.push(geom_idx as i64);
+            continue;
+        }
+
+        offsets.extend_from_slice(&part_offsets[start + 1..end + 1]);
+        output_validity.extend_constant(end - start, true);
+        indices.resize(indices.len() + end - start, geom_idx as i64);
+    }
+
+    let validity: Bitmap = output_validity.into();
+    (
+        offsets
+            .try_into()
+            .expect("offsets of the parts are monotonic"),
+        (validity.unset_bits() > 0).then_some(validity),
+        PrimitiveArray::from_vec(indices),
+    )
+}
+
+impl Explode for MultiPointArray {
+    type Output = PointArray;
+
+    /// The coordinates are shared with the input unless it has nulls, which need placeholder
+    /// coordinates in the output.
+    fn explode(&self) -> (Self::Output, PrimitiveArray<i64>) {
+        let geom_offsets = self.geom_offsets();
+        if self.null_count() == 0 {
+            let (start, end) = (
+                geom_offsets.first().to_usize(),
+                geom_offsets.last().to_usize(),
+            );
+            let mut indices = Vec::with_capacity(end - start);
+            for geom_idx in 0..self.len() {
+                let (part_start, part_end) = geom_offsets.start_end(geom_idx);
+                indices.resize(indices.len() + part_end - part_start, geom_idx as i64);
+            }
+            let points = PointArray::new(
+                self.values_x().clone().sliced(start, end - start),
+                self.values_y().clone().sliced(start, end - start),
+                None,
+            );
+            return (
+                points.with_crs(self.crs()),
+                PrimitiveArray::from_vec(indices),
+            );
+        }
+
+        let mut x = vec![];
+        let mut y = vec![];
+        let mut validity = MutableBitmap::new();
+        let mut indices = vec![];
+        for geom_idx in 0..self.len() {
+            if self.is_null(geom_idx) {
+                x.push(0.);
+                y.push(0.);
+                validity.push(false);
+                indices.push(geom_idx as i64);
+                continue;
+            }
+
+            let (start, end) = geom_offsets.start_end(geom_idx);
+            x.extend_from_slice(&self.values_x()[start..end]);
+            y.extend_from_slice(&self.values_y()[start..end]);
+            validity.extend_constant(end - start, true);
+            indices.resize(indices.len() + end - start, geom_idx as i64);
+        }
+
+        let points = PointArray::new(x.into(), y.into(), Some(validity.into()));
+        (
+            points.with_crs(self.crs()),
+            PrimitiveArray::from_vec(indices),
+        )
+    }
+}
+
+/// Line strings share the coordinates of the input.
+impl Explode for MultiLineStringArray {
+    type Output = LineStringArray;
+
+    fn explode(&self) -> (Self::Output, PrimitiveArray<i64>) {
+        let (geom_offsets, validity, indices) =
+            explode_offsets(self.geom_offsets(), self.validity(), self.ring_offsets());
+        let line_strings = LineStringArray::new(
+            self.values_x().clone(),
+            self.values_y().clone(),
+            geom_offsets,
+            validity,
+        );
+        (line_strings.with_crs(self.crs()), indices)
+    }
+}
+
+/// Polygons share the rings and coordinates of the input.
+impl Explode for MultiPolygonArray {
+    type Output = PolygonArray;
+
+    fn explode(&self) -> (Self::Output, PrimitiveArray<i64>) {
+        let (geom_offsets, validity, indices) =
+            explode_offsets(self.geom_offsets(), self.validity(), self.polygon_offsets());
+        let polygons = PolygonArray::new(
+            self.values_x().clone(),
+            self.values_y().clone(),
+            geom_offsets,
+            self.ring_offsets().clone(),
+            validity,
+        );
+        (polygons.with_crs(self.crs()), indices)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point, polygon, MultiLineString, MultiPoint, MultiPolygon};
+
+    #[test]
+    fn multi_points() {
+        let arr: MultiPointArray = vec![
+            Some(MultiPoint::new(vec![
+                point!(x: 0., y: 1.),
+                point!(x: 2., y: 3.),
+            ])),
+            None,
+            Some(MultiPoint::new(vec![point!(x: 4., y: 5.)])),
+        ]
+        .into();
+        let (points, indices) = arr.explode();
+        assert_eq!(indices.values().as_slice(), &[0, 0, 1, 2]);
+        assert_eq!(points.get_as_geo(1), Some(point!(x: 2., y: 3.)));
+        assert!(points.is_null(2));
+        assert_eq!(points.get_as_geo(3), Some(point!(x: 4., y: 5.)));
+
+        let (points, indices) = arr.sliced_range(2..3).explode();
+        assert_eq!(indices.values().as_slice(), &[0]);
+        assert_eq!(points.get_as_geo(0), Some(point!(x: 4., y: 5.)));
+    }
+
+    #[test]
+    fn multi_line_strings() {
+        let ls0 = line_string![(x: 0., y: 1.), (x: 2., y: 3.)];
+        let ls1 = line_string![(x: 4., y: 5.), (x: 6., y: 7.), (x: 8., y: 9.)];
+        let arr: MultiLineStringArray = vec![
+            Some(MultiLineString::new(vec![ls0.clone()])),
+            None,
+            Some(MultiLineString::new(vec![])),
+            Some(MultiLineString::new(vec![ls0.clone(), ls1.clone()])),
+        ]
+        .into();
+        let (line_strings, indices) = arr.sliced_range(1..4).explode();
+        assert_eq!(indices.values().as_slice(), &[0, 2, 2]);
+        assert!(line_strings.is_null(0));
+        assert_eq!(line_strings.get_as_geo(1), Some(ls0));
+        assert_eq!(line_strings.get_as_geo(2), Some(ls1));
+    }
+
+    #[test]
+    fn multi_polygons() {
+        let p0 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let p1 = polygon![(x: 5., y: 5.), (x: 6., y: 5.), (x: 6., y: 6.), (x: 5., y: 5.)];
+        let arr: MultiPolygonArray = vec![
+            MultiPolygon::new(vec![p0.clone(), p1.clone()]),
+            MultiPolygon::new(vec![p1.clone()]),
+        ]
+        .into();
+        let (polygons, indices) = arr.with_crs(Some("EPSG:4326")).explode();
+        assert_eq!(indices.values().as_slice(), &[0, 0, 1]);
+        assert_eq!(polygons.crs(), Some("EPSG:4326"));
+        assert_eq!(polygons.get_as_geo(1), Some(p1));
+        assert_eq!(polygons.get_as_geo(0), Some(p0));
+    }
+
+    #[test]
+    fn collect_exploded() {
+        let p0 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let p1 = polygon![(x: 5., y: 5.), (x: 6., y: 5.), (x: 6., y: 6.), (x: 5., y: 5.)];
+        let arr: MultiPolygonArray = vec![
+            Some(MultiPolygon::new(vec![p0.clone(), p1.clone()])),
+            None,
+            Some(MultiPolygon::new(vec![p1])),
+        ]
+        .into();
+        let (polygons, indices) = arr.explode();
+        let (collected, keys) = polygons.collect_by_group(&indices).unwrap();
+        assert_eq!(keys.values().as_slice(), &[0, 1, 2]);
+        assert_eq!(collected, arr);
+    }
+
+    #[test]
+    fn collect_points_by_key() {
+        let points: PointArray = vec![
+            Some(point!(x: 0., y: 0.)),
+            Some(point!(x: 1., y: 1.)),
+            Some(point!(x: 2., y: 2.)),
+            None,
+            Some(point!(x: 3., y: 3.)),
+        ]
+        .into();
+        let groups = PrimitiveArray::from([Some(7), Some(3), Some(7), Some(9), None]);
+        let (multi_points, keys) = points.collect_by_group(&groups).unwrap();
+        assert_eq!(keys.values().as_slice(), &[7, 3, 9]);
+        assert_eq!(
+            multi_points.get_as_geo(0),
+            Some(MultiPoint::new(vec![
+                point!(x: 0., y: 0.),
+                point!(x: 2., y: 2.)
+            ]))
+        );
+        assert_eq!(
+            multi_points.get_as_geo(1),
+            Some(MultiPoint::new(vec![point!(x: 1., y: 1.)]))
+        );
+        assert!(multi_points.is_null(2));
+
+        assert!(points
+            .collect_by_group(&PrimitiveArray::from_vec(vec![0]))
+            .is_err());
+    }
+}
@@ -0,0 +1,547 @@
+//! Sort tables larger than memory by the Hilbert key of their geometries.
+//!
+//! [`ExternalHilbertSorter`] buffers pushed rows up to a row budget. Each time the budget is
+//! exceeded, the buffered rows are sorted and spilled to a temporary Arrow IPC file (a "run").
+//! [`ExternalHilbertSorter::finish`] then merges the runs, reading one batch per run at a time,
+//! so that memory use stays bounded regardless of the size of the input.
+
+use crate::algorithm::hilbert::hilbert_keys;
+use crate::error::GeoArrowError;
+use crate::table::GeoTable;
+use arrow2::array::growable::make_growable;
+use arrow2::array::{Array, PrimitiveArray};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::io::ipc::read::{read_file_metadata, FileReader};
+use arrow2::io::ipc::write::{FileWriter, WriteOptions};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Name of the column holding the Hilbert key of each row in spilled runs.
+const KEY_COLUMN: &str = "__hilbert_key";
+
+/// Number of rows per record batch written to a run file. This also bounds how many rows of each
+/// run are held in memory while merging.
+const RUN_BATCH_SIZE: usize = 8192;
+
+/// Distinguishes the run files of sorters within this process.
+static RUN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A chunk together with the sort key of each of its rows.
+type KeyedChunk = (Chunk<Box<dyn Array>>, Vec<u64>);
+
+/// Sorts the rows of [`GeoTable`]s by the Hilbert key of their geometries, spilling to disk when
+/// the rows don't fit in memory.
+///
+/// Push tables with [`push`](Self::push), then call [`finish`](Self::finish) to iterate over the
+/// sorted rows. The sort is stable, and rows with null or empty geometries come last.
+///
+/// The Hilbert curve spans `bounds`, which should be the total bounds of all the rows to be
+/// sorted, such as from [`GeoParquetDataset::bounds`]. Geometries outside `bounds` are clamped to
+/// its edges, which still yields a valid, if less local, ordering.
+///
+/// [`GeoParquetDataset::bounds`]: crate::io::parquet::GeoParquetDataset::bounds
+#[derive(Debug)]
+pub struct ExternalHilbertSorter {
+    bounds: geo::Rect,
+    max_rows_in_memory: usize,
+    temp_dir: PathBuf,
+    schema: Option<Schema>,
+    geometry_column_index: usize,
+    buffered: Vec<KeyedChunk>,
+    buffered_rows: usize,
+    runs: RunFiles,
+}
+
+impl ExternalHilbertSorter {
+    /// Create a sorter holding at most `max_rows_in_memory` unsorted rows before spilling them to
+    /// a run in the system temporary directory.
+    pub fn new(bounds: geo::Rect, max_rows_in_memory: usize) -> Self {
+        Self {
+            bounds,
+            max_rows_in_memory: max_rows_in_memory.max(1),
+            temp_dir: std::env::temp_dir(),
+            schema: None,
+            geometry_column_index: 0,
+            buffered: vec![],
+            buffered_rows: 0,
+            runs: RunFiles(vec![]),
+        }
+    }
+
+    /// Write runs to `temp_dir` instead of the system temporary directory.
+    pub fn with_temp_dir(mut self, temp_dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = temp_dir.into();
+        self
+    }
+
+    /// The number of runs spilled to disk so far.
+    pub fn num_runs(&self) -> usize {
+        self.runs.0.len()
+    }
+
+    /// Add the rows of `table` to the sort.
+    /// # Errors
+    /// Errors if `table` has a different schema or geometry column than the first table pushed,
+    /// or if spilling a run to disk fails.
+    pub fn push(&mut self, table: &GeoTable) -> Result<(), GeoArrowError> {
+        match &self.schema {
+            None => {
+                self.schema = Some(table.schema().clone());
+                self.geometry_column_index = table.geometry_column_index();
+            }
+            Some(schema) => {
+                if schema.fields != table.schema().fields
+                    || self.geometry_column_index != table.geometry_column_index()
+                {
+                    return Err(GeoArrowError::General(
+                        "all tables pushed to a sorter must share one schema".to_string(),
+                    ));
+                }
+            }
+        }
+
+        for (chunk, geometry) in table.chunks().iter().zip(table.geometry()?) {
+            if chunk.is_empty() {
+                continue;
+            }
+            let keys = hilbert_keys(&geometry, &self.bounds)
+                .iter()
+                .map(|key| key.map_or(u64::MAX, |key| u64::from(*key)))
+                .collect();
+            self.buffered_rows += chunk.len();
+            self.buffered.push((chunk.clone(), keys));
+
+            if self.buffered_rows >= self.max_rows_in_memory {
+                self.spill()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sort the buffered rows and write them to a new run file.
+    fn spill(&mut self) -> Result<(), GeoArrowError> {
+        let (chunk, keys) = sort_chunks(std::mem::take(&mut self.buffered));
+        self.buffered_rows = 0;
+
+        let mut fields = self.schema.as_ref().unwrap().fields.clone();
+        fields.push(Field::new(KEY_COLUMN, DataType::UInt32, true));
+        let run_schema = Schema::from(fields);
+
+        let path = self.temp_dir.join(format!(
+            "geoarrow-hilbert-{}-{}.arrow",
+            std::process::id(),
+            RUN_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        // Register the run before writing it, so that a partial file is still cleaned up
+        self.runs.0.push(path.clone());
+
+        let file = BufWriter::new(File::create(&path)?);
+        let options = WriteOptions { compression: None };
+        let mut writer = FileWriter::try_new(file, run_schema, None, options)
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+        for offset in (0..chunk.len()).step_by(RUN_BATCH_SIZE) {
+            let length = RUN_BATCH_SIZE.min(chunk.len() - offset);
+            let key_column = PrimitiveArray::<u32>::from_iter(
+                keys[offset..offset + length]
+                    .iter()
+                    .map(|key| u32::try_from(*key).ok()),
+            );
+            let mut arrays: Vec<Box<dyn Array>> = chunk
+                .arrays()
+                .iter()
+                .map(|array| array.sliced(offset, length))
+                .collect();
+            arrays.push(key_column.boxed());
+            writer
+                .write(&Chunk::new(arrays), None)
+                .map_err(|err| GeoArrowError::External(err.into()))?;
+        }
+        writer
+            .finish()
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+        Ok(())
+    }
+
+    /// Finish the sort, returning an iterator over the sorted rows in chunks of at most
+    /// `batch_size` rows.
+    ///
+    /// If nothing was spilled, the rows are sorted in memory. Otherwise the remaining rows are
+    /// spilled too, and the runs are merged lazily as the iterator advances. Run files are
+    /// deleted when the iterator is dropped.
+    /// # Errors
+    /// Errors if no table was pushed, or if spilling or opening a run fails.
+    pub fn finish(mut self, batch_size: usize) -> Result<SortedChunks, GeoArrowError> {
+        let schema = self.schema.clone().ok_or_else(|| {
+            GeoArrowError::General("no tables were pushed to the sorter".to_string())
+        })?;
+        let batch_size = batch_size.max(1);
+
+        let source = if self.runs.0.is_empty() {
+            let (chunk, _) = sort_chunks(std::mem::take(&mut self.buffered));
+            SortedSource::InMemory { chunk, offset: 0 }
+        } else {
+            if !self.buffered.is_empty() {
+                self.spill()?;
+            }
+            let runs = std::mem::replace(&mut self.runs, RunFiles(vec![]));
+            let mut cursors = Vec::with_capacity(runs.0.len());
+            let mut heap = BinaryHeap::with_capacity(runs.0.len());
+            for (run_idx, path) in runs.0.iter().enumerate() {
+                let mut cursor = RunCursor::try_new(path)?;
+                if let Some(key) = cursor.key()? {
+                    heap.push(Reverse((key, run_idx)));
+                }
+                cursors.push(cursor);
+            }
+            SortedSource::Merge {
+                cursors,
+                heap,
+                _runs: runs,
+            }
+        };
+
+        Ok(SortedChunks {
+            schema,
+            geometry_column_index: self.geometry_column_index,
+            batch_size,
+            source,
+        })
+    }
+}
+
+/// Concatenate `chunks` into one chunk ordered by their keys, keeping the input order of rows
+/// with equal keys.
+fn sort_chunks(chunks: Vec<KeyedChunk>) -> KeyedChunk {
+    let mut rows: Vec<(u64, usize, usize)> = chunks
+        .iter()
+        .enumerate()
+        .flat_map(|(chunk_idx, (_, keys))| {
+            keys.iter()
+                .enumerate()
+                .map(move |(row, key)| (*key, chunk_idx, row))
+        })
+        .collect();
+    rows.sort_by_key(|(key, _, _)| *key);
+
+    let sources: Vec<&Chunk<Box<dyn Array>>> = chunks.iter().map(|(chunk, _)| chunk).collect();
+    let picks: Vec<(usize, usize)> = rows
+        .iter()
+        .map(|(_, chunk_idx, row)| (*chunk_idx, *row))
+        .collect();
+    let num_columns = sources.first().map_or(0, |chunk| chunk.arrays().len());
+    let chunk = gather(&sources, num_columns, &picks);
+    (chunk, rows.into_iter().map(|(key, _, _)| key).collect())
+}
+
+/// Build a chunk with the first `num_columns` columns of `sources`, taking row `row` of source
+/// `source` for each `(source, row)` in `picks`.
+fn gather(
+    sources: &[&Chunk<Box<dyn Array>>],
+    num_columns: usize,
+    picks: &[(usize, usize)],
+) -> Chunk<Box<dyn Array>> {
+    let arrays = (0..num_columns)
+        .map(|column| {
+            let columns: Vec<&dyn Array> = sources
+                .iter()
+                .map(|chunk| chunk.arrays()[column].as_ref())
+                .collect();
+            let mut growable = make_growable(&columns, true, picks.len());
+
+            // Copy consecutive rows of the same source in one call
+            let mut start = 0;
+            while start < picks.len() {
+                let (source, first_row) = picks[start];
+                let mut end = start + 1;
+                while end < picks.len() && picks[end] == (source, first_row + end - start) {
+                    end += 1;
+                }
+                growable.extend(source, first_row, end - start);
+                start = end;
+            }
+            growable.as_box()
+        })
+        .collect();
+    Chunk::new(arrays)
+}
+
+/// Paths of spilled runs, deleted on drop.
+#[derive(Debug)]
+struct RunFiles(Vec<PathBuf>);
+
+impl Drop for RunFiles {
+    fn drop(&mut self) {
+        for path in &self.0 {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Reads the rows of one run in order, one record batch at a time.
+struct RunCursor {
+    reader: FileReader<BufReader<File>>,
+    chunk: Option<KeyedChunk>,
+    row: usize,
+}
+
+impl RunCursor {
+    fn try_new(path: &Path) -> Result<Self, GeoArrowError> {
+        let mut file = BufReader::new(File::open(path)?);
+        let metadata =
+            read_file_metadata(&mut file).map_err(|err| GeoArrowError::External(err.into()))?;
+        let reader = FileReader::new(file, metadata, None, None);
+        Ok(Self {
+            reader,
+            chunk: None,
+            row: 0,
+        })
+    }
+
+    /// The key of the current row, loading the next batch if needed, or `None` once the run is
+    /// exhausted.
+    fn key(&mut self) -> Result<Option<u64>, GeoArrowError> {
+        loop {
+            if let Some((chunk, keys)) = &self.chunk {
+                if self.row < chunk.len() {
+                    return Ok(Some(keys[self.row]));
+                }
+            }
+
+            let maybe_chunk = match self.reader.next() {
+                Some(maybe_chunk) => maybe_chunk,
+                None => {
+                    self.chunk = None;
+                    return Ok(None);
+                }
+            };
+            let mut arrays = maybe_chunk
+                .map_err(|err| GeoArrowError::External(err.into()))?
+                .into_arrays();
+            let key_column = arrays.pop().unwrap();
+            let key_column = key_column
+                .as_any()
+                .downcast_ref::<PrimitiveArray<u32>>()
+                .ok_or_else(|| GeoArrowError::Downcast {
+                    expected: "PrimitiveArray<u32>",
+                    found: key_column.data_type().clone(),
+                })?;
+            let keys = key_column
+                .iter()
+                .map(|key| key.map_or(u64::MAX, |key| u64::from(*key)))
+                .collect();
+            self.chunk = Some((Chunk::new(arrays), keys));
+            self.row = 0;
+        }
+    }
+}
+
+/// Where [`SortedChunks`] takes its rows from.
+enum SortedSource {
+    /// All rows fit in memory and were sorted into one chunk.
+    InMemory {
+        chunk: Chunk<Box<dyn Array>>,
+        offset: usize,
+    },
+
+    /// A k-way merge of spilled runs. `heap` holds the key of the current row of each run that
+    /// is not exhausted; ties go to the earlier run, which keeps the sort stable.
+    Merge {
+        cursors: Vec<RunCursor>,
+        heap: BinaryHeap<Reverse<(u64, usize)>>,
+        _runs: RunFiles,
+    },
+}
+
+/// The sorted rows produced by [`ExternalHilbertSorter::finish`].
+///
+/// An iterator over chunks of the original schema, without the sort key.
+pub struct SortedChunks {
+    schema: Schema,
+    geometry_column_index: usize,
+    batch_size: usize,
+    source: SortedSource,
+}
+
+impl SortedChunks {
+    /// The schema of every chunk.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// The position of the geometry column within the schema.
+    pub fn geometry_column_index(&self) -> usize {
+        self.geometry_column_index
+    }
+
+    /// Collect the remaining chunks into a [`GeoTable`].
+    pub fn into_table(self) -> Result<GeoTable, GeoArrowError> {
+        let schema = self.schema.clone();
+        let geometry_column_index = self.geometry_column_index;
+        let chunks = self.collect::<Result<Vec<_>, _>>()?;
+        GeoTable::try_new(schema, chunks, geometry_column_index)
+    }
+
+    fn next_merged(
+        cursors: &mut [RunCursor],
+        heap: &mut BinaryHeap<Reverse<(u64, usize)>>,
+        num_columns: usize,
+        batch_size: usize,
+    ) -> Result<Option<Chunk<Box<dyn Array>>>, GeoArrowError> {
+        // Batches of the runs that contribute rows to this output chunk
+        let mut sources: Vec<Chunk<Box<dyn Array>>> = vec![];
+        let mut source_of_run: Vec<Option<usize>> = vec![None; cursors.len()];
+        let mut picks = Vec::with_capacity(batch_size);
+
+        while picks.len() < batch_size {
+            let run_idx = match heap.pop() {
+                Some(Reverse((_, run_idx))) => run_idx,
+                None => break,
+            };
+            let cursor = &mut cursors[run_idx];
+            let source = match source_of_run[run_idx] {
+                Some(source) => source,
+                None => {
+                    sources.push(cursor.chunk.as_ref().unwrap().0.clone());
+                    source_of_run[run_idx] = Some(sources.len() - 1);
+                    sources.len() - 1
+                }
+            };
+            picks.push((source, cursor.row));
+
+            cursor.row += 1;
+            if cursor.row == cursor.chunk.as_ref().unwrap().0.len() {
+                // The next batch of this run becomes a new source
+                source_of_run[run_idx] = None;
+            }
+            if let Some(key) = cursor.key()? {
+                heap.push(Reverse((key, run_idx)));
+            }
+        }
+
+        if picks.is_empty() {
+            return Ok(None);
+        }
+        let sources: Vec<&Chunk<Box<dyn Array>>> = sources.iter().collect();
+        Ok(Some(gather(&sources, num_columns, &picks)))
+    }
+}
+
+impl Iterator for SortedChunks {
+    type Item = Result<Chunk<Box<dyn Array>>, GeoArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.source {
+            SortedSource::InMemory { chunk, offset } => {
+                if *offset >= chunk.len() {
+                    return None;
+                }
+                let length = self.batch_size.min(chunk.len() - *offset);
+                let arrays = chunk
+                    .arrays()
+                    .iter()
+                    .map(|array| array.sliced(*offset, length))
+                    .collect();
+                *offset += length;
+                Some(Ok(Chunk::new(arrays)))
+            }
+            SortedSource::Merge { cursors, heap, .. } => {
+                Self::next_merged(cursors, heap, self.schema.fields.len(), self.batch_size)
+                    .transpose()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArray, GeometryArrayTrait, PointArray};
+    use arrow2::array::Int32Array;
+    use geo::{coord, point};
+
+    fn table(points: &[(f64, f64)], ids: std::ops::Range<i32>) -> GeoTable {
+        let geometry: PointArray = points
+            .iter()
+            .map(|(x, y)| Some(point!(x: *x, y: *y)))
+            .collect::<Vec<_>>()
+            .into();
+        let geometry = geometry.into_arrow();
+        let schema = Schema::from(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("geometry", geometry.data_type().clone(), true),
+        ]);
+        let ids = Int32Array::from_vec(ids.collect());
+        GeoTable::new(
+            schema,
+            vec![Chunk::new(vec![ids.boxed(), geometry.boxed()])],
+            1,
+        )
+    }
+
+    fn sorted_ids(table: &GeoTable) -> Vec<i32> {
+        table
+            .chunks()
+            .iter()
+            .flat_map(|chunk| {
+                let ids = chunk.arrays()[0]
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap();
+                ids.values().to_vec()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn spilled_sort_matches_in_memory_sort() {
+        let bounds = geo::Rect::new(coord! { x: 0., y: 0. }, coord! { x: 100., y: 100. });
+        let tables: Vec<GeoTable> = (0..4)
+            .map(|i| {
+                let points: Vec<(f64, f64)> = (0..25)
+                    .map(|j| (((i * 37 + j * 53) % 100) as f64, ((j * 29) % 100) as f64))
+                    .collect();
+                table(&points, i * 25..(i + 1) * 25)
+            })
+            .collect();
+
+        let mut in_memory = ExternalHilbertSorter::new(bounds, 1000);
+        let mut external = ExternalHilbertSorter::new(bounds, 30);
+        for table in &tables {
+            in_memory.push(table).unwrap();
+            external.push(table).unwrap();
+        }
+        assert_eq!(in_memory.num_runs(), 0);
+        assert_eq!(external.num_runs(), 2);
+
+        let expected = in_memory.finish(16).unwrap().into_table().unwrap();
+        let sorted = external.finish(16).unwrap();
+        assert_eq!(sorted.schema(), tables[0].schema());
+        let sorted = sorted.into_table().unwrap();
+        assert_eq!(sorted.len(), 100);
+        assert!(sorted.chunks().iter().all(|chunk| chunk.len() <= 16));
+        assert_eq!(sorted_ids(&sorted), sorted_ids(&expected));
+
+        let keys: Vec<u32> = sorted
+            .geometry()
+            .unwrap()
+            .iter()
+            .flat_map(|geometry: &GeometryArray| hilbert_keys(geometry, &bounds).values().to_vec())
+            .collect();
+        assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn rejects_mismatched_schema() {
+        let bounds = geo::Rect::new(coord! { x: 0., y: 0. }, coord! { x: 1., y: 1. });
+        let mut sorter = ExternalHilbertSorter::new(bounds, 10);
+        sorter.push(&table(&[(0., 0.)], 0..1)).unwrap();
+
+        let other = table(&[(0., 0.)], 0..1).select(&["geometry"]).unwrap();
+        assert!(sorter.push(&other).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Affine transformations of geometry arrays.
+//!
+//! Native arrays are transformed directly on their coordinate buffers: offsets and validity are
+//! shared with the input and only the x and y buffers are rewritten. This avoids materializing a
+//! [`geo`] geometry per row.
+
+use crate::{
+    GeometryArray, LineStringArray, MixedGeometryArray, MultiLineStringArray, MultiPointArray,
+    MultiPolygonArray, PointArray, PolygonArray, WKBArray,
+};
+use arrow2::buffer::Buffer;
+use geo::{AffineTransform, Coord};
+
+/// Apply an [`AffineTransform`] to every coordinate of a geometry array.
+pub trait AffineOps {
+    /// Apply `transform` to every coordinate, returning a new array.
+    fn affine_transform(&self, transform: &AffineTransform) -> Self;
+}
+
+/// Apply `transform` to the coordinate buffers, returning new x and y buffers.
+fn transform_coords(
+    x: &Buffer<f64>,
+    y: &Buffer<f64>,
+    transform: &AffineTransform,
+) -> (Buffer<f64>, Buffer<f64>) {
+    let mut new_x = Vec::with_capacity(x.len());
+    let mut new_y = Vec::with_capacity(y.len());
+    for (x, y) in x.iter().zip(y.iter()) {
+        let coord = transform.apply(Coord { x: *x, y: *y });
+        new_x.push(coord.x);
+        new_y.push(coord.y);
+    }
+    (new_x.into(), new_y.into())
+}
+
+macro_rules! buffer_impl {
+    ($type:ty) => {
+        impl AffineOps for $type {
+            fn affine_transform(&self, transform: &AffineTransform) -> Self {
+                let (x, y) = transform_coords(self.values_x(), self.values_y(), transform);
+                self.with_coords(x, y)
+            }
+        }
+    };
+}
+
+buffer_impl!(PointArray);
+buffer_impl!(LineStringArray);
+buffer_impl!(PolygonArray);
+buffer_impl!(MultiPointArray);
+buffer_impl!(MultiLineStringArray);
+buffer_impl!(MultiPolygonArray);
+
+impl AffineOps for WKBArray {
+    fn affine_transform(&self, transform: &AffineTransform) -> Self {
+        use geo::AffineOps as _;
+
+        let output_geoms: Vec<Option<geo::Geometry>> = self
+            .iter_geo()
+            .map(|maybe_g| maybe_g.map(|geom| geom.affine_transform(transform)))
+            .collect();
+
+        output_geoms.into()
+    }
+}
+
+impl AffineOps for MixedGeometryArray {
+    fn affine_transform(&self, transform: &AffineTransform) -> Self {
+        self.map_children(
+            |arr| arr.affine_transform(transform),
+            |arr| arr.affine_transform(transform),
+            |arr| arr.affine_transform(transform),
+            |arr| arr.affine_transform(transform),
+            |arr| arr.affine_transform(transform),
+            |arr| arr.affine_transform(transform),
+        )
+    }
+}
+
+impl AffineOps for GeometryArray {
+    fn affine_transform(&self, transform: &AffineTransform) -> Self {
+        match self {
+            GeometryArray::Point(arr) => GeometryArray::Point(arr.affine_transform(transform)),
+            GeometryArray::LineString(arr) => {
+                GeometryArray::LineString(arr.affine_transform(transform))
+            }
+            GeometryArray::Polygon(arr) => GeometryArray::Polygon(arr.affine_transform(transform)),
+            GeometryArray::MultiPoint(arr) => {
+                GeometryArray::MultiPoint(arr.affine_transform(transform))
+            }
+            GeometryArray::MultiLineString(arr) => {
+                GeometryArray::MultiLineString(arr.affine_transform(transform))
+            }
+            GeometryArray::MultiPolygon(arr) => {
+                GeometryArray::MultiPolygon(arr.affine_transform(transform))
+            }
+            GeometryArray::WKB(arr) => GeometryArray::WKB(arr.affine_transform(transform)),
+            // A rotated or skewed box is no longer axis-aligned
+            GeometryArray::Rect(arr) => {
+                let polygons: PolygonArray = arr.clone().into();
+                GeometryArray::Polygon(polygons.affine_transform(transform))
+            }
+            GeometryArray::Mixed(arr) => {
+                GeometryArray::Mixed(Box::new(arr.affine_transform(transform)))
+            }
+        }
+    }
+}
+
+/// Translate every geometry by `x_offset` and `y_offset`.
+pub fn translate<A: AffineOps>(array: &A, x_offset: f64, y_offset: f64) -> A {
+    array.affine_transform(&AffineTransform::translate(x_offset, y_offset))
+}
+
+/// Scale every geometry by `x_factor` and `y_factor` relative to `origin`.
+pub fn scale<A: AffineOps>(array: &A, x_factor: f64, y_factor: f64, origin: Coord) -> A {
+    array.affine_transform(&AffineTransform::scale(x_factor, y_factor, origin))
+}
+
+/// Rotate every geometry by `degrees` counter-clockwise around `origin`.
+pub fn rotate<A: AffineOps>(array: &A, degrees: f64, origin: Coord) -> A {
+    array.affine_transform(&AffineTransform::rotate(degrees, origin))
+}
+
+/// Skew every geometry by shearing angles `x_degrees` and `y_degrees` relative to `origin`.
+pub fn skew<A: AffineOps>(array: &A, x_degrees: f64, y_degrees: f64, origin: Coord) -> A {
+    array.affine_transform(&AffineTransform::skew(x_degrees, y_degrees, origin))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::GeometryArrayTrait;
+    use geo::{coord, line_string, point, polygon};
+
+    #[test]
+    fn translate_points() {
+        let arr: PointArray = vec![Some(point!(x: 1., y: 2.)), None].into();
+        let result = translate(&arr, 10., -1.);
+        assert_eq!(result.get_as_geo(0), Some(point!(x: 11., y: 1.)));
+        assert!(result.is_null(1));
+    }
+
+    #[test]
+    fn scale_and_rotate_linestrings() {
+        let arr: LineStringArray = vec![line_string![(x: 1., y: 0.), (x: 2., y: 0.)]].into();
+
+        let scaled = scale(&arr, 2., 3., coord! { x: 0., y: 0. });
+        assert_eq!(
+            scaled.value_as_geo(0),
+            line_string![(x: 2., y: 0.), (x: 4., y: 0.)]
+        );
+
+        let rotated = rotate(&arr, 90., coord! { x: 0., y: 0. });
+        let coords: Vec<_> = rotated.value_as_geo(0).0;
+        assert!((coords[1].x - 0.).abs() < 1e-12);
+        assert!((coords[1].y - 2.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn matches_geo() {
+        use geo::AffineOps as _;
+
+        let polygon = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 1.), (x: 0., y: 1.)];
+        let arr: PolygonArray = vec![polygon.clone()].into();
+        let transform = AffineTransform::skew(20., 10., coord! { x: 1., y: 0.5 });
+
+        let expected = polygon.affine_transform(&transform);
+        assert_eq!(
+            skew(&arr, 20., 10., coord! { x: 1., y: 0.5 }).value_as_geo(0),
+            expected
+        );
+
+        let wkb: WKBArray = vec![Some(geo::Geometry::Polygon(polygon))].into();
+        assert_eq!(
+            wkb.affine_transform(&transform).value_as_geo(0),
+            geo::Geometry::Polygon(expected)
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{GeometryArrayTrait, PointArray};
+use arrow2::array::{MutablePrimitiveArray, PrimitiveArray};
+use rstar::primitives::GeomWithData;
+use rstar::RTree;
+
+/// Allocate each demand point to its nearest facility.
+///
+/// Returns, for each demand point, the index of the nearest facility within `facilities` and the
+/// Euclidean distance to it. Indices are `u64`, so any number of facilities can be indexed. Null
+/// demand points, and every demand point when there are no valid facilities, produce null in both
+/// outputs. Null facilities are never chosen.
+///
+/// Facilities are bulk-loaded into a spatial index once, so each lookup is `O(log F)`.
+pub fn allocate_to_nearest(
+    demand: &PointArray,
+    facilities: &PointArray,
+) -> (PrimitiveArray<u64>, PrimitiveArray<f64>) {
+    let facility_points: Vec<GeomWithData<[f64; 2], u64>> = (0..facilities.len())
+        .filter(|i| facilities.is_valid(*i))
+        .map(|i| {
+            let coord = [facilities.values_x()[i], facilities.values_y()[i]];
+            GeomWithData::new(coord, i as u64)
+        })
+        .collect();
+    let tree = RTree::bulk_load(facility_points);
+
+    let mut indices = MutablePrimitiveArray::<u64>::with_capacity(demand.len());
+    let mut distances = MutablePrimitiveArray::<f64>::with_capacity(demand.len());
+
+    for i in 0..demand.len() {
+        if demand.is_null(i) {
+            indices.push(None);
+            distances.push(None);
+            continue;
+        }
+
+        let x = demand.values_x()[i];
+        let y = demand.values_y()[i];
+        match tree.nearest_neighbor(&[x, y]) {
+            Some(facility) => {
+                let [fx, fy] = *facility.geom();
+                indices.push(Some(facility.data));
+                distances.push(Some((fx - x).hypot(fy - y)));
+            }
+            None => {
+                indices.push(None);
+                distances.push(None);
+            }
+        }
+    }
+
+    (indices.into(), distances.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::Array;
+    use geo::point;
+
+    #[test]
+    fn allocates_to_nearest_facility() {
+        let demand: PointArray =
+            vec![Some(point!(x: 0., y: 0.)), None, Some(point!(x: 9., y: 9.))].into();
+        let facilities: PointArray = vec![
+            Some(point!(x: 10., y: 10.)),
+            Some(point!(x: 0., y: 1.)),
+            None,
+        ]
+        .into();
+
+        let (indices, distances) = allocate_to_nearest(&demand, &facilities);
+        assert_eq!(indices.value(0), 1);
+        assert_eq!(distances.value(0), 1.);
+        assert!(indices.is_null(1));
+        assert!(distances.is_null(1));
+        assert_eq!(indices.value(2), 0);
+        assert_eq!(distances.value(2), 2_f64.sqrt());
+    }
+
+    #[test]
+    fn no_facilities() {
+        let demand: PointArray = vec![point!(x: 0., y: 0.)].into();
+        let facilities: PointArray = Vec::<geo::Point>::new().into();
+
+        let (indices, distances) = allocate_to_nearest(&demand, &facilities);
+        assert_eq!(indices.null_count(), 1);
+        assert_eq!(distances.null_count(), 1);
+    }
+}
@@ -0,0 +1,135 @@
+//! Run user closures over each geometry of an array.
+//!
+//! These are an escape hatch for operations without a dedicated kernel. Each geometry is
+//! converted to a [`geo::Geometry`] and passed to the closure, with rows processed in parallel.
+
+use crate::coerce::from_geo;
+use crate::parallel::parallel_map;
+use crate::{GeometryArray, GeometryArrayTrait, GeometryArrayType};
+use arrow2::array::BooleanArray;
+
+/// Evaluate `predicate` on each geometry of `array`.
+///
+/// The output is null where the geometry is null.
+pub fn predicate_mask<F>(array: &GeometryArray, predicate: F) -> BooleanArray
+where
+    F: Fn(&geo::Geometry) -> bool + Sync,
+{
+    let rows: Vec<usize> = (0..array.len()).collect();
+    let values = parallel_map(&rows, |i| array.get_as_geo(*i).map(|geom| predicate(&geom)));
+    BooleanArray::from(values)
+}
+
+/// Keep the geometries of `array` for which `predicate` returns true.
+///
+/// Null geometries are dropped. The output has the type of `array`.
+pub fn filter_by<F>(array: &GeometryArray, predicate: F) -> GeometryArray
+where
+    F: Fn(&geo::Geometry) -> bool + Sync,
+{
+    let rows: Vec<usize> = (0..array.len()).collect();
+    let kept: Vec<Option<geo::Geometry>> = parallel_map(&rows, |i| {
+        array.get_as_geo(*i).filter(|geom| predicate(geom))
+    })
+    .into_iter()
+    .filter(Option::is_some)
+    .collect();
+
+    match array.geometry_type() {
+        GeometryArrayType::WKB => GeometryArray::WKB(kept.into()),
+        // Geometries read from a mixed array are never collections
+        GeometryArrayType::Mixed => GeometryArray::Mixed(Box::new(kept.try_into().unwrap())),
+        kind => from_geo(kept, kind),
+    }
+}
+
+/// Replace each geometry of `array` with the output of `f`.
+///
+/// `f` returning `None` produces a null geometry; null geometries are not passed to `f` and stay
+/// null. The output is the narrowest array able to hold every returned geometry: a native array
+/// when they all have the same type, WKB otherwise.
+pub fn map_geometries<F>(array: &GeometryArray, f: F) -> GeometryArray
+where
+    F: Fn(geo::Geometry) -> Option<geo::Geometry> + Sync,
+{
+    let rows: Vec<usize> = (0..array.len()).collect();
+    let output_geoms = parallel_map(&rows, |i| array.get_as_geo(*i).and_then(&f));
+    from_geo(output_geoms, array.geometry_type())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{PointArray, WKBArray};
+    use geo::{line_string, point};
+
+    fn points() -> GeometryArray {
+        let arr: PointArray = vec![
+            Some(point!(x: 0., y: 0.)),
+            None,
+            Some(point!(x: 5., y: 5.)),
+            Some(point!(x: 1., y: 8.)),
+        ]
+        .into();
+        GeometryArray::Point(arr)
+    }
+
+    fn x(geom: &geo::Geometry) -> f64 {
+        match geom {
+            geo::Geometry::Point(p) => p.x(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn filter() {
+        let mask = predicate_mask(&points(), |geom| x(geom) > 0.);
+        assert_eq!(
+            mask,
+            BooleanArray::from([Some(false), None, Some(true), Some(true)])
+        );
+
+        match filter_by(&points(), |geom| x(geom) > 0.) {
+            GeometryArray::Point(arr) => {
+                assert_eq!(arr.len(), 2);
+                assert_eq!(arr.value_as_geo(1), point!(x: 1., y: 8.));
+            }
+            _ => panic!("expected a point array"),
+        }
+
+        // WKB stays WKB, even when every geometry has the same type
+        let wkb: WKBArray = vec![Some(geo::Geometry::Point(point!(x: 1., y: 1.)))].into();
+        let output = filter_by(&GeometryArray::WKB(wkb), |_| true);
+        assert!(matches!(output, GeometryArray::WKB(arr) if arr.len() == 1));
+    }
+
+    #[test]
+    fn map() {
+        let output = map_geometries(&points(), |geom| {
+            (x(&geom) > 0.).then(|| {
+                let p: geo::Point = geom.try_into().unwrap();
+                geo::Geometry::LineString(geo::LineString::new(vec![p.0, geo::Coord::zero()]))
+            })
+        });
+        match output {
+            GeometryArray::LineString(arr) => {
+                assert!(arr.is_null(0) && arr.is_null(1));
+                assert_eq!(
+                    arr.value_as_geo(2),
+                    line_string![(x: 5., y: 5.), (x: 0., y: 0.)]
+                );
+            }
+            _ => panic!("expected a line string array"),
+        }
+
+        // Rows of different types can only be held as WKB
+        let output = map_geometries(&points(), |geom| match x(&geom) > 2. {
+            true => Some(geom),
+            false => Some(geo::Geometry::LineString(
+                line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+            )),
+        });
+        assert!(matches!(output, GeometryArray::WKB(_)));
+        assert!(output.get_as_geo(1).is_none());
+    }
+}
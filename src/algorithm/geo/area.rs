@@ -0,0 +1,272 @@
+use crate::geo_traits::{LineStringTrait, MultiPolygonTrait, PointTrait, PolygonTrait};
+use crate::parallel::map_rows;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use arrow2::array::PrimitiveArray;
+use arrow2::bitmap::Bitmap;
+use arrow2::buffer::Buffer;
+use arrow2::datatypes::DataType;
+
+/// Signed and unsigned planar area of a geometry array.
+///
+/// Rows are computed in parallel with the `rayon` feature.
+///
+/// Polygonal areas are computed directly from the coordinate and offset buffers through the
+/// [`geo_traits`](crate::geo_traits), without materializing intermediate geometries. Results
+/// follow [`geo::Area`]: the sign of a polygon's area is that of its exterior ring, and
+/// non-polygonal geometries have zero area. Null geometries produce null areas.
+pub trait Area {
+    /// Signed planar area of each geometry.
+    fn signed_area(&self) -> PrimitiveArray<f64>;
+
+    /// Unsigned planar area of each geometry.
+    fn unsigned_area(&self) -> PrimitiveArray<f64>;
+}
+
+/// Twice the signed area of a ring.
+///
+/// Unclosed rings are treated as implicitly closed. Coordinates are shifted by the first
+/// coordinate to reduce floating point error.
+fn twice_signed_ring_area<'a>(ring: &impl LineStringTrait<'a>) -> f64 {
+    let num_points = ring.num_points();
+    if num_points < 3 {
+        return 0.0;
+    }
+
+    let (shift_x, shift_y) = ring.point(0).unwrap().x_y();
+    let mut total = 0.0;
+    for i in 0..num_points {
+        let j = if i + 1 == num_points { 0 } else { i + 1 };
+        let (x1, y1) = ring.point(i).unwrap().x_y();
+        let (x2, y2) = ring.point(j).unwrap().x_y();
+        let (x1, y1) = (x1 - shift_x, y1 - shift_y);
+        let (x2, y2) = (x2 - shift_x, y2 - shift_y);
+        total += x1 * y2 - x2 * y1;
+    }
+    total
+}
+
+/// Signed planar area of a polygon, read directly from any [`PolygonTrait`] implementation.
+///
+/// As in [`geo::Area`], the sign is that of the exterior ring, and the areas of interior rings
+/// are subtracted whatever their orientation.
+pub fn signed_area_polygon<'a>(polygon: &impl PolygonTrait<'a>) -> f64 {
+    if polygon.exterior().num_points() == 0 {
+        return 0.0;
+    }
+
+    let exterior = twice_signed_ring_area(&polygon.exterior()) / 2.0;
+    let mut area = exterior.abs();
+    for ring_idx in 0..polygon.num_interiors() {
+        area -= (twice_signed_ring_area(&polygon.interior(ring_idx).unwrap()) / 2.0).abs();
+    }
+
+    if exterior < 0.0 {
+        -area
+    } else {
+        area
+    }
+}
+
+/// Signed planar area of a multi polygon: the sum of the signed areas of its polygons.
+pub fn signed_area_multipolygon<'a>(multi_polygon: &impl MultiPolygonTrait<'a>) -> f64 {
+    (0..multi_polygon.num_polygons())
+        .map(|i| signed_area_polygon(&multi_polygon.polygon(i).unwrap()))
+        .sum()
+}
+
+/// Build a float array of `len` values using the validity of the source array.
+pub(super) fn from_fn(
+    len: usize,
+    validity: Option<&Bitmap>,
+    f: impl Fn(usize) -> f64 + Sync + Send,
+) -> PrimitiveArray<f64> {
+    let values: Buffer<f64> = map_rows(len, f).into();
+    PrimitiveArray::new(DataType::Float64, values, validity.cloned())
+}
+
+macro_rules! zero_impl {
+    ($type:ty) => {
+        impl Area for $type {
+            fn signed_area(&self) -> PrimitiveArray<f64> {
+                from_fn(self.len(), self.validity(), |_| 0.0)
+            }
+
+            fn unsigned_area(&self) -> PrimitiveArray<f64> {
+                self.signed_area()
+            }
+        }
+    };
+}
+
+zero_impl!(PointArray);
+zero_impl!(LineStringArray);
+zero_impl!(MultiPointArray);
+zero_impl!(MultiLineStringArray);
+
+impl Area for PolygonArray {
+    fn signed_area(&self) -> PrimitiveArray<f64> {
+        from_fn(self.len(), self.validity(), |i| {
+            signed_area_polygon(&self.value(i))
+        })
+    }
+
+    fn unsigned_area(&self) -> PrimitiveArray<f64> {
+        from_fn(self.len(), self.validity(), |i| {
+            signed_area_polygon(&self.value(i)).abs()
+        })
+    }
+}
+
+impl Area for MultiPolygonArray {
+    fn signed_area(&self) -> PrimitiveArray<f64> {
+        from_fn(self.len(), self.validity(), |i| {
+            signed_area_multipolygon(&self.value(i))
+        })
+    }
+
+    fn unsigned_area(&self) -> PrimitiveArray<f64> {
+        from_fn(self.len(), self.validity(), |i| {
+            let multi_polygon = self.value(i);
+            (0..multi_polygon.num_polygons())
+                .map(|j| signed_area_polygon(&multi_polygon.polygon(j).unwrap()).abs())
+                .sum()
+        })
+    }
+}
+
+impl Area for RectArray {
+    fn signed_area(&self) -> PrimitiveArray<f64> {
+        from_fn(self.len(), self.validity(), |i| {
+            (self.values_maxx()[i] - self.values_minx()[i])
+                * (self.values_maxy()[i] - self.values_miny()[i])
+        })
+    }
+
+    fn unsigned_area(&self) -> PrimitiveArray<f64> {
+        self.signed_area()
+    }
+}
+
+macro_rules! geo_impl {
+    ($type:ty) => {
+        impl Area for $type {
+            fn signed_area(&self) -> PrimitiveArray<f64> {
+                use geo::Area as _;
+
+                map_rows(self.len(), |i| self.get_as_geo(i).map(|g| g.signed_area())).into()
+            }
+
+            fn unsigned_area(&self) -> PrimitiveArray<f64> {
+                use geo::Area as _;
+
+                map_rows(self.len(), |i| {
+                    self.get_as_geo(i).map(|g| g.unsigned_area())
+                })
+                .into()
+            }
+        }
+    };
+}
+
+geo_impl!(WKBArray);
+geo_impl!(MixedGeometryArray);
+
+impl Area for GeometryArray {
+    fn signed_area(&self) -> PrimitiveArray<f64> {
+        match self {
+            GeometryArray::Point(arr) => arr.signed_area(),
+            GeometryArray::LineString(arr) => arr.signed_area(),
+            GeometryArray::Polygon(arr) => arr.signed_area(),
+            GeometryArray::MultiPoint(arr) => arr.signed_area(),
+            GeometryArray::MultiLineString(arr) => arr.signed_area(),
+            GeometryArray::MultiPolygon(arr) => arr.signed_area(),
+            GeometryArray::WKB(arr) => arr.signed_area(),
+            GeometryArray::Rect(arr) => arr.signed_area(),
+            GeometryArray::Mixed(arr) => arr.signed_area(),
+        }
+    }
+
+    fn unsigned_area(&self) -> PrimitiveArray<f64> {
+        match self {
+            GeometryArray::Point(arr) => arr.unsigned_area(),
+            GeometryArray::LineString(arr) => arr.unsigned_area(),
+            GeometryArray::Polygon(arr) => arr.unsigned_area(),
+            GeometryArray::MultiPoint(arr) => arr.unsigned_area(),
+            GeometryArray::MultiLineString(arr) => arr.unsigned_area(),
+            GeometryArray::MultiPolygon(arr) => arr.unsigned_area(),
+            GeometryArray::WKB(arr) => arr.unsigned_area(),
+            GeometryArray::Rect(arr) => arr.unsigned_area(),
+            GeometryArray::Mixed(arr) => arr.unsigned_area(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::Array;
+    use geo::{polygon, MultiPolygon, Polygon};
+
+    fn square_with_hole() -> Polygon {
+        polygon!(
+            exterior: [
+                (x: 0., y: 0.),
+                (x: 10., y: 0.),
+                (x: 10., y: 10.),
+                (x: 0., y: 10.),
+                (x: 0., y: 0.),
+            ],
+            interiors: [
+                [
+                    (x: 1., y: 1.),
+                    (x: 1., y: 2.),
+                    (x: 2., y: 2.),
+                    (x: 2., y: 1.),
+                    (x: 1., y: 1.),
+                ],
+            ],
+        )
+    }
+
+    fn clockwise_triangle() -> Polygon {
+        polygon![(x: 0., y: 0.), (x: 0., y: 4.), (x: 3., y: 0.), (x: 0., y: 0.)]
+    }
+
+    #[test]
+    fn polygon_area() {
+        let arr: PolygonArray =
+            vec![Some(square_with_hole()), None, Some(clockwise_triangle())].into();
+
+        let signed = arr.signed_area();
+        assert_eq!(signed.value(0), 99.);
+        assert!(signed.is_null(1));
+        assert_eq!(signed.value(2), -6.);
+
+        let unsigned = arr.unsigned_area();
+        assert_eq!(unsigned.value(2), 6.);
+    }
+
+    #[test]
+    fn multi_polygon_area_matches_geo() {
+        use geo::Area as _;
+
+        let geom = MultiPolygon::new(vec![square_with_hole(), clockwise_triangle()]);
+        let arr: MultiPolygonArray = vec![geom.clone()].into();
+
+        assert_eq!(arr.signed_area().value(0), geom.signed_area());
+        assert_eq!(arr.unsigned_area().value(0), geom.unsigned_area());
+    }
+
+    #[test]
+    fn enum_dispatch() {
+        let arr: PolygonArray = vec![clockwise_triangle()].into();
+        let arr = GeometryArray::Polygon(arr);
+        assert_eq!(arr.unsigned_area().value(0), 6.);
+
+        let points: PointArray = vec![geo::point!(x: 1., y: 1.)].into();
+        assert_eq!(GeometryArray::Point(points).signed_area().value(0), 0.);
+    }
+}
@@ -0,0 +1,168 @@
+//! Concave hulls, for the footprints of point clouds and other geometries.
+//!
+//! A convex hull bridges every inlet of a shape, so the footprint of an L-shaped building or a
+//! river bend covers a lot of empty space. These kernels wrap the coordinates of each geometry
+//! more tightly, with [`geo`]'s concave hull algorithms. To outline groups of points, collect
+//! them into multi points first with
+//! [`CollectByGroup`](crate::algorithm::explode::CollectByGroup).
+//!
+//! Null geometries produce null hulls, and rows are computed in parallel with the `rayon` feature.
+
+use crate::parallel::map_rows;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use geo::{ConcaveHull as _, CoordsIter, KNearestConcaveHull as _};
+
+/// The concave hull of the coordinates of each geometry.
+pub trait ConcaveHull {
+    /// The concave hull of each geometry, digging hull edges into the geometry after the
+    /// approach of [concaveman](https://github.com/mapbox/concaveman).
+    ///
+    /// An edge is dug into while it is longer than `concavity` times its distance to the nearest
+    /// inner coordinate, so smaller values give tighter hulls and large values give the convex
+    /// hull. `2.0` is a reasonable start.
+    fn concave_hull(&self, concavity: f64) -> PolygonArray;
+
+    /// The concave hull of each geometry, by the k-nearest neighbors algorithm of Moreira and
+    /// Santos.
+    ///
+    /// The hull is walked from coordinate to coordinate, choosing among the `k` nearest ones:
+    /// smaller values give tighter hulls, and values below 3 are raised to 3. This is slower than
+    /// [`concave_hull`](Self::concave_hull), but needs no tuning.
+    fn k_nearest_concave_hull(&self, k: u32) -> PolygonArray;
+}
+
+/// The concave hull of the coordinates of `geom`.
+fn geometry_concave_hull(geom: geo::Geometry, concavity: f64) -> geo::Polygon {
+    match geom {
+        geo::Geometry::LineString(geom) => geom.concave_hull(concavity),
+        geo::Geometry::Polygon(geom) => geom.concave_hull(concavity),
+        geo::Geometry::MultiPoint(geom) => geom.concave_hull(concavity),
+        geo::Geometry::MultiLineString(geom) => geom.concave_hull(concavity),
+        geo::Geometry::MultiPolygon(geom) => geom.concave_hull(concavity),
+        geom => {
+            geo::MultiPoint::from(geom.coords_iter().collect::<Vec<_>>()).concave_hull(concavity)
+        }
+    }
+}
+
+macro_rules! concave_hull_impl {
+    ($type:ty) => {
+        impl ConcaveHull for $type {
+            fn concave_hull(&self, concavity: f64) -> PolygonArray {
+                let hulls = map_rows(self.len(), |i| {
+                    self.get_as_geo(i)
+                        .map(|geom| geometry_concave_hull(geom.into(), concavity))
+                });
+                PolygonArray::from(hulls).with_crs(self.crs())
+            }
+
+            fn k_nearest_concave_hull(&self, k: u32) -> PolygonArray {
+                let hulls = map_rows(self.len(), |i| {
+                    self.get_as_geo(i).map(|geom| {
+                        let geom: geo::Geometry = geom.into();
+                        geom.coords_iter()
+                            .collect::<Vec<_>>()
+                            .k_nearest_concave_hull(k)
+                    })
+                });
+                PolygonArray::from(hulls).with_crs(self.crs())
+            }
+        }
+    };
+}
+
+concave_hull_impl!(PointArray);
+concave_hull_impl!(LineStringArray);
+concave_hull_impl!(PolygonArray);
+concave_hull_impl!(MultiPointArray);
+concave_hull_impl!(MultiLineStringArray);
+concave_hull_impl!(MultiPolygonArray);
+concave_hull_impl!(WKBArray);
+concave_hull_impl!(RectArray);
+concave_hull_impl!(MixedGeometryArray);
+
+impl ConcaveHull for GeometryArray {
+    fn concave_hull(&self, concavity: f64) -> PolygonArray {
+        match self {
+            GeometryArray::Point(arr) => arr.concave_hull(concavity),
+            GeometryArray::LineString(arr) => arr.concave_hull(concavity),
+            GeometryArray::Polygon(arr) => arr.concave_hull(concavity),
+            GeometryArray::MultiPoint(arr) => arr.concave_hull(concavity),
+            GeometryArray::MultiLineString(arr) => arr.concave_hull(concavity),
+            GeometryArray::MultiPolygon(arr) => arr.concave_hull(concavity),
+            GeometryArray::WKB(arr) => arr.concave_hull(concavity),
+            GeometryArray::Rect(arr) => arr.concave_hull(concavity),
+            GeometryArray::Mixed(arr) => arr.concave_hull(concavity),
+        }
+    }
+
+    fn k_nearest_concave_hull(&self, k: u32) -> PolygonArray {
+        match self {
+            GeometryArray::Point(arr) => arr.k_nearest_concave_hull(k),
+            GeometryArray::LineString(arr) => arr.k_nearest_concave_hull(k),
+            GeometryArray::Polygon(arr) => arr.k_nearest_concave_hull(k),
+            GeometryArray::MultiPoint(arr) => arr.k_nearest_concave_hull(k),
+            GeometryArray::MultiLineString(arr) => arr.k_nearest_concave_hull(k),
+            GeometryArray::MultiPolygon(arr) => arr.k_nearest_concave_hull(k),
+            GeometryArray::WKB(arr) => arr.k_nearest_concave_hull(k),
+            GeometryArray::Rect(arr) => arr.k_nearest_concave_hull(k),
+            GeometryArray::Mixed(arr) => arr.k_nearest_concave_hull(k),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::explode::CollectByGroup;
+    use arrow2::array::PrimitiveArray;
+    use geo::{Area, ConvexHull, Intersects, MultiPoint, Point};
+
+    /// An L-shaped cloud of points on a grid.
+    fn l_shape() -> Vec<Point> {
+        let mut points = vec![];
+        for x in 0..10 {
+            for y in 0..10 {
+                if x < 3 || y < 3 {
+                    points.push(Point::new(x as f64, y as f64));
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn footprints() {
+        let cloud = MultiPoint::new(l_shape());
+        let arr: MultiPointArray = vec![Some(cloud.clone()), None].into();
+        let arr = arr.with_crs(Some("EPSG:3857"));
+        let convex_area = cloud.convex_hull().unsigned_area();
+
+        for hulls in [arr.concave_hull(1.0), arr.k_nearest_concave_hull(3)] {
+            assert_eq!(hulls.crs(), Some("EPSG:3857"));
+            assert!(hulls.is_null(1));
+            let hull = hulls.value_as_geo(0);
+            assert!(hull.unsigned_area() < convex_area * 0.8);
+            // The hull surrounds every point of the cloud
+            assert!(cloud.iter().all(|point| hull.intersects(point)));
+        }
+    }
+
+    #[test]
+    fn grouped_points() {
+        let points: PointArray = l_shape().into();
+        let groups = PrimitiveArray::from_vec(
+            l_shape()
+                .iter()
+                .map(|point| i64::from(point.x() >= 5.0))
+                .collect(),
+        );
+        let (multi_points, keys) = points.collect_by_group(&groups).unwrap();
+        let hulls = GeometryArray::MultiPoint(multi_points).concave_hull(2.0);
+        assert_eq!(hulls.len(), 2);
+        assert_eq!(keys.values().as_slice(), &[0, 1]);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use geo::algorithm::convex_hull::ConvexHull as GeoConvexHull;
+use geo::Polygon;
+
+/// Compute the convex hull of each geometry in an array.
+///
+/// Null geometries produce null hulls. The output has no CRS, since it cannot be read from a
+/// generic array; [`ConvexHull`] keeps the CRS of its input.
+pub fn convex_hull<'a, A>(array: &'a A) -> PolygonArray
+where
+    A: GeometryArrayTrait<'a>,
+    A::ScalarGeo: for<'b> GeoConvexHull<'b, f64, Scalar = f64>,
+{
+    let output_geoms: Vec<Option<Polygon>> = (0..array.len())
+        .map(|i| array.get_as_geo(i).map(|geom| geom.convex_hull()))
+        .collect();
+
+    output_geoms.into()
+}
+
+/// Returns the convex hull of each geometry in the array.
+pub trait ConvexHull {
+    fn convex_hull(&self) -> PolygonArray;
+}
+
+macro_rules! convex_hull_impl {
+    ($type:ty) => {
+        impl ConvexHull for $type {
+            fn convex_hull(&self) -> PolygonArray {
+                convex_hull(self).with_crs(self.crs())
+            }
+        }
+    };
+}
+
+convex_hull_impl!(PointArray);
+convex_hull_impl!(LineStringArray);
+convex_hull_impl!(PolygonArray);
+convex_hull_impl!(MultiPointArray);
+convex_hull_impl!(MultiLineStringArray);
+convex_hull_impl!(MultiPolygonArray);
+convex_hull_impl!(WKBArray);
+convex_hull_impl!(RectArray);
+convex_hull_impl!(MixedGeometryArray);
+
+impl ConvexHull for GeometryArray {
+    fn convex_hull(&self) -> PolygonArray {
+        match self {
+            GeometryArray::Point(arr) => arr.convex_hull(),
+            GeometryArray::LineString(arr) => arr.convex_hull(),
+            GeometryArray::Polygon(arr) => arr.convex_hull(),
+            GeometryArray::MultiPoint(arr) => arr.convex_hull(),
+            GeometryArray::MultiLineString(arr) => arr.convex_hull(),
+            GeometryArray::MultiPolygon(arr) => arr.convex_hull(),
+            GeometryArray::WKB(arr) => arr.convex_hull(),
+            GeometryArray::Rect(arr) => arr.convex_hull(),
+            GeometryArray::Mixed(arr) => arr.convex_hull(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, polygon, MultiPoint, Point};
+
+    fn multi_point() -> MultiPoint {
+        vec![
+            Point::new(0., 0.),
+            Point::new(4., 0.),
+            Point::new(2., 1.),
+            Point::new(4., 4.),
+            Point::new(0., 4.),
+        ]
+        .into()
+    }
+
+    fn expected() -> Polygon {
+        polygon![(x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.), (x: 0., y: 0.), (x: 4., y: 0.)]
+    }
+
+    #[test]
+    fn convex_hull_multi_point() {
+        let arr: MultiPointArray = vec![Some(multi_point()), None].into();
+        let result = convex_hull(&arr);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get_as_geo(0), Some(expected()));
+        assert!(result.get_as_geo(1).is_none());
+    }
+
+    #[test]
+    fn convex_hull_enum_dispatch() {
+        let arr: MultiPointArray = vec![multi_point()].into();
+        let result = GeometryArray::MultiPoint(arr).convex_hull();
+        assert_eq!(result.get_as_geo(0), Some(expected()));
+
+        let arr: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 1., y: 1.)]].into();
+        let result = GeometryArray::LineString(arr).convex_hull();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn convex_hull_keeps_crs() {
+        let arr: MultiPointArray = vec![multi_point()].into();
+        let arr = GeometryArray::MultiPoint(arr).with_crs(Some("EPSG:3857"));
+        assert_eq!(arr.convex_hull().crs(), Some("EPSG:3857"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Distances between point arrays, computed directly from the coordinate buffers.
+
+use crate::crs::check_crs;
+use crate::error::GeoArrowError;
+use crate::validity::combine_validities;
+use crate::{GeometryArrayTrait, PointArray};
+use arrow2::array::PrimitiveArray;
+use arrow2::datatypes::DataType;
+use geo::{EuclideanDistance, HaversineDistance};
+
+fn check_same_length(left: &PointArray, right: &PointArray) -> Result<(), GeoArrowError> {
+    if left.len() != right.len() {
+        return Err(GeoArrowError::General(
+            "both arrays must have the same length".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn zip_points(
+    left: &PointArray,
+    right: &PointArray,
+    f: impl Fn(geo::Point, geo::Point) -> f64,
+) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    check_same_length(left, right)?;
+    check_crs(left.crs(), right.crs())?;
+
+    let values: Vec<f64> = (0..left.len())
+        .map(|i| {
+            let left_point = geo::Point::new(left.values_x()[i], left.values_y()[i]);
+            let right_point = geo::Point::new(right.values_x()[i], right.values_y()[i]);
+            f(left_point, right_point)
+        })
+        .collect();
+    let validity = combine_validities(left.validity(), right.validity());
+    Ok(PrimitiveArray::new(
+        DataType::Float64,
+        values.into(),
+        validity,
+    ))
+}
+
+fn broadcast_points(array: &PointArray, f: impl Fn(geo::Point) -> f64) -> PrimitiveArray<f64> {
+    let values: Vec<f64> = (0..array.len())
+        .map(|i| f(geo::Point::new(array.values_x()[i], array.values_y()[i])))
+        .collect();
+    PrimitiveArray::new(DataType::Float64, values.into(), array.validity().cloned())
+}
+
+/// Planar distance between each pair of points at the same index.
+///
+/// The output is null where either input is null. Errors if the arrays differ in length or CRS.
+pub fn euclidean_distance(
+    left: &PointArray,
+    right: &PointArray,
+) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    zip_points(left, right, |left_point, right_point| {
+        left_point.euclidean_distance(&right_point)
+    })
+}
+
+/// Planar distance from each point to a single geometry.
+pub fn euclidean_distance_to<G>(array: &PointArray, other: &G) -> PrimitiveArray<f64>
+where
+    geo::Point: EuclideanDistance<f64, G>,
+{
+    broadcast_points(array, |point| point.euclidean_distance(other))
+}
+
+/// Great-circle distance in meters between each pair of longitude/latitude points at the same
+/// index, using the haversine formula.
+///
+/// The output is null where either input is null. Errors if the arrays differ in length or CRS.
+pub fn haversine_distance(
+    left: &PointArray,
+    right: &PointArray,
+) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    zip_points(left, right, |left_point, right_point| {
+        left_point.haversine_distance(&right_point)
+    })
+}
+
+/// Great-circle distance in meters from each longitude/latitude point to a single point.
+pub fn haversine_distance_to(array: &PointArray, other: &geo::Point) -> PrimitiveArray<f64> {
+    broadcast_points(array, |point| point.haversine_distance(other))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::Array;
+    use geo::{line_string, point};
+
+    #[test]
+    fn euclidean_elementwise() {
+        let left: PointArray = vec![Some(point!(x: 0., y: 0.)), Some(point!(x: 1., y: 1.))].into();
+        let right: PointArray = vec![Some(point!(x: 3., y: 4.)), None].into();
+
+        let result = euclidean_distance(&left, &right).unwrap();
+        assert_eq!(result.value(0), 5.);
+        assert!(result.is_null(1));
+
+        let short: PointArray = vec![point!(x: 0., y: 0.)].into();
+        assert!(euclidean_distance(&left, &short).is_err());
+    }
+
+    #[test]
+    fn euclidean_broadcast() {
+        let points: PointArray = vec![point!(x: 0., y: 1.), point!(x: 5., y: -2.)].into();
+        let line = line_string![(x: 0., y: 0.), (x: 4., y: 0.)];
+
+        let result = euclidean_distance_to(&points, &line);
+        assert_eq!(result.value(0), 1.);
+        assert_eq!(result.value(1), 5_f64.sqrt());
+    }
+
+    #[test]
+    fn haversine() {
+        let left: PointArray = vec![point!(x: 0., y: 0.)].into();
+        let right: PointArray = vec![point!(x: 1., y: 0.)].into();
+
+        let result = haversine_distance(&left, &right).unwrap();
+        assert!((result.value(0) - 111_195.).abs() < 1.);
+
+        let result = haversine_distance_to(&left, &point!(x: 0., y: 1.));
+        assert!((result.value(0) - 111_195.).abs() < 1.);
+    }
+}
@@ -0,0 +1,81 @@
+//! Multi-scale generalization of geometry arrays for tiled rendering.
+
+use crate::algorithm::geo::simplify::Simplify;
+use crate::error::GeoArrowError;
+use std::f64::consts::PI;
+use std::ops::RangeInclusive;
+
+/// Width of the Web Mercator (EPSG:3857) world, in meters.
+pub const WEB_MERCATOR_WORLD_WIDTH: f64 = 2.0 * PI * 6_378_137.0;
+
+/// Simplification tolerance at a zoom level of a square tile pyramid.
+///
+/// A tile at `zoom` covers `world_width / 2^zoom` coordinate units across `tile_size` pixels, so
+/// the tolerance is `pixel_tolerance` pixels expressed in coordinate units. Use
+/// [`WEB_MERCATOR_WORLD_WIDTH`] for Web Mercator coordinates or `360.0` for longitude/latitude.
+pub fn zoom_tolerance(zoom: u8, tile_size: u32, pixel_tolerance: f64, world_width: f64) -> f64 {
+    let units_per_pixel = world_width / (tile_size as f64 * 2_f64.powi(zoom as i32));
+    pixel_tolerance * units_per_pixel
+}
+
+/// Build a pyramid of progressively simplified copies of `array`, one per zoom level.
+///
+/// `tolerance` gives the Douglas-Peucker tolerance for each zoom, typically via
+/// [`zoom_tolerance`]. Levels are computed from the finest zoom down, each one simplifying the
+/// previous (finer) level rather than the original input, so coarse levels are cheap to build.
+/// Because tolerances halve at each finer zoom, the accumulated error stays below twice the
+/// coarsest level's tolerance.
+///
+/// Returns `(zoom, array)` pairs ordered by ascending zoom.
+pub fn simplify_pyramid<A: Simplify>(
+    array: &A,
+    zooms: RangeInclusive<u8>,
+    tolerance: impl Fn(u8) -> f64,
+) -> Result<Vec<(u8, A)>, GeoArrowError> {
+    let mut levels: Vec<(u8, A)> = Vec::with_capacity(zooms.len());
+    for zoom in zooms.rev() {
+        let source = levels.last().map_or(array, |(_, level)| level);
+        let simplified = source.simplify(&tolerance(zoom))?;
+        levels.push((zoom, simplified));
+    }
+
+    levels.reverse();
+    Ok(levels)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArrayTrait, LineStringArray};
+    use geo::line_string;
+
+    #[test]
+    fn tolerance_halves_per_zoom() {
+        let z0 = zoom_tolerance(0, 256, 1., WEB_MERCATOR_WORLD_WIDTH);
+        let z1 = zoom_tolerance(1, 256, 1., WEB_MERCATOR_WORLD_WIDTH);
+        assert!((z0 - 156_543.033_928).abs() < 1e-3);
+        assert_eq!(z0 / 2., z1);
+    }
+
+    #[test]
+    fn pyramid() {
+        let arr: LineStringArray = vec![line_string![
+            (x: 0., y: 0.),
+            (x: 1., y: 0.5),
+            (x: 2., y: 0.),
+            (x: 3., y: 3.),
+            (x: 4., y: 0.),
+        ]]
+        .into();
+
+        let levels = simplify_pyramid(&arr, 0..=2, |zoom| zoom_tolerance(zoom, 1, 1., 4.)).unwrap();
+        let zooms: Vec<u8> = levels.iter().map(|(zoom, _)| *zoom).collect();
+        assert_eq!(zooms, vec![0, 1, 2]);
+
+        let num_coords = |arr: &LineStringArray| arr.get_as_geo(0).unwrap().0.len();
+        // Tolerances are 4, 2 and 1 respectively
+        assert_eq!(num_coords(&levels[2].1), 4);
+        assert_eq!(num_coords(&levels[1].1), 3);
+        assert_eq!(num_coords(&levels[0].1), 2);
+    }
+}
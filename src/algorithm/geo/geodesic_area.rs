@@ -0,0 +1,197 @@
+//! Area of longitude/latitude geometries on the Earth, in square meters.
+//!
+//! Planar [`Area`](super::area::Area) of longitude/latitude coordinates is in square degrees,
+//! which mean different areas at different latitudes. These kernels compute areas on the Earth
+//! directly, so data in EPSG:4326 does not need reprojecting first. As in [`geo`], the sign of a
+//! polygon's area follows the orientation of its exterior ring, and non-polygonal geometries have
+//! zero area. Null geometries produce null areas, and rows are computed in parallel with the
+//! `rayon` feature.
+
+use crate::parallel::map_rows;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use arrow2::array::PrimitiveArray;
+use geo::{ChamberlainDuquetteArea as _, GeodesicArea as _};
+
+/// Area of each geometry on the WGS84 ellipsoid using Karney's geodesic algorithm, in square
+/// meters.
+///
+/// Coordinates must be longitude/latitude in degrees. This is exact to within a few square
+/// millimeters per vertex.
+pub trait GeodesicArea {
+    fn geodesic_area_signed(&self) -> PrimitiveArray<f64>;
+
+    /// The absolute value of [`geodesic_area_signed`](Self::geodesic_area_signed).
+    ///
+    /// Unlike [`geo::GeodesicArea::geodesic_area_unsigned`], which takes a clockwise exterior
+    /// ring to enclose the rest of the Earth, this is the same whatever the orientation of the
+    /// rings, as with planar areas.
+    fn geodesic_area_unsigned(&self) -> PrimitiveArray<f64>;
+}
+
+/// Area of each geometry on a sphere using the algorithm of Chamberlain and Duquette (2007), in
+/// square meters.
+///
+/// Coordinates must be longitude/latitude in degrees. This is faster than [`GeodesicArea`], but
+/// ignores the flattening of the Earth, so it is off by up to about half a percent.
+pub trait ChamberlainDuquetteArea {
+    fn chamberlain_duquette_signed_area(&self) -> PrimitiveArray<f64>;
+
+    fn chamberlain_duquette_unsigned_area(&self) -> PrimitiveArray<f64>;
+}
+
+macro_rules! geo_impl {
+    ($type:ty) => {
+        impl GeodesicArea for $type {
+            fn geodesic_area_signed(&self) -> PrimitiveArray<f64> {
+                map_rows(self.len(), |i| {
+                    self.get_as_geo(i).map(|g| g.geodesic_area_signed())
+                })
+                .into()
+            }
+
+            fn geodesic_area_unsigned(&self) -> PrimitiveArray<f64> {
+                map_rows(self.len(), |i| {
+                    self.get_as_geo(i).map(|g| g.geodesic_area_signed().abs())
+                })
+                .into()
+            }
+        }
+
+        impl ChamberlainDuquetteArea for $type {
+            fn chamberlain_duquette_signed_area(&self) -> PrimitiveArray<f64> {
+                map_rows(self.len(), |i| {
+                    self.get_as_geo(i)
+                        .map(|g| g.chamberlain_duquette_signed_area())
+                })
+                .into()
+            }
+
+            fn chamberlain_duquette_unsigned_area(&self) -> PrimitiveArray<f64> {
+                map_rows(self.len(), |i| {
+                    self.get_as_geo(i)
+                        .map(|g| g.chamberlain_duquette_unsigned_area())
+                })
+                .into()
+            }
+        }
+    };
+}
+
+geo_impl!(PointArray);
+geo_impl!(LineStringArray);
+geo_impl!(PolygonArray);
+geo_impl!(MultiPointArray);
+geo_impl!(MultiLineStringArray);
+geo_impl!(MultiPolygonArray);
+geo_impl!(RectArray);
+geo_impl!(WKBArray);
+geo_impl!(MixedGeometryArray);
+
+impl GeodesicArea for GeometryArray {
+    fn geodesic_area_signed(&self) -> PrimitiveArray<f64> {
+        match self {
+            GeometryArray::Point(arr) => arr.geodesic_area_signed(),
+            GeometryArray::LineString(arr) => arr.geodesic_area_signed(),
+            GeometryArray::Polygon(arr) => arr.geodesic_area_signed(),
+            GeometryArray::MultiPoint(arr) => arr.geodesic_area_signed(),
+            GeometryArray::MultiLineString(arr) => arr.geodesic_area_signed(),
+            GeometryArray::MultiPolygon(arr) => arr.geodesic_area_signed(),
+            GeometryArray::WKB(arr) => arr.geodesic_area_signed(),
+            GeometryArray::Rect(arr) => arr.geodesic_area_signed(),
+            GeometryArray::Mixed(arr) => arr.geodesic_area_signed(),
+        }
+    }
+
+    fn geodesic_area_unsigned(&self) -> PrimitiveArray<f64> {
+        match self {
+            GeometryArray::Point(arr) => arr.geodesic_area_unsigned(),
+            GeometryArray::LineString(arr) => arr.geodesic_area_unsigned(),
+            GeometryArray::Polygon(arr) => arr.geodesic_area_unsigned(),
+            GeometryArray::MultiPoint(arr) => arr.geodesic_area_unsigned(),
+            GeometryArray::MultiLineString(arr) => arr.geodesic_area_unsigned(),
+            GeometryArray::MultiPolygon(arr) => arr.geodesic_area_unsigned(),
+            GeometryArray::WKB(arr) => arr.geodesic_area_unsigned(),
+            GeometryArray::Rect(arr) => arr.geodesic_area_unsigned(),
+            GeometryArray::Mixed(arr) => arr.geodesic_area_unsigned(),
+        }
+    }
+}
+
+impl ChamberlainDuquetteArea for GeometryArray {
+    fn chamberlain_duquette_signed_area(&self) -> PrimitiveArray<f64> {
+        match self {
+            GeometryArray::Point(arr) => arr.chamberlain_duquette_signed_area(),
+            GeometryArray::LineString(arr) => arr.chamberlain_duquette_signed_area(),
+            GeometryArray::Polygon(arr) => arr.chamberlain_duquette_signed_area(),
+            GeometryArray::MultiPoint(arr) => arr.chamberlain_duquette_signed_area(),
+            GeometryArray::MultiLineString(arr) => arr.chamberlain_duquette_signed_area(),
+            GeometryArray::MultiPolygon(arr) => arr.chamberlain_duquette_signed_area(),
+            GeometryArray::WKB(arr) => arr.chamberlain_duquette_signed_area(),
+            GeometryArray::Rect(arr) => arr.chamberlain_duquette_signed_area(),
+            GeometryArray::Mixed(arr) => arr.chamberlain_duquette_signed_area(),
+        }
+    }
+
+    fn chamberlain_duquette_unsigned_area(&self) -> PrimitiveArray<f64> {
+        match self {
+            GeometryArray::Point(arr) => arr.chamberlain_duquette_unsigned_area(),
+            GeometryArray::LineString(arr) => arr.chamberlain_duquette_unsigned_area(),
+            GeometryArray::Polygon(arr) => arr.chamberlain_duquette_unsigned_area(),
+            GeometryArray::MultiPoint(arr) => arr.chamberlain_duquette_unsigned_area(),
+            GeometryArray::MultiLineString(arr) => arr.chamberlain_duquette_unsigned_area(),
+            GeometryArray::MultiPolygon(arr) => arr.chamberlain_duquette_unsigned_area(),
+            GeometryArray::WKB(arr) => arr.chamberlain_duquette_unsigned_area(),
+            GeometryArray::Rect(arr) => arr.chamberlain_duquette_unsigned_area(),
+            GeometryArray::Mixed(arr) => arr.chamberlain_duquette_unsigned_area(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::Array;
+    use geo::{point, polygon, MultiPolygon};
+
+    /// One degree square at the equator, counterclockwise.
+    fn equator_square() -> geo::Polygon {
+        polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)]
+    }
+
+    #[test]
+    fn polygons() {
+        let clockwise = polygon![
+            (x: 0., y: 0.), (x: 0., y: 1.), (x: 1., y: 1.), (x: 1., y: 0.), (x: 0., y: 0.)
+        ];
+        let arr: PolygonArray = vec![Some(equator_square()), None, Some(clockwise)].into();
+
+        // About 12,308 square kilometers on the WGS84 ellipsoid
+        let signed = arr.geodesic_area_signed();
+        assert!((signed.value(0) - 12_308_778_361.469).abs() < 1.0);
+        assert!(signed.is_null(1));
+        assert_eq!(signed.value(2), -signed.value(0));
+        assert_eq!(arr.geodesic_area_unsigned().value(2), signed.value(0));
+
+        let spherical = arr.chamberlain_duquette_unsigned_area();
+        assert!((spherical.value(0) / signed.value(0) - 1.0).abs() < 0.01);
+        assert!(arr.chamberlain_duquette_signed_area().value(2) < 0.0);
+    }
+
+    #[test]
+    fn geometry_arrays() {
+        let multi: MultiPolygonArray =
+            vec![MultiPolygon::new(vec![equator_square(), equator_square()])].into();
+        let polygons: PolygonArray = vec![equator_square()].into();
+        let multi_area = GeometryArray::MultiPolygon(multi).geodesic_area_unsigned();
+        let polygon_area = GeometryArray::Polygon(polygons).geodesic_area_unsigned();
+        assert!((multi_area.value(0) - 2.0 * polygon_area.value(0)).abs() < 1e-3);
+
+        let points: PointArray = vec![point!(x: 1., y: 1.)].into();
+        let points = GeometryArray::Point(points);
+        assert_eq!(points.geodesic_area_signed().value(0), 0.);
+        assert_eq!(points.chamberlain_duquette_unsigned_area().value(0), 0.);
+    }
+}
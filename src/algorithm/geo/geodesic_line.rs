@@ -0,0 +1,102 @@
+//! Geodesic lines between pairs of longitude/latitude points.
+//!
+//! A straight line between two longitude/latitude points is not the shortest route between them
+//! on the Earth, and draws flight routes and other long connections in the wrong place. These
+//! kernels densify the geodesic between each pair of points into a line string, so that it keeps
+//! its shape when drawn or reprojected.
+
+use crate::crs::check_crs;
+use crate::error::GeoArrowError;
+use crate::parallel::map_rows;
+use crate::{GeometryArrayTrait, LineStringArray, PointArray};
+use geo::GeodesicIntermediate;
+
+/// The geodesic on the WGS84 ellipsoid from each point of `origins` to the point at the same
+/// index of `destinations`, with segments no longer than `max_segment_len` meters.
+///
+/// Coordinates must be longitude/latitude in degrees. Each line starts at its origin and ends at
+/// its destination, with intermediate points evenly spaced along the geodesic. Lines between
+/// points closer than `max_segment_len` are a single segment. A null origin or destination
+/// produces a null line, and rows are computed in parallel with the `rayon` feature.
+/// # Errors
+/// Errors if `max_segment_len` is not a positive number, or if the arrays differ in length or
+/// CRS.
+pub fn geodesic_line(
+    origins: &PointArray,
+    destinations: &PointArray,
+    max_segment_len: f64,
+) -> Result<LineStringArray, GeoArrowError> {
+    if !(max_segment_len > 0.0 && max_segment_len.is_finite()) {
+        return Err(GeoArrowError::General(format!(
+            "max_segment_len must be a positive number, got {max_segment_len}"
+        )));
+    }
+    if origins.len() != destinations.len() {
+        return Err(GeoArrowError::General(
+            "both arrays must have the same length".to_string(),
+        ));
+    }
+    let crs = check_crs(origins.crs(), destinations.crs())?;
+
+    let lines = map_rows(origins.len(), |i| {
+        let origin = origins.get_as_geo(i)?;
+        let destination = destinations.get_as_geo(i)?;
+        let points = origin.geodesic_intermediate_fill(&destination, max_segment_len, true);
+        Some(geo::LineString::from(points))
+    });
+    Ok(LineStringArray::from(lines).with_crs(crs))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{coord, point, GeodesicDistance};
+
+    #[test]
+    fn densify() {
+        let origins: PointArray = vec![
+            Some(point!(x: -74.0, y: 40.7)),
+            Some(point!(x: 0., y: 0.)),
+            None,
+        ]
+        .into();
+        let destinations: PointArray = vec![
+            Some(point!(x: 2.35, y: 48.86)),
+            Some(point!(x: 0., y: 0.001)),
+            Some(point!(x: 0., y: 0.)),
+        ]
+        .into();
+        let lines = geodesic_line(
+            &origins.with_crs(Some("EPSG:4326")),
+            &destinations,
+            100_000.,
+        )
+        .unwrap();
+        assert_eq!(lines.crs(), Some("EPSG:4326"));
+
+        // New York to Paris is about 5,840 km, so at least 59 segments
+        let line = lines.value_as_geo(0);
+        assert!((60..=61).contains(&line.0.len()));
+        assert_eq!(line.0.first(), Some(&coord! { x: -74.0, y: 40.7 }));
+        assert_eq!(line.0.last(), Some(&coord! { x: 2.35, y: 48.86 }));
+        for segment in line.lines() {
+            let (start, end) = segment.points();
+            assert!(start.geodesic_distance(&end) <= 100_000.);
+        }
+        // The route bends north of both endpoints
+        assert!(line.0.iter().any(|coord| coord.y > 50.));
+
+        assert_eq!(lines.value_as_geo(1).0.len(), 2);
+        assert!(lines.is_null(2));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let points: PointArray = vec![point!(x: 0., y: 0.)].into();
+        assert!(geodesic_line(&points, &points, 0.).is_err());
+        assert!(geodesic_line(&points, &points, f64::NAN).is_err());
+
+        let empty: PointArray = Vec::<geo::Point>::new().into();
+        assert!(geodesic_line(&points, &empty, 1000.).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Spatial joins between two geometry arrays.
+
+use crate::indexed::{envelope, envelope_tree};
+use crate::GeometryArrayTrait;
+use arrow2::array::PrimitiveArray;
+use geo::{Contains, Intersects};
+use rstar::{RTreeObject, AABB};
+
+/// The relationship a pair of geometries must satisfy to be joined by [`spatial_join`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialPredicate {
+    /// The bounding boxes of the two geometries intersect. This skips the exact check, and so
+    /// returns every candidate pair.
+    BboxIntersects,
+
+    /// The geometries intersect.
+    Intersects,
+
+    /// The left geometry contains the right geometry.
+    Contains,
+
+    /// The left geometry is within the right geometry.
+    Within,
+}
+
+impl SpatialPredicate {
+    fn evaluate(&self, left: &geo::Geometry, right: &geo::Geometry) -> bool {
+        match self {
+            SpatialPredicate::BboxIntersects => true,
+            SpatialPredicate::Intersects => left.intersects(right),
+            SpatialPredicate::Contains => left.contains(right),
+            SpatialPredicate::Within => right.contains(left),
+        }
+    }
+}
+
+/// Find all pairs of geometries from `left` and `right` satisfying `predicate`.
+///
+/// Candidate pairs are found by indexing the bounding boxes of `right` in an [`RTree`] and
+/// querying it with the bounding box of each geometry in `left`; candidates are then checked
+/// exactly with `predicate`. Null and empty geometries never match.
+///
+/// Returns `(left_index, right_index)` arrays of equal length, one entry per matching pair,
+/// ordered by left index and then by right index.
+pub fn spatial_join<'a, L, R>(
+    left: &'a L,
+    right: &'a R,
+    predicate: SpatialPredicate,
+) -> (PrimitiveArray<u32>, PrimitiveArray<u32>)
+where
+    L: GeometryArrayTrait<'a>,
+    L::Scalar: RTreeObject<Envelope = AABB<[f64; 2]>>,
+    L::ScalarGeo: Into<geo::Geometry>,
+    R: GeometryArrayTrait<'a>,
+    R::Scalar: RTreeObject<Envelope = AABB<[f64; 2]>>,
+    R::ScalarGeo: Into<geo::Geometry>,
+{
+    let tree = envelope_tree(right);
+
+    let mut left_indices = vec![];
+    let mut right_indices = vec![];
+    for left_idx in 0..left.len() {
+        let left_envelope = match envelope(left, left_idx) {
+            Some(envelope) => envelope,
+            None => continue,
+        };
+
+        let mut candidates: Vec<usize> = tree
+            .locate_in_envelope_intersecting(&left_envelope)
+            .map(|candidate| candidate.data)
+            .collect();
+        if candidates.is_empty() {
+            continue;
+        }
+        candidates.sort_unstable();
+
+        let left_geom: Option<geo::Geometry> = (predicate != SpatialPredicate::BboxIntersects)
+            .then(|| left.value_as_geo(left_idx).into());
+        for right_idx in candidates {
+            let is_match = match &left_geom {
+                Some(left_geom) => {
+                    predicate.evaluate(left_geom, &right.value_as_geo(right_idx).into())
+                }
+                None => true,
+            };
+            if is_match {
+                left_indices.push(left_idx as u32);
+                right_indices.push(right_idx as u32);
+            }
+        }
+    }
+
+    (
+        PrimitiveArray::from_vec(left_indices),
+        PrimitiveArray::from_vec(right_indices),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArray, PointArray, PolygonArray};
+    use geo::{point, polygon};
+
+    fn zones() -> PolygonArray {
+        vec![
+            Some(polygon![(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.)]),
+            None,
+            Some(polygon![(x: 2., y: 2.), (x: 6., y: 2.), (x: 6., y: 6.), (x: 2., y: 6.)]),
+            Some(polygon![(x: 0., y: 0.), (x: 4., y: 4.), (x: 4., y: 0.)]),
+        ]
+        .into()
+    }
+
+    fn points() -> PointArray {
+        vec![
+            Some(point!(x: 3., y: 3.)),
+            Some(point!(x: 1., y: 3.)),
+            None,
+            Some(point!(x: 10., y: 10.)),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn points_within_polygons() {
+        let (left, right) = spatial_join(&points(), &zones(), SpatialPredicate::Within);
+        assert_eq!(left, PrimitiveArray::from_vec(vec![0, 0, 1]));
+        assert_eq!(right, PrimitiveArray::from_vec(vec![0, 2, 0]));
+
+        let (left, right) = spatial_join(&zones(), &points(), SpatialPredicate::Contains);
+        assert_eq!(left, PrimitiveArray::from_vec(vec![0, 0, 2]));
+        assert_eq!(right, PrimitiveArray::from_vec(vec![0, 1, 0]));
+    }
+
+    #[test]
+    fn bbox_candidates() {
+        // (1, 3) falls in the triangle's bounding box but not in the triangle itself
+        let (left, right) = spatial_join(&points(), &zones(), SpatialPredicate::BboxIntersects);
+        assert_eq!(left, PrimitiveArray::from_vec(vec![0, 0, 0, 1, 1]));
+        assert_eq!(right, PrimitiveArray::from_vec(vec![0, 2, 3, 0, 3]));
+
+        let zones = GeometryArray::Polygon(zones());
+        let (left, right) = spatial_join(&points(), &zones, SpatialPredicate::Intersects);
+        assert_eq!(left, PrimitiveArray::from_vec(vec![0, 0, 0, 1]));
+        assert_eq!(right, PrimitiveArray::from_vec(vec![0, 2, 3, 0]));
+    }
+}
@@ -0,0 +1,265 @@
+//! Length of linear geometries, using several distance metrics.
+//!
+//! Each metric is exposed both as a trait on the concrete array types and as a function
+//! dispatching over [`GeometryArray`]. Point arrays have zero length; areal geometries have no
+//! length and are rejected by the [`GeometryArray`] entry points.
+
+use crate::algorithm::geo::area::from_fn;
+use crate::error::GeoArrowError;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MultiLineStringArray, MultiPointArray,
+    PointArray,
+};
+use arrow2::array::{MutablePrimitiveArray, PrimitiveArray};
+use geo::{EuclideanLength as _, GeodesicLength as _, HaversineLength as _, VincentyLength as _};
+
+/// Planar length of each geometry, in coordinate units.
+pub trait EuclideanLength {
+    fn euclidean_length(&self) -> PrimitiveArray<f64>;
+}
+
+/// Length of each geometry on a sphere using the haversine formula, in meters.
+///
+/// Coordinates must be longitude/latitude in degrees.
+pub trait HaversineLength {
+    fn haversine_length(&self) -> PrimitiveArray<f64>;
+}
+
+/// Length of each geometry on the WGS84 ellipsoid using Karney's geodesic algorithm, in meters.
+///
+/// Coordinates must be longitude/latitude in degrees.
+pub trait GeodesicLength {
+    fn geodesic_length(&self) -> PrimitiveArray<f64>;
+}
+
+/// Length of each geometry on the WGS84 ellipsoid using Vincenty's formulae, in meters.
+///
+/// Coordinates must be longitude/latitude in degrees. Errors if the formula fails to converge
+/// for any segment, which can happen for nearly antipodal points.
+pub trait VincentyLength {
+    fn vincenty_length(&self) -> Result<PrimitiveArray<f64>, GeoArrowError>;
+}
+
+macro_rules! zero_impl {
+    ($type:ty) => {
+        impl EuclideanLength for $type {
+            fn euclidean_length(&self) -> PrimitiveArray<f64> {
+                from_fn(self.len(), self.validity(), |_| 0.0)
+            }
+        }
+
+        impl HaversineLength for $type {
+            fn haversine_length(&self) -> PrimitiveArray<f64> {
+                from_fn(self.len(), self.validity(), |_| 0.0)
+            }
+        }
+
+        impl GeodesicLength for $type {
+            fn geodesic_length(&self) -> PrimitiveArray<f64> {
+                from_fn(self.len(), self.validity(), |_| 0.0)
+            }
+        }
+
+        impl VincentyLength for $type {
+            fn vincenty_length(&self) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+                Ok(from_fn(self.len(), self.validity(), |_| 0.0))
+            }
+        }
+    };
+}
+
+zero_impl!(PointArray);
+zero_impl!(MultiPointArray);
+
+macro_rules! geo_impl {
+    ($type:ty) => {
+        impl EuclideanLength for $type {
+            fn euclidean_length(&self) -> PrimitiveArray<f64> {
+                let mut output = MutablePrimitiveArray::with_capacity(self.len());
+                self.iter_geo()
+                    .for_each(|maybe_g| output.push(maybe_g.map(|g| g.euclidean_length())));
+                output.into()
+            }
+        }
+
+        impl HaversineLength for $type {
+            fn haversine_length(&self) -> PrimitiveArray<f64> {
+                let mut output = MutablePrimitiveArray::with_capacity(self.len());
+                self.iter_geo()
+                    .for_each(|maybe_g| output.push(maybe_g.map(|g| g.haversine_length())));
+                output.into()
+            }
+        }
+
+        impl GeodesicLength for $type {
+            fn geodesic_length(&self) -> PrimitiveArray<f64> {
+                let mut output = MutablePrimitiveArray::with_capacity(self.len());
+                self.iter_geo()
+                    .for_each(|maybe_g| output.push(maybe_g.map(|g| g.geodesic_length())));
+                output.into()
+            }
+        }
+
+        impl VincentyLength for $type {
+            fn vincenty_length(&self) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+                let mut output = MutablePrimitiveArray::with_capacity(self.len());
+                for maybe_g in self.iter_geo() {
+                    let length = maybe_g
+                        .map(|g| g.vincenty_length())
+                        .transpose()
+                        .map_err(|err| GeoArrowError::General(err.to_string()))?;
+                    output.push(length);
+                }
+                Ok(output.into())
+            }
+        }
+    };
+}
+
+geo_impl!(LineStringArray);
+geo_impl!(MultiLineStringArray);
+
+fn areal_error(name: &str) -> GeoArrowError {
+    GeoArrowError::General(format!("{} is not defined for polygonal geometries", name))
+}
+
+/// Planar length of each geometry in a [`GeometryArray`].
+///
+/// Errors for polygonal and WKB arrays.
+pub fn euclidean_length(array: &GeometryArray) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    match array {
+        GeometryArray::Point(arr) => Ok(arr.euclidean_length()),
+        GeometryArray::MultiPoint(arr) => Ok(arr.euclidean_length()),
+        GeometryArray::LineString(arr) => Ok(arr.euclidean_length()),
+        GeometryArray::MultiLineString(arr) => Ok(arr.euclidean_length()),
+        GeometryArray::Polygon(_) | GeometryArray::MultiPolygon(_) | GeometryArray::Rect(_) => {
+            Err(areal_error("euclidean_length"))
+        }
+        GeometryArray::WKB(_) => Err(GeoArrowError::NotYetImplemented(
+            "euclidean_length on WKB arrays".to_string(),
+        )),
+        GeometryArray::Mixed(_) => Err(GeoArrowError::NotYetImplemented(
+            "euclidean_length on mixed geometry arrays".to_string(),
+        )),
+    }
+}
+
+/// Haversine length of each geometry in a [`GeometryArray`], in meters.
+///
+/// Errors for polygonal and WKB arrays.
+pub fn haversine_length(array: &GeometryArray) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    match array {
+        GeometryArray::Point(arr) => Ok(arr.haversine_length()),
+        GeometryArray::MultiPoint(arr) => Ok(arr.haversine_length()),
+        GeometryArray::LineString(arr) => Ok(arr.haversine_length()),
+        GeometryArray::MultiLineString(arr) => Ok(arr.haversine_length()),
+        GeometryArray::Polygon(_) | GeometryArray::MultiPolygon(_) | GeometryArray::Rect(_) => {
+            Err(areal_error("haversine_length"))
+        }
+        GeometryArray::WKB(_) => Err(GeoArrowError::NotYetImplemented(
+            "haversine_length on WKB arrays".to_string(),
+        )),
+        GeometryArray::Mixed(_) => Err(GeoArrowError::NotYetImplemented(
+            "haversine_length on mixed geometry arrays".to_string(),
+        )),
+    }
+}
+
+/// Geodesic length of each geometry in a [`GeometryArray`], in meters.
+///
+/// Errors for polygonal and WKB arrays.
+pub fn geodesic_length(array: &GeometryArray) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    match array {
+        GeometryArray::Point(arr) => Ok(arr.geodesic_length()),
+        GeometryArray::MultiPoint(arr) => Ok(arr.geodesic_length()),
+        GeometryArray::LineString(arr) => Ok(arr.geodesic_length()),
+        GeometryArray::MultiLineString(arr) => Ok(arr.geodesic_length()),
+        GeometryArray::Polygon(_) | GeometryArray::MultiPolygon(_) | GeometryArray::Rect(_) => {
+            Err(areal_error("geodesic_length"))
+        }
+        GeometryArray::WKB(_) => Err(GeoArrowError::NotYetImplemented(
+            "geodesic_length on WKB arrays".to_string(),
+        )),
+        GeometryArray::Mixed(_) => Err(GeoArrowError::NotYetImplemented(
+            "geodesic_length on mixed geometry arrays".to_string(),
+        )),
+    }
+}
+
+/// Vincenty length of each geometry in a [`GeometryArray`], in meters.
+///
+/// Errors for polygonal and WKB arrays, or if the formula fails to converge.
+pub fn vincenty_length(array: &GeometryArray) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    match array {
+        GeometryArray::Point(arr) => arr.vincenty_length(),
+        GeometryArray::MultiPoint(arr) => arr.vincenty_length(),
+        GeometryArray::LineString(arr) => arr.vincenty_length(),
+        GeometryArray::MultiLineString(arr) => arr.vincenty_length(),
+        GeometryArray::Polygon(_) | GeometryArray::MultiPolygon(_) | GeometryArray::Rect(_) => {
+            Err(areal_error("vincenty_length"))
+        }
+        GeometryArray::WKB(_) => Err(GeoArrowError::NotYetImplemented(
+            "vincenty_length on WKB arrays".to_string(),
+        )),
+        GeometryArray::Mixed(_) => Err(GeoArrowError::NotYetImplemented(
+            "vincenty_length on mixed geometry arrays".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PolygonArray;
+    use arrow2::array::Array;
+    use geo::{line_string, polygon, MultiLineString};
+
+    fn lines() -> LineStringArray {
+        vec![
+            Some(line_string![(x: 0., y: 0.), (x: 3., y: 4.), (x: 3., y: 5.)]),
+            None,
+        ]
+        .into()
+    }
+
+    #[test]
+    fn euclidean() {
+        let result = lines().euclidean_length();
+        assert_eq!(result.value(0), 6.);
+        assert!(result.is_null(1));
+
+        let arr: MultiLineStringArray = vec![MultiLineString::new(vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 0.)],
+            line_string![(x: 0., y: 0.), (x: 0., y: 2.)],
+        ])]
+        .into();
+        assert_eq!(arr.euclidean_length().value(0), 3.);
+    }
+
+    #[test]
+    fn ellipsoidal() {
+        // One degree of longitude along the equator
+        let arr: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 1., y: 0.)]].into();
+        let haversine = arr.haversine_length().value(0);
+        let geodesic = arr.geodesic_length().value(0);
+        let vincenty = arr.vincenty_length().unwrap().value(0);
+
+        assert!((haversine - 111_195.).abs() < 1.);
+        assert!((geodesic - 111_319.49).abs() < 0.01);
+        assert!((vincenty - geodesic).abs() < 0.01);
+    }
+
+    #[test]
+    fn enum_dispatch() {
+        let result = euclidean_length(&GeometryArray::LineString(lines())).unwrap();
+        assert_eq!(result.value(0), 6.);
+
+        let arr: PolygonArray =
+            vec![polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)]].into();
+        let arr = GeometryArray::Polygon(arr);
+        assert!(euclidean_length(&arr).is_err());
+        assert!(haversine_length(&arr).is_err());
+        assert!(geodesic_length(&arr).is_err());
+        assert!(vincenty_length(&arr).is_err());
+    }
+}
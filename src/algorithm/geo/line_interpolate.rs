@@ -0,0 +1,109 @@
+//! Linear referencing along line strings.
+//!
+//! Positions along a line string are expressed as fractions of its total planar length, from 0
+//! at its first vertex to 1 at its last, as in [`geo::LineInterpolatePoint`] and
+//! [`geo::LineLocatePoint`]. Rows are computed in parallel with the `rayon` feature.
+
+use crate::crs::check_crs;
+use crate::error::GeoArrowError;
+use crate::parallel::map_rows;
+use crate::{GeometryArrayTrait, LineStringArray, PointArray};
+use arrow2::array::PrimitiveArray;
+use geo::{LineInterpolatePoint as _, LineLocatePoint as _};
+
+/// The point at `fractions[i]` of the way along each line string `i`.
+///
+/// Fractions outside `[0, 1]` are clamped to it. The output is null where the line string or the
+/// fraction is null, where the line string is empty, or where the fraction is NaN.
+///
+/// Errors if `fractions` has a different length than `array`.
+pub fn line_interpolate_point(
+    array: &LineStringArray,
+    fractions: &PrimitiveArray<f64>,
+) -> Result<PointArray, GeoArrowError> {
+    if array.len() != fractions.len() {
+        return Err(GeoArrowError::General(
+            "fractions must have the same length as the array".to_string(),
+        ));
+    }
+
+    let points = map_rows(array.len(), |i| {
+        let fraction = fractions.get(i)?;
+        array.get_as_geo(i)?.line_interpolate_point(fraction)
+    });
+    let points: PointArray = points.into();
+    Ok(points.with_crs(array.crs()))
+}
+
+/// The fraction of the way along each line string `i` of the point on it closest to `points[i]`.
+///
+/// The output is null where the line string or the point is null, or where the line string is
+/// empty or has zero length.
+///
+/// Errors if the arrays differ in length or CRS.
+pub fn line_locate_point(
+    array: &LineStringArray,
+    points: &PointArray,
+) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    if array.len() != points.len() {
+        return Err(GeoArrowError::General(
+            "both arrays must have the same length".to_string(),
+        ));
+    }
+    check_crs(array.crs(), points.crs())?;
+
+    let fractions = map_rows(array.len(), |i| {
+        let point = points.get_as_geo(i)?;
+        array.get_as_geo(i)?.line_locate_point(&point)
+    });
+    Ok(fractions.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::Array;
+    use geo::{line_string, point};
+
+    fn lines() -> LineStringArray {
+        vec![
+            Some(line_string![(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.)]),
+            None,
+            Some(line_string![(x: 0., y: 0.), (x: 0., y: 4.)]),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn interpolate() {
+        let fractions = PrimitiveArray::from([Some(0.75), Some(0.5), None]);
+        let points = line_interpolate_point(&lines(), &fractions).unwrap();
+        assert_eq!(points.get_as_geo(0), Some(point!(x: 10., y: 5.)));
+        assert!(points.is_null(1));
+        assert!(points.is_null(2));
+
+        let clamped =
+            line_interpolate_point(&lines(), &PrimitiveArray::from_vec(vec![2., 0., -1.])).unwrap();
+        assert_eq!(clamped.get_as_geo(0), Some(point!(x: 10., y: 10.)));
+        assert_eq!(clamped.get_as_geo(2), Some(point!(x: 0., y: 0.)));
+
+        assert!(line_interpolate_point(&lines(), &PrimitiveArray::from_vec(vec![0.5])).is_err());
+    }
+
+    #[test]
+    fn locate_round_trip() {
+        let points: PointArray = vec![
+            Some(point!(x: 12., y: 5.)),
+            Some(point!(x: 0., y: 0.)),
+            None,
+        ]
+        .into();
+        let fractions = line_locate_point(&lines(), &points).unwrap();
+        assert_eq!(fractions.value(0), 0.75);
+        assert!(fractions.is_null(1));
+        assert!(fractions.is_null(2));
+
+        let located = line_interpolate_point(&lines(), &fractions).unwrap();
+        assert_eq!(located.get_as_geo(0), Some(point!(x: 10., y: 5.)));
+    }
+}
@@ -0,0 +1,161 @@
+//! Repair invalid polygons.
+//!
+//! Invalid rows, as found by [`validation`](super::validation), are repaired in the spirit of
+//! the `buffer(0)` trick: rings with too few points are dropped, and what is left is rebuilt
+//! with a boolean union, which splits self-intersecting rings into valid polygons and
+//! merges overlapping ones. Valid rows are kept as they are. With the `geos` feature,
+//! `algorithm::geos::make_valid` repairs geometries with GEOS' `MakeValid` instead.
+
+use super::validation::{row_reason, InvalidReason};
+use crate::parallel::map_rows;
+use crate::{GeometryArrayTrait, MultiPolygonArray, PolygonArray};
+use geo::BooleanOps;
+
+/// Repair the invalid geometries of an array.
+pub trait MakeValid {
+    type Output;
+
+    /// The array with every invalid geometry repaired, and the number of geometries repaired.
+    ///
+    /// Repairing a polygon can split it into several, so polygons are output as multi polygons.
+    /// A geometry with nothing left after dropping collapsed rings becomes empty. Null geometries
+    /// stay null.
+    fn make_valid(&self) -> (Self::Output, usize);
+}
+
+/// Whether a ring has enough points to enclose an area. Rings that have enough points but no
+/// area are dropped by the union.
+fn ring_has_area(ring: &geo::LineString) -> bool {
+    ring.0.len() >= 4
+}
+
+/// A polygon repaired into a valid multi polygon.
+fn repair_polygon(polygon: &geo::Polygon) -> geo::MultiPolygon {
+    if !ring_has_area(polygon.exterior()) {
+        return geo::MultiPolygon::new(vec![]);
+    }
+    let interiors = polygon
+        .interiors()
+        .iter()
+        .filter(|ring| ring_has_area(ring))
+        .cloned()
+        .collect();
+    let polygon = geo::Polygon::new(polygon.exterior().clone(), interiors);
+    geo::MultiPolygon::new(vec![polygon]).union(&geo::MultiPolygon::new(vec![]))
+}
+
+/// A multi polygon repaired by repairing each of its polygons, and then merging them.
+fn repair_multi_polygon(multi_polygon: &geo::MultiPolygon) -> geo::MultiPolygon {
+    multi_polygon
+        .0
+        .iter()
+        .map(repair_polygon)
+        .fold(geo::MultiPolygon::new(vec![]), |merged, polygons| {
+            merged.union(&polygons)
+        })
+}
+
+/// Repair each invalid row of `array`, counting the rows repaired. The output has no CRS.
+fn make_valid_rows<'a, A>(
+    array: &'a A,
+    repair: impl Fn(A::ScalarGeo) -> geo::MultiPolygon + Sync + Send,
+    keep: impl Fn(A::ScalarGeo) -> geo::MultiPolygon + Sync + Send,
+) -> (MultiPolygonArray, usize)
+where
+    A: GeometryArrayTrait<'a> + Sync,
+    A::Scalar: super::validation::RingClosure,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    let rows: Vec<Option<(geo::MultiPolygon, bool)>> = map_rows(array.len(), |i| {
+        let reason: Result<(), InvalidReason> = row_reason(array, i)?;
+        let geom = array.value_as_geo(i);
+        Some(match reason {
+            Ok(()) => (keep(geom), false),
+            Err(_) => (repair(geom), true),
+        })
+    });
+
+    let num_repaired = rows
+        .iter()
+        .flatten()
+        .filter(|(_, repaired)| *repaired)
+        .count();
+    let geoms: Vec<Option<geo::MultiPolygon>> = rows
+        .into_iter()
+        .map(|row| row.map(|(geom, _)| geom))
+        .collect();
+    let output: MultiPolygonArray = geoms.into();
+    (output, num_repaired)
+}
+
+impl MakeValid for PolygonArray {
+    type Output = MultiPolygonArray;
+
+    fn make_valid(&self) -> (Self::Output, usize) {
+        let (output, num_repaired) = make_valid_rows(
+            self,
+            |polygon| repair_polygon(&polygon),
+            |polygon| geo::MultiPolygon::new(vec![polygon]),
+        );
+        (output.with_crs(self.crs()), num_repaired)
+    }
+}
+
+impl MakeValid for MultiPolygonArray {
+    type Output = MultiPolygonArray;
+
+    fn make_valid(&self) -> (Self::Output, usize) {
+        let (output, num_repaired) = make_valid_rows(
+            self,
+            |multi_polygon| repair_multi_polygon(&multi_polygon),
+            |multi_polygon| multi_polygon,
+        );
+        (output.with_crs(self.crs()), num_repaired)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::geo::validation::is_valid;
+    use geo::{polygon, Area, MultiPolygon};
+
+    #[test]
+    fn polygons() {
+        let square = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.)];
+        let bowtie = polygon![
+            (x: 0., y: 0.), (x: 2., y: 2.), (x: 2., y: 0.), (x: 0., y: 2.), (x: 0., y: 0.)
+        ];
+        let collapsed = polygon![(x: 0., y: 0.), (x: 1., y: 1.), (x: 2., y: 2.), (x: 0., y: 0.)];
+        let arr: PolygonArray =
+            vec![Some(square.clone()), Some(bowtie), None, Some(collapsed)].into();
+
+        let (repaired, num_repaired) = arr.make_valid();
+        assert_eq!(num_repaired, 2);
+        assert_eq!(
+            repaired.get_as_geo(0),
+            Some(MultiPolygon::new(vec![square]))
+        );
+        let bowtie = repaired.get_as_geo(1).unwrap();
+        assert_eq!(bowtie.0.len(), 2);
+        assert_eq!(bowtie.unsigned_area(), 2.);
+        assert!(repaired.is_null(2));
+        assert_eq!(repaired.get_as_geo(3), Some(MultiPolygon::new(vec![])));
+        assert!(is_valid(&repaired).iter().all(|valid| valid != Some(false)));
+    }
+
+    #[test]
+    fn overlapping_multi_polygons() {
+        let overlapping = MultiPolygon::new(vec![
+            polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.)],
+            polygon![(x: 1., y: 1.), (x: 3., y: 1.), (x: 3., y: 3.), (x: 1., y: 3.)],
+        ]);
+        let arr: MultiPolygonArray = vec![overlapping].into();
+        let (repaired, num_repaired) = arr.make_valid();
+        assert_eq!(num_repaired, 1);
+        let merged = repaired.value_as_geo(0);
+        assert_eq!(merged.0.len(), 1);
+        assert_eq!(merged.unsigned_area(), 7.);
+        assert!(is_valid(&repaired).value(0));
+    }
+}
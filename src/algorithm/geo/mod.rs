@@ -0,0 +1,37 @@
+//! Algorithms implemented on and with [`geo`] geometries.
+
+pub use allocate::allocate_to_nearest;
+pub use apply::{filter_by, map_geometries, predicate_mask};
+pub use convex_hull::convex_hull;
+pub use geodesic_line::geodesic_line;
+pub use join::{spatial_join, SpatialPredicate};
+pub use line_interpolate::{line_interpolate_point, line_locate_point};
+pub use nearest::nearest_neighbor;
+pub use predicates::{
+    contains, contains_geometry, intersects, intersects_geometry, within, within_geometry,
+};
+pub use relate::{relate, relate_geometry, relate_pattern, relate_pattern_geometry};
+pub use zonal_stats::{zonal_stats, ZonalStatistic};
+
+pub mod affine;
+pub mod allocate;
+pub mod apply;
+pub mod area;
+pub mod concave_hull;
+pub mod convex_hull;
+pub mod distance;
+pub mod generalize;
+pub mod geodesic_area;
+pub mod geodesic_line;
+pub mod join;
+pub mod length;
+pub mod line_interpolate;
+pub mod make_valid;
+pub mod nearest;
+pub mod predicates;
+pub mod relate;
+pub mod simplify;
+pub mod trajectory;
+pub mod units;
+pub mod validation;
+pub mod zonal_stats;
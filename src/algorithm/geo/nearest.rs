@@ -0,0 +1,89 @@
+//! k-nearest-neighbor search from points to the geometries of another array.
+
+use crate::indexed::{envelope_tree, k_nearest};
+use crate::{GeometryArrayTrait, PointArray};
+use arrow2::array::{MutablePrimitiveArray, PrimitiveArray};
+use rstar::{RTreeObject, AABB};
+
+/// Find the `k` geometries of `geometries` nearest to each point of `points`.
+///
+/// Returns `(index, distance)` arrays of length `points.len() * k`: the neighbors of point `i`
+/// occupy slots `i * k..(i + 1) * k`, nearest first, with ties broken by the lower index.
+/// Distances are Euclidean, and zero for a point on or inside a geometry. Slots are null where
+/// the point is null or `geometries` has fewer than `k` non-empty geometries.
+///
+/// The geometries are indexed once in a bulk-loaded [`RTree`](rstar::RTree) of their envelopes;
+/// candidates are then ranked by their exact distance.
+pub fn nearest_neighbor<'a, G>(
+    points: &PointArray,
+    geometries: &'a G,
+    k: usize,
+) -> (PrimitiveArray<u32>, PrimitiveArray<f64>)
+where
+    G: GeometryArrayTrait<'a>,
+    G::Scalar: RTreeObject<Envelope = AABB<[f64; 2]>>,
+    G::ScalarGeo: Into<geo::Geometry>,
+{
+    let tree = envelope_tree(geometries);
+
+    let mut indices = MutablePrimitiveArray::with_capacity(points.len() * k);
+    let mut distances = MutablePrimitiveArray::with_capacity(points.len() * k);
+    for maybe_point in points.iter_geo() {
+        let neighbors = match maybe_point {
+            Some(point) => k_nearest(&tree, &point, k, |idx| geometries.value_as_geo(idx).into()),
+            None => vec![],
+        };
+
+        for (idx, distance) in &neighbors {
+            indices.push(Some(*idx as u32));
+            distances.push(Some(*distance));
+        }
+        for _ in neighbors.len()..k {
+            indices.push(None);
+            distances.push(None);
+        }
+    }
+
+    (indices.into(), distances.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::LineStringArray;
+    use arrow2::array::Array;
+    use geo::{line_string, point};
+
+    #[test]
+    fn snap_to_roads() {
+        let roads: LineStringArray = vec![
+            Some(line_string![(x: 0., y: 0.), (x: 10., y: 0.)]),
+            None,
+            Some(line_string![(x: 0., y: 5.), (x: 10., y: 5.)]),
+            Some(line_string![(x: 20., y: 0.), (x: 20., y: 10.)]),
+        ]
+        .into();
+        let points: PointArray = vec![
+            Some(point!(x: 2., y: 1.)),
+            None,
+            Some(point!(x: 18., y: 4.)),
+        ]
+        .into();
+
+        let (indices, distances) = nearest_neighbor(&points, &roads, 2);
+        assert_eq!(indices.len(), 6);
+        assert_eq!(
+            indices.iter().map(|v| v.copied()).collect::<Vec<_>>(),
+            vec![Some(0), Some(2), None, None, Some(3), Some(2)]
+        );
+        assert_eq!(distances.value(0), 1.);
+        assert_eq!(distances.value(1), 4.);
+        assert!(distances.is_null(2));
+        assert_eq!(distances.value(4), 2.);
+        assert_eq!(distances.value(5), 65_f64.sqrt());
+
+        // Only three roads exist
+        let (indices, _) = nearest_neighbor(&points, &roads, 4);
+        assert!(indices.is_valid(2) && indices.is_null(3));
+    }
+}
@@ -0,0 +1,143 @@
+//! Binary spatial predicates returning a [`BooleanArray`].
+//!
+//! Each predicate is available elementwise, comparing the geometries at the same index of two
+//! arrays, and broadcast, comparing every geometry of an array against a single geometry. Null
+//! geometries produce null results. Rows are evaluated in parallel with the `rayon` feature.
+
+use crate::crs::check_crs;
+use crate::error::GeoArrowError;
+use crate::parallel::map_rows;
+use crate::{GeometryArray, GeometryArrayTrait};
+use arrow2::array::BooleanArray;
+use geo::{Contains, Intersects};
+
+/// Apply `predicate` to the geometries at the same index of `left` and `right`, or `None` if
+/// either is null.
+pub(crate) fn elementwise<T: Send>(
+    left: &GeometryArray,
+    right: &GeometryArray,
+    predicate: impl Fn(&geo::Geometry, &geo::Geometry) -> T + Sync + Send,
+) -> Result<Vec<Option<T>>, GeoArrowError> {
+    if left.len() != right.len() {
+        return Err(GeoArrowError::General(
+            "both arrays must have the same length".to_string(),
+        ));
+    }
+    check_crs(left.crs(), right.crs())?;
+
+    Ok(map_rows(left.len(), |i| {
+        match (left.get_as_geo(i), right.get_as_geo(i)) {
+            (Some(left), Some(right)) => Some(predicate(&left, &right)),
+            _ => None,
+        }
+    }))
+}
+
+/// Apply `predicate` to each geometry of `array` and `other`, or `None` for null geometries.
+pub(crate) fn broadcast<T: Send>(
+    array: &GeometryArray,
+    other: &geo::Geometry,
+    predicate: impl Fn(&geo::Geometry, &geo::Geometry) -> T + Sync + Send,
+) -> Vec<Option<T>> {
+    map_rows(array.len(), |i| {
+        array.get_as_geo(i).map(|geom| predicate(&geom, other))
+    })
+}
+
+/// Whether each geometry of `left` contains the geometry at the same index of `right`.
+///
+/// Errors if the arrays differ in length or CRS.
+pub fn contains(
+    left: &GeometryArray,
+    right: &GeometryArray,
+) -> Result<BooleanArray, GeoArrowError> {
+    elementwise(left, right, |left, right| left.contains(right)).map(Into::into)
+}
+
+/// Whether each geometry of `array` contains `other`.
+pub fn contains_geometry(array: &GeometryArray, other: &geo::Geometry) -> BooleanArray {
+    broadcast(array, other, |geom, other| geom.contains(other)).into()
+}
+
+/// Whether each geometry of `left` intersects the geometry at the same index of `right`.
+///
+/// Errors if the arrays differ in length or CRS.
+pub fn intersects(
+    left: &GeometryArray,
+    right: &GeometryArray,
+) -> Result<BooleanArray, GeoArrowError> {
+    elementwise(left, right, |left, right| left.intersects(right)).map(Into::into)
+}
+
+/// Whether each geometry of `array` intersects `other`.
+pub fn intersects_geometry(array: &GeometryArray, other: &geo::Geometry) -> BooleanArray {
+    broadcast(array, other, |geom, other| geom.intersects(other)).into()
+}
+
+/// Whether each geometry of `left` is within the geometry at the same index of `right`.
+///
+/// Errors if the arrays differ in length or CRS.
+pub fn within(left: &GeometryArray, right: &GeometryArray) -> Result<BooleanArray, GeoArrowError> {
+    elementwise(left, right, |left, right| right.contains(left)).map(Into::into)
+}
+
+/// Whether each geometry of `array` is within `other`.
+pub fn within_geometry(array: &GeometryArray, other: &geo::Geometry) -> BooleanArray {
+    broadcast(array, other, |geom, other| other.contains(geom)).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{PointArray, PolygonArray};
+    use geo::{point, polygon};
+
+    fn square() -> geo::Polygon {
+        polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.)]
+    }
+
+    fn points() -> GeometryArray {
+        let arr: PointArray = vec![
+            Some(point!(x: 1., y: 1.)),
+            Some(point!(x: 5., y: 5.)),
+            None,
+            Some(point!(x: 2., y: 1.)),
+        ]
+        .into();
+        GeometryArray::Point(arr)
+    }
+
+    #[test]
+    fn elementwise_predicates() {
+        let polygons: PolygonArray = vec![square(), square(), square(), square()].into();
+        let polygons = GeometryArray::Polygon(polygons);
+
+        let expected = BooleanArray::from([Some(true), Some(false), None, Some(false)]);
+        assert_eq!(contains(&polygons, &points()).unwrap(), expected);
+        assert_eq!(within(&points(), &polygons).unwrap(), expected);
+
+        // Boundary points intersect but are not contained
+        let expected = BooleanArray::from([Some(true), Some(false), None, Some(true)]);
+        assert_eq!(intersects(&points(), &polygons).unwrap(), expected);
+
+        let short = GeometryArray::Polygon(vec![square()].into());
+        assert!(contains(&short, &points()).is_err());
+    }
+
+    #[test]
+    fn broadcast_predicates() {
+        let geom = geo::Geometry::Polygon(square());
+        let expected = BooleanArray::from([Some(true), Some(false), None, Some(false)]);
+        assert_eq!(within_geometry(&points(), &geom), expected);
+
+        let expected = BooleanArray::from([Some(true), Some(false), None, Some(true)]);
+        assert_eq!(intersects_geometry(&points(), &geom), expected);
+
+        let polygons = GeometryArray::Polygon(vec![Some(square()), None].into());
+        let point = geo::Geometry::Point(point!(x: 1., y: 1.));
+        assert_eq!(
+            contains_geometry(&polygons, &point),
+            BooleanArray::from([Some(true), None])
+        );
+    }
+}
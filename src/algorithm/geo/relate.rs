@@ -0,0 +1,192 @@
+//! DE-9IM intersection matrices.
+//!
+//! The [DE-9IM](https://en.wikipedia.org/wiki/DE-9IM) matrix of two geometries records the
+//! dimension of the intersection of the interior, boundary and exterior of one with those of the
+//! other. It describes every topological relationship between them, where the predicates of
+//! [`predicates`](super::predicates) each answer one question.
+//!
+//! Matrices are written as nine characters, row by row: `F` for an empty intersection and `0`,
+//! `1` or `2` for its dimension. Patterns match matrices cell by cell, with `T` matching any
+//! non-empty intersection and `*` matching anything, so `T*F**F***` tests that the first
+//! geometry is within the second.
+
+use crate::algorithm::geo::predicates::{broadcast, elementwise};
+use crate::error::GeoArrowError;
+use crate::GeometryArray;
+use arrow2::array::{BooleanArray, Utf8Array};
+use geo::coordinate_position::CoordPos;
+use geo::dimensions::Dimensions;
+use geo::relate::IntersectionMatrix;
+use geo::Relate;
+
+/// The positions of the rows and columns of a matrix, in order.
+const POSITIONS: [CoordPos; 3] = [CoordPos::Inside, CoordPos::OnBoundary, CoordPos::Outside];
+
+/// The cells of `matrix`, row by row.
+fn cells(matrix: &IntersectionMatrix) -> impl Iterator<Item = Dimensions> + '_ {
+    POSITIONS.into_iter().flat_map(move |left| {
+        POSITIONS
+            .into_iter()
+            .map(move |right| matrix.get(left, right))
+    })
+}
+
+/// The nine character string of `matrix`.
+fn matrix_string(matrix: &IntersectionMatrix) -> String {
+    cells(matrix)
+        .map(|dimensions| match dimensions {
+            Dimensions::Empty => 'F',
+            Dimensions::ZeroDimensional => '0',
+            Dimensions::OneDimensional => '1',
+            Dimensions::TwoDimensional => '2',
+        })
+        .collect()
+}
+
+/// Check that `pattern` is a valid DE-9IM pattern.
+fn parse_pattern(pattern: &str) -> Result<[u8; 9], GeoArrowError> {
+    pattern
+        .as_bytes()
+        .try_into()
+        .ok()
+        .filter(|pattern: &[u8; 9]| {
+            pattern
+                .iter()
+                .all(|c| matches!(c.to_ascii_uppercase(), b'T' | b'F' | b'*' | b'0'..=b'2'))
+        })
+        .ok_or_else(|| {
+            GeoArrowError::General(format!(
+                "invalid DE-9IM pattern {pattern:?}: expected nine of T, F, *, 0, 1 and 2"
+            ))
+        })
+}
+
+/// Whether `matrix` matches the parsed `pattern`.
+fn matches(matrix: &IntersectionMatrix, pattern: &[u8; 9]) -> bool {
+    cells(matrix)
+        .zip(pattern)
+        .all(|(dimensions, c)| match c.to_ascii_uppercase() {
+            b'T' => dimensions != Dimensions::Empty,
+            b'F' => dimensions == Dimensions::Empty,
+            b'0' => dimensions == Dimensions::ZeroDimensional,
+            b'1' => dimensions == Dimensions::OneDimensional,
+            b'2' => dimensions == Dimensions::TwoDimensional,
+            _ => true,
+        })
+}
+
+/// The DE-9IM matrix of each geometry of `left` and the geometry at the same index of `right`.
+///
+/// Errors if the arrays differ in length or CRS.
+pub fn relate(
+    left: &GeometryArray,
+    right: &GeometryArray,
+) -> Result<Utf8Array<i32>, GeoArrowError> {
+    elementwise(left, right, |left, right| {
+        matrix_string(&left.relate(right))
+    })
+    .map(Utf8Array::from)
+}
+
+/// The DE-9IM matrix of each geometry of `array` and `other`.
+pub fn relate_geometry(array: &GeometryArray, other: &geo::Geometry) -> Utf8Array<i32> {
+    Utf8Array::from(broadcast(array, other, |geom, other| {
+        matrix_string(&geom.relate(other))
+    }))
+}
+
+/// Whether the DE-9IM matrix of each geometry of `left` and the geometry at the same index of
+/// `right` matches `pattern`.
+///
+/// Errors if `pattern` is not nine of `T`, `F`, `*`, `0`, `1` and `2`, or if the arrays differ
+/// in length or CRS.
+pub fn relate_pattern(
+    left: &GeometryArray,
+    right: &GeometryArray,
+    pattern: &str,
+) -> Result<BooleanArray, GeoArrowError> {
+    let pattern = parse_pattern(pattern)?;
+    elementwise(left, right, |left, right| {
+        matches(&left.relate(right), &pattern)
+    })
+    .map(Into::into)
+}
+
+/// Whether the DE-9IM matrix of each geometry of `array` and `other` matches `pattern`.
+///
+/// Errors if `pattern` is not nine of `T`, `F`, `*`, `0`, `1` and `2`.
+pub fn relate_pattern_geometry(
+    array: &GeometryArray,
+    other: &geo::Geometry,
+    pattern: &str,
+) -> Result<BooleanArray, GeoArrowError> {
+    let pattern = parse_pattern(pattern)?;
+    Ok(broadcast(array, other, |geom, other| {
+        matches(&geom.relate(other), &pattern)
+    })
+    .into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{PointArray, PolygonArray};
+    use geo::{point, polygon};
+
+    fn square() -> geo::Polygon {
+        polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.)]
+    }
+
+    fn points() -> GeometryArray {
+        let arr: PointArray = vec![
+            Some(point!(x: 1., y: 1.)),
+            Some(point!(x: 2., y: 1.)),
+            None,
+            Some(point!(x: 5., y: 5.)),
+        ]
+        .into();
+        GeometryArray::Point(arr)
+    }
+
+    #[test]
+    fn matrices() {
+        let polygon = geo::Geometry::Polygon(square());
+        let matrices = relate_geometry(&points(), &polygon);
+        assert_eq!(
+            matrices.iter().collect::<Vec<_>>(),
+            vec![
+                Some("0FFFFF212"),
+                Some("F0FFFF212"),
+                None,
+                Some("FF0FFF212")
+            ]
+        );
+
+        let polygons = GeometryArray::Polygon(vec![square(), square()].into());
+        assert!(relate(&points(), &polygons).is_err());
+    }
+
+    #[test]
+    fn patterns() {
+        let polygons: PolygonArray = vec![square(), square(), square(), square()].into();
+        let polygons = GeometryArray::Polygon(polygons);
+
+        // Within
+        let expected = BooleanArray::from([Some(true), Some(false), None, Some(false)]);
+        assert_eq!(
+            relate_pattern(&points(), &polygons, "T*F**F***").unwrap(),
+            expected
+        );
+
+        // Touches
+        let expected = BooleanArray::from([Some(false), Some(true), None, Some(false)]);
+        let polygon = geo::Geometry::Polygon(square());
+        assert_eq!(
+            relate_pattern_geometry(&points(), &polygon, "F0*******").unwrap(),
+            expected
+        );
+
+        assert!(parse_pattern("T*F**F**").is_err());
+        assert!(parse_pattern("T*F**F**X").is_err());
+    }
+}
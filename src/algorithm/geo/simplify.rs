@@ -0,0 +1,952 @@
+use super::validation::{multi_polygon_is_valid, polygon_is_valid};
+use crate::binary::{
+    ewkb_srid, geometry_type_id, is_ewkb, wkb_coords, GeometryTypeId, WkbGeometryType,
+};
+use crate::error::GeoArrowError;
+use crate::parallel::map_rows;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use arrow2::array::BinaryArray;
+use geo::algorithm::simplify::Simplify as GeoSimplify;
+use geo::algorithm::simplify_vw::{
+    SimplifyVw as GeoSimplifyVw, SimplifyVwPreserve as GeoSimplifyVwPreserve,
+};
+use geo::CoordsIter;
+
+/// Simplifies a geometry array using the [Ramer–Douglas–Peucker
+/// algorithm](https://en.wikipedia.org/wiki/Ramer–Douglas–Peucker_algorithm).
+///
+/// Polygon rings and line strings are simplified independently, and the output's offsets are
+/// rebuilt to match the reduced coordinates. Points, multi points and rects are returned
+/// unchanged.
+/// An epsilon less than or equal to zero returns an unaltered copy of the array.
+///
+/// WKB geometries keep their z and m values, their SRID and their dialect: each vertex kept by
+/// the simplification keeps its z and m values, and geometries that are not simplified keep
+/// their bytes. This holds for every simplification trait of this module, whose WKB
+/// implementations return an error if a simplified geometry cannot be encoded.
+pub trait Simplify: Sized {
+    fn simplify(&self, epsilon: &f64) -> Result<Self, GeoArrowError>;
+}
+
+/// Simplifies a geometry array using the [Visvalingam-Whyatt
+/// algorithm](https://www.tandfonline.com/doi/abs/10.1179/000870493786962263).
+///
+/// Points and multi points are returned unchanged. The output geometries may be invalid; use
+/// [`SimplifyVwPreserve`] to avoid self-intersections.
+pub trait SimplifyVw: Sized {
+    fn simplify_vw(&self, epsilon: &f64) -> Result<Self, GeoArrowError>;
+}
+
+/// Simplifies a geometry array using a topology-preserving variant of the
+/// Visvalingam-Whyatt algorithm, which will not produce self-intersections.
+///
+/// Points and multi points are returned unchanged.
+pub trait SimplifyVwPreserve: Sized {
+    fn simplify_vw_preserve(&self, epsilon: &f64) -> Result<Self, GeoArrowError>;
+}
+
+/// Simplifies a polygonal geometry array with the Ramer–Douglas–Peucker algorithm, never
+/// producing invalid geometries.
+///
+/// Each simplified geometry is re-validated. When simplification at `epsilon` makes a row
+/// invalid (collapsed or self-intersecting rings, holes crossing or escaping their shell), the
+/// row is retried with successively halved tolerances, and left unchanged if none of them
+/// produces a valid geometry.
+pub trait SimplifyPreservingValidity: Sized {
+    fn simplify_preserving_validity(&self, epsilon: &f64) -> Result<Self, GeoArrowError>;
+}
+
+/// Simplifies each geometry with the Ramer–Douglas–Peucker algorithm to at most a given number
+/// of vertices, e.g. to cap the payload size of a web response.
+///
+/// The tolerance is chosen per geometry: it is the smallest tolerance (found by bisection) whose
+/// output fits the budget, so geometries already within the budget are returned unchanged. The
+/// budget is best effort, because simplification never removes the endpoints of a line string or
+/// the closing vertices of a ring; geometries that cannot be reduced enough are returned as
+/// simplified as possible. Points, multi points and rects are returned unchanged.
+pub trait SimplifyToVertexCount: Sized {
+    fn simplify_to_vertex_count(&self, max_vertices: usize) -> Result<Self, GeoArrowError>;
+}
+
+/// Number of bisection steps used to search for the tolerance meeting a vertex budget.
+const VERTEX_BUDGET_BISECTIONS: usize = 32;
+
+/// Simplify `geom` with the smallest tolerance whose output has at most `max_vertices`
+/// coordinates.
+fn simplify_to_budget<G>(geom: &G, max_vertices: usize) -> G
+where
+    G: GeoSimplify<f64> + Clone,
+    for<'a> G: CoordsIter<'a, Scalar = f64>,
+{
+    if geom.coords_count() <= max_vertices {
+        return geom.clone();
+    }
+
+    // No vertex lies further than the diagonal of the bounding box from any segment, so this
+    // tolerance simplifies as far as possible
+    let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+    let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for coord in geom.coords_iter() {
+        min_x = min_x.min(coord.x);
+        min_y = min_y.min(coord.y);
+        max_x = max_x.max(coord.x);
+        max_y = max_y.max(coord.y);
+    }
+    let mut high = (max_x - min_x).hypot(max_y - min_y);
+    let mut best = geom.simplify(&high);
+    if best.coords_count() > max_vertices {
+        return best;
+    }
+
+    let mut low = 0.0;
+    for _ in 0..VERTEX_BUDGET_BISECTIONS {
+        let tolerance = (low + high) / 2.0;
+        let simplified = geom.simplify(&tolerance);
+        if simplified.coords_count() <= max_vertices {
+            high = tolerance;
+            best = simplified;
+        } else {
+            low = tolerance;
+        }
+    }
+    best
+}
+
+/// Number of times the tolerance is halved before a row is left unsimplified.
+const MAX_TOLERANCE_HALVINGS: usize = 8;
+
+/// Simplify `geom` with the largest tolerance out of `epsilon`, `epsilon / 2`, ... for which
+/// `is_valid` accepts the output, falling back to the input geometry.
+fn simplify_valid<G: GeoSimplify<f64> + Clone>(
+    geom: &G,
+    epsilon: f64,
+    is_valid: impl Fn(&G) -> bool,
+) -> G {
+    (0..MAX_TOLERANCE_HALVINGS)
+        .map(|halvings| epsilon / 2_f64.powi(halvings as i32))
+        .map(|tolerance| geom.simplify(&tolerance))
+        .find(|simplified| is_valid(simplified))
+        .unwrap_or_else(|| geom.clone())
+}
+
+impl SimplifyPreservingValidity for PolygonArray {
+    fn simplify_preserving_validity(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+        let output_geoms: Vec<Option<geo::Polygon>> = map_rows(self.len(), |i| {
+            self.get_as_geo(i)
+                .map(|geom| simplify_valid(&geom, *epsilon, polygon_is_valid))
+        });
+
+        Ok(Self::from(output_geoms).with_crs(self.crs()))
+    }
+}
+
+impl SimplifyPreservingValidity for MultiPolygonArray {
+    fn simplify_preserving_validity(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+        let output_geoms: Vec<Option<geo::MultiPolygon>> = map_rows(self.len(), |i| {
+            self.get_as_geo(i)
+                .map(|geom| simplify_valid(&geom, *epsilon, multi_polygon_is_valid))
+        });
+
+        Ok(Self::from(output_geoms).with_crs(self.crs()))
+    }
+}
+
+/// Whether a simplified geometry is valid. Simplification cannot make line strings invalid.
+trait SimplifiedIsValid {
+    fn simplified_is_valid(&self) -> bool {
+        true
+    }
+}
+
+impl SimplifiedIsValid for geo::LineString {}
+impl SimplifiedIsValid for geo::MultiLineString {}
+
+impl SimplifiedIsValid for geo::Polygon {
+    fn simplified_is_valid(&self) -> bool {
+        polygon_is_valid(self)
+    }
+}
+
+impl SimplifiedIsValid for geo::MultiPolygon {
+    fn simplified_is_valid(&self) -> bool {
+        multi_polygon_is_valid(self)
+    }
+}
+
+impl SimplifyPreservingValidity for GeometryArray {
+    /// Polygonal arrays are guarded against invalid output; other arrays are simplified as by
+    /// [`Simplify`], since simplification cannot make them invalid.
+    fn simplify_preserving_validity(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+        Ok(match self {
+            GeometryArray::Polygon(arr) => {
+                GeometryArray::Polygon(arr.simplify_preserving_validity(epsilon)?)
+            }
+            GeometryArray::MultiPolygon(arr) => {
+                GeometryArray::MultiPolygon(arr.simplify_preserving_validity(epsilon)?)
+            }
+            GeometryArray::WKB(arr) => {
+                GeometryArray::WKB(arr.simplify_preserving_validity(epsilon)?)
+            }
+            GeometryArray::Mixed(arr) => {
+                GeometryArray::Mixed(Box::new(arr.simplify_preserving_validity(epsilon)?))
+            }
+            arr => arr.simplify(epsilon)?,
+        })
+    }
+}
+
+macro_rules! identity_impl {
+    ($type:ty) => {
+        impl Simplify for $type {
+            fn simplify(&self, _epsilon: &f64) -> Result<Self, GeoArrowError> {
+                Ok(self.clone())
+            }
+        }
+
+        impl SimplifyToVertexCount for $type {
+            fn simplify_to_vertex_count(
+                &self,
+                _max_vertices: usize,
+            ) -> Result<Self, GeoArrowError> {
+                Ok(self.clone())
+            }
+        }
+
+        impl SimplifyVw for $type {
+            fn simplify_vw(&self, _epsilon: &f64) -> Result<Self, GeoArrowError> {
+                Ok(self.clone())
+            }
+        }
+
+        impl SimplifyVwPreserve for $type {
+            fn simplify_vw_preserve(&self, _epsilon: &f64) -> Result<Self, GeoArrowError> {
+                Ok(self.clone())
+            }
+        }
+    };
+}
+
+identity_impl!(PointArray);
+identity_impl!(MultiPointArray);
+identity_impl!(RectArray);
+
+macro_rules! geo_impl {
+    ($type:ty, $geo_type:ty) => {
+        impl Simplify for $type {
+            fn simplify(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+                let output_geoms: Vec<Option<$geo_type>> = map_rows(self.len(), |i| {
+                    self.get_as_geo(i).map(|geom| geom.simplify(epsilon))
+                });
+
+                Ok(Self::from(output_geoms).with_crs(self.crs()))
+            }
+        }
+
+        impl SimplifyToVertexCount for $type {
+            fn simplify_to_vertex_count(&self, max_vertices: usize) -> Result<Self, GeoArrowError> {
+                let output_geoms: Vec<Option<$geo_type>> = map_rows(self.len(), |i| {
+                    self.get_as_geo(i)
+                        .map(|geom| simplify_to_budget(&geom, max_vertices))
+                });
+
+                Ok(Self::from(output_geoms).with_crs(self.crs()))
+            }
+        }
+
+        impl SimplifyVw for $type {
+            fn simplify_vw(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+                let output_geoms: Vec<Option<$geo_type>> = map_rows(self.len(), |i| {
+                    self.get_as_geo(i).map(|geom| geom.simplify_vw(epsilon))
+                });
+
+                Ok(Self::from(output_geoms).with_crs(self.crs()))
+            }
+        }
+
+        impl SimplifyVwPreserve for $type {
+            fn simplify_vw_preserve(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+                let output_geoms: Vec<Option<$geo_type>> = map_rows(self.len(), |i| {
+                    self.get_as_geo(i)
+                        .map(|geom| geom.simplify_vw_preserve(epsilon))
+                });
+
+                Ok(Self::from(output_geoms).with_crs(self.crs()))
+            }
+        }
+    };
+}
+
+geo_impl!(LineStringArray, geo::LineString);
+geo_impl!(PolygonArray, geo::Polygon);
+geo_impl!(MultiLineStringArray, geo::MultiLineString);
+geo_impl!(MultiPolygonArray, geo::MultiPolygon);
+
+/// Mixed arrays are simplified child by child, which keeps their type ids and offsets.
+macro_rules! mixed_impl {
+    ($trait:ident, $method:ident, $arg:ty) => {
+        impl $trait for MixedGeometryArray {
+            fn $method(&self, arg: $arg) -> Result<Self, GeoArrowError> {
+                let points = self.points().$method(arg)?;
+                let line_strings = self.line_strings().$method(arg)?;
+                let polygons = self.polygons().$method(arg)?;
+                let multi_points = self.multi_points().$method(arg)?;
+                let multi_line_strings = self.multi_line_strings().$method(arg)?;
+                let multi_polygons = self.multi_polygons().$method(arg)?;
+                Ok(self.map_children(
+                    |_| points,
+                    |_| line_strings,
+                    |_| polygons,
+                    |_| multi_points,
+                    |_| multi_line_strings,
+                    |_| multi_polygons,
+                ))
+            }
+        }
+    };
+}
+
+mixed_impl!(Simplify, simplify, &f64);
+mixed_impl!(SimplifyToVertexCount, simplify_to_vertex_count, usize);
+mixed_impl!(SimplifyVw, simplify_vw, &f64);
+mixed_impl!(SimplifyVwPreserve, simplify_vw_preserve, &f64);
+
+/// Writes simplified geometries as WKB in the dialect and dimensions of their input, taking the z
+/// and m values of each vertex from the input vertex it was kept from.
+struct SimplifiedWriter {
+    /// The type of the input geometry, whose dimensions are those of every nested geometry.
+    type_id: GeometryTypeId,
+    /// Whether the input is EWKB, and its SRID, if any.
+    ewkb: bool,
+    srid: Option<i32>,
+    /// The coordinates of the input, and the index of the first one not yet matched.
+    input: Vec<[f64; 4]>,
+    next: usize,
+    values: Vec<u8>,
+}
+
+impl SimplifiedWriter {
+    fn new(input: &[u8]) -> Result<Self, String> {
+        Ok(Self {
+            type_id: geometry_type_id(input)?,
+            ewkb: is_ewkb(input),
+            srid: ewkb_srid(input),
+            input: wkb_coords(input)?,
+            next: 0,
+            values: Vec::with_capacity(input.len()),
+        })
+    }
+
+    fn header(&mut self, geometry_type: WkbGeometryType, top_level: bool) {
+        let type_id = GeometryTypeId {
+            geometry_type,
+            ..self.type_id
+        };
+        let srid = self.srid.filter(|_| top_level);
+        let code = match self.ewkb {
+            true => type_id.ewkb_code(srid.is_some()),
+            false => type_id.iso_code(),
+        };
+        self.values.push(1);
+        self.values.extend(code.to_le_bytes());
+        if let Some(srid) = srid {
+            self.values.extend(srid.to_le_bytes());
+        }
+    }
+
+    fn count(&mut self, count: usize) {
+        self.values.extend((count as u32).to_le_bytes());
+    }
+
+    /// Write `coord` with the z and m values of the next input coordinate at the same position.
+    /// Simplification only removes vertices, so the output coordinates are found in order.
+    fn coord(&mut self, coord: &geo::Coord) -> Result<(), String> {
+        let offset = self.input[self.next..]
+            .iter()
+            .position(|[x, y, ..]| *x == coord.x && *y == coord.y)
+            .ok_or_else(|| format!("vertex ({} {}) is not in the input", coord.x, coord.y))?;
+        let [x, y, z, m] = self.input[self.next + offset];
+        self.next += offset + 1;
+        self.values.extend(x.to_le_bytes());
+        self.values.extend(y.to_le_bytes());
+        if self.type_id.has_z {
+            self.values.extend(z.to_le_bytes());
+        }
+        if self.type_id.has_m {
+            self.values.extend(m.to_le_bytes());
+        }
+        Ok(())
+    }
+
+    fn line_string(&mut self, line: &geo::LineString, top_level: bool) -> Result<(), String> {
+        self.header(WkbGeometryType::LineString, top_level);
+        self.count(line.0.len());
+        line.0.iter().try_for_each(|coord| self.coord(coord))
+    }
+
+    fn polygon(&mut self, polygon: &geo::Polygon, top_level: bool) -> Result<(), String> {
+        self.header(WkbGeometryType::Polygon, top_level);
+        self.count(polygon.interiors().len() + 1);
+        std::iter::once(polygon.exterior())
+            .chain(polygon.interiors())
+            .try_for_each(|ring| {
+                self.count(ring.0.len());
+                ring.0.iter().try_for_each(|coord| self.coord(coord))
+            })
+    }
+
+    fn geometry(&mut self, geom: &geo::Geometry, top_level: bool) -> Result<(), String> {
+        match geom {
+            geo::Geometry::Point(point) => {
+                self.header(WkbGeometryType::Point, top_level);
+                self.coord(&point.0)
+            }
+            geo::Geometry::LineString(line) => self.line_string(line, top_level),
+            geo::Geometry::Polygon(polygon) => self.polygon(polygon, top_level),
+            geo::Geometry::MultiPoint(points) => {
+                self.header(WkbGeometryType::MultiPoint, top_level);
+                self.count(points.0.len());
+                points.0.iter().try_for_each(|point| {
+                    self.header(WkbGeometryType::Point, false);
+                    self.coord(&point.0)
+                })
+            }
+            geo::Geometry::MultiLineString(lines) => {
+                self.header(WkbGeometryType::MultiLineString, top_level);
+                self.count(lines.0.len());
+                lines
+                    .0
+                    .iter()
+                    .try_for_each(|line| self.line_string(line, false))
+            }
+            geo::Geometry::MultiPolygon(polygons) => {
+                self.header(WkbGeometryType::MultiPolygon, top_level);
+                self.count(polygons.0.len());
+                polygons
+                    .0
+                    .iter()
+                    .try_for_each(|polygon| self.polygon(polygon, false))
+            }
+            geo::Geometry::GeometryCollection(geoms) => {
+                self.header(WkbGeometryType::GeometryCollection, top_level);
+                self.count(geoms.0.len());
+                geoms
+                    .0
+                    .iter()
+                    .try_for_each(|geom| self.geometry(geom, false))
+            }
+            geom => Err(format!("{geom:?} has no WKB type")),
+        }
+    }
+}
+
+/// Simplify each geometry of `array` after parsing it, re-encoding the output as WKB in the
+/// dialect and dimensions of the input. Geometries that are not valid WKB cannot be simplified,
+/// and are left unchanged, as are geometries that simplification does not change.
+fn simplify_wkb(
+    array: &WKBArray,
+    simplify: impl Fn(geo::Geometry) -> geo::Geometry + Sync,
+) -> Result<WKBArray, GeoArrowError> {
+    let output_geoms = map_rows(array.len(), |i| {
+        let Some(wkb) = array.get(i) else {
+            return Ok(None);
+        };
+        let input = wkb.as_slice();
+        let geom = match wkb.to_typed_scalar() {
+            Ok(geom) => geom,
+            Err(_) => return Ok(Some(input.to_vec())),
+        };
+        let simplified = simplify(geom.clone());
+        if simplified == geom {
+            return Ok(Some(input.to_vec()));
+        }
+
+        SimplifiedWriter::new(input)
+            .and_then(|mut writer| {
+                writer.geometry(&simplified, true)?;
+                Ok(Some(writer.values))
+            })
+            .map_err(|message| {
+                GeoArrowError::General(format!(
+                    "cannot encode the simplified geometry at row {i}: {message}"
+                ))
+            })
+    });
+    let output_geoms = output_geoms
+        .into_iter()
+        .collect::<Result<Vec<Option<Vec<u8>>>, _>>()?;
+
+    Ok(WKBArray::new(BinaryArray::from(output_geoms)).with_crs(array.crs()))
+}
+
+/// WKB arrays are simplified geometry by geometry, as their native equivalents would be: line
+/// strings, polygons and their multi geometries are simplified, including those nested in
+/// geometry collections, and other geometries are returned unchanged.
+macro_rules! wkb_impl {
+    ($trait:ident, $method:ident, $param:ident: $arg:ty, $geom:ident => $simplify:expr) => {
+        impl $trait for WKBArray {
+            fn $method(&self, $param: $arg) -> Result<Self, GeoArrowError> {
+                fn simplify_geometry(geom: geo::Geometry, $param: $arg) -> geo::Geometry {
+                    match geom {
+                        geo::Geometry::LineString($geom) => geo::Geometry::LineString($simplify),
+                        geo::Geometry::Polygon($geom) => geo::Geometry::Polygon($simplify),
+                        geo::Geometry::MultiLineString($geom) => {
+                            geo::Geometry::MultiLineString($simplify)
+                        }
+                        geo::Geometry::MultiPolygon($geom) => {
+                            geo::Geometry::MultiPolygon($simplify)
+                        }
+                        geo::Geometry::GeometryCollection(geoms) => {
+                            geo::Geometry::GeometryCollection(geo::GeometryCollection(
+                                geoms
+                                    .0
+                                    .into_iter()
+                                    .map(|geom| simplify_geometry(geom, $param))
+                                    .collect(),
+                            ))
+                        }
+                        geom => geom,
+                    }
+                }
+
+                simplify_wkb(self, |geom| simplify_geometry(geom, $param))
+            }
+        }
+    };
+}
+
+wkb_impl!(Simplify, simplify, epsilon: &f64, geom => geom.simplify(epsilon));
+wkb_impl!(SimplifyVw, simplify_vw, epsilon: &f64, geom => geom.simplify_vw(epsilon));
+wkb_impl!(SimplifyVwPreserve, simplify_vw_preserve, epsilon: &f64, geom => {
+    geom.simplify_vw_preserve(epsilon)
+});
+wkb_impl!(SimplifyToVertexCount, simplify_to_vertex_count, max_vertices: usize, geom => {
+    simplify_to_budget(&geom, max_vertices)
+});
+wkb_impl!(SimplifyPreservingValidity, simplify_preserving_validity, epsilon: &f64, geom => {
+    simplify_valid(&geom, *epsilon, SimplifiedIsValid::simplified_is_valid)
+});
+
+impl SimplifyPreservingValidity for MixedGeometryArray {
+    fn simplify_preserving_validity(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+        let points = self.points().simplify(epsilon)?;
+        let line_strings = self.line_strings().simplify(epsilon)?;
+        let polygons = self.polygons().simplify_preserving_validity(epsilon)?;
+        let multi_points = self.multi_points().simplify(epsilon)?;
+        let multi_line_strings = self.multi_line_strings().simplify(epsilon)?;
+        let multi_polygons = self
+            .multi_polygons()
+            .simplify_preserving_validity(epsilon)?;
+        Ok(self.map_children(
+            |_| points,
+            |_| line_strings,
+            |_| polygons,
+            |_| multi_points,
+            |_| multi_line_strings,
+            |_| multi_polygons,
+        ))
+    }
+}
+
+impl Simplify for GeometryArray {
+    fn simplify(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+        Ok(match self {
+            GeometryArray::Point(arr) => GeometryArray::Point(arr.simplify(epsilon)?),
+            GeometryArray::LineString(arr) => GeometryArray::LineString(arr.simplify(epsilon)?),
+            GeometryArray::Polygon(arr) => GeometryArray::Polygon(arr.simplify(epsilon)?),
+            GeometryArray::MultiPoint(arr) => GeometryArray::MultiPoint(arr.simplify(epsilon)?),
+            GeometryArray::MultiLineString(arr) => {
+                GeometryArray::MultiLineString(arr.simplify(epsilon)?)
+            }
+            GeometryArray::MultiPolygon(arr) => GeometryArray::MultiPolygon(arr.simplify(epsilon)?),
+            GeometryArray::WKB(arr) => GeometryArray::WKB(arr.simplify(epsilon)?),
+            GeometryArray::Rect(arr) => GeometryArray::Rect(arr.simplify(epsilon)?),
+            GeometryArray::Mixed(arr) => GeometryArray::Mixed(Box::new(arr.simplify(epsilon)?)),
+        })
+    }
+}
+
+impl SimplifyToVertexCount for GeometryArray {
+    fn simplify_to_vertex_count(&self, max_vertices: usize) -> Result<Self, GeoArrowError> {
+        Ok(match self {
+            GeometryArray::Point(arr) => {
+                GeometryArray::Point(arr.simplify_to_vertex_count(max_vertices)?)
+            }
+            GeometryArray::LineString(arr) => {
+                GeometryArray::LineString(arr.simplify_to_vertex_count(max_vertices)?)
+            }
+            GeometryArray::Polygon(arr) => {
+                GeometryArray::Polygon(arr.simplify_to_vertex_count(max_vertices)?)
+            }
+            GeometryArray::MultiPoint(arr) => {
+                GeometryArray::MultiPoint(arr.simplify_to_vertex_count(max_vertices)?)
+            }
+            GeometryArray::MultiLineString(arr) => {
+                GeometryArray::MultiLineString(arr.simplify_to_vertex_count(max_vertices)?)
+            }
+            GeometryArray::MultiPolygon(arr) => {
+                GeometryArray::MultiPolygon(arr.simplify_to_vertex_count(max_vertices)?)
+            }
+            GeometryArray::WKB(arr) => {
+                GeometryArray::WKB(arr.simplify_to_vertex_count(max_vertices)?)
+            }
+            GeometryArray::Rect(arr) => {
+                GeometryArray::Rect(arr.simplify_to_vertex_count(max_vertices)?)
+            }
+            GeometryArray::Mixed(arr) => {
+                GeometryArray::Mixed(Box::new(arr.simplify_to_vertex_count(max_vertices)?))
+            }
+        })
+    }
+}
+
+impl SimplifyVw for GeometryArray {
+    fn simplify_vw(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+        Ok(match self {
+            GeometryArray::Point(arr) => GeometryArray::Point(arr.simplify_vw(epsilon)?),
+            GeometryArray::LineString(arr) => GeometryArray::LineString(arr.simplify_vw(epsilon)?),
+            GeometryArray::Polygon(arr) => GeometryArray::Polygon(arr.simplify_vw(epsilon)?),
+            GeometryArray::MultiPoint(arr) => GeometryArray::MultiPoint(arr.simplify_vw(epsilon)?),
+            GeometryArray::MultiLineString(arr) => {
+                GeometryArray::MultiLineString(arr.simplify_vw(epsilon)?)
+            }
+            GeometryArray::MultiPolygon(arr) => {
+                GeometryArray::MultiPolygon(arr.simplify_vw(epsilon)?)
+            }
+            GeometryArray::WKB(arr) => GeometryArray::WKB(arr.simplify_vw(epsilon)?),
+            GeometryArray::Rect(arr) => GeometryArray::Rect(arr.simplify_vw(epsilon)?),
+            GeometryArray::Mixed(arr) => GeometryArray::Mixed(Box::new(arr.simplify_vw(epsilon)?)),
+        })
+    }
+}
+
+impl SimplifyVwPreserve for GeometryArray {
+    fn simplify_vw_preserve(&self, epsilon: &f64) -> Result<Self, GeoArrowError> {
+        Ok(match self {
+            GeometryArray::Point(arr) => GeometryArray::Point(arr.simplify_vw_preserve(epsilon)?),
+            GeometryArray::LineString(arr) => {
+                GeometryArray::LineString(arr.simplify_vw_preserve(epsilon)?)
+            }
+            GeometryArray::Polygon(arr) => {
+                GeometryArray::Polygon(arr.simplify_vw_preserve(epsilon)?)
+            }
+            GeometryArray::MultiPoint(arr) => {
+                GeometryArray::MultiPoint(arr.simplify_vw_preserve(epsilon)?)
+            }
+            GeometryArray::MultiLineString(arr) => {
+                GeometryArray::MultiLineString(arr.simplify_vw_preserve(epsilon)?)
+            }
+            GeometryArray::MultiPolygon(arr) => {
+                GeometryArray::MultiPolygon(arr.simplify_vw_preserve(epsilon)?)
+            }
+            GeometryArray::WKB(arr) => GeometryArray::WKB(arr.simplify_vw_preserve(epsilon)?),
+            GeometryArray::Rect(arr) => GeometryArray::Rect(arr.simplify_vw_preserve(epsilon)?),
+            GeometryArray::Mixed(arr) => {
+                GeometryArray::Mixed(Box::new(arr.simplify_vw_preserve(epsilon)?))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::GeometryArrayTrait;
+    use geo::{line_string, polygon, Area};
+
+    #[test]
+    fn rdp_test() {
+        let input_geom = line_string![
+            (x: 0.0, y: 0.0 ),
+            (x: 5.0, y: 4.0 ),
+            (x: 11.0, y: 5.5 ),
+            (x: 17.3, y: 3.2 ),
+            (x: 27.8, y: 0.1 ),
+        ];
+        let input_array: LineStringArray = vec![Some(input_geom), None].into();
+        let result_array = input_array.simplify(&1.0).unwrap();
+
+        let expected = line_string![
+            ( x: 0.0, y: 0.0 ),
+            ( x: 5.0, y: 4.0 ),
+            ( x: 11.0, y: 5.5 ),
+            ( x: 27.8, y: 0.1 ),
+        ];
+
+        assert_eq!(expected, result_array.get_as_geo(0).unwrap());
+        assert!(result_array.get_as_geo(1).is_none());
+    }
+
+    #[test]
+    fn wkb_simplify() {
+        let input_geom = line_string![
+            (x: 0.0, y: 0.0 ),
+            (x: 5.0, y: 4.0 ),
+            (x: 11.0, y: 5.5 ),
+            (x: 17.3, y: 3.2 ),
+            (x: 27.8, y: 0.1 ),
+        ];
+        let collection = geo::GeometryCollection(vec![input_geom.clone().into()]);
+        let input_array: WKBArray = vec![
+            Some(input_geom.clone().into()),
+            None,
+            Some(geo::Geometry::GeometryCollection(collection)),
+        ]
+        .into();
+        let result_array = match GeometryArray::WKB(input_array).simplify(&1.0).unwrap() {
+            GeometryArray::WKB(arr) => arr,
+            _ => panic!("expected a WKB array"),
+        };
+
+        let expected = input_geom.simplify(&1.0);
+        assert_eq!(result_array.get_as_geo(0), Some(expected.clone().into()));
+        assert!(result_array.get_as_geo(1).is_none());
+        assert_eq!(
+            result_array.get_as_geo(2),
+            Some(geo::Geometry::GeometryCollection(geo::GeometryCollection(
+                vec![expected.into()]
+            )))
+        );
+
+        // Geometries that are not valid WKB are left unchanged
+        let invalid = WKBArray::new(BinaryArray::<i64>::from(vec![Some(vec![1u8, 2])]));
+        assert_eq!(
+            invalid.simplify(&1.0).unwrap().into_arrow(),
+            invalid.clone().into_arrow()
+        );
+    }
+
+    /// A little endian line string with type code `code`, followed by `srid` if any, whose
+    /// coordinates have a third value, z or m.
+    fn line_wkb(code: u32, srid: Option<i32>, coords: &[[f64; 3]]) -> Vec<u8> {
+        let mut buf = vec![1];
+        buf.extend(code.to_le_bytes());
+        if let Some(srid) = srid {
+            buf.extend(srid.to_le_bytes());
+        }
+        buf.extend((coords.len() as u32).to_le_bytes());
+        for value in coords.iter().flatten() {
+            buf.extend(value.to_le_bytes());
+        }
+        buf
+    }
+
+    #[test]
+    fn wkb_simplify_keeps_dimensions_and_crs() {
+        let coords = [
+            [0.0, 0.0, 1.],
+            [5.0, 4.0, 2.],
+            [11.0, 5.5, 3.],
+            [17.3, 3.2, 4.],
+            [27.8, 0.1, 5.],
+        ];
+        let simplified = [coords[0], coords[1], coords[2], coords[4]];
+        // LINESTRING Z as ISO WKB, SRID=4326;LINESTRING M as EWKB, and an unchanged POINT Z
+        let line_z = line_wkb(1002, None, &coords);
+        let line_m = line_wkb(0x6000_0002, Some(4326), &coords);
+        let mut point_z = vec![1];
+        point_z.extend(1001u32.to_le_bytes());
+        for value in [1f64, 2., 3.] {
+            point_z.extend(value.to_le_bytes());
+        }
+        let input = WKBArray::new(BinaryArray::<i64>::from(vec![
+            Some(line_z),
+            Some(line_m),
+            Some(point_z.clone()),
+            None,
+        ]))
+        .with_crs(Some("EPSG:4326"));
+
+        let result = input.simplify(&1.0).unwrap();
+        assert_eq!(result.crs(), Some("EPSG:4326"));
+        let value = |i: usize| result.get(i).map(|wkb| wkb.as_slice().to_vec());
+        assert_eq!(value(0), Some(line_wkb(1002, None, &simplified)));
+        assert_eq!(
+            value(1),
+            Some(line_wkb(0x6000_0002, Some(4326), &simplified))
+        );
+        assert_eq!(value(2), Some(point_z));
+        assert_eq!(value(3), None);
+
+        let result = input.simplify_preserving_validity(&1.0).unwrap();
+        assert_eq!(result.crs(), Some("EPSG:4326"));
+        assert_eq!(
+            result.get(0).unwrap().as_slice(),
+            line_wkb(1002, None, &simplified)
+        );
+    }
+
+    #[test]
+    fn polygon_simplify() {
+        let input_geom = polygon![
+            (x: 0., y: 0.),
+            (x: 0., y: 10.),
+            (x: 5., y: 11.),
+            (x: 10., y: 10.),
+            (x: 10., y: 0.),
+            (x: 0., y: 0.),
+        ];
+        let input_array: PolygonArray = vec![input_geom].into();
+        let result_array = GeometryArray::Polygon(input_array).simplify(&2.0).unwrap();
+
+        let expected = polygon![
+            (x: 0., y: 0.),
+            (x: 0., y: 10.),
+            (x: 10., y: 10.),
+            (x: 10., y: 0.),
+            (x: 0., y: 0.),
+        ];
+
+        match result_array {
+            GeometryArray::Polygon(arr) => assert_eq!(arr.get_as_geo(0), Some(expected)),
+            _ => panic!("expected a polygon array"),
+        }
+    }
+
+    #[test]
+    fn vertex_budget() {
+        let input_geom = line_string![
+            (x: 0.0, y: 0.0 ),
+            (x: 5.0, y: 4.0 ),
+            (x: 11.0, y: 5.5 ),
+            (x: 17.3, y: 3.2 ),
+            (x: 27.8, y: 0.1 ),
+        ];
+        let input_array: LineStringArray = vec![Some(input_geom.clone()), None].into();
+
+        let result = input_array.simplify_to_vertex_count(4).unwrap();
+        assert_eq!(
+            result.get_as_geo(0),
+            Some(input_array.simplify(&1.0).unwrap().value_as_geo(0))
+        );
+        assert!(result.get_as_geo(1).is_none());
+
+        assert_eq!(
+            input_array
+                .simplify_to_vertex_count(3)
+                .unwrap()
+                .value_as_geo(0)
+                .0
+                .len(),
+            3
+        );
+        assert_eq!(
+            input_array
+                .simplify_to_vertex_count(5)
+                .unwrap()
+                .value_as_geo(0),
+            input_geom
+        );
+
+        // The endpoints are always kept
+        assert_eq!(
+            input_array
+                .simplify_to_vertex_count(1)
+                .unwrap()
+                .value_as_geo(0),
+            line_string![(x: 0.0, y: 0.0), (x: 27.8, y: 0.1)]
+        );
+
+        let wkb: WKBArray = vec![Some(geo::Geometry::LineString(input_geom))].into();
+        assert_eq!(
+            wkb.simplify_to_vertex_count(4).unwrap().get_as_geo(0),
+            Some(input_array.simplify(&1.0).unwrap().value_as_geo(0).into())
+        );
+    }
+
+    #[test]
+    fn keeps_crs() {
+        let input_geom = line_string![(x: 0., y: 0.), (x: 5., y: 0.1), (x: 10., y: 0.)];
+        let input_array = LineStringArray::from(vec![input_geom]).with_crs(Some("EPSG:3857"));
+        assert_eq!(input_array.simplify(&1.0).unwrap().crs(), Some("EPSG:3857"));
+        assert_eq!(
+            input_array.simplify_vw(&1.0).unwrap().crs(),
+            Some("EPSG:3857")
+        );
+        assert_eq!(
+            input_array.simplify_to_vertex_count(2).unwrap().crs(),
+            Some("EPSG:3857")
+        );
+
+        let square = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let input_array = PolygonArray::from(vec![square]).with_crs(Some("EPSG:3857"));
+        assert_eq!(
+            input_array
+                .simplify_preserving_validity(&1.0)
+                .unwrap()
+                .crs(),
+            Some("EPSG:3857")
+        );
+    }
+
+    #[test]
+    fn visvalingam_test() {
+        let input_geom = line_string![
+            (x: 5.0, y: 2.0),
+            (x: 3.0, y: 8.0),
+            (x: 6.0, y: 20.0),
+            (x: 7.0, y: 25.0),
+            (x: 10.0, y: 10.0),
+        ];
+        let input_array: LineStringArray = vec![input_geom].into();
+        let result_array = input_array.simplify_vw(&30.0).unwrap();
+
+        let expected = line_string![
+            (x: 5.0, y: 2.0),
+            (x: 7.0, y: 25.0),
+            (x: 10.0, y: 10.0),
+        ];
+
+        assert_eq!(expected, result_array.get_as_geo(0).unwrap());
+        assert_eq!(
+            input_array
+                .simplify_vw_preserve(&30.0)
+                .unwrap()
+                .get_as_geo(0),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn preserving_validity_falls_back_to_smaller_tolerance() {
+        let square = polygon![
+            (x: 0., y: 0.),
+            (x: 10., y: 0.),
+            (x: 10., y: 10.),
+            (x: 0., y: 10.),
+            (x: 0., y: 0.),
+        ];
+        let input_array: PolygonArray = vec![Some(square.clone()), None].into();
+
+        // A tolerance larger than the square collapses its ring
+        let result = input_array.simplify_preserving_validity(&20.0).unwrap();
+        let output = result.get_as_geo(0).unwrap();
+        assert!(polygon_is_valid(&output));
+        assert_eq!(output.unsigned_area(), 100.);
+        assert!(result.get_as_geo(1).is_none());
+
+        let wkb: WKBArray = vec![Some(geo::Geometry::Polygon(square))].into();
+        let result = wkb.simplify_preserving_validity(&20.0).unwrap();
+        assert_eq!(result.get_as_geo(0).unwrap().unsigned_area(), 100.);
+    }
+
+    #[test]
+    fn validity_checks() {
+        let bowtie = polygon![
+            (x: 0., y: 0.),
+            (x: 2., y: 2.),
+            (x: 2., y: 0.),
+            (x: 0., y: 2.),
+            (x: 0., y: 0.),
+        ];
+        assert!(!polygon_is_valid(&bowtie));
+
+        let hole_outside = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)],
+            interiors: [[(x: 5., y: 5.), (x: 6., y: 5.), (x: 6., y: 6.), (x: 5., y: 5.)]],
+        );
+        assert!(!polygon_is_valid(&hole_outside));
+    }
+}
@@ -0,0 +1,300 @@
+//! Movement analytics over trajectories stored as [`LineStringArray`]s.
+//!
+//! Timestamps are supplied as a [`ListArray`] of `f64` parallel to the trajectories: row `i`
+//! holds one timestamp per vertex of trajectory `i`. Any time unit may be used; speeds are
+//! expressed in coordinate units per time unit.
+
+use crate::error::GeoArrowError;
+use crate::{GeometryArrayTrait, LineStringArray};
+use arrow2::array::{
+    Array, ListArray, MutableArray, MutableListArray, MutablePrimitiveArray, PrimitiveArray,
+    TryPush,
+};
+use geo::{coord, LineString};
+
+type MutableFloatListArray = MutableListArray<i64, MutablePrimitiveArray<f64>>;
+
+/// Coordinates of the trajectory at row `i`.
+fn trajectory_coords(trajectories: &LineStringArray, i: usize) -> (&[f64], &[f64]) {
+    let trajectory = trajectories.value(i);
+    let (start, end) = trajectory.geom_offsets.start_end(i);
+    (&trajectory.x[start..end], &trajectory.y[start..end])
+}
+
+/// Validate `timestamps` against `trajectories` and return its flat values.
+fn timestamp_values<'a>(
+    trajectories: &LineStringArray,
+    timestamps: &'a ListArray<i64>,
+) -> Result<&'a [f64], GeoArrowError> {
+    if trajectories.len() != timestamps.len() {
+        return Err(GeoArrowError::General(
+            "timestamps must have the same length as trajectories".to_string(),
+        ));
+    }
+
+    let values = timestamps
+        .values()
+        .as_any()
+        .downcast_ref::<PrimitiveArray<f64>>()
+        .ok_or_else(|| GeoArrowError::General("timestamps must be a list of f64".to_string()))?
+        .values();
+
+    for i in 0..trajectories.len() {
+        if trajectories.is_null(i) || timestamps.is_null(i) {
+            continue;
+        }
+
+        let (start, end) = timestamps.offsets().start_end(i);
+        if end - start != trajectory_coords(trajectories, i).0.len() {
+            return Err(GeoArrowError::General(format!(
+                "trajectory {} has a different number of vertices and timestamps",
+                i
+            )));
+        }
+
+        if values[start..end].windows(2).any(|w| w[1] < w[0]) {
+            return Err(GeoArrowError::General(format!(
+                "timestamps of trajectory {} must be non-decreasing",
+                i
+            )));
+        }
+    }
+
+    Ok(values)
+}
+
+/// Speed along each segment of each trajectory.
+///
+/// Row `i` of the output holds one speed per segment of trajectory `i`: the Euclidean segment
+/// length divided by its duration. Segments with zero duration have a null speed. Rows where the
+/// trajectory or its timestamps are null are null.
+pub fn segment_speeds(
+    trajectories: &LineStringArray,
+    timestamps: &ListArray<i64>,
+) -> Result<ListArray<i64>, GeoArrowError> {
+    let values = timestamp_values(trajectories, timestamps)?;
+    let mut output = MutableFloatListArray::with_capacity(trajectories.len());
+
+    for i in 0..trajectories.len() {
+        if trajectories.is_null(i) || timestamps.is_null(i) {
+            output.push_null();
+            continue;
+        }
+
+        let (x, y) = trajectory_coords(trajectories, i);
+        let (start, end) = timestamps.offsets().start_end(i);
+        let times = &values[start..end];
+
+        let speeds = (1..x.len()).map(|j| {
+            let duration = times[j] - times[j - 1];
+            if duration == 0.0 {
+                return None;
+            }
+
+            let distance = (x[j] - x[j - 1]).hypot(y[j] - y[j - 1]);
+            Some(distance / duration)
+        });
+        output
+            .try_push(Some(speeds))
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+    }
+
+    Ok(output.into())
+}
+
+/// Resample each trajectory at a fixed time interval.
+///
+/// Samples are taken at `t0`, `t0 + interval`, `t0 + 2 * interval`, ... up to the last
+/// timestamp, with positions linearly interpolated between vertices. Returns the resampled
+/// trajectories together with their timestamps.
+pub fn resample_by_time(
+    trajectories: &LineStringArray,
+    timestamps: &ListArray<i64>,
+    interval: f64,
+) -> Result<(LineStringArray, ListArray<i64>), GeoArrowError> {
+    if interval.is_nan() || interval <= 0.0 {
+        return Err(GeoArrowError::General(
+            "resampling interval must be positive".to_string(),
+        ));
+    }
+
+    let values = timestamp_values(trajectories, timestamps)?;
+    let mut geoms: Vec<Option<LineString>> = Vec::with_capacity(trajectories.len());
+    let mut output_times = MutableFloatListArray::with_capacity(trajectories.len());
+
+    for i in 0..trajectories.len() {
+        if trajectories.is_null(i) || timestamps.is_null(i) {
+            geoms.push(None);
+            output_times.push_null();
+            continue;
+        }
+
+        let (x, y) = trajectory_coords(trajectories, i);
+        let (start, end) = timestamps.offsets().start_end(i);
+        let times = &values[start..end];
+
+        let mut coords = vec![];
+        let mut sample_times = vec![];
+        if let (Some(first), Some(last)) = (times.first(), times.last()) {
+            let mut segment = 0;
+            let mut step = 0;
+            loop {
+                let t = first + step as f64 * interval;
+                if t > *last {
+                    break;
+                }
+
+                // Advance to the segment containing t
+                while segment + 2 < times.len() && times[segment + 1] < t {
+                    segment += 1;
+                }
+
+                let coord = if times.len() == 1 {
+                    coord! { x: x[0], y: y[0] }
+                } else {
+                    let duration = times[segment + 1] - times[segment];
+                    let fraction = if duration == 0.0 {
+                        0.0
+                    } else {
+                        (t - times[segment]) / duration
+                    };
+                    coord! {
+                        x: x[segment] + fraction * (x[segment + 1] - x[segment]),
+                        y: y[segment] + fraction * (y[segment + 1] - y[segment]),
+                    }
+                };
+
+                coords.push(coord);
+                sample_times.push(Some(t));
+                step += 1;
+            }
+        }
+
+        geoms.push(Some(LineString::new(coords)));
+        output_times
+            .try_push(Some(sample_times))
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+    }
+
+    Ok((geoms.into(), output_times.into()))
+}
+
+/// Resample each trajectory at a fixed distance interval along its path.
+///
+/// Samples are placed every `interval` coordinate units of Euclidean distance from the first
+/// vertex. The last vertex is always kept so that the resampled trajectory spans the original.
+pub fn resample_by_distance(
+    trajectories: &LineStringArray,
+    interval: f64,
+) -> Result<LineStringArray, GeoArrowError> {
+    if interval.is_nan() || interval <= 0.0 {
+        return Err(GeoArrowError::General(
+            "resampling interval must be positive".to_string(),
+        ));
+    }
+
+    let mut geoms: Vec<Option<LineString>> = Vec::with_capacity(trajectories.len());
+    for i in 0..trajectories.len() {
+        if trajectories.is_null(i) {
+            geoms.push(None);
+            continue;
+        }
+
+        let (x, y) = trajectory_coords(trajectories, i);
+        let mut coords = vec![];
+        if !x.is_empty() {
+            coords.push(coord! { x: x[0], y: y[0] });
+
+            // Distance along the path at the start of the current segment
+            let mut travelled = 0.0;
+            let mut next_sample = interval;
+            for j in 1..x.len() {
+                let length = (x[j] - x[j - 1]).hypot(y[j] - y[j - 1]);
+                while next_sample <= travelled + length && length > 0.0 {
+                    let fraction = (next_sample - travelled) / length;
+                    coords.push(coord! {
+                        x: x[j - 1] + fraction * (x[j] - x[j - 1]),
+                        y: y[j - 1] + fraction * (y[j] - y[j - 1]),
+                    });
+                    next_sample += interval;
+                }
+                travelled += length;
+            }
+
+            let last = coord! { x: x[x.len() - 1], y: y[y.len() - 1] };
+            if coords.last() != Some(&last) {
+                coords.push(last);
+            }
+        }
+
+        geoms.push(Some(LineString::new(coords)));
+    }
+
+    Ok(geoms.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::line_string;
+
+    fn trajectories() -> LineStringArray {
+        vec![
+            Some(line_string![(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.)]),
+            None,
+        ]
+        .into()
+    }
+
+    fn timestamps() -> ListArray<i64> {
+        let mut times = MutableFloatListArray::new();
+        times
+            .try_push(Some(vec![Some(0.), Some(5.), Some(5.)]))
+            .unwrap();
+        times.push_null();
+        times.into()
+    }
+
+    #[test]
+    fn speeds() {
+        let speeds = segment_speeds(&trajectories(), &timestamps()).unwrap();
+        let row = speeds.value(0);
+        let row = row.as_any().downcast_ref::<PrimitiveArray<f64>>().unwrap();
+        assert_eq!(row.len(), 2);
+        assert_eq!(row.value(0), 2.);
+        assert!(row.is_null(1));
+        assert!(speeds.is_null(1));
+    }
+
+    #[test]
+    fn mismatched_timestamps() {
+        let mut times = MutableFloatListArray::new();
+        times.try_push(Some(vec![Some(0.), Some(1.)])).unwrap();
+        times.push_null();
+        assert!(segment_speeds(&trajectories(), &times.into()).is_err());
+    }
+
+    #[test]
+    fn resample_time() {
+        let (resampled, times) = resample_by_time(&trajectories(), &timestamps(), 2.).unwrap();
+        assert_eq!(
+            resampled.get_as_geo(0),
+            Some(line_string![(x: 0., y: 0.), (x: 4., y: 0.), (x: 8., y: 0.)])
+        );
+        assert!(resampled.get_as_geo(1).is_none());
+
+        let row = times.value(0);
+        let row = row.as_any().downcast_ref::<PrimitiveArray<f64>>().unwrap();
+        assert_eq!(row.values().as_slice(), &[0., 2., 4.]);
+    }
+
+    #[test]
+    fn resample_distance() {
+        let resampled = resample_by_distance(&trajectories(), 8.).unwrap();
+        assert_eq!(
+            resampled.get_as_geo(0),
+            Some(line_string![(x: 0., y: 0.), (x: 8., y: 0.), (x: 10., y: 6.), (x: 10., y: 10.)])
+        );
+        assert!(resample_by_distance(&trajectories(), 0.).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Report planar lengths and areas in chosen units, using the units of the array's CRS.
+//!
+//! The planar kernels in [`length`](super::length) and [`area`](super::area) return values in
+//! coordinate units. For a projected CRS these are linear units (usually meters or feet), but for
+//! a geographic CRS they are degrees, and the planar length or area of a geometry in degrees is
+//! rarely meaningful. The functions here read the units of a CRS and convert the output, and
+//! error for geographic CRSes instead of silently returning degrees or square degrees.
+
+use crate::algorithm::geo::area::Area;
+use crate::algorithm::geo::length::euclidean_length;
+use crate::error::GeoArrowError;
+use crate::GeometryArray;
+use arrow2::array::PrimitiveArray;
+use arrow2::datatypes::DataType;
+
+/// Meters per international foot.
+const FOOT: f64 = 0.3048;
+
+/// Meters per US survey foot.
+const US_SURVEY_FOOT: f64 = 1200.0 / 3937.0;
+
+/// A unit of length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthUnit {
+    Meters,
+    Kilometers,
+    Feet,
+    Miles,
+}
+
+impl LengthUnit {
+    /// The length of one unit, in meters.
+    pub fn meters(&self) -> f64 {
+        match self {
+            LengthUnit::Meters => 1.0,
+            LengthUnit::Kilometers => 1_000.0,
+            LengthUnit::Feet => FOOT,
+            LengthUnit::Miles => 5_280.0 * FOOT,
+        }
+    }
+}
+
+/// A unit of area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AreaUnit {
+    SquareMeters,
+    SquareKilometers,
+    SquareFeet,
+    Acres,
+    Hectares,
+}
+
+impl AreaUnit {
+    /// The area of one unit, in square meters.
+    pub fn square_meters(&self) -> f64 {
+        match self {
+            AreaUnit::SquareMeters => 1.0,
+            AreaUnit::SquareKilometers => 1_000_000.0,
+            AreaUnit::SquareFeet => FOOT * FOOT,
+            AreaUnit::Acres => 43_560.0 * FOOT * FOOT,
+            AreaUnit::Hectares => 10_000.0,
+        }
+    }
+}
+
+/// The units of the coordinates of a CRS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrsUnits {
+    /// Angular units, i.e. a geographic CRS with longitude/latitude coordinates.
+    Degrees,
+
+    /// Linear units of the given length, in meters.
+    Linear { meters_per_unit: f64 },
+}
+
+/// The length in meters of the linear unit of a projected WKT or PROJJSON CRS description.
+///
+/// The unit of the coordinate system is written after those of the base CRS and its ellipsoid,
+/// so the last linear unit mentioned is used.
+fn linear_unit(crs: &str) -> Option<f64> {
+    let crs = crs.to_ascii_lowercase();
+    let candidates = [
+        ("us survey foot", US_SURVEY_FOOT),
+        ("us_survey_foot", US_SURVEY_FOOT),
+        ("foot_us", US_SURVEY_FOOT),
+        ("\"foot\"", FOOT),
+        ("metre", 1.0),
+        ("meter", 1.0),
+    ];
+    candidates
+        .iter()
+        .filter_map(|(name, meters)| crs.rfind(name).map(|position| (position, *meters)))
+        .max_by_key(|(position, _)| *position)
+        .map(|(_, meters)| meters)
+}
+
+/// The units of a CRS given as an `AUTHORITY:CODE` string.
+fn authority_units(authority: &str, code: &str) -> Option<CrsUnits> {
+    let code: u32 = match (authority.to_ascii_uppercase().as_str(), code) {
+        ("OGC", "CRS84" | "CRS83" | "CRS27") => return Some(CrsUnits::Degrees),
+        ("EPSG", code) => code.parse().ok()?,
+        _ => return None,
+    };
+    let meters = CrsUnits::Linear {
+        meters_per_unit: 1.0,
+    };
+    match code {
+        // WGS 84, NAD83, NAD27, ETRS89, GDA94, GDA2020, JGD2000 and other common datums
+        4326 | 4269 | 4267 | 4258 | 4283 | 7844 | 4612 | 4230 | 4617 | 4674 => {
+            Some(CrsUnits::Degrees)
+        }
+        // Web Mercator, World Mercator, LAEA and LCC Europe, Conus Albers
+        3857 | 3395 | 3035 | 3034 | 5070 => Some(meters),
+        // UTM zones on WGS 84, NAD83, NAD27 and ETRS89
+        32601..=32660 | 32701..=32760 | 26901..=26923 | 26701..=26722 | 25828..=25838 => {
+            Some(meters)
+        }
+        _ => None,
+    }
+}
+
+/// Detect the coordinate units of a CRS.
+///
+/// `crs` may be an `AUTHORITY:CODE` string (e.g. `"EPSG:4326"`), a WKT string or a PROJJSON
+/// string. Only common authority codes are recognized, since there is no CRS database to look
+/// them up in. Returns `None` if the units cannot be determined.
+pub fn crs_units(crs: &str) -> Option<CrsUnits> {
+    let trimmed = crs.trim();
+
+    // PROJJSON
+    if trimmed.starts_with('{') {
+        let compact: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.contains("\"type\":\"GeographicCRS\"")
+            || compact.contains("\"type\":\"GeodeticCRS\"")
+        {
+            return Some(CrsUnits::Degrees);
+        }
+        return linear_unit(&compact).map(|meters_per_unit| CrsUnits::Linear { meters_per_unit });
+    }
+
+    // WKT 1 and 2, where the keyword of the outermost node gives the kind of CRS
+    let keyword = trimmed
+        .split(['[', '('])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_uppercase();
+    match keyword.as_str() {
+        "GEOGCS" | "GEOGCRS" | "GEODCRS" | "GEOGRAPHICCRS" => return Some(CrsUnits::Degrees),
+        "PROJCS" | "PROJCRS" | "PROJECTEDCRS" => {
+            return linear_unit(trimmed).map(|meters_per_unit| CrsUnits::Linear { meters_per_unit })
+        }
+        _ => {}
+    }
+
+    let (authority, code) = trimmed.split_once(':')?;
+    authority_units(authority.trim(), code.trim())
+}
+
+/// The meters per coordinate unit of `crs`, erroring for geographic and unrecognized CRSes.
+fn meters_per_unit(crs: &str, operation: &'static str) -> Result<f64, GeoArrowError> {
+    match crs_units(crs) {
+        Some(CrsUnits::Linear { meters_per_unit }) => Ok(meters_per_unit),
+        Some(CrsUnits::Degrees) => Err(GeoArrowError::GeographicCrs { operation }),
+        None => Err(GeoArrowError::General(format!(
+            "Cannot determine the units of CRS {crs}"
+        ))),
+    }
+}
+
+/// Multiply each value of `array` by `factor`, keeping its validity.
+fn scale(array: PrimitiveArray<f64>, factor: f64) -> PrimitiveArray<f64> {
+    let values: Vec<f64> = array.values().iter().map(|value| value * factor).collect();
+    PrimitiveArray::new(DataType::Float64, values.into(), array.validity().cloned())
+}
+
+/// Planar length of each geometry of `array`, whose coordinates are in `crs`, in `unit`.
+///
+/// # Errors
+/// Errors with [`GeoArrowError::GeographicCrs`] if `crs` is a geographic CRS: use one of the
+/// geodesic length kernels for longitude/latitude coordinates instead. Also errors if the units
+/// of `crs` cannot be determined, or if the array is not linear (see
+/// [`euclidean_length`](super::length::euclidean_length)).
+pub fn length_in(
+    array: &GeometryArray,
+    crs: &str,
+    unit: LengthUnit,
+) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    let meters_per_unit = meters_per_unit(crs, "length")?;
+    let length = euclidean_length(array)?;
+    Ok(scale(length, meters_per_unit / unit.meters()))
+}
+
+/// Unsigned planar area of each geometry of `array`, whose coordinates are in `crs`, in `unit`.
+///
+/// # Errors
+/// Errors with [`GeoArrowError::GeographicCrs`] if `crs` is a geographic CRS, or if the units of
+/// `crs` cannot be determined.
+pub fn area_in(
+    array: &GeometryArray,
+    crs: &str,
+    unit: AreaUnit,
+) -> Result<PrimitiveArray<f64>, GeoArrowError> {
+    let meters_per_unit = meters_per_unit(crs, "area")?;
+    let area = array.unsigned_area();
+    Ok(scale(
+        area,
+        meters_per_unit * meters_per_unit / unit.square_meters(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LineStringArray, PolygonArray};
+    use arrow2::array::Array;
+    use geo::{line_string, polygon};
+
+    #[test]
+    fn detect_units() {
+        assert_eq!(crs_units("EPSG:4326"), Some(CrsUnits::Degrees));
+        assert_eq!(crs_units("OGC:CRS84"), Some(CrsUnits::Degrees));
+        assert_eq!(
+            crs_units("EPSG:32633"),
+            Some(CrsUnits::Linear {
+                meters_per_unit: 1.0
+            })
+        );
+        assert_eq!(crs_units("EPSG:2263"), None);
+
+        let geographic = r#"GEOGCS["WGS 84",DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563]],UNIT["degree",0.0174532925199433]]"#;
+        assert_eq!(crs_units(geographic), Some(CrsUnits::Degrees));
+
+        let state_plane = r#"PROJCS["NAD83 / New York Long Island (ftUS)",GEOGCS["NAD83",SPHEROID["GRS 1980",6378137,298.257222101,LENGTHUNIT["metre",1]],UNIT["degree",0.0174532925199433]],PROJECTION["Lambert_Conformal_Conic_2SP"],UNIT["US survey foot",0.304800609601219]]"#;
+        assert_eq!(
+            crs_units(state_plane),
+            Some(CrsUnits::Linear {
+                meters_per_unit: US_SURVEY_FOOT
+            })
+        );
+
+        let projjson = r#"{"type": "GeographicCRS", "name": "WGS 84"}"#;
+        assert_eq!(crs_units(projjson), Some(CrsUnits::Degrees));
+        let projjson =
+            r#"{"type": "ProjectedCRS", "coordinate_system": {"axis": [{"unit": "metre"}]}}"#;
+        assert_eq!(
+            crs_units(projjson),
+            Some(CrsUnits::Linear {
+                meters_per_unit: 1.0
+            })
+        );
+    }
+
+    #[test]
+    fn convert() {
+        let lines: LineStringArray = vec![
+            Some(line_string![(x: 0., y: 0.), (x: 3_000., y: 4_000.)]),
+            None,
+        ]
+        .into();
+        let lines = GeometryArray::LineString(lines);
+        let km = length_in(&lines, "EPSG:3857", LengthUnit::Kilometers).unwrap();
+        assert_eq!(km.value(0), 5.);
+        assert!(km.is_null(1));
+
+        let square =
+            polygon![(x: 0., y: 0.), (x: 100., y: 0.), (x: 100., y: 100.), (x: 0., y: 100.)];
+        let polygons: PolygonArray = vec![square].into();
+        let polygons = GeometryArray::Polygon(polygons);
+        let hectares = area_in(&polygons, "EPSG:32633", AreaUnit::Hectares).unwrap();
+        assert_eq!(hectares.value(0), 1.);
+        let acres = area_in(&polygons, "EPSG:32633", AreaUnit::Acres).unwrap();
+        assert!((acres.value(0) - 2.471).abs() < 1e-3);
+
+        assert!(matches!(
+            area_in(&polygons, "EPSG:4326", AreaUnit::SquareMeters),
+            Err(GeoArrowError::GeographicCrs { operation: "area" })
+        ));
+        assert!(length_in(&lines, "EPSG:2263", LengthUnit::Feet).is_err());
+    }
+}
@@ -0,0 +1,408 @@
+//! Check that geometries are valid in the sense of the OGC Simple Features specification.
+//!
+//! Overlay operations such as intersection and union assume valid input, and can fail or return
+//! wrong results otherwise, so it is worth checking geometries from untrusted sources first.
+//! These checks are implemented in pure Rust on [`geo`] geometries; with the `geos` feature, the
+//! functions of `algorithm::geos::validation` check geometries with GEOS instead.
+
+use crate::enum_::Geometry;
+use crate::geo_traits::{LineStringTrait, MultiPolygonTrait, PointTrait, PolygonTrait};
+use crate::parallel::map_rows;
+use crate::{
+    GeometryArrayTrait, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+    Rect, WKB,
+};
+use arrow2::array::{BooleanArray, Utf8Array};
+use geo::algorithm::line_intersection::{line_intersection, LineIntersection};
+use geo::{Area, Contains, CoordsIter, Line};
+use std::fmt;
+
+/// The message of [`validation_report`] for valid geometries.
+pub const VALID: &str = "Valid Geometry";
+
+/// Why a geometry is invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidReason {
+    /// A coordinate is NaN or infinite.
+    NonFiniteCoordinate,
+
+    /// A line string has fewer than two distinct points, or a ring fewer than four points.
+    TooFewPoints,
+
+    /// The first and last points of a ring differ.
+    UnclosedRing,
+
+    /// A ring encloses no area.
+    CollapsedRing,
+
+    /// Rings cross or overlap one another or themselves. Different rings may touch at points.
+    SelfIntersection,
+
+    /// A hole is not inside the exterior of its polygon.
+    HoleOutsideShell,
+
+    /// The polygons of a multi polygon overlap.
+    OverlappingPolygons,
+}
+
+impl fmt::Display for InvalidReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            InvalidReason::NonFiniteCoordinate => "Invalid Coordinate",
+            InvalidReason::TooFewPoints => "Too few points",
+            InvalidReason::UnclosedRing => "Ring is not closed",
+            InvalidReason::CollapsedRing => "Ring has zero area",
+            InvalidReason::SelfIntersection => "Self-intersection",
+            InvalidReason::HoleOutsideShell => "Hole lies outside shell",
+            InvalidReason::OverlappingPolygons => "Overlapping polygons",
+        };
+        f.write_str(message)
+    }
+}
+
+fn check_coords<'a>(geom: &'a impl CoordsIter<'a, Scalar = f64>) -> Result<(), InvalidReason> {
+    if geom
+        .coords_iter()
+        .all(|coord| coord.x.is_finite() && coord.y.is_finite())
+    {
+        Ok(())
+    } else {
+        Err(InvalidReason::NonFiniteCoordinate)
+    }
+}
+
+/// Whether two segments cross or overlap anywhere other than at a shared endpoint. Segments of
+/// the same ring may only meet where they are `adjacent`, while segments of different rings may
+/// touch at an endpoint of either.
+fn segments_conflict(a: &Line, b: &Line, same_ring: bool, adjacent: bool) -> bool {
+    match line_intersection(*a, *b) {
+        None => false,
+        Some(LineIntersection::Collinear { .. }) => true,
+        Some(LineIntersection::SinglePoint { is_proper, .. }) => {
+            if same_ring {
+                !adjacent
+            } else {
+                is_proper
+            }
+        }
+    }
+}
+
+/// Whether any two segments from `rings` conflict.
+fn rings_intersect(rings: &[&geo::LineString]) -> bool {
+    let segments: Vec<(usize, usize, usize, Line)> = rings
+        .iter()
+        .enumerate()
+        .flat_map(|(ring_idx, ring)| {
+            let num_segments = ring.0.len() - 1;
+            ring.lines()
+                .enumerate()
+                .map(move |(i, line)| (ring_idx, i, num_segments, line))
+        })
+        .collect();
+
+    for (a_idx, (a_ring, a_pos, num_segments, a)) in segments.iter().enumerate() {
+        for (b_ring, b_pos, _, b) in &segments[a_idx + 1..] {
+            let same_ring = a_ring == b_ring;
+            let adjacent =
+                same_ring && (b_pos - a_pos == 1 || (*a_pos == 0 && *b_pos == num_segments - 1));
+            if segments_conflict(a, b, same_ring, adjacent) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn check_ring(ring: &geo::LineString) -> Result<(), InvalidReason> {
+    if ring.0.len() < 4 {
+        Err(InvalidReason::TooFewPoints)
+    } else if !ring.is_closed() {
+        Err(InvalidReason::UnclosedRing)
+    } else {
+        Ok(())
+    }
+}
+
+fn check_line_string(line_string: &geo::LineString) -> Result<(), InvalidReason> {
+    check_coords(line_string)?;
+    match line_string.0.first() {
+        Some(first) if line_string.0.iter().all(|coord| coord == first) => {
+            Err(InvalidReason::TooFewPoints)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_polygon(polygon: &geo::Polygon) -> Result<(), InvalidReason> {
+    check_coords(polygon)?;
+    if polygon.exterior().0.is_empty() && polygon.interiors().is_empty() {
+        return Ok(());
+    }
+    check_ring(polygon.exterior())?;
+    polygon.interiors().iter().try_for_each(check_ring)?;
+
+    let rings: Vec<&geo::LineString> = std::iter::once(polygon.exterior())
+        .chain(polygon.interiors())
+        .collect();
+    if rings_intersect(&rings) {
+        return Err(InvalidReason::SelfIntersection);
+    }
+    // Checked after crossings, as the areas of the two halves of a bowtie cancel out
+    if rings
+        .iter()
+        .any(|ring| geo::Polygon::new((*ring).clone(), vec![]).unsigned_area() == 0.0)
+    {
+        return Err(InvalidReason::CollapsedRing);
+    }
+
+    // Without crossings, a hole is inside its shell iff any one of its vertices is
+    let shell = geo::Polygon::new(polygon.exterior().clone(), vec![]);
+    if polygon
+        .interiors()
+        .iter()
+        .all(|interior| shell.contains(&interior.0[0]))
+    {
+        Ok(())
+    } else {
+        Err(InvalidReason::HoleOutsideShell)
+    }
+}
+
+fn check_multi_polygon(multi_polygon: &geo::MultiPolygon) -> Result<(), InvalidReason> {
+    multi_polygon.0.iter().try_for_each(check_polygon)?;
+
+    let polygons: Vec<&geo::Polygon> = multi_polygon
+        .0
+        .iter()
+        .filter(|polygon| !polygon.exterior().0.is_empty())
+        .collect();
+    let rings: Vec<&geo::LineString> = polygons
+        .iter()
+        .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+        .collect();
+    if rings_intersect(&rings) {
+        return Err(InvalidReason::SelfIntersection);
+    }
+
+    // Without crossings, polygons overlap only if one shell lies inside another
+    let overlapping = polygons.iter().enumerate().any(|(i, a)| {
+        let shell = geo::Polygon::new(a.exterior().clone(), vec![]);
+        polygons
+            .iter()
+            .enumerate()
+            .any(|(j, b)| i != j && shell.contains(&b.exterior().0[0]))
+    });
+    if overlapping {
+        Err(InvalidReason::OverlappingPolygons)
+    } else {
+        Ok(())
+    }
+}
+
+fn check_geometry(geom: &geo::Geometry) -> Result<(), InvalidReason> {
+    match geom {
+        geo::Geometry::Point(point) => check_coords(point),
+        geo::Geometry::Line(line) => check_line_string(&(*line).into()),
+        geo::Geometry::LineString(line_string) => check_line_string(line_string),
+        geo::Geometry::Polygon(polygon) => check_polygon(polygon),
+        geo::Geometry::MultiPoint(multi_point) => check_coords(multi_point),
+        geo::Geometry::MultiLineString(multi_line_string) => {
+            multi_line_string.0.iter().try_for_each(check_line_string)
+        }
+        geo::Geometry::MultiPolygon(multi_polygon) => check_multi_polygon(multi_polygon),
+        geo::Geometry::GeometryCollection(collection) => {
+            collection.0.iter().try_for_each(check_geometry)
+        }
+        geo::Geometry::Rect(rect) => check_coords(rect),
+        geo::Geometry::Triangle(triangle) => check_polygon(&triangle.to_polygon()),
+    }
+}
+
+/// Why `geom` is invalid, or `None` if it is valid.
+///
+/// Empty geometries are valid. Only the first problem found is reported.
+pub fn invalid_reason(geom: &geo::Geometry) -> Option<InvalidReason> {
+    check_geometry(geom).err()
+}
+
+/// Whether a polygon is valid, as used to guard simplification.
+pub(crate) fn polygon_is_valid(polygon: &geo::Polygon) -> bool {
+    check_polygon(polygon).is_ok()
+}
+
+/// Whether a multi polygon is valid, as used to guard simplification.
+pub(crate) fn multi_polygon_is_valid(multi_polygon: &geo::MultiPolygon) -> bool {
+    check_multi_polygon(multi_polygon).is_ok()
+}
+
+/// Whether the rings of a scalar are closed in its coordinate buffers.
+///
+/// Converting a scalar to a [`geo`] geometry closes its rings, so [`is_valid`] and
+/// [`validation_report`] check this on the scalar before converting it.
+pub trait RingClosure {
+    fn rings_closed(&self) -> bool {
+        true
+    }
+}
+
+fn ring_closed<'a>(ring: &impl LineStringTrait<'a>) -> bool {
+    let num_points = ring.num_points();
+    num_points == 0 || ring.point(0).unwrap().x_y() == ring.point(num_points - 1).unwrap().x_y()
+}
+
+fn polygon_rings_closed<'a>(polygon: &impl PolygonTrait<'a>) -> bool {
+    ring_closed(&polygon.exterior())
+        && (0..polygon.num_interiors()).all(|i| ring_closed(&polygon.interior(i).unwrap()))
+}
+
+impl RingClosure for Point<'_> {}
+impl RingClosure for LineString<'_> {}
+impl RingClosure for MultiPoint<'_> {}
+impl RingClosure for MultiLineString<'_> {}
+impl RingClosure for Rect<'_> {}
+impl RingClosure for WKB<'_> {}
+
+impl RingClosure for Polygon<'_> {
+    fn rings_closed(&self) -> bool {
+        polygon_rings_closed(self)
+    }
+}
+
+impl RingClosure for MultiPolygon<'_> {
+    fn rings_closed(&self) -> bool {
+        (0..self.num_polygons()).all(|i| polygon_rings_closed(&self.polygon(i).unwrap()))
+    }
+}
+
+impl RingClosure for Geometry<'_> {
+    fn rings_closed(&self) -> bool {
+        match self {
+            Geometry::Polygon(polygon) => polygon.rings_closed(),
+            Geometry::MultiPolygon(multi_polygon) => multi_polygon.rings_closed(),
+            _ => true,
+        }
+    }
+}
+
+/// Why the geometry at `i` is invalid, or `None` if it is null.
+pub(crate) fn row_reason<'a, A>(array: &'a A, i: usize) -> Option<Result<(), InvalidReason>>
+where
+    A: GeometryArrayTrait<'a>,
+    A::Scalar: RingClosure,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    let scalar = array.get(i)?;
+    if !scalar.rings_closed() {
+        return Some(Err(InvalidReason::UnclosedRing));
+    }
+    let geom: geo::Geometry = A::ScalarGeo::from(scalar).into();
+    Some(check_geometry(&geom))
+}
+
+/// Whether each geometry of `array` is valid.
+///
+/// Null geometries produce null results. Rows are checked in parallel with the `rayon` feature.
+pub fn is_valid<'a, A>(array: &'a A) -> BooleanArray
+where
+    A: GeometryArrayTrait<'a> + Sync,
+    A::Scalar: RingClosure,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    map_rows(array.len(), |i| {
+        row_reason(array, i).map(|reason| reason.is_ok())
+    })
+    .into()
+}
+
+/// Why each geometry of `array` is invalid, or [`VALID`] for valid geometries.
+///
+/// Null geometries produce null results. Rows are checked in parallel with the `rayon` feature.
+pub fn validation_report<'a, A>(array: &'a A) -> Utf8Array<i32>
+where
+    A: GeometryArrayTrait<'a> + Sync,
+    A::Scalar: RingClosure,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    let reports: Vec<Option<String>> = map_rows(array.len(), |i| {
+        row_reason(array, i).map(|reason| match reason {
+            Ok(()) => VALID.to_string(),
+            Err(reason) => reason.to_string(),
+        })
+    });
+    Utf8Array::from(reports)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArray, LineStringArray, MultiPolygonArray, PolygonArray};
+    use arrow2::array::Array;
+    use arrow2::offset::OffsetsBuffer;
+    use geo::{line_string, polygon, MultiPolygon};
+
+    fn square() -> geo::Polygon {
+        polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)]
+    }
+
+    #[test]
+    fn polygons() {
+        let bowtie = polygon![
+            (x: 0., y: 0.), (x: 2., y: 2.), (x: 2., y: 0.), (x: 0., y: 2.), (x: 0., y: 0.)
+        ];
+        let collapsed = polygon![(x: 0., y: 0.), (x: 1., y: 1.), (x: 2., y: 2.), (x: 0., y: 0.)];
+        let arr: PolygonArray = vec![
+            Some(square()),
+            Some(bowtie),
+            None,
+            Some(collapsed),
+            Some(geo::Polygon::new(geo::LineString::new(vec![]), vec![])),
+        ]
+        .into();
+        assert_eq!(
+            is_valid(&arr),
+            BooleanArray::from([Some(true), Some(false), None, Some(false), Some(true)])
+        );
+
+        let report = validation_report(&arr);
+        assert_eq!(report.value(0), VALID);
+        assert_eq!(report.value(1), "Self-intersection");
+        assert!(report.is_null(2));
+        assert_ne!(report.value(3), VALID);
+
+        let unclosed = PolygonArray::new(
+            vec![0., 2., 2., 0.].into(),
+            vec![0., 0., 2., 2.].into(),
+            OffsetsBuffer::try_from(vec![0, 1]).unwrap(),
+            OffsetsBuffer::try_from(vec![0, 4]).unwrap(),
+            None,
+        );
+        assert_eq!(validation_report(&unclosed).value(0), "Ring is not closed");
+    }
+
+    #[test]
+    fn multi_polygons_and_lines() {
+        let overlapping = MultiPolygon::new(vec![
+            square(),
+            polygon![
+                (x: 0.5, y: 0.5), (x: 1., y: 0.5), (x: 1., y: 1.), (x: 0.5, y: 0.5)
+            ],
+        ]);
+        let arr: MultiPolygonArray = vec![overlapping].into();
+        assert_eq!(
+            validation_report(&arr).value(0),
+            InvalidReason::OverlappingPolygons.to_string()
+        );
+
+        let lines: LineStringArray = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+            line_string![(x: 1., y: 1.), (x: 1., y: 1.)],
+            line_string![(x: 0., y: 0.), (x: f64::NAN, y: 1.)],
+        ]
+        .into();
+        let report = validation_report(&GeometryArray::LineString(lines));
+        assert_eq!(report.value(0), VALID);
+        assert_eq!(report.value(1), "Too few points");
+        assert_eq!(report.value(2), "Invalid Coordinate");
+    }
+}
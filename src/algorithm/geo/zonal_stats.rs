@@ -0,0 +1,198 @@
+use crate::enum_::EXTENSION_NAME_KEY;
+use crate::error::GeoArrowError;
+use crate::table::GeoTable;
+use crate::{GeometryArrayTrait, PointArray, PolygonArray};
+use arrow2::array::{Array, MutablePrimitiveArray, PrimitiveArray};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{Field, Schema};
+use geo::{BoundingRect, Contains};
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, AABB};
+use std::collections::BTreeMap;
+
+/// A statistic computed per zone by [`zonal_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZonalStatistic {
+    /// Number of points with a non-null value inside the zone
+    Count,
+
+    /// Sum of the values of points inside the zone
+    Sum,
+
+    /// Mean of the values of points inside the zone; null for zones containing no points
+    Mean,
+}
+
+impl ZonalStatistic {
+    /// The name of the output column holding this statistic.
+    pub fn column_name(&self) -> &'static str {
+        match self {
+            ZonalStatistic::Count => "count",
+            ZonalStatistic::Sum => "sum",
+            ZonalStatistic::Mean => "mean",
+        }
+    }
+}
+
+/// Aggregate the values of points falling in each zone.
+///
+/// A point belongs to every zone that contains it; points on a zone's boundary are not
+/// contained by it. Points with a null geometry or null value are ignored.
+///
+/// Returns a [`GeoTable`] with one row per zone: the zone geometry followed by one column per
+/// requested statistic, in order. Statistics requested more than once get a single column.
+/// Statistics of null zones are null.
+pub fn zonal_stats(
+    points: &PointArray,
+    values: &PrimitiveArray<f64>,
+    zones: &PolygonArray,
+    stats: &[ZonalStatistic],
+) -> Result<GeoTable, GeoArrowError> {
+    if points.len() != values.len() {
+        return Err(GeoArrowError::General(
+            "points and values must have the same length".to_string(),
+        ));
+    }
+
+    let zone_geoms: Vec<Option<geo::Polygon>> = zones.iter_geo().collect();
+    let zone_rects = zone_geoms
+        .iter()
+        .enumerate()
+        .filter_map(|(zone_idx, maybe_zone)| {
+            let rect = maybe_zone.as_ref()?.bounding_rect()?;
+            let rectangle =
+                Rectangle::from_corners(rect.min().x_y().into(), rect.max().x_y().into());
+            Some(GeomWithData::new(rectangle, zone_idx))
+        })
+        .collect();
+    let tree: RTree<GeomWithData<Rectangle<[f64; 2]>, usize>> = RTree::bulk_load(zone_rects);
+
+    let mut counts = vec![0u64; zones.len()];
+    let mut sums = vec![0f64; zones.len()];
+    for point_idx in 0..points.len() {
+        if points.is_null(point_idx) || values.is_null(point_idx) {
+            continue;
+        }
+
+        let x = points.values_x()[point_idx];
+        let y = points.values_y()[point_idx];
+        let point = geo::Point::new(x, y);
+        let envelope = AABB::from_point([x, y]);
+        for candidate in tree.locate_in_envelope_intersecting(&envelope) {
+            let zone_idx = candidate.data;
+            if zone_geoms[zone_idx].as_ref().unwrap().contains(&point) {
+                counts[zone_idx] += 1;
+                sums[zone_idx] += values.value(point_idx);
+            }
+        }
+    }
+
+    let zones_arrow = zones.clone().into_arrow();
+    let mut fields = vec![
+        Field::new("geometry", zones_arrow.data_type().clone(), true).with_metadata(
+            BTreeMap::from([(
+                EXTENSION_NAME_KEY.to_string(),
+                "geoarrow.polygon".to_string(),
+            )]),
+        ),
+    ];
+    let mut arrays: Vec<Box<dyn Array>> = vec![zones_arrow.boxed()];
+
+    for (i, stat) in stats.iter().enumerate() {
+        if stats[..i].contains(stat) {
+            continue;
+        }
+
+        let array: Box<dyn Array> = match stat {
+            ZonalStatistic::Count => {
+                let mut output = MutablePrimitiveArray::<u64>::with_capacity(zones.len());
+                (0..zones.len()).for_each(|i| output.push(zones.is_valid(i).then(|| counts[i])));
+                PrimitiveArray::from(output).boxed()
+            }
+            ZonalStatistic::Sum => {
+                let mut output = MutablePrimitiveArray::<f64>::with_capacity(zones.len());
+                (0..zones.len()).for_each(|i| output.push(zones.is_valid(i).then(|| sums[i])));
+                PrimitiveArray::from(output).boxed()
+            }
+            ZonalStatistic::Mean => {
+                let mut output = MutablePrimitiveArray::<f64>::with_capacity(zones.len());
+                (0..zones.len()).for_each(|i| {
+                    output.push(
+                        (zones.is_valid(i) && counts[i] > 0).then(|| sums[i] / counts[i] as f64),
+                    )
+                });
+                PrimitiveArray::from(output).boxed()
+            }
+        };
+
+        fields.push(Field::new(
+            stat.column_name(),
+            array.data_type().clone(),
+            true,
+        ));
+        arrays.push(array);
+    }
+
+    GeoTable::try_new(Schema::from(fields), vec![Chunk::new(arrays)], 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{point, polygon};
+
+    #[test]
+    fn count_sum_mean() {
+        let zones: PolygonArray = vec![
+            Some(polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.)]),
+            Some(polygon![(x: 5., y: 5.), (x: 6., y: 5.), (x: 6., y: 6.), (x: 5., y: 6.)]),
+            None,
+        ]
+        .into();
+        let points: PointArray = vec![
+            Some(point!(x: 1., y: 1.)),
+            Some(point!(x: 1.5, y: 0.5)),
+            Some(point!(x: 0.5, y: 0.5)),
+            None,
+            Some(point!(x: 10., y: 10.)),
+        ]
+        .into();
+        let values = PrimitiveArray::from([Some(1.), Some(3.), None, Some(100.), Some(100.)]);
+
+        let stats = [
+            ZonalStatistic::Count,
+            ZonalStatistic::Sum,
+            ZonalStatistic::Count,
+            ZonalStatistic::Mean,
+        ];
+        let table = zonal_stats(&points, &values, &zones, &stats).unwrap();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.num_columns(), 4);
+        let names: Vec<&str> = table
+            .schema()
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect();
+        assert_eq!(names, ["geometry", "count", "sum", "mean"]);
+
+        let chunk = &table.chunks()[0];
+        let count = chunk.arrays()[1]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<u64>>()
+            .unwrap();
+        assert_eq!(count, &PrimitiveArray::from([Some(2), Some(0), None]));
+
+        let sum = chunk.arrays()[2]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .unwrap();
+        assert_eq!(sum, &PrimitiveArray::from([Some(4.), Some(0.), None]));
+
+        let mean = chunk.arrays()[3]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .unwrap();
+        assert_eq!(mean, &PrimitiveArray::from([Some(2.), None, None]));
+    }
+}
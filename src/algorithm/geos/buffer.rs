@@ -0,0 +1,169 @@
+use crate::error::GeoArrowError;
+use crate::{GeometryArrayTrait, LineStringArray, MultiPolygonArray, PointArray, PolygonArray};
+use arrow2::array::PrimitiveArray;
+use geos::Geom;
+
+/// Buffer each geometry of an array using GEOS.
+///
+/// `quadsegs` is the number of segments used to approximate a quarter circle. Null geometries
+/// (and, for [`Buffer::buffer_per_row`], null distances) produce null output.
+pub trait Buffer {
+    type Output;
+
+    /// Buffer every geometry by the same distance.
+    fn buffer(&self, width: f64, quadsegs: i32) -> Result<Self::Output, GeoArrowError>;
+
+    /// Buffer each geometry by the distance in the matching row of `widths`.
+    fn buffer_per_row(
+        &self,
+        widths: &PrimitiveArray<f64>,
+        quadsegs: i32,
+    ) -> Result<Self::Output, GeoArrowError>;
+}
+
+fn buffer_geometries<'a>(
+    geoms: impl Iterator<Item = Option<geos::Geometry<'a>>>,
+    widths: impl Iterator<Item = Option<f64>>,
+    quadsegs: i32,
+) -> Result<Vec<Option<geo::Geometry>>, GeoArrowError> {
+    geoms
+        .zip(widths)
+        .map(
+            |(maybe_geom, maybe_width)| match (maybe_geom, maybe_width) {
+                (Some(geom), Some(width)) => {
+                    let buffered = geom
+                        .buffer(width, quadsegs)
+                        .map_err(|err| GeoArrowError::External(err.into()))?;
+                    let buffered: geo::Geometry = buffered
+                        .try_into()
+                        .map_err(|err: geos::Error| GeoArrowError::External(err.into()))?;
+                    Ok(Some(buffered))
+                }
+                _ => Ok(None),
+            },
+        )
+        .collect()
+}
+
+fn check_widths(len: usize, widths: &PrimitiveArray<f64>) -> Result<(), GeoArrowError> {
+    if len != widths.len() {
+        return Err(GeoArrowError::General(
+            "widths must have the same length as the geometry array".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn into_polygon(geom: geo::Geometry) -> Result<geo::Polygon, GeoArrowError> {
+    match geom {
+        geo::Geometry::Polygon(polygon) => Ok(polygon),
+        _ => Err(GeoArrowError::General(
+            "expected buffer to produce a polygon".to_string(),
+        )),
+    }
+}
+
+fn into_multi_polygon(geom: geo::Geometry) -> Result<geo::MultiPolygon, GeoArrowError> {
+    match geom {
+        geo::Geometry::Polygon(polygon) => Ok(geo::MultiPolygon::new(vec![polygon])),
+        geo::Geometry::MultiPolygon(multi_polygon) => Ok(multi_polygon),
+        _ => Err(GeoArrowError::General(
+            "expected buffer to produce a polygon or multi polygon".to_string(),
+        )),
+    }
+}
+
+/// Buffers of points and line strings are always a single polygon.
+macro_rules! polygon_output_impl {
+    ($type:ty) => {
+        impl Buffer for $type {
+            type Output = PolygonArray;
+
+            fn buffer(&self, width: f64, quadsegs: i32) -> Result<Self::Output, GeoArrowError> {
+                let geoms = (0..self.len()).map(|i| self.get_as_geos(i));
+                let widths = std::iter::repeat(Some(width));
+                let output_geoms = buffer_geometries(geoms, widths, quadsegs)?
+                    .into_iter()
+                    .map(|maybe_geom| maybe_geom.map(into_polygon).transpose())
+                    .collect::<Result<Vec<Option<geo::Polygon>>, _>>()?;
+                Ok(output_geoms.into())
+            }
+
+            fn buffer_per_row(
+                &self,
+                widths: &PrimitiveArray<f64>,
+                quadsegs: i32,
+            ) -> Result<Self::Output, GeoArrowError> {
+                check_widths(self.len(), widths)?;
+                let geoms = (0..self.len()).map(|i| self.get_as_geos(i));
+                let widths = widths.iter().map(|width| width.copied());
+                let output_geoms = buffer_geometries(geoms, widths, quadsegs)?
+                    .into_iter()
+                    .map(|maybe_geom| maybe_geom.map(into_polygon).transpose())
+                    .collect::<Result<Vec<Option<geo::Polygon>>, _>>()?;
+                Ok(output_geoms.into())
+            }
+        }
+    };
+}
+
+polygon_output_impl!(PointArray);
+polygon_output_impl!(LineStringArray);
+
+/// Negative buffers may split a polygon in several parts.
+impl Buffer for PolygonArray {
+    type Output = MultiPolygonArray;
+
+    fn buffer(&self, width: f64, quadsegs: i32) -> Result<Self::Output, GeoArrowError> {
+        let geoms = (0..self.len()).map(|i| self.get_as_geos(i));
+        let widths = std::iter::repeat(Some(width));
+        let output_geoms = buffer_geometries(geoms, widths, quadsegs)?
+            .into_iter()
+            .map(|maybe_geom| maybe_geom.map(into_multi_polygon).transpose())
+            .collect::<Result<Vec<Option<geo::MultiPolygon>>, _>>()?;
+        Ok(output_geoms.into())
+    }
+
+    fn buffer_per_row(
+        &self,
+        widths: &PrimitiveArray<f64>,
+        quadsegs: i32,
+    ) -> Result<Self::Output, GeoArrowError> {
+        check_widths(self.len(), widths)?;
+        let geoms = (0..self.len()).map(|i| self.get_as_geos(i));
+        let widths = widths.iter().map(|width| width.copied());
+        let output_geoms = buffer_geometries(geoms, widths, quadsegs)?
+            .into_iter()
+            .map(|maybe_geom| maybe_geom.map(into_multi_polygon).transpose())
+            .collect::<Result<Vec<Option<geo::MultiPolygon>>, _>>()?;
+        Ok(output_geoms.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{point, polygon};
+
+    #[test]
+    fn point_buffer() {
+        let arr: PointArray = vec![Some(point!(x: 0., y: 0.)), None].into();
+        let buffered = arr.buffer(1., 8).unwrap();
+        assert_eq!(buffered.len(), 2);
+        assert!(buffered.get_as_geo(0).is_some());
+        assert!(buffered.get_as_geo(1).is_none());
+    }
+
+    #[test]
+    fn polygon_buffer_per_row() {
+        let square = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.)];
+        let arr: PolygonArray = vec![square.clone(), square].into();
+        let widths = PrimitiveArray::from([Some(1.), None]);
+        let buffered = arr.buffer_per_row(&widths, 8).unwrap();
+        assert_eq!(buffered.get_as_geo(0).unwrap().0.len(), 1);
+        assert!(buffered.get_as_geo(1).is_none());
+
+        let widths = PrimitiveArray::from([Some(1.)]);
+        assert!(arr.buffer_per_row(&widths, 8).is_err());
+    }
+}
@@ -0,0 +1,64 @@
+//! Repair invalid polygons with GEOS.
+
+use crate::error::GeoArrowError;
+use crate::{GeometryArrayTrait, MultiPolygonArray};
+use geos::Geom;
+
+/// The polygons of `geom`, dropping the lines and points that collapsed parts repair to.
+fn polygons(geom: geo::Geometry, output: &mut Vec<geo::Polygon>) {
+    match geom {
+        geo::Geometry::Polygon(polygon) => output.push(polygon),
+        geo::Geometry::MultiPolygon(multi_polygon) => output.extend(multi_polygon.0),
+        geo::Geometry::GeometryCollection(collection) => collection
+            .0
+            .into_iter()
+            .for_each(|geom| polygons(geom, output)),
+        _ => {}
+    }
+}
+
+/// Repair the invalid geometries of a polygonal array with GEOS' `MakeValid`, returning the
+/// repaired array and the number of geometries repaired.
+///
+/// Geometries GEOS considers valid are kept as they are. Repairing a polygon can split it into
+/// several, so the output is a multi polygon array; parts that collapse to lines or points are
+/// dropped. Null geometries stay null. The output has no CRS; set it with `with_crs`.
+///
+/// Errors if a geometry cannot be converted to or from GEOS.
+pub fn make_valid<'a, A>(array: &'a A) -> Result<(MultiPolygonArray, usize), GeoArrowError>
+where
+    A: GeometryArrayTrait<'a>,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    let mut num_repaired = 0;
+    let mut output = Vec::with_capacity(array.len());
+    for i in 0..array.len() {
+        let geom: geo::Geometry = match array.get_as_geo(i) {
+            Some(geom) => geom.into(),
+            None => {
+                output.push(None);
+                continue;
+            }
+        };
+
+        let geos_geom: geos::Geometry = (&geom)
+            .try_into()
+            .map_err(|err: geos::Error| GeoArrowError::External(err.into()))?;
+        let geom = if geos_geom.is_valid() {
+            geom
+        } else {
+            num_repaired += 1;
+            geos_geom
+                .make_valid()
+                .and_then(|repaired| repaired.try_into())
+                .map_err(|err: geos::Error| GeoArrowError::External(err.into()))?
+        };
+
+        let mut parts = vec![];
+        polygons(geom, &mut parts);
+        output.push(Some(geo::MultiPolygon::new(parts)));
+    }
+
+    let output: MultiPolygonArray = output.into();
+    Ok((output, num_repaired))
+}
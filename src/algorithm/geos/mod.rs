@@ -0,0 +1,5 @@
+//! Algorithms implemented with [`geos`] geometries.
+
+pub mod buffer;
+pub mod make_valid;
+pub mod validation;
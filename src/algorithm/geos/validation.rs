@@ -0,0 +1,66 @@
+//! Check that geometries are valid with GEOS.
+//!
+//! These match [`crate::algorithm::geo::validation`], but use GEOS' own validity checks and
+//! messages, such as `"Self-intersection[1 1]"`, which locate the problem.
+
+use crate::error::GeoArrowError;
+use crate::GeometryArrayTrait;
+use arrow2::array::{BooleanArray, Utf8Array};
+use geos::Geom;
+
+/// The geometry at `i` converted to GEOS, or `None` if it is null.
+///
+/// GEOS refuses to build some invalid geometries, such as rings with too few points, so a
+/// failed conversion means the geometry is invalid.
+fn geos_geometry<'a, A>(
+    array: &'a A,
+    i: usize,
+) -> Option<Result<geos::Geometry<'static>, geos::Error>>
+where
+    A: GeometryArrayTrait<'a>,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    array.get_as_geo(i).map(|geom| {
+        let geom: geo::Geometry = geom.into();
+        (&geom).try_into()
+    })
+}
+
+/// Whether each geometry of `array` is valid according to GEOS.
+///
+/// Null geometries produce null results.
+pub fn is_valid<'a, A>(array: &'a A) -> BooleanArray
+where
+    A: GeometryArrayTrait<'a>,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    let values: Vec<Option<bool>> = (0..array.len())
+        .map(|i| geos_geometry(array, i).map(|geom| geom.map_or(false, |geom| geom.is_valid())))
+        .collect();
+    values.into()
+}
+
+/// GEOS' reason for each geometry of `array` being invalid, or `"Valid Geometry"` for valid
+/// geometries.
+///
+/// Geometries GEOS cannot build report the error building them. Null geometries produce null
+/// results.
+pub fn validation_report<'a, A>(array: &'a A) -> Result<Utf8Array<i32>, GeoArrowError>
+where
+    A: GeometryArrayTrait<'a>,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    let reports = (0..array.len())
+        .map(|i| {
+            geos_geometry(array, i)
+                .map(|geom| match geom {
+                    Ok(geom) => geom
+                        .is_valid_reason()
+                        .map_err(|err| GeoArrowError::External(err.into())),
+                    Err(err) => Ok(err.to_string()),
+                })
+                .transpose()
+        })
+        .collect::<Result<Vec<Option<String>>, GeoArrowError>>()?;
+    Ok(Utf8Array::from(reports))
+}
@@ -0,0 +1,218 @@
+//! Square and hexagonal grids, for aggregating data into cells of equal size.
+//!
+//! [`make_grid`] covers a rectangle with grid cells, ready to be joined with points or other
+//! geometries and colored as a choropleth. [`tessellate`] cuts polygons along a grid instead, so
+//! that the area of a polygon can be split between the cells it overlaps.
+
+use crate::error::GeoArrowError;
+use crate::parallel::map_rows;
+use crate::{GeometryArrayTrait, MultiPolygonArray, PolygonArray};
+use arrow2::array::PrimitiveArray;
+use geo::{BooleanOps, BoundingRect};
+
+/// The shape of the cells of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridType {
+    /// Squares with sides of the cell size, in rows and columns.
+    Square,
+    /// Flat-topped regular hexagons with sides of the cell size, in columns offset by half a
+    /// cell from one another.
+    Hexagon,
+}
+
+/// Check that `cell_size` is a positive number.
+fn check_cell_size(cell_size: f64) -> Result<(), GeoArrowError> {
+    if cell_size > 0.0 && cell_size.is_finite() {
+        Ok(())
+    } else {
+        Err(GeoArrowError::General(format!(
+            "cell_size must be a positive number, got {cell_size}"
+        )))
+    }
+}
+
+/// The cells of a grid with a cell at `origin` that overlap `rect`, row by row from the bottom.
+fn grid_cells(
+    rect: &geo::Rect,
+    origin: geo::Coord,
+    cell_size: f64,
+    grid_type: GridType,
+) -> Vec<geo::Polygon> {
+    let (min, max) = (rect.min(), rect.max());
+    let mut cells = vec![];
+    match grid_type {
+        GridType::Square => {
+            // Cells span [origin + i * cell_size, origin + (i + 1) * cell_size]
+            let cols = ((min.x - origin.x) / cell_size).floor() as i64
+                ..((max.x - origin.x) / cell_size).ceil() as i64;
+            let rows = ((min.y - origin.y) / cell_size).floor() as i64
+                ..((max.y - origin.y) / cell_size).ceil() as i64;
+            for row in rows {
+                let y = origin.y + row as f64 * cell_size;
+                for col in cols.clone() {
+                    let x = origin.x + col as f64 * cell_size;
+                    let cell = geo::Rect::new((x, y), (x + cell_size, y + cell_size));
+                    cells.push(cell.to_polygon());
+                }
+            }
+        }
+        GridType::Hexagon => {
+            // Centers are 1.5 sides apart horizontally, and odd columns are shifted up by half
+            // the height of a cell
+            let (dx, height) = (1.5 * cell_size, 3f64.sqrt() * cell_size);
+            let cols = ((min.x - origin.x - cell_size) / dx).floor() as i64
+                ..=((max.x - origin.x + cell_size) / dx).ceil() as i64;
+            let rows = ((min.y - origin.y - height) / height).floor() as i64
+                ..=((max.y - origin.y + height) / height).ceil() as i64;
+            for row in rows {
+                for col in cols.clone() {
+                    let x = origin.x + col as f64 * dx;
+                    let y = origin.y + (row as f64 + 0.5 * col.rem_euclid(2) as f64) * height;
+                    let overlaps = x - cell_size < max.x
+                        && x + cell_size > min.x
+                        && y - height / 2.0 < max.y
+                        && y + height / 2.0 > min.y;
+                    if overlaps {
+                        cells.push(hexagon(x, y, cell_size));
+                    }
+                }
+            }
+        }
+    }
+    cells
+}
+
+/// The flat-topped regular hexagon centered on `(x, y)` with sides of `side`.
+fn hexagon(x: f64, y: f64, side: f64) -> geo::Polygon {
+    let half_height = 3f64.sqrt() / 2.0 * side;
+    let exterior = vec![
+        (x + side, y),
+        (x + side / 2.0, y + half_height),
+        (x - side / 2.0, y + half_height),
+        (x - side, y),
+        (x - side / 2.0, y - half_height),
+        (x + side / 2.0, y - half_height),
+    ];
+    geo::Polygon::new(exterior.into(), vec![])
+}
+
+/// A grid of cells with sides of `cell_size` covering `bbox`.
+///
+/// The grid starts with a cell at the lower left corner of `bbox`: a square cell with that
+/// corner, or a hexagon centered on it. Every cell overlapping `bbox` is returned, so cells on
+/// the edges may extend past it. Cells are ordered row by row, from the bottom left.
+/// # Errors
+/// Errors if `cell_size` is not a positive number.
+pub fn make_grid(
+    bbox: &geo::Rect,
+    cell_size: f64,
+    grid_type: GridType,
+) -> Result<PolygonArray, GeoArrowError> {
+    check_cell_size(cell_size)?;
+    Ok(grid_cells(bbox, bbox.min(), cell_size, grid_type).into())
+}
+
+/// Cut each polygon along a grid of cells with sides of `cell_size`.
+///
+/// Returns the non-empty intersections of each polygon with the cells it overlaps, and the index
+/// of the polygon each intersection was cut from. The grid has a cell at the origin, as in
+/// [`make_grid`], so that the pieces of all polygons line up along the same cells. An
+/// intersection can have several parts where a polygon enters a cell more than once, so
+/// intersections are multi polygons. Null polygons produce no pieces.
+/// # Errors
+/// Errors if `cell_size` is not a positive number.
+pub fn tessellate(
+    polygons: &PolygonArray,
+    cell_size: f64,
+    grid_type: GridType,
+) -> Result<(MultiPolygonArray, PrimitiveArray<u32>), GeoArrowError> {
+    check_cell_size(cell_size)?;
+    let origin = geo::coord! { x: 0., y: 0. };
+    let pieces = map_rows(polygons.len(), |i| {
+        let Some(polygon) = polygons.get_as_geo(i) else {
+            return vec![];
+        };
+        let Some(rect) = polygon.bounding_rect() else {
+            return vec![];
+        };
+        grid_cells(&rect, origin, cell_size, grid_type)
+            .into_iter()
+            .map(|cell| polygon.intersection(&cell))
+            .filter(|piece| !piece.0.is_empty())
+            .collect()
+    });
+
+    let mut indices = vec![];
+    for (i, row) in pieces.iter().enumerate() {
+        indices.resize(indices.len() + row.len(), i as u32);
+    }
+    let pieces: Vec<geo::MultiPolygon> = pieces.into_iter().flatten().collect();
+    Ok((
+        MultiPolygonArray::from(pieces).with_crs(polygons.crs()),
+        PrimitiveArray::from_vec(indices),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{polygon, Area, Contains};
+
+    #[test]
+    fn grids() {
+        let bbox = geo::Rect::new((0., 0.), (3., 2.));
+        let squares = make_grid(&bbox, 1., GridType::Square).unwrap();
+        assert_eq!(squares.len(), 6);
+        assert_eq!(
+            squares.value_as_geo(1),
+            geo::Rect::new((1., 0.), (2., 1.)).to_polygon()
+        );
+
+        // Cells on the edges extend past the bounding box
+        let squares = make_grid(&bbox, 2., GridType::Square).unwrap();
+        assert_eq!(squares.len(), 2);
+
+        let hexagons = make_grid(&bbox, 1., GridType::Hexagon).unwrap();
+        let total_area: f64 = hexagons.iter_geo_values().map(|h| h.unsigned_area()).sum();
+        assert!(hexagons
+            .iter_geo_values()
+            .all(|h| (h.unsigned_area() - 1.5 * 3f64.sqrt()).abs() < 1e-9));
+        assert!(total_area >= bbox.unsigned_area());
+        // Every point of the box is covered by a hexagon
+        for (x, y) in [(0.1, 0.1), (1.3, 0.9), (2.9, 1.9), (1.5, 1.5)] {
+            let point = geo::Point::new(x, y);
+            assert!(hexagons.iter_geo_values().any(|h| h.contains(&point)));
+        }
+
+        assert!(make_grid(&bbox, 0., GridType::Square).is_err());
+        assert!(make_grid(&bbox, f64::INFINITY, GridType::Hexagon).is_err());
+    }
+
+    #[test]
+    fn tessellation() {
+        let polygons: PolygonArray = vec![
+            Some(polygon![(x: 0.5, y: 0.5), (x: 2.5, y: 0.5), (x: 2.5, y: 1.5), (x: 0.5, y: 1.5)]),
+            None,
+            Some(polygon![(x: 0.2, y: 0.2), (x: 0.8, y: 0.2), (x: 0.8, y: 0.8)]),
+        ]
+        .into();
+        let polygons = polygons.with_crs(Some("EPSG:3857"));
+        let (pieces, indices) = tessellate(&polygons, 1., GridType::Square).unwrap();
+        assert_eq!(pieces.crs(), Some("EPSG:3857"));
+        assert_eq!(indices.values().as_slice(), &[0, 0, 0, 0, 0, 0, 2]);
+        assert_eq!(pieces.value_as_geo(0).unsigned_area(), 0.25);
+        assert_eq!(pieces.value_as_geo(1).unsigned_area(), 0.5);
+        let total_area: f64 = pieces.iter_geo_values().map(|p| p.unsigned_area()).sum();
+        assert!((total_area - 2.18).abs() < 1e-9);
+
+        let (pieces, indices) = tessellate(&polygons, 1., GridType::Hexagon).unwrap();
+        assert_eq!(pieces.len(), indices.len());
+        let area_0: f64 = pieces
+            .iter_geo_values()
+            .zip(indices.values().iter())
+            .filter(|(_, i)| **i == 0)
+            .map(|(p, _)| p.unsigned_area())
+            .sum();
+        assert!((area_0 - 2.).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,99 @@
+//! Index points into [H3](https://h3geo.org) cells, and turn cells back into polygons.
+//!
+//! H3 tiles the globe with hexagons at sixteen resolutions, each cell identified by a 64-bit
+//! integer. Indexing two tables of points at the same resolution turns a spatial join into an
+//! equality join on the cell ids, and aggregating by cell id bins points into a regular grid.
+//!
+//! Coordinates are longitude/latitude in degrees, and cell boundaries are returned in
+//! `EPSG:4326`.
+
+use crate::error::GeoArrowError;
+use crate::{GeometryArrayTrait, PointArray, PolygonArray};
+use arrow2::array::PrimitiveArray;
+use h3o::{CellIndex, LatLng, Resolution};
+
+/// The id of the H3 cell containing each point, at `resolution`.
+///
+/// Resolutions range from 0, with cells of about 4.3 million km², to 15, with cells of about
+/// 0.9 m². Null points produce null cell ids.
+/// # Errors
+/// Errors if `resolution` is above 15, or if any coordinate is not finite.
+pub fn points_to_cells(
+    points: &PointArray,
+    resolution: u8,
+) -> Result<PrimitiveArray<u64>, GeoArrowError> {
+    let resolution =
+        Resolution::try_from(resolution).map_err(|err| GeoArrowError::External(err.into()))?;
+    points
+        .iter_geo()
+        .map(|point| {
+            point
+                .map(|point| {
+                    let lat_lng = LatLng::new(point.y(), point.x())
+                        .map_err(|err| GeoArrowError::External(err.into()))?;
+                    Ok(u64::from(lat_lng.to_cell(resolution)))
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+/// The boundary of each H3 cell, as a polygon.
+///
+/// Null cell ids produce null polygons. Cells crossing the antimeridian are not split, so their
+/// longitudes jump between -180 and 180.
+/// # Errors
+/// Errors if any id is not a valid H3 cell index.
+pub fn cells_to_polygons(cells: &PrimitiveArray<u64>) -> Result<PolygonArray, GeoArrowError> {
+    let polygons = cells
+        .iter()
+        .map(|cell| {
+            cell.map(|cell| {
+                let cell = CellIndex::try_from(*cell)
+                    .map_err(|err| GeoArrowError::External(err.into()))?;
+                let exterior: geo::LineString = cell
+                    .boundary()
+                    .iter()
+                    .map(|vertex| geo::coord! { x: vertex.lng(), y: vertex.lat() })
+                    .collect();
+                Ok(geo::Polygon::new(exterior, vec![]))
+            })
+            .transpose()
+        })
+        .collect::<Result<Vec<_>, GeoArrowError>>()?;
+    Ok(PolygonArray::from(polygons).with_crs(Some("EPSG:4326")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{point, Contains};
+
+    #[test]
+    fn round_trip() {
+        let points: PointArray = vec![
+            Some(point!(x: -122.418307270836, y: 37.7752702151959)),
+            None,
+        ]
+        .into();
+        let cells = points_to_cells(&points, 9).unwrap();
+        assert_eq!(cells.value(0), 0x8928308280fffff);
+        assert!(cells.is_null(1));
+
+        let polygons = cells_to_polygons(&cells).unwrap();
+        assert_eq!(polygons.crs(), Some("EPSG:4326"));
+        let hexagon = polygons.value_as_geo(0);
+        assert_eq!(hexagon.exterior().0.len(), 7);
+        assert!(hexagon.contains(&points.value_as_geo(0)));
+        assert!(polygons.is_null(1));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let points: PointArray = vec![point!(x: 0., y: 0.)].into();
+        assert!(points_to_cells(&points, 16).is_err());
+
+        let cells = PrimitiveArray::from_vec(vec![0]);
+        assert!(cells_to_polygons(&cells).is_err());
+    }
+}
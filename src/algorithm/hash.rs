@@ -0,0 +1,245 @@
+//! Hash geometries, and find the distinct geometries of an array.
+//!
+//! Both work on a key of each geometry: its type, the number of parts at each level of nesting
+//! and the bits of its coordinates, with `-0.0` read as `0.0` and every NaN as the same NaN. Two
+//! geometries have the same key exactly when they have the same type and the same coordinates
+//! in the same order, so the same ring starting at another vertex is a different geometry.
+//!
+//! Hashes are computed with a fixed function, so they are the same across processes and can be
+//! stored or used as join keys.
+
+use crate::algorithm::take::Take;
+use crate::error::GeoArrowError;
+use crate::parallel::map_rows;
+use crate::GeometryArrayTrait;
+use arrow2::array::PrimitiveArray;
+use std::collections::HashMap;
+
+/// The bits of `value`, with all zeros and all NaNs made equal.
+fn normalized_bits(value: f64) -> u64 {
+    if value == 0.0 {
+        0
+    } else if value.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
+fn push_coord(coord: &geo::Coord, key: &mut Vec<u64>) {
+    key.push(normalized_bits(coord.x));
+    key.push(normalized_bits(coord.y));
+}
+
+fn push_line_string(line_string: &geo::LineString, key: &mut Vec<u64>) {
+    key.push(line_string.0.len() as u64);
+    line_string
+        .0
+        .iter()
+        .for_each(|coord| push_coord(coord, key));
+}
+
+fn push_polygon(polygon: &geo::Polygon, key: &mut Vec<u64>) {
+    key.push(polygon.interiors().len() as u64 + 1);
+    push_line_string(polygon.exterior(), key);
+    for interior in polygon.interiors() {
+        push_line_string(interior, key);
+    }
+}
+
+/// Append the key of `geom` to `key`, starting with a tag for its type.
+fn push_key(geom: &geo::Geometry, key: &mut Vec<u64>) {
+    match geom {
+        geo::Geometry::Point(geom) => {
+            key.push(1);
+            push_coord(&geom.0, key);
+        }
+        geo::Geometry::Line(geom) => {
+            key.push(2);
+            push_coord(&geom.start, key);
+            push_coord(&geom.end, key);
+        }
+        geo::Geometry::LineString(geom) => {
+            key.push(3);
+            push_line_string(geom, key);
+        }
+        geo::Geometry::Polygon(geom) => {
+            key.push(4);
+            push_polygon(geom, key);
+        }
+        geo::Geometry::MultiPoint(geom) => {
+            key.push(5);
+            key.push(geom.0.len() as u64);
+            geom.0.iter().for_each(|point| push_coord(&point.0, key));
+        }
+        geo::Geometry::MultiLineString(geom) => {
+            key.push(6);
+            key.push(geom.0.len() as u64);
+            geom.0.iter().for_each(|line| push_line_string(line, key));
+        }
+        geo::Geometry::MultiPolygon(geom) => {
+            key.push(7);
+            key.push(geom.0.len() as u64);
+            geom.0.iter().for_each(|polygon| push_polygon(polygon, key));
+        }
+        geo::Geometry::GeometryCollection(geom) => {
+            key.push(8);
+            key.push(geom.0.len() as u64);
+            geom.0.iter().for_each(|geom| push_key(geom, key));
+        }
+        geo::Geometry::Rect(geom) => {
+            key.push(9);
+            push_coord(&geom.min(), key);
+            push_coord(&geom.max(), key);
+        }
+        geo::Geometry::Triangle(geom) => {
+            key.push(10);
+            geom.to_array()
+                .iter()
+                .for_each(|coord| push_coord(coord, key));
+        }
+    }
+}
+
+/// The key of each geometry of `array`, or `None` for nulls.
+fn keys<'a, A>(array: &'a A) -> Vec<Option<Vec<u64>>>
+where
+    A: GeometryArrayTrait<'a> + Sync,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    map_rows(array.len(), |i| {
+        array.get_as_geo(i).map(|geom| {
+            let mut key = vec![];
+            push_key(&geom.into(), &mut key);
+            key
+        })
+    })
+}
+
+/// Hash `key` with 64-bit FNV-1a over whole words, followed by the finalizer of SplitMix64 to
+/// spread the bits of similar keys.
+fn hash_key(key: &[u64]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for word in key {
+        hash = (hash ^ word).wrapping_mul(0x100000001b3);
+    }
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+    hash ^ (hash >> 31)
+}
+
+/// A 64-bit hash of each geometry of `array`.
+///
+/// Equal geometries, as described in the [module docs](self), have equal hashes. Null geometries
+/// produce null hashes, and rows are hashed in parallel with the `rayon` feature.
+pub fn geometry_hash<'a, A>(array: &'a A) -> PrimitiveArray<u64>
+where
+    A: GeometryArrayTrait<'a> + Sync,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    keys(array)
+        .iter()
+        .map(|key| key.as_deref().map(hash_key))
+        .collect()
+}
+
+/// The distinct geometries of `array`, in order of first appearance, and the index into them of
+/// each geometry of `array`.
+///
+/// Taking the inverse index from the distinct geometries gives back `array`. Null geometries are
+/// left out of the distinct geometries and have null indices. The CRS is kept.
+/// # Errors
+/// Errors if the offsets of the distinct geometries would overflow.
+pub fn unique<'a, A>(array: &'a A) -> Result<(A, PrimitiveArray<u32>), GeoArrowError>
+where
+    A: GeometryArrayTrait<'a> + Take + Sync,
+    A::ScalarGeo: Into<geo::Geometry>,
+{
+    let mut first_rows = vec![];
+    let mut seen: HashMap<Vec<u64>, u32> = HashMap::new();
+    let inverse: Vec<Option<u32>> = keys(array)
+        .into_iter()
+        .enumerate()
+        .map(|(row, key)| {
+            key.map(|key| {
+                *seen.entry(key).or_insert_with(|| {
+                    first_rows.push(row as i64);
+                    first_rows.len() as u32 - 1
+                })
+            })
+        })
+        .collect();
+
+    let distinct = array.take(&PrimitiveArray::from_vec(first_rows))?;
+    Ok((distinct, inverse.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LineStringArray, MultiPointArray, PointArray};
+    use arrow2::array::Array;
+    use geo::{line_string, point};
+
+    #[test]
+    fn hashes() {
+        let points: PointArray = vec![
+            Some(point!(x: 0., y: 1.)),
+            Some(point!(x: -0., y: 1.)),
+            Some(point!(x: 1., y: 0.)),
+            None,
+            Some(point!(x: f64::NAN, y: 1.)),
+            Some(point!(x: -f64::NAN, y: 1.)),
+        ]
+        .into();
+        let hashes = geometry_hash(&points);
+        assert_eq!(hashes.value(0), hashes.value(1));
+        assert_ne!(hashes.value(0), hashes.value(2));
+        assert!(hashes.is_null(3));
+        assert_eq!(hashes.value(4), hashes.value(5));
+
+        // The geometry type is part of the hash
+        let multi_point = geo::MultiPoint::new(vec![point!(x: 0., y: 1.)]);
+        let multi_points: MultiPointArray = vec![multi_point].into();
+        assert_ne!(hashes.value(0), geometry_hash(&multi_points).value(0));
+    }
+
+    #[test]
+    fn distinct() {
+        let first = line_string![(x: 0., y: 0.), (x: 1., y: 1.)];
+        let second = line_string![(x: 1., y: 1.), (x: 0., y: 0.)];
+        let lines: LineStringArray = vec![
+            Some(first.clone()),
+            Some(second.clone()),
+            None,
+            Some(first.clone()),
+            Some(second.clone()),
+        ]
+        .into();
+        let lines = lines.with_crs(Some("EPSG:3857"));
+
+        let (distinct, inverse) = unique(&lines).unwrap();
+        assert_eq!(distinct.crs(), Some("EPSG:3857"));
+        assert_eq!(
+            distinct.iter_geo().collect::<Vec<_>>(),
+            vec![Some(first), Some(second)]
+        );
+        assert_eq!(
+            inverse,
+            PrimitiveArray::from([Some(0), Some(1), None, Some(0), Some(1)])
+        );
+
+        let restored = distinct
+            .take(&PrimitiveArray::from(
+                inverse
+                    .iter()
+                    .map(|i| i.map(|i| *i as i64))
+                    .collect::<Vec<_>>(),
+            ))
+            .unwrap();
+        assert_eq!(
+            restored.iter_geo().collect::<Vec<_>>(),
+            lines.iter_geo().collect::<Vec<_>>()
+        );
+    }
+}
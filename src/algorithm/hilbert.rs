@@ -0,0 +1,182 @@
+//! Positions of geometries along a [Hilbert curve](https://en.wikipedia.org/wiki/Hilbert_curve).
+//!
+//! Sorting geometries by their Hilbert key places geometries that are close in space close
+//! together in the output, which makes spatially partitioned files and bounding-box pruning much
+//! more effective.
+
+use crate::algorithm::bounds::TotalBounds;
+use crate::algorithm::take::Take;
+use crate::error::GeoArrowError;
+use crate::indexed::envelope;
+use crate::{GeometryArray, GeometryArrayTrait};
+use arrow2::array::{MutablePrimitiveArray, PrimitiveArray};
+use rstar::Envelope;
+
+/// The number of cells along each axis of the Hilbert grid.
+const HILBERT_GRID_SIZE: u32 = 1 << 16;
+
+/// The distance along a Hilbert curve of order 16 of the grid cell `(x, y)`.
+fn hilbert_distance(mut x: u32, mut y: u32) -> u32 {
+    let n = HILBERT_GRID_SIZE;
+    let mut distance: u64 = 0;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = u32::from(x & s > 0);
+        let ry = u32::from(y & s > 0);
+        distance += u64::from(s) * u64::from(s) * u64::from((3 * rx) ^ ry);
+
+        // Rotate the quadrant so that the curve is continuous
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    distance as u32
+}
+
+/// Map `value` from `[min, max]` to a grid cell along one axis.
+fn grid_cell(value: f64, min: f64, max: f64) -> u32 {
+    let extent = max - min;
+    if extent <= 0.0 || !extent.is_finite() {
+        return 0;
+    }
+    let scaled = (value - min) / extent * f64::from(HILBERT_GRID_SIZE - 1);
+    scaled.round().clamp(0.0, f64::from(HILBERT_GRID_SIZE - 1)) as u32
+}
+
+/// The Hilbert key of `coord` within `bounds`.
+///
+/// `bounds` is divided into a 65536 × 65536 grid, and the key is the position of the cell
+/// containing `coord` along the curve. Coordinates outside `bounds` are clamped to its edges.
+pub fn hilbert_index(coord: geo::Coord, bounds: &geo::Rect) -> u32 {
+    let x = grid_cell(coord.x, bounds.min().x, bounds.max().x);
+    let y = grid_cell(coord.y, bounds.min().y, bounds.max().y);
+    hilbert_distance(x, y)
+}
+
+/// The Hilbert key of the center of each geometry's bounding box within `bounds`.
+///
+/// The output is null for null and empty geometries. `bounds` is usually the total bounds of
+/// the dataset being sorted.
+pub fn hilbert_keys(array: &GeometryArray, bounds: &geo::Rect) -> PrimitiveArray<u32> {
+    let mut output = MutablePrimitiveArray::with_capacity(array.len());
+    for i in 0..array.len() {
+        let key = envelope(array, i).map(|envelope| {
+            let center = envelope.center();
+            hilbert_index(geo::Coord::from((center[0], center[1])), bounds)
+        });
+        output.push(key);
+    }
+    output.into()
+}
+
+/// The indices that sort `array` by the Hilbert key of the center of each geometry's bounding
+/// box within the total bounds of the array.
+///
+/// This is the order FlatGeobuf writes features in to build its packed R-tree. The sort is
+/// stable, and null and empty geometries are placed last. Pass the indices to
+/// [`Take::take`] to reorder the array or its table.
+pub fn hilbert_sort_indices(array: &GeometryArray) -> PrimitiveArray<i64> {
+    let Some([min_x, min_y, max_x, max_y]) = array.total_bounds() else {
+        return PrimitiveArray::from_vec((0..array.len() as i64).collect());
+    };
+    let bounds = geo::Rect::new((min_x, min_y), (max_x, max_y));
+    let keys: Vec<Option<u32>> = hilbert_keys(array, &bounds)
+        .iter()
+        .map(|key| key.copied())
+        .collect();
+
+    // `None` sorts before `Some`, so sort on whether the key is null first to place nulls last
+    let mut indices: Vec<i64> = (0..array.len() as i64).collect();
+    indices.sort_by_key(|&i| (keys[i as usize].is_none(), keys[i as usize]));
+    PrimitiveArray::from_vec(indices)
+}
+
+/// `array` with its geometries sorted along a Hilbert curve, as by [`hilbert_sort_indices`].
+///
+/// Spatially sorted arrays compress better and let readers skip more row groups and pages with
+/// bounding box filters.
+/// # Errors
+/// Errors if the offsets of the sorted array would overflow.
+pub fn hilbert_sort(array: &GeometryArray) -> Result<GeometryArray, GeoArrowError> {
+    array.take(&hilbert_sort_indices(array))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PointArray;
+    use arrow2::array::Array;
+    use geo::{coord, point};
+
+    #[test]
+    fn curve_visits_neighbors() {
+        assert_eq!(hilbert_distance(0, 0), 0);
+
+        // Consecutive distances along the curve are adjacent grid cells
+        let mut cells = vec![(0_u32, 0_u32); 256];
+        for x in 0..16 {
+            for y in 0..16 {
+                let distance = hilbert_distance(x << 12, y << 12) >> 24;
+                cells[distance as usize] = (x, y);
+            }
+        }
+        for pair in cells.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+        }
+    }
+
+    #[test]
+    fn keys() {
+        let bounds = geo::Rect::new(coord! { x: 0., y: 0. }, coord! { x: 10., y: 10. });
+        assert_eq!(hilbert_index(coord! { x: 0., y: 0. }, &bounds), 0);
+        assert_eq!(
+            hilbert_index(coord! { x: -5., y: -5. }, &bounds),
+            hilbert_index(coord! { x: 0., y: 0. }, &bounds)
+        );
+
+        let arr: PointArray = vec![
+            Some(point!(x: 0., y: 0.)),
+            None,
+            Some(point!(x: 10., y: 0.)),
+            Some(point!(x: 0.1, y: 0.1)),
+        ]
+        .into();
+        let keys = hilbert_keys(&GeometryArray::Point(arr), &bounds);
+        assert!(keys.is_null(1));
+        assert!(keys.value(0) < keys.value(3));
+        assert!(keys.value(3) < keys.value(2));
+    }
+
+    #[test]
+    fn sort() {
+        let arr: PointArray = vec![
+            Some(point!(x: 10., y: 0.)),
+            None,
+            Some(point!(x: 0., y: 0.)),
+            Some(point!(x: 0.1, y: 0.1)),
+            Some(point!(x: 0., y: 0.)),
+        ]
+        .into();
+        let arr = GeometryArray::Point(arr.with_crs(Some("EPSG:3857")));
+        let indices = hilbert_sort_indices(&arr);
+        assert_eq!(indices.values().as_slice(), &[2, 4, 3, 0, 1]);
+
+        let sorted = hilbert_sort(&arr).unwrap();
+        assert_eq!(sorted.crs(), Some("EPSG:3857"));
+        assert_eq!(
+            sorted.value_as_geo(0),
+            geo::Geometry::Point(point!(x: 0., y: 0.))
+        );
+        assert!(sorted.is_null(4));
+
+        let empty: PointArray = vec![None, None].into();
+        let indices = hilbert_sort_indices(&GeometryArray::Point(empty));
+        assert_eq!(indices.values().as_slice(), &[0, 1]);
+    }
+}
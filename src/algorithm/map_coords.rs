@@ -0,0 +1,181 @@
+//! Apply a function to every coordinate of a geometry array.
+//!
+//! This is the generic building block for coordinate transformations without a dedicated kernel,
+//! such as custom projections, affine transforms or snapping. Native arrays are transformed
+//! directly on their coordinate buffers: offsets and validity are shared with the input. With
+//! the `rayon` feature, coordinates are transformed in parallel.
+
+use crate::parallel::map_rows;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use arrow2::buffer::Buffer;
+
+/// Apply a function to every coordinate of a geometry array.
+pub trait MapCoords {
+    /// A new array with every `(x, y)` coordinate replaced by `f(x, y)`.
+    ///
+    /// The CRS of the input is kept, so set a new one with `with_crs` if `f` reprojects.
+    fn map_coords<F>(&self, f: F) -> Self
+    where
+        F: Fn(f64, f64) -> (f64, f64) + Sync + Send;
+}
+
+fn map_buffers<F>(x: &Buffer<f64>, y: &Buffer<f64>, f: F) -> (Buffer<f64>, Buffer<f64>)
+where
+    F: Fn(f64, f64) -> (f64, f64) + Sync + Send,
+{
+    let (x, y): (Vec<f64>, Vec<f64>) = map_rows(x.len(), |i| f(x[i], y[i])).into_iter().unzip();
+    (x.into(), y.into())
+}
+
+macro_rules! buffer_impl {
+    ($type:ty) => {
+        impl MapCoords for $type {
+            fn map_coords<F>(&self, f: F) -> Self
+            where
+                F: Fn(f64, f64) -> (f64, f64) + Sync + Send,
+            {
+                let (x, y) = map_buffers(self.values_x(), self.values_y(), f);
+                self.with_coords(x, y)
+            }
+        }
+    };
+}
+
+buffer_impl!(PointArray);
+buffer_impl!(LineStringArray);
+buffer_impl!(PolygonArray);
+buffer_impl!(MultiPointArray);
+buffer_impl!(MultiLineStringArray);
+buffer_impl!(MultiPolygonArray);
+
+impl MapCoords for RectArray {
+    /// Only the minimum and maximum corners of each rectangle are transformed, so rectangles
+    /// stay axis-aligned: under a rotation or a projection, they may not cover the transformed
+    /// area of the input.
+    fn map_coords<F>(&self, f: F) -> Self
+    where
+        F: Fn(f64, f64) -> (f64, f64) + Sync + Send,
+    {
+        let (min_x, min_y) = map_buffers(self.values_minx(), self.values_miny(), &f);
+        let (max_x, max_y) = map_buffers(self.values_maxx(), self.values_maxy(), &f);
+        // A transform may swap the corners, as a reflection does
+        let (min_x, max_x) = min_max(&min_x, &max_x);
+        let (min_y, max_y) = min_max(&min_y, &max_y);
+        RectArray::new(min_x, min_y, max_x, max_y, self.validity().cloned()).with_crs(self.crs())
+    }
+}
+
+/// The element-wise minimum and maximum of `a` and `b`.
+fn min_max(a: &Buffer<f64>, b: &Buffer<f64>) -> (Buffer<f64>, Buffer<f64>) {
+    let (min, max): (Vec<f64>, Vec<f64>) = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| (a.min(*b), a.max(*b)))
+        .unzip();
+    (min.into(), max.into())
+}
+
+impl MapCoords for WKBArray {
+    fn map_coords<F>(&self, f: F) -> Self
+    where
+        F: Fn(f64, f64) -> (f64, f64) + Sync + Send,
+    {
+        let output_geoms: Vec<Option<geo::Geometry>> = map_rows(self.len(), |i| {
+            self.get_as_geo(i).map(|geom| {
+                geo::MapCoords::map_coords(&geom, |coord| {
+                    let (x, y) = f(coord.x, coord.y);
+                    geo::Coord { x, y }
+                })
+            })
+        });
+
+        let output: WKBArray = output_geoms.into();
+        output.with_crs(self.crs())
+    }
+}
+
+impl MapCoords for MixedGeometryArray {
+    fn map_coords<F>(&self, f: F) -> Self
+    where
+        F: Fn(f64, f64) -> (f64, f64) + Sync + Send,
+    {
+        self.map_children(
+            |arr| arr.map_coords(&f),
+            |arr| arr.map_coords(&f),
+            |arr| arr.map_coords(&f),
+            |arr| arr.map_coords(&f),
+            |arr| arr.map_coords(&f),
+            |arr| arr.map_coords(&f),
+        )
+    }
+}
+
+impl MapCoords for GeometryArray {
+    fn map_coords<F>(&self, f: F) -> Self
+    where
+        F: Fn(f64, f64) -> (f64, f64) + Sync + Send,
+    {
+        match self {
+            GeometryArray::Point(arr) => GeometryArray::Point(arr.map_coords(f)),
+            GeometryArray::LineString(arr) => GeometryArray::LineString(arr.map_coords(f)),
+            GeometryArray::Polygon(arr) => GeometryArray::Polygon(arr.map_coords(f)),
+            GeometryArray::MultiPoint(arr) => GeometryArray::MultiPoint(arr.map_coords(f)),
+            GeometryArray::MultiLineString(arr) => {
+                GeometryArray::MultiLineString(arr.map_coords(f))
+            }
+            GeometryArray::MultiPolygon(arr) => GeometryArray::MultiPolygon(arr.map_coords(f)),
+            GeometryArray::WKB(arr) => GeometryArray::WKB(arr.map_coords(f)),
+            GeometryArray::Rect(arr) => GeometryArray::Rect(arr.map_coords(f)),
+            GeometryArray::Mixed(arr) => GeometryArray::Mixed(Box::new(arr.map_coords(f))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point, polygon};
+
+    #[test]
+    fn native() {
+        let arr: PointArray = vec![Some(point!(x: 1., y: 2.)), None].into();
+        let swapped = arr.with_crs(Some("EPSG:4326")).map_coords(|x, y| (y, x));
+        assert_eq!(swapped.get_as_geo(0), Some(point!(x: 2., y: 1.)));
+        assert!(swapped.is_null(1));
+        assert_eq!(swapped.crs(), Some("EPSG:4326"));
+
+        let polygons: PolygonArray =
+            vec![polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)]].into();
+        let shifted = polygons.map_coords(|x, y| (x + 10., y));
+        assert_eq!(shifted.ring_offsets(), polygons.ring_offsets());
+        assert_eq!(
+            shifted.value_as_geo(0),
+            polygon![(x: 10., y: 0.), (x: 11., y: 0.), (x: 11., y: 1.)]
+        );
+    }
+
+    #[test]
+    fn geometry_arrays() {
+        let line = line_string![(x: 0., y: 1.), (x: 2., y: 3.)];
+        let expected = line_string![(x: 0., y: -1.), (x: 2., y: -3.)];
+        let wkb: WKBArray = vec![Some(geo::Geometry::LineString(line.clone()))].into();
+        let lines: LineStringArray = vec![line].into();
+        for arr in [GeometryArray::WKB(wkb), GeometryArray::LineString(lines)] {
+            let reflected = arr.map_coords(|x, y| (x, -y));
+            assert_eq!(
+                reflected.get_as_geo(0),
+                Some(geo::Geometry::LineString(expected.clone()))
+            );
+        }
+
+        let rects: RectArray = vec![geo::Rect::new((0., 1.), (2., 3.))].into();
+        let reflected = rects.map_coords(|x, y| (-x, y));
+        assert_eq!(
+            reflected.value_as_geo(0),
+            geo::Rect::new((-2., 1.), (0., 3.))
+        );
+    }
+}
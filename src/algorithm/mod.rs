@@ -1 +1,25 @@
 pub mod bounding_rect;
+pub mod bounds;
+pub mod clip;
+pub mod counts;
+pub mod delaunay;
+pub mod explode;
+#[cfg(feature = "ipc")]
+pub mod external_sort;
+pub mod geo;
+#[cfg(feature = "geos")]
+pub mod geos;
+pub mod grid;
+#[cfg(feature = "h3")]
+pub mod h3;
+pub mod hash;
+pub mod hilbert;
+pub mod map_coords;
+pub mod orient;
+pub mod point_in_polygon;
+#[cfg(feature = "proj")]
+pub mod proj;
+pub mod rasterize;
+pub mod round;
+pub mod sample;
+pub mod take;
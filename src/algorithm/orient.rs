@@ -0,0 +1,242 @@
+//! Winding order of polygon rings.
+//!
+//! The OGC and GeoParquet conventions wind exterior rings counterclockwise and interior rings
+//! clockwise, while some renderers and formats expect the reverse. These kernels read and fix
+//! the winding order directly on the coordinate buffers: reorienting a ring reverses its
+//! coordinates in place, so the offsets are shared with the input.
+
+use crate::{GeometryArrayTrait, MultiPolygonArray, PolygonArray};
+use arrow2::array::{BooleanArray, ListArray};
+use arrow2::bitmap::MutableBitmap;
+use arrow2::datatypes::DataType;
+use arrow2::offset::{Offsets, OffsetsBuffer};
+
+/// The winding order enforced by [`Orient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Counterclockwise exterior rings and clockwise interior rings.
+    Default,
+
+    /// Clockwise exterior rings and counterclockwise interior rings.
+    Reversed,
+}
+
+/// Enforce a winding order on the rings of polygons.
+pub trait Orient {
+    /// The array with every ring wound in `direction`.
+    ///
+    /// Rings that enclose no area have no winding order and are left as they are.
+    fn orient(&self, direction: Direction) -> Self;
+
+    /// Whether each ring of each geometry is wound counterclockwise, exterior rings first and
+    /// polygon by polygon. Rings that enclose no area are not counterclockwise. Null geometries
+    /// produce null lists.
+    fn is_ccw(&self) -> ListArray<i64>;
+}
+
+/// Twice the signed area of the ring with coordinates `x` and `y`, positive if it is wound
+/// counterclockwise.
+fn twice_signed_area(x: &[f64], y: &[f64]) -> f64 {
+    let Some((&x0, &y0)) = x.first().zip(y.first()) else {
+        return 0.0;
+    };
+    // Shift coordinates by the first one to reduce floating point error
+    (0..x.len())
+        .map(|i| {
+            let j = (i + 1) % x.len();
+            (x[i] - x0) * (y[j] - y0) - (x[j] - x0) * (y[i] - y0)
+        })
+        .sum()
+}
+
+/// Reverse ring `ring` of `ring_offsets` if it is not wound counterclockwise when `ccw`, or
+/// clockwise otherwise.
+fn orient_ring(
+    x: &mut [f64],
+    y: &mut [f64],
+    ring_offsets: &OffsetsBuffer<i64>,
+    ring: usize,
+    ccw: bool,
+) {
+    let (start, end) = ring_offsets.start_end(ring);
+    let area = twice_signed_area(&x[start..end], &y[start..end]);
+    if (ccw && area < 0.0) || (!ccw && area > 0.0) {
+        x[start..end].reverse();
+        y[start..end].reverse();
+    }
+}
+
+/// Whether each of `rings` of `ring_offsets` is wound counterclockwise.
+fn rings_ccw<'a>(
+    x: &'a [f64],
+    y: &'a [f64],
+    ring_offsets: &'a OffsetsBuffer<i64>,
+    rings: std::ops::Range<usize>,
+) -> impl Iterator<Item = bool> + 'a {
+    rings.map(|ring| {
+        let (start, end) = ring_offsets.start_end(ring);
+        twice_signed_area(&x[start..end], &y[start..end]) > 0.0
+    })
+}
+
+/// Build a list array of booleans from the booleans of each row.
+fn boolean_lists(
+    len: usize,
+    is_valid: impl Fn(usize) -> bool,
+    mut row: impl FnMut(usize, &mut MutableBitmap),
+) -> ListArray<i64> {
+    let mut offsets = Offsets::<i64>::with_capacity(len);
+    let mut values = MutableBitmap::new();
+    let mut validity = MutableBitmap::with_capacity(len);
+    for i in 0..len {
+        let start = values.len();
+        if is_valid(i) {
+            row(i, &mut values);
+        }
+        offsets
+            .try_push_usize(values.len() - start)
+            .expect("ring count fits in i64");
+        validity.push(is_valid(i));
+    }
+    let values = BooleanArray::new(DataType::Boolean, values.into(), None);
+    ListArray::new(
+        ListArray::<i64>::default_datatype(DataType::Boolean),
+        offsets.into(),
+        values.boxed(),
+        validity.into(),
+    )
+}
+
+impl Orient for PolygonArray {
+    fn orient(&self, direction: Direction) -> Self {
+        let mut x = self.values_x().to_vec();
+        let mut y = self.values_y().to_vec();
+        for i in 0..self.len() {
+            let (first, last) = self.geom_offsets().start_end(i);
+            for ring in first..last {
+                let exterior = ring == first;
+                let ccw = exterior == (direction == Direction::Default);
+                orient_ring(&mut x, &mut y, self.ring_offsets(), ring, ccw);
+            }
+        }
+        PolygonArray::new(
+            x.into(),
+            y.into(),
+            self.geom_offsets().clone(),
+            self.ring_offsets().clone(),
+            self.validity().cloned(),
+        )
+        .with_crs(self.crs())
+    }
+
+    fn is_ccw(&self) -> ListArray<i64> {
+        let (x, y) = (self.values_x(), self.values_y());
+        boolean_lists(
+            self.len(),
+            |i| self.is_valid(i),
+            |i, values| {
+                let (first, last) = self.geom_offsets().start_end(i);
+                for ccw in rings_ccw(x, y, self.ring_offsets(), first..last) {
+                    values.push(ccw);
+                }
+            },
+        )
+    }
+}
+
+impl Orient for MultiPolygonArray {
+    fn orient(&self, direction: Direction) -> Self {
+        let mut x = self.values_x().to_vec();
+        let mut y = self.values_y().to_vec();
+        for i in 0..self.len() {
+            let (first_polygon, last_polygon) = self.geom_offsets().start_end(i);
+            for polygon in first_polygon..last_polygon {
+                let (first, last) = self.polygon_offsets().start_end(polygon);
+                for ring in first..last {
+                    let exterior = ring == first;
+                    let ccw = exterior == (direction == Direction::Default);
+                    orient_ring(&mut x, &mut y, self.ring_offsets(), ring, ccw);
+                }
+            }
+        }
+        MultiPolygonArray::new(
+            x.into(),
+            y.into(),
+            self.geom_offsets().clone(),
+            self.polygon_offsets().clone(),
+            self.ring_offsets().clone(),
+            self.validity().cloned(),
+        )
+        .with_crs(self.crs())
+    }
+
+    fn is_ccw(&self) -> ListArray<i64> {
+        let (x, y) = (self.values_x(), self.values_y());
+        boolean_lists(
+            self.len(),
+            |i| self.is_valid(i),
+            |i, values| {
+                let (first_polygon, last_polygon) = self.geom_offsets().start_end(i);
+                let (first, _) = self.polygon_offsets().start_end(first_polygon);
+                let last = if last_polygon > first_polygon {
+                    self.polygon_offsets().start_end(last_polygon - 1).1
+                } else {
+                    first
+                };
+                for ccw in rings_ccw(x, y, self.ring_offsets(), first..last) {
+                    values.push(ccw);
+                }
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::Array;
+    use geo::{polygon, MultiPolygon};
+
+    fn clockwise_with_ccw_hole() -> geo::Polygon {
+        polygon!(
+            exterior: [(x: 0., y: 0.), (x: 0., y: 10.), (x: 10., y: 10.), (x: 10., y: 0.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 2.)]],
+        )
+    }
+
+    fn ring_flags(lists: &ListArray<i64>, i: usize) -> Vec<bool> {
+        let values = lists.value(i);
+        let values = values.as_any().downcast_ref::<BooleanArray>().unwrap();
+        values.values_iter().collect()
+    }
+
+    #[test]
+    fn polygons() {
+        let arr: PolygonArray = vec![Some(clockwise_with_ccw_hole()), None].into();
+        let ccw = arr.is_ccw();
+        assert_eq!(ring_flags(&ccw, 0), vec![false, true]);
+        assert!(ccw.is_null(1));
+
+        let oriented = arr.orient(Direction::Default);
+        assert_eq!(ring_flags(&oriented.is_ccw(), 0), vec![true, false]);
+        assert!(oriented.is_null(1));
+        assert_eq!(oriented.ring_offsets(), arr.ring_offsets());
+        let polygon = oriented.value_as_geo(0);
+        assert_eq!(polygon.exterior().0[1], geo::coord! { x: 10., y: 0. });
+
+        let reversed = oriented.orient(Direction::Reversed);
+        assert_eq!(reversed, arr);
+    }
+
+    #[test]
+    fn multi_polygons() {
+        let arr: MultiPolygonArray = vec![MultiPolygon::new(vec![
+            clockwise_with_ccw_hole(),
+            polygon![(x: 20., y: 0.), (x: 21., y: 0.), (x: 21., y: 1.)],
+        ])]
+        .into();
+        assert_eq!(ring_flags(&arr.is_ccw(), 0), vec![false, true, true]);
+        let oriented = arr.orient(Direction::Reversed);
+        assert_eq!(ring_flags(&oriented.is_ccw(), 0), vec![false, true, false]);
+    }
+}
@@ -0,0 +1,246 @@
+//! Test many points against polygons.
+//!
+//! [`PreparedPolygon`] indexes the edges of a polygon once by the horizontal band they span, so
+//! that each point is only tested against the few edges crossing its band rather than against
+//! every edge of the polygon. This makes [`points_in_polygon`] close to linear in the number of
+//! points, even for polygons with many vertices, and [`points_in_polygons`] combines it with an
+//! [`RTree`](rstar::RTree) of the polygons' envelopes.
+//!
+//! As with [`geo::Contains`], points on the boundary of a polygon are not in it.
+
+use crate::geo_traits::{LineStringTrait, PointTrait, PolygonTrait};
+use crate::indexed::envelope_tree;
+use crate::parallel::map_rows;
+use crate::{GeometryArrayTrait, PointArray, PolygonArray};
+use arrow2::array::{BooleanArray, PrimitiveArray};
+use rstar::AABB;
+
+/// The most bands the edges of a polygon are indexed into.
+const MAX_BANDS: usize = 4096;
+
+/// Push the edges of a ring to `edges`, closing it if it is not explicitly closed.
+fn push_ring_edges<'a>(ring: &impl LineStringTrait<'a>, edges: &mut Vec<[f64; 4]>) {
+    let num_points = ring.num_points();
+    let coord = |i: usize| ring.point(i).unwrap().x_y();
+    for i in 1..num_points {
+        let ((x0, y0), (x1, y1)) = (coord(i - 1), coord(i));
+        edges.push([x0, y0, x1, y1]);
+    }
+    if num_points > 1 && coord(0) != coord(num_points - 1) {
+        let ((x0, y0), (x1, y1)) = (coord(num_points - 1), coord(0));
+        edges.push([x0, y0, x1, y1]);
+    }
+}
+
+/// A polygon with its edges indexed for fast point-in-polygon tests.
+#[derive(Debug, Clone)]
+pub struct PreparedPolygon {
+    /// Every edge of every ring as `[x0, y0, x1, y1]`
+    edges: Vec<[f64; 4]>,
+    /// The indices of the edges spanning each horizontal band, bottom band first
+    bands: Vec<Vec<u32>>,
+    /// The bounding box as `[min_x, min_y, max_x, max_y]`
+    bounds: [f64; 4],
+}
+
+impl PreparedPolygon {
+    /// Index the edges of `polygon`.
+    pub fn new<'a>(polygon: &impl PolygonTrait<'a>) -> Self {
+        let mut edges = vec![];
+        push_ring_edges(&polygon.exterior(), &mut edges);
+        for ring_idx in 0..polygon.num_interiors() {
+            push_ring_edges(&polygon.interior(ring_idx).unwrap(), &mut edges);
+        }
+
+        let mut bounds = [
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        ];
+        for [x0, y0, x1, y1] in &edges {
+            bounds[0] = bounds[0].min(x0.min(*x1));
+            bounds[1] = bounds[1].min(y0.min(*y1));
+            bounds[2] = bounds[2].max(x0.max(*x1));
+            bounds[3] = bounds[3].max(y0.max(*y1));
+        }
+
+        let mut prepared = Self {
+            bands: vec![vec![]; edges.len().clamp(1, MAX_BANDS)],
+            edges,
+            bounds,
+        };
+        for (edge_idx, [_, y0, _, y1]) in prepared.edges.iter().enumerate() {
+            let first = prepared.band(y0.min(*y1));
+            let last = prepared.band(y0.max(*y1));
+            for band in &mut prepared.bands[first..=last] {
+                band.push(edge_idx as u32);
+            }
+        }
+        prepared
+    }
+
+    /// The band containing `y`, which must be within the bounds of the polygon.
+    fn band(&self, y: f64) -> usize {
+        let height = self.bounds[3] - self.bounds[1];
+        if height <= 0.0 {
+            return 0;
+        }
+        let band = ((y - self.bounds[1]) / height * self.bands.len() as f64) as usize;
+        band.min(self.bands.len() - 1)
+    }
+
+    /// Whether the point `(x, y)` is in the interior of the polygon.
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        let [min_x, min_y, max_x, max_y] = self.bounds;
+        if !(min_x < x && x < max_x && min_y < y && y < max_y) {
+            return false;
+        }
+
+        // Count the crossings of a ray from the point towards positive x
+        let mut inside = false;
+        for &edge_idx in &self.bands[self.band(y)] {
+            let [x0, y0, x1, y1] = self.edges[edge_idx as usize];
+            if y < y0.min(y1) || y > y0.max(y1) {
+                continue;
+            }
+            let cross = (x1 - x0) * (y - y0) - (y1 - y0) * (x - x0);
+            if cross == 0.0 && x0.min(x1) <= x && x <= x0.max(x1) {
+                // On the boundary
+                return false;
+            }
+            if (y0 > y) != (y1 > y) && x < x0 + (y - y0) * (x1 - x0) / (y1 - y0) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+/// Whether each point is in the interior of `polygon`.
+///
+/// The edges of `polygon` are indexed once, and points are tested in parallel with the `rayon`
+/// feature. Null points produce null results.
+pub fn points_in_polygon<'a>(points: &PointArray, polygon: &impl PolygonTrait<'a>) -> BooleanArray {
+    let prepared = PreparedPolygon::new(polygon);
+    let (x, y) = (points.values_x(), points.values_y());
+    map_rows(points.len(), |i| {
+        points
+            .is_valid(i)
+            .then(|| prepared.contains_point(x[i], y[i]))
+    })
+    .into()
+}
+
+/// Find every pair of a point and a polygon containing it.
+///
+/// Candidate polygons for each point are found in an [`RTree`](rstar::RTree) of the envelopes of
+/// `polygons`, and checked against a [`PreparedPolygon`] built once per polygon. Null points and
+/// polygons never match.
+///
+/// Returns `(point_index, polygon_index)` arrays of equal length, one entry per matching pair,
+/// ordered by point index and then by polygon index.
+pub fn points_in_polygons(
+    points: &PointArray,
+    polygons: &PolygonArray,
+) -> (PrimitiveArray<u32>, PrimitiveArray<u32>) {
+    let tree = envelope_tree(polygons);
+    let prepared = map_rows(polygons.len(), |i| {
+        polygons
+            .get(i)
+            .map(|polygon| PreparedPolygon::new(&polygon))
+    });
+
+    let (x, y) = (points.values_x(), points.values_y());
+    let matches = map_rows(points.len(), |i| {
+        if points.is_null(i) {
+            return vec![];
+        }
+        let mut candidates: Vec<u32> = tree
+            .locate_in_envelope_intersecting(&AABB::from_point([x[i], y[i]]))
+            .map(|candidate| candidate.data)
+            .filter(|&polygon_idx| {
+                prepared[polygon_idx]
+                    .as_ref()
+                    .map_or(false, |polygon| polygon.contains_point(x[i], y[i]))
+            })
+            .map(|polygon_idx| polygon_idx as u32)
+            .collect();
+        candidates.sort_unstable();
+        candidates
+    });
+
+    let mut point_indices = vec![];
+    let mut polygon_indices = vec![];
+    for (point_idx, polygon_idxs) in matches.into_iter().enumerate() {
+        point_indices.resize(point_indices.len() + polygon_idxs.len(), point_idx as u32);
+        polygon_indices.extend(polygon_idxs);
+    }
+    (
+        PrimitiveArray::from_vec(point_indices),
+        PrimitiveArray::from_vec(polygon_indices),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::geo::join::{spatial_join, SpatialPredicate};
+    use geo::{point, polygon, Contains};
+
+    fn square_with_hole() -> geo::Polygon {
+        polygon!(
+            exterior: [(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.)],
+            interiors: [[(x: 4., y: 4.), (x: 6., y: 4.), (x: 6., y: 6.), (x: 4., y: 6.)]],
+        )
+    }
+
+    #[test]
+    fn matches_geo() {
+        let polygon = square_with_hole();
+        let grid: Vec<geo::Point> = (0..=24)
+            .flat_map(|i| {
+                (0..=24).map(move |j| point!(x: i as f64 * 0.5 - 1., y: j as f64 * 0.5 - 1.))
+            })
+            .collect();
+        let points: PointArray = grid.clone().into();
+        let result = points_in_polygon(&points, &&polygon);
+        for (i, point) in grid.iter().enumerate() {
+            assert_eq!(result.value(i), polygon.contains(point), "{:?}", point);
+        }
+    }
+
+    #[test]
+    fn nulls_and_scalars() {
+        let polygons: PolygonArray = vec![Some(square_with_hole()), None].into();
+        let points: PointArray =
+            vec![Some(point!(x: 1., y: 1.)), None, Some(point!(x: 5., y: 5.))].into();
+        let result = points_in_polygon(&points, &polygons.value(0));
+        assert_eq!(result, BooleanArray::from([Some(true), None, Some(false)]));
+    }
+
+    #[test]
+    fn batched_matches_join() {
+        let polygons: PolygonArray = vec![
+            Some(square_with_hole()),
+            None,
+            Some(polygon![(x: 2., y: 2.), (x: 12., y: 2.), (x: 12., y: 12.), (x: 2., y: 12.)]),
+        ]
+        .into();
+        let points: PointArray = vec![
+            Some(point!(x: 1., y: 1.)),
+            Some(point!(x: 5., y: 5.)),
+            None,
+            Some(point!(x: 8., y: 8.)),
+            Some(point!(x: 20., y: 20.)),
+        ]
+        .into();
+        let (point_indices, polygon_indices) = points_in_polygons(&points, &polygons);
+        assert_eq!(point_indices.values().as_slice(), &[0, 1, 3, 3]);
+        assert_eq!(polygon_indices.values().as_slice(), &[0, 2, 0, 2]);
+        assert_eq!(
+            (point_indices, polygon_indices),
+            spatial_join(&points, &polygons, SpatialPredicate::Within)
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Reproject geometry arrays between coordinate reference systems with [PROJ](https://proj.org).
+//!
+//! Native arrays are transformed in one call to PROJ over all of their coordinates: offsets and
+//! validity are shared with the input and only the x and y buffers are rewritten.
+
+use crate::error::GeoArrowError;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, WKBArray,
+};
+use arrow2::buffer::Buffer;
+use geo::{CoordsIter, MapCoordsInPlace};
+use proj::Proj;
+use std::cell::Cell;
+
+/// Transform the coordinates of a geometry array to another coordinate reference system.
+pub trait Reproject: Sized {
+    /// Transform every coordinate from the CRS of this array to `to_crs`, returning a new array
+    /// in `to_crs`.
+    ///
+    /// `to_crs` may be anything PROJ accepts, such as an `AUTHORITY:CODE` string (e.g.
+    /// `"EPSG:3857"`), WKT or PROJJSON. Coordinates are read and written in longitude/latitude
+    /// (or easting/northing) order, whatever the axis order of the CRS definition.
+    /// # Errors
+    /// Errors if the array has no CRS, if PROJ cannot create a transformation between the two
+    /// CRSes, or if any coordinate fails to transform.
+    fn reproject(&self, to_crs: &str) -> Result<Self, GeoArrowError>;
+}
+
+/// Create a transformation from the CRS `from_crs` of an array to `to_crs`.
+fn transformer(from_crs: Option<&str>, to_crs: &str) -> Result<Proj, GeoArrowError> {
+    let from_crs = from_crs.ok_or_else(|| {
+        GeoArrowError::General("cannot reproject an array without a CRS".to_string())
+    })?;
+    Proj::new_known_crs(from_crs, to_crs, None).map_err(|err| GeoArrowError::External(err.into()))
+}
+
+/// Transform the coordinate buffers with `proj`, returning new x and y buffers.
+fn transform_coords(
+    x: &Buffer<f64>,
+    y: &Buffer<f64>,
+    proj: &Proj,
+) -> Result<(Buffer<f64>, Buffer<f64>), GeoArrowError> {
+    let mut coords: Vec<(f64, f64)> = x.iter().copied().zip(y.iter().copied()).collect();
+    proj.convert_array(&mut coords)
+        .map_err(|err| GeoArrowError::External(err.into()))?;
+    let (x, y): (Vec<f64>, Vec<f64>) = coords.into_iter().unzip();
+    Ok((x.into(), y.into()))
+}
+
+/// Transform the coordinates of an array with `proj`, keeping its CRS.
+trait TransformCoords: Sized {
+    fn transform_coords(&self, proj: &Proj) -> Result<Self, GeoArrowError>;
+}
+
+macro_rules! buffer_impl {
+    ($type:ty) => {
+        impl TransformCoords for $type {
+            fn transform_coords(&self, proj: &Proj) -> Result<Self, GeoArrowError> {
+                let (x, y) = transform_coords(self.values_x(), self.values_y(), proj)?;
+                Ok(self.with_coords(x, y))
+            }
+        }
+
+        impl Reproject for $type {
+            fn reproject(&self, to_crs: &str) -> Result<Self, GeoArrowError> {
+                let proj = transformer(self.crs(), to_crs)?;
+                Ok(self.transform_coords(&proj)?.with_crs(Some(to_crs)))
+            }
+        }
+    };
+}
+
+buffer_impl!(PointArray);
+buffer_impl!(LineStringArray);
+buffer_impl!(PolygonArray);
+buffer_impl!(MultiPointArray);
+buffer_impl!(MultiLineStringArray);
+buffer_impl!(MultiPolygonArray);
+
+impl Reproject for WKBArray {
+    /// Geometries are parsed, transformed together in one call to PROJ and encoded again.
+    fn reproject(&self, to_crs: &str) -> Result<Self, GeoArrowError> {
+        let proj = transformer(self.crs(), to_crs)?;
+
+        let mut geoms: Vec<Option<geo::Geometry>> =
+            (0..self.len()).map(|i| self.get_as_geo(i)).collect();
+        let mut coords: Vec<(f64, f64)> = geoms
+            .iter()
+            .flatten()
+            .flat_map(|geom| geom.coords_iter().map(|coord| coord.x_y()))
+            .collect();
+        proj.convert_array(&mut coords)
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+
+        // Parsed WKB holds no lines, rects or triangles, whose coordinates would be visited in a
+        // different order by `coords_iter` and `map_coords_in_place`.
+        let next = Cell::new(0);
+        let coords = coords.as_slice();
+        for geom in geoms.iter_mut().flatten() {
+            geom.map_coords_in_place(|_| {
+                let (x, y) = coords[next.get()];
+                next.set(next.get() + 1);
+                geo::Coord { x, y }
+            });
+        }
+
+        Ok(WKBArray::from(geoms).with_crs(Some(to_crs)))
+    }
+}
+
+impl Reproject for MixedGeometryArray {
+    fn reproject(&self, to_crs: &str) -> Result<Self, GeoArrowError> {
+        let proj = transformer(self.crs(), to_crs)?;
+        let points = self.points().transform_coords(&proj)?;
+        let line_strings = self.line_strings().transform_coords(&proj)?;
+        let polygons = self.polygons().transform_coords(&proj)?;
+        let multi_points = self.multi_points().transform_coords(&proj)?;
+        let multi_line_strings = self.multi_line_strings().transform_coords(&proj)?;
+        let multi_polygons = self.multi_polygons().transform_coords(&proj)?;
+        Ok(self
+            .map_children(
+                |_| points,
+                |_| line_strings,
+                |_| polygons,
+                |_| multi_points,
+                |_| multi_line_strings,
+                |_| multi_polygons,
+            )
+            .with_crs(Some(to_crs)))
+    }
+}
+
+impl Reproject for GeometryArray {
+    /// # Errors
+    /// Rect arrays are not supported, since their edges are no longer axis-aligned once
+    /// reprojected; convert them to polygons first.
+    fn reproject(&self, to_crs: &str) -> Result<Self, GeoArrowError> {
+        Ok(match self {
+            GeometryArray::Point(arr) => GeometryArray::Point(arr.reproject(to_crs)?),
+            GeometryArray::LineString(arr) => GeometryArray::LineString(arr.reproject(to_crs)?),
+            GeometryArray::Polygon(arr) => GeometryArray::Polygon(arr.reproject(to_crs)?),
+            GeometryArray::MultiPoint(arr) => GeometryArray::MultiPoint(arr.reproject(to_crs)?),
+            GeometryArray::MultiLineString(arr) => {
+                GeometryArray::MultiLineString(arr.reproject(to_crs)?)
+            }
+            GeometryArray::MultiPolygon(arr) => GeometryArray::MultiPolygon(arr.reproject(to_crs)?),
+            GeometryArray::WKB(arr) => GeometryArray::WKB(arr.reproject(to_crs)?),
+            GeometryArray::Mixed(arr) => GeometryArray::Mixed(Box::new(arr.reproject(to_crs)?)),
+            GeometryArray::Rect(_) => {
+                return Err(GeoArrowError::NotYetImplemented(
+                    "reprojecting rect arrays".to_string(),
+                ))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point};
+
+    #[test]
+    fn web_mercator() {
+        let points: PointArray = vec![
+            Some(point!(x: 0., y: 0.)),
+            None,
+            Some(point!(x: 180., y: 0.)),
+        ]
+        .into();
+        let projected = points
+            .with_crs(Some("EPSG:4326"))
+            .reproject("EPSG:3857")
+            .unwrap();
+        assert_eq!(projected.crs(), Some("EPSG:3857"));
+        assert!(projected.is_null(1));
+        let p = projected.value_as_geo(2);
+        assert!((p.x() - 20_037_508.342_789_244).abs() < 1e-3);
+        assert!(p.y().abs() < 1e-3);
+
+        let line = line_string![(x: 0., y: 0.), (x: 180., y: 0.)];
+        let wkb = WKBArray::from(vec![Some(geo::Geometry::LineString(line)), None])
+            .with_crs(Some("EPSG:4326"));
+        let projected = wkb.reproject("EPSG:3857").unwrap();
+        assert_eq!(projected.crs(), Some("EPSG:3857"));
+        match projected.get_as_geo(0) {
+            Some(geo::Geometry::LineString(line)) => {
+                assert!((line.0[1].x - 20_037_508.342_789_244).abs() < 1e-3)
+            }
+            _ => panic!("expected a line string"),
+        }
+    }
+
+    #[test]
+    fn requires_crs() {
+        let points: PointArray = vec![point!(x: 0., y: 0.)].into();
+        assert!(points.reproject("EPSG:3857").is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! Rasterize geometries onto a regular grid.
+
+use crate::error::GeoArrowError;
+use crate::{GeometryArrayTrait, PointArray};
+use arrow2::array::{FixedSizeListArray, PrimitiveArray};
+use arrow2::datatypes::{DataType, Field};
+
+/// A regular, north-up grid covering a rectangular extent.
+///
+/// Row 0 is the northernmost row (largest y) and column 0 the westernmost column (smallest x).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridDefinition {
+    /// Western edge of the grid
+    pub min_x: f64,
+
+    /// Southern edge of the grid
+    pub min_y: f64,
+
+    /// Eastern edge of the grid
+    pub max_x: f64,
+
+    /// Northern edge of the grid
+    pub max_y: f64,
+
+    /// Number of columns
+    pub n_cols: usize,
+
+    /// Number of rows
+    pub n_rows: usize,
+}
+
+impl GridDefinition {
+    /// Create a new grid of `n_cols` by `n_rows` cells spanning the given bounds.
+    pub fn new(bounds: [f64; 4], n_cols: usize, n_rows: usize) -> Self {
+        let [min_x, min_y, max_x, max_y] = bounds;
+        Self {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+            n_cols,
+            n_rows,
+        }
+    }
+
+    fn check(&self) -> Result<(), GeoArrowError> {
+        if self.n_cols == 0 || self.n_rows == 0 {
+            return Err(GeoArrowError::General(
+                "grid must have at least one row and one column".to_string(),
+            ));
+        }
+
+        // Written to also reject NaN bounds
+        if !(self.max_x > self.min_x && self.max_y > self.min_y) {
+            return Err(GeoArrowError::General(
+                "grid bounds must have a positive width and height".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The `(row, col)` of the cell containing `(x, y)`, or `None` if it lies outside the grid.
+    ///
+    /// Points on the maximum edges belong to the last row or column.
+    pub fn cell(&self, x: f64, y: f64) -> Option<(usize, usize)> {
+        if !(x >= self.min_x && x <= self.max_x && y >= self.min_y && y <= self.max_y) {
+            return None;
+        }
+
+        let col = ((x - self.min_x) / (self.max_x - self.min_x) * self.n_cols as f64) as usize;
+        let row = ((self.max_y - y) / (self.max_y - self.min_y) * self.n_rows as f64) as usize;
+        Some((row.min(self.n_rows - 1), col.min(self.n_cols - 1)))
+    }
+}
+
+/// Count the points falling in each cell of a grid.
+///
+/// The result holds one fixed-size list per grid row, each with one `u32` count per column.
+/// Null points and points outside the grid are ignored.
+pub fn rasterize_count(
+    points: &PointArray,
+    grid: &GridDefinition,
+) -> Result<FixedSizeListArray, GeoArrowError> {
+    grid.check()?;
+
+    let mut counts = vec![0u32; grid.n_rows * grid.n_cols];
+    for i in 0..points.len() {
+        if points.is_null(i) {
+            continue;
+        }
+
+        if let Some((row, col)) = grid.cell(points.values_x()[i], points.values_y()[i]) {
+            counts[row * grid.n_cols + col] += 1;
+        }
+    }
+
+    let values = PrimitiveArray::<u32>::from_vec(counts);
+    let data_type = DataType::FixedSizeList(
+        Box::new(Field::new("item", DataType::UInt32, false)),
+        grid.n_cols,
+    );
+    Ok(FixedSizeListArray::new(data_type, values.boxed(), None))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::point;
+
+    #[test]
+    fn counts_per_cell() {
+        let points: PointArray = vec![
+            Some(point!(x: 0.5, y: 0.5)),
+            Some(point!(x: 0.1, y: 0.2)),
+            Some(point!(x: 2., y: 2.)),
+            Some(point!(x: 5., y: 5.)),
+            None,
+        ]
+        .into();
+        let grid = GridDefinition::new([0., 0., 2., 2.], 2, 2);
+
+        let raster = rasterize_count(&points, &grid).unwrap();
+        assert_eq!(raster.len(), 2);
+
+        let counts = raster
+            .values()
+            .as_any()
+            .downcast_ref::<PrimitiveArray<u32>>()
+            .unwrap();
+        // Top row first: (row 0, col 1) holds the point on the max corner
+        assert_eq!(counts.values().as_slice(), &[0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn invalid_grid() {
+        let points: PointArray = vec![point!(x: 0., y: 0.)].into();
+        assert!(rasterize_count(&points, &GridDefinition::new([0., 0., 1., 1.], 0, 1)).is_err());
+        assert!(rasterize_count(&points, &GridDefinition::new([1., 0., 1., 1.], 1, 1)).is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Reduce the precision of coordinates.
+//!
+//! Rounding coordinates to a fixed number of decimal places shrinks text encodings such as
+//! GeoJSON and WKT, makes compressed WKB and Parquet columns smaller, and lets geometries that
+//! differ only by floating point noise compare equal. Native arrays are rounded directly on their
+//! coordinate buffers: offsets and validity are shared with the input.
+
+use crate::algorithm::map_coords::MapCoords;
+
+/// Round every coordinate of a geometry array to a number of decimal places.
+///
+/// This is implemented for every array implementing [`MapCoords`].
+pub trait RoundCoords {
+    /// Round every coordinate to `decimals` decimal places, returning a new array.
+    ///
+    /// A negative `decimals` rounds to a power of ten to the left of the decimal point, so `-3`
+    /// rounds to the nearest thousand. Halfway values round away from zero, as in [`f64::round`].
+    fn round_coords(&self, decimals: i32) -> Self;
+}
+
+/// Round `value` to `decimals` decimal places.
+///
+/// Values too large to be scaled without overflowing have no fractional digits left to round, and
+/// are returned as they are, as are infinite and NaN values.
+fn round_value(value: f64, decimals: i32) -> f64 {
+    // Dividing by an exact power of ten is more accurate than multiplying by an inexact one
+    let rounded = if decimals >= 0 {
+        let factor = 10f64.powi(decimals);
+        (value * factor).round() / factor
+    } else {
+        let factor = 10f64.powi(-decimals);
+        (value / factor).round() * factor
+    };
+    if rounded.is_finite() {
+        rounded
+    } else {
+        value
+    }
+}
+
+impl<A: MapCoords> RoundCoords for A {
+    fn round_coords(&self, decimals: i32) -> Self {
+        self.map_coords(|x, y| (round_value(x, decimals), round_value(y, decimals)))
+    }
+}
+
+/// Round every coordinate of `array` to `decimals` decimal places.
+///
+/// See [`RoundCoords::round_coords`].
+pub fn round_coords<A: RoundCoords>(array: &A, decimals: i32) -> A {
+    array.round_coords(decimals)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArray, GeometryArrayTrait, LineStringArray, PointArray, WKBArray};
+    use geo::{line_string, point};
+
+    #[test]
+    fn decimals() {
+        assert_eq!(round_value(1.23456, 2), 1.23);
+        assert_eq!(round_value(-1.235, 0), -1.);
+        assert_eq!(round_value(1234.5, -2), 1200.);
+        assert_eq!(round_value(0.1 + 0.2, 10), 0.3);
+        assert_eq!(round_value(1e300, 10), 1e300);
+        assert!(round_value(f64::NAN, 2).is_nan());
+    }
+
+    #[test]
+    fn arrays() {
+        let arr: PointArray = vec![Some(point!(x: 1.26, y: -0.04)), None].into();
+        let rounded = round_coords(&arr.with_crs(Some("EPSG:4326")), 1);
+        assert_eq!(rounded.get_as_geo(0), Some(point!(x: 1.3, y: -0.)));
+        assert!(rounded.is_null(1));
+        assert_eq!(rounded.crs(), Some("EPSG:4326"));
+
+        let line = line_string![(x: 0.123, y: 4.567), (x: 8.9, y: 10.11)];
+        let expected = line_string![(x: 0.1, y: 4.6), (x: 8.9, y: 10.1)];
+        let arr: LineStringArray = vec![line.clone()].into();
+        let wkb: WKBArray = vec![Some(geo::Geometry::LineString(line))].into();
+        assert_eq!(arr.round_coords(1).value_as_geo(0), expected);
+        assert_eq!(
+            GeometryArray::WKB(wkb).round_coords(1).get_as_geo(0),
+            Some(geo::Geometry::LineString(expected))
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Sample points inside polygons.
+//!
+//! Dasymetric mapping spreads the population of a district over sample points inside it, and
+//! training data for machine learning is often augmented with points drawn from labelled areas.
+//! [`sample_points`] draws these points for a whole array of polygons at once, testing candidate
+//! points against a [`PreparedPolygon`] of each polygon.
+
+use crate::algorithm::point_in_polygon::PreparedPolygon;
+use crate::parallel::map_rows;
+use crate::{GeometryArrayTrait, MultiPointArray, PolygonArray};
+use geo::{Area, BoundingRect};
+
+/// The most candidate points drawn for each point requested with [`SampleMethod::Random`].
+const MAX_ATTEMPTS_PER_POINT: usize = 1000;
+
+/// How [`sample_points`] places points inside a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleMethod {
+    /// Independent, uniformly distributed random points.
+    ///
+    /// Points are drawn by a pseudorandom generator seeded with `seed` and the index of the
+    /// polygon, so that the same seed always gives the same points.
+    Random { seed: u64 },
+    /// The points of a square grid inside the polygon, with the spacing that would put the
+    /// requested number of points in a polygon of the same area.
+    ///
+    /// The number of points is only approximately the one requested, depending on the shape of
+    /// the polygon and how it lines up with the grid.
+    Regular,
+}
+
+/// The [SplitMix64](https://prng.di.unimi.it/splitmix64.c) pseudorandom generator.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed number in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// `n` random points inside `polygon`, drawn by rejection from its bounding box.
+fn sample_random(
+    polygon: &geo::Polygon,
+    prepared: &PreparedPolygon,
+    n: usize,
+    seed: u64,
+) -> Vec<geo::Point> {
+    let mut points = Vec::with_capacity(n);
+    let Some(rect) = polygon.bounding_rect() else {
+        return points;
+    };
+    if polygon.unsigned_area() == 0.0 {
+        return points;
+    }
+
+    let mut rng = SplitMix64(seed);
+    for _ in 0..n * MAX_ATTEMPTS_PER_POINT {
+        if points.len() == n {
+            break;
+        }
+        let x = rect.min().x + rng.next_f64() * rect.width();
+        let y = rect.min().y + rng.next_f64() * rect.height();
+        if prepared.contains_point(x, y) {
+            points.push(geo::Point::new(x, y));
+        }
+    }
+    points
+}
+
+/// About `n` points of a square grid inside `polygon`.
+fn sample_regular(polygon: &geo::Polygon, prepared: &PreparedPolygon, n: usize) -> Vec<geo::Point> {
+    let mut points = vec![];
+    let area = polygon.unsigned_area();
+    let Some(rect) = polygon.bounding_rect() else {
+        return points;
+    };
+    if n == 0 || area == 0.0 {
+        return points;
+    }
+
+    // Grid cells are centered on the points, and start at the corner of the bounding box
+    let spacing = (area / n as f64).sqrt();
+    let cols = (rect.width() / spacing).ceil() as usize;
+    let rows = (rect.height() / spacing).ceil() as usize;
+    for row in 0..rows {
+        let y = rect.min().y + (row as f64 + 0.5) * spacing;
+        for col in 0..cols {
+            let x = rect.min().x + (col as f64 + 0.5) * spacing;
+            if prepared.contains_point(x, y) {
+                points.push(geo::Point::new(x, y));
+            }
+        }
+    }
+    points
+}
+
+/// Sample `n_per_polygon` points inside each polygon, with `method`.
+///
+/// Points are in the interior of their polygon, never on its boundary or in its holes. Random
+/// sampling draws candidate points from the bounding box of the polygon, so a polygon covering a
+/// tiny fraction of its bounding box may get fewer points than requested. Polygons without area
+/// get no points, and null polygons produce null multi points. Rows are sampled in parallel with
+/// the `rayon` feature.
+pub fn sample_points(
+    polygons: &PolygonArray,
+    n_per_polygon: usize,
+    method: SampleMethod,
+) -> MultiPointArray {
+    let samples = map_rows(polygons.len(), |i| {
+        let polygon = polygons.get_as_geo(i)?;
+        let prepared = PreparedPolygon::new(&&polygon);
+        let points = match method {
+            SampleMethod::Random { seed } => {
+                // Give every row its own stream, whatever the order rows are sampled in
+                let seed = SplitMix64(seed ^ (i as u64)).next_u64();
+                sample_random(&polygon, &prepared, n_per_polygon, seed)
+            }
+            SampleMethod::Regular => sample_regular(&polygon, &prepared, n_per_polygon),
+        };
+        Some(geo::MultiPoint::new(points))
+    });
+    MultiPointArray::from(samples).with_crs(polygons.crs())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{polygon, Contains};
+
+    fn polygons() -> PolygonArray {
+        vec![
+            Some(polygon!(
+                exterior: [(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.), (x: 0., y: 10.)],
+                interiors: [[(x: 4., y: 4.), (x: 6., y: 4.), (x: 6., y: 6.), (x: 4., y: 6.)]],
+            )),
+            None,
+            Some(polygon![(x: 0., y: 0.), (x: 1., y: 1.), (x: 2., y: 2.)]),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn random() {
+        let polygons = polygons().with_crs(Some("EPSG:3857"));
+        let samples = sample_points(&polygons, 100, SampleMethod::Random { seed: 1 });
+        assert_eq!(samples.crs(), Some("EPSG:3857"));
+
+        let polygon = polygons.value_as_geo(0);
+        let points = samples.value_as_geo(0);
+        assert_eq!(points.0.len(), 100);
+        assert!(points.iter().all(|point| polygon.contains(point)));
+        assert!(samples.is_null(1));
+        assert!(samples.value_as_geo(2).0.is_empty());
+
+        // Sampling is reproducible from the seed
+        let again = sample_points(&polygons, 100, SampleMethod::Random { seed: 1 });
+        assert_eq!(again.value_as_geo(0), points);
+        let other = sample_points(&polygons, 100, SampleMethod::Random { seed: 2 });
+        assert_ne!(other.value_as_geo(0), points);
+    }
+
+    #[test]
+    fn regular() {
+        let polygons = polygons();
+        let samples = sample_points(&polygons, 96, SampleMethod::Regular);
+
+        // A spacing of 1 fits 96 points around the hole
+        let points = samples.value_as_geo(0);
+        assert_eq!(points.0.len(), 96);
+        assert_eq!(points.0[0], geo::point!(x: 0.5, y: 0.5));
+        assert!(samples.is_null(1));
+        assert!(samples.value_as_geo(2).0.is_empty());
+        assert!(sample_points(&polygons, 0, SampleMethod::Regular)
+            .value_as_geo(0)
+            .0
+            .is_empty());
+    }
+}
@@ -0,0 +1,414 @@
+//! Select geometries by index or by mask, copying them into new arrays.
+//!
+//! Unlike slicing, the outputs of [`Take`] and [`Filter`] own compacted coordinate and offset
+//! buffers holding only the selected geometries.
+
+use crate::error::GeoArrowError;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use arrow2::array::{BinaryArray, BooleanArray, PrimitiveArray};
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::buffer::Buffer;
+use arrow2::offset::{Offsets, OffsetsBuffer};
+
+/// Gather geometries by position.
+pub trait Take: Sized {
+    /// A new array holding the geometry at each of `indices`, in order.
+    ///
+    /// Null indices produce null geometries. Indices may repeat.
+    /// # Errors
+    /// Errors if an index is negative or out of bounds, or if the offsets of the result would
+    /// overflow.
+    fn take(&self, indices: &PrimitiveArray<i64>) -> Result<Self, GeoArrowError>;
+}
+
+/// Keep the geometries where a mask is true.
+pub trait Filter: Sized {
+    /// A new array holding the geometries where `mask` is true, in order.
+    ///
+    /// Null values in the mask drop their geometry.
+    /// # Errors
+    /// Errors if `mask` has a different length than the array.
+    fn filter(&self, mask: &BooleanArray) -> Result<Self, GeoArrowError>;
+}
+
+/// The rows selected by `indices`, checked against an array of length `len`.
+fn take_rows(
+    indices: &PrimitiveArray<i64>,
+    len: usize,
+) -> Result<Vec<Option<usize>>, GeoArrowError> {
+    indices
+        .iter()
+        .map(|index| {
+            index
+                .map(|index| match usize::try_from(*index) {
+                    Ok(row) if row < len => Ok(row),
+                    _ => Err(GeoArrowError::General(format!(
+                        "index {} out of bounds for array of length {}",
+                        index, len
+                    ))),
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+/// The rows kept by `mask`, checked against an array of length `len`.
+fn filter_rows(mask: &BooleanArray, len: usize) -> Result<Vec<Option<usize>>, GeoArrowError> {
+    if mask.len() != len {
+        return Err(GeoArrowError::General(
+            "mask must have the same length as the array".to_string(),
+        ));
+    }
+    Ok(mask
+        .iter()
+        .enumerate()
+        .filter(|(_, keep)| *keep == Some(true))
+        .map(|(row, _)| Some(row))
+        .collect())
+}
+
+/// The validity of the geometries at `rows` of an array with `validity`.
+fn take_validity(validity: Option<&Bitmap>, rows: &[Option<usize>]) -> Option<Bitmap> {
+    if validity.is_none() && rows.iter().all(Option::is_some) {
+        return None;
+    }
+    let bitmap: Bitmap = rows
+        .iter()
+        .map(|row| row.map_or(false, |row| validity.map_or(true, |v| v.get_bit(row))))
+        .collect::<MutableBitmap>()
+        .into();
+    (bitmap.unset_bits() > 0).then_some(bitmap)
+}
+
+/// Gather the lists at `rows` of one level of `offsets`, returning the new offsets and the
+/// positions of the children they hold, in order. Missing rows become empty lists.
+fn take_offsets(
+    offsets: &OffsetsBuffer<i64>,
+    rows: impl Iterator<Item = Option<usize>>,
+) -> Result<(OffsetsBuffer<i64>, Vec<usize>), GeoArrowError> {
+    let mut taken = Offsets::<i64>::new();
+    let mut children = vec![];
+    for row in rows {
+        match row {
+            Some(row) => {
+                let (start, end) = offsets.start_end(row);
+                children.extend(start..end);
+                taken
+                    .try_push_usize(end - start)
+                    .map_err(|_| GeoArrowError::OffsetOverflow {
+                        row: taken.len_proxy(),
+                    })?;
+            }
+            None => taken.extend_constant(1),
+        }
+    }
+    Ok((taken.into(), children))
+}
+
+/// Gather the values of `buffer` at `rows`, with a zero for missing rows.
+fn take_values(buffer: &Buffer<f64>, rows: impl Iterator<Item = Option<usize>>) -> Buffer<f64> {
+    rows.map(|row| row.map_or(0., |row| buffer[row]))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// The coordinate buffers of an array: x, y and any m values.
+type Coords = (Buffer<f64>, Buffer<f64>, Option<Buffer<f64>>);
+
+/// Gather the coordinates at positions `coords`, with their m values if any.
+fn take_coords(
+    x: &Buffer<f64>,
+    y: &Buffer<f64>,
+    m: Option<&Buffer<f64>>,
+    coords: &[usize],
+) -> Coords {
+    let take = |values| take_values(values, coords.iter().copied().map(Some));
+    (take(x), take(y), m.map(take))
+}
+
+/// Implement [`Take`] and [`Filter`] in terms of a function gathering an array's rows.
+macro_rules! impl_take {
+    ($type:ty, $take_rows:ident) => {
+        impl Take for $type {
+            fn take(&self, indices: &PrimitiveArray<i64>) -> Result<Self, GeoArrowError> {
+                $take_rows(self, &take_rows(indices, self.len())?)
+            }
+        }
+
+        impl Filter for $type {
+            fn filter(&self, mask: &BooleanArray) -> Result<Self, GeoArrowError> {
+                $take_rows(self, &filter_rows(mask, self.len())?)
+            }
+        }
+    };
+}
+
+fn take_points(array: &PointArray, rows: &[Option<usize>]) -> Result<PointArray, GeoArrowError> {
+    Ok(PointArray::new(
+        take_values(array.values_x(), rows.iter().copied()),
+        take_values(array.values_y(), rows.iter().copied()),
+        take_validity(array.validity(), rows),
+    )
+    .with_crs(array.crs())
+    .with_optional_m(
+        array
+            .values_m()
+            .map(|m| take_values(m, rows.iter().copied())),
+    ))
+}
+
+fn take_line_strings(
+    array: &LineStringArray,
+    rows: &[Option<usize>],
+) -> Result<LineStringArray, GeoArrowError> {
+    let (geom_offsets, coords) = take_offsets(array.geom_offsets(), rows.iter().copied())?;
+    let (x, y, m) = take_coords(
+        array.values_x(),
+        array.values_y(),
+        array.values_m(),
+        &coords,
+    );
+    Ok(
+        LineStringArray::new(x, y, geom_offsets, take_validity(array.validity(), rows))
+            .with_crs(array.crs())
+            .with_optional_m(m),
+    )
+}
+
+fn take_multi_points(
+    array: &MultiPointArray,
+    rows: &[Option<usize>],
+) -> Result<MultiPointArray, GeoArrowError> {
+    let (geom_offsets, coords) = take_offsets(array.geom_offsets(), rows.iter().copied())?;
+    let (x, y, m) = take_coords(
+        array.values_x(),
+        array.values_y(),
+        array.values_m(),
+        &coords,
+    );
+    Ok(
+        MultiPointArray::new(x, y, geom_offsets, take_validity(array.validity(), rows))
+            .with_crs(array.crs())
+            .with_optional_m(m),
+    )
+}
+
+fn take_polygons(
+    array: &PolygonArray,
+    rows: &[Option<usize>],
+) -> Result<PolygonArray, GeoArrowError> {
+    let (geom_offsets, rings) = take_offsets(array.geom_offsets(), rows.iter().copied())?;
+    let (ring_offsets, coords) = take_offsets(array.ring_offsets(), rings.into_iter().map(Some))?;
+    let (x, y, m) = take_coords(
+        array.values_x(),
+        array.values_y(),
+        array.values_m(),
+        &coords,
+    );
+    Ok(PolygonArray::new(
+        x,
+        y,
+        geom_offsets,
+        ring_offsets,
+        take_validity(array.validity(), rows),
+    )
+    .with_crs(array.crs())
+    .with_optional_m(m))
+}
+
+fn take_multi_line_strings(
+    array: &MultiLineStringArray,
+    rows: &[Option<usize>],
+) -> Result<MultiLineStringArray, GeoArrowError> {
+    let (geom_offsets, rings) = take_offsets(array.geom_offsets(), rows.iter().copied())?;
+    let (ring_offsets, coords) = take_offsets(array.ring_offsets(), rings.into_iter().map(Some))?;
+    let (x, y, m) = take_coords(
+        array.values_x(),
+        array.values_y(),
+        array.values_m(),
+        &coords,
+    );
+    Ok(MultiLineStringArray::new(
+        x,
+        y,
+        geom_offsets,
+        ring_offsets,
+        take_validity(array.validity(), rows),
+    )
+    .with_crs(array.crs())
+    .with_optional_m(m))
+}
+
+fn take_multi_polygons(
+    array: &MultiPolygonArray,
+    rows: &[Option<usize>],
+) -> Result<MultiPolygonArray, GeoArrowError> {
+    let (geom_offsets, polygons) = take_offsets(array.geom_offsets(), rows.iter().copied())?;
+    let (polygon_offsets, rings) =
+        take_offsets(array.polygon_offsets(), polygons.into_iter().map(Some))?;
+    let (ring_offsets, coords) = take_offsets(array.ring_offsets(), rings.into_iter().map(Some))?;
+    let (x, y, m) = take_coords(
+        array.values_x(),
+        array.values_y(),
+        array.values_m(),
+        &coords,
+    );
+    Ok(MultiPolygonArray::new(
+        x,
+        y,
+        geom_offsets,
+        polygon_offsets,
+        ring_offsets,
+        take_validity(array.validity(), rows),
+    )
+    .with_crs(array.crs())
+    .with_optional_m(m))
+}
+
+fn take_rects(array: &RectArray, rows: &[Option<usize>]) -> Result<RectArray, GeoArrowError> {
+    Ok(RectArray::new(
+        take_values(array.values_minx(), rows.iter().copied()),
+        take_values(array.values_miny(), rows.iter().copied()),
+        take_values(array.values_maxx(), rows.iter().copied()),
+        take_values(array.values_maxy(), rows.iter().copied()),
+        take_validity(array.validity(), rows),
+    )
+    .with_crs(array.crs()))
+}
+
+fn take_wkb(array: &WKBArray, rows: &[Option<usize>]) -> Result<WKBArray, GeoArrowError> {
+    let binary = array.clone().into_arrow();
+    let taken: BinaryArray<i64> = rows
+        .iter()
+        .map(|row| row.and_then(|row| binary.get(row)))
+        .collect();
+    Ok(WKBArray::new(taken).with_crs(array.crs()))
+}
+
+/// Mixed arrays are gathered through [`geo`] geometries.
+fn take_mixed(
+    array: &MixedGeometryArray,
+    rows: &[Option<usize>],
+) -> Result<MixedGeometryArray, GeoArrowError> {
+    let geoms: Vec<Option<geo::Geometry>> = rows
+        .iter()
+        .map(|row| row.and_then(|row| array.get_as_geo(row)))
+        .collect();
+    Ok(MixedGeometryArray::try_from(geoms)?.with_crs(array.crs()))
+}
+
+impl_take!(PointArray, take_points);
+impl_take!(LineStringArray, take_line_strings);
+impl_take!(PolygonArray, take_polygons);
+impl_take!(MultiPointArray, take_multi_points);
+impl_take!(MultiLineStringArray, take_multi_line_strings);
+impl_take!(MultiPolygonArray, take_multi_polygons);
+impl_take!(RectArray, take_rects);
+impl_take!(WKBArray, take_wkb);
+impl_take!(MixedGeometryArray, take_mixed);
+
+/// Gather the rows of any geometry array, keeping its type.
+fn take_geometries(
+    array: &GeometryArray,
+    rows: &[Option<usize>],
+) -> Result<GeometryArray, GeoArrowError> {
+    Ok(match array {
+        GeometryArray::Point(arr) => GeometryArray::Point(take_points(arr, rows)?),
+        GeometryArray::LineString(arr) => GeometryArray::LineString(take_line_strings(arr, rows)?),
+        GeometryArray::Polygon(arr) => GeometryArray::Polygon(take_polygons(arr, rows)?),
+        GeometryArray::MultiPoint(arr) => GeometryArray::MultiPoint(take_multi_points(arr, rows)?),
+        GeometryArray::MultiLineString(arr) => {
+            GeometryArray::MultiLineString(take_multi_line_strings(arr, rows)?)
+        }
+        GeometryArray::MultiPolygon(arr) => {
+            GeometryArray::MultiPolygon(take_multi_polygons(arr, rows)?)
+        }
+        GeometryArray::WKB(arr) => GeometryArray::WKB(take_wkb(arr, rows)?),
+        GeometryArray::Rect(arr) => GeometryArray::Rect(take_rects(arr, rows)?),
+        GeometryArray::Mixed(arr) => GeometryArray::Mixed(Box::new(take_mixed(arr, rows)?)),
+    })
+}
+
+impl_take!(GeometryArray, take_geometries);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point, polygon, MultiPolygon};
+
+    #[test]
+    fn take_with_nulls() {
+        let ls0 = line_string![(x: 0., y: 1.), (x: 1., y: 2.)];
+        let ls1 = line_string![(x: 3., y: 4.), (x: 5., y: 6.), (x: 7., y: 8.)];
+        let arr: LineStringArray = vec![Some(ls0.clone()), None, Some(ls1.clone())].into();
+        let arr = arr.with_crs(Some("EPSG:4326"));
+
+        let indices = PrimitiveArray::from([Some(2), None, Some(0), Some(2), Some(1)]);
+        let taken = arr.take(&indices).unwrap();
+        assert_eq!(taken.len(), 5);
+        assert_eq!(taken.crs(), Some("EPSG:4326"));
+        assert_eq!(taken.values_x().len(), 8);
+        assert_eq!(taken.get_as_geo(0), Some(ls1.clone()));
+        assert!(taken.is_null(1));
+        assert_eq!(taken.get_as_geo(2), Some(ls0));
+        assert_eq!(taken.get_as_geo(3), Some(ls1));
+        assert!(taken.is_null(4));
+
+        assert!(arr.take(&PrimitiveArray::from_slice([3])).is_err());
+        assert!(arr.take(&PrimitiveArray::from_slice([-1])).is_err());
+    }
+
+    #[test]
+    fn filter_compacts() {
+        let p0 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let p1 = polygon![(x: 5., y: 5.), (x: 6., y: 5.), (x: 6., y: 6.), (x: 5., y: 5.)];
+        let arr: MultiPolygonArray = vec![
+            MultiPolygon(vec![p0.clone(), p1.clone()]),
+            MultiPolygon(vec![p1.clone()]),
+            MultiPolygon(vec![p0.clone()]),
+        ]
+        .into();
+        let arr = arr.sliced_range(1..3);
+
+        let filtered = arr.filter(&BooleanArray::from([None, Some(true)])).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered.values_x().len(), 4);
+        assert_eq!(filtered.value_as_geo(0), MultiPolygon(vec![p0]));
+        assert!(arr.filter(&BooleanArray::from_slice([true])).is_err());
+
+        let points: PointArray = vec![point!(x: 0., y: 1.), point!(x: 2., y: 3.)].into();
+        let filtered = GeometryArray::Point(points)
+            .filter(&BooleanArray::from_slice([false, true]))
+            .unwrap();
+        assert_eq!(
+            filtered.get_as_geo(0),
+            Some(geo::Geometry::Point(point!(x: 2., y: 3.)))
+        );
+    }
+
+    #[test]
+    fn m_values() {
+        let points: PointArray = vec![point!(x: 0., y: 1.), point!(x: 2., y: 3.)].into();
+        let points = points.with_m(vec![10., 20.].into()).unwrap();
+        let taken = points
+            .take(&PrimitiveArray::from([Some(1), None, Some(0)]))
+            .unwrap();
+        assert_eq!(taken.value(0).m(), Some(20.));
+        assert_eq!(taken.value(2).m(), Some(10.));
+
+        let p0 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let p1 = polygon![(x: 5., y: 5.), (x: 6., y: 5.), (x: 6., y: 6.), (x: 5., y: 5.)];
+        let arr: MultiPolygonArray = vec![MultiPolygon(vec![p0]), MultiPolygon(vec![p1])].into();
+        let arr = arr
+            .with_m((0..8).map(f64::from).collect::<Vec<_>>().into())
+            .unwrap();
+        let filtered = arr
+            .filter(&BooleanArray::from_slice([false, true]))
+            .unwrap();
+        assert!(filtered.has_m());
+        assert_eq!(filtered.values_m().unwrap().as_slice(), &[4., 5., 6., 7.]);
+    }
+}
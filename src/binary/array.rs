@@ -1,20 +1,30 @@
+use super::header::{geometry_type_id, geometry_type_ids, validate_wkb, GeometryTypeId};
+use crate::crs::{check_crs, impl_crs};
 use crate::error::GeoArrowError;
+#[cfg(feature = "rayon")]
+use crate::parallel::MIN_ROWS_PER_TASK;
+use crate::trait_::{Dimension, GeometryArrayType};
 use crate::{GeometryArrayTrait, MutableWKBArray, WKB};
-use arrow2::array::{Array, BinaryArray};
+use arrow2::array::{Array, BinaryArray, FixedSizeBinaryArray};
 use arrow2::bitmap::utils::{BitmapIter, ZipValidity};
 use arrow2::bitmap::Bitmap;
-use rstar::RTree;
+use arrow2::datatypes::DataType;
+use arrow2::offset::{Offsets, OffsetsBuffer};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 /// A [`GeometryArrayTrait`] semantically equivalent to `Vec<Option<Geometry>>` using Arrow's
 /// in-memory representation.
 #[derive(Debug, Clone)]
-pub struct WKBArray(BinaryArray<i64>);
+pub struct WKBArray(BinaryArray<i64>, Option<Arc<str>>);
 
 // Implement geometry accessors
 impl WKBArray {
     /// Create a new WKBArray from a BinaryArray
     pub fn new(arr: BinaryArray<i64>) -> Self {
-        Self(arr)
+        Self(arr, None)
     }
 
     /// Returns true if the array is empty
@@ -22,11 +32,82 @@ impl WKBArray {
         self.len() == 0
     }
 
-    pub fn with_validity(&self, validity: Option<Bitmap>) -> Self {
-        WKBArray::new(self.0.clone().with_validity(validity))
+    /// The distinct types of the geometries of this array, read from their headers only.
+    ///
+    /// Formatting the types gives the `geometry_types` metadata of GeoParquet.
+    /// # Errors
+    /// Errors with [`GeoArrowError::InvalidWkb`] if a header is not valid.
+    pub fn geometry_types(&self) -> Result<HashSet<GeometryTypeId>, GeoArrowError> {
+        let bufs = self
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, buf)| buf.map(|buf| (i, buf)));
+        geometry_type_ids(bufs).map_err(|(row, message)| GeoArrowError::InvalidWkb { row, message })
+    }
+
+    /// Check that every geometry is well-formed WKB or EWKB: a known byte order and type, parts
+    /// of the types and dimensions their collection declares, and counts matching the length
+    /// of the value.
+    ///
+    /// Coordinates are skipped over rather than parsed, so this is much faster than parsing.
+    /// # Errors
+    /// Errors with [`GeoArrowError::InvalidWkb`] for the first geometry that is not well-formed.
+    pub fn validate(&self) -> Result<(), GeoArrowError> {
+        for wkb in (0..self.len()).filter_map(|i| self.get(i)) {
+            validate_wkb(wkb.as_slice()).map_err(|message| GeoArrowError::InvalidWkb {
+                row: wkb.geom_index,
+                message,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// The SRID shared by the EWKB geometries of this array, if any has one.
+    ///
+    /// Geometries without an SRID, or with SRID 0, are ignored. Only headers are read.
+    /// # Errors
+    /// Errors with [`GeoArrowError::CrsMismatch`] if geometries have different SRIDs.
+    pub fn srid(&self) -> Result<Option<i32>, GeoArrowError> {
+        let mut srid = None;
+        for wkb in (0..self.len()).filter_map(|i| self.get(i)) {
+            match (srid, wkb.srid()) {
+                (Some(left), Some(right)) if left != right => {
+                    return Err(GeoArrowError::CrsMismatch {
+                        left: format!("EPSG:{left}"),
+                        right: format!("EPSG:{right}"),
+                    })
+                }
+                (None, right) => srid = right,
+                _ => {}
+            }
+        }
+        Ok(srid)
+    }
+
+    /// The CRS of this array, or else `EPSG:<srid>` from the SRID of its EWKB geometries.
+    ///
+    /// # Errors
+    /// Errors with [`GeoArrowError::CrsMismatch`] if geometries have different SRIDs, or if the
+    /// SRID does not match the CRS of the array.
+    pub(crate) fn resolved_crs(&self) -> Result<Option<String>, GeoArrowError> {
+        let srid_crs = self.srid()?.map(|srid| format!("EPSG:{srid}"));
+        Ok(check_crs(self.crs(), srid_crs.as_deref())?.map(str::to_string))
+    }
+
+    /// This array with its CRS set to `EPSG:<srid>` from the SRID of its EWKB geometries, as
+    /// written by PostGIS. Arrays without SRIDs are returned unchanged.
+    /// # Errors
+    /// Errors with [`GeoArrowError::CrsMismatch`] if geometries have different SRIDs, or if the
+    /// SRID does not match the CRS already set on the array.
+    pub fn with_srid_crs(self) -> Result<Self, GeoArrowError> {
+        let crs = self.resolved_crs()?;
+        Ok(self.with_crs(crs.as_deref()))
     }
 }
 
+impl_crs!(WKBArray, 1);
+
 impl<'a> GeometryArrayTrait<'a> for WKBArray {
     type Scalar = WKB<'a>;
     type ScalarGeo = geo::Geometry;
@@ -43,11 +124,24 @@ impl<'a> GeometryArrayTrait<'a> for WKBArray {
         self.0
     }
 
-    /// Build a spatial index containing this array's geometries
-    fn rstar_tree(&'a self) -> RTree<Self::Scalar> {
-        let mut tree = RTree::new();
-        self.iter().flatten().for_each(|geom| tree.insert(geom));
-        tree
+    fn geometry_type(&self) -> GeometryArrayType {
+        GeometryArrayType::WKB
+    }
+
+    /// The dimension holding the z and m values declared by the header of any non-null
+    /// geometry, e.g. [`Dimension::XYZ`] if any geometry has z values.
+    ///
+    /// Only headers are read. Geometries are always read as two dimensional, dropping z values;
+    /// m values are kept when reading native arrays.
+    fn dimension(&self) -> Dimension {
+        let (mut has_z, mut has_m) = (false, false);
+        for buf in self.0.iter().flatten() {
+            if let Ok(type_id) = geometry_type_id(buf) {
+                has_z |= type_id.has_z;
+                has_m |= type_id.has_m;
+            }
+        }
+        Dimension::new(has_z, has_m)
     }
 
     /// Returns the number of geometries in this array
@@ -90,6 +184,10 @@ impl<'a> GeometryArrayTrait<'a> for WKBArray {
         self.0.slice_unchecked(offset, length);
     }
 
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        Self(self.0.clone().with_validity(validity), self.1.clone())
+    }
+
     fn to_boxed(&self) -> Box<Self> {
         Box::new(self.clone())
     }
@@ -126,6 +224,24 @@ impl WKBArray {
         ZipValidity::new_with_validity(self.iter_geo_values(), self.validity())
     }
 
+    /// Parallel iterator over the scalars of this array, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = Option<WKB<'_>>> {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get(i))
+    }
+
+    /// Parallel iterator over geo Geometry objects, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_geo(&self) -> impl IndexedParallelIterator<Item = Option<geo::Geometry>> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get_as_geo(i))
+    }
+
     /// Iterator over GEOS geometry objects
     #[cfg(feature = "geos")]
     pub fn iter_geos_values(&self) -> impl Iterator<Item = geos::Geometry> + '_ {
@@ -143,16 +259,56 @@ impl WKBArray {
 
 impl From<BinaryArray<i64>> for WKBArray {
     fn from(other: BinaryArray<i64>) -> Self {
-        Self(other)
+        Self::new(other)
+    }
+}
+
+/// The offsets are widened to 64 bits, sharing the values.
+impl From<BinaryArray<i32>> for WKBArray {
+    fn from(other: BinaryArray<i32>) -> Self {
+        let offsets: OffsetsBuffer<i64> = other.offsets().into();
+        Self::new(BinaryArray::new(
+            DataType::LargeBinary,
+            offsets,
+            other.values().clone(),
+            other.validity().cloned(),
+        ))
+    }
+}
+
+/// Every geometry takes the same number of bytes, as for points of the same dimension. Offsets
+/// are built for the shared values; null slots keep their bytes, unread.
+impl From<FixedSizeBinaryArray> for WKBArray {
+    fn from(other: FixedSizeBinaryArray) -> Self {
+        let lengths = std::iter::repeat_n(other.size(), other.len());
+        let offsets = Offsets::<i64>::try_from_lengths(lengths).unwrap();
+        Self::new(BinaryArray::new(
+            DataType::LargeBinary,
+            offsets.into(),
+            other.values().clone(),
+            other.validity().cloned(),
+        ))
     }
 }
 
 impl TryFrom<Box<dyn Array>> for WKBArray {
     type Error = GeoArrowError;
 
+    /// `LargeBinary`, `Binary` and `FixedSizeBinary` arrays are accepted.
     fn try_from(value: Box<dyn Array>) -> Result<Self, Self::Error> {
-        let arr = value.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
-        Ok(arr.clone().into())
+        let any = value.as_any();
+        if let Some(arr) = any.downcast_ref::<BinaryArray<i64>>() {
+            Ok(arr.clone().into())
+        } else if let Some(arr) = any.downcast_ref::<BinaryArray<i32>>() {
+            Ok(arr.clone().into())
+        } else if let Some(arr) = any.downcast_ref::<FixedSizeBinaryArray>() {
+            Ok(arr.clone().into())
+        } else {
+            Err(GeoArrowError::Downcast {
+                expected: "BinaryArray<i64>",
+                found: value.data_type().clone(),
+            })
+        }
     }
 }
 
@@ -162,3 +318,132 @@ impl From<Vec<Option<geo::Geometry>>> for WKBArray {
         mut_arr.into()
     }
 }
+
+impl From<Vec<Option<geo::GeometryCollection>>> for WKBArray {
+    fn from(other: Vec<Option<geo::GeometryCollection>>) -> Self {
+        let geoms: Vec<Option<geo::Geometry>> = other
+            .into_iter()
+            .map(|maybe_g| maybe_g.map(geo::Geometry::GeometryCollection))
+            .collect();
+        geoms.into()
+    }
+}
+
+impl From<Vec<geo::GeometryCollection>> for WKBArray {
+    fn from(other: Vec<geo::GeometryCollection>) -> Self {
+        other.into_iter().map(Some).collect::<Vec<_>>().into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point, GeometryCollection};
+
+    #[test]
+    fn binary_sources() {
+        let wkb: WKBArray = vec![Some(geo::Geometry::Point(point!(x: 0., y: 1.))), None].into();
+        let large = wkb.clone().into_arrow();
+
+        let binary: BinaryArray<i32> = large.iter().collect();
+        let from_binary = WKBArray::try_from(binary.boxed()).unwrap();
+        assert_eq!(from_binary.into_arrow(), large);
+
+        // Points of the same dimension all take 21 bytes
+        let fixed =
+            FixedSizeBinaryArray::new(DataType::FixedSizeBinary(21), large.values().clone(), None);
+        let from_fixed = WKBArray::try_from(fixed.boxed()).unwrap();
+        assert_eq!(
+            from_fixed.get_as_geo(0),
+            Some(geo::Geometry::Point(point!(x: 0., y: 1.)))
+        );
+    }
+
+    #[test]
+    fn srid() {
+        // POINT(1 2) with SRID 4326 or 3857, as little endian EWKB
+        let ewkb = |srid: i32| {
+            let mut buf = vec![0x01, 0x01, 0x00, 0x00, 0x20];
+            buf.extend_from_slice(&srid.to_le_bytes());
+            buf.extend_from_slice(&1f64.to_le_bytes());
+            buf.extend_from_slice(&2f64.to_le_bytes());
+            buf
+        };
+        let wkb: WKBArray = vec![Some(geo::Geometry::Point(point!(x: 0., y: 1.)))].into();
+        let plain = wkb.value(0).to_wkb();
+
+        let arr = WKBArray::new(BinaryArray::from(vec![
+            Some(ewkb(4326)),
+            None,
+            Some(plain.clone()),
+        ]));
+        assert_eq!(arr.srid().unwrap(), Some(4326));
+        let arr = arr.with_srid_crs().unwrap();
+        assert_eq!(arr.crs(), Some("EPSG:4326"));
+        assert_eq!(
+            arr.get_as_geo(0),
+            Some(geo::Geometry::Point(point!(x: 1., y: 2.)))
+        );
+        assert!(arr.with_crs(Some("EPSG:3857")).with_srid_crs().is_err());
+
+        let mixed = WKBArray::new(BinaryArray::from(vec![Some(ewkb(4326)), Some(ewkb(3857))]));
+        assert!(matches!(
+            mixed.srid(),
+            Err(GeoArrowError::CrsMismatch { .. })
+        ));
+        assert_eq!(
+            WKBArray::new(BinaryArray::from(vec![Some(plain)]))
+                .srid()
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn scan_headers() {
+        let arr: WKBArray = vec![
+            Some(geo::Geometry::Point(point!(x: 0., y: 1.))),
+            None,
+            Some(geo::Geometry::LineString(
+                line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+            )),
+            Some(geo::Geometry::Point(point!(x: 2., y: 3.))),
+        ]
+        .into();
+        let mut types: Vec<String> = arr
+            .geometry_types()
+            .unwrap()
+            .iter()
+            .map(|type_id| type_id.to_string())
+            .collect();
+        types.sort();
+        assert_eq!(types, vec!["LineString", "Point"]);
+        arr.validate().unwrap();
+
+        let mut truncated = arr.value(2).to_wkb();
+        truncated.pop();
+        let invalid = WKBArray::new(BinaryArray::from(vec![
+            Some(arr.value(0).to_wkb()),
+            Some(truncated),
+        ]));
+        assert!(invalid.geometry_types().is_ok());
+        assert!(matches!(
+            invalid.validate(),
+            Err(GeoArrowError::InvalidWkb { row: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn geometry_collections() {
+        let collection = GeometryCollection(vec![
+            geo::Geometry::Point(point!(x: 0., y: 1.)),
+            geo::Geometry::LineString(line_string![(x: 0., y: 0.), (x: 1., y: 1.)]),
+        ]);
+        let arr: WKBArray = vec![Some(collection.clone()), None].into();
+        assert_eq!(
+            arr.get_as_geo(0),
+            Some(geo::Geometry::GeometryCollection(collection))
+        );
+        assert!(arr.get_as_geo(1).is_none());
+    }
+}
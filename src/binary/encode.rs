@@ -0,0 +1,308 @@
+//! Encode native geometry arrays as little-endian ISO WKB.
+//!
+//! Arrays with m values are encoded as XYM geometries, e.g. `LineString M` (type code `2002`).
+//! Coordinates are copied straight from the source buffers into a single contiguous values
+//! buffer, without building intermediate [`geo`] geometries.
+
+use super::array::WKBArray;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray,
+};
+use arrow2::array::BinaryArray;
+use arrow2::buffer::Buffer;
+use arrow2::datatypes::DataType;
+use arrow2::offset::{Offsets, OffsetsBuffer};
+
+const LITTLE_ENDIAN: u8 = 1;
+
+/// ISO WKB geometry type codes.
+const POINT: u32 = 1;
+const LINESTRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTIPOINT: u32 = 4;
+const MULTILINESTRING: u32 = 5;
+const MULTIPOLYGON: u32 = 6;
+
+/// The ISO WKB type code offset of geometries with m values.
+const M: u32 = 2000;
+
+/// Appends WKB geometries to one values buffer.
+struct WKBWriter<'a> {
+    x: &'a Buffer<f64>,
+    y: &'a Buffer<f64>,
+    m: Option<&'a Buffer<f64>>,
+    values: Vec<u8>,
+}
+
+impl<'a> WKBWriter<'a> {
+    fn new(
+        x: &'a Buffer<f64>,
+        y: &'a Buffer<f64>,
+        m: Option<&'a Buffer<f64>>,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            x,
+            y,
+            m,
+            values: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn header(&mut self, geometry_type: u32) {
+        let geometry_type = match self.m {
+            Some(_) => geometry_type + M,
+            None => geometry_type,
+        };
+        self.values.push(LITTLE_ENDIAN);
+        self.values.extend(geometry_type.to_le_bytes());
+    }
+
+    fn count(&mut self, count: usize) {
+        self.values.extend((count as u32).to_le_bytes());
+    }
+
+    fn coord(&mut self, i: usize) {
+        self.values.extend(self.x[i].to_le_bytes());
+        self.values.extend(self.y[i].to_le_bytes());
+        if let Some(m) = self.m {
+            self.values.extend(m[i].to_le_bytes());
+        }
+    }
+
+    fn point(&mut self, i: usize) {
+        self.header(POINT);
+        self.coord(i);
+    }
+
+    /// Write the coordinates `start..end` as a count followed by the coordinates.
+    fn coords(&mut self, (start, end): (usize, usize)) {
+        self.count(end - start);
+        (start..end).for_each(|i| self.coord(i));
+    }
+
+    fn line_string(&mut self, coords: (usize, usize)) {
+        self.header(LINESTRING);
+        self.coords(coords);
+    }
+
+    /// Write a polygon made of the rings `start..end` of `ring_offsets`.
+    fn polygon(&mut self, ring_offsets: &OffsetsBuffer<i64>, (start, end): (usize, usize)) {
+        self.header(POLYGON);
+        self.count(end - start);
+        (start..end).for_each(|ring| self.coords(ring_offsets.start_end(ring)));
+    }
+}
+
+/// Build a [`WKBArray`] from an array with `len` rows, where `write` encodes the valid row `i`.
+fn encode<'a>(
+    array: &impl GeometryArrayTrait<'a>,
+    x: &Buffer<f64>,
+    y: &Buffer<f64>,
+    m: Option<&Buffer<f64>>,
+    bytes_per_coord: usize,
+    write: impl Fn(&mut WKBWriter<'_>, usize),
+) -> WKBArray {
+    let bytes_per_coord = bytes_per_coord + if m.is_some() { 8 } else { 0 };
+    let mut writer = WKBWriter::new(x, y, m, x.len() * bytes_per_coord);
+    let mut offsets = Offsets::<i64>::with_capacity(array.len());
+    for i in 0..array.len() {
+        let start = writer.values.len();
+        if array.is_valid(i) {
+            write(&mut writer, i);
+        }
+        offsets.try_push_usize(writer.values.len() - start).unwrap();
+    }
+
+    WKBArray::new(BinaryArray::new(
+        DataType::LargeBinary,
+        offsets.into(),
+        writer.values.into(),
+        array.validity().cloned(),
+    ))
+}
+
+macro_rules! impl_encode {
+    ($array:ty, $bytes_per_coord:expr, |$writer:ident, $geom:ident| $body:expr) => {
+        impl From<&$array> for WKBArray {
+            fn from(value: &$array) -> Self {
+                if value.is_empty() {
+                    return WKBArray::new(BinaryArray::new_empty(DataType::LargeBinary))
+                        .with_crs(value.crs());
+                }
+                let first = value.value(0);
+                encode(
+                    value,
+                    first.x,
+                    first.y,
+                    first.m,
+                    $bytes_per_coord,
+                    |$writer, i| {
+                        let $geom = value.value(i);
+                        $body
+                    },
+                )
+                .with_crs(value.crs())
+            }
+        }
+
+        impl From<$array> for WKBArray {
+            fn from(value: $array) -> Self {
+                (&value).into()
+            }
+        }
+    };
+}
+
+impl_encode!(PointArray, 21, |writer, geom| writer.point(geom.geom_index));
+
+impl_encode!(LineStringArray, 16, |writer, geom| {
+    writer.line_string(geom.geom_offsets.start_end(geom.geom_index))
+});
+
+impl_encode!(PolygonArray, 16, |writer, geom| {
+    writer.polygon(
+        geom.ring_offsets,
+        geom.geom_offsets.start_end(geom.geom_index),
+    )
+});
+
+impl_encode!(MultiPointArray, 21, |writer, geom| {
+    let (start, end) = geom.geom_offsets.start_end(geom.geom_index);
+    writer.header(MULTIPOINT);
+    writer.count(end - start);
+    (start..end).for_each(|i| writer.point(i));
+});
+
+impl_encode!(MultiLineStringArray, 16, |writer, geom| {
+    let (start, end) = geom.geom_offsets.start_end(geom.geom_index);
+    writer.header(MULTILINESTRING);
+    writer.count(end - start);
+    (start..end).for_each(|line| writer.line_string(geom.ring_offsets.start_end(line)));
+});
+
+impl_encode!(MultiPolygonArray, 16, |writer, geom| {
+    let (start, end) = geom.geom_offsets.start_end(geom.geom_index);
+    writer.header(MULTIPOLYGON);
+    writer.count(end - start);
+    (start..end).for_each(|polygon| {
+        writer.polygon(geom.ring_offsets, geom.polygon_offsets.start_end(polygon))
+    });
+});
+
+impl From<RectArray> for WKBArray {
+    /// Rects are encoded as polygons, since WKB has no rectangle type.
+    fn from(value: RectArray) -> Self {
+        let polygons: PolygonArray = value.into();
+        polygons.into()
+    }
+}
+
+impl From<MixedGeometryArray> for WKBArray {
+    fn from(value: MixedGeometryArray) -> Self {
+        let crs = value.crs().map(str::to_string);
+        let geoms: Vec<Option<geo::Geometry>> = value.into();
+        WKBArray::from(geoms).with_crs(crs.as_deref())
+    }
+}
+
+impl From<GeometryArray> for WKBArray {
+    fn from(value: GeometryArray) -> Self {
+        match value {
+            GeometryArray::Point(arr) => arr.into(),
+            GeometryArray::LineString(arr) => arr.into(),
+            GeometryArray::Polygon(arr) => arr.into(),
+            GeometryArray::MultiPoint(arr) => arr.into(),
+            GeometryArray::MultiLineString(arr) => arr.into(),
+            GeometryArray::MultiPolygon(arr) => arr.into(),
+            GeometryArray::WKB(arr) => arr,
+            GeometryArray::Rect(arr) => arr.into(),
+            GeometryArray::Mixed(arr) => (*arr).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point, polygon, MultiLineString, MultiPoint, MultiPolygon};
+    use geozero::{CoordDimensions, ToWkb};
+
+    /// The WKB written by geozero for each row, as the reference encoding.
+    fn reference(geoms: &[Option<geo::Geometry>]) -> Vec<Option<Vec<u8>>> {
+        geoms
+            .iter()
+            .map(|maybe_g| {
+                maybe_g
+                    .as_ref()
+                    .map(|geom| geom.to_wkb(CoordDimensions::xy()).unwrap())
+            })
+            .collect()
+    }
+
+    fn encoded(arr: WKBArray) -> Vec<Option<Vec<u8>>> {
+        (0..arr.len())
+            .map(|i| arr.get(i).map(|wkb| wkb.to_wkb()))
+            .collect()
+    }
+
+    #[test]
+    fn matches_reference_encoding() {
+        let p0 = point!(x: 0., y: 1.);
+        let p1 = point!(x: 2., y: 3.);
+        let ls = line_string![(x: 0., y: 0.), (x: 1., y: 1.), (x: 2., y: 0.)];
+        let poly = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 0.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 1.)]],
+        );
+
+        let points: PointArray = vec![Some(p0), None, Some(p1)].into();
+        assert_eq!(
+            encoded(points.into()),
+            reference(&[Some(p0.into()), None, Some(p1.into())])
+        );
+
+        let lines: LineStringArray = vec![None, Some(ls.clone())].into();
+        assert_eq!(
+            encoded(lines.into()),
+            reference(&[None, Some(ls.clone().into())])
+        );
+
+        let polygons: PolygonArray = vec![poly.clone()].into();
+        assert_eq!(
+            encoded(polygons.into()),
+            reference(&[Some(poly.clone().into())])
+        );
+
+        let multi_points: MultiPointArray = vec![MultiPoint(vec![p0, p1])].into();
+        assert_eq!(
+            encoded(multi_points.into()),
+            reference(&[Some(MultiPoint(vec![p0, p1]).into())])
+        );
+
+        let multi_lines = MultiLineString(vec![ls.clone(), ls]);
+        let arr: MultiLineStringArray = vec![multi_lines.clone()].into();
+        assert_eq!(encoded(arr.into()), reference(&[Some(multi_lines.into())]));
+
+        let multi_polygon = MultiPolygon(vec![poly.clone(), poly]);
+        let arr: MultiPolygonArray = vec![None, Some(multi_polygon.clone())].into();
+        let wkb: WKBArray = GeometryArray::MultiPolygon(arr).into();
+        assert_eq!(encoded(wkb), reference(&[None, Some(multi_polygon.into())]));
+    }
+
+    #[test]
+    fn sliced_and_empty() {
+        let points: PointArray = vec![point!(x: 0., y: 1.), point!(x: 2., y: 3.)].into();
+        let wkb: WKBArray = points.sliced_range(1..2).into();
+        assert_eq!(wkb.len(), 1);
+        assert_eq!(
+            wkb.get_as_geo(0),
+            Some(geo::Geometry::Point(point!(x: 2., y: 3.)))
+        );
+
+        let lines: LineStringArray = Vec::<geo::LineString>::new().into();
+        let wkb: WKBArray = lines.into();
+        assert!(wkb.is_empty());
+    }
+}
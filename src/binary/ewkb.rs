@@ -0,0 +1,87 @@
+//! [EWKB](https://postgis.net/docs/using_postgis_dbmanagement.html#EWKB_EWKT), the WKB dialect
+//! of PostGIS.
+//!
+//! EWKB flags Z, M and SRID with the three high bits of the type code, and stores the SRID, if
+//! any, right after the type code. ISO WKB instead adds 1000 (Z), 2000 (M) or 3000 (ZM) to the
+//! type code, and has no SRID.
+
+use geozero::wkb::{Ewkb, Wkb};
+use geozero::ToGeo;
+
+/// The Z, M and SRID flags of EWKB type codes.
+const EWKB_FLAGS: u32 = 0xe000_0000;
+const SRID_FLAG: u32 = 0x2000_0000;
+
+/// The type code in the header of the WKB or EWKB geometry `buf`.
+fn type_code(buf: &[u8]) -> Option<u32> {
+    let code: [u8; 4] = buf.get(1..5)?.try_into().unwrap();
+    match buf[0] {
+        0 => Some(u32::from_be_bytes(code)),
+        _ => Some(u32::from_le_bytes(code)),
+    }
+}
+
+/// Whether the header of `buf` has any EWKB flag.
+pub(crate) fn is_ewkb(buf: &[u8]) -> bool {
+    type_code(buf).map_or(false, |code| code & EWKB_FLAGS != 0)
+}
+
+/// The SRID in the header of the EWKB geometry `buf`, if any.
+///
+/// SRID 0 is the PostGIS value for an unknown CRS, and is returned as `None`.
+pub(crate) fn ewkb_srid(buf: &[u8]) -> Option<i32> {
+    let code = type_code(buf)?;
+    let srid: [u8; 4] = buf.get(5..9)?.try_into().unwrap();
+    let srid = match buf[0] {
+        0 => i32::from_be_bytes(srid),
+        _ => i32::from_le_bytes(srid),
+    };
+    (code & SRID_FLAG != 0 && srid != 0).then_some(srid)
+}
+
+/// Parse the WKB or EWKB geometry `buf`, dropping any Z and M values.
+pub(crate) fn parse_wkb(buf: &[u8]) -> geozero::error::Result<geo::Geometry> {
+    if is_ewkb(buf) {
+        Ewkb(buf.to_vec()).to_geo()
+    } else {
+        Wkb(buf.to_vec()).to_geo()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::point;
+
+    /// `SRID=4326;POINT(1 2)` as little endian EWKB.
+    const EWKB_POINT: [u8; 25] = [
+        0x01, 0x01, 0x00, 0x00, 0x20, 0xe6, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0xf0, 0x3f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40,
+    ];
+
+    #[test]
+    fn srid() {
+        assert_eq!(ewkb_srid(&EWKB_POINT), Some(4326));
+        assert_eq!(parse_wkb(&EWKB_POINT).unwrap(), point!(x: 1., y: 2.).into());
+
+        // The same point without an SRID
+        let mut wkb = vec![0x01, 0x01, 0x00, 0x00, 0x00];
+        wkb.extend_from_slice(&EWKB_POINT[9..]);
+        assert_eq!(ewkb_srid(&wkb), None);
+        assert!(!is_ewkb(&wkb));
+        assert_eq!(parse_wkb(&wkb).unwrap(), point!(x: 1., y: 2.).into());
+        assert_eq!(ewkb_srid(&[0x01]), None);
+    }
+
+    #[test]
+    fn z_and_m() {
+        // POINT ZM (1 2 3 4), with both EWKB flags
+        let mut ewkb = vec![0x01, 0x01, 0x00, 0x00, 0xc0];
+        for value in [1f64, 2., 3., 4.] {
+            ewkb.extend_from_slice(&value.to_le_bytes());
+        }
+        assert!(is_ewkb(&ewkb));
+        assert_eq!(ewkb_srid(&ewkb), None);
+        assert_eq!(parse_wkb(&ewkb).unwrap(), point!(x: 1., y: 2.).into());
+    }
+}
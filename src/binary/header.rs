@@ -0,0 +1,440 @@
+//! Read the structure of WKB geometries without parsing their coordinates.
+//!
+//! The header of a geometry (its byte order and type code) gives its type and dimensions, and the
+//! counts that follow give the length of the rest, so whole columns can be profiled and checked
+//! far faster than they can be parsed.
+
+use std::collections::HashSet;
+use std::fmt;
+
+/// The EWKB flags for z values, m values and an SRID.
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// The base type of a WKB geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum WkbGeometryType {
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+}
+
+impl WkbGeometryType {
+    /// The type with 2D WKB type code `code`.
+    fn from_code(code: u32) -> Option<Self> {
+        match code {
+            1 => Some(Self::Point),
+            2 => Some(Self::LineString),
+            3 => Some(Self::Polygon),
+            4 => Some(Self::MultiPoint),
+            5 => Some(Self::MultiLineString),
+            6 => Some(Self::MultiPolygon),
+            7 => Some(Self::GeometryCollection),
+            _ => None,
+        }
+    }
+
+    /// The 2D WKB type code of this type.
+    fn code(&self) -> u32 {
+        *self as u32 + 1
+    }
+
+    /// The type of the parts of geometries of this type, for multi geometries.
+    fn part_type(&self) -> Option<Self> {
+        match self {
+            Self::MultiPoint => Some(Self::Point),
+            Self::MultiLineString => Some(Self::LineString),
+            Self::MultiPolygon => Some(Self::Polygon),
+            _ => None,
+        }
+    }
+}
+
+/// The type and dimensions of a WKB geometry, as declared in its header.
+///
+/// Both the ISO (`1001` for a point with z) and the EWKB (high flag bits) type codes are read.
+/// Formatted as in the `geometry_types` metadata of GeoParquet, e.g. `"Point"` or
+/// `"MultiPolygon Z"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GeometryTypeId {
+    pub geometry_type: WkbGeometryType,
+    pub has_z: bool,
+    pub has_m: bool,
+}
+
+impl GeometryTypeId {
+    /// The type declared by the WKB type code `code`.
+    fn from_code(code: u32) -> Option<Self> {
+        let flags = code & (EWKB_Z | EWKB_M);
+        let iso = code & 0x0fff_ffff;
+        let (dimensions, base) = (iso / 1000, iso % 1000);
+        let (has_z, has_m) = match (flags, dimensions) {
+            (0, 0) => (false, false),
+            (0, 1) => (true, false),
+            (0, 2) => (false, true),
+            (0, 3) => (true, true),
+            (flags, 0) => (flags & EWKB_Z != 0, flags & EWKB_M != 0),
+            _ => return None,
+        };
+        Some(Self {
+            geometry_type: WkbGeometryType::from_code(base)?,
+            has_z,
+            has_m,
+        })
+    }
+
+    /// The ISO WKB type code of this type, e.g. `1001` for a point with z.
+    pub fn iso_code(&self) -> u32 {
+        let dimensions = match (self.has_z, self.has_m) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (false, true) => 2,
+            (true, true) => 3,
+        };
+        dimensions * 1000 + self.geometry_type.code()
+    }
+
+    /// The EWKB type code of this type, e.g. `0x8000_0001` for a point with z, flagged as followed
+    /// by an SRID if `srid`.
+    pub(crate) fn ewkb_code(&self, srid: bool) -> u32 {
+        let mut code = self.geometry_type.code();
+        if self.has_z {
+            code |= EWKB_Z;
+        }
+        if self.has_m {
+            code |= EWKB_M;
+        }
+        if srid {
+            code |= EWKB_SRID;
+        }
+        code
+    }
+
+    /// The number of values in each coordinate.
+    fn num_dimensions(&self) -> usize {
+        2 + self.has_z as usize + self.has_m as usize
+    }
+}
+
+impl fmt::Display for GeometryTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.geometry_type)?;
+        match (self.has_z, self.has_m) {
+            (false, false) => Ok(()),
+            (true, false) => write!(f, " Z"),
+            (false, true) => write!(f, " M"),
+            (true, true) => write!(f, " ZM"),
+        }
+    }
+}
+
+/// The numbers of coordinates, parts and polygon rings of a WKB geometry.
+///
+/// Single geometries have one part, and multi geometries and collections one per geometry
+/// they hold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct WkbCounts {
+    pub num_coords: usize,
+    pub num_parts: usize,
+    pub num_rings: usize,
+}
+
+/// A cursor over the bytes of one WKB geometry.
+struct Scanner<'a> {
+    buf: &'a [u8],
+    position: usize,
+    /// The counts of the geometry skipped over
+    counts: WkbCounts,
+    /// The m values of the coordinates skipped over, if they are collected, with `NaN` for
+    /// coordinates without one.
+    m_values: Option<Vec<f64>>,
+    /// The coordinates skipped over, if they are collected, as `[x, y, z, m]` with `NaN` for
+    /// missing values.
+    coords: Option<Vec<[f64; 4]>>,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self {
+            buf,
+            position: 0,
+            counts: WkbCounts::default(),
+            m_values: None,
+            coords: None,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.buf.len())
+            .ok_or_else(|| format!("truncated at byte {}", self.buf.len()))?;
+        let bytes = &self.buf[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self, little_endian: bool) -> Result<u32, String> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().unwrap();
+        Ok(match little_endian {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes),
+        })
+    }
+
+    /// Read a header, returning the byte order (`true` for little endian) and the type.
+    fn header(&mut self) -> Result<(bool, GeometryTypeId, u32), String> {
+        let little_endian = match self.take(1)?[0] {
+            0 => false,
+            1 => true,
+            byte => return Err(format!("unknown byte order {byte}")),
+        };
+        let code = self.u32(little_endian)?;
+        let type_id = GeometryTypeId::from_code(code)
+            .ok_or_else(|| format!("unknown geometry type {code}"))?;
+        Ok((little_endian, type_id, code))
+    }
+
+    /// Skip `count` coordinates of `type_id`, checking they fit in the buffer.
+    fn coords(
+        &mut self,
+        count: u32,
+        type_id: &GeometryTypeId,
+        little_endian: bool,
+    ) -> Result<(), String> {
+        let coord_len = 8 * type_id.num_dimensions();
+        let len = (count as usize)
+            .checked_mul(coord_len)
+            .ok_or_else(|| format!("coordinate count {count} overflows"))?;
+        let bytes = self.take(len)?;
+        self.counts.num_coords += count as usize;
+        if let Some(m_values) = self.m_values.as_mut() {
+            for coord in bytes.chunks_exact(coord_len) {
+                let m = match type_id.has_m {
+                    true => coord[coord_len - 8..].try_into().unwrap(),
+                    false => f64::NAN.to_le_bytes(),
+                };
+                m_values.push(match little_endian {
+                    true => f64::from_le_bytes(m),
+                    false => f64::from_be_bytes(m),
+                });
+            }
+        }
+        if let Some(coords) = self.coords.as_mut() {
+            let value = |coord: &[u8], offset: usize| {
+                let bytes: [u8; 8] = coord[offset..offset + 8].try_into().unwrap();
+                match little_endian {
+                    true => f64::from_le_bytes(bytes),
+                    false => f64::from_be_bytes(bytes),
+                }
+            };
+            for coord in bytes.chunks_exact(coord_len) {
+                coords.push([
+                    value(coord, 0),
+                    value(coord, 8),
+                    if type_id.has_z {
+                        value(coord, 16)
+                    } else {
+                        f64::NAN
+                    },
+                    if type_id.has_m {
+                        value(coord, coord_len - 8)
+                    } else {
+                        f64::NAN
+                    },
+                ]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip one geometry, checking its structure, and return its type.
+    fn geometry(&mut self, top_level: bool) -> Result<GeometryTypeId, String> {
+        let (little_endian, type_id, code) = self.header()?;
+        if code & EWKB_SRID != 0 {
+            if !top_level {
+                return Err("SRID on a nested geometry".to_string());
+            }
+            self.take(4)?;
+        }
+
+        if top_level {
+            self.counts.num_parts = 1;
+        }
+        match type_id.geometry_type {
+            WkbGeometryType::Point => self.coords(1, &type_id, little_endian)?,
+            WkbGeometryType::LineString => {
+                let count = self.u32(little_endian)?;
+                self.coords(count, &type_id, little_endian)?;
+            }
+            WkbGeometryType::Polygon => {
+                let num_rings = self.u32(little_endian)?;
+                self.counts.num_rings += num_rings as usize;
+                for _ in 0..num_rings {
+                    let count = self.u32(little_endian)?;
+                    self.coords(count, &type_id, little_endian)?;
+                }
+            }
+            geometry_type => {
+                let num_parts = self.u32(little_endian)?;
+                if top_level {
+                    self.counts.num_parts = num_parts as usize;
+                }
+                for _ in 0..num_parts {
+                    let part = self.geometry(false)?;
+                    if let Some(expected) = geometry_type.part_type() {
+                        if part.geometry_type != expected {
+                            return Err(format!(
+                                "{geometry_type:?} holding a {:?}",
+                                part.geometry_type
+                            ));
+                        }
+                    }
+                    if (part.has_z, part.has_m) != (type_id.has_z, type_id.has_m) {
+                        return Err(format!("{type_id} holding a {part}"));
+                    }
+                }
+            }
+        }
+        Ok(type_id)
+    }
+}
+
+/// The type declared in the header of the WKB or EWKB geometry `buf`.
+pub(crate) fn geometry_type_id(buf: &[u8]) -> Result<GeometryTypeId, String> {
+    Scanner::new(buf).header().map(|(_, type_id, _)| type_id)
+}
+
+/// Check that `buf` holds exactly one well-formed WKB or EWKB geometry, returning its type.
+///
+/// Only the structure is checked: coordinates are not read, so `NaN` values or unclosed rings are
+/// accepted.
+pub(crate) fn validate_wkb(buf: &[u8]) -> Result<GeometryTypeId, String> {
+    let mut scanner = Scanner::new(buf);
+    let type_id = scanner.geometry(true)?;
+    if scanner.position != buf.len() {
+        return Err(format!("{} trailing bytes", buf.len() - scanner.position));
+    }
+    Ok(type_id)
+}
+
+/// The numbers of coordinates, parts and rings of the WKB or EWKB geometry `buf`, read by
+/// skipping over its coordinates.
+pub(crate) fn wkb_counts(buf: &[u8]) -> Result<WkbCounts, String> {
+    let mut scanner = Scanner::new(buf);
+    scanner.geometry(true)?;
+    Ok(scanner.counts)
+}
+
+/// Append the m value of every coordinate of the WKB or EWKB geometry `buf` to `m_values`, in
+/// order, with `NaN` for coordinates without one.
+pub(crate) fn extend_m_values(buf: &[u8], m_values: &mut Vec<f64>) -> Result<(), String> {
+    let mut scanner = Scanner::new(buf);
+    scanner.m_values = Some(std::mem::take(m_values));
+    let result = scanner.geometry(true);
+    *m_values = scanner.m_values.take().unwrap();
+    result.map(|_| ())
+}
+
+/// The coordinates of the WKB or EWKB geometry `buf`, in order, as `[x, y, z, m]` with `NaN` for
+/// missing values.
+pub(crate) fn wkb_coords(buf: &[u8]) -> Result<Vec<[f64; 4]>, String> {
+    let mut scanner = Scanner::new(buf);
+    scanner.coords = Some(Vec::new());
+    scanner.geometry(true)?;
+    Ok(scanner.coords.take().unwrap())
+}
+
+/// The distinct types of the geometries in `bufs`.
+pub(crate) fn geometry_type_ids<'a>(
+    bufs: impl Iterator<Item = (usize, &'a [u8])>,
+) -> Result<HashSet<GeometryTypeId>, (usize, String)> {
+    bufs.map(|(row, buf)| geometry_type_id(buf).map_err(|message| (row, message)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn point_xy(x: f64, y: f64) -> Vec<u8> {
+        let mut buf = vec![0x01, 0x01, 0x00, 0x00, 0x00];
+        buf.extend_from_slice(&x.to_le_bytes());
+        buf.extend_from_slice(&y.to_le_bytes());
+        buf
+    }
+
+    #[test]
+    fn type_ids() {
+        let point = GeometryTypeId::from_code(1).unwrap();
+        assert_eq!(point.to_string(), "Point");
+        let polygon_z = GeometryTypeId::from_code(1003).unwrap();
+        assert_eq!(polygon_z.to_string(), "Polygon Z");
+        assert_eq!(polygon_z.iso_code(), 1003);
+        assert_eq!(polygon_z.ewkb_code(false), 0x8000_0003);
+        assert_eq!(point.ewkb_code(true), 0x2000_0001);
+
+        // EWKB flags map to the same types as ISO codes
+        assert_eq!(GeometryTypeId::from_code(0x8000_0003), Some(polygon_z));
+        assert_eq!(
+            GeometryTypeId::from_code(0xc000_0001).unwrap().to_string(),
+            "Point ZM"
+        );
+        assert_eq!(GeometryTypeId::from_code(8), None);
+        assert_eq!(GeometryTypeId::from_code(0x8000_1001), None);
+    }
+
+    #[test]
+    fn validate() {
+        let point = point_xy(0., 1.);
+        assert_eq!(validate_wkb(&point).unwrap().to_string(), "Point");
+        assert!(validate_wkb(&point[..20]).is_err());
+        assert!(validate_wkb(&[point.clone(), vec![0]].concat()).is_err());
+        assert!(validate_wkb(&[&[0x02], &point[1..]].concat()).is_err());
+
+        // A multi point of two points, and one holding a line string
+        let mut multi_point = vec![0x01, 0x04, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00];
+        multi_point.extend_from_slice(&point);
+        multi_point.extend_from_slice(&point_xy(2., 3.));
+        assert_eq!(
+            validate_wkb(&multi_point).unwrap().to_string(),
+            "MultiPoint"
+        );
+        let mut invalid = vec![0x01, 0x04, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00];
+        invalid.extend_from_slice(&[0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert!(validate_wkb(&invalid).is_err());
+
+        // A line string claiming more points than it holds
+        let line = [0x01, 0x02, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff];
+        assert!(validate_wkb(&line).is_err());
+    }
+
+    #[test]
+    fn m_values() {
+        // LINESTRING M (0 0 10, 1 1 20), big endian
+        let mut line = vec![0x00];
+        line.extend_from_slice(&2002u32.to_be_bytes());
+        line.extend_from_slice(&2u32.to_be_bytes());
+        for value in [0f64, 0., 10., 1., 1., 20.] {
+            line.extend_from_slice(&value.to_be_bytes());
+        }
+        let mut m_values = vec![];
+        extend_m_values(&line, &mut m_values).unwrap();
+        assert_eq!(m_values, vec![10., 20.]);
+        let coords = wkb_coords(&line).unwrap();
+        assert_eq!(coords[1][..2], [1., 1.]);
+        assert!(coords[1][2].is_nan());
+        assert_eq!(coords[1][3], 20.);
+
+        // Coordinates without m values are NaN
+        extend_m_values(&point_xy(0., 1.), &mut m_values).unwrap();
+        assert_eq!(m_values.len(), 3);
+        assert!(m_values[2].is_nan());
+    }
+}
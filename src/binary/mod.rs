@@ -1,10 +1,17 @@
 //! Helpers for using WKB-encoding GeoArrow data
 
 pub use array::WKBArray;
+pub use header::{GeometryTypeId, WkbGeometryType};
 pub use mutable::MutableWKBArray;
 pub use scalar::WKB;
 
+pub(crate) use ewkb::{ewkb_srid, is_ewkb};
+pub(crate) use header::{extend_m_values, geometry_type_id, wkb_coords, wkb_counts};
+
 mod array;
+mod encode;
+mod ewkb;
+mod header;
 mod iterator;
 mod mutable;
 mod scalar;
@@ -1,4 +1,4 @@
-use crate::trait_::MutableGeometryArray;
+use crate::trait_::{GeometryArrayTrait, MutableGeometryArray};
 use arrow2::array::{MutableArray, MutableBinaryArray};
 use arrow2::bitmap::MutableBitmap;
 use geo::Geometry;
@@ -36,11 +36,42 @@ impl MutableWKBArray {
     pub fn with_capacities(capacity: usize, values: usize) -> Self {
         Self(MutableBinaryArray::<i64>::with_capacities(capacity, values))
     }
+
+    /// Reserves capacity for at least `additional` more geometries of `values` more bytes in
+    /// total.
+    pub fn reserve(&mut self, additional: usize, values: usize) {
+        self.0.reserve(additional, values);
+    }
+
+    /// Shrinks the capacity of the buffers of this array to fit their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
+
+    /// Append the geometries of `other` to this array.
+    ///
+    /// Mutable arrays carry no CRS, so the CRS of `other` is dropped.
+    pub fn extend_from_array(&mut self, other: &WKBArray) {
+        for value in other.clone().into_arrow().iter() {
+            self.0.push(value);
+        }
+    }
+
+    /// Adds a new geometry to the array, or a null if `value` is `None`.
+    pub fn push_geo(&mut self, value: Option<&Geometry>) {
+        self.0
+            .push(value.map(|geom| geom.to_wkb(CoordDimensions::xy()).unwrap()));
+    }
+
+    /// Adds a new null to the array.
+    pub fn push_null(&mut self) {
+        self.0.push_null();
+    }
 }
 
 impl MutableGeometryArray for MutableWKBArray {
     fn len(&self) -> usize {
-        self.0.values().len()
+        self.0.len()
     }
 
     fn validity(&self) -> Option<&MutableBitmap> {
@@ -64,6 +95,10 @@ impl MutableGeometryArray for MutableWKBArray {
     fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn push_null(&mut self) {
+        MutableWKBArray::push_null(self)
+    }
 }
 
 impl From<Vec<Option<Geometry>>> for MutableWKBArray {
@@ -1,6 +1,8 @@
+use super::ewkb::{ewkb_srid, parse_wkb};
+use crate::error::GeoArrowError;
 use arrow2::array::BinaryArray;
 use geo::BoundingRect;
-use geozero::ToGeo;
+use geozero::{CoordDimensions, ToWkb};
 use rstar::{RTreeObject, AABB};
 
 /// An Arrow equivalent of a Point
@@ -10,6 +12,34 @@ pub struct WKB<'a> {
     pub geom_index: usize,
 }
 
+impl WKB<'_> {
+    /// The raw WKB bytes of this geometry.
+    pub fn as_slice(&self) -> &[u8] {
+        self.arr.value(self.geom_index)
+    }
+
+    /// A copy of the raw WKB bytes of this geometry.
+    pub fn to_wkb(&self) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+
+    /// The SRID of this geometry, if it is EWKB with a non-zero SRID.
+    pub fn srid(&self) -> Option<i32> {
+        ewkb_srid(self.as_slice())
+    }
+
+    /// Parse this geometry into a typed [`geo::Geometry`].
+    ///
+    /// Both ISO WKB and PostGIS EWKB are read; Z and M values are dropped. Unlike the [`From`]
+    /// conversion, this returns an error instead of panicking on invalid WKB.
+    pub fn to_typed_scalar(&self) -> Result<geo::Geometry, GeoArrowError> {
+        parse_wkb(self.as_slice()).map_err(|err| GeoArrowError::InvalidWkb {
+            row: self.geom_index,
+            message: err.to_string(),
+        })
+    }
+}
+
 impl From<WKB<'_>> for geo::Geometry {
     fn from(value: WKB<'_>) -> Self {
         (&value).into()
@@ -18,8 +48,7 @@ impl From<WKB<'_>> for geo::Geometry {
 
 impl From<&WKB<'_>> for geo::Geometry {
     fn from(value: &WKB<'_>) -> Self {
-        let buf = value.arr.value(value.geom_index);
-        geozero::wkb::Wkb(buf.to_vec()).to_geo().unwrap()
+        parse_wkb(value.as_slice()).unwrap()
     }
 }
 
@@ -34,3 +63,60 @@ impl RTreeObject for WKB<'_> {
         AABB::from_corners(lower, upper)
     }
 }
+
+/// Encode a [`geo::Geometry`] as 2D ISO WKB.
+fn geometry_to_wkb(geom: geo::Geometry) -> Vec<u8> {
+    // Boxes have no WKB type of their own
+    let geom = match geom {
+        geo::Geometry::Rect(rect) => geo::Geometry::Polygon(rect.to_polygon()),
+        geom => geom,
+    };
+    geom.to_wkb(CoordDimensions::xy()).unwrap()
+}
+
+macro_rules! impl_to_wkb {
+    ($type:ty) => {
+        impl $type {
+            /// Encode this geometry as 2D ISO WKB.
+            pub fn to_wkb(&self) -> Vec<u8> {
+                geometry_to_wkb(self.clone().into())
+            }
+        }
+    };
+}
+
+impl_to_wkb!(crate::Point<'_>);
+impl_to_wkb!(crate::LineString<'_>);
+impl_to_wkb!(crate::Polygon<'_>);
+impl_to_wkb!(crate::MultiPoint<'_>);
+impl_to_wkb!(crate::MultiLineString<'_>);
+impl_to_wkb!(crate::MultiPolygon<'_>);
+impl_to_wkb!(crate::Rect<'_>);
+
+#[cfg(test)]
+mod test {
+    use crate::{GeometryArrayTrait, LineStringArray, RectArray, WKBArray};
+    use geo::{coord, line_string};
+
+    #[test]
+    fn scalar_wkb_roundtrip() {
+        let line = line_string![(x: 0., y: 1.), (x: 2., y: 3.)];
+        let arr: LineStringArray = vec![line.clone()].into();
+        let wkb = arr.value(0).to_wkb();
+
+        let wkb_arr: WKBArray = vec![Some(geo::Geometry::LineString(line.clone()))].into();
+        assert_eq!(wkb_arr.value(0).as_slice(), wkb.as_slice());
+        assert_eq!(
+            wkb_arr.value(0).to_typed_scalar().unwrap(),
+            geo::Geometry::LineString(line)
+        );
+    }
+
+    #[test]
+    fn rect_to_wkb_is_polygon() {
+        let rect = geo::Rect::new(coord! { x: 0., y: 0. }, coord! { x: 1., y: 1. });
+        let arr: RectArray = vec![rect].into();
+        let wkb_arr: WKBArray = vec![Some(geo::Geometry::Polygon(rect.to_polygon()))].into();
+        assert_eq!(arr.value(0).to_wkb(), wkb_arr.value(0).to_wkb());
+    }
+}
@@ -0,0 +1,296 @@
+//! Conversions between geometry array types.
+//!
+//! Kernels producing [`geo`] geometries build the narrowest array able to hold them, and readers
+//! and table operations convert chunks of different types to a common one, promoting single
+//! geometries to multi geometries where possible and falling back to WKB otherwise.
+
+use crate::enum_::EXTENSION_NAME_KEY;
+use crate::error::GeoArrowError;
+use crate::table::GeoTable;
+use crate::{GeometryArray, GeometryArrayTrait, GeometryArrayType, PolygonArray};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::Schema;
+
+/// Replace the geometry column of each chunk of `table` with the matching array of `outputs`.
+///
+/// If chunks end up with different geometry types, every chunk is stored as WKB so that the
+/// output column has a single data type.
+pub(crate) fn replace_geometry(
+    table: &GeoTable,
+    mut outputs: Vec<GeometryArray>,
+) -> Result<GeoTable, GeoArrowError> {
+    let geometry_column_index = table.geometry_column_index();
+    if outputs
+        .windows(2)
+        .any(|pair| pair[0].geometry_type() != pair[1].geometry_type())
+    {
+        outputs = outputs.into_iter().map(to_wkb).collect();
+    }
+    let kind = outputs.first().map(|array| array.geometry_type());
+    let outputs: Vec<_> = outputs
+        .into_iter()
+        .map(|array| array.into_arrow())
+        .collect();
+
+    let mut fields = table.schema().fields.clone();
+    if let (Some(array), Some(kind)) = (outputs.first(), kind) {
+        let field = &mut fields[geometry_column_index];
+        if field.data_type() != array.data_type() {
+            field.data_type = array.data_type().clone();
+        }
+        // Any extension name described the input geometry type. Multi points and line strings
+        // share their layout with line strings and polygons, so the name must be kept accurate.
+        field.metadata.remove(EXTENSION_NAME_KEY);
+        if let Some(name) = kind.extension_name() {
+            field
+                .metadata
+                .insert(EXTENSION_NAME_KEY.to_string(), name.to_string());
+        }
+    }
+    let schema = Schema::from(fields).with_metadata(table.schema().metadata.clone());
+
+    let chunks = table
+        .chunks()
+        .iter()
+        .zip(outputs)
+        .map(|(chunk, geometry)| {
+            let mut arrays = chunk.arrays().to_vec();
+            arrays[geometry_column_index] = geometry;
+            Chunk::new(arrays)
+        })
+        .collect();
+
+    GeoTable::try_new(schema, chunks, geometry_column_index)
+}
+
+/// Convert `geom` to `kind`, which must be a promotion of its own kind.
+pub(crate) fn promote_geometry(geom: geo::Geometry, kind: GeometryArrayType) -> geo::Geometry {
+    match (geom, kind) {
+        (geo::Geometry::Point(geom), GeometryArrayType::MultiPoint) => {
+            geo::Geometry::MultiPoint(geo::MultiPoint(vec![geom]))
+        }
+        (geo::Geometry::LineString(geom), GeometryArrayType::MultiLineString) => {
+            geo::Geometry::MultiLineString(geo::MultiLineString(vec![geom]))
+        }
+        (geo::Geometry::Polygon(geom), GeometryArrayType::MultiPolygon) => {
+            geo::Geometry::MultiPolygon(geo::MultiPolygon(vec![geom]))
+        }
+        (geo::Geometry::Rect(geom), GeometryArrayType::Polygon) => {
+            geo::Geometry::Polygon(geom.to_polygon())
+        }
+        (geo::Geometry::Rect(geom), GeometryArrayType::MultiPolygon) => {
+            geo::Geometry::MultiPolygon(geo::MultiPolygon(vec![geom.to_polygon()]))
+        }
+        (geom, _) => geom,
+    }
+}
+
+/// The name of the type of `geom`, for error messages.
+pub(crate) fn geometry_type_name(geom: &geo::Geometry) -> &'static str {
+    match geom {
+        geo::Geometry::Point(_) => "Point",
+        geo::Geometry::Line(_) => "Line",
+        geo::Geometry::LineString(_) => "LineString",
+        geo::Geometry::Polygon(_) => "Polygon",
+        geo::Geometry::MultiPoint(_) => "MultiPoint",
+        geo::Geometry::MultiLineString(_) => "MultiLineString",
+        geo::Geometry::MultiPolygon(_) => "MultiPolygon",
+        geo::Geometry::GeometryCollection(_) => "GeometryCollection",
+        geo::Geometry::Rect(_) => "Rect",
+        geo::Geometry::Triangle(_) => "Triangle",
+    }
+}
+
+/// Convert `array` to the given type, which must be WKB or a promotion of its own type.
+///
+/// # Errors
+/// Errors if `kind` is neither WKB nor a promotion of the type of `array`.
+pub(crate) fn coerce(
+    array: GeometryArray,
+    kind: GeometryArrayType,
+) -> Result<GeometryArray, GeoArrowError> {
+    Ok(match (array, kind) {
+        (array, kind) if array.geometry_type() == kind => array,
+        (array, GeometryArrayType::WKB) => to_wkb(array),
+        (GeometryArray::Point(arr), GeometryArrayType::MultiPoint) => {
+            GeometryArray::MultiPoint(arr.into())
+        }
+        (GeometryArray::LineString(arr), GeometryArrayType::MultiLineString) => {
+            GeometryArray::MultiLineString(arr.into())
+        }
+        (GeometryArray::Polygon(arr), GeometryArrayType::MultiPolygon) => {
+            GeometryArray::MultiPolygon(arr.into())
+        }
+        (GeometryArray::Rect(arr), GeometryArrayType::Polygon) => {
+            GeometryArray::Polygon(arr.into())
+        }
+        (GeometryArray::Rect(arr), GeometryArrayType::MultiPolygon) => {
+            let polygons: PolygonArray = arr.into();
+            GeometryArray::MultiPolygon(polygons.into())
+        }
+        (array, kind) => {
+            return Err(GeoArrowError::General(format!(
+                "cannot coerce {:?} to {:?}",
+                array.geometry_type(),
+                kind
+            )))
+        }
+    })
+}
+
+/// Build the narrowest array able to hold `geoms`: a native array when every geometry has the
+/// same type, WKB otherwise. All-null input keeps the `fallback` type.
+pub(crate) fn from_geo(
+    geoms: Vec<Option<geo::Geometry>>,
+    fallback: GeometryArrayType,
+) -> GeometryArray {
+    let mut kinds = geoms.iter().flatten().map(GeometryArrayType::of_geo);
+    let kind = match kinds.next() {
+        None => fallback,
+        Some(first) if kinds.all(|kind| kind == first) => first,
+        Some(_) => GeometryArrayType::WKB,
+    };
+
+    macro_rules! collect_variant {
+        ($variant:ident) => {{
+            let typed: Vec<Option<geo::$variant>> = geoms
+                .into_iter()
+                .map(|maybe_geom| {
+                    maybe_geom.map(|geom| match geom {
+                        geo::Geometry::$variant(geom) => geom,
+                        _ => unreachable!(),
+                    })
+                })
+                .collect();
+            GeometryArray::$variant(typed.into())
+        }};
+    }
+
+    match kind {
+        GeometryArrayType::Point => collect_variant!(Point),
+        GeometryArrayType::LineString => collect_variant!(LineString),
+        GeometryArrayType::Polygon => collect_variant!(Polygon),
+        GeometryArrayType::MultiPoint => collect_variant!(MultiPoint),
+        GeometryArrayType::MultiLineString => collect_variant!(MultiLineString),
+        GeometryArrayType::MultiPolygon => collect_variant!(MultiPolygon),
+        GeometryArrayType::Rect => collect_variant!(Rect),
+        // Only reached when every geometry is null
+        GeometryArrayType::Mixed => GeometryArray::Mixed(Box::new(geoms.try_into().unwrap())),
+        // Geometry collections are only held by WKB here
+        GeometryArrayType::GeometryCollection | GeometryArrayType::WKB => {
+            GeometryArray::WKB(geoms.into())
+        }
+    }
+}
+
+/// Build the narrowest array able to hold `geoms`, reading single and multi geometries of the
+/// same dimension as multi geometries. Any other mix of geometry types is built as WKB.
+pub(crate) fn from_geo_promoted(geoms: Vec<Option<geo::Geometry>>) -> GeometryArray {
+    let kind = GeometryArrayType::common(geoms.iter().flatten().map(GeometryArrayType::of_geo));
+    let geoms = geoms
+        .into_iter()
+        .map(|maybe_g| maybe_g.map(|geom| promote_geometry(geom, kind)))
+        .collect();
+    from_geo(geoms, kind)
+}
+
+/// Convert every one of `chunks` to the narrowest type holding the geometries of all of them,
+/// also returning that type.
+///
+/// Chunks of only null geometries have no geometry type of their own, and are built again with
+/// that type. When every geometry is null, the type is `fallback`.
+#[cfg(any(feature = "csv", feature = "postgis", feature = "serde"))]
+pub(crate) fn coerce_chunks(
+    chunks: Vec<GeometryArray>,
+    fallback: GeometryArrayType,
+) -> Result<(GeometryArrayType, Vec<GeometryArray>), GeoArrowError> {
+    let all_null = |chunk: &GeometryArray| chunk.null_count() == chunk.len();
+    let kind = if chunks.iter().all(all_null) {
+        fallback
+    } else {
+        GeometryArrayType::common(
+            chunks
+                .iter()
+                .filter(|chunk| !all_null(chunk))
+                .map(|chunk| chunk.geometry_type()),
+        )
+    };
+    let chunks = chunks
+        .into_iter()
+        .map(|chunk| {
+            if all_null(&chunk) {
+                Ok(from_geo(vec![None; chunk.len()], kind).with_crs(chunk.crs()))
+            } else {
+                coerce(chunk, kind)
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((kind, chunks))
+}
+
+/// `array` as WKB.
+pub(crate) fn to_wkb(array: GeometryArray) -> GeometryArray {
+    GeometryArray::WKB(array.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LineStringArray, PointArray};
+    use geo::{line_string, point};
+
+    fn points() -> GeometryArray {
+        let arr: PointArray = vec![Some(point!(x: 0., y: 0.)), None].into();
+        GeometryArray::Point(arr)
+    }
+
+    #[test]
+    fn coerce_kinds() {
+        let output = coerce(points(), GeometryArrayType::MultiPoint).unwrap();
+        assert!(matches!(output, GeometryArray::MultiPoint(_)));
+        let output = coerce(points(), GeometryArrayType::WKB).unwrap();
+        assert!(matches!(output, GeometryArray::WKB(_)));
+
+        let lines: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 1., y: 1.)]].into();
+        let output = coerce(
+            GeometryArray::LineString(lines),
+            GeometryArrayType::MultiLineString,
+        );
+        assert!(matches!(output, Ok(GeometryArray::MultiLineString(_))));
+
+        // Line strings cannot hold points
+        assert!(coerce(points(), GeometryArrayType::LineString).is_err());
+    }
+
+    #[test]
+    #[cfg(any(feature = "csv", feature = "postgis", feature = "serde"))]
+    fn coerce_chunk_kinds() {
+        let lines: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 1., y: 1.)]].into();
+        let nulls: LineStringArray = vec![None::<geo::LineString>].into();
+        let (kind, chunks) = coerce_chunks(
+            vec![
+                points(),
+                GeometryArray::MultiPoint(PointArray::from(vec![point!(x: 2., y: 2.)]).into()),
+                GeometryArray::LineString(nulls),
+            ],
+            GeometryArrayType::WKB,
+        )
+        .unwrap();
+        assert_eq!(kind, GeometryArrayType::MultiPoint);
+        assert!(chunks
+            .iter()
+            .all(|chunk| matches!(chunk, GeometryArray::MultiPoint(_))));
+        assert!(chunks[2].is_null(0));
+
+        let (kind, chunks) = coerce_chunks(
+            vec![points(), GeometryArray::LineString(lines)],
+            GeometryArrayType::WKB,
+        )
+        .unwrap();
+        assert_eq!(kind, GeometryArrayType::WKB);
+        assert!(matches!(chunks[0], GeometryArray::WKB(_)));
+
+        let (kind, _) = coerce_chunks(vec![], GeometryArrayType::Point).unwrap();
+        assert_eq!(kind, GeometryArrayType::Point);
+    }
+}
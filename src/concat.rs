@@ -0,0 +1,113 @@
+//! Helpers for appending to mutable arrays, shared by the push methods and the `extend_from_array`
+//! and `concat` implementations of each geometry type.
+
+use crate::crs::check_crs;
+use crate::error::GeoArrowError;
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::offset::{Offsets, OffsetsBuffer};
+use arrow2::types::Index;
+
+/// Append the validity of an array of `other_len` values to the validity of a mutable array of
+/// `len` values, where `None` means all values are valid.
+pub(crate) fn extend_validity(
+    validity: &mut Option<MutableBitmap>,
+    len: usize,
+    other: Option<&Bitmap>,
+    other_len: usize,
+) {
+    match (validity.as_mut(), other) {
+        (Some(validity), Some(other)) => validity.extend_from_bitmap(other),
+        (Some(validity), None) => validity.extend_constant(other_len, true),
+        (None, Some(other)) => {
+            let mut bitmap = MutableBitmap::with_capacity(len + other_len);
+            bitmap.extend_constant(len, true);
+            bitmap.extend_from_bitmap(other);
+            *validity = Some(bitmap);
+        }
+        (None, None) => {}
+    }
+}
+
+/// Append a value that is valid if `is_valid` to the validity of a mutable array of `len` values,
+/// where `None` means all values are valid.
+pub(crate) fn push_validity(validity: &mut Option<MutableBitmap>, len: usize, is_valid: bool) {
+    match validity.as_mut() {
+        Some(validity) => validity.push(is_valid),
+        None if !is_valid => {
+            let mut bitmap = MutableBitmap::with_capacity(len + 1);
+            bitmap.extend_constant(len, true);
+            bitmap.push(false);
+            *validity = Some(bitmap);
+        }
+        None => {}
+    }
+}
+
+/// Close the current element of `offsets`, whose children end at `end`.
+pub(crate) fn try_push_end(offsets: &mut Offsets<i64>, end: usize) -> Result<(), GeoArrowError> {
+    let length = end
+        .checked_sub(offsets.last().to_usize())
+        .ok_or(GeoArrowError::Overflow)?;
+    offsets
+        .try_push_usize(length)
+        .map_err(|_| GeoArrowError::OffsetOverflow {
+            row: offsets.len_proxy(),
+        })
+}
+
+/// Append `length` elements of `other`, starting at element `start`, to `offsets`, returning the
+/// range they span in the child array of `other`.
+pub(crate) fn extend_offsets(
+    offsets: &mut Offsets<i64>,
+    other: &OffsetsBuffer<i64>,
+    start: usize,
+    length: usize,
+) -> Result<(usize, usize), GeoArrowError> {
+    offsets
+        .try_extend_from_slice(other, start, length)
+        .map_err(|_| GeoArrowError::OffsetOverflow {
+            row: offsets.len_proxy(),
+        })?;
+    let buffer = other.buffer();
+    Ok((buffer[start].to_usize(), buffer[start + length].to_usize()))
+}
+
+/// The CRS of the concatenation of arrays with the given CRSes.
+/// # Errors
+/// Errors if two known CRSes differ.
+pub(crate) fn concat_crs<'a>(
+    crses: impl IntoIterator<Item = Option<&'a str>>,
+) -> Result<Option<&'a str>, GeoArrowError> {
+    crses
+        .into_iter()
+        .try_fold(None, |crs, other| check_crs(crs, other))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validity() {
+        let mut validity = None;
+        extend_validity(&mut validity, 0, None, 2);
+        assert!(validity.is_none());
+
+        let nulls = Bitmap::from([true, false]);
+        extend_validity(&mut validity, 2, Some(&nulls), 2);
+        extend_validity(&mut validity, 4, None, 1);
+        let validity: Bitmap = validity.unwrap().into();
+        assert_eq!(
+            validity.iter().collect::<Vec<_>>(),
+            vec![true, true, true, false, true]
+        );
+    }
+
+    #[test]
+    fn sliced_offsets() {
+        let other: OffsetsBuffer<i64> = vec![0, 2, 5, 9].try_into().unwrap();
+        let mut offsets = Offsets::<i64>::new();
+        assert_eq!(extend_offsets(&mut offsets, &other, 1, 2).unwrap(), (2, 9));
+        assert_eq!(offsets.as_slice(), &[0, 3, 7]);
+    }
+}
@@ -0,0 +1,222 @@
+//! Flat access to the coordinates of geometry arrays.
+//!
+//! Native arrays store the coordinates of all their geometries contiguously, in order. Each one
+//! maps a range of geometries to the range of its coordinate buffers holding their coordinates,
+//! from which [`impl_coords`] implements `coords` and `num_coords`, along with the accessors of
+//! the coordinate buffers themselves.
+//!
+//! Native arrays may also store an m value (measure) per coordinate, held in a third `m` field of
+//! their coordinate structs.
+
+use crate::error::GeoArrowError;
+use arrow2::array::{Array, PrimitiveArray, StructArray};
+use arrow2::buffer::Buffer;
+use arrow2::datatypes::{DataType, Field};
+use arrow2::offset::OffsetsBuffer;
+use std::ops::Range;
+
+/// The range of the values of `offsets` holding the values of the lists `lists`.
+pub(crate) fn values_range(offsets: &OffsetsBuffer<i64>, lists: Range<usize>) -> Range<usize> {
+    let buffer = offsets.buffer();
+    buffer[lists.start] as usize..buffer[lists.end] as usize
+}
+
+/// The fields and child arrays of a coordinate struct array holding `x`, `y` and optionally `m`.
+pub(crate) fn coord_fields(
+    x: Buffer<f64>,
+    y: Buffer<f64>,
+    m: Option<Buffer<f64>>,
+) -> (Vec<Field>, Vec<Box<dyn Array>>) {
+    [("x", Some(x)), ("y", Some(y)), ("m", m)]
+        .into_iter()
+        .filter_map(|(name, values)| {
+            let values = values?;
+            Some((
+                Field::new(name, DataType::Float64, false),
+                PrimitiveArray::new(DataType::Float64, values, None).boxed(),
+            ))
+        })
+        .unzip()
+}
+
+/// Whether the coordinate struct array `coords` has an `m` field after its `x` and `y` fields.
+/// # Errors
+/// Errors if `coords` has a `z` field, or any other number of fields.
+pub(crate) fn coords_have_m(coords: &StructArray) -> Result<bool, GeoArrowError> {
+    let fields = StructArray::get_fields(coords.data_type());
+    match fields.len() {
+        2 => Ok(false),
+        3 if fields[2].name == "m" => Ok(true),
+        3 | 4 => Err(GeoArrowError::UnsupportedDimension {
+            dimension: fields.len(),
+        }),
+        _ => Err(GeoArrowError::General(
+            "Expected x and y child arrays, and optionally m, in this StructArray.".to_string(),
+        )),
+    }
+}
+
+/// The concatenated m values of several arrays, or `None` if there are none or any array has
+/// none.
+pub(crate) fn concat_m<'a>(values: impl Iterator<Item = Option<&'a [f64]>>) -> Option<Buffer<f64>> {
+    let values = values.collect::<Option<Vec<_>>>()?;
+    (!values.is_empty()).then(|| values.concat().into())
+}
+
+/// Implement `coords` and `num_coords` for a native array with a `coord_range` method, mapping
+/// a range of geometries to the range of its coordinate buffers holding their coordinates.
+macro_rules! impl_coords {
+    ($array:ty) => {
+        impl $array {
+            /// The x coordinate [`Buffer`](arrow2::buffer::Buffer), shared by all geometries in
+            /// the array. Values on null slots are undetermined (they can be anything).
+            #[inline]
+            pub fn values_x(&self) -> &arrow2::buffer::Buffer<f64> {
+                &self.x
+            }
+
+            /// The y coordinate [`Buffer`](arrow2::buffer::Buffer), shared by all geometries in
+            /// the array. Values on null slots are undetermined (they can be anything).
+            #[inline]
+            pub fn values_y(&self) -> &arrow2::buffer::Buffer<f64> {
+                &self.y
+            }
+
+            /// The m value [`Buffer`](arrow2::buffer::Buffer), shared by all geometries in the
+            /// array, if the array has m values.
+            #[inline]
+            pub fn values_m(&self) -> Option<&arrow2::buffer::Buffer<f64>> {
+                self.m.as_ref()
+            }
+
+            /// This array with the m values (measures) `m`, one per coordinate.
+            /// # Errors
+            /// Errors if `m` does not have one value per coordinate.
+            pub fn with_m(
+                self,
+                m: arrow2::buffer::Buffer<f64>,
+            ) -> Result<Self, $crate::error::GeoArrowError> {
+                if m.len() != self.x.len() {
+                    return Err($crate::error::GeoArrowError::General(
+                        "m array must have the same length as the x and y arrays".to_string(),
+                    ));
+                }
+                Ok(Self { m: Some(m), ..self })
+            }
+
+            /// This array with the m values `m`, if any, which must already have one value per
+            /// coordinate: those of an array sharing its coordinates.
+            pub(crate) fn with_optional_m(self, m: Option<arrow2::buffer::Buffer<f64>>) -> Self {
+                debug_assert!(m.as_ref().map_or(true, |m| m.len() == self.x.len()));
+                Self { m, ..self }
+            }
+
+            /// A copy of this array with its coordinate buffers replaced, keeping everything
+            /// else. The new buffers must have the same length as the existing ones.
+            pub(crate) fn with_coords(
+                &self,
+                x: arrow2::buffer::Buffer<f64>,
+                y: arrow2::buffer::Buffer<f64>,
+            ) -> Self {
+                debug_assert_eq!(x.len(), self.x.len());
+                debug_assert_eq!(y.len(), self.y.len());
+                Self {
+                    x,
+                    y,
+                    ..self.clone()
+                }
+            }
+
+            /// Iterate over the coordinates of every non-null geometry, in order.
+            pub fn coords(&self) -> impl Iterator<Item = geo::Coord> + '_ {
+                let (x, y) = (self.values_x(), self.values_y());
+                self.coord_ranges()
+                    .flatten()
+                    .map(move |i| geo::coord! { x: x[i], y: y[i] })
+            }
+
+            /// The number of coordinates of every non-null geometry.
+            pub fn num_coords(&self) -> usize {
+                self.coord_ranges().map(|range| range.len()).sum()
+            }
+
+            /// The ranges of the coordinate buffers holding the coordinates of non-null
+            /// geometries.
+            fn coord_ranges(&self) -> impl Iterator<Item = std::ops::Range<usize>> + '_ {
+                // Without nulls, the coordinates of all geometries are contiguous
+                let validity = self.validity();
+                let all = validity.is_none().then(|| self.coord_range(0..self.len()));
+                let valid = validity.map(|validity| {
+                    validity
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, is_valid)| *is_valid)
+                        .map(|(i, _)| self.coord_range(i..i + 1))
+                });
+                all.into_iter().chain(valid.into_iter().flatten())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_coords;
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiPolygonArray, PointArray,
+        PolygonArray,
+    };
+    use geo::{coord, line_string, point, polygon, Coord, MultiPolygon};
+
+    fn square(x: f64) -> geo::Polygon {
+        polygon![(x: x, y: 0.), (x: x + 1., y: 0.), (x: x + 1., y: 1.), (x: x, y: 0.)]
+    }
+
+    #[test]
+    fn native() {
+        let points: PointArray =
+            vec![Some(point!(x: 0., y: 1.)), None, Some(point!(x: 2., y: 3.))].into();
+        assert_eq!(points.num_coords(), 2);
+        assert_eq!(
+            points.coords().collect::<Vec<_>>(),
+            vec![coord! { x: 0., y: 1. }, coord! { x: 2., y: 3. }]
+        );
+
+        let lines: LineStringArray = vec![
+            line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+            line_string![(x: 2., y: 2.), (x: 3., y: 3.), (x: 4., y: 4.)],
+        ]
+        .into();
+        assert_eq!(lines.num_coords(), 5);
+        let sliced = lines.sliced_range(1..2);
+        assert_eq!(sliced.num_coords(), 3);
+        assert_eq!(sliced.coords().next(), Some(coord! { x: 2., y: 2. }));
+
+        let polygons: PolygonArray = vec![Some(square(0.)), None, Some(square(10.))].into();
+        assert_eq!(polygons.num_coords(), 8);
+        assert_eq!(
+            polygons.sliced_range(2..3).coords().next(),
+            Some(coord! { x: 10., y: 0. })
+        );
+
+        let multi: MultiPolygonArray = vec![MultiPolygon::new(vec![square(0.), square(2.)])].into();
+        assert_eq!(multi.num_coords(), 8);
+        assert_eq!(multi.sliced_range(1..1).num_coords(), 0);
+    }
+
+    #[test]
+    fn mixed() {
+        let mixed: MixedGeometryArray = vec![
+            Some(geo::Geometry::Polygon(square(0.))),
+            None,
+            Some(geo::Geometry::Point(point!(x: 5., y: 6.))),
+        ]
+        .try_into()
+        .unwrap();
+        let coords: Vec<Coord> = mixed.coords().collect();
+        assert_eq!(mixed.num_coords(), 5);
+        assert_eq!(coords[0], coord! { x: 0., y: 0. });
+        assert_eq!(coords[4], coord! { x: 5., y: 6. });
+    }
+}
@@ -0,0 +1,131 @@
+//! Coordinate reference systems attached to geometry arrays.
+//!
+//! Every geometry array carries an optional CRS, as a PROJJSON string or an `AUTHORITY:CODE`
+//! string such as `"EPSG:4326"`. It is kept when slicing and when converting between array types,
+//! and is written to the `ARROW:extension:metadata` of fields created by
+//! [`GeometryArray::into_arrow_field`](crate::GeometryArray::into_arrow_field).
+
+use crate::error::GeoArrowError;
+use serde_json::Value;
+
+/// Implement `crs` and `with_crs` for an array storing its CRS as `Option<Arc<str>>` in `$field`.
+macro_rules! impl_crs {
+    ($array:ty) => {
+        $crate::crs::impl_crs!($array, crs);
+    };
+    ($array:ty, $field:tt) => {
+        impl $array {
+            /// The coordinate reference system of this array, as a PROJJSON or
+            /// `AUTHORITY:CODE` string, if known.
+            pub fn crs(&self) -> Option<&str> {
+                self.$field.as_deref()
+            }
+
+            /// This array with its coordinate reference system set to `crs`.
+            ///
+            /// The coordinates are not transformed.
+            pub fn with_crs(mut self, crs: Option<&str>) -> Self {
+                self.$field = crs.map(std::sync::Arc::from);
+                self
+            }
+        }
+    };
+}
+
+pub(crate) use impl_crs;
+
+/// The CRS of the result of combining arrays in `left` and `right`.
+///
+/// An array without a CRS is assumed to be in the CRS of the other. CRS strings are compared
+/// exactly, so the same CRS written differently (e.g. as PROJJSON and as an EPSG code) is reported
+/// as a mismatch.
+/// # Errors
+/// Errors with [`GeoArrowError::CrsMismatch`] if both CRSes are known and differ.
+pub(crate) fn check_crs<'a>(
+    left: Option<&'a str>,
+    right: Option<&'a str>,
+) -> Result<Option<&'a str>, GeoArrowError> {
+    match (left, right) {
+        (Some(left), Some(right)) if left.trim() != right.trim() => {
+            Err(GeoArrowError::CrsMismatch {
+                left: left.to_string(),
+                right: right.to_string(),
+            })
+        }
+        (Some(crs), _) | (None, Some(crs)) => Ok(Some(crs)),
+        (None, None) => Ok(None),
+    }
+}
+
+/// `crs` as a JSON value: a PROJJSON object is embedded as an object, and any other CRS (e.g.
+/// `"EPSG:4326"` or WKT) as a string.
+pub(crate) fn crs_to_json(crs: &str) -> Value {
+    serde_json::from_str::<Value>(crs)
+        .ok()
+        .filter(Value::is_object)
+        .unwrap_or_else(|| Value::String(crs.to_string()))
+}
+
+/// The CRS held by a JSON value, the inverse of [`crs_to_json`]: a string is returned as is, and
+/// a PROJJSON object serialized. `null` is no CRS.
+pub(crate) fn crs_from_json(crs: &Value) -> Option<String> {
+    match crs {
+        Value::Null => None,
+        Value::String(crs) => Some(crs.clone()),
+        crs => Some(crs.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArrayTrait, PointArray};
+    use geo::point;
+
+    #[test]
+    fn check() {
+        assert_eq!(check_crs(None, None).unwrap(), None);
+        assert_eq!(
+            check_crs(Some("EPSG:4326"), None).unwrap(),
+            Some("EPSG:4326")
+        );
+        assert_eq!(
+            check_crs(None, Some("EPSG:4326")).unwrap(),
+            Some("EPSG:4326")
+        );
+        assert!(matches!(
+            check_crs(Some("EPSG:4326"), Some("EPSG:3857")),
+            Err(GeoArrowError::CrsMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(crs_to_json("EPSG:4326"), Value::String("EPSG:4326".into()));
+        let projjson = crs_to_json(r#"{"type": "GeographicCRS", "name": "WGS 84"}"#);
+        assert!(projjson.is_object());
+        assert_eq!(
+            crs_from_json(&projjson).as_deref(),
+            Some(r#"{"name":"WGS 84","type":"GeographicCRS"}"#)
+        );
+        assert_eq!(
+            crs_from_json(&crs_to_json("\"quoted\"")).as_deref(),
+            Some("\"quoted\"")
+        );
+        assert_eq!(crs_from_json(&Value::Null), None);
+    }
+
+    #[test]
+    fn propagate() {
+        let points: PointArray = vec![point!(x: 0., y: 1.), point!(x: 2., y: 3.)].into();
+        let points = points.with_crs(Some("EPSG:4326"));
+        assert_eq!(points.sliced_range(1..2).crs(), Some("EPSG:4326"));
+
+        let multi_points: crate::MultiPointArray = points.clone().into();
+        assert_eq!(multi_points.crs(), Some("EPSG:4326"));
+        let wkb: crate::WKBArray = points.into();
+        assert_eq!(wkb.crs(), Some("EPSG:4326"));
+        let points = PointArray::try_from(wkb).unwrap();
+        assert_eq!(points.crs(), Some("EPSG:4326"));
+    }
+}
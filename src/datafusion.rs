@@ -0,0 +1,646 @@
+//! [DataFusion](https://arrow.apache.org/datafusion/) support: SQL functions on geometry columns,
+//! and tables to run them on.
+//!
+//! [`register_udfs`] registers the functions below on a [`SessionContext`]. Their arguments are
+//! geometry columns in any GeoArrow layout, or WKB. DataFusion does not pass field metadata to
+//! functions, so geometry types are inferred from data types as in [`GeometryArray::from_arrow`],
+//! reading list layouts as single geometries, and CRSes are not checked. Arguments of one row,
+//! such as constants, are broadcast to the rows of the others.
+//!
+//! | Function | Returns |
+//! | --- | --- |
+//! | `st_area(geom)` | The unsigned planar area of each geometry |
+//! | `st_centroid(geom)` | The centroid of each geometry, as a point |
+//! | `st_intersects(a, b)` | Whether `a` intersects `b` |
+//! | `st_contains(a, b)` | Whether `a` contains `b` |
+//! | `st_within(a, b)` | Whether `a` is within `b` |
+//! | `st_buffer(geom, width)` | The buffer of each point, line string or polygon, as a multi polygon (with the `geos` feature) |
+//! | `st_makeenvelope(minx, miny, maxx, maxy)` | The rectangle with these bounds, as a polygon |
+//!
+//! With the `parquet` feature, [`GeoParquetTable`] exposes a GeoParquet file as a table, and
+//! pushes `st_intersects` filters against constant geometries down to its row groups. Other
+//! tables can be queried with [`to_mem_table`].
+
+#[cfg(feature = "parquet")]
+use crate::algorithm::bounds::TotalBounds;
+use crate::algorithm::geo::area::Area;
+use crate::algorithm::geo::{contains, intersects, within};
+#[cfg(feature = "geos")]
+use crate::algorithm::geos::buffer::Buffer;
+use crate::algorithm::take::Take;
+use crate::error::GeoArrowError;
+#[cfg(feature = "parquet")]
+use crate::io::parquet::{read_geoparquet, GeoParquetReader};
+#[cfg(feature = "parquet")]
+use crate::io::{GeoTableReader, ReaderOptions};
+use crate::table::GeoTable;
+#[cfg(feature = "geos")]
+use crate::MultiPolygonArray;
+use crate::{GeometryArray, GeometryArrayTrait, PointArray, PolygonArray};
+use arrow2::array::{from_data, to_data, Array, PrimitiveArray};
+use arrow2::datatypes::DataType;
+#[cfg(feature = "parquet")]
+use arrow2::datatypes::Schema;
+#[cfg(feature = "parquet")]
+use async_trait::async_trait;
+use datafusion::arrow::array::make_array;
+#[cfg(feature = "parquet")]
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::datatypes::{
+    DataType as ArrowDataType, Field as ArrowField, Schema as ArrowSchema,
+};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::datasource::MemTable;
+#[cfg(feature = "parquet")]
+use datafusion::datasource::{TableProvider, TableType};
+use datafusion::error::{DataFusionError, Result as DataFusionResult};
+#[cfg(feature = "parquet")]
+use datafusion::execution::context::SessionState;
+#[cfg(feature = "parquet")]
+use datafusion::logical_expr::expr::ScalarUDF as ScalarUDFExpr;
+use datafusion::logical_expr::{
+    ColumnarValue, ReturnTypeFunction, ScalarFunctionImplementation, ScalarUDF, Signature,
+    Volatility,
+};
+#[cfg(feature = "parquet")]
+use datafusion::logical_expr::{Expr, TableProviderFilterPushDown};
+#[cfg(feature = "parquet")]
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::prelude::SessionContext;
+#[cfg(feature = "parquet")]
+use datafusion::scalar::ScalarValue;
+use geo::Centroid;
+#[cfg(feature = "parquet")]
+use std::any::Any;
+#[cfg(feature = "parquet")]
+use std::fs::File;
+#[cfg(feature = "parquet")]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Whether arrays of `data_type` can be read as geometries.
+fn is_geometry_type(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::FixedSizeBinary(_)
+        | DataType::Union(..)
+        | DataType::Struct(_) => true,
+        _ => {
+            // Up to three levels of lists of coordinates
+            let mut inner = data_type;
+            for _ in 0..3 {
+                match inner {
+                    DataType::List(field) | DataType::LargeList(field) => inner = field.data_type(),
+                    DataType::Struct(_) => return true,
+                    _ => return false,
+                }
+            }
+            matches!(inner, DataType::Struct(_))
+        }
+    }
+}
+
+/// The number of rows of a call with `args`: the longest of the array arguments, or 1 if all
+/// are scalars.
+fn num_rows(args: &[ColumnarValue]) -> usize {
+    args.iter()
+        .filter_map(|arg| match arg {
+            ColumnarValue::Array(array) => Some(array.len()),
+            ColumnarValue::Scalar(_) => None,
+        })
+        .max()
+        .unwrap_or(1)
+}
+
+/// The geometries of the argument `arg`, as `num_rows` rows. An array of one row is repeated
+/// `num_rows` times.
+fn geometry_arg(arg: &ColumnarValue, num_rows: usize) -> DataFusionResult<GeometryArray> {
+    let array = from_data(&arg.clone().into_array(num_rows).to_data());
+    if !is_geometry_type(array.data_type()) {
+        return Err(DataFusionError::Execution(format!(
+            "expected a geometry argument, found {:?}",
+            array.data_type()
+        )));
+    }
+    let geometry = GeometryArray::try_from_arrow(array.as_ref(), false)
+        .map_err(|err| DataFusionError::External(Box::new(err)))?;
+    if geometry.len() == 1 && num_rows != 1 {
+        return geometry
+            .take(&PrimitiveArray::from_vec(vec![0; num_rows]))
+            .map_err(|err| DataFusionError::External(Box::new(err)));
+    }
+    Ok(geometry)
+}
+
+/// The numbers of the argument `arg`, as `num_rows` rows of `f64`.
+fn float_arg(arg: &ColumnarValue, num_rows: usize) -> DataFusionResult<PrimitiveArray<f64>> {
+    let array = from_data(&arg.clone().into_array(num_rows).to_data());
+    let array = arrow2::compute::cast::cast(array.as_ref(), &DataType::Float64, Default::default())
+        .map_err(|err| DataFusionError::Execution(format!("expected a numeric argument: {err}")))?;
+    array
+        .as_any()
+        .downcast_ref::<PrimitiveArray<f64>>()
+        .cloned()
+        .ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "expected a numeric argument, found {:?}",
+                array.data_type()
+            ))
+        })
+}
+
+/// A function of `num_args` geometry arguments, returning arrays of `return_type`.
+fn geometry_udf<F>(name: &str, num_args: usize, return_type: ArrowDataType, f: F) -> ScalarUDF
+where
+    F: Fn(&[GeometryArray]) -> Result<Box<dyn Array>, GeoArrowError> + Send + Sync + 'static,
+{
+    let return_type: ReturnTypeFunction = Arc::new(move |_| Ok(Arc::new(return_type.clone())));
+    let fun: ScalarFunctionImplementation = Arc::new(move |args: &[ColumnarValue]| {
+        let num_rows = num_rows(args);
+        let geometries = args
+            .iter()
+            .map(|arg| geometry_arg(arg, num_rows))
+            .collect::<DataFusionResult<Vec<_>>>()?;
+        let output = f(&geometries).map_err(|err| DataFusionError::External(Box::new(err)))?;
+        Ok(ColumnarValue::Array(make_array(to_data(output.as_ref()))))
+    });
+    ScalarUDF::new(
+        name,
+        &Signature::any(num_args, Volatility::Immutable),
+        &return_type,
+        &fun,
+    )
+}
+
+/// `st_area(geom)`: the unsigned planar area of each geometry.
+pub fn st_area() -> ScalarUDF {
+    geometry_udf("st_area", 1, ArrowDataType::Float64, |args| {
+        Ok(args[0].unsigned_area().boxed())
+    })
+}
+
+/// `st_centroid(geom)`: the centroid of each geometry, null for empty geometries.
+pub fn st_centroid() -> ScalarUDF {
+    let empty: PointArray = Vec::<geo::Point>::new().into();
+    let return_type = ArrowDataType::from(empty.into_arrow().data_type().clone());
+    geometry_udf("st_centroid", 1, return_type, |args| {
+        let centroids: PointArray = args[0]
+            .iter_geo()
+            .map(|maybe_geom| maybe_geom.and_then(|geom| geom.centroid()))
+            .collect::<Vec<_>>()
+            .into();
+        Ok(centroids.into_arrow().boxed())
+    })
+}
+
+/// `st_intersects(a, b)`: whether each geometry of `a` intersects the geometry in the same row of
+/// `b`.
+pub fn st_intersects() -> ScalarUDF {
+    geometry_udf("st_intersects", 2, ArrowDataType::Boolean, |args| {
+        Ok(intersects(&args[0], &args[1])?.boxed())
+    })
+}
+
+/// `st_contains(a, b)`: whether each geometry of `a` contains the geometry in the same row of
+/// `b`.
+pub fn st_contains() -> ScalarUDF {
+    geometry_udf("st_contains", 2, ArrowDataType::Boolean, |args| {
+        Ok(contains(&args[0], &args[1])?.boxed())
+    })
+}
+
+/// `st_within(a, b)`: whether each geometry of `a` is within the geometry in the same row of `b`.
+pub fn st_within() -> ScalarUDF {
+    geometry_udf("st_within", 2, ArrowDataType::Boolean, |args| {
+        Ok(within(&args[0], &args[1])?.boxed())
+    })
+}
+
+/// The number of segments approximating a quarter circle in `st_buffer`, as in PostGIS.
+#[cfg(feature = "geos")]
+const BUFFER_QUADSEGS: i32 = 8;
+
+/// `st_buffer(geom, width)`: the buffer of each geometry of `geom` by the width in the same row
+/// of `width`, computed with GEOS. Points, line strings and polygons are supported, and buffers
+/// are returned as multi polygons.
+#[cfg(feature = "geos")]
+pub fn st_buffer() -> ScalarUDF {
+    let empty: MultiPolygonArray = Vec::<geo::MultiPolygon>::new().into();
+    let return_type = ArrowDataType::from(empty.into_arrow().data_type().clone());
+    let return_type: ReturnTypeFunction = Arc::new(move |_| Ok(Arc::new(return_type.clone())));
+    let fun: ScalarFunctionImplementation = Arc::new(move |args: &[ColumnarValue]| {
+        let num_rows = num_rows(args);
+        let geometry = geometry_arg(&args[0], num_rows)?;
+        let widths = float_arg(&args[1], num_rows)?;
+        let buffered: MultiPolygonArray = match &geometry {
+            GeometryArray::Point(arr) => {
+                arr.buffer_per_row(&widths, BUFFER_QUADSEGS).map(Into::into)
+            }
+            GeometryArray::LineString(arr) => {
+                arr.buffer_per_row(&widths, BUFFER_QUADSEGS).map(Into::into)
+            }
+            GeometryArray::Polygon(arr) => arr.buffer_per_row(&widths, BUFFER_QUADSEGS),
+            geometry => Err(GeoArrowError::NotYetImplemented(format!(
+                "st_buffer of {:?} arrays",
+                geometry.geometry_type()
+            ))),
+        }
+        .map_err(|err| DataFusionError::External(Box::new(err)))?;
+        Ok(ColumnarValue::Array(make_array(to_data(
+            buffered.into_arrow().boxed().as_ref(),
+        ))))
+    });
+    ScalarUDF::new(
+        "st_buffer",
+        &Signature::any(2, Volatility::Immutable),
+        &return_type,
+        &fun,
+    )
+}
+
+/// `st_makeenvelope(minx, miny, maxx, maxy)`: the rectangle with the bounds in each row, as a
+/// polygon, or null if any bound is null.
+pub fn st_makeenvelope() -> ScalarUDF {
+    let empty: PolygonArray = Vec::<geo::Polygon>::new().into();
+    let return_type = ArrowDataType::from(empty.into_arrow().data_type().clone());
+    let return_type: ReturnTypeFunction = Arc::new(move |_| Ok(Arc::new(return_type.clone())));
+    let fun: ScalarFunctionImplementation = Arc::new(move |args: &[ColumnarValue]| {
+        let num_rows = num_rows(args);
+        let bounds = args
+            .iter()
+            .map(|arg| float_arg(arg, num_rows))
+            .collect::<DataFusionResult<Vec<_>>>()?;
+        let envelopes: PolygonArray = (0..num_rows)
+            .map(|i| {
+                let [minx, miny, maxx, maxy] = [0, 1, 2, 3].map(|j| bounds[j].get(i));
+                Some(geo::Rect::new((minx?, miny?), (maxx?, maxy?)).to_polygon())
+            })
+            .collect::<Vec<_>>()
+            .into();
+        Ok(ColumnarValue::Array(make_array(to_data(
+            envelopes.into_arrow().boxed().as_ref(),
+        ))))
+    });
+    ScalarUDF::new(
+        "st_makeenvelope",
+        &Signature::any(4, Volatility::Immutable),
+        &return_type,
+        &fun,
+    )
+}
+
+/// Register every function of this module on `ctx`.
+pub fn register_udfs(ctx: &SessionContext) {
+    ctx.register_udf(st_area());
+    ctx.register_udf(st_centroid());
+    ctx.register_udf(st_intersects());
+    ctx.register_udf(st_contains());
+    ctx.register_udf(st_within());
+    #[cfg(feature = "geos")]
+    ctx.register_udf(st_buffer());
+    ctx.register_udf(st_makeenvelope());
+}
+
+/// The DataFusion schema of a table with the arrow2 schema `schema`.
+fn arrow_schema(schema: &arrow2::datatypes::Schema) -> ArrowSchema {
+    ArrowSchema::new(
+        schema
+            .fields
+            .iter()
+            .cloned()
+            .map(ArrowField::from)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// A DataFusion table holding the chunks of `table`, one record batch each.
+///
+/// Register it with `SessionContext::register_table` to query it with SQL. Geometry columns keep
+/// their GeoArrow layout, and their extension name and CRS in the field metadata. To query part
+/// of a GeoParquet file, read it with a bbox and column selection first, so that only the row
+/// groups and columns needed are decoded.
+pub fn to_mem_table(table: &GeoTable) -> Result<MemTable, GeoArrowError> {
+    let schema = Arc::new(arrow_schema(table.schema()));
+    let batches = table
+        .chunks()
+        .iter()
+        .map(|chunk| {
+            let columns = chunk
+                .arrays()
+                .iter()
+                .map(|array| make_array(to_data(array.as_ref())))
+                .collect();
+            RecordBatch::try_new(schema.clone(), columns)
+                .map_err(|err| GeoArrowError::External(err.into()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    MemTable::try_new(schema, vec![batches]).map_err(|err| GeoArrowError::External(err.into()))
+}
+
+/// A GeoParquet file as a DataFusion table.
+///
+/// Each scan reads the file with [`read_geoparquet`], decoding only the columns the query uses.
+/// Filters `st_intersects(geom, other)` and `st_intersects(other, geom)`, where `geom` is the
+/// primary geometry column and `other` a constant geometry such as an `st_makeenvelope` of
+/// literals, are pushed down as the bbox of `other`: row groups whose bbox covering statistics
+/// show they cannot intersect it are never read. The rows of the row groups read are still
+/// filtered by DataFusion.
+#[cfg(feature = "parquet")]
+#[derive(Debug, Clone)]
+pub struct GeoParquetTable {
+    path: PathBuf,
+    schema: Schema,
+    geometry_column: String,
+}
+
+#[cfg(feature = "parquet")]
+impl GeoParquetTable {
+    /// Open the GeoParquet file at `path`, reading only its footer.
+    pub fn try_new(path: impl Into<PathBuf>) -> Result<Self, GeoArrowError> {
+        let path = path.into();
+        let reader =
+            GeoParquetReader::try_new(File::open(&path)?, &ReaderOptions::default(), None)?;
+        Ok(Self {
+            schema: reader.schema().unwrap().clone(),
+            geometry_column: reader.geometry_column().to_string(),
+            path,
+        })
+    }
+
+    /// The bbox that `filter` restricts the primary geometry column to, if it is an
+    /// `st_intersects` of that column and a constant geometry.
+    fn filter_bbox(&self, filter: &Expr) -> Option<[f64; 4]> {
+        let Expr::ScalarUDF(ScalarUDFExpr { fun, args }) = filter else {
+            return None;
+        };
+        if fun.name != "st_intersects" || args.len() != 2 {
+            return None;
+        }
+        let is_geometry_column = |expr: &Expr| matches!(expr, Expr::Column(column) if column.name == self.geometry_column);
+        if is_geometry_column(&args[0]) {
+            constant_bbox(&args[1])
+        } else if is_geometry_column(&args[1]) {
+            constant_bbox(&args[0])
+        } else {
+            None
+        }
+    }
+}
+
+/// The value of `expr` if it is a numeric literal, possibly negated.
+#[cfg(feature = "parquet")]
+fn literal_f64(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Literal(ScalarValue::Float64(value)) => *value,
+        Expr::Literal(ScalarValue::Float32(value)) => value.map(f64::from),
+        Expr::Literal(ScalarValue::Int64(value)) => value.map(|value| value as f64),
+        Expr::Literal(ScalarValue::Int32(value)) => value.map(f64::from),
+        Expr::Negative(expr) => literal_f64(expr).map(|value| -value),
+        _ => None,
+    }
+}
+
+/// The bbox of `expr` if it is a constant geometry: an `st_makeenvelope` of numeric literals, or
+/// a literal geometry, such as one folded from a call on literals by the optimizer.
+#[cfg(feature = "parquet")]
+fn constant_bbox(expr: &Expr) -> Option<[f64; 4]> {
+    match expr {
+        Expr::ScalarUDF(ScalarUDFExpr { fun, args }) if fun.name == "st_makeenvelope" => {
+            let bounds = args.iter().map(literal_f64).collect::<Option<Vec<_>>>()?;
+            let rect = geo::Rect::new((bounds[0], bounds[1]), (bounds[2], bounds[3]));
+            Some([rect.min().x, rect.min().y, rect.max().x, rect.max().y])
+        }
+        Expr::Literal(value) => {
+            let array = from_data(&value.to_array().to_data());
+            let geometry = GeometryArray::try_from_arrow(array.as_ref(), false).ok()?;
+            geometry.total_bounds()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(feature = "parquet")]
+#[async_trait]
+impl TableProvider for GeoParquetTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::new(arrow_schema(&self.schema))
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    fn supports_filter_pushdown(
+        &self,
+        filter: &Expr,
+    ) -> DataFusionResult<TableProviderFilterPushDown> {
+        Ok(match self.filter_bbox(filter) {
+            Some(_) => TableProviderFilterPushDown::Inexact,
+            None => TableProviderFilterPushDown::Unsupported,
+        })
+    }
+
+    async fn scan(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        // Rows must intersect the bbox of every filter, so only row groups intersecting all of
+        // them are read
+        let bbox = filters
+            .iter()
+            .filter_map(|filter| self.filter_bbox(filter))
+            .reduce(|left, right| {
+                [
+                    left[0].max(right[0]),
+                    left[1].max(right[1]),
+                    left[2].min(right[2]),
+                    left[3].min(right[3]),
+                ]
+            });
+        let columns: Option<Vec<String>> = projection.map(|projection| {
+            projection
+                .iter()
+                .map(|i| self.schema.fields[*i].name.clone())
+                .collect()
+        });
+        let options = ReaderOptions {
+            columns: columns.clone(),
+            ..Default::default()
+        };
+        let external = |err: GeoArrowError| DataFusionError::External(Box::new(err));
+        let table = read_geoparquet(File::open(&self.path)?, &options, bbox).map_err(external)?;
+
+        // The geometry column is read even when it is not projected
+        let projection = columns.map(|columns| {
+            columns
+                .iter()
+                .map(|name| {
+                    table
+                        .schema()
+                        .fields
+                        .iter()
+                        .position(|field| &field.name == name)
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        });
+        to_mem_table(&table)
+            .map_err(external)?
+            .scan(state, projection.as_ref(), &[], limit)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::PolygonArray;
+    use datafusion::arrow::array::{Array as _, BooleanArray, Float64Array};
+    use geo::{point, polygon};
+
+    fn arg(array: GeometryArray) -> ColumnarValue {
+        ColumnarValue::Array(make_array(to_data(array.into_arrow().as_ref())))
+    }
+
+    #[test]
+    fn functions() {
+        let polygons: PolygonArray = vec![
+            polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.)],
+            polygon![(x: 5., y: 5.), (x: 6., y: 5.), (x: 6., y: 6.), (x: 5., y: 6.)],
+        ]
+        .into();
+        let points: PointArray = vec![point!(x: 1., y: 1.), point!(x: 1., y: 1.)].into();
+        let polygons = arg(GeometryArray::Polygon(polygons));
+        let points = arg(GeometryArray::Point(points));
+
+        let ColumnarValue::Array(area) = (st_area().fun)(&[polygons.clone()]).unwrap() else {
+            panic!("expected an array");
+        };
+        let area = area.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(area.values().to_vec(), vec![4., 1.]);
+
+        let ColumnarValue::Array(contained) = (st_contains().fun)(&[polygons, points]).unwrap()
+        else {
+            panic!("expected an array");
+        };
+        let contained = contained.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert!(contained.value(0));
+        assert!(!contained.value(1));
+
+        let not_geometry = ColumnarValue::Array(Arc::new(Float64Array::from(vec![1.])));
+        assert!((st_area().fun)(&[not_geometry]).is_err());
+    }
+
+    #[test]
+    fn envelopes() {
+        use datafusion::scalar::ScalarValue;
+
+        let bound = |value| ColumnarValue::Scalar(ScalarValue::Float64(value));
+        let ColumnarValue::Array(envelope) = (st_makeenvelope().fun)(&[
+            bound(Some(0.)),
+            bound(Some(0.)),
+            bound(Some(2.)),
+            bound(Some(1.)),
+        ])
+        .unwrap() else {
+            panic!("expected an array");
+        };
+        let envelope = arg(GeometryArray::try_from_arrow(
+            from_data(&envelope.to_data()).as_ref(),
+            false,
+        )
+        .unwrap());
+
+        // The envelope of one row is broadcast to every point
+        let points: PointArray = vec![point!(x: 1., y: 0.5), point!(x: 3., y: 0.5)].into();
+        let ColumnarValue::Array(intersects) =
+            (st_intersects().fun)(&[arg(GeometryArray::Point(points)), envelope]).unwrap()
+        else {
+            panic!("expected an array");
+        };
+        let intersects = intersects.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert!(intersects.value(0));
+        assert!(!intersects.value(1));
+
+        let points: PointArray = vec![point!(x: 1., y: 0.5)].into();
+        let args = [
+            arg(GeometryArray::Point(points)),
+            bound(Some(0.)),
+            bound(Some(1.)),
+            bound(Some(1.)),
+        ];
+        assert!((st_makeenvelope().fun)(&args).is_err());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "parquet")]
+    async fn geoparquet_bbox_pushdown() {
+        use datafusion::physical_plan::collect;
+        use datafusion::prelude::{col, lit};
+
+        let ctx = SessionContext::new();
+        register_udfs(&ctx);
+        let table = GeoParquetTable::try_new("fixtures/geoparquet/points.parquet").unwrap();
+        let envelope = st_makeenvelope().call(vec![lit(0.), lit(0.), lit(1.5), lit(1.5)]);
+        let filter = st_intersects().call(vec![col("geometry"), envelope]);
+        assert_eq!(
+            table.supports_filter_pushdown(&filter).unwrap(),
+            TableProviderFilterPushDown::Inexact
+        );
+        let unrelated = col("id").eq(lit(1_i64));
+        assert_eq!(
+            table.supports_filter_pushdown(&unrelated).unwrap(),
+            TableProviderFilterPushDown::Unsupported
+        );
+
+        // The fixture has two row groups of four points, around (0, 0) and (100, 100). Only the
+        // first is read, whole, and the second is pruned
+        let plan = table
+            .scan(&ctx.state(), None, &[filter], None)
+            .await
+            .unwrap();
+        let batches = collect(plan, ctx.task_ctx()).await.unwrap();
+        let num_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(num_rows, 4);
+
+        // DataFusion then keeps the rows intersecting the envelope
+        ctx.register_table("points", Arc::new(table)).unwrap();
+        let batches = ctx
+            .sql("SELECT id FROM points WHERE st_intersects(geometry, st_makeenvelope(0, 0, 1.5, 1.5))")
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let num_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+        assert_eq!(num_rows, 2);
+    }
+
+    #[test]
+    #[cfg(feature = "geos")]
+    fn buffer() {
+        use datafusion::scalar::ScalarValue;
+
+        let points: PointArray = vec![Some(point!(x: 0., y: 0.)), None].into();
+        let width = ColumnarValue::Scalar(ScalarValue::Float64(Some(1.)));
+        let ColumnarValue::Array(buffered) =
+            (st_buffer().fun)(&[arg(GeometryArray::Point(points)), width]).unwrap()
+        else {
+            panic!("expected an array");
+        };
+        let buffered =
+            GeometryArray::try_from_arrow(from_data(&buffered.to_data()).as_ref(), true).unwrap();
+        assert!(matches!(buffered, GeometryArray::MultiPolygon(_)));
+        let area = buffered.unsigned_area();
+        assert!((area.value(0) - std::f64::consts::PI).abs() < 0.1);
+        assert!(area.is_null(1));
+    }
+}
@@ -0,0 +1,215 @@
+//! Human-readable [`Display`] of scalars and arrays as WKT.
+//!
+//! Scalars display as their WKT string, such as `POINT(0 1)`. Arrays display their type, length
+//! and values, such as `PointArray[3] [POINT(0 1), null, POINT(2 3)]`, keeping only the first
+//! and last few values of long arrays and the start of long WKT strings. The [`Debug`]
+//! implementations still show the underlying buffers.
+
+use crate::enum_::Geometry;
+use crate::{
+    GeometryArray, GeometryArrayTrait, GeometryCollection, LineString, LineStringArray,
+    MixedGeometryArray, MultiLineString, MultiLineStringArray, MultiPoint, MultiPointArray,
+    MultiPolygon, MultiPolygonArray, OwnedLineString, OwnedMultiLineString, OwnedMultiPoint,
+    OwnedMultiPolygon, OwnedPoint, OwnedPolygon, Point, PointArray, Polygon, PolygonArray, Rect,
+    RectArray, WKBArray, WKB,
+};
+use geozero::ToWkt;
+use std::fmt::{self, Display, Formatter};
+
+/// Arrays longer than this only display their first and last `MAX_ROWS / 2` values.
+const MAX_ROWS: usize = 10;
+
+/// The most characters of WKT displayed for each value of an array.
+const MAX_WKT_LEN: usize = 80;
+
+/// Write the WKT string of `geom`.
+fn fmt_geo(geom: geo::Geometry, f: &mut Formatter<'_>) -> fmt::Result {
+    // The geometry is already in memory, so writing it out cannot fail
+    let wkt = geom.to_wkt().map_err(|_| fmt::Error)?;
+    f.write_str(&wkt)
+}
+
+macro_rules! impl_display_scalar {
+    ($type:ty) => {
+        impl Display for $type {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                fmt_geo(self.clone().into(), f)
+            }
+        }
+    };
+}
+
+impl_display_scalar!(Point<'_>);
+impl_display_scalar!(LineString<'_>);
+impl_display_scalar!(Polygon<'_>);
+impl_display_scalar!(MultiPoint<'_>);
+impl_display_scalar!(MultiLineString<'_>);
+impl_display_scalar!(MultiPolygon<'_>);
+impl_display_scalar!(GeometryCollection<'_>);
+impl_display_scalar!(OwnedPoint);
+impl_display_scalar!(OwnedLineString);
+impl_display_scalar!(OwnedPolygon);
+impl_display_scalar!(OwnedMultiPoint);
+impl_display_scalar!(OwnedMultiLineString);
+impl_display_scalar!(OwnedMultiPolygon);
+
+impl Display for Rect<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rect: geo::Rect = self.clone().into();
+        fmt_geo(geo::Geometry::Polygon(rect.to_polygon()), f)
+    }
+}
+
+impl Display for WKB<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.to_typed_scalar() {
+            Ok(geom) => fmt_geo(geom, f),
+            Err(_) => f.write_str("<invalid WKB>"),
+        }
+    }
+}
+
+impl Display for Geometry<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Geometry::Point(geom) => geom.fmt(f),
+            Geometry::LineString(geom) => geom.fmt(f),
+            Geometry::Polygon(geom) => geom.fmt(f),
+            Geometry::MultiPoint(geom) => geom.fmt(f),
+            Geometry::MultiLineString(geom) => geom.fmt(f),
+            Geometry::MultiPolygon(geom) => geom.fmt(f),
+            Geometry::WKB(geom) => geom.fmt(f),
+            Geometry::Rect(geom) => geom.fmt(f),
+            Geometry::GeometryCollection(geom) => geom.fmt(f),
+        }
+    }
+}
+
+/// Write the value at `i` of `array`, shortening long WKT strings.
+fn fmt_value<'a, A>(array: &'a A, i: usize, f: &mut Formatter<'_>) -> fmt::Result
+where
+    A: GeometryArrayTrait<'a>,
+    A::Scalar: Display,
+{
+    if array.is_null(i) {
+        return f.write_str("null");
+    }
+    let wkt = array.value(i).to_string();
+    match wkt.char_indices().nth(MAX_WKT_LEN) {
+        Some((end, _)) => write!(f, "{}…", &wkt[..end]),
+        None => f.write_str(&wkt),
+    }
+}
+
+/// Write `name`, the length of `array` and its values, eliding the middle of long arrays.
+fn fmt_array<'a, A>(array: &'a A, name: &str, f: &mut Formatter<'_>) -> fmt::Result
+where
+    A: GeometryArrayTrait<'a>,
+    A::Scalar: Display,
+{
+    let len = array.len();
+    write!(f, "{name}[{len}] [")?;
+    let rows: Vec<Option<usize>> = if len <= MAX_ROWS {
+        (0..len).map(Some).collect()
+    } else {
+        // `None` stands for the elided values
+        (0..MAX_ROWS / 2)
+            .map(Some)
+            .chain([None])
+            .chain((len - MAX_ROWS / 2..len).map(Some))
+            .collect()
+    };
+    for (position, row) in rows.into_iter().enumerate() {
+        if position > 0 {
+            f.write_str(", ")?;
+        }
+        match row {
+            Some(i) => fmt_value(array, i, f)?,
+            None => f.write_str("…")?,
+        }
+    }
+    f.write_str("]")
+}
+
+macro_rules! impl_display_array {
+    ($type:ty, $name:literal) => {
+        impl Display for $type {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                fmt_array(self, $name, f)
+            }
+        }
+    };
+}
+
+impl_display_array!(PointArray, "PointArray");
+impl_display_array!(LineStringArray, "LineStringArray");
+impl_display_array!(PolygonArray, "PolygonArray");
+impl_display_array!(MultiPointArray, "MultiPointArray");
+impl_display_array!(MultiLineStringArray, "MultiLineStringArray");
+impl_display_array!(MultiPolygonArray, "MultiPolygonArray");
+impl_display_array!(WKBArray, "WKBArray");
+impl_display_array!(RectArray, "RectArray");
+impl_display_array!(MixedGeometryArray, "MixedGeometryArray");
+
+impl Display for GeometryArray {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GeometryArray::Point(arr) => arr.fmt(f),
+            GeometryArray::LineString(arr) => arr.fmt(f),
+            GeometryArray::Polygon(arr) => arr.fmt(f),
+            GeometryArray::MultiPoint(arr) => arr.fmt(f),
+            GeometryArray::MultiLineString(arr) => arr.fmt(f),
+            GeometryArray::MultiPolygon(arr) => arr.fmt(f),
+            GeometryArray::WKB(arr) => arr.fmt(f),
+            GeometryArray::Rect(arr) => arr.fmt(f),
+            GeometryArray::Mixed(arr) => arr.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point};
+
+    #[test]
+    fn scalars() {
+        let points: PointArray = vec![point!(x: 0., y: 1.)].into();
+        assert_eq!(points.value(0).to_string(), "POINT(0 1)");
+        assert_eq!(OwnedPoint::from(points.value(0)).to_string(), "POINT(0 1)");
+
+        let lines: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 1., y: 2.)]].into();
+        assert_eq!(lines.value(0).to_string(), "LINESTRING(0 0,1 2)");
+
+        let wkb: WKBArray = vec![Some(geo::Geometry::Point(point!(x: 2., y: 3.)))].into();
+        assert_eq!(wkb.value(0).to_string(), "POINT(2 3)");
+    }
+
+    #[test]
+    fn arrays() {
+        let points: PointArray = vec![Some(point!(x: 0., y: 1.)), None].into();
+        assert_eq!(points.to_string(), "PointArray[2] [POINT(0 1), null]");
+        assert_eq!(
+            GeometryArray::Point(points).to_string(),
+            "PointArray[2] [POINT(0 1), null]"
+        );
+
+        let points: PointArray = (0..20)
+            .map(|i| point!(x: i as f64, y: 0.))
+            .collect::<Vec<_>>()
+            .into();
+        assert_eq!(
+            points.to_string(),
+            "PointArray[20] [POINT(0 0), POINT(1 0), POINT(2 0), POINT(3 0), POINT(4 0), …, \
+             POINT(15 0), POINT(16 0), POINT(17 0), POINT(18 0), POINT(19 0)]"
+        );
+
+        let long: LineStringArray = vec![geo::LineString::from(
+            (0..100).map(|i| (i as f64, 0.)).collect::<Vec<_>>(),
+        )]
+        .into();
+        let display = long.to_string();
+        assert!(display.starts_with("LineStringArray[1] [LINESTRING(0 0,1 0,"));
+        assert!(display.ends_with("…]"));
+    }
+}
@@ -1,14 +1,91 @@
-use crate::GeometryArrayTrait;
-use arrow2::array::{Array, BinaryArray, ListArray, StructArray};
+use crate::coerce::{coerce, from_geo, promote_geometry};
+use crate::crs::{crs_from_json, crs_to_json};
+use crate::error::GeoArrowError;
+use crate::geo_traits::{GeometryTrait, GeometryType};
+use crate::offsets::widen_list_offsets;
+use crate::trait_::MutableGeometryArray as _;
+use crate::{Dimension, GeometryArrayTrait, GeometryArrayType};
+use arrow2::array::{Array, ListArray, StructArray, UnionArray};
 use arrow2::bitmap::Bitmap;
-use arrow2::datatypes::DataType;
-use rstar::{RTree, RTreeObject, AABB};
+use arrow2::datatypes::{DataType, Field, Metadata};
+use rstar::{RTreeObject, AABB};
+use serde_json::{json, Value};
 
 use crate::{
-    LineStringArray, MultiLineStringArray, MultiPointArray, MultiPolygonArray, PointArray,
-    PolygonArray, WKBArray,
+    GeometryCollectionArray, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, MutableLineStringArray, MutableMultiLineStringArray,
+    MutableMultiPointArray, MutableMultiPolygonArray, MutablePointArray, MutablePolygonArray,
+    MutableWKBArray, PointArray, PolygonArray, RectArray, WKBArray,
 };
 
+/// Field metadata key holding the name of an Arrow extension type.
+pub(crate) const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+
+/// Field metadata key holding the serialized parameters of an Arrow extension type.
+pub(crate) const EXTENSION_METADATA_KEY: &str = "ARROW:extension:metadata";
+
+/// The GeoArrow extension metadata for geometries in `crs`, as a JSON object.
+///
+/// A PROJJSON `crs` is embedded as an object; any other CRS (e.g. `"EPSG:4326"` or WKT) is
+/// embedded as a string.
+pub(crate) fn extension_metadata(crs: Option<&str>) -> String {
+    let metadata = match crs {
+        Some(crs) => json!({ "crs": crs_to_json(crs) }),
+        None => json!({}),
+    };
+    metadata.to_string()
+}
+
+/// The CRS held by GeoArrow extension metadata, the inverse of [`extension_metadata`].
+///
+/// A string CRS is returned as is, and a PROJJSON object serialized. Returns `None` if the
+/// metadata is not a JSON object or has no `crs`.
+fn crs_from_extension_metadata(metadata: &str) -> Option<String> {
+    let metadata: Value = serde_json::from_str(metadata).ok()?;
+    crs_from_json(metadata.get("crs")?)
+}
+
+/// The extension name of a field, read from either its data type or its metadata.
+pub(crate) fn extension_name(field: &Field) -> Option<&str> {
+    match field.data_type() {
+        DataType::Extension(name, _, _) => Some(name.as_str()),
+        _ => field
+            .metadata
+            .get(EXTENSION_NAME_KEY)
+            .map(|name| name.as_str()),
+    }
+}
+
+/// `field` with its extension type, if any, moved into its metadata.
+///
+/// arrow2 imports fields with an `ARROW:extension:name` as [`DataType::Extension`], while
+/// geometry arrays are built from their storage type.
+pub(crate) fn storage_field(field: Field) -> Field {
+    let DataType::Extension(name, storage, extension_metadata) = field.data_type else {
+        return field;
+    };
+    let mut metadata = field.metadata;
+    metadata.insert(EXTENSION_NAME_KEY.to_string(), name);
+    if let Some(extension_metadata) = extension_metadata {
+        metadata.insert(EXTENSION_METADATA_KEY.to_string(), extension_metadata);
+    }
+    Field::new(field.name, *storage, field.is_nullable).with_metadata(metadata)
+}
+
+/// `field` with the extension type named in its metadata, if any, as its data type, the inverse
+/// of [`storage_field`].
+///
+/// arrow2 only exports the extension name and metadata of [`DataType::Extension`] fields.
+#[cfg(feature = "ipc")]
+pub(crate) fn extension_field(mut field: Field) -> Field {
+    let Some(name) = field.metadata.remove(EXTENSION_NAME_KEY) else {
+        return field;
+    };
+    let extension_metadata = field.metadata.remove(EXTENSION_METADATA_KEY);
+    field.data_type = DataType::Extension(name, Box::new(field.data_type), extension_metadata);
+    field
+}
+
 pub enum Geometry<'a> {
     Point(crate::Point<'a>),
     LineString(crate::LineString<'a>),
@@ -17,6 +94,41 @@ pub enum Geometry<'a> {
     MultiLineString(crate::MultiLineString<'a>),
     MultiPolygon(crate::MultiPolygon<'a>),
     WKB(crate::WKB<'a>),
+    Rect(crate::Rect<'a>),
+    GeometryCollection(crate::GeometryCollection<'a>),
+}
+
+impl<'a> GeometryTrait<'a> for Geometry<'a> {
+    type Point = crate::Point<'a>;
+    type LineString = crate::LineString<'a>;
+    type Polygon = crate::Polygon<'a>;
+    type MultiPoint = crate::MultiPoint<'a>;
+    type MultiLineString = crate::MultiLineString<'a>;
+    type MultiPolygon = crate::MultiPolygon<'a>;
+    type Rect = crate::Rect<'a>;
+
+    fn as_type(
+        &'a self,
+    ) -> GeometryType<
+        crate::Point<'a>,
+        crate::LineString<'a>,
+        crate::Polygon<'a>,
+        crate::MultiPoint<'a>,
+        crate::MultiLineString<'a>,
+        crate::MultiPolygon<'a>,
+        crate::Rect<'a>,
+    > {
+        match self {
+            Geometry::Point(geom) => GeometryType::Point(geom.clone()),
+            Geometry::LineString(geom) => GeometryType::LineString(geom.clone()),
+            Geometry::Polygon(geom) => GeometryType::Polygon(geom.clone()),
+            Geometry::MultiPoint(geom) => GeometryType::MultiPoint(geom.clone()),
+            Geometry::MultiLineString(geom) => GeometryType::MultiLineString(geom.clone()),
+            Geometry::MultiPolygon(geom) => GeometryType::MultiPolygon(geom.clone()),
+            Geometry::Rect(geom) => GeometryType::Rect(geom.clone()),
+            Geometry::WKB(_) | Geometry::GeometryCollection(_) => GeometryType::Unsupported,
+        }
+    }
 }
 
 impl RTreeObject for Geometry<'_> {
@@ -31,6 +143,8 @@ impl RTreeObject for Geometry<'_> {
             Geometry::MultiLineString(geom) => geom.envelope(),
             Geometry::MultiPolygon(geom) => geom.envelope(),
             Geometry::WKB(geom) => geom.envelope(),
+            Geometry::Rect(geom) => geom.envelope(),
+            Geometry::GeometryCollection(geom) => geom.envelope(),
         }
     }
 }
@@ -45,6 +159,8 @@ impl From<Geometry<'_>> for geo::Geometry {
             Geometry::MultiLineString(geom) => geom.into(),
             Geometry::MultiPolygon(geom) => geom.into(),
             Geometry::WKB(geom) => geom.into(),
+            Geometry::Rect(geom) => geom.into(),
+            Geometry::GeometryCollection(geom) => geom.into(),
         }
     }
 }
@@ -58,50 +174,277 @@ pub enum GeometryArray {
     MultiLineString(MultiLineStringArray),
     MultiPolygon(MultiPolygonArray),
     WKB(WKBArray),
+    Rect(RectArray),
+    Mixed(Box<MixedGeometryArray>),
 }
 
 impl GeometryArray {
-    /// Convert an [`arrow2`] [`Array`] to a [`GeometryArray`].
-    pub fn from_arrow(arr: &dyn Array, is_multi: bool) -> Self {
-        match arr.data_type() {
-            DataType::LargeBinary => {
-                let lit_arr = arr.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
-                GeometryArray::WKB(lit_arr.clone().into())
+    /// Build the narrowest array able to hold `geoms`.
+    ///
+    /// Geometries of a single type build the native array of that type. Single and multi
+    /// geometries of the same kind build the multi array, and polygons and rects a polygon array.
+    /// Other mixes build a [`MixedGeometryArray`], unless they contain geometry collections, which
+    /// only WKB holds. Lines are stored as line strings and triangles as polygons. An empty slice
+    /// builds an empty WKB array.
+    pub fn from_geometries(geoms: &[geo::Geometry]) -> Self {
+        let geoms: Vec<geo::Geometry> = geoms
+            .iter()
+            .map(|geom| match geom {
+                geo::Geometry::Line(line) => geo::Geometry::LineString((*line).into()),
+                geo::Geometry::Triangle(triangle) => geo::Geometry::Polygon(triangle.to_polygon()),
+                geom => geom.clone(),
+            })
+            .collect();
+
+        match GeometryArrayType::common(geoms.iter().map(GeometryArrayType::of_geo)) {
+            GeometryArrayType::WKB => {
+                let has_collection = geoms
+                    .iter()
+                    .any(|geom| matches!(geom, geo::Geometry::GeometryCollection(_)));
+                let geoms: Vec<Option<geo::Geometry>> = geoms.into_iter().map(Some).collect();
+                if geoms.is_empty() || has_collection {
+                    GeometryArray::WKB(geoms.into())
+                } else {
+                    GeometryArray::Mixed(Box::new(geoms.try_into().unwrap()))
+                }
             }
-            DataType::Struct(_) => {
-                let lit_arr = arr.as_any().downcast_ref::<StructArray>().unwrap();
-                GeometryArray::Point(lit_arr.clone().try_into().unwrap())
+            kind => {
+                let geoms = geoms
+                    .into_iter()
+                    .map(|geom| Some(promote_geometry(geom, kind)))
+                    .collect();
+                from_geo(geoms, kind)
             }
-            DataType::List(dt) | DataType::LargeList(dt) => match dt.data_type() {
-                DataType::Struct(_) => {
-                    let lit_arr = arr.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+        }
+    }
+
+    /// Convert an [`arrow2`] [`Array`] to a [`GeometryArray`], inferring the geometry type from
+    /// its data type.
+    ///
+    /// Multi points share their layout with line strings, and multi line strings with polygons,
+    /// so `is_multi` selects between them. Prefer [`GeometryArray::from_arrow_field`] when the
+    /// array's [`Field`] is available. Lists may have 32-bit or 64-bit offsets, and WKB may be
+    /// stored in `Binary`, `LargeBinary` or `FixedSizeBinary` arrays.
+    /// # Panics
+    /// Panics if the array does not have a geometry layout. See [`GeometryArray::try_from_arrow`]
+    /// for a fallible version.
+    pub fn from_arrow(arr: &dyn Array, is_multi: bool) -> Self {
+        Self::try_from_arrow(arr, is_multi).unwrap()
+    }
 
-                    if is_multi {
-                        GeometryArray::MultiPoint(lit_arr.clone().try_into().unwrap())
-                    } else {
-                        GeometryArray::LineString(lit_arr.clone().try_into().unwrap())
+    /// Convert an [`arrow2`] [`Array`] to a [`GeometryArray`], inferring the geometry type from
+    /// its data type, as in [`GeometryArray::from_arrow`].
+    /// # Errors
+    /// Errors with [`GeoArrowError::NotYetImplemented`] if the data type is not a geometry layout,
+    /// or with [`GeoArrowError::Downcast`] if the array does not match its data type.
+    pub fn try_from_arrow(arr: &dyn Array, is_multi: bool) -> Result<Self, GeoArrowError> {
+        let arr = widen_list_offsets(arr);
+        let arr = arr.as_ref();
+        let unsupported = |data_type: &DataType| {
+            GeoArrowError::NotYetImplemented(format!(
+                "reading geometries from arrays of type {:?}",
+                data_type
+            ))
+        };
+        let list = || {
+            arr.as_any()
+                .downcast_ref::<ListArray<i64>>()
+                .cloned()
+                .ok_or_else(|| GeoArrowError::Downcast {
+                    expected: "ListArray<i64>",
+                    found: arr.data_type().clone(),
+                })
+        };
+        let array = match arr.data_type() {
+            DataType::LargeBinary | DataType::Binary | DataType::FixedSizeBinary(_) => {
+                GeometryArray::WKB(arr.to_boxed().try_into()?)
+            }
+            DataType::Union(_, _, _) => {
+                let union_arr = arr.as_any().downcast_ref::<UnionArray>().ok_or_else(|| {
+                    GeoArrowError::Downcast {
+                        expected: "UnionArray",
+                        found: arr.data_type().clone(),
                     }
+                })?;
+                GeometryArray::Mixed(Box::new(union_arr.try_into()?))
+            }
+            DataType::Struct(fields) => {
+                let struct_arr = arr
+                    .as_any()
+                    .downcast_ref::<StructArray>()
+                    .cloned()
+                    .ok_or_else(|| GeoArrowError::Downcast {
+                        expected: "StructArray",
+                        found: arr.data_type().clone(),
+                    })?;
+                // Rects are told apart from 4D points by their field names
+                let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+                if names == ["minx", "miny", "maxx", "maxy"] {
+                    GeometryArray::Rect(struct_arr.try_into()?)
+                } else {
+                    GeometryArray::Point(struct_arr.try_into()?)
                 }
+            }
+            DataType::List(dt) | DataType::LargeList(dt) => match dt.data_type() {
+                DataType::Struct(_) if is_multi => GeometryArray::MultiPoint(list()?.try_into()?),
+                DataType::Struct(_) => GeometryArray::LineString(list()?.try_into()?),
                 DataType::List(dt2) | DataType::LargeList(dt2) => match dt2.data_type() {
-                    DataType::Struct(_) => {
-                        let lit_arr = arr.as_any().downcast_ref::<ListArray<i64>>().unwrap();
-                        if is_multi {
-                            GeometryArray::MultiLineString(lit_arr.clone().try_into().unwrap())
-                        } else {
-                            GeometryArray::Polygon(lit_arr.clone().try_into().unwrap())
-                        }
+                    DataType::Struct(_) if is_multi => {
+                        GeometryArray::MultiLineString(list()?.try_into()?)
                     }
+                    DataType::Struct(_) => GeometryArray::Polygon(list()?.try_into()?),
                     DataType::List(_) | DataType::LargeList(_) => {
-                        let lit_arr = arr.as_any().downcast_ref::<ListArray<i64>>().unwrap();
-                        GeometryArray::MultiPolygon(lit_arr.clone().try_into().unwrap())
+                        GeometryArray::MultiPolygon(list()?.try_into()?)
                     }
-                    _ => panic!("Unexpected inner list type: {:?}", dt2),
+                    _ => return Err(unsupported(arr.data_type())),
                 },
-                _ => panic!("Unexpected inner list type: {:?}", dt),
+                _ => return Err(unsupported(arr.data_type())),
             },
-            dt => panic!("Unexpected geoarrow type: {:?}", dt),
+            data_type => return Err(unsupported(data_type)),
+        };
+        Ok(array)
+    }
+
+    /// Convert an [`arrow2`] [`Array`] to a [`GeometryArray`] of the type named by the GeoArrow
+    /// extension name of `field` (e.g. `geoarrow.multipoint`).
+    ///
+    /// The extension name is read from an [`DataType::Extension`] data type, or from the
+    /// `ARROW:extension:name` metadata key. Fields without an extension name fall back to
+    /// [`GeometryArray::from_arrow`], reading list-of-struct arrays as single geometries. The CRS
+    /// of the array is read from the `ARROW:extension:metadata` metadata key.
+    ///
+    /// `geoarrow.geometrycollection` arrays are read as WKB, as [`GeometryArray`] has no geometry
+    /// collection variant.
+    /// # Errors
+    /// Errors if the extension name is not a GeoArrow geometry type, or if the array does not
+    /// have the layout of its extension type.
+    pub fn from_arrow_field(field: &Field, arr: &dyn Array) -> Result<Self, GeoArrowError> {
+        let arr = arr.to_boxed();
+        let crs = field
+            .metadata
+            .get(EXTENSION_METADATA_KEY)
+            .and_then(|metadata| crs_from_extension_metadata(metadata));
+        let name = match extension_name(field) {
+            Some(name) => name,
+            None => return Ok(Self::try_from_arrow(arr.as_ref(), false)?.with_crs(crs.as_deref())),
+        };
+
+        let array = match name {
+            "geoarrow.point" => GeometryArray::Point(arr.try_into()?),
+            "geoarrow.linestring" => GeometryArray::LineString(arr.try_into()?),
+            "geoarrow.polygon" => GeometryArray::Polygon(arr.try_into()?),
+            "geoarrow.multipoint" => GeometryArray::MultiPoint(arr.try_into()?),
+            "geoarrow.multilinestring" => GeometryArray::MultiLineString(arr.try_into()?),
+            "geoarrow.multipolygon" => GeometryArray::MultiPolygon(arr.try_into()?),
+            "geoarrow.wkb" => GeometryArray::WKB(arr.try_into()?),
+            "geoarrow.geometry" => GeometryArray::Mixed(Box::new(arr.try_into()?)),
+            // There is no geometry collection variant, so collections are held as WKB
+            "geoarrow.geometrycollection" => {
+                let collections: GeometryCollectionArray = arr.try_into()?;
+                let collections: Vec<Option<geo::GeometryCollection>> = collections.into();
+                GeometryArray::WKB(collections.into())
+            }
+            name => {
+                return Err(GeoArrowError::NotYetImplemented(format!(
+                    "reading arrays with extension type {name}"
+                )))
+            }
+        };
+        Ok(array.with_crs(crs.as_deref()))
+    }
+
+    /// The GeoArrow extension name describing this array's geometry type, for the
+    /// `ARROW:extension:name` metadata of its field.
+    ///
+    /// Returns `None` for rect arrays, which have no GeoArrow extension type.
+    pub fn extension_name(&self) -> Option<&'static str> {
+        self.geometry_type().extension_name()
+    }
+
+    /// The coordinate reference system of this array, as a PROJJSON or `AUTHORITY:CODE` string,
+    /// if known.
+    pub fn crs(&self) -> Option<&str> {
+        match self {
+            GeometryArray::Point(arr) => arr.crs(),
+            GeometryArray::LineString(arr) => arr.crs(),
+            GeometryArray::Polygon(arr) => arr.crs(),
+            GeometryArray::MultiPoint(arr) => arr.crs(),
+            GeometryArray::MultiLineString(arr) => arr.crs(),
+            GeometryArray::MultiPolygon(arr) => arr.crs(),
+            GeometryArray::WKB(arr) => arr.crs(),
+            GeometryArray::Rect(arr) => arr.crs(),
+            GeometryArray::Mixed(arr) => arr.crs(),
+        }
+    }
+
+    /// This array with its coordinate reference system set to `crs`.
+    ///
+    /// The coordinates are not transformed.
+    pub fn with_crs(self, crs: Option<&str>) -> Self {
+        match self {
+            GeometryArray::Point(arr) => GeometryArray::Point(arr.with_crs(crs)),
+            GeometryArray::LineString(arr) => GeometryArray::LineString(arr.with_crs(crs)),
+            GeometryArray::Polygon(arr) => GeometryArray::Polygon(arr.with_crs(crs)),
+            GeometryArray::MultiPoint(arr) => GeometryArray::MultiPoint(arr.with_crs(crs)),
+            GeometryArray::MultiLineString(arr) => {
+                GeometryArray::MultiLineString(arr.with_crs(crs))
+            }
+            GeometryArray::MultiPolygon(arr) => GeometryArray::MultiPolygon(arr.with_crs(crs)),
+            GeometryArray::WKB(arr) => GeometryArray::WKB(arr.with_crs(crs)),
+            GeometryArray::Rect(arr) => GeometryArray::Rect(arr.with_crs(crs)),
+            GeometryArray::Mixed(arr) => GeometryArray::Mixed(Box::new(arr.with_crs(crs))),
         }
     }
+
+    /// Convert this array into an [`arrow2`] array, together with a [`Field`] named `name`
+    /// declaring its GeoArrow extension type.
+    ///
+    /// The field carries the `ARROW:extension:name` and `ARROW:extension:metadata` metadata keys
+    /// required by the GeoArrow specification, so that other implementations (e.g. pyarrow with
+    /// geoarrow-c) recognize the geometry type. The metadata holds the CRS of the array, if any.
+    /// Rect arrays have no GeoArrow extension type, and produce a field without metadata.
+    pub fn into_arrow_field(self, name: &str) -> (Field, Box<dyn Array>) {
+        let extension_name = self.extension_name();
+        let crs = self.crs().map(str::to_string);
+        let array = self.into_arrow();
+        let mut field = Field::new(name, array.data_type().clone(), true);
+        if let Some(extension_name) = extension_name {
+            let mut metadata = Metadata::new();
+            metadata.insert(EXTENSION_NAME_KEY.to_string(), extension_name.to_string());
+            metadata.insert(
+                EXTENSION_METADATA_KEY.to_string(),
+                extension_metadata(crs.as_deref()),
+            );
+            field = field.with_metadata(metadata);
+        }
+        (field, array)
+    }
+
+    /// Access the value at slot `i` as an Arrow scalar, or [`None`] if the slot is null.
+    ///
+    /// This is equivalent to [`GeometryArrayTrait::get`], but doesn't require the trait to be in
+    /// scope.
+    /// # Panic
+    /// Panics iff `i >= self.len()`.
+    pub fn get(&self, i: usize) -> Option<Geometry<'_>> {
+        if self.is_null(i) {
+            return None;
+        }
+
+        Some(self.value(i))
+    }
+
+    /// Access the value at slot `i` as a [`geo::Geometry`], or [`None`] if the slot is null.
+    /// # Panic
+    /// Panics iff `i >= self.len()`.
+    pub fn get_as_geo(&self, i: usize) -> Option<geo::Geometry> {
+        self.get(i).map(|geom| geom.into())
+    }
+
+    /// Iterator over [`geo::Geometry`] objects, taking validity into account.
+    pub fn iter_geo(&self) -> impl Iterator<Item = Option<geo::Geometry>> + '_ {
+        (0..self.len()).map(|i| self.get_as_geo(i))
+    }
 }
 
 impl<'a> GeometryArrayTrait<'a> for GeometryArray {
@@ -118,6 +461,8 @@ impl<'a> GeometryArrayTrait<'a> for GeometryArray {
             GeometryArray::MultiLineString(arr) => Geometry::MultiLineString(arr.value(i)),
             GeometryArray::MultiPolygon(arr) => Geometry::MultiPolygon(arr.value(i)),
             GeometryArray::WKB(arr) => Geometry::WKB(arr.value(i)),
+            GeometryArray::Rect(arr) => Geometry::Rect(arr.value(i)),
+            GeometryArray::Mixed(arr) => arr.value(i),
         }
     }
 
@@ -130,15 +475,37 @@ impl<'a> GeometryArrayTrait<'a> for GeometryArray {
             GeometryArray::MultiLineString(arr) => arr.into_arrow().boxed(),
             GeometryArray::MultiPolygon(arr) => arr.into_arrow().boxed(),
             GeometryArray::WKB(arr) => arr.into_arrow().boxed(),
+            GeometryArray::Rect(arr) => arr.into_arrow().boxed(),
+            GeometryArray::Mixed(arr) => arr.into_arrow().boxed(),
+        }
+    }
+
+    fn geometry_type(&self) -> GeometryArrayType {
+        match self {
+            GeometryArray::Point(arr) => arr.geometry_type(),
+            GeometryArray::LineString(arr) => arr.geometry_type(),
+            GeometryArray::Polygon(arr) => arr.geometry_type(),
+            GeometryArray::MultiPoint(arr) => arr.geometry_type(),
+            GeometryArray::MultiLineString(arr) => arr.geometry_type(),
+            GeometryArray::MultiPolygon(arr) => arr.geometry_type(),
+            GeometryArray::WKB(arr) => arr.geometry_type(),
+            GeometryArray::Rect(arr) => arr.geometry_type(),
+            GeometryArray::Mixed(arr) => arr.geometry_type(),
         }
     }
 
-    fn rstar_tree(&'a self) -> rstar::RTree<Self::Scalar> {
-        let mut tree = RTree::new();
-        (0..self.len())
-            .filter_map(|geom_idx| self.get(geom_idx))
-            .for_each(|geom| tree.insert(geom));
-        tree
+    fn dimension(&self) -> Dimension {
+        match self {
+            GeometryArray::Point(arr) => arr.dimension(),
+            GeometryArray::LineString(arr) => arr.dimension(),
+            GeometryArray::Polygon(arr) => arr.dimension(),
+            GeometryArray::MultiPoint(arr) => arr.dimension(),
+            GeometryArray::MultiLineString(arr) => arr.dimension(),
+            GeometryArray::MultiPolygon(arr) => arr.dimension(),
+            GeometryArray::WKB(arr) => arr.dimension(),
+            GeometryArray::Rect(arr) => arr.dimension(),
+            GeometryArray::Mixed(arr) => arr.dimension(),
+        }
     }
 
     /// The length of the [`GeometryArray`]. Every array has a length corresponding to the number
@@ -152,6 +519,8 @@ impl<'a> GeometryArrayTrait<'a> for GeometryArray {
             GeometryArray::MultiLineString(arr) => arr.len(),
             GeometryArray::MultiPolygon(arr) => arr.len(),
             GeometryArray::WKB(arr) => arr.len(),
+            GeometryArray::Rect(arr) => arr.len(),
+            GeometryArray::Mixed(arr) => arr.len(),
         }
     }
 
@@ -167,6 +536,8 @@ impl<'a> GeometryArrayTrait<'a> for GeometryArray {
             GeometryArray::MultiLineString(arr) => arr.validity(),
             GeometryArray::MultiPolygon(arr) => arr.validity(),
             GeometryArray::WKB(arr) => arr.validity(),
+            GeometryArray::Rect(arr) => arr.validity(),
+            GeometryArray::Mixed(arr) => arr.validity(),
         }
     }
 
@@ -185,6 +556,8 @@ impl<'a> GeometryArrayTrait<'a> for GeometryArray {
             GeometryArray::MultiLineString(arr) => arr.slice(offset, length),
             GeometryArray::MultiPolygon(arr) => arr.slice(offset, length),
             GeometryArray::WKB(arr) => arr.slice(offset, length),
+            GeometryArray::Rect(arr) => arr.slice(offset, length),
+            GeometryArray::Mixed(arr) => arr.slice(offset, length),
         };
     }
 
@@ -204,13 +577,34 @@ impl<'a> GeometryArrayTrait<'a> for GeometryArray {
             GeometryArray::WKB(arr) => {
                 arr.slice_unchecked(offset, length);
             }
+            GeometryArray::Rect(arr) => arr.slice_unchecked(offset, length),
+            GeometryArray::Mixed(arr) => arr.slice_unchecked(offset, length),
         }
     }
 
-    // /// Clones this [`GeometryArray`] with a new assigned bitmap.
-    // /// # Panic
-    // /// This function panics iff `validity.len() != self.len()`.
-    // pub fn with_validity(&self, validity: Option<Bitmap>) -> Box<GeometryArrayTrait>;
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        match self {
+            GeometryArray::Point(arr) => GeometryArray::Point(arr.with_validity(validity)),
+            GeometryArray::LineString(arr) => {
+                GeometryArray::LineString(arr.with_validity(validity))
+            }
+            GeometryArray::Polygon(arr) => GeometryArray::Polygon(arr.with_validity(validity)),
+            GeometryArray::MultiPoint(arr) => {
+                GeometryArray::MultiPoint(arr.with_validity(validity))
+            }
+            GeometryArray::MultiLineString(arr) => {
+                GeometryArray::MultiLineString(arr.with_validity(validity))
+            }
+            GeometryArray::MultiPolygon(arr) => {
+                GeometryArray::MultiPolygon(arr.with_validity(validity))
+            }
+            GeometryArray::WKB(arr) => GeometryArray::WKB(arr.with_validity(validity)),
+            GeometryArray::Rect(arr) => GeometryArray::Rect(arr.with_validity(validity)),
+            GeometryArray::Mixed(arr) => {
+                GeometryArray::Mixed(Box::new(arr.with_validity(validity)))
+            }
+        }
+    }
 
     /// Clone a [`GeometryArray`] to an owned `Box<GeometryArray>`.
     fn to_boxed(&self) -> Box<GeometryArray> {
@@ -222,6 +616,580 @@ impl<'a> GeometryArrayTrait<'a> for GeometryArray {
             GeometryArray::MultiLineString(arr) => GeometryArray::MultiLineString(arr.clone()),
             GeometryArray::MultiPolygon(arr) => GeometryArray::MultiPolygon(arr.clone()),
             GeometryArray::WKB(arr) => GeometryArray::WKB(arr.clone()),
+            GeometryArray::Rect(arr) => GeometryArray::Rect(arr.clone()),
+            GeometryArray::Mixed(arr) => GeometryArray::Mixed(arr.clone()),
         })
     }
 }
+
+/// A builder of a [`GeometryArray`] from geometries of any type, promoting its type as needed.
+///
+/// The array takes the type of the first geometry pushed. A single geometry and a multi geometry
+/// of the same kind promote the array to the multi type (e.g. points and multi points build a
+/// [`MultiPointArray`]), and geometries with no common native type, or with no native type at
+/// all such as geometry collections, promote it to WKB. The geometries already pushed are
+/// converted once per promotion, so streams of a single type never pay for it.
+///
+/// Rects are stored as polygons. An array of only nulls is built as WKB.
+#[derive(Debug, Clone, Default)]
+pub struct MutableGeometryArray(GeometryBuilder);
+
+#[derive(Debug, Clone)]
+enum GeometryBuilder {
+    /// Only nulls have been pushed
+    Nulls(usize),
+    Point(MutablePointArray),
+    LineString(MutableLineStringArray),
+    Polygon(MutablePolygonArray),
+    MultiPoint(MutableMultiPointArray),
+    MultiLineString(MutableMultiLineStringArray),
+    MultiPolygon(MutableMultiPolygonArray),
+    Wkb(MutableWKBArray),
+}
+
+impl Default for GeometryBuilder {
+    fn default() -> Self {
+        GeometryBuilder::Nulls(0)
+    }
+}
+
+impl GeometryBuilder {
+    /// An empty builder of `kind`.
+    fn new(kind: GeometryArrayType) -> Self {
+        match kind {
+            GeometryArrayType::Point => GeometryBuilder::Point(MutablePointArray::new()),
+            GeometryArrayType::LineString => {
+                GeometryBuilder::LineString(MutableLineStringArray::new())
+            }
+            GeometryArrayType::Polygon => GeometryBuilder::Polygon(MutablePolygonArray::new()),
+            GeometryArrayType::MultiPoint => {
+                GeometryBuilder::MultiPoint(MutableMultiPointArray::new())
+            }
+            GeometryArrayType::MultiLineString => {
+                GeometryBuilder::MultiLineString(MutableMultiLineStringArray::new())
+            }
+            GeometryArrayType::MultiPolygon => {
+                GeometryBuilder::MultiPolygon(MutableMultiPolygonArray::new())
+            }
+            GeometryArrayType::WKB => GeometryBuilder::Wkb(MutableWKBArray::new()),
+            kind @ (GeometryArrayType::Rect
+            | GeometryArrayType::Mixed
+            | GeometryArrayType::GeometryCollection) => {
+                unreachable!("no builder of {:?}", kind)
+            }
+        }
+    }
+
+    /// A builder holding the geometries of `array`.
+    fn from_array(array: GeometryArray) -> Result<Self, GeoArrowError> {
+        let mut builder = Self::new(array.geometry_type());
+        match (&mut builder, &array) {
+            (GeometryBuilder::Point(builder), GeometryArray::Point(arr)) => {
+                builder.extend_from_array(arr)
+            }
+            (GeometryBuilder::LineString(builder), GeometryArray::LineString(arr)) => {
+                builder.extend_from_array(arr)?
+            }
+            (GeometryBuilder::Polygon(builder), GeometryArray::Polygon(arr)) => {
+                builder.extend_from_array(arr)?
+            }
+            (GeometryBuilder::MultiPoint(builder), GeometryArray::MultiPoint(arr)) => {
+                builder.extend_from_array(arr)?
+            }
+            (GeometryBuilder::MultiLineString(builder), GeometryArray::MultiLineString(arr)) => {
+                builder.extend_from_array(arr)?
+            }
+            (GeometryBuilder::MultiPolygon(builder), GeometryArray::MultiPolygon(arr)) => {
+                builder.extend_from_array(arr)?
+            }
+            (GeometryBuilder::Wkb(builder), GeometryArray::WKB(arr)) => {
+                builder.extend_from_array(arr)
+            }
+            _ => unreachable!(),
+        }
+        Ok(builder)
+    }
+}
+
+impl MutableGeometryArray {
+    /// Creates a new empty [`MutableGeometryArray`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of geometries in the array.
+    pub fn len(&self) -> usize {
+        match &self.0 {
+            GeometryBuilder::Nulls(len) => *len,
+            GeometryBuilder::Point(builder) => builder.len(),
+            GeometryBuilder::LineString(builder) => builder.len(),
+            GeometryBuilder::Polygon(builder) => builder.len(),
+            GeometryBuilder::MultiPoint(builder) => builder.len(),
+            GeometryBuilder::MultiLineString(builder) => builder.len(),
+            GeometryBuilder::MultiPolygon(builder) => builder.len(),
+            GeometryBuilder::Wkb(builder) => builder.len(),
+        }
+    }
+
+    /// Whether the array is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The type of the array being built, or `None` if only nulls have been pushed.
+    pub fn geometry_type(&self) -> Option<GeometryArrayType> {
+        match &self.0 {
+            GeometryBuilder::Nulls(_) => None,
+            GeometryBuilder::Point(_) => Some(GeometryArrayType::Point),
+            GeometryBuilder::LineString(_) => Some(GeometryArrayType::LineString),
+            GeometryBuilder::Polygon(_) => Some(GeometryArrayType::Polygon),
+            GeometryBuilder::MultiPoint(_) => Some(GeometryArrayType::MultiPoint),
+            GeometryBuilder::MultiLineString(_) => Some(GeometryArrayType::MultiLineString),
+            GeometryBuilder::MultiPolygon(_) => Some(GeometryArrayType::MultiPolygon),
+            GeometryBuilder::Wkb(_) => Some(GeometryArrayType::WKB),
+        }
+    }
+
+    /// Adds a new null to the array.
+    pub fn push_null(&mut self) {
+        match &mut self.0 {
+            GeometryBuilder::Nulls(len) => *len += 1,
+            GeometryBuilder::Point(builder) => builder.push_null(),
+            GeometryBuilder::LineString(builder) => builder.push_null(),
+            GeometryBuilder::Polygon(builder) => builder.push_null(),
+            GeometryBuilder::MultiPoint(builder) => builder.push_null(),
+            GeometryBuilder::MultiLineString(builder) => builder.push_null(),
+            GeometryBuilder::MultiPolygon(builder) => builder.push_null(),
+            GeometryBuilder::Wkb(builder) => builder.push_null(),
+        }
+    }
+
+    /// Adds a new geometry to the array, or a null if `value` is `None`, promoting the array if
+    /// its type cannot hold the geometry.
+    /// # Errors
+    /// Errors if the offsets of the array would overflow.
+    pub fn push_geometry(&mut self, value: Option<geo::Geometry>) -> Result<(), GeoArrowError> {
+        let geom = match value {
+            None => {
+                self.push_null();
+                return Ok(());
+            }
+            Some(geo::Geometry::Rect(rect)) => geo::Geometry::Polygon(rect.to_polygon()),
+            Some(geom) => geom,
+        };
+
+        let kind = GeometryArrayType::of_geo(&geom);
+        let target = match self.geometry_type() {
+            None => kind,
+            Some(current) => current.promote(kind),
+        };
+        if self.geometry_type() != Some(target) {
+            self.promote(target)?;
+        }
+
+        let geom = promote_geometry(geom, target);
+        match (&mut self.0, geom) {
+            (GeometryBuilder::Point(builder), geo::Geometry::Point(geom)) => {
+                builder.push_point(Some(&geom))
+            }
+            (GeometryBuilder::LineString(builder), geo::Geometry::LineString(geom)) => {
+                builder.try_push_line_string(Some(&&geom))?
+            }
+            (GeometryBuilder::Polygon(builder), geo::Geometry::Polygon(geom)) => {
+                builder.try_push_polygon(Some(&&geom))?
+            }
+            (GeometryBuilder::MultiPoint(builder), geo::Geometry::MultiPoint(geom)) => {
+                builder.try_push_multi_point(Some(&&geom))?
+            }
+            (GeometryBuilder::MultiLineString(builder), geo::Geometry::MultiLineString(geom)) => {
+                builder.try_push_multi_line_string(Some(&&geom))?
+            }
+            (GeometryBuilder::MultiPolygon(builder), geo::Geometry::MultiPolygon(geom)) => {
+                builder.try_push_multi_polygon(Some(&&geom))?
+            }
+            (GeometryBuilder::Wkb(builder), geom) => builder.push_geo(Some(&geom)),
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Convert the geometries pushed so far to `kind`.
+    fn promote(&mut self, kind: GeometryArrayType) -> Result<(), GeoArrowError> {
+        self.0 = match std::mem::take(&mut self.0) {
+            GeometryBuilder::Nulls(len) => {
+                let mut array = MutableGeometryArray(GeometryBuilder::new(kind));
+                (0..len).for_each(|_| array.push_null());
+                array.0
+            }
+            builder => {
+                let array: GeometryArray = MutableGeometryArray(builder).into();
+                GeometryBuilder::from_array(coerce(array, kind)?)?
+            }
+        };
+        Ok(())
+    }
+}
+
+impl From<&[geo::Geometry]> for GeometryArray {
+    /// See [`GeometryArray::from_geometries`].
+    fn from(other: &[geo::Geometry]) -> Self {
+        Self::from_geometries(other)
+    }
+}
+
+impl From<MutableGeometryArray> for GeometryArray {
+    fn from(other: MutableGeometryArray) -> Self {
+        match other.0 {
+            GeometryBuilder::Nulls(len) => {
+                let mut builder = MutableWKBArray::with_capacity(len);
+                (0..len).for_each(|_| builder.push_null());
+                GeometryArray::WKB(builder.into())
+            }
+            GeometryBuilder::Point(builder) => GeometryArray::Point(builder.into()),
+            GeometryBuilder::LineString(builder) => GeometryArray::LineString(builder.into()),
+            GeometryBuilder::Polygon(builder) => GeometryArray::Polygon(builder.into()),
+            GeometryBuilder::MultiPoint(builder) => GeometryArray::MultiPoint(builder.into()),
+            GeometryBuilder::MultiLineString(builder) => {
+                GeometryArray::MultiLineString(builder.into())
+            }
+            GeometryBuilder::MultiPolygon(builder) => GeometryArray::MultiPolygon(builder.into()),
+            GeometryBuilder::Wkb(builder) => GeometryArray::WKB(builder.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::BinaryArray;
+    use geo::{line_string, point};
+
+    #[test]
+    fn get_respects_validity() {
+        let arr: PointArray = vec![Some(point!(x: 1., y: 2.)), None].into();
+        let arr = GeometryArray::Point(arr);
+
+        assert!(matches!(arr.get(0), Some(Geometry::Point(_))));
+        assert!(arr.get(1).is_none());
+        assert_eq!(
+            arr.get_as_geo(0),
+            Some(geo::Geometry::Point(point!(x: 1., y: 2.)))
+        );
+        assert_eq!(arr.get_as_geo(1), None);
+    }
+
+    #[test]
+    fn geometry_type() {
+        let arr: MultiPolygonArray = vec![geo::MultiPolygon::new(vec![])].into();
+        let arr = GeometryArray::MultiPolygon(arr);
+        assert_eq!(arr.geometry_type(), GeometryArrayType::MultiPolygon);
+        assert_eq!(arr.dimension(), Dimension::XY);
+        assert!(!arr.has_z());
+
+        // A little endian ISO WKB point with a z value, after an XY point and a null
+        let mut point_z = vec![1, 0xe9, 0x03, 0, 0];
+        for value in [1f64, 2., 3.] {
+            point_z.extend(value.to_le_bytes());
+        }
+        let wkb: WKBArray = vec![Some(geo::Geometry::Point(point!(x: 1., y: 2.))), None].into();
+        let mut values: Vec<Option<Vec<u8>>> = wkb
+            .into_arrow()
+            .iter()
+            .map(|v| v.map(<[u8]>::to_vec))
+            .collect();
+        let xy = GeometryArray::WKB(WKBArray::new(BinaryArray::from(values.clone())));
+        assert_eq!(xy.geometry_type(), GeometryArrayType::WKB);
+        assert!(!xy.has_z());
+        values.push(Some(point_z));
+        let xyz = GeometryArray::WKB(WKBArray::new(BinaryArray::from(values)));
+        assert_eq!(xyz.dimension(), Dimension::XYZ);
+        assert_eq!(
+            xyz.value_as_geo(2),
+            geo::Geometry::Point(point!(x: 1., y: 2.))
+        );
+    }
+
+    #[test]
+    fn mutable_promotes() {
+        let mut builder = MutableGeometryArray::new();
+        builder.push_null();
+        assert_eq!(builder.geometry_type(), None);
+        builder
+            .push_geometry(Some(geo::Geometry::Point(point!(x: 1., y: 2.))))
+            .unwrap();
+        assert_eq!(builder.geometry_type(), Some(GeometryArrayType::Point));
+
+        let multi_point = geo::MultiPoint::new(vec![point!(x: 3., y: 4.), point!(x: 5., y: 6.)]);
+        builder
+            .push_geometry(Some(geo::Geometry::MultiPoint(multi_point.clone())))
+            .unwrap();
+        assert_eq!(builder.geometry_type(), Some(GeometryArrayType::MultiPoint));
+        assert_eq!(builder.len(), 3);
+        let arr: GeometryArray = builder.clone().into();
+        assert!(arr.is_null(0));
+        assert_eq!(
+            arr.get_as_geo(1),
+            Some(geo::Geometry::MultiPoint(geo::MultiPoint::new(vec![
+                point!(x: 1., y: 2.)
+            ])))
+        );
+
+        let line = line_string![(x: 0., y: 0.), (x: 1., y: 1.)];
+        builder
+            .push_geometry(Some(geo::Geometry::LineString(line.clone())))
+            .unwrap();
+        builder.push_geometry(None).unwrap();
+        let arr: GeometryArray = builder.into();
+        assert_eq!(arr.geometry_type(), GeometryArrayType::WKB);
+        assert_eq!(arr.len(), 5);
+        assert!(arr.is_null(0) && arr.is_null(4));
+        assert_eq!(
+            arr.get_as_geo(2),
+            Some(geo::Geometry::MultiPoint(multi_point))
+        );
+        assert_eq!(arr.get_as_geo(3), Some(geo::Geometry::LineString(line)));
+
+        let mut nulls = MutableGeometryArray::new();
+        nulls.push_null();
+        let nulls: GeometryArray = nulls.into();
+        assert_eq!(nulls.geometry_type(), GeometryArrayType::WKB);
+        assert!(nulls.is_null(0));
+    }
+
+    #[test]
+    fn from_geometries() {
+        let p = |x: f64| geo::Geometry::Point(point!(x: x, y: 0.));
+        let square = geo::Rect::new(geo::coord! { x: 0., y: 0. }, geo::coord! { x: 1., y: 1. });
+        let multi = geo::Geometry::MultiPoint(geo::MultiPoint::new(vec![point!(x: 2., y: 0.)]));
+
+        let arr = GeometryArray::from_geometries(&[p(0.), p(1.)]);
+        assert_eq!(arr.geometry_type(), GeometryArrayType::Point);
+
+        let arr: GeometryArray = [p(0.), multi.clone()].as_slice().into();
+        assert_eq!(arr.geometry_type(), GeometryArrayType::MultiPoint);
+        assert_eq!(arr.value_as_geo(1), multi);
+
+        let rects = [geo::Geometry::Rect(square), geo::Geometry::Rect(square)];
+        let arr = GeometryArray::from_geometries(&rects);
+        assert_eq!(arr.geometry_type(), GeometryArrayType::Rect);
+        let arr = GeometryArray::from_geometries(&[
+            geo::Geometry::Rect(square),
+            geo::Geometry::Polygon(square.to_polygon()),
+        ]);
+        assert_eq!(arr.geometry_type(), GeometryArrayType::Polygon);
+
+        let line = geo::Geometry::LineString(line_string![(x: 0., y: 0.), (x: 1., y: 1.)]);
+        let arr = GeometryArray::from_geometries(&[p(0.), line.clone()]);
+        assert_eq!(arr.geometry_type(), GeometryArrayType::Mixed);
+        assert_eq!(arr.value_as_geo(1), line);
+
+        let collection = geo::Geometry::GeometryCollection(geo::GeometryCollection(vec![p(0.)]));
+        let arr = GeometryArray::from_geometries(&[p(0.), collection.clone()]);
+        assert_eq!(arr.geometry_type(), GeometryArrayType::WKB);
+        assert_eq!(arr.value_as_geo(1), collection);
+
+        assert_eq!(
+            GeometryArray::from_geometries(&[]).geometry_type(),
+            GeometryArrayType::WKB
+        );
+    }
+
+    #[test]
+    fn iter_geo() {
+        let line = line_string![(x: 0., y: 0.), (x: 1., y: 1.)];
+        let arr: LineStringArray = vec![None, Some(line.clone())].into();
+        let geoms: Vec<_> = GeometryArray::LineString(arr).iter_geo().collect();
+        assert_eq!(geoms, vec![None, Some(geo::Geometry::LineString(line))]);
+    }
+
+    #[test]
+    fn from_arrow_rect() {
+        let rect = geo::Rect::new(geo::coord! { x: 0., y: 0. }, geo::coord! { x: 1., y: 2. });
+        let arr: RectArray = vec![rect].into();
+        let arr = GeometryArray::from_arrow(&arr.into_arrow(), false);
+        assert!(matches!(arr, GeometryArray::Rect(_)));
+        assert_eq!(arr.get_as_geo(0), Some(geo::Geometry::Rect(rect)));
+
+        // Four coordinate fields are a 4D point, not a rect
+        let coords: Vec<_> = ["x", "y", "z", "m"]
+            .iter()
+            .map(|name| Field::new(*name, DataType::Float64, false))
+            .collect();
+        let values = (0..4)
+            .map(|_| arrow2::array::Float64Array::from_slice([0.]).boxed())
+            .collect();
+        let points = StructArray::new(DataType::Struct(coords), values, None);
+        assert!(matches!(
+            GeometryArray::try_from_arrow(&points, false),
+            Err(GeoArrowError::UnsupportedDimension { dimension: 4 })
+        ));
+    }
+
+    #[test]
+    fn from_arrow_geometry_collection() {
+        let collection = geo::GeometryCollection(vec![
+            geo::Geometry::Point(point!(x: 0., y: 1.)),
+            geo::Geometry::LineString(line_string![(x: 0., y: 0.), (x: 1., y: 1.)]),
+        ]);
+        let arr: GeometryCollectionArray = vec![Some(collection.clone()), None].try_into().unwrap();
+        let arr = arr.into_arrow();
+
+        let mut metadata = arrow2::datatypes::Metadata::new();
+        metadata.insert(
+            EXTENSION_NAME_KEY.to_string(),
+            GeometryArrayType::GeometryCollection
+                .extension_name()
+                .unwrap()
+                .to_string(),
+        );
+        let field = Field::new("geometry", arr.data_type().clone(), true).with_metadata(metadata);
+        let output = GeometryArray::from_arrow_field(&field, &arr).unwrap();
+        assert!(matches!(output, GeometryArray::WKB(_)));
+        assert_eq!(
+            output.get_as_geo(0),
+            Some(geo::Geometry::GeometryCollection(collection))
+        );
+        assert!(output.is_null(1));
+    }
+
+    #[test]
+    fn from_arrow_field() {
+        let line = line_string![(x: 0., y: 0.), (x: 1., y: 1.)];
+        let arr: LineStringArray = vec![line.clone()].into();
+        let arr = arr.into_arrow();
+
+        let field = Field::new("geometry", arr.data_type().clone(), true);
+        let output = GeometryArray::from_arrow_field(&field, &arr).unwrap();
+        assert!(matches!(output, GeometryArray::LineString(_)));
+        assert_eq!(output.extension_name(), Some("geoarrow.linestring"));
+
+        let mut metadata = arrow2::datatypes::Metadata::new();
+        metadata.insert(
+            EXTENSION_NAME_KEY.to_string(),
+            "geoarrow.multipoint".to_string(),
+        );
+        let field = field.with_metadata(metadata);
+        let output = GeometryArray::from_arrow_field(&field, &arr).unwrap();
+        assert!(matches!(output, GeometryArray::MultiPoint(_)));
+        assert_eq!(
+            output.get_as_geo(0),
+            Some(geo::Geometry::MultiPoint(line.into_points().into()))
+        );
+
+        // The extension name must match the layout of the array
+        let mut metadata = arrow2::datatypes::Metadata::new();
+        metadata.insert(EXTENSION_NAME_KEY.to_string(), "geoarrow.point".to_string());
+        let field = Field::new("geometry", arr.data_type().clone(), true).with_metadata(metadata);
+        assert!(GeometryArray::from_arrow_field(&field, &arr).is_err());
+
+        // Arrays without a geometry layout are errors rather than panics
+        let ints = arrow2::array::Int32Array::from_slice([0, 1]);
+        let field = Field::new("geometry", ints.data_type().clone(), true);
+        assert!(matches!(
+            GeometryArray::from_arrow_field(&field, &ints),
+            Err(GeoArrowError::NotYetImplemented(_))
+        ));
+        let floats = ListArray::<i64>::new_null(
+            DataType::LargeList(Box::new(Field::new("item", DataType::Float64, true))),
+            1,
+        );
+        assert!(GeometryArray::try_from_arrow(&floats, false).is_err());
+    }
+
+    #[test]
+    fn into_arrow_field() {
+        let points: PointArray = vec![point!(x: 0., y: 1.)].into();
+        let arr = GeometryArray::MultiPoint(points.into()).with_crs(Some("EPSG:4326"));
+        let (field, arr) = arr.into_arrow_field("geom");
+        assert_eq!(field.name, "geom");
+        assert_eq!(field.data_type(), arr.data_type());
+        assert_eq!(
+            field.metadata.get(EXTENSION_NAME_KEY).unwrap(),
+            "geoarrow.multipoint"
+        );
+        assert_eq!(
+            field.metadata.get(EXTENSION_METADATA_KEY).unwrap(),
+            r#"{"crs":"EPSG:4326"}"#
+        );
+
+        let roundtrip = GeometryArray::from_arrow_field(&field, arr.as_ref()).unwrap();
+        assert!(matches!(roundtrip, GeometryArray::MultiPoint(_)));
+        assert_eq!(roundtrip.crs(), Some("EPSG:4326"));
+
+        assert_eq!(
+            extension_metadata(Some(r#"{"type": "GeographicCRS"}"#)),
+            r#"{"crs":{"type":"GeographicCRS"}}"#
+        );
+        assert_eq!(
+            extension_metadata(Some(r#"GEOGCS["WGS 84"]"#)),
+            r#"{"crs":"GEOGCS[\"WGS 84\"]"}"#
+        );
+        assert_eq!(extension_metadata(None), "{}");
+    }
+
+    #[test]
+    #[cfg(feature = "ipc")]
+    fn extension_fields() {
+        let points: PointArray = vec![point!(x: 0., y: 1.)].into();
+        let arr = GeometryArray::Point(points).with_crs(Some("EPSG:4326"));
+        let (field, _) = arr.into_arrow_field("geom");
+
+        let extension = extension_field(field.clone());
+        assert!(matches!(
+            extension.data_type(),
+            DataType::Extension(name, _, Some(_)) if name == "geoarrow.point"
+        ));
+        assert!(extension.metadata.is_empty());
+        assert_eq!(storage_field(extension), field);
+
+        let plain = Field::new("id", DataType::Int32, false);
+        assert_eq!(extension_field(plain.clone()), plain);
+        assert_eq!(storage_field(plain.clone()), plain);
+    }
+
+    #[test]
+    fn crs_roundtrip() {
+        for crs in [
+            "EPSG:4326",
+            r#"GEOGCS["WGS 84",UNIT["degree",0.0174532925199433]]"#,
+            "line\nbreak\u{1}",
+            r#"{"name":"WGS 84, \"quoted\" {braced}","type":"GeographicCRS"}"#,
+        ] {
+            let metadata = extension_metadata(Some(crs));
+            assert_eq!(crs_from_extension_metadata(&metadata).as_deref(), Some(crs));
+        }
+
+        // PROJJSON is parsed, so its whitespace and key order are not kept
+        let projjson = r#"{"type": "GeographicCRS", "name": "WGS 84"}"#;
+        let metadata = extension_metadata(Some(projjson));
+        let parsed: Value = serde_json::from_str(&metadata).unwrap();
+        assert_eq!(
+            parsed["crs"],
+            serde_json::from_str::<Value>(projjson).unwrap()
+        );
+
+        let metadata = r#"{"edges": "spherical", "crs": "OGC:CRS84", "other": [1, {}]}"#;
+        assert_eq!(
+            crs_from_extension_metadata(metadata).as_deref(),
+            Some("OGC:CRS84")
+        );
+        assert_eq!(crs_from_extension_metadata(r#"{"crs": null}"#), None);
+        assert_eq!(crs_from_extension_metadata("{}"), None);
+        assert_eq!(crs_from_extension_metadata(""), None);
+    }
+
+    #[test]
+    fn from_arrow_union() {
+        let geoms = vec![
+            Some(geo::Geometry::Point(point!(x: 0., y: 1.))),
+            None,
+            Some(geo::Geometry::LineString(
+                line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+            )),
+        ];
+        let arr = MixedGeometryArray::try_from(geoms.clone()).unwrap();
+        let arr = GeometryArray::from_arrow(&arr.into_arrow(), false);
+        assert!(matches!(arr, GeometryArray::Mixed(_)));
+        assert_eq!(arr.iter_geo().collect::<Vec<_>>(), geoms);
+    }
+}
@@ -0,0 +1,239 @@
+//! Logical equality of geometry arrays and scalars.
+//!
+//! Two arrays are equal when they hold the same geometries and nulls in the same slots, and have
+//! the same CRS. How the values are laid out in memory, such as the offset a sliced array starts
+//! at or the values stored under null slots, is not compared. Geometries with m values never
+//! equal geometries without, and missing m values (`NaN`) equal each other.
+
+use crate::enum_::Geometry;
+use crate::{
+    GeometryArray, GeometryArrayTrait, GeometryCollection, GeometryCollectionArray, LineString,
+    LineStringArray, MixedGeometryArray, MultiLineString, MultiLineStringArray, MultiPoint,
+    MultiPointArray, MultiPolygon, MultiPolygonArray, Point, PointArray, Polygon, PolygonArray,
+    Rect, RectArray, WKBArray, WKB,
+};
+use arrow2::buffer::Buffer;
+use arrow2::offset::OffsetsBuffer;
+
+/// The coordinates of a scalar, nested in any number of levels of lists.
+struct Nested<'a> {
+    x: &'a Buffer<f64>,
+    y: &'a Buffer<f64>,
+    m: Option<&'a Buffer<f64>>,
+    /// The offsets of each level of lists, outermost first
+    offsets: &'a [&'a OffsetsBuffer<i64>],
+}
+
+impl Nested<'_> {
+    /// Whether element `left_index` of `level` equals element `right_index` of `right`.
+    fn eq_at(&self, left_index: usize, right: &Nested, right_index: usize, level: usize) -> bool {
+        if level == self.offsets.len() {
+            return self.x[left_index] == right.x[right_index]
+                && self.y[left_index] == right.y[right_index]
+                && self.m.zip(right.m).map_or(true, |(left, right)| {
+                    let (left, right) = (left[left_index], right[right_index]);
+                    left == right || (left.is_nan() && right.is_nan())
+                });
+        }
+
+        let (left_start, left_end) = self.offsets[level].start_end(left_index);
+        let (right_start, right_end) = right.offsets[level].start_end(right_index);
+        left_end - left_start == right_end - right_start
+            && (0..left_end - left_start)
+                .all(|i| self.eq_at(left_start + i, right, right_start + i, level + 1))
+    }
+}
+
+/// Implement `PartialEq` for a scalar from the offsets of its levels of lists.
+macro_rules! impl_scalar_eq {
+    ($scalar:ident, [$($offsets:ident),*]) => {
+        impl PartialEq for $scalar<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                let left = Nested {
+                    x: self.x,
+                    y: self.y,
+                    m: self.m,
+                    offsets: &[$(self.$offsets),*],
+                };
+                let right = Nested {
+                    x: other.x,
+                    y: other.y,
+                    m: other.m,
+                    offsets: &[$(other.$offsets),*],
+                };
+                self.m.is_some() == other.m.is_some()
+                    && left.eq_at(self.geom_index, &right, other.geom_index, 0)
+            }
+        }
+    };
+}
+
+impl_scalar_eq!(Point, []);
+impl_scalar_eq!(LineString, [geom_offsets]);
+impl_scalar_eq!(Polygon, [geom_offsets, ring_offsets]);
+impl_scalar_eq!(MultiPoint, [geom_offsets]);
+impl_scalar_eq!(MultiLineString, [geom_offsets, ring_offsets]);
+impl_scalar_eq!(MultiPolygon, [geom_offsets, polygon_offsets, ring_offsets]);
+
+impl PartialEq for Rect<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.lower() == other.lower() && self.upper() == other.upper()
+    }
+}
+
+/// WKB geometries are equal when their bytes are, so the same geometry written with different
+/// byte orders is not.
+impl PartialEq for WKB<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+/// Geometry collections are equal when their members are, in order.
+impl PartialEq for GeometryCollection<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_geometries() == other.num_geometries()
+            && self
+                .geometries()
+                .zip(other.geometries())
+                .all(|(left, right)| left == right)
+    }
+}
+
+/// Geometries of different types are never equal, even if they hold the same coordinates.
+impl PartialEq for Geometry<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Geometry::Point(left), Geometry::Point(right)) => left == right,
+            (Geometry::LineString(left), Geometry::LineString(right)) => left == right,
+            (Geometry::Polygon(left), Geometry::Polygon(right)) => left == right,
+            (Geometry::MultiPoint(left), Geometry::MultiPoint(right)) => left == right,
+            (Geometry::MultiLineString(left), Geometry::MultiLineString(right)) => left == right,
+            (Geometry::MultiPolygon(left), Geometry::MultiPolygon(right)) => left == right,
+            (Geometry::WKB(left), Geometry::WKB(right)) => left == right,
+            (Geometry::Rect(left), Geometry::Rect(right)) => left == right,
+            (Geometry::GeometryCollection(left), Geometry::GeometryCollection(right)) => {
+                left == right
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Implement `PartialEq` for an array by comparing its scalars slot by slot.
+macro_rules! impl_array_eq {
+    ($array:ty) => {
+        impl PartialEq for $array {
+            fn eq(&self, other: &Self) -> bool {
+                self.len() == other.len()
+                    && self.crs() == other.crs()
+                    && (0..self.len()).all(|i| self.get(i) == other.get(i))
+            }
+        }
+    };
+}
+
+impl_array_eq!(PointArray);
+impl_array_eq!(LineStringArray);
+impl_array_eq!(PolygonArray);
+impl_array_eq!(MultiPointArray);
+impl_array_eq!(MultiLineStringArray);
+impl_array_eq!(MultiPolygonArray);
+impl_array_eq!(WKBArray);
+impl_array_eq!(RectArray);
+impl_array_eq!(MixedGeometryArray);
+impl_array_eq!(GeometryCollectionArray);
+
+/// Arrays of different types are never equal; see [`array_eq`] to compare them.
+impl PartialEq for GeometryArray {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (GeometryArray::Point(left), GeometryArray::Point(right)) => left == right,
+            (GeometryArray::LineString(left), GeometryArray::LineString(right)) => left == right,
+            (GeometryArray::Polygon(left), GeometryArray::Polygon(right)) => left == right,
+            (GeometryArray::MultiPoint(left), GeometryArray::MultiPoint(right)) => left == right,
+            (GeometryArray::MultiLineString(left), GeometryArray::MultiLineString(right)) => {
+                left == right
+            }
+            (GeometryArray::MultiPolygon(left), GeometryArray::MultiPolygon(right)) => {
+                left == right
+            }
+            (GeometryArray::WKB(left), GeometryArray::WKB(right)) => left == right,
+            (GeometryArray::Rect(left), GeometryArray::Rect(right)) => left == right,
+            (GeometryArray::Mixed(left), GeometryArray::Mixed(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+/// Whether two arrays hold the same geometries, whatever their layout.
+///
+/// Unlike `==`, arrays of different types are compared geometry by geometry as [`geo`]
+/// geometries, so for example a [`WKBArray`] equals the [`PointArray`] it was encoded from, and
+/// a [`MixedGeometryArray`] equals a [`PolygonArray`] holding the same polygons. Nulls must be
+/// in the same slots, and CRSes must be equal.
+pub fn array_eq(left: &GeometryArray, right: &GeometryArray) -> bool {
+    left.len() == right.len()
+        && left.crs() == right.crs()
+        && (0..left.len()).all(|i| left.get_as_geo(i) == right.get_as_geo(i))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point, polygon};
+
+    #[test]
+    fn sliced_arrays() {
+        let ls0 = line_string![(x: 0., y: 1.), (x: 1., y: 2.)];
+        let ls1 = line_string![(x: 3., y: 4.), (x: 5., y: 6.)];
+        let arr: LineStringArray = vec![Some(ls0.clone()), None, Some(ls1.clone())].into();
+        let sliced = arr.sliced_range(1..3);
+        let rebuilt: LineStringArray = vec![None, Some(ls1.clone())].into();
+        assert_eq!(sliced, rebuilt);
+        assert_ne!(arr, rebuilt);
+        assert_ne!(sliced, rebuilt.clone().with_crs(Some("EPSG:4326")));
+
+        let other: LineStringArray = vec![Some(ls1), Some(ls0)].into();
+        assert_ne!(sliced, other.sliced_range(0..2));
+        assert_eq!(arr.value(0), other.value(1));
+    }
+
+    #[test]
+    fn across_layouts() {
+        let p0 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let polygons: PolygonArray = vec![Some(p0.clone()), None].into();
+        let wkb: WKBArray = polygons.clone().into();
+        let native = GeometryArray::Polygon(polygons);
+        let wkb = GeometryArray::WKB(wkb);
+        assert!(native != wkb);
+        assert!(array_eq(&native, &wkb));
+
+        let points: PointArray = vec![point!(x: 0., y: 0.), point!(x: 1., y: 0.)].into();
+        assert!(!array_eq(&native, &GeometryArray::Point(points)));
+    }
+
+    #[test]
+    fn m_values() {
+        let ls = line_string![(x: 0., y: 1.), (x: 1., y: 2.)];
+        let arr: LineStringArray = vec![ls.clone()].into();
+        let with_m = arr.clone().with_m(vec![0., 1.].into()).unwrap();
+        assert_ne!(arr, with_m);
+        assert_ne!(with_m, arr);
+        assert_eq!(with_m, with_m.clone());
+        assert_ne!(with_m, arr.clone().with_m(vec![0., 2.].into()).unwrap());
+
+        // Missing m values are equal
+        let nan = arr.with_m(vec![f64::NAN, 1.].into()).unwrap();
+        assert_eq!(nan, nan.clone());
+
+        // Empty geometries differ too if only one has m values
+        let empty: MultiPointArray = vec![geo::MultiPoint(vec![])].into();
+        let empty_m = empty.clone().with_m(Vec::<f64>::new().into()).unwrap();
+        assert_ne!(empty, empty_m);
+
+        let points: PointArray = vec![point!(x: 0., y: 1.)].into();
+        let points_m = points.clone().with_m(vec![5.].into()).unwrap();
+        assert_ne!(points.value(0), points_m.value(0));
+    }
+}
@@ -1,4 +1,5 @@
 //! Defines [`Error`], representing all errors returned by this crate.
+use arrow2::datatypes::DataType;
 use std::fmt::Debug;
 use thiserror::Error;
 
@@ -13,6 +14,58 @@ pub enum GeoArrowError {
     #[error("General error: {0}")]
     General(String),
 
+    /// Returned when a column's data type differs from the one declared for it.
+    #[error("Schema mismatch in column \"{column}\": expected {expected:?}, found {found:?}")]
+    SchemaMismatch {
+        column: String,
+        expected: Box<DataType>,
+        found: Box<DataType>,
+    },
+
+    /// Returned when an Arrow array is not of the concrete type required to read it.
+    #[error("Failed to downcast array of type {found:?} to {expected}")]
+    Downcast {
+        expected: &'static str,
+        found: DataType,
+    },
+
+    /// Returned when an offset no longer fits in the offset type while building geometry `row`.
+    #[error("Offset overflow at row {row}")]
+    OffsetOverflow { row: usize },
+
+    /// Returned when the WKB geometry at `row` cannot be parsed.
+    #[error("Invalid WKB at row {row}: {message}")]
+    InvalidWkb { row: usize, message: String },
+
+    /// Returned when the WKT geometry at `row` cannot be parsed.
+    #[error("Invalid WKT at row {row}: {message}")]
+    InvalidWkt { row: usize, message: String },
+
+    /// Returned when the geometry at `row` is not of the type required by the target array.
+    #[error("Expected a {expected} at row {row}, found a {found}")]
+    UnexpectedGeometryType {
+        row: usize,
+        expected: &'static str,
+        found: &'static str,
+    },
+
+    /// Returned when coordinates have a dimension other than the ones supported.
+    #[error("Unsupported coordinate dimension: {dimension}")]
+    UnsupportedDimension { dimension: usize },
+
+    /// Returned when a length or area in linear units is requested for coordinates in a
+    /// geographic CRS, whose units are degrees.
+    #[error("Cannot compute the {operation} in linear units of geometries in a geographic CRS; reproject them or use a geodesic kernel")]
+    GeographicCrs { operation: &'static str },
+
+    /// Returned when combining arrays in different coordinate reference systems.
+    #[error("Cannot combine geometries in CRS {left} with geometries in CRS {right}")]
+    CrsMismatch { left: String, right: String },
+
+    /// Wrapper for an IO error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     /// Wrapper for an error triggered by a dependency
     #[error(transparent)]
     External(#[from] anyhow::Error),
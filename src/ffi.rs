@@ -0,0 +1,78 @@
+//! Move geometry arrays across the [Arrow C data interface].
+//!
+//! The C data interface shares buffers between libraries in the same process without copying
+//! them, so geometry arrays can be handed to and from pyarrow, DuckDB or geoarrow-c. The GeoArrow
+//! extension name and CRS travel in the `ARROW:extension:name` and `ARROW:extension:metadata`
+//! metadata of the exported schema.
+//!
+//! [Arrow C data interface]: https://arrow.apache.org/docs/format/CDataInterface.html
+
+use crate::enum_::storage_field;
+use crate::error::GeoArrowError;
+use crate::GeometryArray;
+use arrow2::ffi::{
+    export_array_to_c, export_field_to_c, import_array_from_c, import_field_from_c, ArrowArray,
+    ArrowSchema,
+};
+
+/// Export `array` to the C data interface as a field named `name` and its array.
+///
+/// The buffers of `array` are not copied: they are released when the consumer calls the release
+/// callback of the exported [`ArrowArray`].
+pub fn to_ffi(array: GeometryArray, name: &str) -> (ArrowSchema, ArrowArray) {
+    let (field, array) = array.into_arrow_field(name);
+    (export_field_to_c(&field), export_array_to_c(array))
+}
+
+/// Import a geometry array from the C data interface, taking ownership of `array`.
+///
+/// The geometry type and CRS are read from the extension metadata of `schema`, as in
+/// [`GeometryArray::from_arrow_field`]. The buffers of `array` are not copied.
+///
+/// # Safety
+/// `schema` and `array` must be valid C data interface structs, and `array` must have the data
+/// type described by `schema`.
+/// # Errors
+/// Errors if the structs cannot be imported, or if they do not describe a geometry array.
+pub unsafe fn from_ffi(
+    schema: &ArrowSchema,
+    array: ArrowArray,
+) -> Result<GeometryArray, GeoArrowError> {
+    let field = import_field_from_c(schema).map_err(|err| GeoArrowError::External(err.into()))?;
+    let field = storage_field(field);
+    let array = import_array_from_c(array, field.data_type().clone())
+        .map_err(|err| GeoArrowError::External(err.into()))?;
+    GeometryArray::from_arrow_field(&field, array.as_ref())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArrayTrait, PointArray, PolygonArray};
+    use geo::{point, polygon};
+
+    fn round_trip(array: GeometryArray) -> GeometryArray {
+        let (schema, array) = to_ffi(array, "geometry");
+        unsafe { from_ffi(&schema, array) }.unwrap()
+    }
+
+    #[test]
+    fn round_trips() {
+        let points: PointArray = vec![Some(point!(x: 1., y: 2.)), None].into();
+        let output = round_trip(GeometryArray::Point(points).with_crs(Some("EPSG:4326")));
+        assert!(matches!(output, GeometryArray::Point(_)));
+        assert_eq!(output.crs(), Some("EPSG:4326"));
+        assert_eq!(output.get_as_geo(0), Some(point!(x: 1., y: 2.).into()));
+        assert!(output.is_null(1));
+
+        let polygon = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)];
+        let polygons: PolygonArray = vec![polygon.clone()].into();
+        let output = round_trip(GeometryArray::MultiPolygon(polygons.into()));
+        assert!(matches!(output, GeometryArray::MultiPolygon(_)));
+        assert_eq!(output.crs(), None);
+        assert_eq!(
+            output.get_as_geo(0),
+            Some(geo::MultiPolygon::new(vec![polygon]).into())
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use super::{
+    LineStringTrait, MultiLineStringTrait, MultiPointTrait, MultiPolygonTrait, PointTrait,
+    PolygonTrait, RectTrait,
+};
+use geo::{Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon, Rect};
+
+/// A geometry as one of the concrete geometry traits.
+#[derive(Debug)]
+pub enum GeometryType<P, L, Y, MP, ML, MY, R> {
+    Point(P),
+    LineString(L),
+    Polygon(Y),
+    MultiPoint(MP),
+    MultiLineString(ML),
+    MultiPolygon(MY),
+    Rect(R),
+    /// A geometry without a geometry trait: a line, triangle or geometry collection, or a WKB
+    /// geometry, which must be parsed first.
+    Unsupported,
+}
+
+pub trait GeometryTrait<'a>: Send + Sync {
+    type Point: 'a + PointTrait;
+    type LineString: 'a + LineStringTrait<'a>;
+    type Polygon: 'a + PolygonTrait<'a>;
+    type MultiPoint: 'a + MultiPointTrait<'a>;
+    type MultiLineString: 'a + MultiLineStringTrait<'a>;
+    type MultiPolygon: 'a + MultiPolygonTrait<'a>;
+    type Rect: 'a + RectTrait;
+
+    /// The type of this geometry, holding it as the matching geometry trait
+    #[allow(clippy::type_complexity)]
+    fn as_type(
+        &'a self,
+    ) -> GeometryType<
+        Self::Point,
+        Self::LineString,
+        Self::Polygon,
+        Self::MultiPoint,
+        Self::MultiLineString,
+        Self::MultiPolygon,
+        Self::Rect,
+    >;
+}
+
+impl<'a> GeometryTrait<'a> for Geometry<f64> {
+    type Point = &'a Point;
+    type LineString = &'a LineString;
+    type Polygon = &'a Polygon;
+    type MultiPoint = &'a MultiPoint;
+    type MultiLineString = &'a MultiLineString;
+    type MultiPolygon = &'a MultiPolygon;
+    type Rect = &'a Rect;
+
+    #[allow(clippy::type_complexity)]
+    fn as_type(
+        &'a self,
+    ) -> GeometryType<
+        &'a Point,
+        &'a LineString,
+        &'a Polygon,
+        &'a MultiPoint,
+        &'a MultiLineString,
+        &'a MultiPolygon,
+        &'a Rect,
+    > {
+        match self {
+            Geometry::Point(geom) => GeometryType::Point(geom),
+            Geometry::LineString(geom) => GeometryType::LineString(geom),
+            Geometry::Polygon(geom) => GeometryType::Polygon(geom),
+            Geometry::MultiPoint(geom) => GeometryType::MultiPoint(geom),
+            Geometry::MultiLineString(geom) => GeometryType::MultiLineString(geom),
+            Geometry::MultiPolygon(geom) => GeometryType::MultiPolygon(geom),
+            Geometry::Rect(geom) => GeometryType::Rect(geom),
+            Geometry::Line(_) | Geometry::Triangle(_) | Geometry::GeometryCollection(_) => {
+                GeometryType::Unsupported
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enum_::GeometryArray;
+    use crate::{GeometryArrayTrait, PolygonArray};
+    use geo::{point, polygon};
+
+    /// The number of interior rings of a polygon, or `None` for other geometries
+    fn num_interiors<'a>(geom: &'a impl GeometryTrait<'a>) -> Option<usize> {
+        match geom.as_type() {
+            GeometryType::Polygon(polygon) => Some(polygon.num_interiors()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn as_type() {
+        let p0 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        assert_eq!(num_interiors(&Geometry::Polygon(p0.clone())), Some(0));
+        assert_eq!(num_interiors(&Geometry::Point(point!(x: 0., y: 0.))), None);
+
+        let arr = GeometryArray::Polygon(PolygonArray::from(vec![p0]));
+        let scalar = arr.value(0);
+        assert_eq!(num_interiors(&scalar), Some(0));
+
+        let rect = Rect::new((0., 1.), (2., 3.));
+        match Geometry::Rect(rect).as_type() {
+            GeometryType::Rect(rect) => assert_eq!(rect.max().x_y(), (2., 3.)),
+            _ => unreachable!(),
+        }
+
+        let line = Geometry::Line(geo::Line::new((0., 0.), (1., 1.)));
+        assert!(matches!(line.as_type(), GeometryType::Unsupported));
+        let wkb = GeometryArray::WKB(vec![Some(line)].into());
+        assert!(matches!(wkb.value(0).as_type(), GeometryType::Unsupported));
+    }
+}
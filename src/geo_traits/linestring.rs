@@ -7,46 +7,30 @@ pub trait LineStringTrait<'a>: Send + Sync {
     type Iter: Iterator<Item = Self::ItemType>;
 
     /// An iterator over the points in this LineString
-    fn points(&'a self) -> Self::Iter;
+    fn points(&self) -> Self::Iter;
 
     /// The number of points in this LineString
-    fn num_points(&'a self) -> usize;
+    fn num_points(&self) -> usize;
 
     /// Access to a specified point in this LineString
     /// Will return None if the provided index is out of bounds
-    fn point(&'a self, i: usize) -> Option<Self::ItemType>;
+    fn point(&self, i: usize) -> Option<Self::ItemType>;
 }
 
-impl<'a> LineStringTrait<'a> for geo::LineString<f64> {
+impl<'a> LineStringTrait<'a> for &'a geo::LineString<f64> {
     type ItemType = geo::Coord;
     type Iter = Cloned<Iter<'a, Self::ItemType>>;
 
-    fn points(&'a self) -> Self::Iter {
-        self.0.iter().cloned()
+    fn points(&self) -> Self::Iter {
+        let line_string: &'a geo::LineString = self;
+        line_string.0.iter().cloned()
     }
 
     fn num_points(&self) -> usize {
         self.0.len()
     }
 
-    fn point(&'a self, i: usize) -> Option<Self::ItemType> {
-        self.0.get(i).cloned()
-    }
-}
-
-impl<'a> LineStringTrait<'a> for &geo::LineString<f64> {
-    type ItemType = geo::Coord;
-    type Iter = Cloned<Iter<'a, Self::ItemType>>;
-
-    fn points(&'a self) -> Self::Iter {
-        self.0.iter().cloned()
-    }
-
-    fn num_points(&self) -> usize {
-        self.0.len()
-    }
-
-    fn point(&'a self, i: usize) -> Option<Self::ItemType> {
+    fn point(&self, i: usize) -> Option<Self::ItemType> {
         self.0.get(i).cloned()
     }
 }
@@ -1,13 +1,42 @@
+//! Traits for reading geometries without knowing how they are stored.
+//!
+//! The scalars of geometry arrays (such as [`Polygon`](crate::Polygon)) implement these traits by
+//! reading straight from the array's coordinate and offset buffers, and references to [`geo`]
+//! geometries implement them over their `Vec`s. A kernel written against the traits runs on
+//! either without allocating:
+//!
+//! ```
+//! use geoarrow::geo_traits::{LineStringTrait, PointTrait, PolygonTrait};
+//! use geoarrow::{GeometryArrayTrait, PolygonArray};
+//! use geo::polygon;
+//!
+//! fn exterior_max_x<'a>(polygon: &impl PolygonTrait<'a>) -> f64 {
+//!     let exterior = polygon.exterior();
+//!     (0..exterior.num_points())
+//!         .map(|i| exterior.point(i).unwrap().x())
+//!         .fold(f64::NEG_INFINITY, f64::max)
+//! }
+//!
+//! let polygon = polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+//! let array: PolygonArray = vec![polygon.clone()].into();
+//! assert_eq!(exterior_max_x(&array.value(0)), 2.);
+//! assert_eq!(exterior_max_x(&&polygon), 2.);
+//! ```
+
+pub use geometry::{GeometryTrait, GeometryType};
 pub use linestring::LineStringTrait;
 pub use multilinestring::MultiLineStringTrait;
 pub use multipoint::MultiPointTrait;
 pub use multipolygon::MultiPolygonTrait;
 pub use point::PointTrait;
 pub use polygon::PolygonTrait;
+pub use rect::RectTrait;
 
+mod geometry;
 pub mod linestring;
 mod multilinestring;
 pub mod multipoint;
 mod multipolygon;
 pub mod point;
 pub mod polygon;
+mod rect;
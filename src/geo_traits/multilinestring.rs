@@ -1,4 +1,3 @@
-use std::iter::Cloned;
 use std::slice::Iter;
 
 use super::LineStringTrait;
@@ -9,29 +8,31 @@ pub trait MultiLineStringTrait<'a>: Send + Sync {
     type Iter: Iterator<Item = Self::ItemType>;
 
     /// An iterator over the LineStrings in this MultiLineString
-    fn lines(&'a self) -> Self::Iter;
+    fn lines(&self) -> Self::Iter;
 
     /// The number of lines in this MultiLineString
-    fn num_lines(&'a self) -> usize;
+    fn num_lines(&self) -> usize;
 
     /// Access to a specified line in this MultiLineString
     /// Will return None if the provided index is out of bounds
-    fn line(&'a self, i: usize) -> Option<Self::ItemType>;
+    fn line(&self, i: usize) -> Option<Self::ItemType>;
 }
 
-impl<'a> MultiLineStringTrait<'a> for MultiLineString<f64> {
-    type ItemType = LineString;
-    type Iter = Cloned<Iter<'a, Self::ItemType>>;
+impl<'a> MultiLineStringTrait<'a> for &'a MultiLineString<f64> {
+    type ItemType = &'a LineString;
+    type Iter = Iter<'a, LineString>;
 
-    fn lines(&'a self) -> Self::Iter {
-        self.0.iter().cloned()
+    fn lines(&self) -> Self::Iter {
+        let multi_line_string: &'a MultiLineString = self;
+        multi_line_string.0.iter()
     }
 
-    fn num_lines(&'a self) -> usize {
+    fn num_lines(&self) -> usize {
         self.0.len()
     }
 
-    fn line(&'a self, i: usize) -> Option<Self::ItemType> {
-        self.0.get(i).cloned()
+    fn line(&self, i: usize) -> Option<Self::ItemType> {
+        let multi_line_string: &'a MultiLineString = self;
+        multi_line_string.0.get(i)
     }
 }
@@ -1,5 +1,4 @@
 use super::PolygonTrait;
-use std::iter::Cloned;
 use std::slice::Iter;
 
 pub trait MultiPolygonTrait<'a>: Send + Sync {
@@ -7,29 +6,31 @@ pub trait MultiPolygonTrait<'a>: Send + Sync {
     type Iter: Iterator<Item = Self::ItemType>;
 
     /// An iterator over the Polygons in this MultiPolygon
-    fn polygons(&'a self) -> Self::Iter;
+    fn polygons(&self) -> Self::Iter;
 
     /// The number of polygons in this MultiPolygon
-    fn num_polygons(&'a self) -> usize;
+    fn num_polygons(&self) -> usize;
 
     /// Access to a specified polygon in this MultiPolygon
     /// Will return None if the provided index is out of bounds
-    fn polygon(&'a self, i: usize) -> Option<Self::ItemType>;
+    fn polygon(&self, i: usize) -> Option<Self::ItemType>;
 }
 
-impl<'a> MultiPolygonTrait<'a> for geo::MultiPolygon<f64> {
-    type ItemType = geo::Polygon;
-    type Iter = Cloned<Iter<'a, Self::ItemType>>;
+impl<'a> MultiPolygonTrait<'a> for &'a geo::MultiPolygon<f64> {
+    type ItemType = &'a geo::Polygon;
+    type Iter = Iter<'a, geo::Polygon>;
 
-    fn polygons(&'a self) -> Self::Iter {
-        self.0.iter().cloned()
+    fn polygons(&self) -> Self::Iter {
+        let multi_polygon: &'a geo::MultiPolygon = self;
+        multi_polygon.0.iter()
     }
 
-    fn num_polygons(&'a self) -> usize {
+    fn num_polygons(&self) -> usize {
         self.0.len()
     }
 
-    fn polygon(&'a self, i: usize) -> Option<Self::ItemType> {
-        self.0.get(i).cloned()
+    fn polygon(&self, i: usize) -> Option<Self::ItemType> {
+        let multi_polygon: &'a geo::MultiPolygon = self;
+        multi_polygon.0.get(i)
     }
 }
@@ -1,4 +1,3 @@
-use std::iter::Cloned;
 use std::slice::Iter;
 
 use super::LineStringTrait;
@@ -9,57 +8,39 @@ pub trait PolygonTrait<'a>: Send + Sync {
     type Iter: Iterator<Item = Self::ItemType>;
 
     /// The exterior ring of the polygon
-    fn exterior(&'a self) -> Self::ItemType;
+    fn exterior(&self) -> Self::ItemType;
 
     /// An iterator of the interior rings of this Polygon
-    fn interiors(&'a self) -> Self::Iter;
+    fn interiors(&self) -> Self::Iter;
 
     /// The number of interior rings in this Polygon
-    fn num_interiors(&'a self) -> usize;
+    fn num_interiors(&self) -> usize;
 
     /// Access to a specified interior ring in this Polygon
     /// Will return None if the provided index is out of bounds
-    fn interior(&'a self, i: usize) -> Option<Self::ItemType>;
+    fn interior(&self, i: usize) -> Option<Self::ItemType>;
 }
 
-impl<'a> PolygonTrait<'a> for Polygon<f64> {
-    type ItemType = LineString;
-    type Iter = Cloned<Iter<'a, Self::ItemType>>;
+impl<'a> PolygonTrait<'a> for &'a Polygon<f64> {
+    type ItemType = &'a LineString;
+    type Iter = Iter<'a, LineString>;
 
-    fn exterior(&'a self) -> Self::ItemType {
-        self.exterior().clone()
+    fn exterior(&self) -> Self::ItemType {
+        let polygon: &'a Polygon = self;
+        polygon.exterior()
     }
 
-    fn interiors(&'a self) -> Self::Iter {
-        self.interiors().iter().cloned()
+    fn interiors(&self) -> Self::Iter {
+        let polygon: &'a Polygon = self;
+        polygon.interiors().iter()
     }
 
-    fn num_interiors(&'a self) -> usize {
-        self.interiors().len()
+    fn num_interiors(&self) -> usize {
+        Polygon::interiors(self).len()
     }
 
-    fn interior(&'a self, i: usize) -> Option<Self::ItemType> {
-        self.interiors().get(i).cloned()
-    }
-}
-
-impl<'a> PolygonTrait<'a> for &Polygon<f64> {
-    type ItemType = LineString;
-    type Iter = Cloned<Iter<'a, Self::ItemType>>;
-
-    fn exterior(&'a self) -> Self::ItemType {
-        (*self).exterior().clone()
-    }
-
-    fn interiors(&'a self) -> Self::Iter {
-        (*self).interiors().iter().cloned()
-    }
-
-    fn num_interiors(&'a self) -> usize {
-        self.interiors().len()
-    }
-
-    fn interior(&'a self, i: usize) -> Option<Self::ItemType> {
-        (*self).interiors().get(i).cloned()
+    fn interior(&self, i: usize) -> Option<Self::ItemType> {
+        let polygon: &'a Polygon = self;
+        polygon.interiors().get(i)
     }
 }
@@ -0,0 +1,36 @@
+use super::point::PointTrait;
+use geo::{Coord, Rect};
+
+pub trait RectTrait: Send + Sync {
+    type ItemType: PointTrait;
+
+    /// The corner of this box with the minimum x and y
+    fn min(&self) -> Self::ItemType;
+
+    /// The corner of this box with the maximum x and y
+    fn max(&self) -> Self::ItemType;
+}
+
+impl RectTrait for Rect<f64> {
+    type ItemType = Coord;
+
+    fn min(&self) -> Self::ItemType {
+        Rect::min(*self)
+    }
+
+    fn max(&self) -> Self::ItemType {
+        Rect::max(*self)
+    }
+}
+
+impl RectTrait for &Rect<f64> {
+    type ItemType = Coord;
+
+    fn min(&self) -> Self::ItemType {
+        Rect::min(**self)
+    }
+
+    fn max(&self) -> Self::ItemType {
+        Rect::max(**self)
+    }
+}
@@ -0,0 +1,229 @@
+//! [`serde`] support for geometries, in the geometry objects of
+//! [GeoJSON](https://datatracker.ietf.org/doc/html/rfc7946).
+//!
+//! Every scalar serializes to a GeoJSON geometry such as
+//! `{"type": "Point", "coordinates": [0, 1]}`, and the owned scalars, such as [`OwnedPoint`],
+//! deserialize from one. Arrays convert to a list of GeoJSON features with
+//! `to_geojson_features`, which web handlers can return as the `features` of a feature
+//! collection.
+
+use crate::enum_::Geometry;
+use crate::error::GeoArrowError;
+use crate::{
+    GeometryArray, GeometryArrayTrait, GeometryCollection, LineString, LineStringArray,
+    MixedGeometryArray, MultiLineString, MultiLineStringArray, MultiPoint, MultiPointArray,
+    MultiPolygon, MultiPolygonArray, OwnedLineString, OwnedMultiLineString, OwnedMultiPoint,
+    OwnedMultiPolygon, OwnedPoint, OwnedPolygon, Point, PointArray, Polygon, PolygonArray, Rect,
+    RectArray, WKBArray, WKB,
+};
+use geozero::geojson::GeoJson;
+use geozero::{ToGeo, ToJson};
+use serde::de::Error as _;
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
+
+/// The GeoJSON geometry object of `geom`.
+fn to_geojson_value(geom: geo::Geometry) -> Result<Value, GeoArrowError> {
+    // GeoJSON has no rectangles
+    let geom = match geom {
+        geo::Geometry::Rect(rect) => geo::Geometry::Polygon(rect.to_polygon()),
+        geom => geom,
+    };
+    let json = geom
+        .to_json()
+        .map_err(|err| GeoArrowError::External(err.into()))?;
+    serde_json::from_str(&json).map_err(|err| GeoArrowError::External(err.into()))
+}
+
+/// Serialize `geom` as a GeoJSON geometry object.
+fn serialize_geo<S: Serializer>(geom: geo::Geometry, serializer: S) -> Result<S::Ok, S::Error> {
+    to_geojson_value(geom)
+        .map_err(S::Error::custom)?
+        .serialize(serializer)
+}
+
+macro_rules! impl_serialize {
+    ($type:ty) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_geo(self.clone().into(), serializer)
+            }
+        }
+    };
+}
+
+impl_serialize!(Point<'_>);
+impl_serialize!(LineString<'_>);
+impl_serialize!(Polygon<'_>);
+impl_serialize!(MultiPoint<'_>);
+impl_serialize!(MultiLineString<'_>);
+impl_serialize!(MultiPolygon<'_>);
+impl_serialize!(Rect<'_>);
+impl_serialize!(GeometryCollection<'_>);
+impl_serialize!(OwnedPoint);
+impl_serialize!(OwnedLineString);
+impl_serialize!(OwnedPolygon);
+impl_serialize!(OwnedMultiPoint);
+impl_serialize!(OwnedMultiLineString);
+impl_serialize!(OwnedMultiPolygon);
+
+impl Serialize for WKB<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let geom = self.to_typed_scalar().map_err(S::Error::custom)?;
+        serialize_geo(geom, serializer)
+    }
+}
+
+impl Serialize for Geometry<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Geometry::Point(geom) => geom.serialize(serializer),
+            Geometry::LineString(geom) => geom.serialize(serializer),
+            Geometry::Polygon(geom) => geom.serialize(serializer),
+            Geometry::MultiPoint(geom) => geom.serialize(serializer),
+            Geometry::MultiLineString(geom) => geom.serialize(serializer),
+            Geometry::MultiPolygon(geom) => geom.serialize(serializer),
+            Geometry::WKB(geom) => geom.serialize(serializer),
+            Geometry::Rect(geom) => geom.serialize(serializer),
+            Geometry::GeometryCollection(geom) => geom.serialize(serializer),
+        }
+    }
+}
+
+/// Deserialize a GeoJSON geometry object.
+fn deserialize_geo<'de, D: Deserializer<'de>>(deserializer: D) -> Result<geo::Geometry, D::Error> {
+    let value = Value::deserialize(deserializer)?;
+    GeoJson(&value.to_string())
+        .to_geo()
+        .map_err(D::Error::custom)
+}
+
+macro_rules! impl_deserialize {
+    ($type:ty, $array:ty, $variant:ident) => {
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                match deserialize_geo(deserializer)? {
+                    geo::Geometry::$variant(geom) => {
+                        let array: $array = vec![geom].into();
+                        Ok(array.value(0).into())
+                    }
+                    _ => Err(D::Error::custom(concat!(
+                        "expected a GeoJSON ",
+                        stringify!($variant)
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+impl_deserialize!(OwnedPoint, PointArray, Point);
+impl_deserialize!(OwnedLineString, LineStringArray, LineString);
+impl_deserialize!(OwnedPolygon, PolygonArray, Polygon);
+impl_deserialize!(OwnedMultiPoint, MultiPointArray, MultiPoint);
+impl_deserialize!(OwnedMultiLineString, MultiLineStringArray, MultiLineString);
+impl_deserialize!(OwnedMultiPolygon, MultiPolygonArray, MultiPolygon);
+
+/// The GeoJSON features of `array`, with null geometries for null rows.
+fn geojson_features<'a, A>(array: &'a A) -> Result<Vec<Value>, GeoArrowError>
+where
+    A: GeometryArrayTrait<'a>,
+    A::Scalar: Serialize,
+{
+    (0..array.len())
+        .map(|i| {
+            let geometry = if array.is_null(i) {
+                Value::Null
+            } else {
+                serde_json::to_value(array.value(i))
+                    .map_err(|err| GeoArrowError::External(err.into()))?
+            };
+            Ok(json!({ "type": "Feature", "geometry": geometry, "properties": {} }))
+        })
+        .collect()
+}
+
+macro_rules! impl_to_geojson_features {
+    ($type:ty) => {
+        impl $type {
+            /// One GeoJSON feature per geometry, with empty properties. Null geometries become
+            /// features with a null geometry.
+            /// # Errors
+            /// Errors if a geometry cannot be written as GeoJSON, such as invalid WKB.
+            pub fn to_geojson_features(&self) -> Result<Vec<Value>, GeoArrowError> {
+                geojson_features(self)
+            }
+        }
+    };
+}
+
+impl_to_geojson_features!(PointArray);
+impl_to_geojson_features!(LineStringArray);
+impl_to_geojson_features!(PolygonArray);
+impl_to_geojson_features!(MultiPointArray);
+impl_to_geojson_features!(MultiLineStringArray);
+impl_to_geojson_features!(MultiPolygonArray);
+impl_to_geojson_features!(WKBArray);
+impl_to_geojson_features!(RectArray);
+impl_to_geojson_features!(MixedGeometryArray);
+
+impl GeometryArray {
+    /// One GeoJSON feature per geometry, with empty properties. Null geometries become features
+    /// with a null geometry.
+    /// # Errors
+    /// Errors if a geometry cannot be written as GeoJSON, such as invalid WKB.
+    pub fn to_geojson_features(&self) -> Result<Vec<Value>, GeoArrowError> {
+        match self {
+            GeometryArray::Point(arr) => arr.to_geojson_features(),
+            GeometryArray::LineString(arr) => arr.to_geojson_features(),
+            GeometryArray::Polygon(arr) => arr.to_geojson_features(),
+            GeometryArray::MultiPoint(arr) => arr.to_geojson_features(),
+            GeometryArray::MultiLineString(arr) => arr.to_geojson_features(),
+            GeometryArray::MultiPolygon(arr) => arr.to_geojson_features(),
+            GeometryArray::WKB(arr) => arr.to_geojson_features(),
+            GeometryArray::Rect(arr) => arr.to_geojson_features(),
+            GeometryArray::Mixed(arr) => arr.to_geojson_features(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point};
+
+    #[test]
+    fn scalars() {
+        let points: PointArray = vec![point!(x: 0., y: 1.)].into();
+        assert_eq!(
+            serde_json::to_value(points.value(0)).unwrap(),
+            json!({ "type": "Point", "coordinates": [0, 1] })
+        );
+
+        let lines: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 1., y: 2.)]].into();
+        let json = serde_json::to_string(&lines.value(0)).unwrap();
+        let line: OwnedLineString = serde_json::from_str(&json).unwrap();
+        assert_eq!(geo::LineString::from(line), lines.value_as_geo(0));
+
+        assert!(serde_json::from_str::<OwnedPoint>(&json).is_err());
+        assert!(serde_json::from_str::<OwnedPoint>("{\"type\": \"Point\"}").is_err());
+    }
+
+    #[test]
+    fn features() {
+        let points: PointArray = vec![Some(point!(x: 0., y: 1.)), None].into();
+        let features = GeometryArray::Point(points).to_geojson_features().unwrap();
+        assert_eq!(
+            features,
+            vec![
+                json!({
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [0, 1] },
+                    "properties": {},
+                }),
+                json!({ "type": "Feature", "geometry": null, "properties": {} }),
+            ]
+        );
+    }
+}
@@ -0,0 +1,384 @@
+use crate::coerce::geometry_type_name;
+use crate::concat::try_push_end;
+use crate::crs::impl_crs;
+use crate::error::GeoArrowError;
+use crate::offsets::widen_list_offsets;
+#[cfg(feature = "rayon")]
+use crate::parallel::MIN_ROWS_PER_TASK;
+use crate::slice::slice_validity_unchecked;
+use crate::trait_::GeometryArrayType;
+use crate::validity::check_validity_len;
+use crate::{GeometryArrayTrait, MixedGeometryArray};
+use arrow2::array::{Array, ListArray};
+use arrow2::bitmap::utils::{BitmapIter, ZipValidity};
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::datatypes::{DataType, Field};
+use arrow2::offset::{Offsets, OffsetsBuffer};
+use arrow2::types::Index;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// A [`GeometryArrayTrait`] semantically equivalent to `Vec<Option<GeometryCollection>>` using
+/// Arrow's in-memory representation: a list over a [`MixedGeometryArray`] holding the members of
+/// every geometry collection.
+#[derive(Debug, Clone)]
+pub struct GeometryCollectionArray {
+    /// The members of every geometry collection
+    array: MixedGeometryArray,
+
+    /// Offsets into the mixed array where each geometry collection starts
+    geom_offsets: OffsetsBuffer<i64>,
+
+    /// Validity bitmap
+    validity: Option<Bitmap>,
+
+    /// Coordinate reference system
+    crs: Option<Arc<str>>,
+}
+
+fn check(
+    array: &MixedGeometryArray,
+    validity_len: Option<usize>,
+    geom_offsets: &OffsetsBuffer<i64>,
+) -> Result<(), GeoArrowError> {
+    if validity_len.map_or(false, |len| len != geom_offsets.len_proxy()) {
+        return Err(GeoArrowError::General(
+            "validity mask length must match the number of values".to_string(),
+        ));
+    }
+
+    if geom_offsets.last().to_usize() > array.len() {
+        return Err(GeoArrowError::General(
+            "largest geometry offset must not exceed the length of the mixed array".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl GeometryCollectionArray {
+    /// Create a new GeometryCollectionArray from parts
+    /// # Implementation
+    /// This function is `O(1)`.
+    /// # Panics
+    /// Panics if the validity or the offsets do not match the other parts.
+    pub fn new(
+        array: MixedGeometryArray,
+        geom_offsets: OffsetsBuffer<i64>,
+        validity: Option<Bitmap>,
+    ) -> Self {
+        Self::try_new(array, geom_offsets, validity).unwrap()
+    }
+
+    /// Create a new GeometryCollectionArray from parts
+    /// # Implementation
+    /// This function is `O(1)`.
+    /// # Errors
+    /// Errors if the validity or the offsets do not match the other parts.
+    pub fn try_new(
+        array: MixedGeometryArray,
+        geom_offsets: OffsetsBuffer<i64>,
+        validity: Option<Bitmap>,
+    ) -> Result<Self, GeoArrowError> {
+        check(&array, validity.as_ref().map(|v| v.len()), &geom_offsets)?;
+        Ok(Self {
+            array,
+            geom_offsets,
+            validity,
+            crs: None,
+        })
+    }
+
+    /// The mixed array holding the members of every geometry collection.
+    #[inline]
+    pub fn array(&self) -> &MixedGeometryArray {
+        &self.array
+    }
+
+    /// Offsets into the mixed array where each geometry collection starts.
+    #[inline]
+    pub fn geom_offsets(&self) -> &OffsetsBuffer<i64> {
+        &self.geom_offsets
+    }
+}
+
+impl_crs!(GeometryCollectionArray);
+
+impl<'a> GeometryArrayTrait<'a> for GeometryCollectionArray {
+    type Scalar = crate::GeometryCollection<'a>;
+    type ScalarGeo = geo::GeometryCollection;
+    type ArrowArray = ListArray<i64>;
+
+    fn value(&'a self, i: usize) -> Self::Scalar {
+        crate::GeometryCollection {
+            array: &self.array,
+            geom_offsets: &self.geom_offsets,
+            geom_index: i,
+        }
+    }
+
+    /// geo converts any geometry into a geometry collection holding it, so the members are
+    /// converted from a reference to the scalar instead.
+    fn value_as_geo(&'a self, i: usize) -> Self::ScalarGeo {
+        (&self.value(i)).into()
+    }
+
+    fn into_arrow(self) -> Self::ArrowArray {
+        let values = self.array.into_arrow().boxed();
+        let list_data_type = DataType::LargeList(Box::new(Field::new(
+            "geometries",
+            values.data_type().clone(),
+            true,
+        )));
+
+        ListArray::new(list_data_type, self.geom_offsets, values, self.validity)
+    }
+
+    fn geometry_type(&self) -> GeometryArrayType {
+        GeometryArrayType::GeometryCollection
+    }
+
+    /// Returns the number of geometries in this array
+    #[inline]
+    fn len(&self) -> usize {
+        self.geom_offsets.len_proxy()
+    }
+
+    /// Returns the optional validity.
+    #[inline]
+    fn validity(&self) -> Option<&Bitmap> {
+        self.validity.as_ref()
+    }
+
+    /// Slices this [`GeometryCollectionArray`] in place.
+    /// # Implementation
+    /// This operation is `O(1)`. The mixed array is not sliced.
+    /// # Panic
+    /// This function panics iff `offset + length > self.len()`.
+    #[inline]
+    fn slice(&mut self, offset: usize, length: usize) {
+        assert!(
+            offset + length <= self.len(),
+            "offset + length may not exceed length of array"
+        );
+        unsafe { self.slice_unchecked(offset, length) };
+    }
+
+    /// Slices this [`GeometryCollectionArray`] in place.
+    /// # Implementation
+    /// This operation is `O(1)`. The mixed array is not sliced.
+    /// # Safety
+    /// The caller must ensure that `offset + length <= self.len()`.
+    #[inline]
+    unsafe fn slice_unchecked(&mut self, offset: usize, length: usize) {
+        slice_validity_unchecked(&mut self.validity, offset, length);
+        self.geom_offsets.slice_unchecked(offset, length + 1);
+    }
+
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        check_validity_len(validity.as_ref(), self.len());
+        Self {
+            validity,
+            ..self.clone()
+        }
+    }
+
+    fn to_boxed(&self) -> Box<Self> {
+        Box::new(self.clone())
+    }
+}
+
+// Implement geometry accessors
+impl GeometryCollectionArray {
+    /// Iterator over geo Geometry objects, not looking at validity
+    pub fn iter_geo_values(&self) -> impl Iterator<Item = geo::GeometryCollection> + '_ {
+        (0..self.len()).map(|i| self.value_as_geo(i))
+    }
+
+    /// Iterator over geo Geometry objects, taking into account validity
+    pub fn iter_geo(
+        &self,
+    ) -> ZipValidity<
+        geo::GeometryCollection,
+        impl Iterator<Item = geo::GeometryCollection> + '_,
+        BitmapIter,
+    > {
+        ZipValidity::new_with_validity(self.iter_geo_values(), self.validity())
+    }
+
+    /// Parallel iterator over the scalars of this array, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = Option<crate::GeometryCollection<'_>>> {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get(i))
+    }
+
+    /// Parallel iterator over geo Geometry objects, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_geo(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = Option<geo::GeometryCollection>> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get_as_geo(i))
+    }
+}
+
+impl TryFrom<ListArray<i64>> for GeometryCollectionArray {
+    type Error = GeoArrowError;
+
+    fn try_from(value: ListArray<i64>) -> Result<Self, Self::Error> {
+        Self::try_new(
+            value.values().clone().try_into()?,
+            value.offsets().clone(),
+            value.validity().cloned(),
+        )
+    }
+}
+
+impl TryFrom<Box<dyn Array>> for GeometryCollectionArray {
+    type Error = GeoArrowError;
+
+    /// Both `List` and `LargeList` arrays are accepted.
+    fn try_from(value: Box<dyn Array>) -> Result<Self, Self::Error> {
+        let value = widen_list_offsets(value.as_ref());
+        let arr = value
+            .as_any()
+            .downcast_ref::<ListArray<i64>>()
+            .ok_or_else(|| GeoArrowError::Downcast {
+                expected: "ListArray<i64>",
+                found: value.data_type().clone(),
+            })?;
+        arr.clone().try_into()
+    }
+}
+
+impl TryFrom<Vec<Option<geo::GeometryCollection>>> for GeometryCollectionArray {
+    type Error = GeoArrowError;
+
+    /// Lines are stored as line strings, and rects and triangles as polygons.
+    /// # Errors
+    /// Errors if any geometry collection holds another geometry collection.
+    fn try_from(other: Vec<Option<geo::GeometryCollection>>) -> Result<Self, Self::Error> {
+        let mut geom_offsets = Offsets::<i64>::with_capacity(other.len());
+        let mut validity = MutableBitmap::with_capacity(other.len());
+        let mut geoms = vec![];
+
+        for (row, maybe_collection) in other.into_iter().enumerate() {
+            validity.push(maybe_collection.is_some());
+            for geom in maybe_collection
+                .into_iter()
+                .flat_map(|collection| collection.0)
+            {
+                if let geo::Geometry::GeometryCollection(_) = geom {
+                    return Err(GeoArrowError::UnexpectedGeometryType {
+                        row,
+                        expected: "non-collection geometry",
+                        found: geometry_type_name(&geom),
+                    });
+                }
+                geoms.push(Some(geom));
+            }
+            try_push_end(&mut geom_offsets, geoms.len())?;
+        }
+
+        Self::try_new(
+            geoms.try_into()?,
+            geom_offsets.into(),
+            (validity.unset_bits() > 0).then(|| validity.into()),
+        )
+    }
+}
+
+impl TryFrom<Vec<geo::GeometryCollection>> for GeometryCollectionArray {
+    type Error = GeoArrowError;
+
+    fn try_from(other: Vec<geo::GeometryCollection>) -> Result<Self, Self::Error> {
+        other.into_iter().map(Some).collect::<Vec<_>>().try_into()
+    }
+}
+
+impl From<GeometryCollectionArray> for Vec<Option<geo::GeometryCollection>> {
+    fn from(value: GeometryCollectionArray) -> Self {
+        value.iter_geo().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enum_::Geometry;
+    use geo::{line_string, point, polygon, GeometryCollection};
+    use rstar::{RTreeObject, AABB};
+
+    fn gc0() -> GeometryCollection {
+        GeometryCollection(vec![
+            point!(x: 0., y: 1.).into(),
+            line_string![(x: 0., y: 0.), (x: 1., y: 1.)].into(),
+        ])
+    }
+
+    fn gc1() -> GeometryCollection {
+        GeometryCollection(vec![
+            polygon![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 0.)].into(),
+        ])
+    }
+
+    #[test]
+    fn geo_roundtrip() {
+        let geoms = vec![
+            Some(gc0()),
+            None,
+            Some(GeometryCollection(vec![])),
+            Some(gc1()),
+        ];
+        let arr = GeometryCollectionArray::try_from(geoms.clone()).unwrap();
+        assert_eq!(arr.len(), 4);
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value(0).num_geometries(), 2);
+        assert!(matches!(
+            arr.value(0).geometry(1),
+            Some(Geometry::LineString(_))
+        ));
+        assert!(arr.value(0).geometry(2).is_none());
+        assert_eq!(
+            arr.value(3).envelope(),
+            AABB::from_corners([0., 0.], [2., 2.])
+        );
+
+        let output: Vec<Option<GeometryCollection>> = arr.into();
+        assert_eq!(output, geoms);
+
+        let nested = GeometryCollection(vec![geo::Geometry::GeometryCollection(gc0())]);
+        assert!(matches!(
+            GeometryCollectionArray::try_from(vec![gc1(), nested]),
+            Err(GeoArrowError::UnexpectedGeometryType { row: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn arrow_roundtrip() {
+        let arr = GeometryCollectionArray::try_from(vec![Some(gc0()), None, Some(gc1())]).unwrap();
+        let list = arr.into_arrow();
+        assert_eq!(list.len(), 3);
+
+        let arr = GeometryCollectionArray::try_from(list.boxed()).unwrap();
+        assert_eq!(
+            arr.iter_geo().collect::<Vec<_>>(),
+            vec![Some(gc0()), None, Some(gc1())]
+        );
+    }
+
+    #[test]
+    fn slice() {
+        let mut arr = GeometryCollectionArray::try_from(vec![gc0(), gc1()]).unwrap();
+        arr.slice(1, 1);
+        assert_eq!(arr.len(), 1);
+        assert_eq!(arr.get_as_geo(0), Some(gc1()));
+    }
+}
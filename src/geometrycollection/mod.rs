@@ -0,0 +1,7 @@
+//! Helpers for using GeometryCollection GeoArrow data
+
+pub use array::GeometryCollectionArray;
+pub use scalar::GeometryCollection;
+
+mod array;
+mod scalar;
@@ -0,0 +1,61 @@
+use crate::algorithm::bounding_rect::bounding_rect_envelopes;
+use crate::enum_::Geometry;
+use crate::{GeometryArrayTrait, MixedGeometryArray};
+use arrow2::offset::OffsetsBuffer;
+use rstar::{RTreeObject, AABB};
+
+/// An Arrow equivalent of a GeometryCollection
+#[derive(Debug, Clone)]
+pub struct GeometryCollection<'a> {
+    /// The members of every geometry collection of the array
+    pub array: &'a MixedGeometryArray,
+
+    /// Offsets into the mixed array where each geometry collection starts
+    pub geom_offsets: &'a OffsetsBuffer<i64>,
+
+    pub geom_index: usize,
+}
+
+impl<'a> GeometryCollection<'a> {
+    /// The number of geometries in this collection.
+    pub fn num_geometries(&self) -> usize {
+        let (start, end) = self.geom_offsets.start_end(self.geom_index);
+        end - start
+    }
+
+    /// The `i`th geometry of this collection, or `None` if `i` is out of bounds.
+    pub fn geometry(&self, i: usize) -> Option<Geometry<'a>> {
+        let (start, end) = self.geom_offsets.start_end(self.geom_index);
+        if i >= end - start {
+            return None;
+        }
+
+        Some(self.array.value(start + i))
+    }
+
+    /// Iterate over the geometries of this collection.
+    pub fn geometries(&self) -> impl Iterator<Item = Geometry<'a>> + '_ {
+        (0..self.num_geometries()).map(|i| self.geometry(i).unwrap())
+    }
+}
+
+impl From<&GeometryCollection<'_>> for geo::GeometryCollection {
+    fn from(value: &GeometryCollection<'_>) -> Self {
+        geo::GeometryCollection(value.geometries().map(geo::Geometry::from).collect())
+    }
+}
+
+impl From<GeometryCollection<'_>> for geo::Geometry {
+    fn from(value: GeometryCollection<'_>) -> Self {
+        geo::Geometry::GeometryCollection((&value).into())
+    }
+}
+
+impl RTreeObject for GeometryCollection<'_> {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        let (lower, upper) = bounding_rect_envelopes(self.geometries().map(|geom| geom.envelope()));
+        AABB::from_corners(lower, upper)
+    }
+}
@@ -0,0 +1,273 @@
+//! Arrow2's [`MutableArray`] and [`Growable`] traits for geometry arrays.
+//!
+//! Query engines built on arrow2 concatenate, filter and take columns through these traits without
+//! knowing their concrete types. The mutable geometry arrays implement [`MutableArray`], and each
+//! geometry array has a growable, such as [`GrowablePoint`], that copies slices of a set of arrays
+//! of that type into a new one. Both produce the Arrow storage arrays of
+//! [`GeometryArrayTrait::into_arrow`]; use the [`From`] implementations of the growables to
+//! keep the geometry type.
+//!
+//! Mutable arrays carry no CRS, so the CRS of the input arrays is dropped.
+
+use crate::binary::MutableWKBArray;
+use crate::linestring::{LineStringCapacity, MutableLineStringArray};
+use crate::multilinestring::{MultiLineStringCapacity, MutableMultiLineStringArray};
+use crate::multipoint::{MultiPointCapacity, MutableMultiPointArray};
+use crate::multipolygon::{MultiPolygonCapacity, MutableMultiPolygonArray};
+use crate::point::MutablePointArray;
+use crate::polygon::{MutablePolygonArray, PolygonCapacity};
+use crate::trait_::{GeometryArrayTrait, MutableGeometryArray};
+use crate::{
+    LineStringArray, MultiLineStringArray, MultiPointArray, MultiPolygonArray, PointArray,
+    PolygonArray, WKBArray,
+};
+use arrow2::array::growable::Growable;
+use arrow2::array::{Array, MutableArray};
+use arrow2::bitmap::MutableBitmap;
+use arrow2::datatypes::DataType;
+use std::any::Any;
+use std::sync::OnceLock;
+
+/// Implement [`MutableArray`] for the mutable array `$mutable` of `$array`, reserving room for
+/// more geometries with `$reserve`.
+macro_rules! impl_mutable_array {
+    ($mutable:ty, $array:ty, $reserve:expr) => {
+        impl MutableArray for $mutable {
+            fn data_type(&self) -> &DataType {
+                // Every array of a type has the same Arrow data type
+                static DATA_TYPE: OnceLock<DataType> = OnceLock::new();
+                DATA_TYPE.get_or_init(|| {
+                    <$array>::from(<$mutable>::default())
+                        .into_arrow()
+                        .data_type()
+                        .clone()
+                })
+            }
+
+            fn len(&self) -> usize {
+                MutableGeometryArray::len(self)
+            }
+
+            fn validity(&self) -> Option<&MutableBitmap> {
+                MutableGeometryArray::validity(self)
+            }
+
+            fn as_box(&mut self) -> Box<dyn Array> {
+                let array: $array = std::mem::take(self).into();
+                array.into_arrow().boxed()
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+
+            fn as_mut_any(&mut self) -> &mut dyn Any {
+                self
+            }
+
+            fn push_null(&mut self) {
+                <$mutable>::push_null(self)
+            }
+
+            fn reserve(&mut self, additional: usize) {
+                let reserve: fn(&mut $mutable, usize) = $reserve;
+                reserve(self, additional)
+            }
+
+            fn shrink_to_fit(&mut self) {
+                <$mutable>::shrink_to_fit(self)
+            }
+        }
+    };
+}
+
+impl_mutable_array!(MutablePointArray, PointArray, |array, additional| {
+    array.reserve(additional)
+});
+impl_mutable_array!(
+    MutableLineStringArray,
+    LineStringArray,
+    |array, additional| array.reserve(LineStringCapacity::new(0, additional))
+);
+impl_mutable_array!(MutablePolygonArray, PolygonArray, |array, additional| {
+    array.reserve(PolygonCapacity::new(0, 0, additional))
+});
+impl_mutable_array!(
+    MutableMultiPointArray,
+    MultiPointArray,
+    |array, additional| array.reserve(MultiPointCapacity::new(0, additional))
+);
+impl_mutable_array!(
+    MutableMultiLineStringArray,
+    MultiLineStringArray,
+    |array, additional| array.reserve(MultiLineStringCapacity::new(0, 0, additional))
+);
+impl_mutable_array!(
+    MutableMultiPolygonArray,
+    MultiPolygonArray,
+    |array, additional| array.reserve(MultiPolygonCapacity::new(0, 0, 0, additional))
+);
+impl_mutable_array!(MutableWKBArray, WKBArray, |array, additional| {
+    array.reserve(additional, 0)
+});
+
+/// Define `$growable`, the [`Growable`] of `$array`, appending slices of the input arrays to
+/// `$mutable` with `$extend`.
+macro_rules! impl_growable {
+    ($growable:ident, $array:ty, $mutable:ty, $extend:expr) => {
+        #[doc = concat!("Concatenate slices of [`", stringify!($array), "`]s.")]
+        #[derive(Debug)]
+        pub struct $growable<'a> {
+            arrays: Vec<&'a $array>,
+            values: $mutable,
+        }
+
+        impl<'a> $growable<'a> {
+            /// Creates a growable of `arrays`, reserving room for `capacity` geometries.
+            pub fn new(arrays: Vec<&'a $array>, capacity: usize) -> Self {
+                let mut values = <$mutable>::default();
+                MutableArray::reserve(&mut values, capacity);
+                Self { arrays, values }
+            }
+        }
+
+        impl<'a> Growable<'a> for $growable<'a> {
+            fn extend(&mut self, index: usize, start: usize, len: usize) {
+                let array = self.arrays[index].sliced_range(start..start + len);
+                let extend: fn(&mut $mutable, &$array) = $extend;
+                extend(&mut self.values, &array)
+            }
+
+            fn extend_validity(&mut self, additional: usize) {
+                for _ in 0..additional {
+                    self.values.push_null();
+                }
+            }
+
+            fn len(&self) -> usize {
+                MutableGeometryArray::len(&self.values)
+            }
+
+            fn as_box(&mut self) -> Box<dyn Array> {
+                self.values.as_box()
+            }
+        }
+
+        impl<'a> From<$growable<'a>> for $array {
+            fn from(growable: $growable<'a>) -> Self {
+                growable.values.into()
+            }
+        }
+    };
+}
+
+impl_growable!(
+    GrowablePoint,
+    PointArray,
+    MutablePointArray,
+    |values, array| values.extend_from_array(array)
+);
+impl_growable!(
+    GrowableLineString,
+    LineStringArray,
+    MutableLineStringArray,
+    |values, array| values.extend_from_array(array).expect("offsets fit in i64")
+);
+impl_growable!(
+    GrowablePolygon,
+    PolygonArray,
+    MutablePolygonArray,
+    |values, array| values.extend_from_array(array).expect("offsets fit in i64")
+);
+impl_growable!(
+    GrowableMultiPoint,
+    MultiPointArray,
+    MutableMultiPointArray,
+    |values, array| values.extend_from_array(array).expect("offsets fit in i64")
+);
+impl_growable!(
+    GrowableMultiLineString,
+    MultiLineStringArray,
+    MutableMultiLineStringArray,
+    |values, array| values.extend_from_array(array).expect("offsets fit in i64")
+);
+impl_growable!(
+    GrowableMultiPolygon,
+    MultiPolygonArray,
+    MutableMultiPolygonArray,
+    |values, array| values.extend_from_array(array).expect("offsets fit in i64")
+);
+impl_growable!(GrowableWKB, WKBArray, MutableWKBArray, |values, array| {
+    values.extend_from_array(array)
+});
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::BinaryArray;
+    use geo::{line_string, point};
+
+    #[test]
+    fn mutable_arrays() {
+        let mut points = MutablePointArray::new();
+        points.push_point(Some(&point!(x: 1., y: 2.)));
+        MutableArray::push_null(&mut points);
+        MutableArray::reserve(&mut points, 10);
+        assert_eq!(MutableArray::len(&points), 2);
+        assert!(!MutableArray::is_valid(&points, 1));
+
+        let data_type = points.data_type().clone();
+        let boxed = points.as_box();
+        assert_eq!(boxed.data_type(), &data_type);
+        assert_eq!(boxed.len(), 2);
+        assert_eq!(boxed.null_count(), 1);
+        assert_eq!(MutableArray::len(&points), 0);
+
+        let mut lines = MutableLineStringArray::new();
+        MutableArray::push_null(&mut lines);
+        assert_eq!(
+            lines.as_box().data_type(),
+            LineStringArray::from(vec![line_string![(x: 0., y: 0.)]])
+                .into_arrow()
+                .data_type()
+        );
+    }
+
+    #[test]
+    fn growables() {
+        let first: LineStringArray = vec![
+            Some(line_string![(x: 0., y: 0.), (x: 1., y: 1.)]),
+            None,
+            Some(line_string![(x: 2., y: 2.), (x: 3., y: 3.)]),
+        ]
+        .into();
+        let second: LineStringArray = vec![line_string![(x: 4., y: 4.)]].into();
+
+        let mut growable = GrowableLineString::new(vec![&first, &second], 4);
+        growable.extend(0, 1, 2);
+        growable.extend(1, 0, 1);
+        growable.extend_validity(1);
+        growable.extend(0, 0, 1);
+        assert_eq!(growable.len(), 5);
+
+        let output: LineStringArray = growable.into();
+        assert!(output.is_null(0));
+        assert_eq!(output.get_as_geo(1), first.get_as_geo(2));
+        assert_eq!(output.get_as_geo(2), second.get_as_geo(0));
+        assert!(output.is_null(3));
+        assert_eq!(output.get_as_geo(4), first.get_as_geo(0));
+
+        let points: PointArray = vec![Some(point!(x: 1., y: 2.)), None].into();
+        let mut growable = GrowablePoint::new(vec![&points], 2);
+        growable.extend(0, 0, 2);
+        let boxed = growable.as_box();
+        assert_eq!(boxed.len(), 2);
+        assert!(boxed.is_null(1));
+        assert_eq!(growable.len(), 0);
+
+        let wkb: WKBArray = vec![Some(geo::Geometry::Point(point!(x: 1., y: 2.)))].into();
+        let mut growable = GrowableWKB::new(vec![&wkb], 1);
+        growable.extend(0, 0, 1);
+        assert!(growable.as_box().as_any().is::<BinaryArray<i64>>());
+    }
+}
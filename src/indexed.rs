@@ -0,0 +1,310 @@
+//! Spatial index entries and [`IndexedGeometryArray`], a geometry array paired with a reusable
+//! spatial index.
+
+use crate::GeometryArrayTrait;
+use geo::{Closest, ClosestPoint, EuclideanDistance, Intersects};
+use rstar::primitives::{GeomWithData, Rectangle};
+use rstar::{RTree, RTreeObject, AABB};
+use std::ops::Deref;
+
+/// An [`RTree`] entry wrapping a scalar together with its precomputed envelope and its row
+/// index in the source array.
+///
+/// rstar asks leaf objects for their envelope on every query. For scalars such as [`WKB`] this
+/// means re-parsing the geometry each time; caching the envelope makes queries cheap. The wrapped
+/// scalar is available through [`Deref`].
+///
+/// [`WKB`]: crate::WKB
+#[derive(Debug, Clone)]
+pub struct CachedEnvelope<T: RTreeObject> {
+    inner: T,
+    envelope: T::Envelope,
+    index: usize,
+}
+
+impl<T: RTreeObject> CachedEnvelope<T> {
+    /// Wrap `inner`, found at row `geom_index` of its array, computing its envelope once.
+    pub fn new(inner: T, geom_index: usize) -> Self {
+        let envelope = inner.envelope();
+        Self {
+            inner,
+            envelope,
+            index: geom_index,
+        }
+    }
+
+    /// The row index of the wrapped geometry in its array.
+    pub fn geom_index(&self) -> usize {
+        self.index
+    }
+
+    /// The wrapped scalar.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: RTreeObject> Deref for CachedEnvelope<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: RTreeObject> RTreeObject for CachedEnvelope<T> {
+    type Envelope = T::Envelope;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.envelope
+    }
+}
+
+/// An entry of an envelope index: the bounding box of a geometry and its position in the array.
+pub type EnvelopeEntry = GeomWithData<Rectangle<[f64; 2]>, usize>;
+
+/// The envelope of the geometry at `i`, or `None` if it is null or empty.
+pub(crate) fn envelope<'a, A>(array: &'a A, i: usize) -> Option<AABB<[f64; 2]>>
+where
+    A: GeometryArrayTrait<'a>,
+    A::Scalar: RTreeObject<Envelope = AABB<[f64; 2]>>,
+{
+    let envelope = array.get(i)?.envelope();
+    // The envelope of an empty geometry spans infinite bounds
+    let finite = envelope
+        .lower()
+        .iter()
+        .chain(envelope.upper().iter())
+        .all(|value| value.is_finite());
+    finite.then_some(envelope)
+}
+
+/// Bulk load an [`RTree`] of the envelopes of every non-null, non-empty geometry in `array`.
+pub(crate) fn envelope_tree<'a, A>(array: &'a A) -> RTree<EnvelopeEntry>
+where
+    A: GeometryArrayTrait<'a>,
+    A::Scalar: RTreeObject<Envelope = AABB<[f64; 2]>>,
+{
+    let entries = (0..array.len())
+        .filter_map(|i| {
+            let envelope = envelope(array, i)?;
+            let rectangle = Rectangle::from_corners(envelope.lower(), envelope.upper());
+            Some(GeomWithData::new(rectangle, i))
+        })
+        .collect();
+    RTree::bulk_load(entries)
+}
+
+/// Euclidean distance from `point` to `geom`, or `None` if `geom` is empty.
+fn distance_to(geom: &geo::Geometry, point: &geo::Point) -> Option<f64> {
+    if geom.intersects(point) {
+        return Some(0.);
+    }
+    match geom.closest_point(point) {
+        Closest::Intersection(_) => Some(0.),
+        Closest::SinglePoint(closest) => Some(point.euclidean_distance(&closest)),
+        Closest::Indeterminate => None,
+    }
+}
+
+/// The `k` entries of `tree` whose geometries are closest to `point`, as `(index, distance)`
+/// pairs sorted by distance and then index. `geom_at` returns the exact geometry for an index.
+pub(crate) fn k_nearest(
+    tree: &RTree<EnvelopeEntry>,
+    point: &geo::Point,
+    k: usize,
+    geom_at: impl Fn(usize) -> geo::Geometry,
+) -> Vec<(usize, f64)> {
+    let mut best: Vec<(usize, f64)> = Vec::with_capacity(k);
+    if k == 0 {
+        return best;
+    }
+
+    for (entry, envelope_distance_2) in
+        tree.nearest_neighbor_iter_with_distance_2(&[point.x(), point.y()])
+    {
+        // Envelopes arrive in order of distance, and no geometry is closer than its envelope
+        if best.len() == k && envelope_distance_2.sqrt() > best[k - 1].1 {
+            break;
+        }
+
+        let distance = match distance_to(&geom_at(entry.data), point) {
+            Some(distance) => distance,
+            None => continue,
+        };
+        let candidate = (entry.data, distance);
+        let position = best.partition_point(|existing| {
+            existing.1 < candidate.1 || (existing.1 == candidate.1 && existing.0 < candidate.0)
+        });
+        if position < k {
+            best.insert(position, candidate);
+            best.truncate(k);
+        }
+    }
+    best
+}
+
+/// A geometry array together with an [`RTree`] of its envelopes.
+///
+/// [`GeometryArrayTrait::rstar_tree`] builds a new tree of scalars on every call. This wrapper
+/// bulk loads the tree once, so it can be shared by any number of queries. The tree stores the
+/// bounding box and index of each geometry; queries filter candidates from the tree and then
+/// check them against the exact geometry. Null and empty geometries are never returned.
+#[derive(Debug, Clone)]
+pub struct IndexedGeometryArray<G> {
+    array: G,
+    tree: RTree<EnvelopeEntry>,
+}
+
+impl<G> IndexedGeometryArray<G>
+where
+    G: for<'a> GeometryArrayTrait<'a>,
+    for<'a> <G as GeometryArrayTrait<'a>>::Scalar: RTreeObject<Envelope = AABB<[f64; 2]>>,
+    for<'a> <G as GeometryArrayTrait<'a>>::ScalarGeo: Into<geo::Geometry>,
+{
+    /// Index `array`.
+    pub fn new(array: G) -> Self {
+        let tree = envelope_tree(&array);
+        Self { array, tree }
+    }
+
+    /// The indexed array.
+    pub fn array(&self) -> &G {
+        &self.array
+    }
+
+    /// The envelope index. Each entry's data is the position of its geometry in the array.
+    pub fn tree(&self) -> &RTree<EnvelopeEntry> {
+        &self.tree
+    }
+
+    /// Consume this wrapper, returning the array and dropping the index.
+    pub fn into_inner(self) -> G {
+        self.array
+    }
+
+    /// Indices of the geometries whose bounding box intersects `bbox`, in ascending order.
+    pub fn query_bbox(&self, bbox: &geo::Rect) -> Vec<usize> {
+        let envelope = AABB::from_corners(bbox.min().x_y().into(), bbox.max().x_y().into());
+        let mut indices: Vec<usize> = self
+            .tree
+            .locate_in_envelope_intersecting(&envelope)
+            .map(|entry| entry.data)
+            .collect();
+        indices.sort_unstable();
+        indices
+    }
+
+    /// The index of the geometry closest to `point` and its distance, or `None` if the array
+    /// has no non-empty geometries.
+    ///
+    /// Ties are broken by the lower index.
+    pub fn nearest_neighbor(&self, point: &geo::Point) -> Option<(usize, f64)> {
+        self.nearest_neighbors(point, 1).into_iter().next()
+    }
+
+    /// The indices of the `k` geometries closest to `point` and their distances, nearest first.
+    ///
+    /// Fewer than `k` entries are returned if the array has fewer non-empty geometries. Ties are
+    /// broken by the lower index.
+    pub fn nearest_neighbors(&self, point: &geo::Point, k: usize) -> Vec<(usize, f64)> {
+        k_nearest(&self.tree, point, k, |idx| {
+            self.array.value_as_geo(idx).into()
+        })
+    }
+
+    /// Indices of the geometries within `distance` of `point`, in ascending order.
+    pub fn within_distance(&self, point: &geo::Point, distance: f64) -> Vec<usize> {
+        let mut indices: Vec<usize> = self
+            .tree
+            .locate_within_distance([point.x(), point.y()], distance * distance)
+            .map(|entry| entry.data)
+            .filter(|idx| {
+                let geom: geo::Geometry = self.array.value_as_geo(*idx).into();
+                distance_to(&geom, point).map_or(false, |d| d <= distance)
+            })
+            .collect();
+        indices.sort_unstable();
+        indices
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LineStringArray, PointArray, PolygonArray, WKBArray};
+    use geo::{coord, line_string, point, polygon};
+
+    fn points() -> IndexedGeometryArray<PointArray> {
+        let arr: PointArray = vec![
+            Some(point!(x: 0., y: 0.)),
+            None,
+            Some(point!(x: 5., y: 5.)),
+            Some(point!(x: 1., y: 1.)),
+        ]
+        .into();
+        IndexedGeometryArray::new(arr)
+    }
+
+    #[test]
+    fn cached_envelopes() {
+        let arr: WKBArray = vec![
+            None,
+            Some(geo::Geometry::Point(point!(x: 1., y: 2.))),
+            Some(geo::Geometry::Point(point!(x: 10., y: 20.))),
+        ]
+        .into();
+        let tree = arr.rstar_tree();
+        assert_eq!(tree.size(), 2);
+
+        let search_box = AABB::from_corners([0., 0.], [5., 5.]);
+        let entry = tree.locate_in_envelope(&search_box).next().unwrap();
+        assert_eq!(entry.geom_index(), 1);
+        assert_eq!(entry.envelope(), AABB::from_point([1., 2.]));
+        assert_eq!(entry.geom_index, 1);
+    }
+
+    #[test]
+    fn query_points() {
+        let indexed = points();
+        assert_eq!(indexed.tree().size(), 3);
+
+        let bbox = geo::Rect::new(coord! { x: -1., y: -1. }, coord! { x: 2., y: 2. });
+        assert_eq!(indexed.query_bbox(&bbox), vec![0, 3]);
+
+        assert_eq!(
+            indexed.nearest_neighbor(&point!(x: 4., y: 4.)),
+            Some((2, 2_f64.sqrt()))
+        );
+        assert_eq!(
+            indexed.within_distance(&point!(x: 0.5, y: 0.5), 1.),
+            vec![0, 3]
+        );
+    }
+
+    #[test]
+    fn exact_distances() {
+        // (0, 4) lies within the line's bounding box, but is 2√2 away from the line itself
+        let lines: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 4., y: 4.)]].into();
+        let indexed = IndexedGeometryArray::new(lines);
+        assert!(indexed
+            .within_distance(&point!(x: 0., y: 4.), 1.)
+            .is_empty());
+
+        let polygons: PolygonArray = vec![
+            polygon![(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 4.)],
+            polygon![(x: 10., y: 0.), (x: 12., y: 0.), (x: 12., y: 2.)],
+        ]
+        .into();
+        let indexed = IndexedGeometryArray::new(polygons);
+        assert_eq!(
+            indexed.nearest_neighbor(&point!(x: 1., y: 1.)),
+            Some((0, 0.))
+        );
+        assert_eq!(
+            indexed.nearest_neighbor(&point!(x: 13., y: 1.)),
+            Some((1, 1.))
+        );
+        assert_eq!(indexed.into_inner().len(), 2);
+    }
+}
@@ -0,0 +1,502 @@
+//! Read delimited text files with a geometry column.
+//!
+//! Geometries are read either from a column of WKT strings or from a pair of longitude and
+//! latitude columns. The types of the other columns are inferred from their values, as booleans,
+//! integers, floats, dates, timestamps or strings.
+
+use crate::coerce::{coerce_chunks, from_geo};
+use crate::error::GeoArrowError;
+use crate::io::stream::ChunkParts;
+use crate::io::wkt::from_wkt;
+use crate::io::{apply_filters, AttributeFilter, GeoTableReader, ReaderOptions};
+use crate::table::GeoTable;
+use crate::{GeometryArray, GeometryArrayType, PointArray};
+use arrow2::array::{Array, PrimitiveArray, Utf8Array};
+use arrow2::bitmap::Bitmap;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::io::csv::read::{
+    deserialize_batch, deserialize_column, infer, infer_schema, read_rows, ByteRecord, Reader,
+    ReaderBuilder,
+};
+use std::io::{Read, Seek};
+
+/// The number of rows in each output chunk when [`ReaderOptions::batch_size`] is not set.
+const DEFAULT_BATCH_SIZE: usize = 65536;
+
+/// Where the geometries of a CSV file are read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvGeometry {
+    /// A column of WKT strings, replaced in the output by a geometry column of the same name.
+    /// Empty strings are null geometries.
+    Wkt(String),
+
+    /// A column of longitudes (or x coordinates) and a column of latitudes (or y coordinates),
+    /// replaced in the output by a point column named `"geometry"`. Rows where either value is
+    /// missing or not a number are null points.
+    LonLat { lon: String, lat: String },
+}
+
+/// Options describing the layout of a CSV file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvReaderOptions {
+    /// The column or columns holding the geometries.
+    pub geometry: CsvGeometry,
+
+    /// The field delimiter.
+    pub delimiter: u8,
+
+    /// Whether the first line holds column names. Otherwise columns are named `column_1`,
+    /// `column_2`, etc.
+    pub has_header: bool,
+
+    /// The number of rows read to infer column types. `None` reads the whole file.
+    pub infer_schema_rows: Option<usize>,
+
+    /// The CRS of the geometries, if known.
+    pub crs: Option<String>,
+}
+
+impl CsvReaderOptions {
+    /// Options for a comma-delimited file with a header, with geometries in the WKT column
+    /// `column`.
+    pub fn wkt(column: impl Into<String>) -> Self {
+        Self::new(CsvGeometry::Wkt(column.into()))
+    }
+
+    /// Options for a comma-delimited file with a header, with points in the columns `lon` and
+    /// `lat`.
+    pub fn lon_lat(lon: impl Into<String>, lat: impl Into<String>) -> Self {
+        Self::new(CsvGeometry::LonLat {
+            lon: lon.into(),
+            lat: lat.into(),
+        })
+    }
+
+    fn new(geometry: CsvGeometry) -> Self {
+        Self {
+            geometry,
+            delimiter: b',',
+            has_header: true,
+            infer_schema_rows: Some(1000),
+            crs: None,
+        }
+    }
+
+    /// The columns the geometries are read from.
+    fn source_columns(&self) -> Vec<&str> {
+        match &self.geometry {
+            CsvGeometry::Wkt(column) => vec![column],
+            CsvGeometry::LonLat { lon, lat } => vec![lon, lat],
+        }
+    }
+
+    /// The name of the output geometry column.
+    fn geometry_column(&self) -> &str {
+        match &self.geometry {
+            CsvGeometry::Wkt(column) => column,
+            CsvGeometry::LonLat { .. } => "geometry",
+        }
+    }
+}
+
+/// Reads a CSV file `options.batch_size` rows at a time, as a [`GeoTableReader`].
+///
+/// The geometry column of each table has the narrowest type holding the geometries of its own
+/// chunk, so tables of a WKT column may have geometry columns of different types.
+pub struct CsvReader<R: Read> {
+    reader: Reader<R>,
+    csv_options: CsvReaderOptions,
+    filters: Vec<AttributeFilter>,
+    /// The fields of every column of the file
+    fields: Vec<Field>,
+    /// The columns needed either for output, for evaluating a filter, or for building geometries
+    projection: Vec<usize>,
+    read_schema: Schema,
+    /// Positions within `read_schema` of the attribute columns to output
+    output_indices: Vec<usize>,
+    /// Positions within `read_schema` of the geometry sources
+    source_indices: Vec<usize>,
+    rows: Vec<ByteRecord>,
+    /// The number of rows read so far
+    row_offset: usize,
+}
+
+impl<R: Read> CsvReader<R> {
+    /// Infer the schema of the CSV file `reader`, with `csv_options` and `options` applied as in
+    /// [`read_csv`].
+    /// # Errors
+    /// Errors if the file is not valid CSV or if a geometry column is missing.
+    pub fn try_new(
+        reader: R,
+        csv_options: &CsvReaderOptions,
+        options: &ReaderOptions,
+    ) -> Result<Self, GeoArrowError> {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(csv_options.delimiter)
+            .has_headers(csv_options.has_header)
+            .from_reader(reader);
+        let (mut fields, _) = infer_schema(
+            &mut reader,
+            csv_options.infer_schema_rows,
+            csv_options.has_header,
+            &infer,
+        )
+        .map_err(|err| GeoArrowError::External(err.into()))?;
+
+        // Geometry columns are parsed with the type they are read as, whatever their values look
+        // like
+        let sources = csv_options.source_columns();
+        for source in &sources {
+            let field = fields
+                .iter_mut()
+                .find(|field| &field.name == source)
+                .ok_or_else(|| GeoArrowError::General(format!("column {source} not found")))?;
+            field.data_type = match csv_options.geometry {
+                CsvGeometry::Wkt(_) => DataType::Utf8,
+                CsvGeometry::LonLat { .. } => DataType::Float64,
+            };
+        }
+
+        let projection: Vec<usize> = (0..fields.len())
+            .filter(|idx| {
+                let name = fields[*idx].name.as_str();
+                sources.contains(&name) || options.is_required(name)
+            })
+            .collect();
+        let read_schema = Schema::from(
+            projection
+                .iter()
+                .map(|idx| fields[*idx].clone())
+                .collect::<Vec<_>>(),
+        );
+
+        let output_indices: Vec<usize> = read_schema
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| {
+                !sources.contains(&field.name.as_str()) && options.is_selected(&field.name)
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        let source_indices: Vec<usize> = sources
+            .iter()
+            .map(|source| {
+                read_schema
+                    .fields
+                    .iter()
+                    .position(|field| &field.name == source)
+                    .unwrap()
+            })
+            .collect();
+
+        let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+        Ok(Self {
+            reader,
+            csv_options: csv_options.clone(),
+            filters: options.filters.clone(),
+            fields,
+            projection,
+            read_schema,
+            output_indices,
+            source_indices,
+            rows: vec![ByteRecord::default(); batch_size],
+            row_offset: 0,
+        })
+    }
+
+    /// The fields of the attribute columns to output.
+    fn output_fields(&self) -> Vec<Field> {
+        self.output_indices
+            .iter()
+            .map(|idx| self.read_schema.fields[*idx].clone())
+            .collect()
+    }
+
+    /// The attribute columns and the geometries of the next chunk with rows left after
+    /// filtering.
+    fn next_chunk(&mut self) -> Result<Option<ChunkParts>, GeoArrowError> {
+        loop {
+            let num_rows = read_rows(&mut self.reader, 0, &mut self.rows)
+                .map_err(|err| GeoArrowError::External(err.into()))?;
+            if num_rows == 0 {
+                return Ok(None);
+            }
+            let chunk = deserialize_batch(
+                &self.rows[..num_rows],
+                &self.fields,
+                Some(&self.projection),
+                self.row_offset,
+                deserialize_column,
+            )
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+
+            // Geometries are parsed before filtering, so that errors point at rows of the file,
+            // and filtered along with the other columns
+            let source_arrays: Vec<&dyn Array> = self
+                .source_indices
+                .iter()
+                .map(|idx| chunk.arrays()[*idx].as_ref())
+                .collect();
+            let geometry =
+                read_geometry(&self.csv_options.geometry, &source_arrays, self.row_offset)?;
+            let (geometry_field, geometry) =
+                geometry.into_arrow_field(self.csv_options.geometry_column());
+            let mut arrays = chunk.into_arrays();
+            arrays.push(geometry);
+            let chunk = apply_filters(&self.read_schema, &Chunk::new(arrays), &self.filters)?;
+            self.row_offset += num_rows;
+            if chunk.is_empty() {
+                continue;
+            }
+
+            let mut arrays = chunk.into_arrays();
+            let geometry =
+                GeometryArray::from_arrow_field(&geometry_field, arrays.pop().unwrap().as_ref())?;
+            let arrays = self
+                .output_indices
+                .iter()
+                .map(|idx| arrays[*idx].clone())
+                .collect();
+            return Ok(Some((arrays, geometry)));
+        }
+    }
+}
+
+impl<R: Read> Iterator for CsvReader<R> {
+    type Item = Result<GeoTable, GeoArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut arrays, geometry) = match self.next_chunk() {
+            Ok(chunk) => chunk?,
+            Err(err) => return Some(Err(err)),
+        };
+        let (geometry_field, geometry) = geometry
+            .with_crs(self.csv_options.crs.as_deref())
+            .into_arrow_field(self.csv_options.geometry_column());
+        arrays.push(geometry);
+        let mut fields = self.output_fields();
+        fields.push(geometry_field);
+        let geometry_column_index = fields.len() - 1;
+        Some(GeoTable::try_new(
+            Schema::from(fields),
+            vec![Chunk::new(arrays)],
+            geometry_column_index,
+        ))
+    }
+}
+
+impl<R: Read> GeoTableReader for CsvReader<R> {
+    fn schema(&self) -> Option<&Schema> {
+        None
+    }
+}
+
+/// Read a CSV file into a [`GeoTable`], whose geometry column is the last column.
+///
+/// Only the columns selected in `options` (plus the geometry) are returned, and only rows
+/// matching every attribute filter are kept. Columns that are neither selected nor referenced by a
+/// filter are never parsed. The file is read `options.batch_size` rows at a time; use
+/// [`CsvReader`] to process one chunk at a time instead.
+///
+/// WKT columns are read as in [`from_wkt`], promoted to the narrowest type holding the
+/// geometries of every chunk.
+/// # Errors
+/// Errors if the file is not valid CSV, if a geometry column is missing, or with
+/// [`GeoArrowError::InvalidWkt`] if a WKT string cannot be parsed. Rows of errors are counted
+/// from the first row after the header.
+pub fn read_csv<R: Read + Seek>(
+    reader: R,
+    csv_options: &CsvReaderOptions,
+    options: &ReaderOptions,
+) -> Result<GeoTable, GeoArrowError> {
+    let mut reader = CsvReader::try_new(reader, csv_options, options)?;
+    let mut chunks = vec![];
+    while let Some(chunk) = reader.next_chunk()? {
+        chunks.push(chunk);
+    }
+
+    // The narrowest geometry type holding every chunk
+    let fallback = match csv_options.geometry {
+        CsvGeometry::Wkt(_) => GeometryArrayType::WKB,
+        CsvGeometry::LonLat { .. } => GeometryArrayType::Point,
+    };
+    let (arrays, geometries): (Vec<_>, Vec<_>) = chunks.into_iter().unzip();
+    let (kind, geometries) = coerce_chunks(geometries, fallback)?;
+    let to_output = |geometry: GeometryArray| {
+        geometry
+            .with_crs(csv_options.crs.as_deref())
+            .into_arrow_field(csv_options.geometry_column())
+    };
+
+    let (geometry_field, _) = to_output(from_geo(vec![], kind));
+    let chunks = arrays
+        .into_iter()
+        .zip(geometries)
+        .map(|(mut arrays, geometry)| {
+            arrays.push(to_output(geometry).1);
+            Chunk::new(arrays)
+        })
+        .collect();
+
+    let mut output_fields = reader.output_fields();
+    output_fields.push(geometry_field);
+    let geometry_column_index = output_fields.len() - 1;
+    GeoTable::try_new(Schema::from(output_fields), chunks, geometry_column_index)
+}
+
+/// Build the geometries of one chunk from its `sources` columns.
+///
+/// `row_offset` is the number of rows before the chunk, for error messages.
+fn read_geometry(
+    geometry: &CsvGeometry,
+    sources: &[&dyn Array],
+    row_offset: usize,
+) -> Result<GeometryArray, GeoArrowError> {
+    match geometry {
+        CsvGeometry::Wkt(_) => {
+            let wkt = sources[0]
+                .as_any()
+                .downcast_ref::<Utf8Array<i32>>()
+                .unwrap();
+            // Empty fields are read as empty strings
+            let wkt: Utf8Array<i32> = wkt
+                .iter()
+                .map(|maybe_wkt| maybe_wkt.filter(|wkt| !wkt.trim().is_empty()))
+                .collect();
+            from_wkt(&wkt).map_err(|err| match err {
+                GeoArrowError::InvalidWkt { row, message } => GeoArrowError::InvalidWkt {
+                    row: row + row_offset,
+                    message,
+                },
+                err => err,
+            })
+        }
+        CsvGeometry::LonLat { .. } => {
+            let lon = sources[0]
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f64>>()
+                .unwrap();
+            let lat = sources[1]
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f64>>()
+                .unwrap();
+            let validity = match (lon.validity(), lat.validity()) {
+                (Some(lon), Some(lat)) => Some(lon & lat),
+                (Some(validity), None) | (None, Some(validity)) => Some(validity.clone()),
+                (None, None) => None::<Bitmap>,
+            };
+            let points = PointArray::new(lon.values().clone(), lat.values().clone(), validity);
+            Ok(GeometryArray::Point(points))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::{AttributeFilter, ComparisonOp, FilterValue};
+    use crate::GeometryArrayTrait;
+    use geo::point;
+    use std::io::Cursor;
+
+    const WKT_CSV: &str = "id,name,wkt
+1,a,POINT (0 1)
+2,b,
+3,c,MULTIPOINT ((2 3))
+";
+
+    #[test]
+    fn wkt() {
+        let options = ReaderOptions {
+            batch_size: Some(2),
+            ..Default::default()
+        };
+        let csv_options = CsvReaderOptions {
+            crs: Some("EPSG:4326".to_string()),
+            ..CsvReaderOptions::wkt("wkt")
+        };
+        let table = read_csv(Cursor::new(WKT_CSV), &csv_options, &options).unwrap();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.chunks().len(), 2);
+        assert_eq!(table.schema().fields[0].data_type(), &DataType::Int64);
+        assert_eq!(table.geometry_field().name, "wkt");
+
+        // Points in the first chunk are promoted to match the multi points of the second
+        let geometry = table.geometry().unwrap();
+        assert!(matches!(geometry[0], GeometryArray::MultiPoint(_)));
+        assert!(matches!(geometry[1], GeometryArray::MultiPoint(_)));
+        assert_eq!(geometry[0].crs(), Some("EPSG:4326"));
+        assert!(geometry[0].is_null(1));
+    }
+
+    #[test]
+    fn lon_lat() {
+        let csv = "name;lon;lat\na;1.5;2\nb;;3\nc;-4;5.25\n";
+        let csv_options = CsvReaderOptions {
+            delimiter: b';',
+            ..CsvReaderOptions::lon_lat("lon", "lat")
+        };
+        let options = ReaderOptions {
+            filters: vec![AttributeFilter::new(
+                "name",
+                ComparisonOp::NotEq,
+                FilterValue::Utf8("a".to_string()),
+            )],
+            ..Default::default()
+        };
+        let table = read_csv(Cursor::new(csv), &csv_options, &options).unwrap();
+        assert_eq!(table.num_columns(), 2);
+        assert_eq!(table.geometry_field().name, "geometry");
+
+        let geometry = table.geometry().unwrap();
+        assert!(geometry[0].is_null(0));
+        assert_eq!(
+            geometry[0].get_as_geo(1),
+            Some(point!(x: -4., y: 5.25).into())
+        );
+    }
+
+    #[test]
+    fn stream() {
+        let options = ReaderOptions {
+            batch_size: Some(2),
+            ..Default::default()
+        };
+        let reader = CsvReader::try_new(
+            Cursor::new(WKT_CSV),
+            &CsvReaderOptions::wkt("wkt"),
+            &options,
+        )
+        .unwrap();
+        assert!(reader.schema().is_none());
+
+        // Each table keeps the geometry type of its own chunk
+        let tables = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(tables.len(), 2);
+        assert!(matches!(
+            tables[0].geometry().unwrap()[0],
+            GeometryArray::Point(_)
+        ));
+        assert!(matches!(
+            tables[1].geometry().unwrap()[0],
+            GeometryArray::MultiPoint(_)
+        ));
+        assert_eq!(GeoTable::concat(&tables).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn invalid_wkt() {
+        let csv = "wkt\nPOINT (0 1)\nPOINT (0\n";
+        let options = ReaderOptions {
+            batch_size: Some(1),
+            ..Default::default()
+        };
+        let err = read_csv(Cursor::new(csv), &CsvReaderOptions::wkt("wkt"), &options).unwrap_err();
+        assert!(matches!(err, GeoArrowError::InvalidWkt { row: 1, .. }));
+
+        let missing = read_csv(Cursor::new(csv), &CsvReaderOptions::wkt("geom"), &options);
+        assert!(missing.is_err());
+    }
+}
@@ -0,0 +1,365 @@
+use crate::error::GeoArrowError;
+use arrow2::array::{Array, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::chunk::Chunk;
+use arrow2::compute::filter::filter_chunk;
+use arrow2::datatypes::{DataType, Schema};
+use arrow2::offset::Offset;
+use arrow2::types::NativeType;
+use std::cmp::Ordering;
+
+/// A comparison operator used in an [`AttributeFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl ComparisonOp {
+    fn evaluate<T: PartialOrd + ?Sized>(&self, lhs: &T, rhs: &T) -> bool {
+        self.matches(lhs.partial_cmp(rhs))
+    }
+
+    /// Whether a value ordered as `ordering` relative to the filter value matches.
+    fn matches(&self, ordering: Option<Ordering>) -> bool {
+        match ordering {
+            Some(ordering) => match self {
+                ComparisonOp::Eq => ordering == Ordering::Equal,
+                ComparisonOp::NotEq => ordering != Ordering::Equal,
+                ComparisonOp::Lt => ordering == Ordering::Less,
+                ComparisonOp::LtEq => ordering != Ordering::Greater,
+                ComparisonOp::Gt => ordering == Ordering::Greater,
+                ComparisonOp::GtEq => ordering != Ordering::Less,
+            },
+            // Only reachable for NaN
+            None => *self == ComparisonOp::NotEq,
+        }
+    }
+}
+
+/// A scalar value that an attribute column is compared against.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Boolean(bool),
+    Int(i64),
+    Float(f64),
+    Utf8(String),
+}
+
+/// A simple predicate on a single attribute column, of the form `column <op> value`.
+///
+/// Rows where the column is null never match. Integer columns are compared exactly, even with
+/// values beyond the range where `f64` is exact; float columns are compared as `f64`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeFilter {
+    pub column: String,
+    pub op: ComparisonOp,
+    pub value: FilterValue,
+}
+
+impl AttributeFilter {
+    pub fn new(column: impl Into<String>, op: ComparisonOp, value: FilterValue) -> Self {
+        Self {
+            column: column.into(),
+            op,
+            value,
+        }
+    }
+
+    /// Evaluate this filter against a chunk described by `schema`, returning a mask of the rows
+    /// that match.
+    pub fn evaluate<A: AsRef<dyn Array>>(
+        &self,
+        schema: &Schema,
+        chunk: &Chunk<A>,
+    ) -> Result<Bitmap, GeoArrowError> {
+        let column_idx = schema
+            .fields
+            .iter()
+            .position(|field| field.name == self.column)
+            .ok_or_else(|| {
+                GeoArrowError::General(format!("filter column {} not found", self.column))
+            })?;
+        let array = chunk.arrays()[column_idx].as_ref();
+
+        match (array.data_type(), &self.value) {
+            (DataType::Boolean, FilterValue::Boolean(value)) => {
+                let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+                Ok(compare_iter(
+                    array.iter(),
+                    |lhs| self.op.evaluate(&lhs, value),
+                    array.len(),
+                ))
+            }
+            (DataType::Utf8, FilterValue::Utf8(value)) => {
+                Ok(compare_utf8::<i32>(array, self.op, value))
+            }
+            (DataType::LargeUtf8, FilterValue::Utf8(value)) => {
+                Ok(compare_utf8::<i64>(array, self.op, value))
+            }
+            (data_type, FilterValue::Int(_) | FilterValue::Float(_)) => {
+                let value = &self.value;
+                match data_type {
+                    DataType::Int8 => Ok(compare_integer::<i8>(array, self.op, value)),
+                    DataType::Int16 => Ok(compare_integer::<i16>(array, self.op, value)),
+                    DataType::Int32 => Ok(compare_integer::<i32>(array, self.op, value)),
+                    DataType::Int64 => Ok(compare_integer::<i64>(array, self.op, value)),
+                    DataType::UInt8 => Ok(compare_integer::<u8>(array, self.op, value)),
+                    DataType::UInt16 => Ok(compare_integer::<u16>(array, self.op, value)),
+                    DataType::UInt32 => Ok(compare_integer::<u32>(array, self.op, value)),
+                    DataType::UInt64 => Ok(compare_integer::<u64>(array, self.op, value)),
+                    DataType::Float32 => {
+                        Ok(compare_float(array, |v: f32| v as f64, self.op, value))
+                    }
+                    DataType::Float64 => Ok(compare_float(array, |v: f64| v, self.op, value)),
+                    data_type => Err(GeoArrowError::General(format!(
+                        "cannot compare column {} of type {:?} with a number",
+                        self.column, data_type
+                    ))),
+                }
+            }
+            (data_type, value) => Err(GeoArrowError::General(format!(
+                "cannot compare column {} of type {:?} with {:?}",
+                self.column, data_type, value
+            ))),
+        }
+    }
+}
+
+fn compare_iter<T>(
+    values: impl Iterator<Item = Option<T>>,
+    predicate: impl Fn(T) -> bool,
+    len: usize,
+) -> Bitmap {
+    let mut mask = MutableBitmap::with_capacity(len);
+    for value in values {
+        mask.push(value.map_or(false, &predicate));
+    }
+    mask.into()
+}
+
+/// The order of the integer `lhs` relative to the numeric `value`, without rounding either.
+///
+/// Returns `None` if `value` is NaN or not a number.
+pub(crate) fn cmp_integer(lhs: i128, value: &FilterValue) -> Option<Ordering> {
+    let value = match *value {
+        FilterValue::Int(value) => return Some(lhs.cmp(&i128::from(value))),
+        FilterValue::Float(value) if !value.is_nan() => value,
+        _ => return None,
+    };
+    // Every i64 and u64 lies strictly within these bounds, which are exact as f64
+    if value >= 2_f64.powi(64) {
+        return Some(Ordering::Less);
+    }
+    if value < -(2_f64.powi(63)) {
+        return Some(Ordering::Greater);
+    }
+
+    let floor = value.floor();
+    match lhs.cmp(&(floor as i128)) {
+        Ordering::Equal if floor != value => Some(Ordering::Less),
+        ordering => Some(ordering),
+    }
+}
+
+/// Compare an integer column exactly, widening both sides to `i128`.
+fn compare_integer<T: NativeType + Into<i128>>(
+    array: &dyn Array,
+    op: ComparisonOp,
+    value: &FilterValue,
+) -> Bitmap {
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    compare_iter(
+        array.iter(),
+        |lhs| op.matches(cmp_integer((*lhs).into(), value)),
+        array.len(),
+    )
+}
+
+fn compare_float<T: NativeType>(
+    array: &dyn Array,
+    to_f64: impl Fn(T) -> f64,
+    op: ComparisonOp,
+    value: &FilterValue,
+) -> Bitmap {
+    let value = match *value {
+        FilterValue::Int(value) => value as f64,
+        FilterValue::Float(value) => value,
+        _ => unreachable!(),
+    };
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    compare_iter(
+        array.iter(),
+        |lhs| op.evaluate(&to_f64(*lhs), &value),
+        array.len(),
+    )
+}
+
+fn compare_utf8<O: Offset>(array: &dyn Array, op: ComparisonOp, value: &str) -> Bitmap {
+    let array = array.as_any().downcast_ref::<Utf8Array<O>>().unwrap();
+    compare_iter(array.iter(), |lhs| op.evaluate(lhs, value), array.len())
+}
+
+/// Keep only the rows of `chunk` that match every one of `filters`.
+pub fn apply_filters<A: AsRef<dyn Array>>(
+    schema: &Schema,
+    chunk: &Chunk<A>,
+    filters: &[AttributeFilter],
+) -> Result<Chunk<Box<dyn Array>>, GeoArrowError> {
+    let mut mask: Option<Bitmap> = None;
+    for filter in filters {
+        let filter_mask = filter.evaluate(schema, chunk)?;
+        mask = Some(match mask {
+            Some(mask) => &mask & &filter_mask,
+            None => filter_mask,
+        });
+    }
+
+    match mask {
+        Some(mask) => {
+            let mask = BooleanArray::new(DataType::Boolean, mask, None);
+            filter_chunk(chunk, &mask).map_err(|err| GeoArrowError::External(err.into()))
+        }
+        None => Ok(Chunk::new(
+            chunk
+                .arrays()
+                .iter()
+                .map(|arr| arr.as_ref().to_boxed())
+                .collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::{Int32Array, Int64Array, UInt64Array};
+    use arrow2::datatypes::Field;
+
+    fn chunk() -> (Schema, Chunk<Box<dyn Array>>) {
+        let schema = Schema::from(vec![
+            Field::new("population", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        let chunk = Chunk::new(vec![
+            Int32Array::from(vec![Some(10), Some(200), None, Some(3000)]).boxed(),
+            Utf8Array::<i32>::from(vec![Some("a"), Some("b"), Some("c"), None]).boxed(),
+        ]);
+        (schema, chunk)
+    }
+
+    #[test]
+    fn numeric_filter() {
+        let (schema, chunk) = chunk();
+        let filter = AttributeFilter::new("population", ComparisonOp::Gt, FilterValue::Int(100));
+        let mask = filter.evaluate(&schema, &chunk).unwrap();
+        assert_eq!(
+            mask.iter().collect::<Vec<_>>(),
+            vec![false, true, false, true]
+        );
+    }
+
+    #[test]
+    fn large_integers() {
+        // 2^53 + 1 is the first integer that f64 cannot represent
+        let big = 1_i64 << 53;
+        let schema = Schema::from(vec![
+            Field::new("signed", DataType::Int64, true),
+            Field::new("unsigned", DataType::UInt64, true),
+        ]);
+        let chunk = Chunk::new(vec![
+            Int64Array::from_slice([big, big + 1, -big - 1]).boxed(),
+            UInt64Array::from_slice([u64::MAX - 1, u64::MAX, big as u64 + 1]).boxed(),
+        ]);
+        let mask = |column: &str, op: ComparisonOp, value: FilterValue| {
+            AttributeFilter::new(column, op, value)
+                .evaluate(&schema, &chunk)
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>()
+        };
+
+        let value = FilterValue::Int(big + 1);
+        assert_eq!(
+            mask("signed", ComparisonOp::Eq, value.clone()),
+            [false, true, false]
+        );
+        assert_eq!(
+            mask("unsigned", ComparisonOp::Eq, value),
+            [false, false, true]
+        );
+        assert_eq!(
+            mask("signed", ComparisonOp::Lt, FilterValue::Int(-big)),
+            [false, false, true]
+        );
+        assert_eq!(
+            mask("unsigned", ComparisonOp::Gt, FilterValue::Int(i64::MAX)),
+            [true, true, false]
+        );
+
+        // Float values are compared without rounding the column
+        let value = FilterValue::Float(big as f64);
+        assert_eq!(
+            mask("signed", ComparisonOp::Gt, value.clone()),
+            [false, true, false]
+        );
+        assert_eq!(
+            mask("signed", ComparisonOp::Eq, value),
+            [true, false, false]
+        );
+        assert_eq!(
+            mask(
+                "signed",
+                ComparisonOp::Lt,
+                FilterValue::Float(big as f64 + 2.)
+            ),
+            [true, true, true]
+        );
+        assert_eq!(
+            mask(
+                "unsigned",
+                ComparisonOp::Lt,
+                FilterValue::Float(2_f64.powi(64))
+            ),
+            [true, true, true]
+        );
+        assert_eq!(
+            mask("signed", ComparisonOp::NotEq, FilterValue::Float(f64::NAN)),
+            [true, true, true]
+        );
+    }
+
+    #[test]
+    fn fractional_values() {
+        let (schema, chunk) = chunk();
+        let filter =
+            AttributeFilter::new("population", ComparisonOp::LtEq, FilterValue::Float(200.5));
+        let mask = filter.evaluate(&schema, &chunk).unwrap();
+        assert_eq!(
+            mask.iter().collect::<Vec<_>>(),
+            vec![true, true, false, false]
+        );
+    }
+
+    #[test]
+    fn combined_filters() {
+        let (schema, chunk) = chunk();
+        let filters = vec![
+            AttributeFilter::new("population", ComparisonOp::GtEq, FilterValue::Float(10.)),
+            AttributeFilter::new("name", ComparisonOp::NotEq, FilterValue::Utf8("a".into())),
+        ];
+        let filtered = apply_filters(&schema, &chunk, &filters).unwrap();
+        assert_eq!(filtered.len(), 1);
+    }
+
+    #[test]
+    fn mismatched_types() {
+        let (schema, chunk) = chunk();
+        let filter = AttributeFilter::new("name", ComparisonOp::Eq, FilterValue::Int(1));
+        assert!(filter.evaluate(&schema, &chunk).is_err());
+    }
+}
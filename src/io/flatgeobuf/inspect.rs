@@ -0,0 +1,151 @@
+use crate::error::GeoArrowError;
+use crate::io::info::bounds_2d;
+use crate::io::DatasetInfo;
+use crate::GeometryArrayType;
+use arrow2::datatypes::{DataType, Field, Schema};
+use flatgeobuf::{ColumnType, FgbReader, GeometryType, Header};
+use std::io::{Read, Seek};
+
+/// The name given to the geometry field of a FlatGeobuf schema. FlatGeobuf does not store a name
+/// for its geometry column.
+pub(super) const GEOMETRY_COLUMN_NAME: &str = "geometry";
+
+/// Summarize a FlatGeobuf file from its header, without reading any features.
+pub fn inspect_flatgeobuf<R: Read + Seek>(reader: &mut R) -> Result<DatasetInfo, GeoArrowError> {
+    let fgb = FgbReader::open(reader).map_err(|err| GeoArrowError::External(err.into()))?;
+    let header = fgb.header();
+
+    let mut fields = vec![];
+    if let Some(columns) = header.columns() {
+        for column in columns.iter() {
+            fields.push(Field::new(
+                column.name(),
+                column_data_type(column.type_())?,
+                column.nullable(),
+            ));
+        }
+    }
+    fields.push(Field::new(
+        GEOMETRY_COLUMN_NAME,
+        DataType::LargeBinary,
+        true,
+    ));
+
+    let geometry_types = geometry_type_name(&header)
+        .map(|name| vec![name])
+        .unwrap_or_default();
+
+    Ok(DatasetInfo {
+        schema: Schema::from(fields),
+        geometry_column: GEOMETRY_COLUMN_NAME.to_string(),
+        crs: header_crs(&header),
+        bounds: header
+            .envelope()
+            .and_then(|envelope| bounds_2d(&envelope.iter().collect::<Vec<f64>>())),
+        // A features count of 0 means unknown
+        num_features: match header.features_count() {
+            0 => None,
+            count => Some(count as usize),
+        },
+        geometry_types,
+    })
+}
+
+/// The Arrow data type used to represent a FlatGeobuf property column.
+pub(super) fn column_data_type(column_type: ColumnType) -> Result<DataType, GeoArrowError> {
+    let data_type = match column_type {
+        ColumnType::Byte => DataType::Int8,
+        ColumnType::UByte => DataType::UInt8,
+        ColumnType::Bool => DataType::Boolean,
+        ColumnType::Short => DataType::Int16,
+        ColumnType::UShort => DataType::UInt16,
+        ColumnType::Int => DataType::Int32,
+        ColumnType::UInt => DataType::UInt32,
+        ColumnType::Long => DataType::Int64,
+        ColumnType::ULong => DataType::UInt64,
+        ColumnType::Float => DataType::Float32,
+        ColumnType::Double => DataType::Float64,
+        ColumnType::String | ColumnType::Json | ColumnType::DateTime => DataType::Utf8,
+        ColumnType::Binary => DataType::Binary,
+        column_type => {
+            return Err(GeoArrowError::NotYetImplemented(format!(
+                "FlatGeobuf column type {:?}",
+                column_type
+            )))
+        }
+    };
+    Ok(data_type)
+}
+
+/// The CRS declared in a FlatGeobuf header, as WKT or as an `<org>:<code>` identifier.
+pub(super) fn header_crs(header: &Header) -> Option<String> {
+    let crs = header.crs()?;
+    if let Some(wkt) = crs.wkt() {
+        return Some(wkt.to_string());
+    }
+
+    let code = crs.code();
+    if code == 0 {
+        return None;
+    }
+    Some(format!("{}:{}", crs.org().unwrap_or("EPSG"), code))
+}
+
+/// The type of array holding the geometries of a FlatGeobuf file with `header`.
+///
+/// Geometry collections and `Unknown` (mixed) geometry types are held as WKB.
+pub(super) fn header_geometry_type(header: &Header) -> GeometryArrayType {
+    match header.geometry_type() {
+        GeometryType::Point => GeometryArrayType::Point,
+        GeometryType::LineString => GeometryArrayType::LineString,
+        GeometryType::Polygon => GeometryArrayType::Polygon,
+        GeometryType::MultiPoint => GeometryArrayType::MultiPoint,
+        GeometryType::MultiLineString => GeometryArrayType::MultiLineString,
+        GeometryType::MultiPolygon => GeometryArrayType::MultiPolygon,
+        _ => GeometryArrayType::WKB,
+    }
+}
+
+/// The GeoParquet name of the geometry type declared in a FlatGeobuf header.
+///
+/// Returns `None` for `Unknown` (mixed) geometry types.
+fn geometry_type_name(header: &Header) -> Option<String> {
+    let name = match header.geometry_type() {
+        GeometryType::Point => "Point",
+        GeometryType::LineString => "LineString",
+        GeometryType::Polygon => "Polygon",
+        GeometryType::MultiPoint => "MultiPoint",
+        GeometryType::MultiLineString => "MultiLineString",
+        GeometryType::MultiPolygon => "MultiPolygon",
+        GeometryType::GeometryCollection => "GeometryCollection",
+        _ => return None,
+    };
+
+    if header.has_z() {
+        Some(format!("{} Z", name))
+    } else {
+        Some(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn inspect_countries() {
+        let mut file = File::open("fixtures/flatgeobuf/countries.fgb").unwrap();
+        let info = inspect_flatgeobuf(&mut file).unwrap();
+
+        let names: Vec<_> = info.schema.fields.iter().map(|field| &field.name).collect();
+        assert_eq!(names, ["id", "name", "geometry"]);
+        assert_eq!(info.schema.fields[1].data_type(), &DataType::Utf8);
+        assert_eq!(info.geometry_column, "geometry");
+        assert_eq!(info.geometry_types, ["MultiPolygon"]);
+        assert_eq!(info.bounds, Some([-180., -85.609038, 180., 83.64513]));
+        assert_eq!(info.num_features, Some(179));
+        // The header stores both the EPSG code and the WKT, which is preferred
+        assert!(info.crs.unwrap().starts_with(r#"GEOGCRS["WGS 84""#));
+    }
+}
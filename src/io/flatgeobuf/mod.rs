@@ -0,0 +1,7 @@
+//! Read [FlatGeobuf](https://flatgeobuf.org/) files.
+
+pub use inspect::inspect_flatgeobuf;
+pub use reader::{read_flatgeobuf, FlatGeobufReader};
+
+mod inspect;
+mod reader;
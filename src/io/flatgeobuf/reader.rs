@@ -0,0 +1,425 @@
+use super::inspect::{column_data_type, header_crs, header_geometry_type, GEOMETRY_COLUMN_NAME};
+use crate::coerce::{coerce, from_geo, promote_geometry};
+use crate::error::GeoArrowError;
+use crate::io::stream::ChunkParts;
+use crate::io::{apply_filters, AttributeFilter, GeoTableReader, ReaderOptions};
+use crate::table::GeoTable;
+use crate::{GeometryArray, GeometryArrayType};
+use arrow2::array::{
+    Array, MutableArray, MutableBinaryArray, MutableBooleanArray, MutablePrimitiveArray,
+    MutableUtf8Array,
+};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use flatgeobuf::{FallibleStreamingIterator, FeatureIter, FgbReader, Seekable};
+use geozero::{ColumnValue, FeatureProperties, PropertyProcessor, ToGeo};
+use std::io::{Read, Seek};
+
+/// The number of rows in each output chunk when [`ReaderOptions::batch_size`] is not set.
+const DEFAULT_BATCH_SIZE: usize = 65536;
+
+/// Builds the array of a property column.
+enum ColumnBuilder {
+    Boolean(MutableBooleanArray),
+    Int8(MutablePrimitiveArray<i8>),
+    UInt8(MutablePrimitiveArray<u8>),
+    Int16(MutablePrimitiveArray<i16>),
+    UInt16(MutablePrimitiveArray<u16>),
+    Int32(MutablePrimitiveArray<i32>),
+    UInt32(MutablePrimitiveArray<u32>),
+    Int64(MutablePrimitiveArray<i64>),
+    UInt64(MutablePrimitiveArray<u64>),
+    Float32(MutablePrimitiveArray<f32>),
+    Float64(MutablePrimitiveArray<f64>),
+    Utf8(MutableUtf8Array<i32>),
+    Binary(MutableBinaryArray<i32>),
+}
+
+/// Apply `$expr` to the inner builder of any variant of `$self`.
+macro_rules! with_builder {
+    ($self:expr, $builder:ident => $expr:expr) => {
+        match $self {
+            ColumnBuilder::Boolean($builder) => $expr,
+            ColumnBuilder::Int8($builder) => $expr,
+            ColumnBuilder::UInt8($builder) => $expr,
+            ColumnBuilder::Int16($builder) => $expr,
+            ColumnBuilder::UInt16($builder) => $expr,
+            ColumnBuilder::Int32($builder) => $expr,
+            ColumnBuilder::UInt32($builder) => $expr,
+            ColumnBuilder::Int64($builder) => $expr,
+            ColumnBuilder::UInt64($builder) => $expr,
+            ColumnBuilder::Float32($builder) => $expr,
+            ColumnBuilder::Float64($builder) => $expr,
+            ColumnBuilder::Utf8($builder) => $expr,
+            ColumnBuilder::Binary($builder) => $expr,
+        }
+    };
+}
+
+impl ColumnBuilder {
+    /// A builder for a column of type `data_type`, as given by [`column_data_type`].
+    fn new(data_type: &DataType) -> Self {
+        match data_type {
+            DataType::Boolean => Self::Boolean(MutableBooleanArray::new()),
+            DataType::Int8 => Self::Int8(MutablePrimitiveArray::new()),
+            DataType::UInt8 => Self::UInt8(MutablePrimitiveArray::new()),
+            DataType::Int16 => Self::Int16(MutablePrimitiveArray::new()),
+            DataType::UInt16 => Self::UInt16(MutablePrimitiveArray::new()),
+            DataType::Int32 => Self::Int32(MutablePrimitiveArray::new()),
+            DataType::UInt32 => Self::UInt32(MutablePrimitiveArray::new()),
+            DataType::Int64 => Self::Int64(MutablePrimitiveArray::new()),
+            DataType::UInt64 => Self::UInt64(MutablePrimitiveArray::new()),
+            DataType::Float32 => Self::Float32(MutablePrimitiveArray::new()),
+            DataType::Float64 => Self::Float64(MutablePrimitiveArray::new()),
+            DataType::Binary => Self::Binary(MutableBinaryArray::new()),
+            _ => Self::Utf8(MutableUtf8Array::new()),
+        }
+    }
+
+    fn len(&self) -> usize {
+        with_builder!(self, builder => builder.len())
+    }
+
+    fn push_null(&mut self) {
+        with_builder!(self, builder => builder.push_null())
+    }
+
+    /// Push `value`. Returns `false` if `value` does not have the type of this column.
+    fn push(&mut self, value: &ColumnValue) -> bool {
+        match (self, value) {
+            (Self::Boolean(builder), ColumnValue::Bool(value)) => builder.push(Some(*value)),
+            (Self::Int8(builder), ColumnValue::Byte(value)) => builder.push(Some(*value)),
+            (Self::UInt8(builder), ColumnValue::UByte(value)) => builder.push(Some(*value)),
+            (Self::Int16(builder), ColumnValue::Short(value)) => builder.push(Some(*value)),
+            (Self::UInt16(builder), ColumnValue::UShort(value)) => builder.push(Some(*value)),
+            (Self::Int32(builder), ColumnValue::Int(value)) => builder.push(Some(*value)),
+            (Self::UInt32(builder), ColumnValue::UInt(value)) => builder.push(Some(*value)),
+            (Self::Int64(builder), ColumnValue::Long(value)) => builder.push(Some(*value)),
+            (Self::UInt64(builder), ColumnValue::ULong(value)) => builder.push(Some(*value)),
+            (Self::Float32(builder), ColumnValue::Float(value)) => builder.push(Some(*value)),
+            (Self::Float64(builder), ColumnValue::Double(value)) => builder.push(Some(*value)),
+            (
+                Self::Utf8(builder),
+                ColumnValue::String(value)
+                | ColumnValue::Json(value)
+                | ColumnValue::DateTime(value),
+            ) => builder.push(Some(*value)),
+            (Self::Binary(builder), ColumnValue::Binary(value)) => builder.push(Some(*value)),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Take the values pushed so far as an array, leaving the builder empty.
+    fn take(&mut self) -> Box<dyn Array> {
+        with_builder!(self, builder => builder.as_box())
+    }
+}
+
+/// Pushes the properties of one feature to the builders of the columns being read.
+struct PropertyRow<'a> {
+    /// The builders of every column of the file, `None` for columns not read
+    builders: &'a mut [Option<ColumnBuilder>],
+    /// The number of rows pushed before this one
+    len: usize,
+    /// The first column whose value does not have the type of its column
+    mismatch: Option<usize>,
+}
+
+impl PropertyProcessor for PropertyRow<'_> {
+    fn property(
+        &mut self,
+        idx: usize,
+        _name: &str,
+        value: &ColumnValue,
+    ) -> geozero::error::Result<bool> {
+        if let Some(Some(builder)) = self.builders.get_mut(idx) {
+            // Only the first value of a column repeated within a feature is kept
+            if builder.len() == self.len && !builder.push(value) {
+                self.mismatch = Some(idx);
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Reads a FlatGeobuf file `options.batch_size` features at a time, as a [`GeoTableReader`].
+///
+/// The schema of every table is known from the header before any feature is read; see
+/// [`read_flatgeobuf`] for the type of its geometry column.
+pub struct FlatGeobufReader<R: Read + Seek> {
+    features: FeatureIter<R, Seekable>,
+    filters: Vec<AttributeFilter>,
+    /// The names of every column of the file
+    column_names: Vec<String>,
+    /// The columns needed either for output or for evaluating a filter
+    read_schema: Schema,
+    /// Positions within `read_schema` of the columns to output
+    output_indices: Vec<usize>,
+    /// The builders of every column of the file, `None` for columns not read
+    builders: Vec<Option<ColumnBuilder>>,
+    /// The type of the geometry column, declared by the header
+    geometry_type: GeometryArrayType,
+    /// The schema of every table yielded
+    schema: Schema,
+    batch_size: usize,
+    /// The number of features read so far
+    row_number: usize,
+}
+
+impl<R: Read + Seek> FlatGeobufReader<R> {
+    /// Open the FlatGeobuf file `reader`, with `options` and `bbox` applied as in
+    /// [`read_flatgeobuf`].
+    /// # Errors
+    /// Errors if the header cannot be read or declares columns of unsupported types.
+    pub fn try_new(
+        reader: R,
+        options: &ReaderOptions,
+        bbox: Option<[f64; 4]>,
+    ) -> Result<Self, GeoArrowError> {
+        let fgb = FgbReader::open(reader).map_err(|err| GeoArrowError::External(err.into()))?;
+        let header = fgb.header();
+        let crs = header_crs(&header);
+
+        let mut column_names = vec![];
+        let mut fields = vec![];
+        let mut builders = vec![];
+        if let Some(columns) = header.columns() {
+            for column in columns.iter() {
+                let name = column.name().to_string();
+                let builder = if options.is_required(&name) {
+                    let data_type = column_data_type(column.type_())?;
+                    let builder = ColumnBuilder::new(&data_type);
+                    fields.push(Field::new(name.clone(), data_type, true));
+                    Some(builder)
+                } else {
+                    None
+                };
+                column_names.push(name);
+                builders.push(builder);
+            }
+        }
+        let output_indices: Vec<usize> = fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| options.is_selected(&field.name))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let geometry_type = header_geometry_type(&header);
+        let mut output_fields: Vec<Field> = output_indices
+            .iter()
+            .map(|idx| fields[*idx].clone())
+            .collect();
+        let (geometry_field, _) = from_geo(vec![], geometry_type)
+            .with_crs(crs.as_deref())
+            .into_arrow_field(GEOMETRY_COLUMN_NAME);
+        output_fields.push(geometry_field);
+
+        let features = match bbox {
+            Some([minx, miny, maxx, maxy]) => fgb.select_bbox(minx, miny, maxx, maxy),
+            None => fgb.select_all(),
+        }
+        .map_err(|err| GeoArrowError::External(err.into()))?;
+
+        Ok(Self {
+            features,
+            filters: options.filters.clone(),
+            column_names,
+            read_schema: Schema::from(fields),
+            output_indices,
+            builders,
+            geometry_type,
+            schema: Schema::from(output_fields),
+            batch_size: options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1),
+            row_number: 0,
+        })
+    }
+
+    /// The attribute columns and the geometries of the next chunk with rows left after
+    /// filtering.
+    fn next_chunk(&mut self) -> Result<Option<ChunkParts>, GeoArrowError> {
+        loop {
+            let mut geoms = Vec::with_capacity(self.batch_size);
+            while geoms.len() < self.batch_size {
+                let feature = match self
+                    .features
+                    .next()
+                    .map_err(|err| GeoArrowError::External(err.into()))?
+                {
+                    Some(feature) => feature,
+                    None => break,
+                };
+
+                let mut row = PropertyRow {
+                    builders: &mut self.builders,
+                    len: geoms.len(),
+                    mismatch: None,
+                };
+                feature
+                    .process_properties(&mut row)
+                    .map_err(|err| GeoArrowError::External(err.into()))?;
+                if let Some(idx) = row.mismatch {
+                    return Err(GeoArrowError::General(format!(
+                        "feature {}: value of column {} does not match its declared type",
+                        self.row_number, self.column_names[idx]
+                    )));
+                }
+                // Properties missing from the feature are null
+                for builder in self.builders.iter_mut().flatten() {
+                    if builder.len() == geoms.len() {
+                        builder.push_null();
+                    }
+                }
+
+                let geom = match feature.geometry() {
+                    Some(_) => Some(feature.to_geo().map_err(|err| {
+                        GeoArrowError::General(format!(
+                            "feature {}: invalid geometry: {err}",
+                            self.row_number
+                        ))
+                    })?),
+                    None => None,
+                };
+                geoms.push(geom);
+                self.row_number += 1;
+            }
+            if geoms.is_empty() {
+                return Ok(None);
+            }
+
+            let geoms_len = geoms.len();
+            let geoms = geoms
+                .into_iter()
+                .map(|geom| geom.map(|geom| promote_geometry(geom, self.geometry_type)))
+                .collect();
+            let geometry = coerce(from_geo(geoms, self.geometry_type), self.geometry_type)
+                .map_err(|_| {
+                    GeoArrowError::General(format!(
+                        "features {}..{} do not all have the geometry type {:?} of the header",
+                        self.row_number - geoms_len,
+                        self.row_number,
+                        self.geometry_type
+                    ))
+                })?;
+            let (geometry_field, geometry) = geometry.into_arrow_field(GEOMETRY_COLUMN_NAME);
+            let mut arrays: Vec<Box<dyn Array>> = self
+                .builders
+                .iter_mut()
+                .flatten()
+                .map(|builder| builder.take())
+                .collect();
+            arrays.push(geometry);
+            let chunk = apply_filters(&self.read_schema, &Chunk::new(arrays), &self.filters)?;
+            if chunk.is_empty() {
+                continue;
+            }
+
+            let mut arrays = chunk.into_arrays();
+            let geometry =
+                GeometryArray::from_arrow_field(&geometry_field, arrays.pop().unwrap().as_ref())?;
+            let arrays = self
+                .output_indices
+                .iter()
+                .map(|idx| arrays[*idx].clone())
+                .collect();
+            return Ok(Some((arrays, geometry)));
+        }
+    }
+}
+
+impl<R: Read + Seek> Iterator for FlatGeobufReader<R> {
+    type Item = Result<GeoTable, GeoArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut arrays, geometry) = match self.next_chunk() {
+            Ok(chunk) => chunk?,
+            Err(err) => return Some(Err(err)),
+        };
+        arrays.push(geometry.into_arrow());
+        Some(GeoTable::try_new(
+            self.schema.clone(),
+            vec![Chunk::new(arrays)],
+            self.schema.fields.len() - 1,
+        ))
+    }
+}
+
+impl<R: Read + Seek> GeoTableReader for FlatGeobufReader<R> {
+    fn schema(&self) -> Option<&Schema> {
+        Some(&self.schema)
+    }
+}
+
+/// Read a FlatGeobuf file into a [`GeoTable`], whose geometry column is the last column, named
+/// `"geometry"`.
+///
+/// Only the columns selected in `options` (plus the geometry) are returned, and only features
+/// matching every attribute filter are kept. Columns that are neither selected nor referenced by a
+/// filter are skipped. When `bbox` is given as `[minx, miny, maxx, maxy]`, only features whose
+/// bounding boxes intersect it are read, using the spatial index of the file. Use
+/// [`FlatGeobufReader`] to process one chunk at a time instead.
+///
+/// Geometries are stored in the native array of the geometry type declared by the header, with
+/// single geometries read as multi geometries in multi-geometry files, and carry the CRS of the
+/// header. Geometry collections and files of mixed (`Unknown`) geometry type are read as WKB.
+/// # Errors
+/// Errors if the file cannot be read, if its header declares columns of unsupported types, if a
+/// feature does not have the geometry type of the header, or if `bbox` is given and the file has no
+/// spatial index.
+pub fn read_flatgeobuf<R: Read + Seek>(
+    reader: R,
+    options: &ReaderOptions,
+    bbox: Option<[f64; 4]>,
+) -> Result<GeoTable, GeoArrowError> {
+    let mut reader = FlatGeobufReader::try_new(reader, options, bbox)?;
+    let mut chunks = vec![];
+    while let Some((mut arrays, geometry)) = reader.next_chunk()? {
+        arrays.push(geometry.into_arrow());
+        chunks.push(Chunk::new(arrays));
+    }
+
+    let geometry_column_index = reader.schema.fields.len() - 1;
+    GeoTable::try_new(reader.schema, chunks, geometry_column_index)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::enum_::{EXTENSION_METADATA_KEY, EXTENSION_NAME_KEY};
+    use std::fs::File;
+
+    #[test]
+    fn schema_from_header() {
+        // 179 multi polygons in EPSG:4326, with string `id` and `name` columns
+        let file = File::open("fixtures/flatgeobuf/countries.fgb").unwrap();
+        let options = ReaderOptions {
+            columns: Some(vec!["name".to_string()]),
+            batch_size: Some(100),
+            ..Default::default()
+        };
+        let reader = FlatGeobufReader::try_new(file, &options, None).unwrap();
+
+        let schema = reader.schema().unwrap().clone();
+        let names: Vec<_> = schema.fields.iter().map(|field| &field.name).collect();
+        assert_eq!(names, ["name", "geometry"]);
+        assert_eq!(schema.fields[0].data_type(), &DataType::Utf8);
+        let metadata = &schema.fields[1].metadata;
+        assert_eq!(
+            metadata.get(EXTENSION_NAME_KEY).unwrap(),
+            "geoarrow.multipolygon"
+        );
+        assert!(metadata
+            .get(EXTENSION_METADATA_KEY)
+            .unwrap()
+            .contains("WGS 84"));
+
+        let tables = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables.iter().map(GeoTable::len).sum::<usize>(), 179);
+        for table in &tables {
+            assert_eq!(table.schema(), &schema);
+        }
+    }
+}
@@ -0,0 +1,484 @@
+//! Read [newline-delimited GeoJSON](https://stevage.github.io/ndgeojson/) files.
+//!
+//! Each non-empty line holds one GeoJSON feature. The types of its properties are inferred from
+//! the first chunk of features, as booleans, integers, floats or strings; nested objects and
+//! arrays are read as their JSON text.
+
+use crate::coerce::{coerce_chunks, from_geo, from_geo_promoted};
+use crate::error::GeoArrowError;
+use crate::io::stream::ChunkParts;
+use crate::io::{apply_filters, AttributeFilter, GeoTableReader, ReaderOptions};
+use crate::table::GeoTable;
+use crate::{GeometryArray, GeometryArrayType};
+use arrow2::array::{
+    Array, MutableArray, MutableBooleanArray, MutablePrimitiveArray, MutableUtf8Array,
+};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use geozero::geojson::GeoJson;
+use geozero::ToGeo;
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::io::{BufRead, Lines};
+
+/// The number of rows in each output chunk when [`ReaderOptions::batch_size`] is not set.
+const DEFAULT_BATCH_SIZE: usize = 65536;
+
+/// The name of the output geometry column.
+const GEOMETRY_COLUMN_NAME: &str = "geometry";
+
+/// The Arrow type of a JSON value, or `None` for `null`.
+fn value_data_type(value: &Value) -> Option<DataType> {
+    match value {
+        Value::Null => None,
+        Value::Bool(_) => Some(DataType::Boolean),
+        Value::Number(number) if number.is_i64() => Some(DataType::Int64),
+        Value::Number(_) => Some(DataType::Float64),
+        Value::String(_) | Value::Array(_) | Value::Object(_) => Some(DataType::Utf8),
+    }
+}
+
+/// The narrowest type holding values of both `left` and `right`.
+fn merge_data_types(left: DataType, right: DataType) -> DataType {
+    match (left, right) {
+        (left, right) if left == right => left,
+        (DataType::Int64, DataType::Float64) | (DataType::Float64, DataType::Int64) => {
+            DataType::Float64
+        }
+        _ => DataType::Utf8,
+    }
+}
+
+/// Builds the array of a property column.
+enum PropertyBuilder {
+    Boolean(MutableBooleanArray),
+    Int64(MutablePrimitiveArray<i64>),
+    Float64(MutablePrimitiveArray<f64>),
+    Utf8(MutableUtf8Array<i32>),
+}
+
+impl PropertyBuilder {
+    fn new(data_type: &DataType) -> Self {
+        match data_type {
+            DataType::Boolean => Self::Boolean(MutableBooleanArray::new()),
+            DataType::Int64 => Self::Int64(MutablePrimitiveArray::new()),
+            DataType::Float64 => Self::Float64(MutablePrimitiveArray::new()),
+            _ => Self::Utf8(MutableUtf8Array::new()),
+        }
+    }
+
+    fn data_type(&self) -> &DataType {
+        match self {
+            Self::Boolean(builder) => builder.data_type(),
+            Self::Int64(builder) => builder.data_type(),
+            Self::Float64(builder) => builder.data_type(),
+            Self::Utf8(builder) => builder.data_type(),
+        }
+    }
+
+    /// Push `value`, where a missing value is null. Returns `false` if `value` does not fit the
+    /// type of this column.
+    fn push(&mut self, value: Option<&Value>) -> bool {
+        let value = value.filter(|value| !value.is_null());
+        match (self, value) {
+            (Self::Boolean(builder), None) => builder.push_null(),
+            (Self::Int64(builder), None) => builder.push_null(),
+            (Self::Float64(builder), None) => builder.push_null(),
+            (Self::Utf8(builder), None) => builder.push_null(),
+            (Self::Boolean(builder), Some(Value::Bool(value))) => builder.push(Some(*value)),
+            (Self::Int64(builder), Some(value)) if value.is_i64() => builder.push(value.as_i64()),
+            (Self::Float64(builder), Some(Value::Number(value))) => builder.push(value.as_f64()),
+            (Self::Utf8(builder), Some(Value::String(value))) => builder.push(Some(value)),
+            (Self::Utf8(builder), Some(value)) => builder.push(Some(value.to_string())),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Take the values pushed so far as an array, leaving the builder empty.
+    fn take(&mut self) -> Box<dyn Array> {
+        match self {
+            Self::Boolean(builder) => builder.as_box(),
+            Self::Int64(builder) => builder.as_box(),
+            Self::Float64(builder) => builder.as_box(),
+            Self::Utf8(builder) => builder.as_box(),
+        }
+    }
+}
+
+/// A feature parsed from one line of the file.
+struct Feature {
+    /// The line of the feature, counted from 0
+    line: usize,
+    properties: Map<String, Value>,
+    geometry: Option<geo::Geometry>,
+}
+
+impl Feature {
+    fn parse(line: usize, text: &str) -> Result<Self, GeoArrowError> {
+        let invalid = |message: String| GeoArrowError::General(format!("line {line}: {message}"));
+        let mut feature = match serde_json::from_str(text) {
+            Ok(Value::Object(feature)) => feature,
+            Ok(_) => return Err(invalid("expected a GeoJSON feature".to_string())),
+            Err(err) => return Err(invalid(err.to_string())),
+        };
+        let properties = match feature.remove("properties") {
+            Some(Value::Object(properties)) => properties,
+            None | Some(Value::Null) => Map::new(),
+            Some(_) => return Err(invalid("properties must be an object".to_string())),
+        };
+        let geometry = match feature.remove("geometry") {
+            None | Some(Value::Null) => None,
+            Some(geometry) => Some(
+                GeoJson(&geometry.to_string())
+                    .to_geo()
+                    .map_err(|err| invalid(err.to_string()))?,
+            ),
+        };
+        Ok(Self {
+            line,
+            properties,
+            geometry,
+        })
+    }
+}
+
+/// Reads a newline-delimited GeoJSON file `options.batch_size` features at a time, as a
+/// [`GeoTableReader`].
+///
+/// The geometry column of each table has the narrowest type holding the geometries of its own
+/// chunk, so tables may have geometry columns of different types.
+pub struct GeoJsonLinesReader<R: BufRead> {
+    lines: Lines<R>,
+    /// Features read to infer the schema and not yet returned
+    pending: VecDeque<Feature>,
+    filters: Vec<AttributeFilter>,
+    /// The property columns needed either for output or for evaluating a filter
+    read_schema: Schema,
+    /// Positions within `read_schema` of the property columns to output
+    output_indices: Vec<usize>,
+    builders: Vec<PropertyBuilder>,
+    batch_size: usize,
+    /// The number of lines read so far
+    line_number: usize,
+}
+
+impl<R: BufRead> GeoJsonLinesReader<R> {
+    /// Read the first `options.batch_size` features of `reader` to infer the types of their
+    /// properties.
+    ///
+    /// Only the properties selected in `options` or referenced by a filter are kept.
+    /// # Errors
+    /// Errors if one of the first features cannot be read.
+    pub fn try_new(reader: R, options: &ReaderOptions) -> Result<Self, GeoArrowError> {
+        let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+        let mut reader = Self {
+            lines: reader.lines(),
+            pending: VecDeque::new(),
+            filters: options.filters.clone(),
+            read_schema: Schema::from(vec![]),
+            output_indices: vec![],
+            builders: vec![],
+            batch_size,
+            line_number: 0,
+        };
+        while reader.pending.len() < batch_size {
+            match reader.next_feature()? {
+                Some(feature) => reader.pending.push_back(feature),
+                None => break,
+            }
+        }
+
+        // Columns are ordered by their first appearance, and columns of only nulls are strings
+        let mut fields: Vec<(String, Option<DataType>)> = vec![];
+        for feature in &reader.pending {
+            for (name, value) in &feature.properties {
+                if !options.is_required(name) {
+                    continue;
+                }
+                let data_type = value_data_type(value);
+                match fields.iter_mut().find(|(field, _)| field == name) {
+                    Some((_, existing)) => {
+                        *existing = match (existing.take(), data_type) {
+                            (Some(left), Some(right)) => Some(merge_data_types(left, right)),
+                            (left, right) => left.or(right),
+                        }
+                    }
+                    None => fields.push((name.clone(), data_type)),
+                }
+            }
+        }
+        let fields: Vec<Field> = fields
+            .into_iter()
+            .map(|(name, data_type)| Field::new(name, data_type.unwrap_or(DataType::Utf8), true))
+            .collect();
+
+        reader.output_indices = fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| options.is_selected(&field.name))
+            .map(|(idx, _)| idx)
+            .collect();
+        reader.builders = fields
+            .iter()
+            .map(|field| PropertyBuilder::new(field.data_type()))
+            .collect();
+        reader.read_schema = Schema::from(fields);
+        Ok(reader)
+    }
+
+    /// The next feature of the file, skipping empty lines.
+    fn next_feature(&mut self) -> Result<Option<Feature>, GeoArrowError> {
+        for line in self.lines.by_ref() {
+            let line = line.map_err(|err| GeoArrowError::External(err.into()))?;
+            let line_number = self.line_number;
+            self.line_number += 1;
+            if !line.trim().is_empty() {
+                return Feature::parse(line_number, &line).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// The fields of the property columns to output.
+    fn output_fields(&self) -> Vec<Field> {
+        self.output_indices
+            .iter()
+            .map(|idx| self.read_schema.fields[*idx].clone())
+            .collect()
+    }
+
+    /// Push the properties of `feature` to the column builders.
+    fn push_properties(&mut self, feature: &Feature) -> Result<(), GeoArrowError> {
+        for (field, builder) in self.read_schema.fields.iter().zip(self.builders.iter_mut()) {
+            let value = feature.properties.get(&field.name);
+            if !builder.push(value) {
+                return Err(GeoArrowError::SchemaMismatch {
+                    column: format!("{} (line {})", field.name, feature.line),
+                    expected: Box::new(builder.data_type().clone()),
+                    found: Box::new(value.and_then(value_data_type).unwrap()),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The property columns and the geometries of the next chunk with rows left after
+    /// filtering.
+    fn next_chunk(&mut self) -> Result<Option<ChunkParts>, GeoArrowError> {
+        loop {
+            let mut geoms = Vec::with_capacity(self.batch_size);
+            while geoms.len() < self.batch_size {
+                let feature = match self.pending.pop_front() {
+                    Some(feature) => feature,
+                    None => match self.next_feature()? {
+                        Some(feature) => feature,
+                        None => break,
+                    },
+                };
+                self.push_properties(&feature)?;
+                geoms.push(feature.geometry);
+            }
+            if geoms.is_empty() {
+                return Ok(None);
+            }
+
+            let (geometry_field, geometry) =
+                from_geo_promoted(geoms).into_arrow_field(GEOMETRY_COLUMN_NAME);
+            let mut arrays: Vec<Box<dyn Array>> = self
+                .builders
+                .iter_mut()
+                .map(|builder| builder.take())
+                .collect();
+            arrays.push(geometry);
+            let chunk = apply_filters(&self.read_schema, &Chunk::new(arrays), &self.filters)?;
+            if chunk.is_empty() {
+                continue;
+            }
+
+            let mut arrays = chunk.into_arrays();
+            let geometry =
+                GeometryArray::from_arrow_field(&geometry_field, arrays.pop().unwrap().as_ref())?;
+            let arrays = self
+                .output_indices
+                .iter()
+                .map(|idx| arrays[*idx].clone())
+                .collect();
+            return Ok(Some((arrays, geometry)));
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for GeoJsonLinesReader<R> {
+    type Item = Result<GeoTable, GeoArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut arrays, geometry) = match self.next_chunk() {
+            Ok(chunk) => chunk?,
+            Err(err) => return Some(Err(err)),
+        };
+        let (geometry_field, geometry) = geometry.into_arrow_field(GEOMETRY_COLUMN_NAME);
+        arrays.push(geometry);
+        let mut fields = self.output_fields();
+        fields.push(geometry_field);
+        let geometry_column_index = fields.len() - 1;
+        Some(GeoTable::try_new(
+            Schema::from(fields),
+            vec![Chunk::new(arrays)],
+            geometry_column_index,
+        ))
+    }
+}
+
+impl<R: BufRead> GeoTableReader for GeoJsonLinesReader<R> {
+    fn schema(&self) -> Option<&Schema> {
+        None
+    }
+}
+
+/// Read a newline-delimited GeoJSON file into a [`GeoTable`], whose geometry column is the last
+/// column, named `"geometry"`.
+///
+/// Only the properties selected in `options` (plus the geometry) are returned, and only features
+/// matching every attribute filter are kept. Property types are inferred from the first
+/// `options.batch_size` features; properties first seen after those are skipped. Use
+/// [`GeoJsonLinesReader`] to process one chunk at a time instead.
+///
+/// Geometries are stored in the narrowest type holding every feature, as in
+/// [`from_wkt`](crate::io::wkt::from_wkt).
+/// # Errors
+/// Errors if a line is not a valid GeoJSON feature, or with [`GeoArrowError::SchemaMismatch`] if
+/// a property does not match the type inferred for its column. Lines of errors are counted from
+/// 0.
+pub fn read_geojson_lines<R: BufRead>(
+    reader: R,
+    options: &ReaderOptions,
+) -> Result<GeoTable, GeoArrowError> {
+    let mut reader = GeoJsonLinesReader::try_new(reader, options)?;
+    let mut attributes = vec![];
+    let mut geometries = vec![];
+    while let Some((arrays, geometry)) = reader.next_chunk()? {
+        attributes.push(arrays);
+        geometries.push(geometry);
+    }
+
+    // The narrowest geometry type holding every chunk
+    let (kind, geometries) = coerce_chunks(geometries, GeometryArrayType::WKB)?;
+    let (geometry_field, _) = from_geo(vec![], kind).into_arrow_field(GEOMETRY_COLUMN_NAME);
+    let chunks = attributes
+        .into_iter()
+        .zip(geometries)
+        .map(|(mut arrays, geometry)| {
+            arrays.push(geometry.into_arrow_field(GEOMETRY_COLUMN_NAME).1);
+            Chunk::new(arrays)
+        })
+        .collect();
+
+    let mut fields = reader.output_fields();
+    fields.push(geometry_field);
+    let geometry_column_index = fields.len() - 1;
+    GeoTable::try_new(Schema::from(fields), chunks, geometry_column_index)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::{ComparisonOp, FilterValue};
+    use crate::GeometryArrayTrait;
+    use arrow2::array::{PrimitiveArray, Utf8Array};
+    use geo::point;
+    use std::io::Cursor;
+
+    const FEATURES: &str = r#"{"type": "Feature", "properties": {"id": 1, "name": "a", "score": 1}, "geometry": {"type": "Point", "coordinates": [0, 1]}}
+
+{"type": "Feature", "properties": {"id": 2, "name": "b", "score": 2.5}, "geometry": null}
+{"type": "Feature", "properties": {"id": 3, "name": "c", "tags": ["x"]}, "geometry": {"type": "MultiPoint", "coordinates": [[2, 3]]}}
+"#;
+
+    #[test]
+    fn infer_properties() {
+        let table = read_geojson_lines(Cursor::new(FEATURES), &ReaderOptions::default()).unwrap();
+        assert_eq!(table.len(), 3);
+        let fields: Vec<_> = table.schema().fields[..4]
+            .iter()
+            .map(|field| (field.name.as_str(), field.data_type().clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("id", DataType::Int64),
+                ("name", DataType::Utf8),
+                ("score", DataType::Float64),
+                ("tags", DataType::Utf8),
+            ]
+        );
+
+        let chunk = &table.chunks()[0];
+        let scores = chunk.arrays()[2]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .unwrap();
+        assert_eq!(scores, &PrimitiveArray::from([Some(1.), Some(2.5), None]));
+        let tags = chunk.arrays()[3]
+            .as_any()
+            .downcast_ref::<Utf8Array<i32>>()
+            .unwrap();
+        assert_eq!(tags.get(2), Some(r#"["x"]"#));
+
+        // Points and multi points are promoted to multi points
+        let geometry = table.geometry().unwrap();
+        assert!(matches!(geometry[0], GeometryArray::MultiPoint(_)));
+        assert!(geometry[0].is_null(1));
+    }
+
+    #[test]
+    fn projection_and_filters() {
+        let options = ReaderOptions {
+            columns: Some(vec!["name".to_string()]),
+            filters: vec![AttributeFilter::new(
+                "id",
+                ComparisonOp::NotEq,
+                FilterValue::Int(2),
+            )],
+            batch_size: Some(2),
+        };
+        let table = read_geojson_lines(Cursor::new(FEATURES), &options).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.chunks().len(), 2);
+        assert_eq!(table.num_columns(), 2);
+        assert_eq!(table.schema().fields[0].name, "name");
+        assert_eq!(table.geometry_field().name, "geometry");
+
+        let geometry = table.geometry().unwrap();
+        assert!(matches!(geometry[0], GeometryArray::MultiPoint(_)));
+        assert!(matches!(geometry[1], GeometryArray::MultiPoint(_)));
+
+        // Each streamed table keeps the geometry type of its own chunk
+        let tables = GeoJsonLinesReader::try_new(Cursor::new(FEATURES), &options)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(tables.len(), 2);
+        match &tables[0].geometry().unwrap()[0] {
+            GeometryArray::Point(points) => {
+                assert_eq!(points.get_as_geo(0), Some(point!(x: 0., y: 1.)))
+            }
+            _ => panic!("expected a point array"),
+        }
+    }
+
+    #[test]
+    fn invalid_features() {
+        let options = ReaderOptions {
+            batch_size: Some(1),
+            ..Default::default()
+        };
+        let lines = "{\"type\": \"Feature\", \"properties\": {\"id\": 1}, \"geometry\": null}\n\
+            {\"type\": \"Feature\", \"properties\": {\"id\": \"x\"}, \"geometry\": null}\n";
+        let err = read_geojson_lines(Cursor::new(lines), &options).unwrap_err();
+        assert!(matches!(err, GeoArrowError::SchemaMismatch { .. }));
+
+        let lines = "{\"type\": \"Feature\", \"geometry\": {\"type\": \"Point\"}}\n";
+        assert!(read_geojson_lines(Cursor::new(lines), &options).is_err());
+    }
+}
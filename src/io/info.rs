@@ -0,0 +1,54 @@
+use arrow2::datatypes::Schema;
+
+/// A summary of a dataset, read from file-level metadata only.
+///
+/// This is what the `inspect_*` functions return. None of these values require decoding any
+/// features, so it is cheap to produce even for very large files.
+#[derive(Debug, Clone)]
+pub struct DatasetInfo {
+    /// The Arrow schema of the dataset, including the geometry column.
+    pub schema: Schema,
+
+    /// The name of the primary geometry column.
+    pub geometry_column: String,
+
+    /// The coordinate reference system of the geometry column, if known. This is a PROJJSON
+    /// string for GeoParquet and an `AUTHORITY:CODE` or WKT string for FlatGeobuf.
+    pub crs: Option<String>,
+
+    /// The 2D bounds of the dataset as `[minx, miny, maxx, maxy]`, if stored in the file.
+    pub bounds: Option<[f64; 4]>,
+
+    /// The number of features, if stored in the file.
+    pub num_features: Option<usize>,
+
+    /// The geometry types present in the dataset, as named by the
+    /// [GeoParquet specification](https://github.com/opengeospatial/geoparquet) (e.g.
+    /// `"MultiPolygon"`). An empty list means the geometry types are unknown.
+    pub geometry_types: Vec<String>,
+}
+
+/// Reduce a bbox with either 2 or 3 dimensions to `[minx, miny, maxx, maxy]`.
+#[cfg(any(feature = "parquet", feature = "flatgeobuf"))]
+pub(crate) fn bounds_2d(bbox: &[f64]) -> Option<[f64; 4]> {
+    match bbox.len() {
+        4 => Some([bbox[0], bbox[1], bbox[2], bbox[3]]),
+        6 => Some([bbox[0], bbox[1], bbox[3], bbox[4]]),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, any(feature = "parquet", feature = "flatgeobuf")))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bounds_2d_drops_z() {
+        assert_eq!(bounds_2d(&[0., 1., 2., 3.]), Some([0., 1., 2., 3.]),);
+        assert_eq!(
+            bounds_2d(&[0., 1., 10., 2., 3., 20.]),
+            Some([0., 1., 2., 3.]),
+        );
+        assert_eq!(bounds_2d(&[0., 1.]), None);
+    }
+}
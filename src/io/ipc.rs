@@ -0,0 +1,252 @@
+//! Read and write [Arrow IPC] (Feather v2) files.
+//!
+//! Arrow IPC stores Arrow memory as is, so geometry columns are written in their GeoArrow layout
+//! without any conversion. Their extension name and CRS are stored in the field metadata, as
+//! `ARROW:extension:name` and `ARROW:extension:metadata`, where other GeoArrow implementations
+//! read them.
+//!
+//! [Arrow IPC]: https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format
+
+use crate::enum_::{extension_field, storage_field};
+use crate::error::GeoArrowError;
+use crate::io::{apply_filters, AttributeFilter, GeoTableReader, ReaderOptions};
+use crate::table::{default_geometry_column, GeoTable};
+use arrow2::array::Array;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::Schema;
+use arrow2::io::ipc::read::{read_file_metadata, FileReader};
+use arrow2::io::ipc::write::{FileWriter, WriteOptions};
+use std::io::{Read, Seek, Write};
+
+/// Reads an Arrow IPC file one record batch at a time, as a [`GeoTableReader`].
+pub struct IpcReader<R: Read + Seek> {
+    reader: FileReader<R>,
+    /// The columns needed either for output or for evaluating a filter
+    read_schema: Schema,
+    output_schema: Schema,
+    /// Positions within `read_schema` of the columns to output
+    output_indices: Vec<usize>,
+    filters: Vec<AttributeFilter>,
+    geometry_column: String,
+}
+
+impl<R: Read + Seek> IpcReader<R> {
+    /// Read the metadata of the Arrow IPC file `reader`, with `options` applied as in
+    /// [`read_ipc`].
+    pub fn try_new(mut reader: R, options: &ReaderOptions) -> Result<Self, GeoArrowError> {
+        let mut metadata =
+            read_file_metadata(&mut reader).map_err(|err| GeoArrowError::External(err.into()))?;
+        // Read geometry columns with their storage type, which is what geometry arrays are built
+        // from
+        metadata.schema.fields = metadata
+            .schema
+            .fields
+            .into_iter()
+            .map(storage_field)
+            .collect();
+
+        let schema = &metadata.schema;
+        let geometry_index = default_geometry_column(&schema.fields)
+            .ok_or_else(|| GeoArrowError::General("geometry column not found".to_string()))?;
+        let geometry_column = schema.fields[geometry_index].name.clone();
+
+        // Columns needed either for output or for evaluating a filter
+        let projection: Vec<usize> = (0..schema.fields.len())
+            .filter(|idx| *idx == geometry_index || options.is_required(&schema.fields[*idx].name))
+            .collect();
+        let read_schema = Schema::from(
+            projection
+                .iter()
+                .map(|idx| schema.fields[*idx].clone())
+                .collect::<Vec<_>>(),
+        );
+
+        let output_indices: Vec<usize> = read_schema
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.name == geometry_column || options.is_selected(&field.name))
+            .map(|(idx, _)| idx)
+            .collect();
+        let output_schema = read_schema
+            .clone()
+            .filter(|_, field| field.name == geometry_column || options.is_selected(&field.name))
+            .with_metadata(schema.metadata.clone());
+
+        Ok(Self {
+            reader: FileReader::new(reader, metadata, Some(projection), None),
+            read_schema,
+            output_schema,
+            output_indices,
+            filters: options.filters.clone(),
+            geometry_column,
+        })
+    }
+
+    /// The next chunk with rows left after filtering.
+    fn next_chunk(&mut self) -> Option<Result<Chunk<Box<dyn Array>>, GeoArrowError>> {
+        for maybe_chunk in self.reader.by_ref() {
+            let chunk = match maybe_chunk {
+                Ok(chunk) => chunk,
+                Err(err) => return Some(Err(GeoArrowError::External(err.into()))),
+            };
+            let chunk = match apply_filters(&self.read_schema, &chunk, &self.filters) {
+                Ok(chunk) => chunk,
+                Err(err) => return Some(Err(err)),
+            };
+            if chunk.is_empty() {
+                continue;
+            }
+
+            let arrays = self
+                .output_indices
+                .iter()
+                .map(|idx| chunk.arrays()[*idx].clone())
+                .collect();
+            return Some(Ok(Chunk::new(arrays)));
+        }
+        None
+    }
+}
+
+impl<R: Read + Seek> Iterator for IpcReader<R> {
+    type Item = Result<GeoTable, GeoArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.next_chunk()?;
+        Some(chunk.and_then(|chunk| {
+            GeoTable::from_arrow(
+                self.output_schema.clone(),
+                vec![chunk],
+                Some(&self.geometry_column),
+            )
+        }))
+    }
+}
+
+impl<R: Read + Seek> GeoTableReader for IpcReader<R> {
+    fn schema(&self) -> Option<&Schema> {
+        Some(&self.output_schema)
+    }
+}
+
+/// Read an Arrow IPC file into a [`GeoTable`].
+///
+/// The geometry column is the first column with a `geoarrow.*` extension name, or else the
+/// column named `"geometry"`. Only the columns selected in `options` (plus the geometry column)
+/// are returned, and only rows matching every attribute filter are kept. Columns that are neither
+/// selected nor referenced by a filter are never decoded. Each record batch of the file becomes
+/// one chunk, so `options.batch_size` is ignored.
+///
+/// Use [`IpcReader`] to read the file one record batch at a time instead.
+pub fn read_ipc<R: Read + Seek>(
+    reader: R,
+    options: &ReaderOptions,
+) -> Result<GeoTable, GeoArrowError> {
+    let mut reader = IpcReader::try_new(reader, options)?;
+    let mut chunks = vec![];
+    while let Some(chunk) = reader.next_chunk() {
+        chunks.push(chunk?);
+    }
+    GeoTable::from_arrow(reader.output_schema, chunks, Some(&reader.geometry_column))
+}
+
+/// Write `table` to an Arrow IPC file, one record batch per chunk.
+///
+/// Geometry columns keep their GeoArrow layout, extension name and CRS, so that [`read_ipc`] and
+/// other GeoArrow readers read them back as geometries.
+pub fn write_ipc<W: Write>(table: &GeoTable, writer: W) -> Result<(), GeoArrowError> {
+    // arrow2 only writes the extension metadata of extension data types
+    let fields = table
+        .schema()
+        .fields
+        .iter()
+        .cloned()
+        .map(extension_field)
+        .collect::<Vec<_>>();
+    let schema = Schema::from(fields).with_metadata(table.schema().metadata.clone());
+
+    let options = WriteOptions { compression: None };
+    let mut writer = FileWriter::try_new(writer, schema, None, options)
+        .map_err(|err| GeoArrowError::External(err.into()))?;
+    for chunk in table.chunks() {
+        writer
+            .write(chunk, None)
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+    }
+    writer
+        .finish()
+        .map_err(|err| GeoArrowError::External(err.into()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArray, PointArray};
+    use arrow2::array::PrimitiveArray;
+    use geo::point;
+    use std::io::Cursor;
+
+    fn table() -> GeoTable {
+        let points: PointArray =
+            vec![Some(point!(x: 0., y: 1.)), None, Some(point!(x: 2., y: 3.))].into();
+        let (geometry_field, geometry) = GeometryArray::Point(points)
+            .with_crs(Some("EPSG:4326"))
+            .into_arrow_field("geom");
+        let ids = PrimitiveArray::from_vec(vec![1i32, 2, 3]);
+        let schema = Schema::from(vec![
+            arrow2::datatypes::Field::new("id", ids.data_type().clone(), false),
+            geometry_field,
+        ]);
+        GeoTable::new(schema, vec![Chunk::new(vec![ids.boxed(), geometry])], 1)
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut buf = Cursor::new(vec![]);
+        write_ipc(&table(), &mut buf).unwrap();
+
+        buf.set_position(0);
+        let output = read_ipc(&mut buf, &ReaderOptions::default()).unwrap();
+        assert_eq!(output.schema(), table().schema());
+        assert_eq!(output.geometry_column_index(), 1);
+        assert_eq!(output.len(), 3);
+
+        let geometry = output.geometry().unwrap();
+        assert!(matches!(geometry[0], GeometryArray::Point(_)));
+        assert_eq!(geometry[0].crs(), Some("EPSG:4326"));
+        assert_eq!(geometry[0].get_as_geo(2), Some(point!(x: 2., y: 3.).into()));
+    }
+
+    #[test]
+    fn select_columns() {
+        let mut buf = Cursor::new(vec![]);
+        write_ipc(&table(), &mut buf).unwrap();
+
+        buf.set_position(0);
+        let options = ReaderOptions {
+            columns: Some(vec![]),
+            ..Default::default()
+        };
+        let output = read_ipc(&mut buf, &options).unwrap();
+        assert_eq!(output.num_columns(), 1);
+        assert_eq!(output.geometry_field().name, "geom");
+    }
+
+    #[test]
+    fn stream() {
+        let mut buf = Cursor::new(vec![]);
+        let input = GeoTable::concat(&[table(), table()]).unwrap();
+        write_ipc(&input, &mut buf).unwrap();
+
+        buf.set_position(0);
+        let reader = IpcReader::try_new(&mut buf, &ReaderOptions::default()).unwrap();
+        assert_eq!(reader.schema(), Some(table().schema()));
+        let tables = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(tables.len(), 2);
+        assert!(tables
+            .iter()
+            .all(|table| table.chunks().len() == 1 && table.len() == 3));
+    }
+}
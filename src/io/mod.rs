@@ -0,0 +1,27 @@
+//! Readers and writers for geospatial file formats.
+
+pub use filter::{apply_filters, AttributeFilter, ComparisonOp, FilterValue};
+pub use info::DatasetInfo;
+pub use options::ReaderOptions;
+pub use stream::GeoTableReader;
+
+#[cfg(feature = "csv")]
+pub mod csv;
+mod filter;
+#[cfg(feature = "flatgeobuf")]
+pub mod flatgeobuf;
+#[cfg(feature = "serde")]
+pub mod geojson_lines;
+mod info;
+#[cfg(feature = "ipc")]
+pub mod ipc;
+#[cfg(feature = "object_store")]
+pub mod object_reader;
+mod options;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "postgis")]
+pub mod postgis;
+mod stream;
+pub mod wkb;
+pub mod wkt;
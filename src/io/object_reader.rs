@@ -0,0 +1,157 @@
+//! Read files from object stores, such as S3, GCS, Azure or HTTP servers, with range requests.
+
+use crate::error::GeoArrowError;
+use futures::future::BoxFuture;
+use futures::io::{AsyncRead, AsyncSeek};
+use futures::{ready, FutureExt};
+use object_store::path::Path;
+use object_store::ObjectStore;
+use std::io::{self, SeekFrom};
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// An [`AsyncRead`] and [`AsyncSeek`] over a file in an [`ObjectStore`].
+///
+/// Each read fetches exactly the bytes asked for with one range request, so readers that know
+/// which parts of a file they need, such as a Parquet reader reading the footer and then a few
+/// column chunks, never download the whole file.
+pub struct ObjectReader {
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    size: usize,
+    position: usize,
+    /// The request in flight, and the range it fetches
+    request: Option<(
+        Range<usize>,
+        BoxFuture<'static, object_store::Result<bytes::Bytes>>,
+    )>,
+}
+
+impl ObjectReader {
+    /// A reader of the file at `path` in `store`, fetching its size with a `HEAD` request.
+    pub async fn try_new(store: Arc<dyn ObjectStore>, path: Path) -> Result<Self, GeoArrowError> {
+        let meta = store
+            .head(&path)
+            .await
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+        Ok(Self::with_size(store, path, meta.size))
+    }
+
+    /// A reader of the file at `path` in `store`, which is `size` bytes long.
+    pub fn with_size(store: Arc<dyn ObjectStore>, path: Path, size: usize) -> Self {
+        Self {
+            store,
+            path,
+            size,
+            position: 0,
+            request: None,
+        }
+    }
+
+    /// The size of the file in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+/// A clone reads the same file from the same position, but independently: a request in flight is
+/// not shared.
+impl Clone for ObjectReader {
+    fn clone(&self) -> Self {
+        Self {
+            position: self.position,
+            ..Self::with_size(self.store.clone(), self.path.clone(), self.size)
+        }
+    }
+}
+
+impl AsyncRead for ObjectReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        let end = this.size.min(this.position + buf.len());
+        if this.position >= end {
+            return Poll::Ready(Ok(0));
+        }
+
+        if this.request.is_none() {
+            let (store, path, range) = (this.store.clone(), this.path.clone(), this.position..end);
+            let request = async move { store.get_range(&path, range).await }.boxed();
+            this.request = Some((this.position..end, request));
+        }
+        let (range, request) = this.request.as_mut().unwrap();
+        let range = range.clone();
+        let result = ready!(request.as_mut().poll(cx));
+        this.request = None;
+
+        let bytes = result.map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        // The buffer may have shrunk since the request was made
+        let len = bytes.len().min(buf.len()).min(range.len());
+        buf[..len].copy_from_slice(&bytes[..len]);
+        this.position += len;
+        Poll::Ready(Ok(len))
+    }
+}
+
+impl AsyncSeek for ObjectReader {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset as i64),
+            SeekFrom::End(offset) => (self.size as i64).checked_add(offset),
+            SeekFrom::Current(offset) => (self.position as i64).checked_add(offset),
+        };
+        match position {
+            Some(position) if position >= 0 => {
+                self.position = position as usize;
+                self.request = None;
+                Poll::Ready(Ok(position as u64))
+            }
+            _ => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "seek to a negative or overflowing position",
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::io::{AsyncReadExt, AsyncSeekExt};
+    use object_store::memory::InMemory;
+
+    #[test]
+    fn range_reads() {
+        let store = Arc::new(InMemory::new());
+        let path = Path::from("data.bin");
+        block_on(store.put(&path, (0..100u8).collect::<Vec<_>>().into())).unwrap();
+
+        let mut reader = block_on(ObjectReader::try_new(store, path)).unwrap();
+        assert_eq!(reader.size(), 100);
+
+        let mut buf = [0; 4];
+        block_on(reader.seek(SeekFrom::End(-4))).unwrap();
+        block_on(reader.read_exact(&mut buf)).unwrap();
+        assert_eq!(buf, [96, 97, 98, 99]);
+        assert_eq!(block_on(reader.read(&mut buf)).unwrap(), 0);
+
+        block_on(reader.seek(SeekFrom::Start(10))).unwrap();
+        block_on(reader.read_exact(&mut buf)).unwrap();
+        assert_eq!(buf, [10, 11, 12, 13]);
+
+        let mut rest = vec![];
+        block_on(reader.clone().read_to_end(&mut rest)).unwrap();
+        assert_eq!(rest, (14..100u8).collect::<Vec<_>>());
+        assert!(block_on(reader.seek(SeekFrom::Current(-20))).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+use super::filter::AttributeFilter;
+
+/// Options controlling which parts of a dataset get read into Arrow memory.
+///
+/// Honored by the readers of GeoParquet, FlatGeobuf, newline-delimited GeoJSON and CSV files.
+#[derive(Debug, Clone, Default)]
+pub struct ReaderOptions {
+    /// The attribute columns to read. `None` reads every column. The geometry column is always
+    /// read.
+    pub columns: Option<Vec<String>>,
+
+    /// Only rows matching all of these filters are kept. Columns referenced by a filter are read
+    /// even when they are not part of `columns`, but are dropped from the output.
+    pub filters: Vec<AttributeFilter>,
+
+    /// The maximum number of rows in each output chunk.
+    pub batch_size: Option<usize>,
+}
+
+impl ReaderOptions {
+    /// Whether the column `name` is part of the requested output.
+    pub fn is_selected(&self, name: &str) -> bool {
+        self.columns
+            .as_ref()
+            .map_or(true, |columns| columns.iter().any(|column| column == name))
+    }
+
+    /// Whether the column `name` must be read, either for output or to evaluate a filter.
+    pub fn is_required(&self, name: &str) -> bool {
+        self.is_selected(name) || self.filters.iter().any(|filter| filter.column == name)
+    }
+}
@@ -0,0 +1,120 @@
+use super::reader::ReadPlan;
+use crate::error::GeoArrowError;
+use crate::io::object_reader::ObjectReader;
+use crate::io::ReaderOptions;
+use crate::table::GeoTable;
+use arrow2::array::Array;
+use arrow2::chunk::Chunk;
+use arrow2::io::parquet::read::{
+    read_columns_many_async, read_metadata_async, RowGroupDeserializer, RowGroupMetaData,
+};
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, StreamExt, TryStreamExt};
+use object_store::path::Path;
+use object_store::ObjectStore;
+use std::sync::Arc;
+
+/// Read the row group `row_group` with `reader`, returning its chunks after filtering.
+async fn read_row_group(
+    reader: ObjectReader,
+    plan: &ReadPlan,
+    row_group: &RowGroupMetaData,
+    batch_size: Option<usize>,
+) -> Result<Vec<Chunk<Box<dyn Array>>>, GeoArrowError> {
+    // Each column chunk is fetched by its own reader, concurrently
+    let factory = move || {
+        let reader = reader.clone();
+        async move { Ok::<_, std::io::Error>(reader) }.boxed()
+    };
+    let columns = read_columns_many_async(
+        factory,
+        row_group,
+        plan.read_schema.fields.clone(),
+        batch_size,
+        None,
+        None,
+    )
+    .await
+    .map_err(|err| GeoArrowError::External(err.into()))?;
+
+    let mut chunks = vec![];
+    for maybe_chunk in RowGroupDeserializer::new(columns, row_group.num_rows(), None) {
+        let chunk = maybe_chunk.map_err(|err| GeoArrowError::External(err.into()))?;
+        chunks.extend(plan.output_chunk(chunk)?);
+    }
+    Ok(chunks)
+}
+
+/// Open the GeoParquet file at `path` in `store` and plan its read.
+async fn plan_object(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    options: &ReaderOptions,
+    bbox: Option<[f64; 4]>,
+) -> Result<(ObjectReader, ReadPlan), GeoArrowError> {
+    let mut reader = ObjectReader::try_new(store, path).await?;
+    let metadata = read_metadata_async(&mut reader)
+        .await
+        .map_err(|err| GeoArrowError::External(err.into()))?;
+    let plan = ReadPlan::try_new(metadata, options, bbox)?;
+    Ok((reader, plan))
+}
+
+/// Stream the GeoParquet file at `path` in `store`, one [`GeoTable`] per chunk.
+///
+/// Only the footer is fetched before this returns. Row groups are then fetched one at a time as
+/// the stream is polled, with one range request per column chunk, so that only the selected
+/// columns of the row groups left after pruning are downloaded. `options` and `bbox` are applied
+/// as in [`read_geoparquet`](super::read_geoparquet).
+pub async fn stream_geoparquet_async(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    options: &ReaderOptions,
+    bbox: Option<[f64; 4]>,
+) -> Result<BoxStream<'static, Result<GeoTable, GeoArrowError>>, GeoArrowError> {
+    let (reader, plan) = plan_object(store, path, options, bbox).await?;
+    let plan = Arc::new(plan);
+    let batch_size = options.batch_size;
+
+    let row_groups = plan.row_groups.clone();
+    let tables = stream::iter(row_groups)
+        .then(move |row_group| {
+            let (reader, plan) = (reader.clone(), plan.clone());
+            async move {
+                let chunks = read_row_group(reader, &plan, &row_group, batch_size).await?;
+                chunks
+                    .into_iter()
+                    .map(|chunk| {
+                        GeoTable::from_arrow(
+                            plan.output_schema.clone(),
+                            vec![chunk],
+                            Some(&plan.geometry_column),
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            }
+        })
+        .map_ok(|tables| stream::iter(tables.into_iter().map(Ok)))
+        .try_flatten();
+    Ok(tables.boxed())
+}
+
+/// Read the GeoParquet file at `path` in `store` into a [`GeoTable`].
+///
+/// The footer and the selected columns of the row groups left after pruning are fetched with
+/// range requests, so that the rest of the file is never downloaded. `options` and `bbox` are
+/// applied as in [`read_geoparquet`](super::read_geoparquet).
+pub async fn read_geoparquet_async(
+    store: Arc<dyn ObjectStore>,
+    path: Path,
+    options: &ReaderOptions,
+    bbox: Option<[f64; 4]>,
+) -> Result<GeoTable, GeoArrowError> {
+    let (reader, plan) = plan_object(store, path, options, bbox).await?;
+    let mut chunks = vec![];
+    for row_group in &plan.row_groups {
+        let reader = reader.clone();
+        chunks.extend(read_row_group(reader, &plan, row_group, options.batch_size).await?);
+    }
+    GeoTable::from_arrow(plan.output_schema, chunks, Some(&plan.geometry_column))
+}
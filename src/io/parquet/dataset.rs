@@ -0,0 +1,594 @@
+use super::metadata::{GeoParquetBboxCovering, GeoParquetMetadata};
+use crate::algorithm::bounds::bounds_intersect;
+use crate::error::GeoArrowError;
+use crate::io::filter::cmp_integer;
+use crate::io::info::bounds_2d;
+use crate::io::{apply_filters, AttributeFilter, ComparisonOp, FilterValue, ReaderOptions};
+use crate::parallel::parallel_map;
+use crate::table::GeoTable;
+use arrow2::array::{new_null_array, Array, PrimitiveArray, StructArray, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::io::parquet::read::statistics::{deserialize, Statistics};
+use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader, RowGroupMetaData};
+use arrow2::types::NativeType;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// The footer of a single file in a [`GeoParquetDataset`].
+#[derive(Debug, Clone)]
+struct DatasetFile {
+    path: PathBuf,
+    schema: Schema,
+    row_groups: Vec<RowGroupMetaData>,
+    bounds: Option<[f64; 4]>,
+    covering: Option<GeoParquetBboxCovering>,
+}
+
+/// A collection of GeoParquet files sharing a primary geometry column, read as one table.
+///
+/// Opening a dataset reads only the file footers, concurrently. The schemas of all files are
+/// merged by column name: a column missing from some files is read as null for those files, and
+/// a column whose type differs between files is an error.
+#[derive(Debug, Clone)]
+pub struct GeoParquetDataset {
+    files: Vec<DatasetFile>,
+    schema: Schema,
+    geometry_column: String,
+}
+
+impl GeoParquetDataset {
+    /// Open every file with a `.parquet` extension directly inside `dir`, in file name order.
+    pub fn from_directory(dir: impl AsRef<Path>) -> Result<Self, GeoArrowError> {
+        let mut paths = vec![];
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().map_or(false, |ext| ext == "parquet") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Self::try_new(paths)
+    }
+
+    /// Open the GeoParquet files at `paths`. Rows are read in the order of `paths`.
+    ///
+    /// Errors if `paths` is empty, if any file is missing GeoParquet metadata, if the files
+    /// disagree on the primary geometry column, or if their schemas cannot be merged.
+    pub fn try_new(paths: Vec<PathBuf>) -> Result<Self, GeoArrowError> {
+        if paths.is_empty() {
+            return Err(GeoArrowError::General(
+                "a dataset needs at least one file".to_string(),
+            ));
+        }
+
+        let footers = parallel_map(&paths, |path| read_footer(path))
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let geometry_column = footers[0].0.clone();
+        let mut files = Vec::with_capacity(footers.len());
+        for (file_geometry_column, file) in footers {
+            if file_geometry_column != geometry_column {
+                return Err(GeoArrowError::General(format!(
+                    "{} has primary geometry column {}, expected {}",
+                    file.path.display(),
+                    file_geometry_column,
+                    geometry_column
+                )));
+            }
+            files.push(file);
+        }
+
+        let schema = merge_schemas(&files)?;
+        Ok(Self {
+            files,
+            schema,
+            geometry_column,
+        })
+    }
+
+    /// The merged schema of all files.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// The name of the primary geometry column.
+    pub fn geometry_column(&self) -> &str {
+        &self.geometry_column
+    }
+
+    /// The paths of the files in this dataset.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|file| file.path.as_path())
+    }
+
+    /// The total number of rows across all files.
+    pub fn num_rows(&self) -> usize {
+        self.files
+            .iter()
+            .flat_map(|file| file.row_groups.iter())
+            .map(|row_group| row_group.num_rows())
+            .sum()
+    }
+
+    /// The union of the bounds stored in each file, as `[minx, miny, maxx, maxy]`.
+    ///
+    /// `None` if any file does not store its bounds.
+    pub fn bounds(&self) -> Option<[f64; 4]> {
+        self.files
+            .iter()
+            .map(|file| file.bounds)
+            .reduce(|left, right| match (left, right) {
+                (Some(left), Some(right)) => Some([
+                    left[0].min(right[0]),
+                    left[1].min(right[1]),
+                    left[2].max(right[2]),
+                    left[3].max(right[3]),
+                ]),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// Read the dataset into a single [`GeoTable`] with the merged schema, reading files
+    /// concurrently.
+    ///
+    /// `options` and `bbox` are applied as in [`read_geoparquet`](super::read_geoparquet). Files
+    /// whose stored bounds do not intersect `bbox`, and row groups whose column statistics show
+    /// that no row can match `options.filters` or intersect `bbox`, are skipped without being
+    /// decoded. `bbox` only prunes whole files and row groups: rows that are read are not
+    /// filtered by it.
+    pub fn read(
+        &self,
+        options: &ReaderOptions,
+        bbox: Option<[f64; 4]>,
+    ) -> Result<GeoTable, GeoArrowError> {
+        let output_schema = self.schema.clone().filter(|_, field| {
+            field.name == self.geometry_column || options.is_selected(&field.name)
+        });
+
+        let files: Vec<&DatasetFile> = self
+            .files
+            .iter()
+            .filter(|file| match (file.bounds, bbox) {
+                (Some(bounds), Some(bbox)) => bounds_intersect(&bounds, &bbox),
+                _ => true,
+            })
+            .collect();
+
+        let chunks = parallel_map(&files, |file| {
+            self.read_file(file, &output_schema, options, bbox)
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+        GeoTable::from_arrow(output_schema, chunks, Some(&self.geometry_column))
+    }
+
+    /// Read the matching rows of one file, with columns in the order of `output_schema`.
+    fn read_file(
+        &self,
+        file: &DatasetFile,
+        output_schema: &Schema,
+        options: &ReaderOptions,
+        bbox: Option<[f64; 4]>,
+    ) -> Result<Vec<Chunk<Box<dyn Array>>>, GeoArrowError> {
+        // A column missing from this file is all null, so a filter on it matches nothing
+        let has_filter_columns = options.filters.iter().all(|filter| {
+            file.schema
+                .fields
+                .iter()
+                .any(|field| field.name == filter.column)
+        });
+        if !has_filter_columns {
+            return Ok(vec![]);
+        }
+
+        let read_schema = file.schema.clone().filter(|_, field| {
+            field.name == self.geometry_column || options.is_required(&field.name)
+        });
+
+        let row_groups = prune_row_groups(&read_schema, &file.row_groups, &options.filters)?;
+        let row_groups =
+            prune_row_groups_by_bbox(&file.schema, row_groups, file.covering.as_ref(), bbox)?;
+        if row_groups.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let reader = File::open(&file.path)?;
+        let file_reader = FileReader::new(
+            reader,
+            row_groups,
+            read_schema.clone(),
+            options.batch_size,
+            None,
+            None,
+        );
+
+        let mut chunks = vec![];
+        for maybe_chunk in file_reader {
+            let chunk = maybe_chunk.map_err(|err| GeoArrowError::External(err.into()))?;
+            let chunk = apply_filters(&read_schema, &chunk, &options.filters)?;
+            if chunk.is_empty() {
+                continue;
+            }
+
+            let arrays = output_schema
+                .fields
+                .iter()
+                .map(|field| {
+                    match read_schema
+                        .fields
+                        .iter()
+                        .position(|read_field| read_field.name == field.name)
+                    {
+                        Some(idx) => chunk.arrays()[idx].clone(),
+                        None => new_null_array(field.data_type().clone(), chunk.len()),
+                    }
+                })
+                .collect();
+            chunks.push(Chunk::new(arrays));
+        }
+        Ok(chunks)
+    }
+}
+
+/// Read the footer of the file at `path`, returning its primary geometry column.
+fn read_footer(path: &Path) -> Result<(String, DatasetFile), GeoArrowError> {
+    let mut reader = File::open(path)?;
+    let metadata = read_metadata(&mut reader).map_err(|err| GeoArrowError::External(err.into()))?;
+    let schema = infer_schema(&metadata).map_err(|err| GeoArrowError::External(err.into()))?;
+    let geo_metadata = GeoParquetMetadata::from_parquet_metadata(&metadata)?;
+    let column_metadata = geo_metadata.primary_column_metadata()?;
+    let bounds = column_metadata.bbox.as_deref().and_then(bounds_2d);
+    let covering = column_metadata
+        .covering
+        .as_ref()
+        .map(|covering| covering.bbox.clone());
+
+    let file = DatasetFile {
+        path: path.to_path_buf(),
+        schema,
+        row_groups: metadata.row_groups,
+        bounds,
+        covering,
+    };
+    Ok((geo_metadata.primary_column, file))
+}
+
+/// Merge the schemas of `files` by column name, in order of first appearance.
+fn merge_schemas(files: &[DatasetFile]) -> Result<Schema, GeoArrowError> {
+    let mut fields: Vec<Field> = vec![];
+    for file in files {
+        for field in &file.schema.fields {
+            match fields.iter().find(|existing| existing.name == field.name) {
+                Some(existing) if existing.data_type() != field.data_type() => {
+                    return Err(GeoArrowError::SchemaMismatch {
+                        column: field.name.clone(),
+                        expected: Box::new(existing.data_type().clone()),
+                        found: Box::new(field.data_type().clone()),
+                    });
+                }
+                Some(_) => {}
+                None => fields.push(field.clone()),
+            }
+        }
+    }
+
+    // Columns missing from some files are filled with nulls
+    for field in fields.iter_mut() {
+        let in_every_file = files.iter().all(|file| {
+            file.schema
+                .fields
+                .iter()
+                .any(|file_field| file_field.name == field.name)
+        });
+        if !in_every_file {
+            field.is_nullable = true;
+        }
+    }
+
+    Ok(Schema::from(fields).with_metadata(files[0].schema.metadata.clone()))
+}
+
+/// Keep the row groups whose statistics do not rule out every filter.
+fn prune_row_groups(
+    schema: &Schema,
+    row_groups: &[RowGroupMetaData],
+    filters: &[AttributeFilter],
+) -> Result<Vec<RowGroupMetaData>, GeoArrowError> {
+    let mut keep = vec![true; row_groups.len()];
+    for filter in filters {
+        let field = match schema
+            .fields
+            .iter()
+            .find(|field| field.name == filter.column)
+        {
+            Some(field) => field,
+            None => continue,
+        };
+        let statistics =
+            deserialize(field, row_groups).map_err(|err| GeoArrowError::External(err.into()))?;
+        for (idx, keep) in keep.iter_mut().enumerate() {
+            *keep = *keep && might_match(filter, &statistics, idx);
+        }
+    }
+
+    Ok(row_groups
+        .iter()
+        .zip(keep)
+        .filter(|(_, keep)| *keep)
+        .map(|(row_group, _)| row_group.clone())
+        .collect())
+}
+
+/// Keep the row groups that may hold a geometry intersecting `bbox`, going by the statistics of
+/// the bbox `covering` columns.
+///
+/// Every row group is kept if there is no `bbox` or no covering, and so is any row group missing
+/// statistics for one of the covering columns.
+pub(super) fn prune_row_groups_by_bbox(
+    schema: &Schema,
+    row_groups: Vec<RowGroupMetaData>,
+    covering: Option<&GeoParquetBboxCovering>,
+    bbox: Option<[f64; 4]>,
+) -> Result<Vec<RowGroupMetaData>, GeoArrowError> {
+    let (Some(covering), Some(bbox)) = (covering, bbox) else {
+        return Ok(row_groups);
+    };
+
+    // The smallest minimums and largest maximums of each row group
+    let xmin = covering_statistics(schema, &row_groups, &covering.xmin, false)?;
+    let ymin = covering_statistics(schema, &row_groups, &covering.ymin, false)?;
+    let xmax = covering_statistics(schema, &row_groups, &covering.xmax, true)?;
+    let ymax = covering_statistics(schema, &row_groups, &covering.ymax, true)?;
+    let (Some(xmin), Some(ymin), Some(xmax), Some(ymax)) = (xmin, ymin, xmax, ymax) else {
+        return Ok(row_groups);
+    };
+
+    Ok(row_groups
+        .into_iter()
+        .enumerate()
+        .filter(
+            |(idx, _)| match (xmin[*idx], ymin[*idx], xmax[*idx], ymax[*idx]) {
+                (Some(xmin), Some(ymin), Some(xmax), Some(ymax)) => {
+                    bounds_intersect(&[xmin, ymin, xmax, ymax], &bbox)
+                }
+                _ => true,
+            },
+        )
+        .map(|(_, row_group)| row_group)
+        .collect())
+}
+
+/// The minimum, or the maximum if `max`, of the column at `path` in each row group.
+///
+/// `None` if the path does not lead to a column of `schema`.
+fn covering_statistics(
+    schema: &Schema,
+    row_groups: &[RowGroupMetaData],
+    path: &[String],
+    max: bool,
+) -> Result<Option<Vec<Option<f64>>>, GeoArrowError> {
+    let Some((column, nested)) = path.split_first() else {
+        return Ok(None);
+    };
+    let Some(field) = schema.fields.iter().find(|field| &field.name == column) else {
+        return Ok(None);
+    };
+    let statistics =
+        deserialize(field, row_groups).map_err(|err| GeoArrowError::External(err.into()))?;
+    let values = if max {
+        statistics.max_value
+    } else {
+        statistics.min_value
+    };
+    Ok(nested_column(values.as_ref(), nested).map(|array| {
+        (0..array.len())
+            .map(|idx| {
+                if array.is_null(idx) {
+                    None
+                } else {
+                    numeric_value(array, idx)
+                }
+            })
+            .collect()
+    }))
+}
+
+/// The field at `path` of nested struct arrays, or `array` itself if `path` is empty.
+fn nested_column<'a>(array: &'a dyn Array, path: &[String]) -> Option<&'a dyn Array> {
+    let Some((name, rest)) = path.split_first() else {
+        return Some(array);
+    };
+    let array = array.as_any().downcast_ref::<StructArray>()?;
+    let idx = array
+        .fields()
+        .iter()
+        .position(|field| &field.name == name)?;
+    nested_column(array.values()[idx].as_ref(), rest)
+}
+
+/// Whether the row group at `idx` may contain a row matching `filter`, given its statistics.
+///
+/// Returns `true` whenever the statistics are missing or of a type that is not understood.
+fn might_match(filter: &AttributeFilter, statistics: &Statistics, idx: usize) -> bool {
+    let min = statistics.min_value.as_ref();
+    let max = statistics.max_value.as_ref();
+    if min.is_null(idx) || max.is_null(idx) {
+        return true;
+    }
+
+    match &filter.value {
+        FilterValue::Int(_) | FilterValue::Float(_) => {
+            // Integer statistics are compared exactly, as in `AttributeFilter::evaluate`
+            if let (Some(min), Some(max)) = (integer_value(min, idx), integer_value(max, idx)) {
+                return ordering_might_match(
+                    filter.op,
+                    cmp_integer(min, &filter.value),
+                    cmp_integer(max, &filter.value),
+                );
+            }
+
+            let value = match filter.value {
+                FilterValue::Int(value) => value as f64,
+                FilterValue::Float(value) => value,
+                _ => unreachable!(),
+            };
+            match (numeric_value(min, idx), numeric_value(max, idx)) {
+                (Some(min), Some(max)) => range_might_match(filter.op, &min, &max, &value),
+                _ => true,
+            }
+        }
+        FilterValue::Utf8(value) => match (utf8_value(min, idx), utf8_value(max, idx)) {
+            (Some(min), Some(max)) => range_might_match(filter.op, min, max, value.as_str()),
+            _ => true,
+        },
+        FilterValue::Boolean(_) => true,
+    }
+}
+
+/// Whether some value between `min` and `max` (inclusive) compares to `value` with `op`.
+fn range_might_match<T: PartialOrd + ?Sized>(
+    op: ComparisonOp,
+    min: &T,
+    max: &T,
+    value: &T,
+) -> bool {
+    ordering_might_match(op, min.partial_cmp(value), max.partial_cmp(value))
+}
+
+/// Whether some value between a minimum and a maximum (inclusive) compares to a value with `op`,
+/// given the order of the minimum and the maximum relative to that value.
+///
+/// Unordered values, such as NaN, might always match.
+fn ordering_might_match(op: ComparisonOp, min: Option<Ordering>, max: Option<Ordering>) -> bool {
+    let (Some(min), Some(max)) = (min, max) else {
+        return true;
+    };
+    match op {
+        ComparisonOp::Eq => min != Ordering::Greater && max != Ordering::Less,
+        ComparisonOp::NotEq => !(min == Ordering::Equal && max == Ordering::Equal),
+        ComparisonOp::Lt => min == Ordering::Less,
+        ComparisonOp::LtEq => min != Ordering::Greater,
+        ComparisonOp::Gt => max == Ordering::Greater,
+        ComparisonOp::GtEq => max != Ordering::Less,
+    }
+}
+
+fn primitive_value<T: NativeType>(array: &dyn Array, idx: usize) -> T {
+    array
+        .as_any()
+        .downcast_ref::<PrimitiveArray<T>>()
+        .unwrap()
+        .value(idx)
+}
+
+fn integer_value(array: &dyn Array, idx: usize) -> Option<i128> {
+    match array.data_type() {
+        DataType::Int8 => Some(primitive_value::<i8>(array, idx).into()),
+        DataType::Int16 => Some(primitive_value::<i16>(array, idx).into()),
+        DataType::Int32 => Some(primitive_value::<i32>(array, idx).into()),
+        DataType::Int64 => Some(primitive_value::<i64>(array, idx).into()),
+        DataType::UInt8 => Some(primitive_value::<u8>(array, idx).into()),
+        DataType::UInt16 => Some(primitive_value::<u16>(array, idx).into()),
+        DataType::UInt32 => Some(primitive_value::<u32>(array, idx).into()),
+        DataType::UInt64 => Some(primitive_value::<u64>(array, idx).into()),
+        _ => None,
+    }
+}
+
+fn numeric_value(array: &dyn Array, idx: usize) -> Option<f64> {
+    match array.data_type() {
+        DataType::Int8 => Some(primitive_value::<i8>(array, idx) as f64),
+        DataType::Int16 => Some(primitive_value::<i16>(array, idx) as f64),
+        DataType::Int32 => Some(primitive_value::<i32>(array, idx) as f64),
+        DataType::Int64 => Some(primitive_value::<i64>(array, idx) as f64),
+        DataType::UInt8 => Some(primitive_value::<u8>(array, idx) as f64),
+        DataType::UInt16 => Some(primitive_value::<u16>(array, idx) as f64),
+        DataType::UInt32 => Some(primitive_value::<u32>(array, idx) as f64),
+        DataType::UInt64 => Some(primitive_value::<u64>(array, idx) as f64),
+        DataType::Float32 => Some(primitive_value::<f32>(array, idx) as f64),
+        DataType::Float64 => Some(primitive_value::<f64>(array, idx)),
+        _ => None,
+    }
+}
+
+fn utf8_value(array: &dyn Array, idx: usize) -> Option<&str> {
+    match array.data_type() {
+        DataType::Utf8 => Some(
+            array
+                .as_any()
+                .downcast_ref::<Utf8Array<i32>>()
+                .unwrap()
+                .value(idx),
+        ),
+        DataType::LargeUtf8 => Some(
+            array
+                .as_any()
+                .downcast_ref::<Utf8Array<i64>>()
+                .unwrap()
+                .value(idx),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn statistics_ranges() {
+        assert!(range_might_match(ComparisonOp::Eq, &1., &5., &3.));
+        assert!(!range_might_match(ComparisonOp::Eq, &1., &5., &6.));
+        assert!(!range_might_match(ComparisonOp::NotEq, &2., &2., &2.));
+        assert!(!range_might_match(ComparisonOp::Lt, &2., &4., &2.));
+        assert!(range_might_match(ComparisonOp::LtEq, &2., &4., &2.));
+        assert!(!range_might_match(ComparisonOp::Gt, "a", "m", "m"));
+        assert!(range_might_match(ComparisonOp::GtEq, "a", "m", "m"));
+
+        // 2^53 and 2^53 + 1 are the same f64
+        let big = 1_i128 << 53;
+        let value = FilterValue::Int(1 << 53 | 1);
+        let (min, max) = (cmp_integer(big, &value), cmp_integer(big, &value));
+        assert!(ordering_might_match(ComparisonOp::Lt, min, max));
+        assert!(!ordering_might_match(ComparisonOp::Eq, min, max));
+    }
+
+    #[test]
+    fn nested_columns() {
+        let xmin = PrimitiveArray::<f64>::from_slice([0., 10.]).boxed();
+        let ymin = PrimitiveArray::<f32>::from([Some(1.), None]).boxed();
+        let fields = vec![
+            Field::new("xmin", DataType::Float64, true),
+            Field::new("ymin", DataType::Float32, true),
+        ];
+        let bbox = StructArray::new(DataType::Struct(fields), vec![xmin, ymin], None);
+
+        let path = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+        let ymin = nested_column(&bbox, &path(&["ymin"])).unwrap();
+        assert_eq!(numeric_value(ymin, 0), Some(1.));
+        assert!(ymin.is_null(1));
+        assert!(nested_column(&bbox, &path(&["zmin"])).is_none());
+        assert!(nested_column(&bbox, &path(&["xmin", "value"])).is_none());
+        assert_eq!(nested_column(&bbox, &[]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn bounds() {
+        assert!(bounds_intersect(&[0., 0., 2., 2.], &[1., 1., 3., 3.]));
+        assert!(bounds_intersect(&[0., 0., 2., 2.], &[2., 2., 3., 3.]));
+        assert!(!bounds_intersect(&[0., 0., 2., 2.], &[2.5, 0., 3., 3.]));
+    }
+}
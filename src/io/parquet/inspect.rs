@@ -0,0 +1,48 @@
+use super::metadata::GeoParquetMetadata;
+use crate::error::GeoArrowError;
+use crate::io::info::bounds_2d;
+use crate::io::DatasetInfo;
+use arrow2::io::parquet::read::{infer_schema, read_metadata};
+use std::io::{Read, Seek};
+
+/// Summarize a GeoParquet file from its footer, without reading any row groups.
+pub fn inspect_geoparquet<R: Read + Seek>(reader: &mut R) -> Result<DatasetInfo, GeoArrowError> {
+    let metadata = read_metadata(reader).map_err(|err| GeoArrowError::External(err.into()))?;
+    let schema = infer_schema(&metadata).map_err(|err| GeoArrowError::External(err.into()))?;
+    let geo_metadata = GeoParquetMetadata::from_parquet_metadata(&metadata)?;
+    let column_metadata = geo_metadata.primary_column_metadata()?;
+
+    Ok(DatasetInfo {
+        schema,
+        geometry_column: geo_metadata.primary_column.clone(),
+        crs: column_metadata.crs_string(),
+        bounds: column_metadata.bbox.as_deref().and_then(bounds_2d),
+        num_features: Some(metadata.num_rows),
+        geometry_types: column_metadata.geometry_types.clone(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn inspect_points() {
+        let mut file = File::open("fixtures/geoparquet/points.parquet").unwrap();
+        let info = inspect_geoparquet(&mut file).unwrap();
+
+        let names: Vec<_> = info.schema.fields.iter().map(|field| &field.name).collect();
+        assert_eq!(names, ["id", "geometry", "bbox"]);
+        assert_eq!(info.geometry_column, "geometry");
+        assert_eq!(info.geometry_types, ["Point"]);
+        assert_eq!(info.bounds, Some([0., 0., 103., 103.]));
+        assert_eq!(info.num_features, Some(8));
+        assert_eq!(
+            info.crs.as_deref(),
+            Some(
+                r#"{"id":{"authority":"EPSG","code":4326},"name":"WGS 84","type":"GeographicCRS"}"#
+            )
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::crs::{crs_from_json, crs_to_json};
+use crate::error::GeoArrowError;
+use arrow2::io::parquet::read::FileMetaData;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The key under which GeoParquet stores its metadata in the Parquet key-value metadata.
+const GEOPARQUET_METADATA_KEY: &str = "geo";
+
+/// File-level GeoParquet metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoParquetMetadata {
+    /// The version of the GeoParquet specification this file conforms to.
+    pub version: String,
+
+    /// The name of the "primary" geometry column.
+    pub primary_column: String,
+
+    /// Metadata about each geometry column, keyed by column name.
+    pub columns: HashMap<String, GeoParquetColumnMetadata>,
+}
+
+/// Column-level GeoParquet metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoParquetColumnMetadata {
+    /// Name of the geometry encoding format. Currently only `"WKB"` is supported.
+    pub encoding: String,
+
+    /// The geometry types of all geometries in this column, or an empty list if unknown.
+    pub geometry_types: Vec<String>,
+
+    /// PROJJSON object representing the coordinate reference system of this column.
+    ///
+    /// A missing value means the CRS is `OGC:CRS84`; an explicit `null` means it is unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crs: Option<serde_json::Value>,
+
+    /// Winding order of exterior rings of polygons, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<String>,
+
+    /// Name of the coordinate system for the edges. Either `"planar"` or `"spherical"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edges: Option<String>,
+
+    /// Bounding box of the column, as `[minx, miny, maxx, maxy]` or
+    /// `[minx, miny, minz, maxx, maxy, maxz]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<Vec<f64>>,
+
+    /// Coordinate epoch in case of a dynamic CRS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch: Option<f64>,
+
+    /// Columns holding simpler versions of each geometry, from GeoParquet 1.1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub covering: Option<GeoParquetCovering>,
+}
+
+/// Columns "covering" the geometry column, which readers can filter on without decoding
+/// geometries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoParquetCovering {
+    /// The columns holding the bounding box of each geometry.
+    pub bbox: GeoParquetBboxCovering,
+}
+
+/// The paths of the columns holding the bounding box of each geometry.
+///
+/// Each path is the name of a top-level column followed by the names of nested struct fields,
+/// such as `["bbox", "xmin"]` for the `xmin` field of a `bbox` struct column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoParquetBboxCovering {
+    pub xmin: Vec<String>,
+    pub ymin: Vec<String>,
+    pub xmax: Vec<String>,
+    pub ymax: Vec<String>,
+}
+
+impl GeoParquetMetadata {
+    /// Parse GeoParquet metadata from its JSON string representation.
+    pub fn from_json(value: &str) -> Result<Self, GeoArrowError> {
+        serde_json::from_str(value).map_err(|err| GeoArrowError::External(err.into()))
+    }
+
+    /// Extract GeoParquet metadata from the key-value metadata of a Parquet file.
+    pub fn from_parquet_metadata(metadata: &FileMetaData) -> Result<Self, GeoArrowError> {
+        let value = metadata
+            .key_value_metadata()
+            .as_ref()
+            .and_then(|kv_metadata| {
+                kv_metadata
+                    .iter()
+                    .find(|kv| kv.key == GEOPARQUET_METADATA_KEY)
+            })
+            .and_then(|kv| kv.value.as_deref())
+            .ok_or_else(|| {
+                GeoArrowError::General("Parquet file is missing GeoParquet metadata".to_string())
+            })?;
+        Self::from_json(value)
+    }
+
+    /// The metadata of the primary geometry column.
+    pub fn primary_column_metadata(&self) -> Result<&GeoParquetColumnMetadata, GeoArrowError> {
+        self.columns.get(&self.primary_column).ok_or_else(|| {
+            GeoArrowError::General(format!(
+                "primary column {} missing from GeoParquet column metadata",
+                self.primary_column
+            ))
+        })
+    }
+}
+
+impl GeoParquetColumnMetadata {
+    /// The CRS of this column as a string, for [`GeometryArray::with_crs`]: a PROJJSON object is
+    /// serialized, and a string (e.g. `"OGC:CRS84"`) returned as is.
+    ///
+    /// Returns `None` if the CRS is missing or `null`, which this metadata cannot tell apart.
+    ///
+    /// [`GeometryArray::with_crs`]: crate::GeometryArray::with_crs
+    pub fn crs_string(&self) -> Option<String> {
+        crs_from_json(self.crs.as_ref()?)
+    }
+
+    /// This metadata with its CRS set from a CRS string, such as the one returned by
+    /// [`GeometryArray::crs`](crate::GeometryArray::crs).
+    ///
+    /// A PROJJSON string is stored as an object, and any other CRS as a JSON string.
+    pub fn with_crs(mut self, crs: Option<&str>) -> Self {
+        self.crs = crs.map(crs_to_json);
+        self
+    }
+
+    /// This metadata with its bbox set from `[minx, miny, maxx, maxy]` bounds, such as the
+    /// [`TotalBounds`](crate::algorithm::bounds::TotalBounds) of the column being written.
+    pub fn with_bbox(mut self, bounds: Option<[f64; 4]>) -> Self {
+        self.bbox = bounds.map(|bounds| bounds.to_vec());
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_metadata() {
+        let value = r#"{
+            "version": "1.0.0-beta.1",
+            "primary_column": "geometry",
+            "columns": {
+                "geometry": {
+                    "encoding": "WKB",
+                    "geometry_types": ["Polygon", "MultiPolygon"],
+                    "bbox": [-180.0, -90.0, 180.0, 83.6]
+                }
+            }
+        }"#;
+        let meta = GeoParquetMetadata::from_json(value).unwrap();
+        let column_meta = meta.primary_column_metadata().unwrap();
+        assert_eq!(column_meta.encoding, "WKB");
+        assert_eq!(column_meta.geometry_types, vec!["Polygon", "MultiPolygon"]);
+        assert_eq!(column_meta.bbox, Some(vec![-180.0, -90.0, 180.0, 83.6]));
+        assert_eq!(column_meta.crs, None);
+        assert_eq!(column_meta.covering, None);
+    }
+
+    #[test]
+    fn parse_covering() {
+        let value = r#"{
+            "version": "1.1.0",
+            "primary_column": "geometry",
+            "columns": {
+                "geometry": {
+                    "encoding": "WKB",
+                    "geometry_types": [],
+                    "covering": {
+                        "bbox": {
+                            "xmin": ["bbox", "xmin"],
+                            "ymin": ["bbox", "ymin"],
+                            "xmax": ["bbox", "xmax"],
+                            "ymax": ["bbox", "ymax"]
+                        }
+                    }
+                }
+            }
+        }"#;
+        let meta = GeoParquetMetadata::from_json(value).unwrap();
+        let covering = meta.primary_column_metadata().unwrap().covering.as_ref();
+        assert_eq!(covering.unwrap().bbox.ymax, vec!["bbox", "ymax"]);
+    }
+
+    #[test]
+    fn crs_strings() {
+        let column_meta = GeoParquetColumnMetadata {
+            encoding: "WKB".to_string(),
+            geometry_types: vec![],
+            crs: None,
+            orientation: None,
+            edges: None,
+            bbox: None,
+            epoch: None,
+            covering: None,
+        };
+        assert_eq!(column_meta.crs_string(), None);
+
+        // PROJJSON is serialized with sorted keys
+        let projjson = r#"{"name":"WGS 84","type":"GeographicCRS"}"#;
+        let column_meta = column_meta.with_crs(Some(projjson));
+        assert!(column_meta.crs.as_ref().unwrap().is_object());
+        assert_eq!(column_meta.crs_string().as_deref(), Some(projjson));
+
+        let column_meta = column_meta.with_crs(Some("EPSG:4326"));
+        assert_eq!(
+            column_meta.crs,
+            Some(serde_json::Value::String("EPSG:4326".to_string()))
+        );
+        assert_eq!(column_meta.crs_string().as_deref(), Some("EPSG:4326"));
+    }
+}
@@ -0,0 +1,17 @@
+//! Read [GeoParquet](https://github.com/opengeospatial/geoparquet) files.
+
+#[cfg(feature = "object_store")]
+pub use async_reader::{read_geoparquet_async, stream_geoparquet_async};
+pub use dataset::GeoParquetDataset;
+pub use inspect::inspect_geoparquet;
+pub use metadata::{
+    GeoParquetBboxCovering, GeoParquetColumnMetadata, GeoParquetCovering, GeoParquetMetadata,
+};
+pub use reader::{read_geoparquet, GeoParquetReader};
+
+#[cfg(feature = "object_store")]
+mod async_reader;
+mod dataset;
+mod inspect;
+mod metadata;
+mod reader;
@@ -0,0 +1,235 @@
+use super::dataset::prune_row_groups_by_bbox;
+use super::metadata::GeoParquetMetadata;
+use crate::algorithm::bounds::bounds_intersect;
+use crate::enum_::{extension_metadata, EXTENSION_METADATA_KEY};
+use crate::error::GeoArrowError;
+use crate::io::info::bounds_2d;
+use crate::io::{apply_filters, AttributeFilter, GeoTableReader, ReaderOptions};
+use crate::table::GeoTable;
+use arrow2::array::Array;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::Schema;
+use arrow2::io::parquet::read::{
+    infer_schema, read_metadata, FileMetaData, FileReader, RowGroupMetaData,
+};
+use std::io::{Read, Seek};
+
+/// What to read from a GeoParquet file, and how to turn it into the output table.
+#[derive(Debug, Clone)]
+pub(super) struct ReadPlan {
+    /// The columns needed either for output or for evaluating a filter
+    pub read_schema: Schema,
+    /// The schema of the output, with the CRS in the metadata of the geometry field
+    pub output_schema: Schema,
+    /// Positions within `read_schema` of the columns to output
+    output_indices: Vec<usize>,
+    filters: Vec<AttributeFilter>,
+    /// The row groups left after pruning by bbox
+    pub row_groups: Vec<RowGroupMetaData>,
+    pub geometry_column: String,
+}
+
+impl ReadPlan {
+    /// Plan the read of the file with footer `metadata`.
+    pub fn try_new(
+        metadata: FileMetaData,
+        options: &ReaderOptions,
+        bbox: Option<[f64; 4]>,
+    ) -> Result<Self, GeoArrowError> {
+        let schema = infer_schema(&metadata).map_err(|err| GeoArrowError::External(err.into()))?;
+        let geo_metadata = GeoParquetMetadata::from_parquet_metadata(&metadata)?;
+        let geometry_column = geo_metadata.primary_column.as_str();
+
+        let read_schema = schema
+            .clone()
+            .filter(|_, field| field.name == geometry_column || options.is_required(&field.name));
+        let output_indices: Vec<usize> = read_schema
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.name == geometry_column || options.is_selected(&field.name))
+            .map(|(idx, _)| idx)
+            .collect();
+        let mut output_schema = read_schema
+            .clone()
+            .filter(|_, field| field.name == geometry_column || options.is_selected(&field.name));
+        let column_metadata = geo_metadata.primary_column_metadata()?;
+        let crs = column_metadata.crs_string();
+        if let Some(field) = output_schema
+            .fields
+            .iter_mut()
+            .find(|field| field.name == geometry_column)
+        {
+            if crs.is_some() {
+                field.metadata.insert(
+                    EXTENSION_METADATA_KEY.to_string(),
+                    extension_metadata(crs.as_deref()),
+                );
+            }
+        }
+
+        let file_bounds = column_metadata.bbox.as_deref().and_then(bounds_2d);
+        let row_groups = match (file_bounds, bbox) {
+            (Some(bounds), Some(bbox)) if !bounds_intersect(&bounds, &bbox) => vec![],
+            _ => prune_row_groups_by_bbox(
+                &schema,
+                metadata.row_groups,
+                column_metadata
+                    .covering
+                    .as_ref()
+                    .map(|covering| &covering.bbox),
+                bbox,
+            )?,
+        };
+
+        Ok(Self {
+            read_schema,
+            output_schema,
+            output_indices,
+            filters: options.filters.clone(),
+            row_groups,
+            geometry_column: geometry_column.to_string(),
+        })
+    }
+
+    /// Filter the rows of a chunk read with `read_schema` and keep the output columns, or
+    /// `None` if no row is left.
+    pub fn output_chunk(
+        &self,
+        chunk: Chunk<Box<dyn Array>>,
+    ) -> Result<Option<Chunk<Box<dyn Array>>>, GeoArrowError> {
+        let chunk = apply_filters(&self.read_schema, &chunk, &self.filters)?;
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+
+        let arrays = self
+            .output_indices
+            .iter()
+            .map(|idx| chunk.arrays()[*idx].clone())
+            .collect();
+        Ok(Some(Chunk::new(arrays)))
+    }
+}
+
+/// Reads a GeoParquet file one chunk at a time, as a [`GeoTableReader`].
+///
+/// Chunks hold at most `options.batch_size` rows, and never span row groups.
+pub struct GeoParquetReader<R: Read + Seek> {
+    reader: FileReader<R>,
+    plan: ReadPlan,
+}
+
+impl<R: Read + Seek> GeoParquetReader<R> {
+    /// Read the metadata of the GeoParquet file `reader` and plan the read of its chunks, with
+    /// `options` and `bbox` applied as in [`read_geoparquet`].
+    pub fn try_new(
+        mut reader: R,
+        options: &ReaderOptions,
+        bbox: Option<[f64; 4]>,
+    ) -> Result<Self, GeoArrowError> {
+        let metadata =
+            read_metadata(&mut reader).map_err(|err| GeoArrowError::External(err.into()))?;
+        let plan = ReadPlan::try_new(metadata, options, bbox)?;
+        let reader = FileReader::new(
+            reader,
+            plan.row_groups.clone(),
+            plan.read_schema.clone(),
+            options.batch_size,
+            None,
+            None,
+        );
+        Ok(Self { reader, plan })
+    }
+
+    /// The name of the primary geometry column.
+    pub fn geometry_column(&self) -> &str {
+        &self.plan.geometry_column
+    }
+
+    /// The next chunk with rows left after filtering.
+    fn next_chunk(&mut self) -> Option<Result<Chunk<Box<dyn Array>>, GeoArrowError>> {
+        for maybe_chunk in self.reader.by_ref() {
+            let chunk = maybe_chunk
+                .map_err(|err| GeoArrowError::External(err.into()))
+                .and_then(|chunk| self.plan.output_chunk(chunk));
+            match chunk {
+                Ok(None) => continue,
+                Ok(Some(chunk)) => return Some(Ok(chunk)),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        None
+    }
+}
+
+impl<R: Read + Seek> Iterator for GeoParquetReader<R> {
+    type Item = Result<GeoTable, GeoArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.next_chunk()?;
+        Some(chunk.and_then(|chunk| {
+            GeoTable::from_arrow(
+                self.plan.output_schema.clone(),
+                vec![chunk],
+                Some(&self.plan.geometry_column),
+            )
+        }))
+    }
+}
+
+impl<R: Read + Seek> GeoTableReader for GeoParquetReader<R> {
+    fn schema(&self) -> Option<&Schema> {
+        Some(&self.plan.output_schema)
+    }
+}
+
+/// Read a GeoParquet file into a [`GeoTable`].
+///
+/// Only the columns selected in `options` (plus the primary geometry column) are returned, and
+/// only rows matching every attribute filter are kept. Columns that are neither selected nor
+/// referenced by a filter are never decoded. The CRS of the geometry column, if any, is stored in
+/// the extension metadata of its field, so that [`GeoTable::geometry`] returns arrays carrying it.
+///
+/// With a `bbox` of `[minx, miny, maxx, maxy]`, nothing is read if the bbox stored in the
+/// metadata does not intersect it. If the file has a GeoParquet 1.1 bbox covering column, row
+/// groups whose statistics for that column show they do not intersect `bbox` are skipped too.
+/// This only prunes whole row groups: use [`GeoTable::filter_bbox`] to keep exactly the rows
+/// intersecting `bbox`.
+///
+/// Use [`GeoParquetReader`] to read the file one chunk at a time instead.
+pub fn read_geoparquet<R: Read + Seek>(
+    reader: R,
+    options: &ReaderOptions,
+    bbox: Option<[f64; 4]>,
+) -> Result<GeoTable, GeoArrowError> {
+    let mut reader = GeoParquetReader::try_new(reader, options, bbox)?;
+    let mut chunks = vec![];
+    while let Some(chunk) = reader.next_chunk() {
+        chunks.push(chunk?);
+    }
+    let plan = reader.plan;
+    GeoTable::from_arrow(plan.output_schema, chunks, Some(&plan.geometry_column))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn bbox_pruning() {
+        // Two row groups of four points, around (0, 0) and (100, 100)
+        let path = "fixtures/geoparquet/points.parquet";
+        let table = read_geoparquet(File::open(path).unwrap(), &Default::default(), None).unwrap();
+        assert_eq!(table.len(), 8);
+
+        let bbox = Some([0., 0., 1.5, 1.5]);
+        let table = read_geoparquet(File::open(path).unwrap(), &Default::default(), bbox).unwrap();
+        assert_eq!(table.len(), 4);
+
+        let bbox = Some([50., 50., 60., 60.]);
+        let table = read_geoparquet(File::open(path).unwrap(), &Default::default(), bbox).unwrap();
+        assert_eq!(table.len(), 0);
+    }
+}
@@ -0,0 +1,356 @@
+//! Read the results of [PostGIS](https://postgis.net) queries.
+//!
+//! PostGIS sends `geometry` and `geography` values as EWKB, which is WKB with an optional SRID in
+//! the header. Rows are streamed from the server and converted to Arrow one batch at a time.
+
+use crate::binary::ewkb_srid;
+use crate::coerce::{coerce_chunks, from_geo};
+use crate::error::GeoArrowError;
+use crate::io::stream::ChunkParts;
+use crate::io::GeoTableReader;
+use crate::table::GeoTable;
+use crate::{GeometryArray, GeometryArrayType};
+use arrow2::array::{
+    Array, MutableArray, MutableBinaryArray, MutableBooleanArray, MutablePrimitiveArray,
+    MutableUtf8Array,
+};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use geozero::wkb::Ewkb;
+use geozero::ToGeo;
+use postgres::fallible_iterator::FallibleIterator;
+use postgres::types::{FromSql, ToSql, Type};
+use postgres::{Client, Row, RowIter, Statement};
+
+/// The number of rows in each output chunk when no batch size is given.
+const DEFAULT_BATCH_SIZE: usize = 65536;
+
+/// The raw EWKB of a `geometry` or `geography` value.
+struct EwkbBytes<'a>(&'a [u8]);
+
+impl<'a> FromSql<'a> for EwkbBytes<'a> {
+    fn from_sql(
+        _ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(Self(raw))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        is_geometry(ty)
+    }
+}
+
+fn is_geometry(ty: &Type) -> bool {
+    matches!(ty.name(), "geometry" | "geography")
+}
+
+/// Builds the arrays of an attribute column.
+enum ColumnBuilder {
+    Boolean(MutableBooleanArray),
+    Int16(MutablePrimitiveArray<i16>),
+    Int32(MutablePrimitiveArray<i32>),
+    Int64(MutablePrimitiveArray<i64>),
+    Float32(MutablePrimitiveArray<f32>),
+    Float64(MutablePrimitiveArray<f64>),
+    Utf8(MutableUtf8Array<i32>),
+    Binary(MutableBinaryArray<i32>),
+}
+
+impl ColumnBuilder {
+    /// A builder for values of the Postgres type `ty`, or `None` if the type is not supported.
+    fn new(ty: &Type) -> Option<Self> {
+        let builder = match *ty {
+            Type::BOOL => Self::Boolean(MutableBooleanArray::new()),
+            Type::INT2 => Self::Int16(MutablePrimitiveArray::new()),
+            Type::INT4 => Self::Int32(MutablePrimitiveArray::new()),
+            Type::INT8 => Self::Int64(MutablePrimitiveArray::new()),
+            Type::FLOAT4 => Self::Float32(MutablePrimitiveArray::new()),
+            Type::FLOAT8 => Self::Float64(MutablePrimitiveArray::new()),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+                Self::Utf8(MutableUtf8Array::new())
+            }
+            Type::BYTEA => Self::Binary(MutableBinaryArray::new()),
+            _ => return None,
+        };
+        Some(builder)
+    }
+
+    fn data_type(&self) -> &DataType {
+        match self {
+            Self::Boolean(builder) => builder.data_type(),
+            Self::Int16(builder) => builder.data_type(),
+            Self::Int32(builder) => builder.data_type(),
+            Self::Int64(builder) => builder.data_type(),
+            Self::Float32(builder) => builder.data_type(),
+            Self::Float64(builder) => builder.data_type(),
+            Self::Utf8(builder) => builder.data_type(),
+            Self::Binary(builder) => builder.data_type(),
+        }
+    }
+
+    /// Push the value of column `idx` of `row`.
+    fn push(&mut self, row: &Row, idx: usize) -> Result<(), postgres::Error> {
+        match self {
+            Self::Boolean(builder) => builder.push(row.try_get(idx)?),
+            Self::Int16(builder) => builder.push(row.try_get(idx)?),
+            Self::Int32(builder) => builder.push(row.try_get(idx)?),
+            Self::Int64(builder) => builder.push(row.try_get(idx)?),
+            Self::Float32(builder) => builder.push(row.try_get(idx)?),
+            Self::Float64(builder) => builder.push(row.try_get(idx)?),
+            Self::Utf8(builder) => builder.push(row.try_get::<_, Option<&str>>(idx)?),
+            Self::Binary(builder) => builder.push(row.try_get::<_, Option<&[u8]>>(idx)?),
+        }
+        Ok(())
+    }
+
+    /// Take the values pushed so far as an array, leaving the builder empty.
+    fn take(&mut self) -> Box<dyn Array> {
+        match self {
+            Self::Boolean(builder) => builder.as_box(),
+            Self::Int16(builder) => builder.as_box(),
+            Self::Int32(builder) => builder.as_box(),
+            Self::Int64(builder) => builder.as_box(),
+            Self::Float32(builder) => builder.as_box(),
+            Self::Float64(builder) => builder.as_box(),
+            Self::Utf8(builder) => builder.as_box(),
+            Self::Binary(builder) => builder.as_box(),
+        }
+    }
+}
+
+/// Reads the result of a query `batch_size` rows at a time, as a [`GeoTableReader`].
+///
+/// Rows are fetched from the server as the reader is consumed. The geometry column of each table
+/// has the narrowest type holding the geometries of its own chunk, so tables may have geometry
+/// columns of different types.
+pub struct PostgisReader<'a> {
+    rows: RowIter<'a>,
+    statement: Statement,
+    geometry_idx: usize,
+    /// The builders of the attribute columns, with their positions in the result
+    builders: Vec<(usize, ColumnBuilder)>,
+    batch_size: usize,
+    /// The SRID of the geometries read so far
+    srid: Option<i32>,
+    /// The number of rows read so far
+    row_number: usize,
+}
+
+impl<'a> PostgisReader<'a> {
+    /// Run `query`, checking the columns of its result as in [`read_postgis`].
+    /// # Errors
+    /// Errors if the query fails, if its result has no geometry column, more than one, or
+    /// columns of unsupported types.
+    pub fn try_new(
+        client: &'a mut Client,
+        query: &str,
+        batch_size: Option<usize>,
+    ) -> Result<Self, GeoArrowError> {
+        let statement = client
+            .prepare(query)
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+        let columns = statement.columns();
+        let geometry_columns: Vec<usize> = (0..columns.len())
+            .filter(|idx| is_geometry(columns[*idx].type_()))
+            .collect();
+        let geometry_idx = match geometry_columns.as_slice() {
+            [idx] => *idx,
+            [] => {
+                return Err(GeoArrowError::General(
+                    "query result has no geometry column".to_string(),
+                ))
+            }
+            _ => {
+                return Err(GeoArrowError::NotYetImplemented(
+                    "reading more than one geometry column".to_string(),
+                ))
+            }
+        };
+
+        let mut builders = vec![];
+        for (idx, column) in columns.iter().enumerate() {
+            if idx == geometry_idx {
+                continue;
+            }
+            let builder = ColumnBuilder::new(column.type_()).ok_or_else(|| {
+                GeoArrowError::NotYetImplemented(format!(
+                    "reading column {} of type {}",
+                    column.name(),
+                    column.type_()
+                ))
+            })?;
+            builders.push((idx, builder));
+        }
+
+        let rows = client
+            .query_raw(&statement, std::iter::empty::<&dyn ToSql>())
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+
+        Ok(Self {
+            rows,
+            statement,
+            geometry_idx,
+            builders,
+            batch_size: batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1),
+            srid: None,
+            row_number: 0,
+        })
+    }
+
+    /// The CRS of the geometries read so far.
+    fn crs(&self) -> Option<String> {
+        self.srid.map(|srid| format!("EPSG:{srid}"))
+    }
+
+    fn geometry_name(&self) -> &str {
+        self.statement.columns()[self.geometry_idx].name()
+    }
+
+    /// The fields of the attribute columns.
+    fn attribute_fields(&self) -> Vec<Field> {
+        let columns = self.statement.columns();
+        self.builders
+            .iter()
+            .map(|(idx, builder)| {
+                Field::new(columns[*idx].name(), builder.data_type().clone(), true)
+            })
+            .collect()
+    }
+
+    /// The attribute columns and the geometries of the next `batch_size` rows.
+    fn next_chunk(&mut self) -> Result<Option<ChunkParts>, GeoArrowError> {
+        let mut geoms = Vec::with_capacity(self.batch_size);
+        while geoms.len() < self.batch_size {
+            let row = match self
+                .rows
+                .next()
+                .map_err(|err| GeoArrowError::External(err.into()))?
+            {
+                Some(row) => row,
+                None => break,
+            };
+            for (idx, builder) in self.builders.iter_mut() {
+                builder
+                    .push(&row, *idx)
+                    .map_err(|err| GeoArrowError::External(err.into()))?;
+            }
+
+            let ewkb: Option<EwkbBytes> = row
+                .try_get(self.geometry_idx)
+                .map_err(|err| GeoArrowError::External(err.into()))?;
+            let geom = ewkb
+                .map(|EwkbBytes(buf)| {
+                    match (self.srid, ewkb_srid(buf)) {
+                        (Some(left), Some(right)) if left != right => {
+                            return Err(GeoArrowError::CrsMismatch {
+                                left: format!("EPSG:{left}"),
+                                right: format!("EPSG:{right}"),
+                            })
+                        }
+                        (None, right) => self.srid = right,
+                        _ => {}
+                    }
+                    Ewkb(buf.to_vec())
+                        .to_geo()
+                        .map_err(|err| GeoArrowError::InvalidWkb {
+                            row: self.row_number,
+                            message: err.to_string(),
+                        })
+                })
+                .transpose()?;
+            geoms.push(geom);
+            self.row_number += 1;
+        }
+
+        if geoms.is_empty() {
+            return Ok(None);
+        }
+        let arrays = self
+            .builders
+            .iter_mut()
+            .map(|(_, builder)| builder.take())
+            .collect();
+        Ok(Some((arrays, from_geo(geoms, GeometryArrayType::WKB))))
+    }
+}
+
+impl Iterator for PostgisReader<'_> {
+    type Item = Result<GeoTable, GeoArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut arrays, geometry) = match self.next_chunk() {
+            Ok(chunk) => chunk?,
+            Err(err) => return Some(Err(err)),
+        };
+        let (geometry_field, geometry) = geometry
+            .with_crs(self.crs().as_deref())
+            .into_arrow_field(self.geometry_name());
+        arrays.insert(self.geometry_idx, geometry);
+        let mut fields = self.attribute_fields();
+        fields.insert(self.geometry_idx, geometry_field);
+        Some(GeoTable::try_new(
+            Schema::from(fields),
+            vec![Chunk::new(arrays)],
+            self.geometry_idx,
+        ))
+    }
+}
+
+impl GeoTableReader for PostgisReader<'_> {
+    fn schema(&self) -> Option<&Schema> {
+        None
+    }
+}
+
+/// Run `query` and read its result into a [`GeoTable`], `batch_size` rows per chunk.
+///
+/// The result must have exactly one `geometry` or `geography` column, whose SRID, if any,
+/// becomes an `EPSG:<srid>` CRS. Attribute columns may be booleans, integers, floats,
+/// strings or `bytea`; cast other types in the query, e.g. to `text`. Select columns and filter
+/// rows in the query itself, so that PostGIS can use its indexes.
+///
+/// Geometries are stored in the narrowest type holding every row, as in
+/// [`from_wkt`](crate::io::wkt::from_wkt). Use [`PostgisReader`] to process one chunk at a time
+/// instead.
+/// # Errors
+/// Errors if the query fails, if its result has no geometry column, more than one, or columns of
+/// unsupported types, if a geometry cannot be parsed, or with [`GeoArrowError::CrsMismatch`] if
+/// geometries have different SRIDs.
+pub fn read_postgis(
+    client: &mut Client,
+    query: &str,
+    batch_size: Option<usize>,
+) -> Result<GeoTable, GeoArrowError> {
+    let mut reader = PostgisReader::try_new(client, query, batch_size)?;
+    let mut attributes = vec![];
+    let mut geometries = vec![];
+    while let Some((arrays, geometry)) = reader.next_chunk()? {
+        attributes.push(arrays);
+        geometries.push(geometry);
+    }
+
+    // The narrowest geometry type holding every chunk
+    let (kind, geometries) = coerce_chunks(geometries, GeometryArrayType::WKB)?;
+    let crs = reader.crs();
+    let geometry_idx = reader.geometry_idx;
+    let to_output = |geometry: GeometryArray| {
+        geometry
+            .with_crs(crs.as_deref())
+            .into_arrow_field(reader.geometry_name())
+    };
+
+    let (geometry_field, _) = to_output(from_geo(vec![], kind));
+    let chunks = attributes
+        .into_iter()
+        .zip(geometries)
+        .map(|(mut arrays, geometry)| {
+            arrays.insert(geometry_idx, to_output(geometry).1);
+            Chunk::new(arrays)
+        })
+        .collect();
+
+    let mut fields = reader.attribute_fields();
+    fields.insert(geometry_idx, geometry_field);
+    GeoTable::try_new(Schema::from(fields), chunks, geometry_idx)
+}
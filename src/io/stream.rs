@@ -0,0 +1,28 @@
+use crate::error::GeoArrowError;
+use crate::table::GeoTable;
+use arrow2::datatypes::Schema;
+
+/// The attribute columns and the geometries of one chunk, as read by a row-oriented reader
+/// before its geometries are given a common type.
+#[cfg(any(
+    feature = "csv",
+    feature = "flatgeobuf",
+    feature = "postgis",
+    feature = "serde"
+))]
+pub(crate) type ChunkParts = (Vec<Box<dyn arrow2::array::Array>>, crate::GeometryArray);
+
+/// An iterator over a dataset one chunk at a time, each chunk read into its own [`GeoTable`].
+///
+/// Only the chunk being yielded is held in memory, so datasets larger than memory can be
+/// processed by consuming the iterator as it goes. Readers honor the column selection, filters
+/// and batch size of their [`ReaderOptions`](super::ReaderOptions), and skip chunks left empty
+/// by filtering.
+pub trait GeoTableReader: Iterator<Item = Result<GeoTable, GeoArrowError>> {
+    /// The schema of every table yielded, or `None` when the type of the geometry column is
+    /// inferred from the geometries of each chunk, and so may differ between tables.
+    ///
+    /// Tables yielded by a reader returning `None` can be combined with [`GeoTable::concat`],
+    /// which promotes their geometries to a common type.
+    fn schema(&self) -> Option<&Schema>;
+}
@@ -0,0 +1,300 @@
+//! Parse [`WKBArray`]s into native geometry arrays.
+
+use crate::binary::{extend_m_values, geometry_type_id, WkbGeometryType};
+use crate::coerce::{from_geo, geometry_type_name, promote_geometry};
+use crate::error::GeoArrowError;
+use crate::parallel::map_rows;
+use crate::{
+    GeometryArray, GeometryArrayTrait, GeometryArrayType, LineStringArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, WKBArray,
+};
+use arrow2::buffer::Buffer;
+
+/// The type of array holding the WKB geometry in `buf`, read from its header, which is WKB for
+/// a geometry collection.
+///
+/// ISO (`1001`, `2001`, `3001`, ...) and extended (high flag bits) type codes for geometries with
+/// Z or M dimensions map to the kind of their 2D equivalent.
+fn wkb_geometry_kind(buf: &[u8], row: usize) -> Result<GeometryArrayType, GeoArrowError> {
+    let type_id =
+        geometry_type_id(buf).map_err(|message| GeoArrowError::InvalidWkb { row, message })?;
+    Ok(match type_id.geometry_type {
+        WkbGeometryType::Point => GeometryArrayType::Point,
+        WkbGeometryType::LineString => GeometryArrayType::LineString,
+        WkbGeometryType::Polygon => GeometryArrayType::Polygon,
+        WkbGeometryType::MultiPoint => GeometryArrayType::MultiPoint,
+        WkbGeometryType::MultiLineString => GeometryArrayType::MultiLineString,
+        WkbGeometryType::MultiPolygon => GeometryArrayType::MultiPolygon,
+        WkbGeometryType::GeometryCollection => GeometryArrayType::WKB,
+    })
+}
+
+/// Parse every geometry of `array`, keeping nulls, in parallel with the `rayon` feature.
+fn parse_wkb(array: &WKBArray) -> Result<Vec<Option<geo::Geometry>>, GeoArrowError> {
+    map_rows(array.len(), |i| {
+        array.get(i).map(|wkb| wkb.to_typed_scalar()).transpose()
+    })
+    .into_iter()
+    .collect()
+}
+
+/// The m value of every coordinate of `array`, in order, or `None` if no geometry declares m
+/// values. Null geometries are given `null_coords` coordinates each, and coordinates without an
+/// m value are `NaN`.
+fn wkb_m_values(
+    array: &WKBArray,
+    null_coords: usize,
+) -> Result<Option<Buffer<f64>>, GeoArrowError> {
+    if !array.has_m() {
+        return Ok(None);
+    }
+    let mut m_values = Vec::new();
+    for i in 0..array.len() {
+        match array.get(i) {
+            Some(wkb) => extend_m_values(wkb.as_slice(), &mut m_values)
+                .map_err(|message| GeoArrowError::InvalidWkb { row: i, message })?,
+            None => m_values.extend(std::iter::repeat_n(f64::NAN, null_coords)),
+        }
+    }
+    Ok(Some(m_values.into()))
+}
+
+/// Native arrays parsed from WKB, keeping the m values of the WKB geometries.
+trait WithWkbM: Sized {
+    /// The number of coordinates taken by a null geometry.
+    const NULL_COORDS: usize;
+
+    fn with_m(self, m: Buffer<f64>) -> Result<Self, GeoArrowError>;
+
+    fn with_wkb_m(self, wkb: &WKBArray) -> Result<Self, GeoArrowError> {
+        match wkb_m_values(wkb, Self::NULL_COORDS)? {
+            Some(m) => self.with_m(m),
+            None => Ok(self),
+        }
+    }
+}
+
+macro_rules! impl_with_wkb_m {
+    ($array:ty, $null_coords:expr) => {
+        impl WithWkbM for $array {
+            const NULL_COORDS: usize = $null_coords;
+
+            fn with_m(self, m: Buffer<f64>) -> Result<Self, GeoArrowError> {
+                <$array>::with_m(self, m)
+            }
+        }
+    };
+}
+
+// Null points still take a slot of the coordinate buffers
+impl_with_wkb_m!(PointArray, 1);
+impl_with_wkb_m!(LineStringArray, 0);
+impl_with_wkb_m!(PolygonArray, 0);
+impl_with_wkb_m!(MultiPointArray, 0);
+impl_with_wkb_m!(MultiLineStringArray, 0);
+impl_with_wkb_m!(MultiPolygonArray, 0);
+
+/// Parse a [`WKBArray`] into a [`GeometryArray`] of the narrowest type able to hold every
+/// value.
+///
+/// The geometry types are found by reading only the header of each geometry, and the array is
+/// returned unchanged, without parsing, if they have no common native type. Single and multi
+/// geometries of the same dimension (e.g. points and multi points) are read as multi geometries.
+/// An array of only nulls is returned unchanged. m values are kept.
+///
+/// EWKB is read too: if the array has no CRS, the SRID of its geometries sets the CRS of the
+/// output, as in [`WKBArray::with_srid_crs`].
+/// # Errors
+/// Errors with [`GeoArrowError::InvalidWkb`] if any geometry is not valid WKB, or with
+/// [`GeoArrowError::CrsMismatch`] if geometries have different SRIDs.
+pub fn from_wkb(array: &WKBArray) -> Result<GeometryArray, GeoArrowError> {
+    let kinds = (0..array.len())
+        .filter_map(|i| array.get(i).map(|wkb| wkb_geometry_kind(wkb.as_slice(), i)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let kind = match GeometryArrayType::common(kinds.into_iter()) {
+        GeometryArrayType::WKB => return Ok(GeometryArray::WKB(array.clone())),
+        kind => kind,
+    };
+
+    let geoms = parse_wkb(array)?
+        .into_iter()
+        .map(|maybe_g| maybe_g.map(|geom| promote_geometry(geom, kind)))
+        .collect();
+    let output = from_geo(geoms, kind).with_crs(array.resolved_crs()?.as_deref());
+    Ok(match output {
+        GeometryArray::Point(arr) => GeometryArray::Point(arr.with_wkb_m(array)?),
+        GeometryArray::LineString(arr) => GeometryArray::LineString(arr.with_wkb_m(array)?),
+        GeometryArray::Polygon(arr) => GeometryArray::Polygon(arr.with_wkb_m(array)?),
+        GeometryArray::MultiPoint(arr) => GeometryArray::MultiPoint(arr.with_wkb_m(array)?),
+        GeometryArray::MultiLineString(arr) => {
+            GeometryArray::MultiLineString(arr.with_wkb_m(array)?)
+        }
+        GeometryArray::MultiPolygon(arr) => GeometryArray::MultiPolygon(arr.with_wkb_m(array)?),
+        output => output,
+    })
+}
+
+macro_rules! impl_try_from_wkb {
+    ($array:ty, $variant:ident $(, $single:ident)?) => {
+        impl TryFrom<&WKBArray> for $array {
+            type Error = GeoArrowError;
+
+            fn try_from(value: &WKBArray) -> Result<Self, Self::Error> {
+                let geoms = parse_wkb(value)?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(row, maybe_g)| {
+                        maybe_g
+                            .map(|geom| match geom {
+                                geo::Geometry::$variant(geom) => Ok(geom),
+                                $(geo::Geometry::$single(geom) => Ok(geo::$variant(vec![geom])),)?
+                                other => Err(GeoArrowError::UnexpectedGeometryType {
+                                    row,
+                                    expected: stringify!($variant),
+                                    found: geometry_type_name(&other),
+                                }),
+                            })
+                            .transpose()
+                    })
+                    .collect::<Result<Vec<Option<geo::$variant>>, _>>()?;
+                Self::from(geoms)
+                    .with_crs(value.resolved_crs()?.as_deref())
+                    .with_wkb_m(value)
+            }
+        }
+
+        impl TryFrom<WKBArray> for $array {
+            type Error = GeoArrowError;
+
+            /// Parse every geometry. Null geometries stay null, and m values are kept.
+            /// # Errors
+            /// Errors if any geometry is not valid WKB, or is of another type, or if EWKB
+            /// geometries have different SRIDs.
+            fn try_from(value: WKBArray) -> Result<Self, Self::Error> {
+                (&value).try_into()
+            }
+        }
+    };
+}
+
+impl_try_from_wkb!(PointArray, Point);
+impl_try_from_wkb!(LineStringArray, LineString);
+impl_try_from_wkb!(PolygonArray, Polygon);
+impl_try_from_wkb!(MultiPointArray, MultiPoint, Point);
+impl_try_from_wkb!(MultiLineStringArray, MultiLineString, LineString);
+impl_try_from_wkb!(MultiPolygonArray, MultiPolygon, Polygon);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::BinaryArray;
+    use geo::{line_string, point, polygon};
+
+    fn wkb(geoms: Vec<Option<geo::Geometry>>) -> WKBArray {
+        geoms.into()
+    }
+
+    #[test]
+    fn typed_arrays() {
+        let arr = wkb(vec![
+            Some(point!(x: 1., y: 2.).into()),
+            None,
+            Some(point!(x: 3., y: 4.).into()),
+        ]);
+        let points = PointArray::try_from(arr).unwrap();
+        assert_eq!(points.get_as_geo(0), Some(point!(x: 1., y: 2.)));
+        assert!(points.is_null(1));
+        assert_eq!(points.get_as_geo(2), Some(point!(x: 3., y: 4.)));
+
+        let arr = wkb(vec![
+            Some(point!(x: 1., y: 2.).into()),
+            Some(line_string![(x: 0., y: 0.), (x: 1., y: 1.)].into()),
+        ]);
+        assert!(matches!(
+            LineStringArray::try_from(&arr),
+            Err(GeoArrowError::UnexpectedGeometryType { row: 0, .. })
+        ));
+        let multi_points = MultiPointArray::try_from(&wkb(vec![Some(point!(x: 1., y: 2.).into())]));
+        assert_eq!(
+            multi_points.unwrap().value_as_geo(0),
+            geo::MultiPoint(vec![point!(x: 1., y: 2.)])
+        );
+
+        let invalid = WKBArray::new(BinaryArray::<i64>::from_slice([[1_u8, 1, 0]]));
+        assert!(matches!(
+            PointArray::try_from(invalid),
+            Err(GeoArrowError::InvalidWkb { row: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn m_values() {
+        let lines: LineStringArray = vec![
+            Some(line_string![(x: 0., y: 0.), (x: 1., y: 1.)]),
+            None,
+            Some(line_string![(x: 2., y: 2.), (x: 3., y: 3.)]),
+        ]
+        .into();
+        let lines = lines.with_m(vec![0., 1., 2., 3.].into()).unwrap();
+        let arr = WKBArray::from(&lines);
+        assert!(arr.has_m());
+        assert_eq!(
+            arr.geometry_types()
+                .unwrap()
+                .into_iter()
+                .next()
+                .unwrap()
+                .to_string(),
+            "LineString M"
+        );
+
+        let output = LineStringArray::try_from(&arr).unwrap();
+        assert_eq!(output.value(2).m_values(), Some(&[2., 3.][..]));
+        assert_eq!(output.value_as_geo(2), lines.value_as_geo(2));
+        match from_wkb(&arr).unwrap() {
+            GeometryArray::LineString(output) => assert!(output.has_m()),
+            _ => panic!("expected a line string array"),
+        }
+
+        let points: PointArray = vec![Some(point!(x: 1., y: 2.)), None].into();
+        let points = points.with_m(vec![7., f64::NAN].into()).unwrap();
+        let output = PointArray::try_from(WKBArray::from(&points)).unwrap();
+        assert_eq!(output.value(0).m(), Some(7.));
+        assert!(output.is_null(1));
+    }
+
+    #[test]
+    fn sniff_geometry_type() {
+        let polygon = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let arr = wkb(vec![
+            Some(polygon.clone().into()),
+            None,
+            Some(geo::MultiPolygon(vec![polygon.clone()]).into()),
+        ]);
+        match from_wkb(&arr).unwrap() {
+            GeometryArray::MultiPolygon(arr) => {
+                assert_eq!(arr.value_as_geo(0), geo::MultiPolygon(vec![polygon]));
+                assert!(arr.is_null(1));
+            }
+            _ => panic!("expected a multi polygon array"),
+        }
+
+        let arr = wkb(vec![
+            Some(point!(x: 0., y: 0.).into()),
+            Some(line_string![(x: 0., y: 0.), (x: 1., y: 1.)].into()),
+        ]);
+        assert!(matches!(from_wkb(&arr).unwrap(), GeometryArray::WKB(_)));
+
+        // Big-endian ISO WKB of POINT Z (1 2 3)
+        let mut point_z = vec![0_u8];
+        point_z.extend(1001_u32.to_be_bytes());
+        for value in [1_f64, 2., 3.] {
+            point_z.extend(value.to_be_bytes());
+        }
+        assert_eq!(
+            wkb_geometry_kind(&point_z, 0).unwrap(),
+            GeometryArrayType::Point
+        );
+        assert!(wkb_geometry_kind(&[2, 1, 0, 0, 0], 0).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Read and write geometries as columns of [well-known text
+//! (WKT)](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry) strings.
+
+use crate::coerce::{from_geo_promoted, geometry_type_name};
+use crate::error::GeoArrowError;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MixedGeometryArray, MultiLineStringArray,
+    MultiPointArray, MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use arrow2::array::{MutableUtf8Array, Utf8Array};
+use arrow2::offset::Offset;
+use geozero::wkt::WktStr;
+use geozero::{ToGeo, ToWkt};
+
+/// Parse every string of `array`, keeping nulls.
+fn parse_wkt<O: Offset>(array: &Utf8Array<O>) -> Result<Vec<Option<geo::Geometry>>, GeoArrowError> {
+    array
+        .iter()
+        .enumerate()
+        .map(|(row, maybe_wkt)| {
+            maybe_wkt
+                .map(|wkt| {
+                    WktStr(wkt)
+                        .to_geo()
+                        .map_err(|err| GeoArrowError::InvalidWkt {
+                            row,
+                            message: err.to_string(),
+                        })
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+/// Write one WKT string per geometry, keeping nulls.
+fn write_wkt(
+    geoms: impl Iterator<Item = Option<geo::Geometry>>,
+) -> Result<Utf8Array<i64>, GeoArrowError> {
+    let mut output = MutableUtf8Array::<i64>::with_capacity(geoms.size_hint().0);
+    for maybe_geom in geoms {
+        let wkt = maybe_geom
+            .map(|geom| geom.to_wkt())
+            .transpose()
+            .map_err(|err| GeoArrowError::External(err.into()))?;
+        output.push(wkt);
+    }
+    Ok(output.into())
+}
+
+/// Parse a WKT column into a [`GeometryArray`] of the narrowest type able to hold every value.
+///
+/// Single and multi geometries of the same dimension (e.g. `POINT` and `MULTIPOINT`) are read
+/// as multi geometries. Any other mix of geometry types, as well as geometry collections, is
+/// read as WKB. A column of only nulls is read as WKB.
+/// # Errors
+/// Errors with [`GeoArrowError::InvalidWkt`] if any string is not valid WKT.
+pub fn from_wkt<O: Offset>(array: &Utf8Array<O>) -> Result<GeometryArray, GeoArrowError> {
+    Ok(from_geo_promoted(parse_wkt(array)?))
+}
+
+macro_rules! impl_from_wkt {
+    ($array:ty, $variant:ident $(, $single:ident)?) => {
+        impl $array {
+            /// Parse a column of WKT strings. Null strings become null geometries.
+            /// # Errors
+            /// Errors if any string is not valid WKT, or holds a geometry of another type.
+            pub fn from_wkt<O: Offset>(array: &Utf8Array<O>) -> Result<Self, GeoArrowError> {
+                let geoms = parse_wkt(array)?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(row, maybe_g)| {
+                        maybe_g
+                            .map(|geom| match geom {
+                                geo::Geometry::$variant(geom) => Ok(geom),
+                                $(geo::Geometry::$single(geom) => Ok(geo::$variant(vec![geom])),)?
+                                other => Err(GeoArrowError::UnexpectedGeometryType {
+                                    row,
+                                    expected: stringify!($variant),
+                                    found: geometry_type_name(&other),
+                                }),
+                            })
+                            .transpose()
+                    })
+                    .collect::<Result<Vec<Option<geo::$variant>>, _>>()?;
+                Ok(geoms.into())
+            }
+        }
+    };
+}
+
+macro_rules! impl_to_wkt {
+    ($array:ty) => {
+        impl $array {
+            /// Write each geometry as a WKT string. Null geometries become null strings.
+            ///
+            /// This differs from [`ToWkt::to_wkt`], which writes the whole array as a single
+            /// `GEOMETRYCOLLECTION`.
+            pub fn to_wkt(&self) -> Result<Utf8Array<i64>, GeoArrowError> {
+                write_wkt((0..self.len()).map(|i| self.get_as_geo(i).map(geo::Geometry::from)))
+            }
+        }
+    };
+}
+
+impl_to_wkt!(PointArray);
+impl_to_wkt!(LineStringArray);
+impl_to_wkt!(PolygonArray);
+impl_to_wkt!(MultiPointArray);
+impl_to_wkt!(MultiLineStringArray);
+impl_to_wkt!(MultiPolygonArray);
+impl_to_wkt!(RectArray);
+impl_to_wkt!(WKBArray);
+impl_to_wkt!(MixedGeometryArray);
+
+impl GeometryArray {
+    /// Write each geometry as a WKT string. Null geometries become null strings.
+    pub fn to_wkt(&self) -> Result<Utf8Array<i64>, GeoArrowError> {
+        match self {
+            GeometryArray::Point(arr) => arr.to_wkt(),
+            GeometryArray::LineString(arr) => arr.to_wkt(),
+            GeometryArray::Polygon(arr) => arr.to_wkt(),
+            GeometryArray::MultiPoint(arr) => arr.to_wkt(),
+            GeometryArray::MultiLineString(arr) => arr.to_wkt(),
+            GeometryArray::MultiPolygon(arr) => arr.to_wkt(),
+            GeometryArray::WKB(arr) => arr.to_wkt(),
+            GeometryArray::Rect(arr) => arr.to_wkt(),
+            GeometryArray::Mixed(arr) => arr.to_wkt(),
+        }
+    }
+}
+
+impl_from_wkt!(PointArray, Point);
+impl_from_wkt!(LineStringArray, LineString);
+impl_from_wkt!(PolygonArray, Polygon);
+impl_from_wkt!(MultiPointArray, MultiPoint, Point);
+impl_from_wkt!(MultiLineStringArray, MultiLineString, LineString);
+impl_from_wkt!(MultiPolygonArray, MultiPolygon, Polygon);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow2::array::Array;
+    use geo::{line_string, point};
+
+    #[test]
+    fn typed_arrays() {
+        let wkt = Utf8Array::<i32>::from([Some("POINT (1 2)"), None, Some("POINT(3 4)")]);
+        let arr = PointArray::from_wkt(&wkt).unwrap();
+        assert_eq!(arr.get_as_geo(0), Some(point!(x: 1., y: 2.)));
+        assert!(arr.is_null(1));
+        assert_eq!(arr.get_as_geo(2), Some(point!(x: 3., y: 4.)));
+
+        let wkt = Utf8Array::<i64>::from_slice(["LINESTRING (0 0, 1 1)", "POINT (0 0)"]);
+        assert!(matches!(
+            LineStringArray::from_wkt(&wkt),
+            Err(GeoArrowError::UnexpectedGeometryType { row: 1, .. })
+        ));
+
+        let wkt = Utf8Array::<i32>::from_slice(["MULTIPOINT (0 0, 1 1)", "POINT (2 2)"]);
+        let arr = MultiPointArray::from_wkt(&wkt).unwrap();
+        assert_eq!(
+            arr.value_as_geo(1),
+            geo::MultiPoint(vec![point!(x: 2., y: 2.)])
+        );
+
+        let wkt = Utf8Array::<i32>::from_slice(["POINT (1"]);
+        assert!(matches!(
+            PointArray::from_wkt(&wkt),
+            Err(GeoArrowError::InvalidWkt { row: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn sniff_geometry_type() {
+        let wkt = Utf8Array::<i32>::from([Some("LINESTRING (0 0, 1 1)"), None]);
+        match from_wkt(&wkt).unwrap() {
+            GeometryArray::LineString(arr) => {
+                assert_eq!(
+                    arr.value_as_geo(0),
+                    line_string![(x: 0., y: 0.), (x: 1., y: 1.)]
+                )
+            }
+            _ => panic!("expected a line string array"),
+        }
+
+        let wkt = Utf8Array::<i32>::from_slice([
+            "POLYGON ((0 0, 1 0, 1 1, 0 0))",
+            "MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)))",
+        ]);
+        assert!(matches!(
+            from_wkt(&wkt).unwrap(),
+            GeometryArray::MultiPolygon(_)
+        ));
+
+        let wkt = Utf8Array::<i32>::from_slice(["POINT (0 0)", "LINESTRING (0 0, 1 1)"]);
+        assert!(matches!(from_wkt(&wkt).unwrap(), GeometryArray::WKB(_)));
+    }
+
+    #[test]
+    fn write_per_row() {
+        let arr: PointArray = vec![Some(point!(x: 1., y: 2.)), None].into();
+        let wkt = arr.to_wkt().unwrap();
+        assert_eq!(wkt.len(), 2);
+        assert_eq!(wkt.value(0), "POINT(1 2)");
+        assert!(wkt.is_null(1));
+
+        let wkt = Utf8Array::<i32>::from([
+            Some("MULTILINESTRING((0 0,1 1),(2 2,3 3))"),
+            None,
+            Some("POLYGON((0 0,1 0,1 1,0 0))"),
+        ]);
+        let arr = from_wkt(&wkt).unwrap();
+        assert!(matches!(arr, GeometryArray::WKB(_)));
+        let roundtrip = arr.to_wkt().unwrap();
+        assert_eq!(
+            roundtrip.iter().collect::<Vec<_>>(),
+            wkt.iter().collect::<Vec<_>>()
+        );
+    }
+}
@@ -2,25 +2,60 @@
 //! specification.
 
 pub use binary::{MutableWKBArray, WKBArray, WKB};
-pub use enum_::GeometryArray;
-pub use linestring::{LineString, LineStringArray, MutableLineStringArray};
-pub use multilinestring::{MultiLineString, MultiLineStringArray, MutableMultiLineStringArray};
-pub use multipoint::{MultiPoint, MultiPointArray, MutableMultiPointArray};
-pub use multipolygon::{MultiPolygon, MultiPolygonArray, MutableMultiPolygonArray};
-pub use point::{MutablePointArray, Point, PointArray};
-pub use polygon::{MutablePolygonArray, Polygon, PolygonArray};
-pub use trait_::GeometryArrayTrait;
+pub use enum_::{GeometryArray, MutableGeometryArray};
+pub use eq::array_eq;
+pub use geometrycollection::{GeometryCollection, GeometryCollectionArray};
+pub use indexed::IndexedGeometryArray;
+pub use linestring::{LineString, LineStringArray, MutableLineStringArray, OwnedLineString};
+pub use mixed::MixedGeometryArray;
+pub use multilinestring::{
+    MultiLineString, MultiLineStringArray, MutableMultiLineStringArray, OwnedMultiLineString,
+};
+pub use multipoint::{MultiPoint, MultiPointArray, MutableMultiPointArray, OwnedMultiPoint};
+pub use multipolygon::{
+    MultiPolygon, MultiPolygonArray, MutableMultiPolygonArray, OwnedMultiPolygon,
+};
+pub use point::{MutablePointArray, OwnedPoint, Point, PointArray};
+pub use polygon::{MutablePolygonArray, OwnedPolygon, Polygon, PolygonArray};
+pub use rect::{Rect, RectArray};
+pub use table::GeoTable;
+pub use trait_::{Dimension, GeometryArrayTrait, GeometryArrayType};
 
 pub mod algorithm;
 pub mod binary;
+mod coerce;
+mod concat;
+mod coords;
+mod crs;
+#[cfg(feature = "datafusion")]
+pub mod datafusion;
+mod display;
 pub mod enum_;
+mod eq;
 pub mod error;
+pub mod ffi;
 pub mod geo_traits;
+#[cfg(feature = "serde")]
+pub mod geojson;
+pub mod geometrycollection;
+pub mod growable;
+pub mod indexed;
+pub mod io;
 pub mod linestring;
+pub mod mixed;
 pub mod multilinestring;
 pub mod multipoint;
 pub mod multipolygon;
+mod offsets;
+mod parallel;
+pub mod pipeline;
 pub mod point;
+#[cfg(feature = "polars")]
+pub mod polars;
 pub mod polygon;
+mod processor;
+pub mod rect;
 mod slice;
+pub mod table;
 pub mod trait_;
+pub mod validity;
@@ -1,14 +1,25 @@
+use crate::concat::concat_crs;
+use crate::coords::{concat_m, coord_fields, coords_have_m, impl_coords, values_range};
+use crate::crs::impl_crs;
 use crate::error::GeoArrowError;
+use crate::offsets::widen_list_offsets;
+#[cfg(feature = "rayon")]
+use crate::parallel::MIN_ROWS_PER_TASK;
 use crate::slice::slice_validity_unchecked;
-use crate::{GeometryArrayTrait, MultiPointArray};
+use crate::trait_::{Dimension, GeometryArrayType};
+use crate::validity::check_validity_len;
+use crate::{GeometryArrayTrait, MultiLineStringArray, MultiPointArray};
 use arrow2::array::{Array, ListArray, PrimitiveArray, StructArray};
 use arrow2::bitmap::utils::{BitmapIter, ZipValidity};
 use arrow2::bitmap::Bitmap;
 use arrow2::buffer::Buffer;
 use arrow2::datatypes::{DataType, Field};
-use arrow2::offset::OffsetsBuffer;
+use arrow2::offset::{Offsets, OffsetsBuffer};
 use geozero::{GeomProcessor, GeozeroGeometry};
-use rstar::RTree;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::ops::Range;
+use std::sync::Arc;
 
 use super::MutableLineStringArray;
 
@@ -22,11 +33,17 @@ pub struct LineStringArray {
     /// Buffer of y coordinates
     y: Buffer<f64>,
 
+    /// Buffer of m values (measures), one per coordinate, if any
+    m: Option<Buffer<f64>>,
+
     /// Offsets into the coordinate array where each geometry starts
     geom_offsets: OffsetsBuffer<i64>,
 
     /// Validity bitmap
     validity: Option<Bitmap>,
+
+    /// Coordinate reference system
+    crs: Option<Arc<str>>,
 }
 
 pub(super) fn check(
@@ -64,8 +81,10 @@ impl LineStringArray {
         Self {
             x,
             y,
+            m: None,
             geom_offsets,
             validity,
+            crs: None,
         }
     }
 
@@ -82,12 +101,54 @@ impl LineStringArray {
         Ok(Self {
             x,
             y,
+            m: None,
             geom_offsets,
             validity,
+            crs: None,
         })
     }
+
+    /// Offsets into the coordinate buffers where each geometry starts.
+    #[inline]
+    pub fn geom_offsets(&self) -> &OffsetsBuffer<i64> {
+        &self.geom_offsets
+    }
+
+    /// Concatenate `arrays` into one array, copying their coordinates and rebuilding offsets.
+    ///
+    /// Arrays without a CRS are assumed to be in the CRS of the others. m values are kept if
+    /// every array has them.
+    /// # Errors
+    /// Errors if two arrays have different CRSes, or if the offsets of the result would overflow.
+    pub fn concat(arrays: &[&LineStringArray]) -> Result<Self, GeoArrowError> {
+        let crs = concat_crs(arrays.iter().map(|array| array.crs()))?;
+        let mut mutable = MutableLineStringArray::new();
+        for array in arrays {
+            mutable.extend_from_array(array)?;
+        }
+        let array = Self::from(mutable).with_crs(crs);
+        let m = concat_m(arrays.iter().map(|array| {
+            let range = array.coord_range(0..array.len());
+            array.values_m().map(|m| &m[range])
+        }));
+        match m {
+            Some(m) => array.with_m(m),
+            None => Ok(array),
+        }
+    }
+}
+
+impl_crs!(LineStringArray);
+
+impl LineStringArray {
+    /// The range of the coordinate buffers holding the coordinates of the geometries `geoms`.
+    pub(crate) fn coord_range(&self, geoms: Range<usize>) -> Range<usize> {
+        values_range(&self.geom_offsets, geoms)
+    }
 }
 
+impl_coords!(LineStringArray);
+
 impl<'a> GeometryArrayTrait<'a> for LineStringArray {
     type Scalar = crate::LineString<'a>;
     type ScalarGeo = geo::LineString;
@@ -98,6 +159,7 @@ impl<'a> GeometryArrayTrait<'a> for LineStringArray {
         crate::LineString {
             x: &self.x,
             y: &self.y,
+            m: self.m.as_ref(),
             geom_offsets: &self.geom_offsets,
             geom_index: i,
         }
@@ -105,36 +167,31 @@ impl<'a> GeometryArrayTrait<'a> for LineStringArray {
 
     fn into_arrow(self) -> ListArray<i64> {
         // Data type
-        let coord_field_x = Field::new("x", DataType::Float64, false);
-        let coord_field_y = Field::new("y", DataType::Float64, false);
-        let struct_data_type = DataType::Struct(vec![coord_field_x, coord_field_y]);
+        let (coord_fields, coord_values) = coord_fields(self.x, self.y, self.m);
+        let struct_data_type = DataType::Struct(coord_fields);
         let list_data_type = DataType::LargeList(Box::new(Field::new(
             "vertices",
             struct_data_type.clone(),
             true,
         )));
 
-        // Validity
-        let validity: Option<Bitmap> = if let Some(validity) = self.validity {
-            validity.into()
-        } else {
-            None
-        };
-
         // Array data
-        let array_x = PrimitiveArray::new(DataType::Float64, self.x, None).boxed();
-        let array_y = PrimitiveArray::new(DataType::Float64, self.y, None).boxed();
-
-        let coord_array = StructArray::new(struct_data_type, vec![array_x, array_y], None).boxed();
+        let coord_array = StructArray::new(struct_data_type, coord_values, None).boxed();
+
+        ListArray::new(
+            list_data_type,
+            self.geom_offsets,
+            coord_array,
+            self.validity,
+        )
+    }
 
-        ListArray::new(list_data_type, self.geom_offsets, coord_array, validity)
+    fn geometry_type(&self) -> GeometryArrayType {
+        GeometryArrayType::LineString
     }
 
-    /// Build a spatial index containing this array's geometries
-    fn rstar_tree(&'a self) -> RTree<Self::Scalar> {
-        let mut tree = RTree::new();
-        self.iter().flatten().for_each(|geom| tree.insert(geom));
-        tree
+    fn dimension(&self) -> Dimension {
+        Dimension::new(false, self.m.is_some())
     }
 
     /// Returns the number of geometries in this array
@@ -183,6 +240,14 @@ impl<'a> GeometryArrayTrait<'a> for LineStringArray {
         self.geom_offsets.slice_unchecked(offset, length + 1);
     }
 
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        check_validity_len(validity.as_ref(), self.len());
+        Self {
+            validity,
+            ..self.clone()
+        }
+    }
+
     fn to_boxed(&self) -> Box<Self> {
         Box::new(self.clone())
     }
@@ -202,6 +267,26 @@ impl LineStringArray {
         ZipValidity::new_with_validity(self.iter_geo_values(), self.validity())
     }
 
+    /// Parallel iterator over the scalars of this array, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = Option<crate::LineString<'_>>> {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get(i))
+    }
+
+    /// Parallel iterator over geo Geometry objects, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_geo(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = Option<geo::LineString>> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get_as_geo(i))
+    }
+
     /// Returns the value at slot `i` as a GEOS geometry.
     #[cfg(feature = "geos")]
     pub fn value_as_geos(&self, i: usize) -> geos::Geometry {
@@ -254,20 +339,45 @@ impl TryFrom<ListArray<i64>> for LineStringArray {
             .downcast_ref::<PrimitiveArray<f64>>()
             .unwrap();
 
-        Ok(Self::new(
+        let array = Self::new(
             x_array_values.values().clone(),
             y_array_values.values().clone(),
             geom_offsets.clone(),
             validity.cloned(),
-        ))
+        );
+        if !coords_have_m(struct_array)? {
+            return Ok(array);
+        }
+        let m_array_values = struct_array.values()[2]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .unwrap();
+        array.with_m(m_array_values.values().clone())
+    }
+}
+
+impl TryFrom<ListArray<i32>> for LineStringArray {
+    type Error = GeoArrowError;
+
+    /// Read a list array with 32-bit offsets, widening them to 64 bits.
+    fn try_from(value: ListArray<i32>) -> Result<Self, Self::Error> {
+        value.boxed().try_into()
     }
 }
 
 impl TryFrom<Box<dyn Array>> for LineStringArray {
     type Error = GeoArrowError;
 
+    /// Both `List` and `LargeList` arrays are accepted.
     fn try_from(value: Box<dyn Array>) -> Result<Self, Self::Error> {
-        let arr = value.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+        let value = widen_list_offsets(value.as_ref());
+        let arr = value
+            .as_any()
+            .downcast_ref::<ListArray<i64>>()
+            .ok_or_else(|| GeoArrowError::Downcast {
+                expected: "ListArray<i64>",
+                found: value.data_type().clone(),
+            })?;
         arr.clone().try_into()
     }
 }
@@ -291,6 +401,26 @@ impl From<Vec<geo::LineString>> for LineStringArray {
 impl From<LineStringArray> for MultiPointArray {
     fn from(value: LineStringArray) -> Self {
         Self::new(value.x, value.y, value.geom_offsets, value.validity)
+            .with_crs(value.crs.as_deref())
+            .with_optional_m(value.m)
+    }
+}
+
+/// Wrap each line string in a multi line string of length one
+impl From<LineStringArray> for MultiLineStringArray {
+    fn from(value: LineStringArray) -> Self {
+        let geom_offsets = Offsets::<i64>::try_from_lengths((0..value.len()).map(|_| 1))
+            .unwrap()
+            .into();
+        Self::new(
+            value.x,
+            value.y,
+            geom_offsets,
+            value.geom_offsets,
+            value.validity,
+        )
+        .with_crs(value.crs.as_deref())
+        .with_optional_m(value.m)
     }
 }
 
@@ -362,7 +492,7 @@ mod test {
     #[test]
     fn geozero_process_geom() -> geozero::error::Result<()> {
         let arr: LineStringArray = vec![ls0(), ls1()].into();
-        let wkt = arr.to_wkt()?;
+        let wkt = ToWkt::to_wkt(&arr)?;
         let expected = "GEOMETRYCOLLECTION(LINESTRING(0 1,1 2),LINESTRING(3 4,5 6))";
         assert_eq!(wkt, expected);
         Ok(())
@@ -374,16 +504,31 @@ mod test {
         let tree = arr.rstar_tree();
 
         let search_box = AABB::from_corners([3.5, 5.5], [4.5, 6.5]);
-        let results: Vec<&crate::LineString> =
-            tree.locate_in_envelope_intersecting(&search_box).collect();
+        let results: Vec<_> = tree.locate_in_envelope_intersecting(&search_box).collect();
 
         assert_eq!(results.len(), 1);
         assert_eq!(
-            results[0].geom_index, 1,
+            results[0].geom_index(),
+            1,
             "The second element in the LineStringArray should be found"
         );
     }
 
+    #[test]
+    fn m_values() {
+        let arr: LineStringArray = vec![ls0(), ls1()].into();
+        let mut arr = arr.with_m(vec![0., 5., 10., 15.].into()).unwrap();
+        assert!(arr.has_m());
+        assert_eq!(arr.value(1).m_values(), Some(&[10., 15.][..]));
+
+        let round_trip = LineStringArray::try_from(arr.clone().into_arrow()).unwrap();
+        assert_eq!(round_trip.value(0).m_values(), Some(&[0., 5.][..]));
+
+        arr.slice(1, 1);
+        let concat = LineStringArray::concat(&[&arr]).unwrap();
+        assert_eq!(concat.values_m().unwrap().as_slice(), &[10., 15.]);
+    }
+
     #[test]
     fn slice() {
         let mut arr: LineStringArray = vec![ls0(), ls1()].into();
@@ -0,0 +1,70 @@
+//! Capacities of the buffers of a [`MutableLineStringArray`](super::MutableLineStringArray).
+
+use crate::geo_traits::LineStringTrait;
+use std::ops::Add;
+
+/// The number of coordinates and geometries to allocate in a
+/// [`MutableLineStringArray`](super::MutableLineStringArray).
+///
+/// Counting the geometries to ingest first, with [`from_line_strings`](Self::from_line_strings),
+/// lets the array allocate its buffers once rather than growing them geometry by geometry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineStringCapacity {
+    coord_capacity: usize,
+    geom_capacity: usize,
+}
+
+impl LineStringCapacity {
+    /// Creates a new capacity from its parts.
+    pub fn new(coord_capacity: usize, geom_capacity: usize) -> Self {
+        Self {
+            coord_capacity,
+            geom_capacity,
+        }
+    }
+
+    /// Creates a new capacity of zero.
+    pub fn new_empty() -> Self {
+        Self::default()
+    }
+
+    /// The number of coordinates.
+    pub fn coord_capacity(&self) -> usize {
+        self.coord_capacity
+    }
+
+    /// The number of geometries.
+    pub fn geom_capacity(&self) -> usize {
+        self.geom_capacity
+    }
+
+    /// Add the capacity of a line string, or of a null if `value` is `None`.
+    pub fn add_line_string<'a>(&mut self, value: Option<&impl LineStringTrait<'a>>) {
+        self.geom_capacity += 1;
+        if let Some(line_string) = value {
+            self.coord_capacity += line_string.num_points();
+        }
+    }
+
+    /// The capacity needed to hold `geoms`, where `None` is a null.
+    pub fn from_line_strings<'a>(
+        geoms: impl Iterator<Item = Option<impl LineStringTrait<'a>>>,
+    ) -> Self {
+        let mut capacity = Self::new_empty();
+        for geom in geoms {
+            capacity.add_line_string(geom.as_ref());
+        }
+        capacity
+    }
+}
+
+impl Add for LineStringCapacity {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            coord_capacity: self.coord_capacity + rhs.coord_capacity,
+            geom_capacity: self.geom_capacity + rhs.geom_capacity,
+        }
+    }
+}
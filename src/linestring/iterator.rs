@@ -82,16 +82,16 @@ impl<'a> LineStringArray {
 /// Iterator of values of a [`PointArray`]
 #[derive(Clone, Debug)]
 pub struct LineStringIterator<'a> {
-    geom: &'a LineString<'a>,
+    geom: LineString<'a>,
     index: usize,
     end: usize,
 }
 
 impl<'a> LineStringIterator<'a> {
     #[inline]
-    pub fn new(geom: &'a LineString<'a>) -> Self {
+    pub fn new(geom: &LineString<'a>) -> Self {
         Self {
-            geom,
+            geom: geom.clone(),
             index: 0,
             end: geom.num_points(),
         }
@@ -1,10 +1,12 @@
 //! Helpers for using LineString GeoArrow data
 
 pub use array::LineStringArray;
-pub use mutable::MutableLineStringArray;
-pub use scalar::LineString;
+pub use capacity::LineStringCapacity;
+pub use mutable::{MutableLineStringArray, ToGeoArrowLineString};
+pub use scalar::{LineString, OwnedLineString};
 
 mod array;
+mod capacity;
 mod iterator;
 mod mutable;
 mod scalar;
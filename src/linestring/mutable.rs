@@ -1,5 +1,10 @@
+use crate::concat::{extend_offsets, extend_validity};
 use crate::error::GeoArrowError;
+use crate::geo_traits::{LineStringTrait, PointTrait};
+use crate::linestring::LineStringCapacity;
 use crate::multipoint::MutableMultiPointArray;
+use crate::processor::{reject_curves, reject_geometries};
+use crate::trait_::MutableGeometryArray;
 use crate::GeometryArrayTrait;
 use crate::LineStringArray;
 use arrow2::array::ListArray;
@@ -7,6 +12,8 @@ use arrow2::bitmap::{Bitmap, MutableBitmap};
 use arrow2::offset::Offsets;
 use arrow2::types::Index;
 use geo::{CoordsIter, LineString};
+use geozero::error::GeozeroError;
+use geozero::{FeatureProcessor, GeomProcessor, GeozeroGeometry, PropertyProcessor};
 use std::convert::From;
 
 /// The Arrow equivalent to `Vec<Option<LineString>>`.
@@ -63,6 +70,21 @@ impl MutableLineStringArray {
         })
     }
 
+    /// Append the geometries of `other` to this array.
+    ///
+    /// Mutable arrays carry no CRS, so the CRS of `other` is dropped.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn extend_from_array(&mut self, other: &LineStringArray) -> Result<(), GeoArrowError> {
+        let len = self.geom_offsets.len_proxy();
+        let (start, end) =
+            extend_offsets(&mut self.geom_offsets, other.geom_offsets(), 0, other.len())?;
+        self.x.extend_from_slice(&other.values_x()[start..end]);
+        self.y.extend_from_slice(&other.values_y()[start..end]);
+        extend_validity(&mut self.validity, len, other.validity(), other.len());
+        Ok(())
+    }
+
     /// Extract the low-level APIs from the [`MutableLineStringArray`].
     pub fn into_inner(self) -> (Vec<f64>, Vec<f64>, Offsets<i64>, Option<MutableBitmap>) {
         (self.x, self.y, self.geom_offsets, self.validity)
@@ -82,6 +104,62 @@ impl MutableLineStringArray {
         Ok(())
     }
 
+    /// Adds a new line string to the array, or a null if `value` is `None`.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn try_push_line_string<'a>(
+        &mut self,
+        value: Option<&impl LineStringTrait<'a>>,
+    ) -> Result<(), GeoArrowError> {
+        if let Some(line_string) = value {
+            for point in line_string.points() {
+                self.x.push(point.x());
+                self.y.push(point.y());
+            }
+            self.try_push_valid()?;
+        } else {
+            self.push_null();
+        }
+        Ok(())
+    }
+
+    /// Creates a new [`MutableLineStringArray`] with the capacity counted by `capacity`.
+    pub fn with_capacity(capacity: LineStringCapacity) -> Self {
+        Self::with_capacities(capacity.coord_capacity(), capacity.geom_capacity())
+    }
+
+    /// Reserves capacity for at least `additional` more values.
+    pub fn reserve(&mut self, additional: LineStringCapacity) {
+        self.x.reserve(additional.coord_capacity());
+        self.y.reserve(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Reserves capacity for exactly `additional` more coordinates, so that ingesting a counted
+    /// batch does not over-allocate the coordinate buffers. Offsets and validity may still
+    /// reserve more than needed.
+    pub fn reserve_exact(&mut self, additional: LineStringCapacity) {
+        self.x.reserve_exact(additional.coord_capacity());
+        self.y.reserve_exact(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Shrinks the capacity of the buffers of this array to fit their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        self.x.shrink_to_fit();
+        self.y.shrink_to_fit();
+        self.geom_offsets.shrink_to_fit();
+        if let Some(validity) = &mut self.validity {
+            validity.shrink_to_fit();
+        }
+    }
+
     #[inline]
     /// Needs to be called when a valid value was extended to this array.
     /// This is a relatively low level function, prefer `try_push` when you can.
@@ -92,16 +170,20 @@ impl MutableLineStringArray {
             .checked_sub(offset)
             .ok_or(GeoArrowError::Overflow)?;
 
-        // TODO: remove unwrap
-        self.geom_offsets.try_push_usize(length).unwrap();
+        self.geom_offsets
+            .try_push_usize(length)
+            .map_err(|_| GeoArrowError::OffsetOverflow {
+                row: self.geom_offsets.len_proxy(),
+            })?;
         if let Some(validity) = &mut self.validity {
             validity.push(true)
         }
         Ok(())
     }
 
+    /// Adds a new null to the array.
     #[inline]
-    fn push_null(&mut self) {
+    pub fn push_null(&mut self) {
         self.geom_offsets.extend_constant(1);
         match &mut self.validity {
             Some(validity) => validity.push(false),
@@ -130,6 +212,28 @@ impl Default for MutableLineStringArray {
     }
 }
 
+impl MutableGeometryArray for MutableLineStringArray {
+    fn len(&self) -> usize {
+        self.geom_offsets.len_proxy()
+    }
+
+    fn validity(&self) -> Option<&MutableBitmap> {
+        self.validity.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn push_null(&mut self) {
+        MutableLineStringArray::push_null(self)
+    }
+}
+
 impl From<MutableLineStringArray> for LineStringArray {
     fn from(other: MutableLineStringArray) -> Self {
         let validity = other.validity.and_then(|x| {
@@ -237,3 +341,94 @@ impl From<MutableLineStringArray> for MutableMultiPointArray {
         Self::try_new(value.x, value.y, value.geom_offsets, value.validity).unwrap()
     }
 }
+
+/// Convert a geozero geometry, such as a geometry collection of line strings, to a GeoArrow
+/// [`LineStringArray`].
+pub trait ToGeoArrowLineString {
+    /// Convert to GeoArrow LineStringArray
+    fn to_geoarrow(&self) -> geozero::error::Result<LineStringArray>;
+
+    /// Convert to a GeoArrow MutableLineStringArray
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutableLineStringArray>;
+}
+
+impl<T: GeozeroGeometry> ToGeoArrowLineString for T {
+    fn to_geoarrow(&self) -> geozero::error::Result<LineStringArray> {
+        Ok(self.to_mutable_geoarrow()?.into())
+    }
+
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutableLineStringArray> {
+        let mut mutable_array = MutableLineStringArray::new();
+        self.process_geom(&mut mutable_array)?;
+        Ok(mutable_array)
+    }
+}
+
+/// Each line string processed is appended as one geometry.
+impl GeomProcessor for MutableLineStringArray {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> geozero::error::Result<()> {
+        self.x.push(x);
+        self.y.push(y);
+        Ok(())
+    }
+
+    fn linestring_begin(
+        &mut self,
+        _tagged: bool,
+        size: usize,
+        _idx: usize,
+    ) -> geozero::error::Result<()> {
+        self.x.reserve(size);
+        self.y.reserve(size);
+        Ok(())
+    }
+
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> geozero::error::Result<()> {
+        self.try_push_valid()
+            .map_err(|err| GeozeroError::Geometry(err.to_string()))
+    }
+
+    reject_geometries!(
+        "Only line string geometries allowed",
+        [
+            point_begin(_idx: usize),
+            multipoint_begin(_size: usize, _idx: usize),
+            polygon_begin(_tagged: bool, _size: usize, _idx: usize),
+            multilinestring_begin(_size: usize, _idx: usize),
+            multipolygon_begin(_size: usize, _idx: usize),
+        ]
+    );
+    reject_curves!("Only line string geometries allowed");
+}
+
+impl PropertyProcessor for MutableLineStringArray {}
+
+/// Features are appended as one geometry each, ignoring their properties.
+impl FeatureProcessor for MutableLineStringArray {}
+
+#[cfg(test)]
+mod test {
+    use super::ToGeoArrowLineString;
+    use crate::GeometryArrayTrait;
+    use geo::{line_string, point, Geometry, GeometryCollection};
+
+    #[test]
+    fn from_geozero() {
+        let ls0 = line_string![(x: 0., y: 1.), (x: 1., y: 2.)];
+        let ls1 = line_string![(x: 3., y: 4.), (x: 5., y: 6.), (x: 7., y: 8.)];
+        let geo = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::LineString(ls0.clone()),
+            Geometry::LineString(ls1.clone()),
+        ]));
+        let arr = geo.to_geoarrow().unwrap();
+        assert_eq!(arr.len(), 2);
+        assert_eq!(arr.value_as_geo(0), ls0);
+        assert_eq!(arr.value_as_geo(1), ls1);
+
+        let geo = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::LineString(ls0),
+            Geometry::Point(point!(x: 0., y: 1.)),
+        ]));
+        assert!(geo.to_geoarrow().is_err());
+    }
+}
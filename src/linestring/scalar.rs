@@ -16,6 +16,9 @@ pub struct LineString<'a> {
     /// Buffer of y coordinates
     pub y: &'a Buffer<f64>,
 
+    /// Buffer of m values, if the array has them
+    pub m: Option<&'a Buffer<f64>>,
+
     /// Offsets into the coordinate array where each geometry starts
     pub geom_offsets: &'a OffsetsBuffer<i64>,
 
@@ -26,7 +29,7 @@ impl<'a> LineStringTrait<'a> for LineString<'a> {
     type ItemType = Point<'a>;
     type Iter = LineStringIterator<'a>;
 
-    fn points(&'a self) -> Self::Iter {
+    fn points(&self) -> Self::Iter {
         LineStringIterator::new(self)
     }
 
@@ -35,21 +38,30 @@ impl<'a> LineStringTrait<'a> for LineString<'a> {
         end - start
     }
 
-    fn point(&'a self, i: usize) -> Option<Self::ItemType> {
+    fn point(&self, i: usize) -> Option<Self::ItemType> {
         let (start, end) = self.geom_offsets.start_end(self.geom_index);
-        if i > (end - start) {
+        if i >= end - start {
             return None;
         }
 
         let point = Point {
             x: self.x,
             y: self.y,
+            m: self.m,
             geom_index: start + i,
         };
         Some(point)
     }
 }
 
+impl<'a> LineString<'a> {
+    /// The m values (measures) of the points of this line string, if its array has m values.
+    pub fn m_values(&self) -> Option<&'a [f64]> {
+        let (start, end) = self.geom_offsets.start_end(self.geom_index);
+        self.m.map(|m| &m[start..end])
+    }
+}
+
 impl From<LineString<'_>> for geo::LineString {
     fn from(value: LineString<'_>) -> Self {
         (&value).into()
@@ -86,3 +98,94 @@ impl RTreeObject for LineString<'_> {
         AABB::from_corners(lower, upper)
     }
 }
+
+/// An owned [`LineString`], holding clones of the buffers of its array.
+#[derive(Debug, Clone)]
+pub struct OwnedLineString {
+    /// Buffer of x coordinates
+    pub x: Buffer<f64>,
+
+    /// Buffer of y coordinates
+    pub y: Buffer<f64>,
+
+    /// Buffer of m values, if the array has them
+    pub m: Option<Buffer<f64>>,
+
+    /// Offsets into the coordinate array where each geometry starts
+    pub geom_offsets: OffsetsBuffer<i64>,
+
+    pub geom_index: usize,
+}
+
+impl From<LineString<'_>> for OwnedLineString {
+    fn from(value: LineString<'_>) -> Self {
+        Self {
+            x: value.x.clone(),
+            y: value.y.clone(),
+            m: value.m.cloned(),
+            geom_offsets: value.geom_offsets.clone(),
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedLineString> for LineString<'a> {
+    fn from(value: &'a OwnedLineString) -> Self {
+        Self {
+            x: &value.x,
+            y: &value.y,
+            m: value.m.as_ref(),
+            geom_offsets: &value.geom_offsets,
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl From<OwnedLineString> for geo::LineString {
+    fn from(value: OwnedLineString) -> Self {
+        LineString::from(&value).into()
+    }
+}
+
+impl From<OwnedLineString> for geo::Geometry {
+    fn from(value: OwnedLineString) -> Self {
+        geo::Geometry::LineString(value.into())
+    }
+}
+
+impl RTreeObject for OwnedLineString {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        LineString::from(self).envelope()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::trait_::GeometryArrayTrait;
+    use crate::LineStringArray;
+    use geo::line_string;
+    use rstar::RTree;
+
+    fn first_line_string() -> OwnedLineString {
+        let arr: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 1., y: 2.)]].into();
+        arr.value(0).into()
+    }
+
+    #[test]
+    fn owned_scalars() {
+        let owned = first_line_string();
+        let borrowed = LineString::from(&owned);
+        assert_eq!(borrowed.num_points(), 2);
+        assert_eq!(
+            geo::LineString::from(owned.clone()),
+            line_string![(x: 0., y: 0.), (x: 1., y: 2.)]
+        );
+
+        let tree = RTree::bulk_load(vec![owned]);
+        let envelope = tree.root().envelope();
+        assert_eq!((envelope.lower(), envelope.upper()), ([0., 0.], [1., 2.]));
+    }
+}
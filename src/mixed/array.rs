@@ -0,0 +1,705 @@
+use crate::coerce::geometry_type_name;
+use crate::crs::impl_crs;
+use crate::enum_::Geometry;
+use crate::error::GeoArrowError;
+#[cfg(feature = "rayon")]
+use crate::parallel::MIN_ROWS_PER_TASK;
+use crate::slice::slice_validity_unchecked;
+use crate::trait_::GeometryArrayType;
+use crate::validity::check_validity_len;
+use crate::{
+    GeometryArrayTrait, LineStringArray, MultiLineStringArray, MultiPointArray, MultiPolygonArray,
+    PointArray, PolygonArray,
+};
+use arrow2::array::{Array, UnionArray};
+use arrow2::bitmap::utils::{BitmapIter, ZipValidity};
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::buffer::Buffer;
+use arrow2::datatypes::{DataType, Field, UnionMode};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// GeoArrow type ids of the children of a mixed geometry union.
+const POINT: i8 = 1;
+const LINESTRING: i8 = 2;
+const POLYGON: i8 = 3;
+const MULTIPOINT: i8 = 4;
+const MULTILINESTRING: i8 = 5;
+const MULTIPOLYGON: i8 = 6;
+
+/// A [`GeometryArrayTrait`] semantically equivalent to `Vec<Option<Geometry>>` using Arrow's
+/// in-memory representation of a dense union.
+///
+/// Each geometry is stored in the child array of its type: `type_ids` holds the
+/// [GeoArrow](https://github.com/geoarrow/geoarrow) type id of every geometry (`1` for points up
+/// to `6` for multi polygons) and `offsets` its position within that child. Null geometries are
+/// stored as nulls of the point child.
+#[derive(Debug, Clone)]
+pub struct MixedGeometryArray {
+    /// The GeoArrow type id of each geometry
+    type_ids: Buffer<i8>,
+
+    /// The position of each geometry in the child array of its type
+    offsets: Buffer<i32>,
+
+    points: PointArray,
+    line_strings: LineStringArray,
+    polygons: PolygonArray,
+    multi_points: MultiPointArray,
+    multi_line_strings: MultiLineStringArray,
+    multi_polygons: MultiPolygonArray,
+
+    /// Validity bitmap, derived from the validity of the children
+    validity: Option<Bitmap>,
+
+    /// Coordinate reference system, shared by all children
+    crs: Option<Arc<str>>,
+}
+
+impl MixedGeometryArray {
+    /// Create a new MixedGeometryArray from parts
+    /// # Implementation
+    /// This function is `O(N)`, as the validity of each geometry is read from its child.
+    /// # Panics
+    /// Panics if the type ids and offsets have different lengths, or refer to missing geometries.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        type_ids: Buffer<i8>,
+        offsets: Buffer<i32>,
+        points: PointArray,
+        line_strings: LineStringArray,
+        polygons: PolygonArray,
+        multi_points: MultiPointArray,
+        multi_line_strings: MultiLineStringArray,
+        multi_polygons: MultiPolygonArray,
+    ) -> Self {
+        Self::try_new(
+            type_ids,
+            offsets,
+            points,
+            line_strings,
+            polygons,
+            multi_points,
+            multi_line_strings,
+            multi_polygons,
+        )
+        .unwrap()
+    }
+
+    /// Create a new MixedGeometryArray from parts
+    /// # Implementation
+    /// This function is `O(N)`, as the validity of each geometry is read from its child.
+    /// # Errors
+    /// Errors if the type ids and offsets have different lengths, or refer to missing geometries.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        type_ids: Buffer<i8>,
+        offsets: Buffer<i32>,
+        points: PointArray,
+        line_strings: LineStringArray,
+        polygons: PolygonArray,
+        multi_points: MultiPointArray,
+        multi_line_strings: MultiLineStringArray,
+        multi_polygons: MultiPolygonArray,
+    ) -> Result<Self, GeoArrowError> {
+        if type_ids.len() != offsets.len() {
+            return Err(GeoArrowError::General(
+                "type ids and offsets must have the same length".to_string(),
+            ));
+        }
+
+        let mut array = Self {
+            type_ids,
+            offsets,
+            points,
+            line_strings,
+            polygons,
+            multi_points,
+            multi_line_strings,
+            multi_polygons,
+            validity: None,
+            crs: None,
+        };
+
+        let mut validity = MutableBitmap::with_capacity(array.type_ids.len());
+        for (&type_id, &offset) in array.type_ids.iter().zip(array.offsets.iter()) {
+            let offset = usize::try_from(offset)
+                .map_err(|_| GeoArrowError::General("offsets must not be negative".to_string()))?;
+            let child_len = array.child_len(type_id)?;
+            if offset >= child_len {
+                return Err(GeoArrowError::General(format!(
+                    "offset {offset} out of bounds for child {type_id} of length {child_len}"
+                )));
+            }
+            validity.push(array.child_is_valid(type_id, offset));
+        }
+        array.validity = (validity.unset_bits() > 0).then(|| validity.into());
+        Ok(array)
+    }
+
+    fn child_len(&self, type_id: i8) -> Result<usize, GeoArrowError> {
+        Ok(match type_id {
+            POINT => self.points.len(),
+            LINESTRING => self.line_strings.len(),
+            POLYGON => self.polygons.len(),
+            MULTIPOINT => self.multi_points.len(),
+            MULTILINESTRING => self.multi_line_strings.len(),
+            MULTIPOLYGON => self.multi_polygons.len(),
+            _ => {
+                return Err(GeoArrowError::General(format!(
+                    "unknown geometry type id {type_id}"
+                )))
+            }
+        })
+    }
+
+    fn child_is_valid(&self, type_id: i8, offset: usize) -> bool {
+        match type_id {
+            POINT => self.points.is_valid(offset),
+            LINESTRING => self.line_strings.is_valid(offset),
+            POLYGON => self.polygons.is_valid(offset),
+            MULTIPOINT => self.multi_points.is_valid(offset),
+            MULTILINESTRING => self.multi_line_strings.is_valid(offset),
+            MULTIPOLYGON => self.multi_polygons.is_valid(offset),
+            _ => unreachable!(),
+        }
+    }
+
+    /// The GeoArrow type id of each geometry.
+    pub fn type_ids(&self) -> &Buffer<i8> {
+        &self.type_ids
+    }
+
+    /// The position of each geometry in the child array of its type.
+    pub fn offsets(&self) -> &Buffer<i32> {
+        &self.offsets
+    }
+
+    /// The child array holding the point geometries, and null geometries.
+    pub fn points(&self) -> &PointArray {
+        &self.points
+    }
+
+    /// The child array holding the line string geometries.
+    pub fn line_strings(&self) -> &LineStringArray {
+        &self.line_strings
+    }
+
+    /// The child array holding the polygon geometries.
+    pub fn polygons(&self) -> &PolygonArray {
+        &self.polygons
+    }
+
+    /// The child array holding the multi point geometries.
+    pub fn multi_points(&self) -> &MultiPointArray {
+        &self.multi_points
+    }
+
+    /// The child array holding the multi line string geometries.
+    pub fn multi_line_strings(&self) -> &MultiLineStringArray {
+        &self.multi_line_strings
+    }
+
+    /// The child array holding the multi polygon geometries.
+    pub fn multi_polygons(&self) -> &MultiPolygonArray {
+        &self.multi_polygons
+    }
+
+    /// A copy of this array with each child replaced by the output of a kernel that keeps the
+    /// length and validity of its input.
+    pub(crate) fn map_children(
+        &self,
+        points: impl FnOnce(&PointArray) -> PointArray,
+        line_strings: impl FnOnce(&LineStringArray) -> LineStringArray,
+        polygons: impl FnOnce(&PolygonArray) -> PolygonArray,
+        multi_points: impl FnOnce(&MultiPointArray) -> MultiPointArray,
+        multi_line_strings: impl FnOnce(&MultiLineStringArray) -> MultiLineStringArray,
+        multi_polygons: impl FnOnce(&MultiPolygonArray) -> MultiPolygonArray,
+    ) -> Self {
+        Self {
+            type_ids: self.type_ids.clone(),
+            offsets: self.offsets.clone(),
+            points: points(&self.points),
+            line_strings: line_strings(&self.line_strings),
+            polygons: polygons(&self.polygons),
+            multi_points: multi_points(&self.multi_points),
+            multi_line_strings: multi_line_strings(&self.multi_line_strings),
+            multi_polygons: multi_polygons(&self.multi_polygons),
+            validity: self.validity.clone(),
+            crs: self.crs.clone(),
+        }
+    }
+}
+
+impl_crs!(MixedGeometryArray);
+
+impl<'a> GeometryArrayTrait<'a> for MixedGeometryArray {
+    type Scalar = Geometry<'a>;
+    type ScalarGeo = geo::Geometry;
+    type ArrowArray = UnionArray;
+
+    fn value(&'a self, i: usize) -> Self::Scalar {
+        let offset = self.offsets[i] as usize;
+        match self.type_ids[i] {
+            POINT => Geometry::Point(self.points.value(offset)),
+            LINESTRING => Geometry::LineString(self.line_strings.value(offset)),
+            POLYGON => Geometry::Polygon(self.polygons.value(offset)),
+            MULTIPOINT => Geometry::MultiPoint(self.multi_points.value(offset)),
+            MULTILINESTRING => Geometry::MultiLineString(self.multi_line_strings.value(offset)),
+            MULTIPOLYGON => Geometry::MultiPolygon(self.multi_polygons.value(offset)),
+            _ => unreachable!(),
+        }
+    }
+
+    fn into_arrow(self) -> Self::ArrowArray {
+        let children: Vec<(&str, Box<dyn Array>)> = vec![
+            ("Point", self.points.into_arrow().boxed()),
+            ("LineString", self.line_strings.into_arrow().boxed()),
+            ("Polygon", self.polygons.into_arrow().boxed()),
+            ("MultiPoint", self.multi_points.into_arrow().boxed()),
+            (
+                "MultiLineString",
+                self.multi_line_strings.into_arrow().boxed(),
+            ),
+            ("MultiPolygon", self.multi_polygons.into_arrow().boxed()),
+        ];
+        let fields = children
+            .iter()
+            .map(|(name, child)| Field::new(*name, child.data_type().clone(), true))
+            .collect();
+        let ids = vec![
+            POINT as i32,
+            LINESTRING as i32,
+            POLYGON as i32,
+            MULTIPOINT as i32,
+            MULTILINESTRING as i32,
+            MULTIPOLYGON as i32,
+        ];
+        let data_type = DataType::Union(fields, Some(ids), UnionMode::Dense);
+        let children = children.into_iter().map(|(_, child)| child).collect();
+
+        UnionArray::new(data_type, self.type_ids, children, Some(self.offsets))
+    }
+
+    fn geometry_type(&self) -> GeometryArrayType {
+        GeometryArrayType::Mixed
+    }
+
+    /// Returns the number of geometries in this array
+    #[inline]
+    fn len(&self) -> usize {
+        self.type_ids.len()
+    }
+
+    /// Returns the optional validity.
+    #[inline]
+    fn validity(&self) -> Option<&Bitmap> {
+        self.validity.as_ref()
+    }
+
+    /// Slices this [`MixedGeometryArray`] in place.
+    /// # Implementation
+    /// This operation is `O(1)`. The children are not sliced.
+    /// # Panic
+    /// This function panics iff `offset + length > self.len()`.
+    #[inline]
+    fn slice(&mut self, offset: usize, length: usize) {
+        assert!(
+            offset + length <= self.len(),
+            "offset + length may not exceed length of array"
+        );
+        unsafe { self.slice_unchecked(offset, length) };
+    }
+
+    /// Slices this [`MixedGeometryArray`] in place.
+    /// # Implementation
+    /// This operation is `O(1)`. The children are not sliced.
+    /// # Safety
+    /// The caller must ensure that `offset + length <= self.len()`.
+    #[inline]
+    unsafe fn slice_unchecked(&mut self, offset: usize, length: usize) {
+        slice_validity_unchecked(&mut self.validity, offset, length);
+        self.type_ids.slice_unchecked(offset, length);
+        self.offsets.slice_unchecked(offset, length);
+    }
+
+    /// The validity of a mixed array is derived from its children, so the new validity is also
+    /// written to the child slot of each geometry.
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        check_validity_len(validity.as_ref(), self.len());
+        let child_validity = |type_id: i8, child: Option<&Bitmap>, child_len: usize| {
+            let mut bitmap = match child {
+                Some(child) => child.iter().collect(),
+                None => MutableBitmap::from_len_set(child_len),
+            };
+            let rows = self.type_ids.iter().zip(self.offsets.iter()).enumerate();
+            for (row, (_, offset)) in rows.filter(|(_, (id, _))| **id == type_id) {
+                let is_valid = validity.as_ref().map_or(true, |v| v.get_bit(row));
+                bitmap.set(*offset as usize, is_valid);
+            }
+            let bitmap: Bitmap = bitmap.into();
+            (bitmap.unset_bits() > 0).then_some(bitmap)
+        };
+
+        Self {
+            points: self.points.with_validity(child_validity(
+                POINT,
+                self.points.validity(),
+                self.points.len(),
+            )),
+            line_strings: self.line_strings.with_validity(child_validity(
+                LINESTRING,
+                self.line_strings.validity(),
+                self.line_strings.len(),
+            )),
+            polygons: self.polygons.with_validity(child_validity(
+                POLYGON,
+                self.polygons.validity(),
+                self.polygons.len(),
+            )),
+            multi_points: self.multi_points.with_validity(child_validity(
+                MULTIPOINT,
+                self.multi_points.validity(),
+                self.multi_points.len(),
+            )),
+            multi_line_strings: self.multi_line_strings.with_validity(child_validity(
+                MULTILINESTRING,
+                self.multi_line_strings.validity(),
+                self.multi_line_strings.len(),
+            )),
+            multi_polygons: self.multi_polygons.with_validity(child_validity(
+                MULTIPOLYGON,
+                self.multi_polygons.validity(),
+                self.multi_polygons.len(),
+            )),
+            validity: validity.filter(|validity| validity.unset_bits() > 0),
+            ..self.clone()
+        }
+    }
+
+    fn to_boxed(&self) -> Box<Self> {
+        Box::new(self.clone())
+    }
+}
+
+// Implement geometry accessors
+impl MixedGeometryArray {
+    /// Iterator over geo Geometry objects, not looking at validity
+    pub fn iter_geo_values(&self) -> impl Iterator<Item = geo::Geometry> + '_ {
+        (0..self.len()).map(|i| self.value_as_geo(i))
+    }
+
+    /// Iterator over geo Geometry objects, taking into account validity
+    pub fn iter_geo(
+        &self,
+    ) -> ZipValidity<geo::Geometry, impl Iterator<Item = geo::Geometry> + '_, BitmapIter> {
+        ZipValidity::new_with_validity(self.iter_geo_values(), self.validity())
+    }
+
+    /// Parallel iterator over the scalars of this array, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = Option<Geometry<'_>>> {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get(i))
+    }
+
+    /// Parallel iterator over geo Geometry objects, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_geo(&self) -> impl IndexedParallelIterator<Item = Option<geo::Geometry>> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get_as_geo(i))
+    }
+}
+
+impl MixedGeometryArray {
+    /// Iterate over the coordinates of every non-null geometry, in order.
+    pub fn coords(&self) -> impl Iterator<Item = geo::Coord> + '_ {
+        (0..self.len()).filter(|i| self.is_valid(*i)).flat_map(|i| {
+            let (x, y, range) = self.child_coords(i);
+            range.map(move |j| geo::coord! { x: x[j], y: y[j] })
+        })
+    }
+
+    /// The number of coordinates of every non-null geometry.
+    pub fn num_coords(&self) -> usize {
+        (0..self.len())
+            .filter(|i| self.is_valid(*i))
+            .map(|i| self.child_coords(i).2.len())
+            .sum()
+    }
+
+    /// The coordinate buffers of the child holding geometry `i`, and the range of them holding
+    /// its coordinates.
+    fn child_coords(&self, i: usize) -> (&Buffer<f64>, &Buffer<f64>, Range<usize>) {
+        let offset = self.offsets[i] as usize;
+        let geoms = offset..offset + 1;
+        match self.type_ids[i] {
+            POINT => (
+                self.points.values_x(),
+                self.points.values_y(),
+                self.points.coord_range(geoms),
+            ),
+            LINESTRING => (
+                self.line_strings.values_x(),
+                self.line_strings.values_y(),
+                self.line_strings.coord_range(geoms),
+            ),
+            POLYGON => (
+                self.polygons.values_x(),
+                self.polygons.values_y(),
+                self.polygons.coord_range(geoms),
+            ),
+            MULTIPOINT => (
+                self.multi_points.values_x(),
+                self.multi_points.values_y(),
+                self.multi_points.coord_range(geoms),
+            ),
+            MULTILINESTRING => (
+                self.multi_line_strings.values_x(),
+                self.multi_line_strings.values_y(),
+                self.multi_line_strings.coord_range(geoms),
+            ),
+            MULTIPOLYGON => (
+                self.multi_polygons.values_x(),
+                self.multi_polygons.values_y(),
+                self.multi_polygons.coord_range(geoms),
+            ),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl TryFrom<&UnionArray> for MixedGeometryArray {
+    type Error = GeoArrowError;
+
+    /// Read a dense or sparse union. The type of each child is given by its type id, or by its
+    /// position (starting at `1` for points) if the union has no type ids.
+    fn try_from(value: &UnionArray) -> Result<Self, Self::Error> {
+        let ids = match value.data_type() {
+            DataType::Union(_, ids, _) => ids.clone(),
+            dt => {
+                return Err(GeoArrowError::Downcast {
+                    expected: "UnionArray",
+                    found: dt.clone(),
+                })
+            }
+        };
+        let child_type_ids: Vec<i8> = (0..value.fields().len())
+            .map(|position| match &ids {
+                Some(ids) => ids[position] as i8,
+                None => position as i8 + 1,
+            })
+            .collect();
+
+        let mut points = PointArray::from(Vec::<geo::Point>::new());
+        let mut line_strings = LineStringArray::from(Vec::<geo::LineString>::new());
+        let mut polygons = PolygonArray::from(Vec::<geo::Polygon>::new());
+        let mut multi_points = MultiPointArray::from(Vec::<geo::MultiPoint>::new());
+        let mut multi_line_strings = MultiLineStringArray::from(Vec::<geo::MultiLineString>::new());
+        let mut multi_polygons = MultiPolygonArray::from(Vec::<geo::MultiPolygon>::new());
+        for (child, &type_id) in value.fields().iter().zip(child_type_ids.iter()) {
+            let child = child.clone();
+            match type_id {
+                POINT => points = child.try_into()?,
+                LINESTRING => line_strings = child.try_into()?,
+                POLYGON => polygons = child.try_into()?,
+                MULTIPOINT => multi_points = child.try_into()?,
+                MULTILINESTRING => multi_line_strings = child.try_into()?,
+                MULTIPOLYGON => multi_polygons = child.try_into()?,
+                _ => {
+                    return Err(GeoArrowError::NotYetImplemented(format!(
+                        "union child with geometry type id {type_id}"
+                    )))
+                }
+            }
+        }
+
+        let mut type_ids = Vec::with_capacity(value.len());
+        let mut offsets = Vec::with_capacity(value.len());
+        for i in 0..value.len() {
+            let (position, offset) = value.index(i);
+            type_ids.push(child_type_ids[position]);
+            offsets.push(offset as i32);
+        }
+
+        Self::try_new(
+            type_ids.into(),
+            offsets.into(),
+            points,
+            line_strings,
+            polygons,
+            multi_points,
+            multi_line_strings,
+            multi_polygons,
+        )
+    }
+}
+
+impl TryFrom<Box<dyn Array>> for MixedGeometryArray {
+    type Error = GeoArrowError;
+
+    fn try_from(value: Box<dyn Array>) -> Result<Self, Self::Error> {
+        let arr =
+            value
+                .as_any()
+                .downcast_ref::<UnionArray>()
+                .ok_or_else(|| GeoArrowError::Downcast {
+                    expected: "UnionArray",
+                    found: value.data_type().clone(),
+                })?;
+        arr.try_into()
+    }
+}
+
+impl TryFrom<Vec<Option<geo::Geometry>>> for MixedGeometryArray {
+    type Error = GeoArrowError;
+
+    /// Lines are stored as line strings, and rects and triangles as polygons.
+    /// # Errors
+    /// Errors if any geometry is a geometry collection.
+    fn try_from(other: Vec<Option<geo::Geometry>>) -> Result<Self, Self::Error> {
+        let mut type_ids = Vec::with_capacity(other.len());
+        let mut offsets = Vec::with_capacity(other.len());
+        let mut points = vec![];
+        let mut line_strings = vec![];
+        let mut polygons = vec![];
+        let mut multi_points = vec![];
+        let mut multi_line_strings = vec![];
+        let mut multi_polygons = vec![];
+
+        macro_rules! push {
+            ($type_id:expr, $child:ident, $geom:expr) => {{
+                type_ids.push($type_id);
+                offsets.push($child.len() as i32);
+                $child.push($geom);
+            }};
+        }
+
+        for (row, maybe_geom) in other.into_iter().enumerate() {
+            match maybe_geom {
+                None => push!(POINT, points, None),
+                Some(geo::Geometry::Point(geom)) => push!(POINT, points, Some(geom)),
+                Some(geo::Geometry::Line(geom)) => {
+                    push!(LINESTRING, line_strings, Some(geom.into()))
+                }
+                Some(geo::Geometry::LineString(geom)) => {
+                    push!(LINESTRING, line_strings, Some(geom))
+                }
+                Some(geo::Geometry::Polygon(geom)) => push!(POLYGON, polygons, Some(geom)),
+                Some(geo::Geometry::Rect(geom)) => {
+                    push!(POLYGON, polygons, Some(geom.to_polygon()))
+                }
+                Some(geo::Geometry::Triangle(geom)) => {
+                    push!(POLYGON, polygons, Some(geom.to_polygon()))
+                }
+                Some(geo::Geometry::MultiPoint(geom)) => {
+                    push!(MULTIPOINT, multi_points, Some(geom))
+                }
+                Some(geo::Geometry::MultiLineString(geom)) => {
+                    push!(MULTILINESTRING, multi_line_strings, Some(geom))
+                }
+                Some(geo::Geometry::MultiPolygon(geom)) => {
+                    push!(MULTIPOLYGON, multi_polygons, Some(geom))
+                }
+                Some(geom) => {
+                    return Err(GeoArrowError::UnexpectedGeometryType {
+                        row,
+                        expected: "non-collection geometry",
+                        found: geometry_type_name(&geom),
+                    })
+                }
+            }
+        }
+
+        Self::try_new(
+            type_ids.into(),
+            offsets.into(),
+            points.into(),
+            line_strings.into(),
+            polygons.into(),
+            multi_points.into(),
+            multi_line_strings.into(),
+            multi_polygons.into(),
+        )
+    }
+}
+
+impl TryFrom<Vec<geo::Geometry>> for MixedGeometryArray {
+    type Error = GeoArrowError;
+
+    fn try_from(other: Vec<geo::Geometry>) -> Result<Self, Self::Error> {
+        other.into_iter().map(Some).collect::<Vec<_>>().try_into()
+    }
+}
+
+impl From<MixedGeometryArray> for Vec<Option<geo::Geometry>> {
+    fn from(value: MixedGeometryArray) -> Self {
+        value.iter_geo().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point, polygon};
+
+    fn geoms() -> Vec<Option<geo::Geometry>> {
+        vec![
+            Some(point!(x: 0., y: 1.).into()),
+            None,
+            Some(geo::MultiPoint(vec![point!(x: 2., y: 3.), point!(x: 4., y: 5.)]).into()),
+            Some(line_string![(x: 0., y: 0.), (x: 1., y: 1.)].into()),
+            Some(polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)].into()),
+            Some(point!(x: 6., y: 7.).into()),
+        ]
+    }
+
+    #[test]
+    fn geo_roundtrip() {
+        let arr = MixedGeometryArray::try_from(geoms()).unwrap();
+        assert_eq!(arr.len(), 6);
+        assert!(arr.is_null(1));
+        assert_eq!(arr.type_ids().as_slice(), &[1, 1, 4, 2, 3, 1]);
+        assert_eq!(arr.offsets().as_slice(), &[0, 1, 0, 0, 0, 2]);
+        assert!(matches!(arr.value(2), Geometry::MultiPoint(_)));
+
+        let output: Vec<Option<geo::Geometry>> = arr.into();
+        assert_eq!(output, geoms());
+
+        let collection = geo::GeometryCollection(vec![point!(x: 0., y: 0.).into()]);
+        assert!(matches!(
+            MixedGeometryArray::try_from(vec![geo::Geometry::GeometryCollection(collection)]),
+            Err(GeoArrowError::UnexpectedGeometryType { row: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn arrow_roundtrip() {
+        let arr = MixedGeometryArray::try_from(geoms()).unwrap();
+        let union = arr.into_arrow();
+        assert_eq!(union.len(), 6);
+        assert!(matches!(
+            union.data_type(),
+            DataType::Union(fields, Some(_), UnionMode::Dense) if fields.len() == 6
+        ));
+
+        let arr = MixedGeometryArray::try_from(&union).unwrap();
+        assert!(arr.is_null(1));
+        assert_eq!(arr.iter_geo().collect::<Vec<_>>(), geoms());
+    }
+
+    #[test]
+    fn slice() {
+        let mut arr = MixedGeometryArray::try_from(geoms()).unwrap();
+        arr.slice(2, 3);
+        assert_eq!(arr.len(), 3);
+        assert!(arr.validity().is_none());
+        assert_eq!(arr.get_as_geo(1), geoms()[3]);
+    }
+}
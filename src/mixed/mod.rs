@@ -0,0 +1,8 @@
+//! Helpers for using GeoArrow data holding geometries of different types in one array.
+//!
+//! Values of a [`MixedGeometryArray`] are [`Geometry`](crate::enum_::Geometry) scalars, tagged
+//! with their type.
+
+pub use array::MixedGeometryArray;
+
+mod array;
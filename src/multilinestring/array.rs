@@ -1,5 +1,13 @@
+use crate::concat::concat_crs;
+use crate::coords::{concat_m, coords_have_m, impl_coords, values_range};
+use crate::crs::impl_crs;
 use crate::error::GeoArrowError;
+use crate::offsets::widen_list_offsets;
+#[cfg(feature = "rayon")]
+use crate::parallel::MIN_ROWS_PER_TASK;
 use crate::slice::slice_validity_unchecked;
+use crate::trait_::{Dimension, GeometryArrayType};
+use crate::validity::check_validity_len;
 use crate::{GeometryArrayTrait, PolygonArray};
 use arrow2::array::{Array, ListArray, PrimitiveArray, StructArray};
 use arrow2::bitmap::utils::{BitmapIter, ZipValidity};
@@ -7,7 +15,10 @@ use arrow2::bitmap::Bitmap;
 use arrow2::buffer::Buffer;
 use arrow2::offset::OffsetsBuffer;
 use geozero::{GeomProcessor, GeozeroGeometry};
-use rstar::RTree;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::ops::Range;
+use std::sync::Arc;
 
 use super::MutableMultiLineStringArray;
 
@@ -21,6 +32,9 @@ pub struct MultiLineStringArray {
     /// Buffer of y coordinates
     y: Buffer<f64>,
 
+    /// Buffer of m values (measures), one per coordinate, if any
+    m: Option<Buffer<f64>>,
+
     /// Offsets into the ring array where each geometry starts
     geom_offsets: OffsetsBuffer<i64>,
 
@@ -29,6 +43,9 @@ pub struct MultiLineStringArray {
 
     /// Validity bitmap
     validity: Option<Bitmap>,
+
+    /// Coordinate reference system
+    crs: Option<Arc<str>>,
 }
 
 pub(super) fn check(
@@ -67,9 +84,11 @@ impl MultiLineStringArray {
         Self {
             x,
             y,
+            m: None,
             geom_offsets,
             ring_offsets,
             validity,
+            crs: None,
         }
     }
 
@@ -87,13 +106,61 @@ impl MultiLineStringArray {
         Ok(Self {
             x,
             y,
+            m: None,
             geom_offsets,
             ring_offsets,
             validity,
+            crs: None,
         })
     }
+
+    /// Offsets into the line string offsets where each geometry starts.
+    #[inline]
+    pub fn geom_offsets(&self) -> &OffsetsBuffer<i64> {
+        &self.geom_offsets
+    }
+
+    /// Offsets into the coordinate buffers where each line string starts.
+    #[inline]
+    pub fn ring_offsets(&self) -> &OffsetsBuffer<i64> {
+        &self.ring_offsets
+    }
+
+    /// Concatenate `arrays` into one array, copying their coordinates and rebuilding offsets.
+    ///
+    /// Arrays without a CRS are assumed to be in the CRS of the others. m values are kept if
+    /// every array has them.
+    /// # Errors
+    /// Errors if two arrays have different CRSes, or if the offsets of the result would overflow.
+    pub fn concat(arrays: &[&MultiLineStringArray]) -> Result<Self, GeoArrowError> {
+        let crs = concat_crs(arrays.iter().map(|array| array.crs()))?;
+        let mut mutable = MutableMultiLineStringArray::new();
+        for array in arrays {
+            mutable.extend_from_array(array)?;
+        }
+        let array = Self::from(mutable).with_crs(crs);
+        let m = concat_m(arrays.iter().map(|array| {
+            let range = array.coord_range(0..array.len());
+            array.values_m().map(|m| &m[range])
+        }));
+        match m {
+            Some(m) => array.with_m(m),
+            None => Ok(array),
+        }
+    }
 }
 
+impl_crs!(MultiLineStringArray);
+
+impl MultiLineStringArray {
+    /// The range of the coordinate buffers holding the coordinates of the geometries `geoms`.
+    pub(crate) fn coord_range(&self, geoms: Range<usize>) -> Range<usize> {
+        values_range(&self.ring_offsets, values_range(&self.geom_offsets, geoms))
+    }
+}
+
+impl_coords!(MultiLineStringArray);
+
 impl<'a> GeometryArrayTrait<'a> for MultiLineStringArray {
     type Scalar = crate::MultiLineString<'a>;
     type ScalarGeo = geo::MultiLineString;
@@ -103,6 +170,7 @@ impl<'a> GeometryArrayTrait<'a> for MultiLineStringArray {
         crate::MultiLineString {
             x: &self.x,
             y: &self.y,
+            m: self.m.as_ref(),
             geom_offsets: &self.geom_offsets,
             ring_offsets: &self.ring_offsets,
             geom_index: i,
@@ -114,11 +182,12 @@ impl<'a> GeometryArrayTrait<'a> for MultiLineStringArray {
         polygon_array.into_arrow()
     }
 
-    /// Build a spatial index containing this array's geometries
-    fn rstar_tree(&'a self) -> RTree<Self::Scalar> {
-        let mut tree = RTree::new();
-        self.iter().flatten().for_each(|geom| tree.insert(geom));
-        tree
+    fn geometry_type(&self) -> GeometryArrayType {
+        GeometryArrayType::MultiLineString
+    }
+
+    fn dimension(&self) -> Dimension {
+        Dimension::new(false, self.m.is_some())
     }
 
     /// Returns the number of geometries in this array
@@ -167,6 +236,14 @@ impl<'a> GeometryArrayTrait<'a> for MultiLineStringArray {
         self.geom_offsets.slice_unchecked(offset, length + 1);
     }
 
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        check_validity_len(validity.as_ref(), self.len());
+        Self {
+            validity,
+            ..self.clone()
+        }
+    }
+
     fn to_boxed(&self) -> Box<Self> {
         Box::new(self.clone())
     }
@@ -190,6 +267,28 @@ impl MultiLineStringArray {
         ZipValidity::new_with_validity(self.iter_geo_values(), self.validity())
     }
 
+    /// Parallel iterator over the scalars of this array, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = Option<crate::MultiLineString<'_>>> {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get(i))
+    }
+
+    /// Parallel iterator over geo Geometry objects, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_geo(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = Option<geo::MultiLineString>> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get_as_geo(i))
+    }
+
     // GEOS from not implemented for MultiLineString I suppose
     //
     // /// Returns the value at slot `i` as a GEOS geometry.
@@ -252,21 +351,46 @@ impl TryFrom<ListArray<i64>> for MultiLineStringArray {
             .downcast_ref::<PrimitiveArray<f64>>()
             .unwrap();
 
-        Ok(Self::new(
+        let array = Self::new(
             x_array_values.values().clone(),
             y_array_values.values().clone(),
             geom_offsets.clone(),
             ring_offsets.clone(),
             validity.cloned(),
-        ))
+        );
+        if !coords_have_m(coords_array)? {
+            return Ok(array);
+        }
+        let m_array_values = coords_array.values()[2]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .unwrap();
+        array.with_m(m_array_values.values().clone())
+    }
+}
+
+impl TryFrom<ListArray<i32>> for MultiLineStringArray {
+    type Error = GeoArrowError;
+
+    /// Read a list array with 32-bit offsets, widening them to 64 bits.
+    fn try_from(value: ListArray<i32>) -> Result<Self, Self::Error> {
+        value.boxed().try_into()
     }
 }
 
 impl TryFrom<Box<dyn Array>> for MultiLineStringArray {
     type Error = GeoArrowError;
 
+    /// Both `List` and `LargeList` arrays are accepted.
     fn try_from(value: Box<dyn Array>) -> Result<Self, Self::Error> {
-        let arr = value.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+        let value = widen_list_offsets(value.as_ref());
+        let arr = value
+            .as_any()
+            .downcast_ref::<ListArray<i64>>()
+            .ok_or_else(|| GeoArrowError::Downcast {
+                expected: "ListArray<i64>",
+                found: value.data_type().clone(),
+            })?;
         arr.clone().try_into()
     }
 }
@@ -296,6 +420,8 @@ impl From<MultiLineStringArray> for PolygonArray {
             value.ring_offsets,
             value.validity,
         )
+        .with_crs(value.crs.as_deref())
+        .with_optional_m(value.m)
     }
 }
 
@@ -391,7 +517,7 @@ mod test {
     #[test]
     fn geozero_process_geom() -> geozero::error::Result<()> {
         let arr: MultiLineStringArray = vec![ml0(), ml1()].into();
-        let wkt = arr.to_wkt()?;
+        let wkt = ToWkt::to_wkt(&arr)?;
         let expected = "GEOMETRYCOLLECTION(MULTILINESTRING((-111 45,-111 41,-104 41,-104 45)),MULTILINESTRING((-111 45,-111 41,-104 41,-104 45),(-110 44,-110 42,-105 42,-105 44)))";
         assert_eq!(wkt, expected);
         Ok(())
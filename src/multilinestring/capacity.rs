@@ -0,0 +1,83 @@
+//! Capacities of the buffers of a
+//! [`MutableMultiLineStringArray`](super::MutableMultiLineStringArray).
+
+use crate::geo_traits::{LineStringTrait, MultiLineStringTrait};
+use std::ops::Add;
+
+/// The number of coordinates, line strings and geometries to allocate in a
+/// [`MutableMultiLineStringArray`](super::MutableMultiLineStringArray).
+///
+/// Counting the geometries to ingest first, with
+/// [`from_multi_line_strings`](Self::from_multi_line_strings),
+/// lets the array allocate its buffers once rather than growing them geometry by geometry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MultiLineStringCapacity {
+    coord_capacity: usize,
+    ring_capacity: usize,
+    geom_capacity: usize,
+}
+
+impl MultiLineStringCapacity {
+    /// Creates a new capacity from its parts.
+    pub fn new(coord_capacity: usize, ring_capacity: usize, geom_capacity: usize) -> Self {
+        Self {
+            coord_capacity,
+            ring_capacity,
+            geom_capacity,
+        }
+    }
+
+    /// Creates a new capacity of zero.
+    pub fn new_empty() -> Self {
+        Self::default()
+    }
+
+    /// The number of coordinates.
+    pub fn coord_capacity(&self) -> usize {
+        self.coord_capacity
+    }
+
+    /// The number of line strings.
+    pub fn ring_capacity(&self) -> usize {
+        self.ring_capacity
+    }
+
+    /// The number of geometries.
+    pub fn geom_capacity(&self) -> usize {
+        self.geom_capacity
+    }
+
+    /// Add the capacity of a multi line string, or of a null if `value` is `None`.
+    pub fn add_multi_line_string<'a>(&mut self, value: Option<&impl MultiLineStringTrait<'a>>) {
+        self.geom_capacity += 1;
+        if let Some(multi_line_string) = value {
+            self.ring_capacity += multi_line_string.num_lines();
+            for line in multi_line_string.lines() {
+                self.coord_capacity += line.num_points();
+            }
+        }
+    }
+
+    /// The capacity needed to hold `geoms`, where `None` is a null.
+    pub fn from_multi_line_strings<'a>(
+        geoms: impl Iterator<Item = Option<impl MultiLineStringTrait<'a>>>,
+    ) -> Self {
+        let mut capacity = Self::new_empty();
+        for geom in geoms {
+            capacity.add_multi_line_string(geom.as_ref());
+        }
+        capacity
+    }
+}
+
+impl Add for MultiLineStringCapacity {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            coord_capacity: self.coord_capacity + rhs.coord_capacity,
+            ring_capacity: self.ring_capacity + rhs.ring_capacity,
+            geom_capacity: self.geom_capacity + rhs.geom_capacity,
+        }
+    }
+}
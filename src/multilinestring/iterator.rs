@@ -83,16 +83,16 @@ impl<'a> MultiLineStringArray {
 /// Iterator of values of a [`PointArray`]
 #[derive(Clone, Debug)]
 pub struct MultiLineStringIterator<'a> {
-    geom: &'a MultiLineString<'a>,
+    geom: MultiLineString<'a>,
     index: usize,
     end: usize,
 }
 
 impl<'a> MultiLineStringIterator<'a> {
     #[inline]
-    pub fn new(geom: &'a MultiLineString<'a>) -> Self {
+    pub fn new(geom: &MultiLineString<'a>) -> Self {
         Self {
-            geom,
+            geom: geom.clone(),
             index: 0,
             end: geom.num_lines(),
         }
@@ -1,8 +1,10 @@
 pub use array::MultiLineStringArray;
-pub use mutable::MutableMultiLineStringArray;
-pub use scalar::MultiLineString;
+pub use capacity::MultiLineStringCapacity;
+pub use mutable::{MutableMultiLineStringArray, ToGeoArrowMultiLineString};
+pub use scalar::{MultiLineString, OwnedMultiLineString};
 
 mod array;
+mod capacity;
 mod iterator;
 mod mutable;
 mod scalar;
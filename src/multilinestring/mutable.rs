@@ -1,3 +1,4 @@
+use crate::trait_::MutableGeometryArray;
 use crate::GeometryArrayTrait;
 use arrow2::array::ListArray;
 use arrow2::bitmap::{Bitmap, MutableBitmap};
@@ -5,9 +6,14 @@ use arrow2::offset::{Offsets, OffsetsBuffer};
 use arrow2::types::Index;
 use geo::MultiLineString;
 
+use crate::concat::{extend_offsets, extend_validity, push_validity, try_push_end};
 use crate::error::GeoArrowError;
+use crate::geo_traits::{LineStringTrait, MultiLineStringTrait, PointTrait};
+use crate::multilinestring::MultiLineStringCapacity;
 use crate::polygon::MutablePolygonArray;
+use crate::processor::{push_offset, reject_curves, reject_geometries};
 use crate::MultiLineStringArray;
+use geozero::{FeatureProcessor, GeomProcessor, GeozeroGeometry, PropertyProcessor};
 
 #[derive(Debug, Clone)]
 pub struct MutableMultiLineStringArray {
@@ -61,8 +67,34 @@ impl MutableMultiLineStringArray {
         ring_offsets: Offsets<i64>,
         validity: Option<MutableBitmap>,
     ) -> Result<Self, GeoArrowError> {
-        MutablePolygonArray::try_new(x, y, geom_offsets, ring_offsets, validity)
-            .map(|result| result.into())
+        Ok(Self {
+            x,
+            y,
+            geom_offsets,
+            ring_offsets,
+            validity,
+        })
+    }
+
+    /// Append the geometries of `other` to this array.
+    ///
+    /// Mutable arrays carry no CRS, so the CRS of `other` is dropped.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn extend_from_array(&mut self, other: &MultiLineStringArray) -> Result<(), GeoArrowError> {
+        let len = self.geom_offsets.len_proxy();
+        let (ring_start, ring_end) =
+            extend_offsets(&mut self.geom_offsets, other.geom_offsets(), 0, other.len())?;
+        let (start, end) = extend_offsets(
+            &mut self.ring_offsets,
+            other.ring_offsets(),
+            ring_start,
+            ring_end - ring_start,
+        )?;
+        self.x.extend_from_slice(&other.values_x()[start..end]);
+        self.y.extend_from_slice(&other.values_y()[start..end]);
+        extend_validity(&mut self.validity, len, other.validity(), other.len());
+        Ok(())
     }
 
     /// Extract the low-level APIs from the [`MutableLineStringArray`].
@@ -80,6 +112,96 @@ impl MutableMultiLineStringArray {
         let arr: MultiLineStringArray = self.into();
         arr.into_arrow()
     }
+
+    /// Creates a new [`MutableMultiLineStringArray`] with the capacity counted by `capacity`.
+    pub fn with_capacity(capacity: MultiLineStringCapacity) -> Self {
+        Self::with_capacities(
+            capacity.coord_capacity(),
+            capacity.geom_capacity(),
+            capacity.ring_capacity(),
+        )
+    }
+
+    /// Reserves capacity for at least `additional` more values.
+    pub fn reserve(&mut self, additional: MultiLineStringCapacity) {
+        self.x.reserve(additional.coord_capacity());
+        self.y.reserve(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        self.ring_offsets.reserve(additional.ring_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Reserves capacity for exactly `additional` more coordinates, so that ingesting a counted
+    /// batch does not over-allocate the coordinate buffers. Offsets and validity may still
+    /// reserve more than needed.
+    pub fn reserve_exact(&mut self, additional: MultiLineStringCapacity) {
+        self.x.reserve_exact(additional.coord_capacity());
+        self.y.reserve_exact(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        self.ring_offsets.reserve(additional.ring_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Shrinks the capacity of the buffers of this array to fit their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        self.x.shrink_to_fit();
+        self.y.shrink_to_fit();
+        self.geom_offsets.shrink_to_fit();
+        self.ring_offsets.shrink_to_fit();
+        if let Some(validity) = &mut self.validity {
+            validity.shrink_to_fit();
+        }
+    }
+
+    /// Adds a new multi line string to the array, or a null if `value` is `None`.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn try_push_multi_line_string<'a>(
+        &mut self,
+        value: Option<&impl MultiLineStringTrait<'a>>,
+    ) -> Result<(), GeoArrowError> {
+        let Some(multi_line_string) = value else {
+            self.push_null();
+            return Ok(());
+        };
+        for line in multi_line_string.lines() {
+            self.try_push_ring(line)?;
+        }
+        self.try_push_valid()
+    }
+
+    /// Adds a new value to the array.
+    pub fn try_push_geo(&mut self, value: Option<MultiLineString>) -> Result<(), GeoArrowError> {
+        self.try_push_multi_line_string(value.as_ref().as_ref())
+    }
+
+    /// Adds a new null to the array.
+    pub fn push_null(&mut self) {
+        self.geom_offsets.extend_constant(1);
+        let len = self.geom_offsets.len_proxy() - 1;
+        push_validity(&mut self.validity, len, false);
+    }
+
+    /// Close the current geometry, whose children were pushed, as a valid value.
+    fn try_push_valid(&mut self) -> Result<(), GeoArrowError> {
+        let len = self.geom_offsets.len_proxy();
+        try_push_end(&mut self.geom_offsets, self.ring_offsets.len_proxy())?;
+        push_validity(&mut self.validity, len, true);
+        Ok(())
+    }
+
+    /// Push the coordinates of the line string `ring` and close it.
+    fn try_push_ring<'a>(&mut self, ring: impl LineStringTrait<'a>) -> Result<(), GeoArrowError> {
+        for point in ring.points() {
+            self.x.push(point.x());
+            self.y.push(point.y());
+        }
+        try_push_end(&mut self.ring_offsets, self.x.len())
+    }
 }
 
 impl Default for MutableMultiLineStringArray {
@@ -88,6 +210,28 @@ impl Default for MutableMultiLineStringArray {
     }
 }
 
+impl MutableGeometryArray for MutableMultiLineStringArray {
+    fn len(&self) -> usize {
+        self.geom_offsets.len_proxy()
+    }
+
+    fn validity(&self) -> Option<&MutableBitmap> {
+        self.validity.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn push_null(&mut self) {
+        MutableMultiLineStringArray::push_null(self)
+    }
+}
+
 impl From<MutableMultiLineStringArray> for MultiLineStringArray {
     fn from(other: MutableMultiLineStringArray) -> Self {
         let validity = other.validity.and_then(|x| {
@@ -223,3 +367,103 @@ impl From<MutableMultiLineStringArray> for MutablePolygonArray {
         .unwrap()
     }
 }
+
+/// Convert a geozero geometry, such as a geometry collection of multi line strings, to a GeoArrow
+/// [`MultiLineStringArray`].
+pub trait ToGeoArrowMultiLineString {
+    /// Convert to GeoArrow MultiLineStringArray
+    fn to_geoarrow(&self) -> geozero::error::Result<MultiLineStringArray>;
+
+    /// Convert to a GeoArrow MutableMultiLineStringArray
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutableMultiLineStringArray>;
+}
+
+impl<T: GeozeroGeometry> ToGeoArrowMultiLineString for T {
+    fn to_geoarrow(&self) -> geozero::error::Result<MultiLineStringArray> {
+        Ok(self.to_mutable_geoarrow()?.into())
+    }
+
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutableMultiLineStringArray> {
+        let mut mutable_array = MutableMultiLineStringArray::new();
+        self.process_geom(&mut mutable_array)?;
+        Ok(mutable_array)
+    }
+}
+
+/// Each multi line string processed is appended as one geometry, and each line string as a multi
+/// line string of length one.
+impl GeomProcessor for MutableMultiLineStringArray {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> geozero::error::Result<()> {
+        self.x.push(x);
+        self.y.push(y);
+        Ok(())
+    }
+
+    fn linestring_begin(
+        &mut self,
+        _tagged: bool,
+        size: usize,
+        _idx: usize,
+    ) -> geozero::error::Result<()> {
+        self.x.reserve(size);
+        self.y.reserve(size);
+        Ok(())
+    }
+
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> geozero::error::Result<()> {
+        push_offset(&mut self.ring_offsets, self.x.len())?;
+        if tagged {
+            self.multilinestring_end(idx)?;
+        }
+        Ok(())
+    }
+
+    fn multilinestring_end(&mut self, _idx: usize) -> geozero::error::Result<()> {
+        push_offset(&mut self.geom_offsets, self.ring_offsets.len_proxy())?;
+        if let Some(validity) = &mut self.validity {
+            validity.push(true)
+        }
+        Ok(())
+    }
+
+    reject_geometries!(
+        "Only line string and multi line string geometries allowed",
+        [
+            point_begin(_idx: usize),
+            multipoint_begin(_size: usize, _idx: usize),
+            polygon_begin(_tagged: bool, _size: usize, _idx: usize),
+            multipolygon_begin(_size: usize, _idx: usize),
+        ]
+    );
+    reject_curves!("Only line string and multi line string geometries allowed");
+}
+
+impl PropertyProcessor for MutableMultiLineStringArray {}
+
+/// Features are appended as one geometry each, ignoring their properties.
+impl FeatureProcessor for MutableMultiLineStringArray {}
+
+#[cfg(test)]
+mod test {
+    use super::ToGeoArrowMultiLineString;
+    use crate::GeometryArrayTrait;
+    use geo::{line_string, point, Geometry, GeometryCollection, MultiLineString};
+
+    #[test]
+    fn from_geozero() {
+        let ls0 = line_string![(x: 0., y: 1.), (x: 1., y: 2.)];
+        let ls1 = line_string![(x: 3., y: 4.), (x: 5., y: 6.)];
+        let mls = MultiLineString(vec![ls0.clone(), ls1]);
+        let geo = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::MultiLineString(mls.clone()),
+            Geometry::LineString(ls0.clone()),
+        ]));
+        let arr = geo.to_geoarrow().unwrap();
+        assert_eq!(arr.len(), 2);
+        assert_eq!(arr.value_as_geo(0), mls);
+        assert_eq!(arr.value_as_geo(1), MultiLineString(vec![ls0]));
+
+        let geo = Geometry::Point(point!(x: 0., y: 1.));
+        assert!(geo.to_geoarrow().is_err());
+    }
+}
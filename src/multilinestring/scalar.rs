@@ -16,6 +16,9 @@ pub struct MultiLineString<'a> {
     /// Buffer of y coordinates
     pub y: &'a Buffer<f64>,
 
+    /// Buffer of m values, if the array has them
+    pub m: Option<&'a Buffer<f64>>,
+
     /// Offsets into the ring array where each geometry starts
     pub geom_offsets: &'a OffsetsBuffer<i64>,
 
@@ -29,24 +32,25 @@ impl<'a> MultiLineStringTrait<'a> for MultiLineString<'a> {
     type ItemType = LineString<'a>;
     type Iter = MultiLineStringIterator<'a>;
 
-    fn lines(&'a self) -> Self::Iter {
+    fn lines(&self) -> Self::Iter {
         MultiLineStringIterator::new(self)
     }
 
-    fn num_lines(&'a self) -> usize {
+    fn num_lines(&self) -> usize {
         let (start, end) = self.geom_offsets.start_end(self.geom_index);
         end - start
     }
 
-    fn line(&'a self, i: usize) -> Option<Self::ItemType> {
+    fn line(&self, i: usize) -> Option<Self::ItemType> {
         let (start, end) = self.geom_offsets.start_end(self.geom_index);
-        if i > (end - start) {
+        if i >= end - start {
             return None;
         }
 
         Some(LineString {
             x: self.x,
             y: self.y,
+            m: self.m,
             geom_offsets: self.ring_offsets,
             geom_index: start + i,
         })
@@ -97,3 +101,70 @@ impl RTreeObject for MultiLineString<'_> {
         AABB::from_corners(lower, upper)
     }
 }
+
+/// An owned [`MultiLineString`], holding clones of the buffers of its array.
+#[derive(Debug, Clone)]
+pub struct OwnedMultiLineString {
+    /// Buffer of x coordinates
+    pub x: Buffer<f64>,
+
+    /// Buffer of y coordinates
+    pub y: Buffer<f64>,
+
+    /// Buffer of m values, if the array has them
+    pub m: Option<Buffer<f64>>,
+
+    /// Offsets into the ring array where each geometry starts
+    pub geom_offsets: OffsetsBuffer<i64>,
+
+    /// Offsets into the coordinate array where each ring starts
+    pub ring_offsets: OffsetsBuffer<i64>,
+
+    pub geom_index: usize,
+}
+
+impl From<MultiLineString<'_>> for OwnedMultiLineString {
+    fn from(value: MultiLineString<'_>) -> Self {
+        Self {
+            x: value.x.clone(),
+            y: value.y.clone(),
+            m: value.m.cloned(),
+            geom_offsets: value.geom_offsets.clone(),
+            ring_offsets: value.ring_offsets.clone(),
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedMultiLineString> for MultiLineString<'a> {
+    fn from(value: &'a OwnedMultiLineString) -> Self {
+        Self {
+            x: &value.x,
+            y: &value.y,
+            m: value.m.as_ref(),
+            geom_offsets: &value.geom_offsets,
+            ring_offsets: &value.ring_offsets,
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl From<OwnedMultiLineString> for geo::MultiLineString {
+    fn from(value: OwnedMultiLineString) -> Self {
+        MultiLineString::from(&value).into()
+    }
+}
+
+impl From<OwnedMultiLineString> for geo::Geometry {
+    fn from(value: OwnedMultiLineString) -> Self {
+        geo::Geometry::MultiLineString(value.into())
+    }
+}
+
+impl RTreeObject for OwnedMultiLineString {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        MultiLineString::from(self).envelope()
+    }
+}
@@ -0,0 +1,70 @@
+//! Capacities of the buffers of a [`MutableMultiPointArray`](super::MutableMultiPointArray).
+
+use crate::geo_traits::MultiPointTrait;
+use std::ops::Add;
+
+/// The number of coordinates and geometries to allocate in a
+/// [`MutableMultiPointArray`](super::MutableMultiPointArray).
+///
+/// Counting the geometries to ingest first, with [`from_multi_points`](Self::from_multi_points),
+/// lets the array allocate its buffers once rather than growing them geometry by geometry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MultiPointCapacity {
+    coord_capacity: usize,
+    geom_capacity: usize,
+}
+
+impl MultiPointCapacity {
+    /// Creates a new capacity from its parts.
+    pub fn new(coord_capacity: usize, geom_capacity: usize) -> Self {
+        Self {
+            coord_capacity,
+            geom_capacity,
+        }
+    }
+
+    /// Creates a new capacity of zero.
+    pub fn new_empty() -> Self {
+        Self::default()
+    }
+
+    /// The number of coordinates.
+    pub fn coord_capacity(&self) -> usize {
+        self.coord_capacity
+    }
+
+    /// The number of geometries.
+    pub fn geom_capacity(&self) -> usize {
+        self.geom_capacity
+    }
+
+    /// Add the capacity of a multi point, or of a null if `value` is `None`.
+    pub fn add_multi_point<'a>(&mut self, value: Option<&impl MultiPointTrait<'a>>) {
+        self.geom_capacity += 1;
+        if let Some(multi_point) = value {
+            self.coord_capacity += multi_point.num_points();
+        }
+    }
+
+    /// The capacity needed to hold `geoms`, where `None` is a null.
+    pub fn from_multi_points<'a>(
+        geoms: impl Iterator<Item = Option<impl MultiPointTrait<'a>>>,
+    ) -> Self {
+        let mut capacity = Self::new_empty();
+        for geom in geoms {
+            capacity.add_multi_point(geom.as_ref());
+        }
+        capacity
+    }
+}
+
+impl Add for MultiPointCapacity {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            coord_capacity: self.coord_capacity + rhs.coord_capacity,
+            geom_capacity: self.geom_capacity + rhs.geom_capacity,
+        }
+    }
+}
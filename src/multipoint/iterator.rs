@@ -82,16 +82,16 @@ impl<'a> MultiPointArray {
 /// Iterator of values of a [`PointArray`]
 #[derive(Clone, Debug)]
 pub struct MultiPointIterator<'a> {
-    geom: &'a MultiPoint<'a>,
+    geom: MultiPoint<'a>,
     index: usize,
     end: usize,
 }
 
 impl<'a> MultiPointIterator<'a> {
     #[inline]
-    pub fn new(geom: &'a MultiPoint<'a>) -> Self {
+    pub fn new(geom: &MultiPoint<'a>) -> Self {
         Self {
-            geom,
+            geom: geom.clone(),
             index: 0,
             end: geom.num_points(),
         }
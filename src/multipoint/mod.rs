@@ -1,8 +1,10 @@
 pub use array::MultiPointArray;
-pub use mutable::MutableMultiPointArray;
-pub use scalar::MultiPoint;
+pub use capacity::MultiPointCapacity;
+pub use mutable::{MutableMultiPointArray, ToGeoArrowMultiPoint};
+pub use scalar::{MultiPoint, OwnedMultiPoint};
 
 mod array;
+mod capacity;
 mod iterator;
 mod mutable;
 mod scalar;
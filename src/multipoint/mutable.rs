@@ -1,12 +1,18 @@
 use super::array::MultiPointArray;
+use crate::concat::{extend_offsets, extend_validity};
 use crate::error::GeoArrowError;
+use crate::geo_traits::{MultiPointTrait, PointTrait};
 use crate::linestring::MutableLineStringArray;
+use crate::multipoint::MultiPointCapacity;
+use crate::processor::{reject_curves, reject_geometries};
 use crate::trait_::{GeometryArrayTrait, MutableGeometryArray};
 use arrow2::array::ListArray;
 use arrow2::bitmap::{Bitmap, MutableBitmap};
 use arrow2::offset::Offsets;
 use arrow2::types::Index;
 use geo::MultiPoint;
+use geozero::error::GeozeroError;
+use geozero::{FeatureProcessor, GeomProcessor, GeozeroGeometry, PropertyProcessor};
 
 /// The Arrow equivalent to `Vec<Option<MultiPoint>>`.
 /// Converting a [`MutableMultiPointArray`] into a [`MultiPointArray`] is `O(1)`.
@@ -52,7 +58,27 @@ impl MutableMultiPointArray {
         geom_offsets: Offsets<i64>,
         validity: Option<MutableBitmap>,
     ) -> Result<Self, GeoArrowError> {
-        MutableLineStringArray::try_new(x, y, geom_offsets, validity).map(|result| result.into())
+        Ok(Self {
+            x,
+            y,
+            geom_offsets,
+            validity,
+        })
+    }
+
+    /// Append the geometries of `other` to this array.
+    ///
+    /// Mutable arrays carry no CRS, so the CRS of `other` is dropped.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn extend_from_array(&mut self, other: &MultiPointArray) -> Result<(), GeoArrowError> {
+        let len = self.geom_offsets.len_proxy();
+        let (start, end) =
+            extend_offsets(&mut self.geom_offsets, other.geom_offsets(), 0, other.len())?;
+        self.x.extend_from_slice(&other.values_x()[start..end]);
+        self.y.extend_from_slice(&other.values_y()[start..end]);
+        extend_validity(&mut self.validity, len, other.validity(), other.len());
+        Ok(())
     }
 
     /// Extract the low-level APIs from the [`MutableMultiPointArray`].
@@ -79,6 +105,62 @@ impl MutableMultiPointArray {
         Ok(())
     }
 
+    /// Adds a new multi point to the array, or a null if `value` is `None`.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn try_push_multi_point<'a>(
+        &mut self,
+        value: Option<&impl MultiPointTrait<'a>>,
+    ) -> Result<(), GeoArrowError> {
+        if let Some(multi_point) = value {
+            for point in multi_point.points() {
+                self.x.push(point.x());
+                self.y.push(point.y());
+            }
+            self.try_push_valid()?;
+        } else {
+            self.push_null();
+        }
+        Ok(())
+    }
+
+    /// Creates a new [`MutableMultiPointArray`] with the capacity counted by `capacity`.
+    pub fn with_capacity(capacity: MultiPointCapacity) -> Self {
+        Self::with_capacities(capacity.coord_capacity(), capacity.geom_capacity())
+    }
+
+    /// Reserves capacity for at least `additional` more values.
+    pub fn reserve(&mut self, additional: MultiPointCapacity) {
+        self.x.reserve(additional.coord_capacity());
+        self.y.reserve(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Reserves capacity for exactly `additional` more coordinates, so that ingesting a counted
+    /// batch does not over-allocate the coordinate buffers. Offsets and validity may still
+    /// reserve more than needed.
+    pub fn reserve_exact(&mut self, additional: MultiPointCapacity) {
+        self.x.reserve_exact(additional.coord_capacity());
+        self.y.reserve_exact(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Shrinks the capacity of the buffers of this array to fit their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        self.x.shrink_to_fit();
+        self.y.shrink_to_fit();
+        self.geom_offsets.shrink_to_fit();
+        if let Some(validity) = &mut self.validity {
+            validity.shrink_to_fit();
+        }
+    }
+
     #[inline]
     /// Needs to be called when a valid value was extended to this array.
     /// This is a relatively low level function, prefer `try_push` when you can.
@@ -89,16 +171,20 @@ impl MutableMultiPointArray {
             .checked_sub(offset)
             .ok_or(GeoArrowError::Overflow)?;
 
-        // TODO: remove unwrap
-        self.geom_offsets.try_push_usize(length).unwrap();
+        self.geom_offsets
+            .try_push_usize(length)
+            .map_err(|_| GeoArrowError::OffsetOverflow {
+                row: self.geom_offsets.len_proxy(),
+            })?;
         if let Some(validity) = &mut self.validity {
             validity.push(true)
         }
         Ok(())
     }
 
+    /// Adds a new null to the array.
     #[inline]
-    fn push_null(&mut self) {
+    pub fn push_null(&mut self) {
         self.geom_offsets.extend_constant(1);
         match &mut self.validity {
             Some(validity) => validity.push(false),
@@ -124,7 +210,7 @@ impl Default for MutableMultiPointArray {
 
 impl MutableGeometryArray for MutableMultiPointArray {
     fn len(&self) -> usize {
-        self.x.len()
+        self.geom_offsets.len_proxy()
     }
 
     fn validity(&self) -> Option<&MutableBitmap> {
@@ -138,6 +224,10 @@ impl MutableGeometryArray for MutableMultiPointArray {
     fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn push_null(&mut self) {
+        MutableMultiPointArray::push_null(self)
+    }
 }
 
 impl From<MutableMultiPointArray> for MultiPointArray {
@@ -252,3 +342,90 @@ impl From<MutableMultiPointArray> for MutableLineStringArray {
         Self::try_new(value.x, value.y, value.geom_offsets, value.validity).unwrap()
     }
 }
+
+/// Convert a geozero geometry, such as a geometry collection of multi points, to a GeoArrow
+/// [`MultiPointArray`].
+pub trait ToGeoArrowMultiPoint {
+    /// Convert to GeoArrow MultiPointArray
+    fn to_geoarrow(&self) -> geozero::error::Result<MultiPointArray>;
+
+    /// Convert to a GeoArrow MutableMultiPointArray
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutableMultiPointArray>;
+}
+
+impl<T: GeozeroGeometry> ToGeoArrowMultiPoint for T {
+    fn to_geoarrow(&self) -> geozero::error::Result<MultiPointArray> {
+        Ok(self.to_mutable_geoarrow()?.into())
+    }
+
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutableMultiPointArray> {
+        let mut mutable_array = MutableMultiPointArray::new();
+        self.process_geom(&mut mutable_array)?;
+        Ok(mutable_array)
+    }
+}
+
+/// Each multi point processed is appended as one geometry, and each point as a multi point of
+/// length one.
+impl GeomProcessor for MutableMultiPointArray {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> geozero::error::Result<()> {
+        self.x.push(x);
+        self.y.push(y);
+        Ok(())
+    }
+
+    fn point_end(&mut self, _idx: usize) -> geozero::error::Result<()> {
+        self.try_push_valid()
+            .map_err(|err| GeozeroError::Geometry(err.to_string()))
+    }
+
+    fn multipoint_begin(&mut self, size: usize, _idx: usize) -> geozero::error::Result<()> {
+        self.x.reserve(size);
+        self.y.reserve(size);
+        Ok(())
+    }
+
+    fn multipoint_end(&mut self, _idx: usize) -> geozero::error::Result<()> {
+        self.try_push_valid()
+            .map_err(|err| GeozeroError::Geometry(err.to_string()))
+    }
+
+    reject_geometries!(
+        "Only point and multi point geometries allowed",
+        [
+            linestring_begin(_tagged: bool, _size: usize, _idx: usize),
+            polygon_begin(_tagged: bool, _size: usize, _idx: usize),
+            multilinestring_begin(_size: usize, _idx: usize),
+            multipolygon_begin(_size: usize, _idx: usize),
+        ]
+    );
+    reject_curves!("Only point and multi point geometries allowed");
+}
+
+impl PropertyProcessor for MutableMultiPointArray {}
+
+/// Features are appended as one geometry each, ignoring their properties.
+impl FeatureProcessor for MutableMultiPointArray {}
+
+#[cfg(test)]
+mod test {
+    use super::ToGeoArrowMultiPoint;
+    use crate::GeometryArrayTrait;
+    use geo::{line_string, point, Geometry, GeometryCollection, MultiPoint};
+
+    #[test]
+    fn from_geozero() {
+        let mp = MultiPoint(vec![point!(x: 0., y: 1.), point!(x: 2., y: 3.)]);
+        let geo = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::MultiPoint(mp.clone()),
+            Geometry::Point(point!(x: 4., y: 5.)),
+        ]));
+        let arr = geo.to_geoarrow().unwrap();
+        assert_eq!(arr.len(), 2);
+        assert_eq!(arr.value_as_geo(0), mp);
+        assert_eq!(arr.value_as_geo(1), MultiPoint(vec![point!(x: 4., y: 5.)]));
+
+        let geo = Geometry::LineString(line_string![(x: 0., y: 1.), (x: 1., y: 2.)]);
+        assert!(geo.to_geoarrow().is_err());
+    }
+}
@@ -15,6 +15,9 @@ pub struct MultiPoint<'a> {
     /// Buffer of y coordinates
     pub y: &'a Buffer<f64>,
 
+    /// Buffer of m values, if the array has them
+    pub m: Option<&'a Buffer<f64>>,
+
     /// Offsets into the coordinate array where each geometry starts
     pub geom_offsets: &'a OffsetsBuffer<i64>,
 
@@ -25,7 +28,7 @@ impl<'a> MultiPointTrait<'a> for MultiPoint<'a> {
     type ItemType = Point<'a>;
     type Iter = MultiPointIterator<'a>;
 
-    fn points(&'a self) -> Self::Iter {
+    fn points(&self) -> Self::Iter {
         MultiPointIterator::new(self)
     }
 
@@ -34,15 +37,16 @@ impl<'a> MultiPointTrait<'a> for MultiPoint<'a> {
         end - start
     }
 
-    fn point(&'a self, i: usize) -> Option<Self::ItemType> {
+    fn point(&self, i: usize) -> Option<Self::ItemType> {
         let (start, end) = self.geom_offsets.start_end(self.geom_index);
-        if i > (end - start) {
+        if i >= end - start {
             return None;
         }
 
         let point = Point {
             x: self.x,
             y: self.y,
+            m: self.m,
             geom_index: start + i,
         };
         Some(point)
@@ -82,3 +86,65 @@ impl RTreeObject for MultiPoint<'_> {
         AABB::from_corners(lower, upper)
     }
 }
+
+/// An owned [`MultiPoint`], holding clones of the buffers of its array.
+#[derive(Debug, Clone)]
+pub struct OwnedMultiPoint {
+    /// Buffer of x coordinates
+    pub x: Buffer<f64>,
+
+    /// Buffer of y coordinates
+    pub y: Buffer<f64>,
+
+    /// Buffer of m values, if the array has them
+    pub m: Option<Buffer<f64>>,
+
+    /// Offsets into the coordinate array where each geometry starts
+    pub geom_offsets: OffsetsBuffer<i64>,
+
+    pub geom_index: usize,
+}
+
+impl From<MultiPoint<'_>> for OwnedMultiPoint {
+    fn from(value: MultiPoint<'_>) -> Self {
+        Self {
+            x: value.x.clone(),
+            y: value.y.clone(),
+            m: value.m.cloned(),
+            geom_offsets: value.geom_offsets.clone(),
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedMultiPoint> for MultiPoint<'a> {
+    fn from(value: &'a OwnedMultiPoint) -> Self {
+        Self {
+            x: &value.x,
+            y: &value.y,
+            m: value.m.as_ref(),
+            geom_offsets: &value.geom_offsets,
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl From<OwnedMultiPoint> for geo::MultiPoint {
+    fn from(value: OwnedMultiPoint) -> Self {
+        MultiPoint::from(&value).into()
+    }
+}
+
+impl From<OwnedMultiPoint> for geo::Geometry {
+    fn from(value: OwnedMultiPoint) -> Self {
+        geo::Geometry::MultiPoint(value.into())
+    }
+}
+
+impl RTreeObject for OwnedMultiPoint {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        MultiPoint::from(self).envelope()
+    }
+}
@@ -1,5 +1,13 @@
+use crate::concat::concat_crs;
+use crate::coords::{concat_m, coord_fields, coords_have_m, impl_coords, values_range};
+use crate::crs::impl_crs;
 use crate::error::GeoArrowError;
+use crate::offsets::widen_list_offsets;
+#[cfg(feature = "rayon")]
+use crate::parallel::MIN_ROWS_PER_TASK;
 use crate::slice::slice_validity_unchecked;
+use crate::trait_::{Dimension, GeometryArrayType};
+use crate::validity::check_validity_len;
 use crate::GeometryArrayTrait;
 use arrow2::array::{Array, ListArray, PrimitiveArray, StructArray};
 use arrow2::bitmap::utils::{BitmapIter, ZipValidity};
@@ -8,7 +16,10 @@ use arrow2::buffer::Buffer;
 use arrow2::datatypes::{DataType, Field};
 use arrow2::offset::OffsetsBuffer;
 use geozero::{GeomProcessor, GeozeroGeometry};
-use rstar::RTree;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::ops::Range;
+use std::sync::Arc;
 
 use super::MutableMultiPolygonArray;
 
@@ -22,6 +33,9 @@ pub struct MultiPolygonArray {
     /// Buffer of y coordinates
     y: Buffer<f64>,
 
+    /// Buffer of m values (measures), one per coordinate, if any
+    m: Option<Buffer<f64>>,
+
     /// Offsets into the polygon array where each geometry starts
     geom_offsets: OffsetsBuffer<i64>,
 
@@ -33,6 +47,9 @@ pub struct MultiPolygonArray {
 
     /// Validity bitmap
     validity: Option<Bitmap>,
+
+    /// Coordinate reference system
+    crs: Option<Arc<str>>,
 }
 
 pub(super) fn check(
@@ -72,10 +89,12 @@ impl MultiPolygonArray {
         Self {
             x,
             y,
+            m: None,
             geom_offsets,
             polygon_offsets,
             ring_offsets,
             validity,
+            crs: None,
         }
     }
 
@@ -94,14 +113,91 @@ impl MultiPolygonArray {
         Ok(Self {
             x,
             y,
+            m: None,
             geom_offsets,
             polygon_offsets,
             ring_offsets,
             validity,
+            crs: None,
         })
     }
+
+    /// Offsets into the polygon offsets where each geometry starts.
+    #[inline]
+    pub fn geom_offsets(&self) -> &OffsetsBuffer<i64> {
+        &self.geom_offsets
+    }
+
+    /// Offsets into the ring offsets where each polygon starts.
+    #[inline]
+    pub fn polygon_offsets(&self) -> &OffsetsBuffer<i64> {
+        &self.polygon_offsets
+    }
+
+    /// Offsets into the coordinate buffers where each ring starts.
+    #[inline]
+    pub fn ring_offsets(&self) -> &OffsetsBuffer<i64> {
+        &self.ring_offsets
+    }
+
+    /// Concatenate `arrays` into one array, copying their coordinates and rebuilding offsets.
+    ///
+    /// Arrays without a CRS are assumed to be in the CRS of the others. m values are kept if
+    /// every array has them.
+    /// # Errors
+    /// Errors if two arrays have different CRSes, or if the offsets of the result would overflow.
+    pub fn concat(arrays: &[&MultiPolygonArray]) -> Result<Self, GeoArrowError> {
+        let crs = concat_crs(arrays.iter().map(|array| array.crs()))?;
+        let mut mutable = MutableMultiPolygonArray::new();
+        for array in arrays {
+            mutable.extend_from_array(array)?;
+        }
+        let array = Self::from(mutable).with_crs(crs);
+        let m = concat_m(arrays.iter().map(|array| {
+            let range = array.coord_range(0..array.len());
+            array.values_m().map(|m| &m[range])
+        }));
+        match m {
+            Some(m) => array.with_m(m),
+            None => Ok(array),
+        }
+    }
 }
 
+impl MultiPolygonArray {
+    /// The number of interior rings (holes) of all the polygons of each multi polygon, read
+    /// from the offsets. Null multi polygons give null counts.
+    pub fn num_interior_rings(&self) -> PrimitiveArray<u32> {
+        let counts = (0..self.len())
+            .map(|i| {
+                let (start, end) = self.geom_offsets.start_end(i);
+                (start..end)
+                    .map(|polygon| {
+                        let (ring_start, ring_end) = self.polygon_offsets.start_end(polygon);
+                        (ring_end - ring_start).saturating_sub(1) as u32
+                    })
+                    .sum()
+            })
+            .collect::<Vec<_>>();
+        PrimitiveArray::new(DataType::UInt32, counts.into(), self.validity.clone())
+    }
+}
+
+impl_crs!(MultiPolygonArray);
+
+impl MultiPolygonArray {
+    /// The range of the coordinate buffers holding the coordinates of the geometries `geoms`.
+    pub(crate) fn coord_range(&self, geoms: Range<usize>) -> Range<usize> {
+        let polygons = values_range(&self.geom_offsets, geoms);
+        values_range(
+            &self.ring_offsets,
+            values_range(&self.polygon_offsets, polygons),
+        )
+    }
+}
+
+impl_coords!(MultiPolygonArray);
+
 impl<'a> GeometryArrayTrait<'a> for MultiPolygonArray {
     type Scalar = crate::MultiPolygon<'a>;
     type ScalarGeo = geo::MultiPolygon;
@@ -111,6 +207,7 @@ impl<'a> GeometryArrayTrait<'a> for MultiPolygonArray {
         crate::MultiPolygon {
             x: &self.x,
             y: &self.y,
+            m: self.m.as_ref(),
             geom_offsets: &self.geom_offsets,
             polygon_offsets: &self.polygon_offsets,
             ring_offsets: &self.ring_offsets,
@@ -120,9 +217,8 @@ impl<'a> GeometryArrayTrait<'a> for MultiPolygonArray {
 
     fn into_arrow(self) -> Self::ArrowArray {
         // Data type
-        let coord_field_x = Field::new("x", DataType::Float64, false);
-        let coord_field_y = Field::new("y", DataType::Float64, false);
-        let struct_data_type = DataType::Struct(vec![coord_field_x, coord_field_y]);
+        let (coord_fields, coord_values) = coord_fields(self.x, self.y, self.m);
+        let struct_data_type = DataType::Struct(coord_fields);
         let inner_list_data_type = DataType::LargeList(Box::new(Field::new(
             "vertices",
             struct_data_type.clone(),
@@ -146,12 +242,8 @@ impl<'a> GeometryArrayTrait<'a> for MultiPolygonArray {
             None
         };
 
-        // Array data
-        let array_x = PrimitiveArray::new(DataType::Float64, self.x, None).boxed();
-        let array_y = PrimitiveArray::new(DataType::Float64, self.y, None).boxed();
-
         // Coord struct array
-        let coord_array = StructArray::new(struct_data_type, vec![array_x, array_y], None).boxed();
+        let coord_array = StructArray::new(struct_data_type, coord_values, None).boxed();
 
         // Rings array
         let inner_list_array =
@@ -175,11 +267,12 @@ impl<'a> GeometryArrayTrait<'a> for MultiPolygonArray {
         )
     }
 
-    /// Build a spatial index containing this array's geometries
-    fn rstar_tree(&'a self) -> RTree<Self::Scalar> {
-        let mut tree = RTree::new();
-        self.iter().flatten().for_each(|geom| tree.insert(geom));
-        tree
+    fn geometry_type(&self) -> GeometryArrayType {
+        GeometryArrayType::MultiPolygon
+    }
+
+    fn dimension(&self) -> Dimension {
+        Dimension::new(false, self.m.is_some())
     }
 
     /// Returns the number of geometries in this array
@@ -228,6 +321,14 @@ impl<'a> GeometryArrayTrait<'a> for MultiPolygonArray {
         self.geom_offsets.slice_unchecked(offset, length + 1);
     }
 
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        check_validity_len(validity.as_ref(), self.len());
+        Self {
+            validity,
+            ..self.clone()
+        }
+    }
+
     fn to_boxed(&self) -> Box<Self> {
         Box::new(self.clone())
     }
@@ -248,6 +349,26 @@ impl MultiPolygonArray {
         ZipValidity::new_with_validity(self.iter_geo_values(), self.validity())
     }
 
+    /// Parallel iterator over the scalars of this array, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = Option<crate::MultiPolygon<'_>>> {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get(i))
+    }
+
+    /// Parallel iterator over geo Geometry objects, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_geo(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = Option<geo::MultiPolygon>> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get_as_geo(i))
+    }
+
     // GEOS from not implemented for MultiLineString I suppose
     //
     // /// Returns the value at slot `i` as a GEOS geometry.
@@ -317,22 +438,47 @@ impl TryFrom<ListArray<i64>> for MultiPolygonArray {
             .downcast_ref::<PrimitiveArray<f64>>()
             .unwrap();
 
-        Ok(Self::new(
+        let array = Self::new(
             x_array_values.values().clone(),
             y_array_values.values().clone(),
             geom_offsets.clone(),
             polygon_offsets.clone(),
             ring_offsets.clone(),
             validity.cloned(),
-        ))
+        );
+        if !coords_have_m(coords_array)? {
+            return Ok(array);
+        }
+        let m_array_values = coords_array.values()[2]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .unwrap();
+        array.with_m(m_array_values.values().clone())
+    }
+}
+
+impl TryFrom<ListArray<i32>> for MultiPolygonArray {
+    type Error = GeoArrowError;
+
+    /// Read a list array with 32-bit offsets, widening them to 64 bits.
+    fn try_from(value: ListArray<i32>) -> Result<Self, Self::Error> {
+        value.boxed().try_into()
     }
 }
 
 impl TryFrom<Box<dyn Array>> for MultiPolygonArray {
     type Error = GeoArrowError;
 
+    /// Both `List` and `LargeList` arrays are accepted.
     fn try_from(value: Box<dyn Array>) -> Result<Self, Self::Error> {
-        let arr = value.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+        let value = widen_list_offsets(value.as_ref());
+        let arr = value
+            .as_any()
+            .downcast_ref::<ListArray<i64>>()
+            .ok_or_else(|| GeoArrowError::Downcast {
+                expected: "ListArray<i64>",
+                found: value.data_type().clone(),
+            })?;
         arr.clone().try_into()
     }
 }
@@ -473,12 +619,30 @@ mod test {
     #[test]
     fn geozero_process_geom() -> geozero::error::Result<()> {
         let arr: MultiPolygonArray = vec![mp0(), mp1()].into();
-        let wkt = arr.to_wkt()?;
+        let wkt = ToWkt::to_wkt(&arr)?;
         let expected = "GEOMETRYCOLLECTION(MULTIPOLYGON(((-111 45,-111 41,-104 41,-104 45,-111 45)),((-111 45,-111 41,-104 41,-104 45,-111 45),(-110 44,-110 42,-105 42,-105 44,-110 44))),MULTIPOLYGON(((-111 45,-111 41,-104 41,-104 45,-111 45)),((-110 44,-110 42,-105 42,-105 44,-110 44))))";
         assert_eq!(wkt, expected);
         Ok(())
     }
 
+    #[test]
+    fn rings() {
+        let arr: MultiPolygonArray = vec![Some(mp0()), Some(mp1()), None].into();
+        let counts = arr.num_interior_rings();
+        assert_eq!(counts.values().as_slice()[..2], [1, 0]);
+        assert!(counts.is_null(2));
+
+        let multi_polygon = arr.value(0);
+        assert_eq!(multi_polygon.num_interior_rings(), 1);
+        assert_eq!(multi_polygon.exteriors().count(), 2);
+        let rings = multi_polygon
+            .rings()
+            .map(geo::LineString::from)
+            .collect::<Vec<_>>();
+        assert_eq!(rings.len(), 3);
+        assert_eq!(rings[2], mp0().0[1].interiors()[0]);
+    }
+
     #[test]
     fn slice() {
         let mut arr: MultiPolygonArray = vec![mp0(), mp1()].into();
@@ -659,4 +823,20 @@ mod test {
         let arr: MultiPolygonArray = mut_arr.into();
         let _tree = arr.rstar_tree();
     }
+
+    #[test]
+    fn extend_from_array() {
+        let arr: MultiPolygonArray = vec![mp0(), mp1(), mp0()].into();
+        let mut mutable = MutableMultiPolygonArray::new();
+        mutable.extend_from_array(&arr.sliced_range(1..3)).unwrap();
+        mutable
+            .extend_from_array(&vec![None, Some(mp1())].into())
+            .unwrap();
+        let arr: MultiPolygonArray = mutable.into();
+        assert_eq!(arr.len(), 4);
+        assert_eq!(arr.get_as_geo(0), Some(mp1()));
+        assert_eq!(arr.get_as_geo(1), Some(mp0()));
+        assert!(arr.is_null(2));
+        assert_eq!(arr.get_as_geo(3), Some(mp1()));
+    }
 }
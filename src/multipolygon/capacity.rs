@@ -0,0 +1,104 @@
+//! Capacities of the buffers of a [`MutableMultiPolygonArray`](super::MutableMultiPolygonArray).
+
+use crate::geo_traits::{LineStringTrait, MultiPolygonTrait, PolygonTrait};
+use std::ops::Add;
+
+/// The number of coordinates, rings, polygons and geometries to allocate in a
+/// [`MutableMultiPolygonArray`](super::MutableMultiPolygonArray).
+///
+/// Counting the geometries to ingest first, with
+/// [`from_multi_polygons`](Self::from_multi_polygons),
+/// lets the array allocate its buffers once rather than growing them geometry by geometry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MultiPolygonCapacity {
+    coord_capacity: usize,
+    ring_capacity: usize,
+    polygon_capacity: usize,
+    geom_capacity: usize,
+}
+
+impl MultiPolygonCapacity {
+    /// Creates a new capacity from its parts.
+    pub fn new(
+        coord_capacity: usize,
+        ring_capacity: usize,
+        polygon_capacity: usize,
+        geom_capacity: usize,
+    ) -> Self {
+        Self {
+            coord_capacity,
+            ring_capacity,
+            polygon_capacity,
+            geom_capacity,
+        }
+    }
+
+    /// Creates a new capacity of zero.
+    pub fn new_empty() -> Self {
+        Self::default()
+    }
+
+    /// The number of coordinates.
+    pub fn coord_capacity(&self) -> usize {
+        self.coord_capacity
+    }
+
+    /// The number of rings.
+    pub fn ring_capacity(&self) -> usize {
+        self.ring_capacity
+    }
+
+    /// The number of polygons.
+    pub fn polygon_capacity(&self) -> usize {
+        self.polygon_capacity
+    }
+
+    /// The number of geometries.
+    pub fn geom_capacity(&self) -> usize {
+        self.geom_capacity
+    }
+
+    /// Add the capacity of a multi polygon, or of a null if `value` is `None`.
+    pub fn add_multi_polygon<'a>(&mut self, value: Option<&impl MultiPolygonTrait<'a>>) {
+        self.geom_capacity += 1;
+        if let Some(multi_polygon) = value {
+            self.polygon_capacity += multi_polygon.num_polygons();
+            for polygon in multi_polygon.polygons() {
+                self.add_polygon_rings(&polygon);
+            }
+        }
+    }
+
+    /// Add the rings of `polygon`.
+    fn add_polygon_rings<'a>(&mut self, polygon: &impl PolygonTrait<'a>) {
+        self.ring_capacity += 1 + polygon.num_interiors();
+        self.coord_capacity += polygon.exterior().num_points();
+        for ring in polygon.interiors() {
+            self.coord_capacity += ring.num_points();
+        }
+    }
+
+    /// The capacity needed to hold `geoms`, where `None` is a null.
+    pub fn from_multi_polygons<'a>(
+        geoms: impl Iterator<Item = Option<impl MultiPolygonTrait<'a>>>,
+    ) -> Self {
+        let mut capacity = Self::new_empty();
+        for geom in geoms {
+            capacity.add_multi_polygon(geom.as_ref());
+        }
+        capacity
+    }
+}
+
+impl Add for MultiPolygonCapacity {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            coord_capacity: self.coord_capacity + rhs.coord_capacity,
+            ring_capacity: self.ring_capacity + rhs.ring_capacity,
+            polygon_capacity: self.polygon_capacity + rhs.polygon_capacity,
+            geom_capacity: self.geom_capacity + rhs.geom_capacity,
+        }
+    }
+}
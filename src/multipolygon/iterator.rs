@@ -82,16 +82,16 @@ impl<'a> MultiPolygonArray {
 /// Iterator of values of a [`PointArray`]
 #[derive(Clone, Debug)]
 pub struct MultiPolygonIterator<'a> {
-    geom: &'a MultiPolygon<'a>,
+    geom: MultiPolygon<'a>,
     index: usize,
     end: usize,
 }
 
 impl<'a> MultiPolygonIterator<'a> {
     #[inline]
-    pub fn new(geom: &'a MultiPolygon<'a>) -> Self {
+    pub fn new(geom: &MultiPolygon<'a>) -> Self {
         Self {
-            geom,
+            geom: geom.clone(),
             index: 0,
             end: geom.num_polygons(),
         }
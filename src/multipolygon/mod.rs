@@ -1,8 +1,10 @@
 pub use array::MultiPolygonArray;
-pub use mutable::MutableMultiPolygonArray;
-pub use scalar::MultiPolygon;
+pub use capacity::MultiPolygonCapacity;
+pub use mutable::{MutableMultiPolygonArray, ToGeoArrowMultiPolygon};
+pub use scalar::{MultiPolygon, OwnedMultiPolygon};
 
 mod array;
+mod capacity;
 mod iterator;
 mod mutable;
 mod scalar;
@@ -1,3 +1,4 @@
+use crate::trait_::MutableGeometryArray;
 use crate::GeometryArrayTrait;
 use arrow2::array::ListArray;
 use arrow2::bitmap::{Bitmap, MutableBitmap};
@@ -5,8 +6,14 @@ use arrow2::offset::{Offsets, OffsetsBuffer};
 use arrow2::types::Index;
 use geo::MultiPolygon;
 
+use crate::concat::{extend_offsets, extend_validity, push_validity, try_push_end};
 use crate::error::GeoArrowError;
+use crate::geo_traits::{LineStringTrait, MultiPolygonTrait, PointTrait, PolygonTrait};
+use crate::multipolygon::MultiPolygonCapacity;
+use crate::processor::{push_offset, reject_curves, reject_geometries};
 use crate::MultiPolygonArray;
+use geozero::error::GeozeroError;
+use geozero::{FeatureProcessor, GeomProcessor, GeozeroGeometry, PropertyProcessor};
 
 pub type MutableMultiPolygonParts = (
     Vec<f64>,
@@ -86,6 +93,33 @@ impl MutableMultiPolygonArray {
         })
     }
 
+    /// Append the geometries of `other` to this array.
+    ///
+    /// Mutable arrays carry no CRS, so the CRS of `other` is dropped.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn extend_from_array(&mut self, other: &MultiPolygonArray) -> Result<(), GeoArrowError> {
+        let len = self.geom_offsets.len_proxy();
+        let (polygon_start, polygon_end) =
+            extend_offsets(&mut self.geom_offsets, other.geom_offsets(), 0, other.len())?;
+        let (ring_start, ring_end) = extend_offsets(
+            &mut self.polygon_offsets,
+            other.polygon_offsets(),
+            polygon_start,
+            polygon_end - polygon_start,
+        )?;
+        let (start, end) = extend_offsets(
+            &mut self.ring_offsets,
+            other.ring_offsets(),
+            ring_start,
+            ring_end - ring_start,
+        )?;
+        self.x.extend_from_slice(&other.values_x()[start..end]);
+        self.y.extend_from_slice(&other.values_y()[start..end]);
+        extend_validity(&mut self.validity, len, other.validity(), other.len());
+        Ok(())
+    }
+
     /// Extract the low-level APIs from the [`MutableLineStringArray`].
     pub fn into_inner(self) -> MutableMultiPolygonParts {
         (
@@ -102,6 +136,104 @@ impl MutableMultiPolygonArray {
         let arr: MultiPolygonArray = self.into();
         arr.into_arrow()
     }
+
+    /// Creates a new [`MutableMultiPolygonArray`] with the capacity counted by `capacity`.
+    pub fn with_capacity(capacity: MultiPolygonCapacity) -> Self {
+        Self::with_capacities(
+            capacity.coord_capacity(),
+            capacity.geom_capacity(),
+            capacity.polygon_capacity(),
+            capacity.ring_capacity(),
+        )
+    }
+
+    /// Reserves capacity for at least `additional` more values.
+    pub fn reserve(&mut self, additional: MultiPolygonCapacity) {
+        self.x.reserve(additional.coord_capacity());
+        self.y.reserve(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        self.polygon_offsets.reserve(additional.polygon_capacity());
+        self.ring_offsets.reserve(additional.ring_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Reserves capacity for exactly `additional` more coordinates, so that ingesting a counted
+    /// batch does not over-allocate the coordinate buffers. Offsets and validity may still
+    /// reserve more than needed.
+    pub fn reserve_exact(&mut self, additional: MultiPolygonCapacity) {
+        self.x.reserve_exact(additional.coord_capacity());
+        self.y.reserve_exact(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        self.polygon_offsets.reserve(additional.polygon_capacity());
+        self.ring_offsets.reserve(additional.ring_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Shrinks the capacity of the buffers of this array to fit their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        self.x.shrink_to_fit();
+        self.y.shrink_to_fit();
+        self.geom_offsets.shrink_to_fit();
+        self.polygon_offsets.shrink_to_fit();
+        self.ring_offsets.shrink_to_fit();
+        if let Some(validity) = &mut self.validity {
+            validity.shrink_to_fit();
+        }
+    }
+
+    /// Adds a new multi polygon to the array, or a null if `value` is `None`.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn try_push_multi_polygon<'a>(
+        &mut self,
+        value: Option<&impl MultiPolygonTrait<'a>>,
+    ) -> Result<(), GeoArrowError> {
+        let Some(multi_polygon) = value else {
+            self.push_null();
+            return Ok(());
+        };
+        for polygon in multi_polygon.polygons() {
+            self.try_push_ring(polygon.exterior())?;
+            for ring in polygon.interiors() {
+                self.try_push_ring(ring)?;
+            }
+            try_push_end(&mut self.polygon_offsets, self.ring_offsets.len_proxy())?;
+        }
+        self.try_push_valid()
+    }
+
+    /// Adds a new value to the array.
+    pub fn try_push_geo(&mut self, value: Option<MultiPolygon>) -> Result<(), GeoArrowError> {
+        self.try_push_multi_polygon(value.as_ref().as_ref())
+    }
+
+    /// Adds a new null to the array.
+    pub fn push_null(&mut self) {
+        self.geom_offsets.extend_constant(1);
+        let len = self.geom_offsets.len_proxy() - 1;
+        push_validity(&mut self.validity, len, false);
+    }
+
+    /// Close the current geometry, whose children were pushed, as a valid value.
+    fn try_push_valid(&mut self) -> Result<(), GeoArrowError> {
+        let len = self.geom_offsets.len_proxy();
+        try_push_end(&mut self.geom_offsets, self.polygon_offsets.len_proxy())?;
+        push_validity(&mut self.validity, len, true);
+        Ok(())
+    }
+
+    /// Push the coordinates of `ring` and close it.
+    fn try_push_ring<'a>(&mut self, ring: impl LineStringTrait<'a>) -> Result<(), GeoArrowError> {
+        for point in ring.points() {
+            self.x.push(point.x());
+            self.y.push(point.y());
+        }
+        try_push_end(&mut self.ring_offsets, self.x.len())
+    }
 }
 
 impl Default for MutableMultiPolygonArray {
@@ -110,6 +242,28 @@ impl Default for MutableMultiPolygonArray {
     }
 }
 
+impl MutableGeometryArray for MutableMultiPolygonArray {
+    fn len(&self) -> usize {
+        self.geom_offsets.len_proxy()
+    }
+
+    fn validity(&self) -> Option<&MutableBitmap> {
+        self.validity.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn push_null(&mut self) {
+        MutableMultiPolygonArray::push_null(self)
+    }
+}
+
 impl From<MutableMultiPolygonArray> for MultiPolygonArray {
     fn from(other: MutableMultiPolygonArray) -> Self {
         let validity = other.validity.and_then(|x| {
@@ -284,3 +438,111 @@ impl From<Vec<Option<MultiPolygon>>> for MutableMultiPolygonArray {
         }
     }
 }
+
+/// Convert a geozero geometry, such as a geometry collection of multi polygons, to a GeoArrow
+/// [`MultiPolygonArray`].
+pub trait ToGeoArrowMultiPolygon {
+    /// Convert to GeoArrow MultiPolygonArray
+    fn to_geoarrow(&self) -> geozero::error::Result<MultiPolygonArray>;
+
+    /// Convert to a GeoArrow MutableMultiPolygonArray
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutableMultiPolygonArray>;
+}
+
+impl<T: GeozeroGeometry> ToGeoArrowMultiPolygon for T {
+    fn to_geoarrow(&self) -> geozero::error::Result<MultiPolygonArray> {
+        Ok(self.to_mutable_geoarrow()?.into())
+    }
+
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutableMultiPolygonArray> {
+        let mut mutable_array = MutableMultiPolygonArray::new();
+        self.process_geom(&mut mutable_array)?;
+        Ok(mutable_array)
+    }
+}
+
+/// Each multi polygon processed is appended as one geometry, and each polygon as a multi polygon
+/// of length one.
+impl GeomProcessor for MutableMultiPolygonArray {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> geozero::error::Result<()> {
+        self.x.push(x);
+        self.y.push(y);
+        Ok(())
+    }
+
+    fn linestring_begin(
+        &mut self,
+        tagged: bool,
+        size: usize,
+        _idx: usize,
+    ) -> geozero::error::Result<()> {
+        if tagged {
+            return Err(GeozeroError::Geometry(
+                "Only polygon and multi polygon geometries allowed".to_string(),
+            ));
+        }
+        self.x.reserve(size);
+        self.y.reserve(size);
+        Ok(())
+    }
+
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> geozero::error::Result<()> {
+        push_offset(&mut self.ring_offsets, self.x.len())
+    }
+
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> geozero::error::Result<()> {
+        push_offset(&mut self.polygon_offsets, self.ring_offsets.len_proxy())?;
+        if tagged {
+            self.multipolygon_end(idx)?;
+        }
+        Ok(())
+    }
+
+    fn multipolygon_end(&mut self, _idx: usize) -> geozero::error::Result<()> {
+        push_offset(&mut self.geom_offsets, self.polygon_offsets.len_proxy())?;
+        if let Some(validity) = &mut self.validity {
+            validity.push(true)
+        }
+        Ok(())
+    }
+
+    reject_geometries!(
+        "Only polygon and multi polygon geometries allowed",
+        [
+            point_begin(_idx: usize),
+            multipoint_begin(_size: usize, _idx: usize),
+            multilinestring_begin(_size: usize, _idx: usize),
+        ]
+    );
+    reject_curves!("Only polygon and multi polygon geometries allowed");
+}
+
+impl PropertyProcessor for MutableMultiPolygonArray {}
+
+/// Features are appended as one geometry each, ignoring their properties.
+impl FeatureProcessor for MutableMultiPolygonArray {}
+
+#[cfg(test)]
+mod test {
+    use super::ToGeoArrowMultiPolygon;
+    use crate::GeometryArrayTrait;
+    use geo::{line_string, polygon, Geometry, GeometryCollection, MultiPolygon};
+
+    #[test]
+    fn from_geozero() {
+        let p0 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let p1 = polygon![(x: 5., y: 5.), (x: 6., y: 5.), (x: 6., y: 6.), (x: 5., y: 5.)];
+        let mp = MultiPolygon(vec![p0.clone(), p1]);
+        let geo = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::MultiPolygon(mp.clone()),
+            Geometry::Polygon(p0.clone()),
+        ]));
+        let arr = geo.to_geoarrow().unwrap();
+        assert_eq!(arr.len(), 2);
+        assert_eq!(arr.value_as_geo(0), mp);
+        assert_eq!(arr.value_as_geo(1), MultiPolygon(vec![p0]));
+
+        let geo = Geometry::LineString(line_string![(x: 0., y: 1.), (x: 1., y: 2.)]);
+        assert!(geo.to_geoarrow().is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::algorithm::bounding_rect::bounding_rect_multipolygon;
 use crate::geo_traits::MultiPolygonTrait;
-use crate::Polygon;
+use crate::{LineString, Polygon};
 use arrow2::buffer::Buffer;
 use arrow2::offset::OffsetsBuffer;
 use rstar::{RTreeObject, AABB};
@@ -16,6 +16,9 @@ pub struct MultiPolygon<'a> {
     /// Buffer of y coordinates
     pub y: &'a Buffer<f64>,
 
+    /// Buffer of m values, if the array has them
+    pub m: Option<&'a Buffer<f64>>,
+
     /// Offsets into the polygon array where each geometry starts
     pub geom_offsets: &'a OffsetsBuffer<i64>,
 
@@ -32,18 +35,18 @@ impl<'a> MultiPolygonTrait<'a> for MultiPolygon<'a> {
     type ItemType = Polygon<'a>;
     type Iter = MultiPolygonIterator<'a>;
 
-    fn polygons(&'a self) -> Self::Iter {
+    fn polygons(&self) -> Self::Iter {
         MultiPolygonIterator::new(self)
     }
 
-    fn num_polygons(&'a self) -> usize {
+    fn num_polygons(&self) -> usize {
         let (start, end) = self.geom_offsets.start_end(self.geom_index);
         end - start
     }
 
-    fn polygon(&'a self, i: usize) -> Option<Self::ItemType> {
+    fn polygon(&self, i: usize) -> Option<Self::ItemType> {
         let (start, end) = self.geom_offsets.start_end(self.geom_index);
-        if i > (end - start) {
+        if i >= end - start {
             return None;
         }
 
@@ -51,6 +54,7 @@ impl<'a> MultiPolygonTrait<'a> for MultiPolygon<'a> {
         Some(Polygon {
             x: self.x,
             y: self.y,
+            m: self.m,
             geom_offsets: self.polygon_offsets,
             ring_offsets: self.ring_offsets,
             geom_index: start + i,
@@ -58,6 +62,48 @@ impl<'a> MultiPolygonTrait<'a> for MultiPolygon<'a> {
     }
 }
 
+impl<'a> MultiPolygon<'a> {
+    /// The exterior rings of the polygons of this multi polygon, in order.
+    pub fn exteriors(&self) -> impl Iterator<Item = LineString<'a>> + 'a {
+        self.clone()
+            .into_polygons()
+            .map(|polygon| polygon.exterior())
+    }
+
+    /// The number of interior rings (holes) of all the polygons of this multi polygon.
+    pub fn num_interior_rings(&self) -> usize {
+        let (start, end) = self.geom_offsets.start_end(self.geom_index);
+        (start..end)
+            .map(|polygon| {
+                let (ring_start, ring_end) = self.polygon_offsets.start_end(polygon);
+                (ring_end - ring_start).saturating_sub(1)
+            })
+            .sum()
+    }
+
+    /// Iterate over the rings of all the polygons of this multi polygon, in order, each exterior
+    /// followed by the interior rings of its polygon.
+    pub fn rings(&self) -> impl Iterator<Item = LineString<'a>> + 'a {
+        self.clone()
+            .into_polygons()
+            .flat_map(|polygon| polygon.rings())
+    }
+
+    /// The polygons of this multi polygon, owning the scalar so that they can borrow its
+    /// buffers for `'a`.
+    fn into_polygons(self) -> impl Iterator<Item = Polygon<'a>> {
+        let (start, end) = self.geom_offsets.start_end(self.geom_index);
+        (start..end).map(move |polygon| Polygon {
+            x: self.x,
+            y: self.y,
+            m: self.m,
+            geom_offsets: self.polygon_offsets,
+            ring_offsets: self.ring_offsets,
+            geom_index: polygon,
+        })
+    }
+}
+
 impl From<MultiPolygon<'_>> for geo::MultiPolygon {
     fn from(value: MultiPolygon<'_>) -> Self {
         (&value).into()
@@ -100,3 +146,75 @@ impl RTreeObject for MultiPolygon<'_> {
         AABB::from_corners(lower, upper)
     }
 }
+
+/// An owned [`MultiPolygon`], holding clones of the buffers of its array.
+#[derive(Debug, Clone)]
+pub struct OwnedMultiPolygon {
+    /// Buffer of x coordinates
+    pub x: Buffer<f64>,
+
+    /// Buffer of y coordinates
+    pub y: Buffer<f64>,
+
+    /// Buffer of m values, if the array has them
+    pub m: Option<Buffer<f64>>,
+
+    /// Offsets into the polygon array where each geometry starts
+    pub geom_offsets: OffsetsBuffer<i64>,
+
+    /// Offsets into the ring array where each polygon starts
+    pub polygon_offsets: OffsetsBuffer<i64>,
+
+    /// Offsets into the coordinate array where each ring starts
+    pub ring_offsets: OffsetsBuffer<i64>,
+
+    pub geom_index: usize,
+}
+
+impl From<MultiPolygon<'_>> for OwnedMultiPolygon {
+    fn from(value: MultiPolygon<'_>) -> Self {
+        Self {
+            x: value.x.clone(),
+            y: value.y.clone(),
+            m: value.m.cloned(),
+            geom_offsets: value.geom_offsets.clone(),
+            polygon_offsets: value.polygon_offsets.clone(),
+            ring_offsets: value.ring_offsets.clone(),
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedMultiPolygon> for MultiPolygon<'a> {
+    fn from(value: &'a OwnedMultiPolygon) -> Self {
+        Self {
+            x: &value.x,
+            y: &value.y,
+            m: value.m.as_ref(),
+            geom_offsets: &value.geom_offsets,
+            polygon_offsets: &value.polygon_offsets,
+            ring_offsets: &value.ring_offsets,
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl From<OwnedMultiPolygon> for geo::MultiPolygon {
+    fn from(value: OwnedMultiPolygon) -> Self {
+        MultiPolygon::from(&value).into()
+    }
+}
+
+impl From<OwnedMultiPolygon> for geo::Geometry {
+    fn from(value: OwnedMultiPolygon) -> Self {
+        geo::Geometry::MultiPolygon(value.into())
+    }
+}
+
+impl RTreeObject for OwnedMultiPolygon {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        MultiPolygon::from(self).envelope()
+    }
+}
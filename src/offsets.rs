@@ -0,0 +1,109 @@
+//! Conversion of Arrow lists with 32-bit offsets to the 64-bit offsets of geometry arrays.
+//!
+//! Many producers (e.g. GDAL and pyarrow) write `List` rather than `LargeList` arrays. Geometry
+//! arrays store `i64` offsets, so these are widened when read.
+
+use arrow2::array::{Array, ListArray};
+use arrow2::datatypes::{DataType, Field};
+use arrow2::offset::OffsetsBuffer;
+
+/// Rebuild the field of a list's values around the widened values.
+fn values_field(field: &Field, values: &dyn Array) -> Field {
+    Field::new(
+        field.name.clone(),
+        values.data_type().clone(),
+        field.is_nullable,
+    )
+    .with_metadata(field.metadata.clone())
+}
+
+/// A copy of `array` with every `List` level, including nested ones, converted to a `LargeList`.
+///
+/// Other arrays are returned unchanged.
+/// # Implementation
+/// Child values are shared, so only the offsets are copied: this is `O(N)` over the number of
+/// lists, not over the number of coordinates.
+pub(crate) fn widen_list_offsets(array: &dyn Array) -> Box<dyn Array> {
+    match array.data_type().to_logical_type() {
+        DataType::List(field) => {
+            let list = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
+            let values = widen_list_offsets(list.values().as_ref());
+            let offsets: OffsetsBuffer<i64> = list.offsets().into();
+            ListArray::<i64>::new(
+                DataType::LargeList(Box::new(values_field(field, values.as_ref()))),
+                offsets,
+                values,
+                list.validity().cloned(),
+            )
+            .boxed()
+        }
+        DataType::LargeList(field) => {
+            let list = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+            let values = widen_list_offsets(list.values().as_ref());
+            ListArray::<i64>::new(
+                DataType::LargeList(Box::new(values_field(field, values.as_ref()))),
+                list.offsets().clone(),
+                values,
+                list.validity().cloned(),
+            )
+            .boxed()
+        }
+        _ => array.to_boxed(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        GeometryArray, GeometryArrayTrait, LineStringArray, MultiPolygonArray, PolygonArray,
+    };
+    use geo::{line_string, polygon};
+
+    /// Narrow every offset of a `LargeList` array to `i32`, as written by pyarrow by default.
+    fn narrow(array: &dyn Array) -> Box<dyn Array> {
+        match array.data_type() {
+            DataType::LargeList(field) => {
+                let list = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+                let values = narrow(list.values().as_ref());
+                let offsets: Vec<i32> = list.offsets().iter().map(|o| *o as i32).collect();
+                ListArray::<i32>::new(
+                    DataType::List(Box::new(values_field(field, values.as_ref()))),
+                    offsets.try_into().unwrap(),
+                    values,
+                    list.validity().cloned(),
+                )
+                .boxed()
+            }
+            _ => array.to_boxed(),
+        }
+    }
+
+    #[test]
+    fn read_i32_offsets() {
+        let line = line_string![(x: 0., y: 0.), (x: 1., y: 1.)];
+        let lines: LineStringArray = vec![Some(line.clone()), None].into();
+        let narrowed = narrow(&lines.into_arrow());
+        assert!(matches!(narrowed.data_type(), DataType::List(_)));
+        let lines = LineStringArray::try_from(narrowed.clone()).unwrap();
+        assert_eq!(lines.get_as_geo(0), Some(line));
+        assert!(lines.is_null(1));
+        assert!(matches!(
+            GeometryArray::from_arrow(narrowed.as_ref(), false),
+            GeometryArray::LineString(_)
+        ));
+
+        let polygon = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let polygons: PolygonArray = vec![polygon.clone()].into();
+        let narrowed = narrow(&polygons.into_arrow());
+        let polygons = PolygonArray::try_from(narrowed).unwrap();
+        assert_eq!(polygons.value_as_geo(0), polygon);
+
+        let multi_polygon = geo::MultiPolygon(vec![polygon.clone(), polygon]);
+        let multi_polygons: MultiPolygonArray = vec![multi_polygon.clone()].into();
+        let narrowed = narrow(&multi_polygons.into_arrow());
+        let list = narrowed.as_any().downcast_ref::<ListArray<i32>>().unwrap();
+        let multi_polygons = MultiPolygonArray::try_from(list.clone()).unwrap();
+        assert_eq!(multi_polygons.value_as_geo(0), multi_polygon);
+    }
+}
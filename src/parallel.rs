@@ -0,0 +1,101 @@
+//! Order-preserving data parallelism, on rayon's thread pool with the `rayon` feature and on
+//! scoped threads otherwise.
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// The minimum number of rows a rayon task processes, so that cheap per-row kernels are not
+/// dominated by scheduling overhead.
+#[cfg(feature = "rayon")]
+pub(crate) const MIN_ROWS_PER_TASK: usize = 1024;
+
+/// Apply `f` to every row index in `0..len`, preserving order.
+///
+/// With the `rayon` feature the rows are split into chunks processed on rayon's thread pool;
+/// otherwise they are processed sequentially on the calling thread.
+pub(crate) fn map_rows<R: Send>(len: usize, f: impl Fn(usize) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    {
+        (0..len)
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(f)
+            .collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        (0..len).map(f).collect()
+    }
+}
+
+/// Apply `f` to every item on rayon's thread pool, preserving order.
+#[cfg(feature = "rayon")]
+pub(crate) fn parallel_map<T: Sync, R: Send>(
+    items: &[T],
+    f: impl Fn(&T) -> R + Sync + Send,
+) -> Vec<R> {
+    items.par_iter().map(f).collect()
+}
+
+/// Apply `f` to every item on a pool of scoped threads, preserving order.
+#[cfg(not(feature = "rayon"))]
+pub(crate) fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let num_threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(items.len())
+        .max(1);
+    let chunk_size = items.len().div_ceil(num_threads);
+    if chunk_size == 0 {
+        return vec![];
+    }
+
+    let f = &f;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("worker thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parallel_map_keeps_order() {
+        let items: Vec<usize> = (0..100).collect();
+        assert_eq!(
+            parallel_map(&items, |i| i * 2),
+            (0..200).step_by(2).collect::<Vec<_>>()
+        );
+        assert!(parallel_map(&Vec::<usize>::new(), |i| *i).is_empty());
+    }
+
+    #[test]
+    fn map_rows_keeps_order() {
+        assert_eq!(map_rows(5000, |i| i + 1), (1..5001).collect::<Vec<_>>());
+        assert!(map_rows(0, |i| i).is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_iter_geo() {
+        use crate::PolygonArray;
+        use geo::polygon;
+
+        let p0 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let arr: PolygonArray = vec![Some(p0.clone()), None].into();
+        let geoms: Vec<_> = arr.par_iter_geo().collect();
+        assert_eq!(geoms, vec![Some(p0), None]);
+        assert_eq!(
+            arr.par_iter().map(|g| g.is_some()).collect::<Vec<_>>(),
+            vec![true, false]
+        );
+    }
+}
@@ -0,0 +1,446 @@
+//! Chunk-parallel execution of graphs of geometry kernels.
+//!
+//! A [`Pipeline`] is a directed acyclic graph of operations over the geometry column of each
+//! chunk, rooted at its [input](Pipeline::INPUT). [`Pipeline::map`] and [`Pipeline::then`] chain
+//! an operation onto the last node added, while [`Pipeline::add_map`] and [`Pipeline::add_then`]
+//! branch from any earlier node, and [`Pipeline::execute_nodes`] computes several nodes in one
+//! run.
+//!
+//! Per-geometry [`Pipeline::map`] operations reading the same array, directly or through other
+//! maps, are fused into one stage: each geometry of the array is converted to a
+//! [`geo::Geometry`] once and passed through every operation of the stage in turn, being cloned
+//! where the graph branches. Only the nodes whose arrays are needed (the requested nodes and the
+//! inputs of whole-array kernels) are collected into arrays. So a reprojection followed by two
+//! simplifications at different tolerances converts each chunk once and builds two arrays,
+//! rather than one per operation. Fusion works on [`geo::Geometry`] values rather than on Arrow
+//! buffers, so each row of a stage is still materialized as a `geo` geometry. Whole-array
+//! [`Pipeline::then`] kernels act as fusion barriers.
+//!
+//! Chunks are independent, so they are processed concurrently, on rayon's thread pool with the
+//! `rayon` feature and on scoped threads otherwise.
+
+use crate::coerce::{from_geo, replace_geometry};
+use crate::error::GeoArrowError;
+use crate::parallel::parallel_map;
+use crate::table::GeoTable;
+use crate::{GeometryArray, GeometryArrayTrait};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+type MapFn = Arc<dyn Fn(geo::Geometry) -> Result<geo::Geometry, GeoArrowError> + Send + Sync>;
+type ArrayFn = Arc<dyn Fn(GeometryArray) -> Result<GeometryArray, GeoArrowError> + Send + Sync>;
+
+#[derive(Clone)]
+enum Operation {
+    /// A per-geometry transform
+    Map(MapFn),
+
+    /// A kernel over a whole array
+    Array(ArrayFn),
+}
+
+/// An operation and the node it reads.
+#[derive(Clone)]
+struct Node {
+    input: NodeId,
+    operation: Operation,
+}
+
+/// A node of a [`Pipeline`]: its input, or the output of one of its operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// Which nodes a run computes.
+struct Plan {
+    /// Whether each node is requested or read, directly or not, by a requested node
+    needed: Vec<bool>,
+    /// Whether each needed node is built into an array, rather than only flowing through a stage
+    materialized: Vec<bool>,
+}
+
+/// A directed acyclic graph of geometry operations executed chunk-parallel with operator fusion.
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    /// The operations, where `nodes[i]` computes the node `NodeId(i + 1)`
+    nodes: Vec<Node>,
+}
+
+impl Pipeline {
+    /// The node holding the array the pipeline runs over.
+    pub const INPUT: NodeId = NodeId(0);
+
+    /// Create an empty pipeline, which returns its input unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The last node added, or the input of an empty pipeline. This is the node computed by
+    /// [`Pipeline::execute`].
+    pub fn output(&self) -> NodeId {
+        NodeId(self.nodes.len())
+    }
+
+    /// Append a per-geometry operation reading the last node added. Null geometries are skipped.
+    pub fn map<F>(mut self, f: F) -> Self
+    where
+        F: Fn(geo::Geometry) -> Result<geo::Geometry, GeoArrowError> + Send + Sync + 'static,
+    {
+        self.add_map(self.output(), f);
+        self
+    }
+
+    /// Append a whole-array kernel reading the last node added.
+    pub fn then<F>(mut self, f: F) -> Self
+    where
+        F: Fn(GeometryArray) -> Result<GeometryArray, GeoArrowError> + Send + Sync + 'static,
+    {
+        self.add_then(self.output(), f);
+        self
+    }
+
+    /// Add a per-geometry operation reading `input`, returning its node. Null geometries are
+    /// skipped.
+    ///
+    /// # Panics
+    /// Panics if `input` is not a node of this pipeline.
+    pub fn add_map<F>(&mut self, input: NodeId, f: F) -> NodeId
+    where
+        F: Fn(geo::Geometry) -> Result<geo::Geometry, GeoArrowError> + Send + Sync + 'static,
+    {
+        self.add(input, Operation::Map(Arc::new(f)))
+    }
+
+    /// Add a whole-array kernel reading `input`, returning its node.
+    ///
+    /// # Panics
+    /// Panics if `input` is not a node of this pipeline.
+    pub fn add_then<F>(&mut self, input: NodeId, f: F) -> NodeId
+    where
+        F: Fn(GeometryArray) -> Result<GeometryArray, GeoArrowError> + Send + Sync + 'static,
+    {
+        self.add(input, Operation::Array(Arc::new(f)))
+    }
+
+    fn add(&mut self, input: NodeId, operation: Operation) -> NodeId {
+        assert!(
+            input.0 <= self.nodes.len(),
+            "{input:?} is not a node of this pipeline"
+        );
+        self.nodes.push(Node { input, operation });
+        self.output()
+    }
+
+    /// The number of passes over each chunk to compute [`Pipeline::output`], after fusing map
+    /// operations.
+    pub fn num_stages(&self) -> usize {
+        let plan = self.plan(&[self.output()]);
+        let roots = self.roots();
+        let mut map_stages = HashSet::new();
+        let mut array_stages = 0;
+        for (i, node) in self.nodes.iter().enumerate() {
+            if plan.needed[i + 1] {
+                match node.operation {
+                    Operation::Map(_) => {
+                        map_stages.insert(roots[i + 1]);
+                    }
+                    Operation::Array(_) => array_stages += 1,
+                }
+            }
+        }
+        map_stages.len() + array_stages
+    }
+
+    /// The array each node is computed from, row by row: the node itself for the input and
+    /// whole-array kernels, and the nearest such node it reads for maps.
+    fn roots(&self) -> Vec<NodeId> {
+        let mut roots = vec![Self::INPUT];
+        for (i, node) in self.nodes.iter().enumerate() {
+            roots.push(match node.operation {
+                Operation::Map(_) => roots[node.input.0],
+                Operation::Array(_) => NodeId(i + 1),
+            });
+        }
+        roots
+    }
+
+    fn plan(&self, outputs: &[NodeId]) -> Plan {
+        let mut needed = vec![false; self.nodes.len() + 1];
+        let mut materialized = vec![false; self.nodes.len() + 1];
+        for output in outputs {
+            needed[output.0] = true;
+            materialized[output.0] = true;
+        }
+        materialized[Self::INPUT.0] = true;
+        for (i, node) in self.nodes.iter().enumerate().rev() {
+            if needed[i + 1] {
+                needed[node.input.0] = true;
+                if let Operation::Array(_) = node.operation {
+                    materialized[i + 1] = true;
+                    materialized[node.input.0] = true;
+                }
+            }
+        }
+        Plan {
+            needed,
+            materialized,
+        }
+    }
+
+    /// Run the pipeline over a single array, computing [`Pipeline::output`].
+    pub fn execute(&self, array: GeometryArray) -> Result<GeometryArray, GeoArrowError> {
+        let mut outputs = self.execute_nodes(array, &[self.output()])?;
+        Ok(outputs.remove(0))
+    }
+
+    /// Run the pipeline over a single array, computing each of `outputs`. Nodes that several
+    /// outputs read are computed once.
+    ///
+    /// # Errors
+    /// Errors if an operation fails, or if one of `outputs` is not a node of this pipeline.
+    pub fn execute_nodes(
+        &self,
+        array: GeometryArray,
+        outputs: &[NodeId],
+    ) -> Result<Vec<GeometryArray>, GeoArrowError> {
+        if let Some(output) = outputs.iter().find(|output| output.0 > self.nodes.len()) {
+            return Err(GeoArrowError::General(format!(
+                "{output:?} is not a node of this pipeline"
+            )));
+        }
+        let plan = self.plan(outputs);
+        let roots = self.roots();
+
+        let mut arrays: Vec<Option<GeometryArray>> = (0..=self.nodes.len()).map(|_| None).collect();
+        arrays[Self::INPUT.0] = Some(array);
+        for id in (0..=self.nodes.len()).map(NodeId) {
+            if !plan.needed[id.0] || roots[id.0] != id {
+                continue;
+            }
+            if let Some(Node {
+                input,
+                operation: Operation::Array(f),
+            }) = id.0.checked_sub(1).map(|i| &self.nodes[i])
+            {
+                // Inputs come before the nodes reading them, so were built earlier
+                let input = arrays[input.0].as_ref().unwrap();
+                arrays[id.0] = Some(f(*input.to_boxed())?);
+            }
+            self.execute_stage(id, &plan, &roots, &mut arrays)?;
+        }
+
+        Ok(outputs
+            .iter()
+            .map(|output| *arrays[output.0].as_ref().unwrap().to_boxed())
+            .collect())
+    }
+
+    /// Run the map operations computed from the array of `root` over each of its rows, building
+    /// the arrays of the materialized ones.
+    fn execute_stage(
+        &self,
+        root: NodeId,
+        plan: &Plan,
+        roots: &[NodeId],
+        arrays: &mut [Option<GeometryArray>],
+    ) -> Result<(), GeoArrowError> {
+        let stage: Vec<(NodeId, NodeId, &MapFn)> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| plan.needed[i + 1] && roots[i + 1] == root)
+            .filter_map(|(i, node)| match &node.operation {
+                Operation::Map(f) => Some((NodeId(i + 1), node.input, f)),
+                Operation::Array(_) => None,
+            })
+            .collect();
+        if stage.is_empty() {
+            return Ok(());
+        }
+
+        // A node's geometry is moved into the last operation of the stage reading it, and
+        // cloned for the others
+        let mut last_reader = vec![None; self.nodes.len() + 1];
+        for (id, input, _) in &stage {
+            last_reader[input.0] = Some(*id);
+        }
+
+        let array = arrays[root.0].as_ref().unwrap();
+        let (kind, crs) = (array.geometry_type(), array.crs().map(str::to_string));
+        let mut values: Vec<Option<geo::Geometry>> = vec![None; self.nodes.len() + 1];
+        let mut output_geoms: Vec<Vec<Option<geo::Geometry>>> = vec![vec![]; stage.len()];
+        for i in 0..array.len() {
+            values[root.0] = array.get_as_geo(i);
+            for ((id, input, f), geoms) in stage.iter().zip(output_geoms.iter_mut()) {
+                let geom = match last_reader[input.0] == Some(*id) {
+                    true => values[input.0].take(),
+                    false => values[input.0].clone(),
+                };
+                values[id.0] = geom.map(|geom| f(geom)).transpose()?;
+                if plan.materialized[id.0] {
+                    geoms.push(values[id.0].clone());
+                }
+            }
+        }
+
+        for ((id, _, _), geoms) in stage.into_iter().zip(output_geoms) {
+            if plan.materialized[id.0] {
+                arrays[id.0] = Some(from_geo(geoms, kind).with_crs(crs.as_deref()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the pipeline over each chunk concurrently, preserving chunk order.
+    pub fn execute_chunks(
+        &self,
+        chunks: Vec<GeometryArray>,
+    ) -> Result<Vec<GeometryArray>, GeoArrowError> {
+        // Copying a chunk only copies the references to its buffers
+        parallel_map(&chunks, |chunk| self.execute(*chunk.to_boxed()))
+            .into_iter()
+            .collect()
+    }
+
+    /// Run the pipeline over the geometry column of a [`GeoTable`].
+    ///
+    /// If chunks end up with different geometry types, every chunk is stored as WKB so that the
+    /// output column has a single data type.
+    pub fn execute_table(&self, table: &GeoTable) -> Result<GeoTable, GeoArrowError> {
+        let outputs = self.execute_chunks(table.geometry()?)?;
+        replace_geometry(table, outputs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LineStringArray, PointArray};
+    use arrow2::array::Int32Array;
+    use arrow2::chunk::Chunk;
+    use arrow2::datatypes::{Field, Schema};
+    use geo::{line_string, point, Translate};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn points() -> GeometryArray {
+        let arr: PointArray = vec![Some(point!(x: 0., y: 0.)), None].into();
+        GeometryArray::Point(arr)
+    }
+
+    #[test]
+    fn fuses_consecutive_maps() {
+        let pipeline = Pipeline::new()
+            .map(|geom| Ok(geom.translate(1., 0.)))
+            .map(|geom| Ok(geom.translate(0., 1.)))
+            .then(Ok)
+            .map(Ok);
+        assert_eq!(pipeline.num_stages(), 3);
+
+        let output = pipeline.execute(points()).unwrap();
+        match output {
+            GeometryArray::Point(arr) => {
+                assert_eq!(arr.get_as_geo(0), Some(point!(x: 1., y: 1.)));
+                assert!(arr.get_as_geo(1).is_none());
+            }
+            _ => panic!("expected a point array"),
+        }
+    }
+
+    #[test]
+    fn branches() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut pipeline = Pipeline::new();
+        let shifted = pipeline.add_map(Pipeline::INPUT, move |geom| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(geom.translate(1., 0.))
+        });
+        let up = pipeline.add_map(shifted, |geom| Ok(geom.translate(0., 1.)));
+        let down = pipeline.add_map(shifted, |geom| Ok(geom.translate(0., -1.)));
+        let wkb = pipeline.add_then(shifted, |array| Ok(GeometryArray::WKB(array.into())));
+
+        // Computing `wkb` takes one map stage and one kernel
+        assert_eq!(pipeline.output(), wkb);
+        assert_eq!(pipeline.num_stages(), 2);
+
+        let outputs = pipeline
+            .execute_nodes(points(), &[up, down, wkb, Pipeline::INPUT])
+            .unwrap();
+        // The shared operation runs once for the single non-null row
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let point_at = |array: &GeometryArray| match array {
+            GeometryArray::Point(arr) => arr.get_as_geo(0),
+            _ => panic!("expected a point array"),
+        };
+        assert_eq!(point_at(&outputs[0]), Some(point!(x: 1., y: 1.)));
+        assert_eq!(point_at(&outputs[1]), Some(point!(x: 1., y: -1.)));
+        match &outputs[2] {
+            GeometryArray::WKB(arr) => {
+                assert_eq!(arr.get_as_geo(0), Some(point!(x: 1., y: 0.).into()));
+                assert!(arr.get_as_geo(1).is_none());
+            }
+            _ => panic!("expected a WKB array"),
+        }
+        assert_eq!(point_at(&outputs[3]), Some(point!(x: 0., y: 0.)));
+
+        assert!(pipeline.execute_nodes(points(), &[NodeId(10)]).is_err());
+    }
+
+    #[test]
+    fn keeps_crs() {
+        let pipeline = Pipeline::new().map(|geom| Ok(geom.translate(1., 0.)));
+        let output = pipeline
+            .execute(points().with_crs(Some("EPSG:4326")))
+            .unwrap();
+        assert_eq!(output.crs(), Some("EPSG:4326"));
+    }
+
+    #[test]
+    fn output_type_follows_geometries() {
+        let pipeline = Pipeline::new().map(|geom| match geom {
+            geo::Geometry::Point(p) => Ok(geo::Geometry::LineString(line_string![p.0, p.0])),
+            geom => Ok(geom),
+        });
+        let arr: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 1., y: 1.)]].into();
+        let chunks = vec![points(), GeometryArray::LineString(arr)];
+
+        let outputs = pipeline.execute_chunks(chunks).unwrap();
+        assert!(matches!(outputs[0], GeometryArray::LineString(_)));
+        assert!(matches!(outputs[1], GeometryArray::LineString(_)));
+
+        // Rows of different types can only be held as WKB
+        let pipeline = Pipeline::new().map(|geom| match geom {
+            geo::Geometry::Point(p) if p.x() > 0. => Ok(geom),
+            _ => Ok(geo::Geometry::LineString(
+                line_string![(x: 0., y: 0.), (x: 1., y: 1.)],
+            )),
+        });
+        let arr: PointArray = vec![point!(x: 0., y: 0.), point!(x: 1., y: 1.)].into();
+        let output = pipeline.execute(GeometryArray::Point(arr)).unwrap();
+        assert!(matches!(output, GeometryArray::WKB(_)));
+    }
+
+    #[test]
+    fn errors_propagate() {
+        let pipeline = Pipeline::new().map(|_| Err(GeoArrowError::General("failed".to_string())));
+        assert!(pipeline.execute_chunks(vec![points(), points()]).is_err());
+    }
+
+    #[test]
+    fn table() {
+        let geometry = points().into_arrow();
+        let schema = Schema::from(vec![
+            Field::new("id", arrow2::datatypes::DataType::Int32, false),
+            Field::new("geometry", geometry.data_type().clone(), true),
+        ]);
+        let chunk = Chunk::new(vec![Int32Array::from_slice([0, 1]).boxed(), geometry]);
+        let table = GeoTable::try_new(schema, vec![chunk.clone(), chunk], 1).unwrap();
+
+        let pipeline = Pipeline::new().map(|geom| Ok(geom.translate(1., 1.)));
+        let output = pipeline.execute_table(&table).unwrap();
+        assert_eq!(output.len(), 4);
+        match &output.geometry().unwrap()[1] {
+            GeometryArray::Point(arr) => assert_eq!(arr.get_as_geo(0), Some(point!(x: 1., y: 1.))),
+            _ => panic!("expected a point array"),
+        }
+    }
+}
@@ -1,13 +1,24 @@
+use crate::concat::concat_crs;
+use crate::coords::{concat_m, coord_fields, coords_have_m, impl_coords};
+use crate::crs::impl_crs;
 use crate::error::GeoArrowError;
+#[cfg(feature = "rayon")]
+use crate::parallel::MIN_ROWS_PER_TASK;
 use crate::slice::slice_validity_unchecked;
-use crate::{GeometryArrayTrait, MutablePointArray};
+use crate::trait_::{Dimension, GeometryArrayType};
+use crate::validity::check_validity_len;
+use crate::{GeometryArrayTrait, MultiPointArray, MutablePointArray};
 use arrow2::array::{Array, PrimitiveArray, StructArray};
 use arrow2::bitmap::utils::{BitmapIter, ZipValidity};
 use arrow2::bitmap::Bitmap;
 use arrow2::buffer::Buffer;
-use arrow2::datatypes::{DataType, Field};
+use arrow2::datatypes::DataType;
+use arrow2::offset::Offsets;
 use geozero::{GeomProcessor, GeozeroGeometry};
-use rstar::RTree;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::ops::Range;
+use std::sync::Arc;
 
 /// A [`GeometryArrayTrait`] semantically equivalent to `Vec<Option<Point>>` using Arrow's
 /// in-memory representation.
@@ -15,7 +26,10 @@ use rstar::RTree;
 pub struct PointArray {
     x: Buffer<f64>,
     y: Buffer<f64>,
+    /// m values (measures), one per point, if any
+    m: Option<Buffer<f64>>,
     validity: Option<Bitmap>,
+    crs: Option<Arc<str>>,
 }
 
 pub(super) fn check(
@@ -43,7 +57,13 @@ impl PointArray {
     /// This function is `O(1)`.
     pub fn new(x: Buffer<f64>, y: Buffer<f64>, validity: Option<Bitmap>) -> Self {
         check(&x, &y, validity.as_ref().map(|v| v.len())).unwrap();
-        Self { x, y, validity }
+        Self {
+            x,
+            y,
+            m: None,
+            validity,
+            crs: None,
+        }
     }
 
     /// Create a new PointArray from parts
@@ -55,24 +75,50 @@ impl PointArray {
         validity: Option<Bitmap>,
     ) -> Result<Self, GeoArrowError> {
         check(&x, &y, validity.as_ref().map(|v| v.len()))?;
-        Ok(Self { x, y, validity })
+        Ok(Self {
+            x,
+            y,
+            m: None,
+            validity,
+            crs: None,
+        })
     }
 
-    /// The values [`Buffer`].
-    /// Values on null slots are undetermined (they can be anything).
-    #[inline]
-    pub fn values_x(&self) -> &Buffer<f64> {
-        &self.x
+    /// Concatenate `arrays` into one array, copying their coordinates and rebuilding offsets.
+    ///
+    /// Arrays without a CRS are assumed to be in the CRS of the others. m values are kept if
+    /// every array has them.
+    /// # Errors
+    /// Errors if two arrays have different CRSes.
+    pub fn concat(arrays: &[&PointArray]) -> Result<Self, GeoArrowError> {
+        let crs = concat_crs(arrays.iter().map(|array| array.crs()))?;
+        let mut mutable = MutablePointArray::new();
+        for array in arrays {
+            mutable.extend_from_array(array);
+        }
+        let array = Self::from(mutable).with_crs(crs);
+        match concat_m(
+            arrays
+                .iter()
+                .map(|array| array.values_m().map(|m| m.as_slice())),
+        ) {
+            Some(m) => array.with_m(m),
+            None => Ok(array),
+        }
     }
+}
 
-    /// The values [`Buffer`].
-    /// Values on null slots are undetermined (they can be anything).
-    #[inline]
-    pub fn values_y(&self) -> &Buffer<f64> {
-        &self.y
+impl_crs!(PointArray);
+
+impl PointArray {
+    /// The range of the coordinate buffers holding the coordinates of the geometries `geoms`.
+    pub(crate) fn coord_range(&self, geoms: Range<usize>) -> Range<usize> {
+        geoms
     }
 }
 
+impl_coords!(PointArray);
+
 impl<'a> GeometryArrayTrait<'a> for PointArray {
     type Scalar = crate::Point<'a>;
     type ScalarGeo = geo::Point;
@@ -82,34 +128,23 @@ impl<'a> GeometryArrayTrait<'a> for PointArray {
         crate::Point {
             x: &self.x,
             y: &self.y,
+            m: self.m.as_ref(),
             geom_index: i,
         }
     }
 
+    /// A struct array with `x` and `y` fields, and an `m` field if this array has m values.
     fn into_arrow(self) -> StructArray {
-        let field_x = Field::new("x", DataType::Float64, false);
-        let field_y = Field::new("y", DataType::Float64, false);
-
-        let array_x = PrimitiveArray::new(DataType::Float64, self.x, None).boxed();
-        let array_y = PrimitiveArray::new(DataType::Float64, self.y, None).boxed();
-
-        let struct_data_type = DataType::Struct(vec![field_x, field_y]);
-        let struct_values = vec![array_x, array_y];
-
-        let validity: Option<Bitmap> = if let Some(validity) = self.validity {
-            validity.into()
-        } else {
-            None
-        };
+        let (fields, values) = coord_fields(self.x, self.y, self.m);
+        StructArray::new(DataType::Struct(fields), values, self.validity)
+    }
 
-        StructArray::new(struct_data_type, struct_values, validity)
+    fn geometry_type(&self) -> GeometryArrayType {
+        GeometryArrayType::Point
     }
 
-    /// Build a spatial index containing this array's geometries
-    fn rstar_tree(&'a self) -> RTree<Self::Scalar> {
-        let mut tree = RTree::new();
-        self.iter().flatten().for_each(|geom| tree.insert(geom));
-        tree
+    fn dimension(&self) -> Dimension {
+        Dimension::new(false, self.m.is_some())
     }
 
     /// Returns the number of geometries in this array
@@ -157,6 +192,17 @@ impl<'a> GeometryArrayTrait<'a> for PointArray {
         slice_validity_unchecked(&mut self.validity, offset, length);
         self.x.slice_unchecked(offset, length);
         self.y.slice_unchecked(offset, length);
+        if let Some(m) = self.m.as_mut() {
+            m.slice_unchecked(offset, length);
+        }
+    }
+
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        check_validity_len(validity.as_ref(), self.len());
+        Self {
+            validity,
+            ..self.clone()
+        }
     }
 
     fn to_boxed(&self) -> Box<Self> {
@@ -178,6 +224,24 @@ impl PointArray {
         ZipValidity::new_with_validity(self.iter_geo_values(), self.validity())
     }
 
+    /// Parallel iterator over the scalars of this array, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = Option<crate::Point<'_>>> {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get(i))
+    }
+
+    /// Parallel iterator over geo Geometry objects, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_geo(&self) -> impl IndexedParallelIterator<Item = Option<geo::Point>> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get_as_geo(i))
+    }
+
     /// Returns the value at slot `i` as a GEOS geometry.
     #[cfg(feature = "geos")]
     pub fn value_as_geos(&self, i: usize) -> geos::Geometry {
@@ -209,33 +273,37 @@ impl PointArray {
     }
 }
 
+/// Downcast a coordinate child array to its concrete type.
+fn downcast_coords(array: &dyn Array) -> Result<&PrimitiveArray<f64>, GeoArrowError> {
+    array
+        .as_any()
+        .downcast_ref::<PrimitiveArray<f64>>()
+        .ok_or_else(|| GeoArrowError::Downcast {
+            expected: "PrimitiveArray<f64>",
+            found: array.data_type().clone(),
+        })
+}
+
 impl TryFrom<StructArray> for PointArray {
     type Error = GeoArrowError;
 
     fn try_from(value: StructArray) -> Result<Self, Self::Error> {
         let arrays = value.values();
         let validity = value.validity();
+        let has_m = coords_have_m(&value)?;
 
-        if !arrays.len() == 2 {
-            return Err(GeoArrowError::General(
-                "Expected two child arrays of this StructArray.".to_string(),
-            ));
-        }
-
-        let x_array_values = arrays[0]
-            .as_any()
-            .downcast_ref::<PrimitiveArray<f64>>()
-            .unwrap();
-        let y_array_values = arrays[1]
-            .as_any()
-            .downcast_ref::<PrimitiveArray<f64>>()
-            .unwrap();
+        let x_array_values = downcast_coords(arrays[0].as_ref())?;
+        let y_array_values = downcast_coords(arrays[1].as_ref())?;
 
-        Ok(Self::new(
+        let array = Self::try_new(
             x_array_values.values().clone(),
             y_array_values.values().clone(),
             validity.cloned(),
-        ))
+        )?;
+        match has_m {
+            true => array.with_m(downcast_coords(arrays[2].as_ref())?.values().clone()),
+            false => Ok(array),
+        }
     }
 }
 
@@ -243,29 +311,20 @@ impl TryFrom<Box<dyn Array>> for PointArray {
     type Error = GeoArrowError;
 
     fn try_from(value: Box<dyn Array>) -> Result<Self, Self::Error> {
-        let arr = value.as_any().downcast_ref::<StructArray>().unwrap();
+        let arr = value
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .ok_or_else(|| GeoArrowError::Downcast {
+                expected: "StructArray",
+                found: value.data_type().clone(),
+            })?;
         arr.clone().try_into()
     }
 }
 
 impl From<PointArray> for StructArray {
     fn from(value: PointArray) -> Self {
-        let field_x = Field::new("x", DataType::Float64, false);
-        let field_y = Field::new("y", DataType::Float64, false);
-
-        let array_x = PrimitiveArray::<f64>::new(DataType::Float64, value.x, None);
-        let array_y = PrimitiveArray::<f64>::new(DataType::Float64, value.y, None);
-
-        let struct_data_type = DataType::Struct(vec![field_x, field_y]);
-        let struct_values: Vec<Box<dyn Array>> = vec![array_x.boxed(), array_y.boxed()];
-
-        let validity: Option<Bitmap> = if let Some(validity) = value.validity {
-            validity.into()
-        } else {
-            None
-        };
-
-        StructArray::new(struct_data_type, struct_values, validity)
+        value.into_arrow()
     }
 }
 
@@ -283,6 +342,18 @@ impl From<Vec<geo::Point>> for PointArray {
     }
 }
 
+/// Wrap each point in a multi point of length one
+impl From<PointArray> for MultiPointArray {
+    fn from(value: PointArray) -> Self {
+        let geom_offsets = Offsets::<i64>::try_from_lengths((0..value.len()).map(|_| 1))
+            .unwrap()
+            .into();
+        Self::new(value.x, value.y, geom_offsets, value.validity)
+            .with_crs(value.crs.as_deref())
+            .with_optional_m(value.m)
+    }
+}
+
 impl GeozeroGeometry for PointArray {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> geozero::error::Result<()>
     where
@@ -330,7 +401,7 @@ mod test {
     fn geozero_process_geom() -> geozero::error::Result<()> {
         let points: Vec<Point> = vec![p0(), p1(), p2()];
         let point_array: PointArray = points.into();
-        let wkt = point_array.to_wkt()?;
+        let wkt = ToWkt::to_wkt(&point_array)?;
         let expected = "GEOMETRYCOLLECTION(POINT(0 1),POINT(1 2),POINT(2 3))";
         assert_eq!(wkt, expected);
         Ok(())
@@ -344,4 +415,37 @@ mod test {
         assert_eq!(point_array.len(), 1);
         assert_eq!(point_array.get_as_geo(0), Some(p1()));
     }
+
+    #[test]
+    fn m_values() {
+        let points: PointArray = vec![p0(), p1(), p2()].into();
+        let mut points = points.with_m(vec![10., 20., 30.].into()).unwrap();
+        assert_eq!(points.dimension(), Dimension::XYM);
+        assert_eq!(points.value(1).m(), Some(20.));
+
+        let round_trip = PointArray::try_from(points.clone().into_arrow()).unwrap();
+        assert_eq!(round_trip.values_m().unwrap().as_slice(), &[10., 20., 30.]);
+
+        points.slice(2, 1);
+        assert_eq!(points.value(0).m(), Some(30.));
+        let concat = PointArray::concat(&[&points, &points]).unwrap();
+        assert_eq!(concat.values_m().unwrap().as_slice(), &[30., 30.]);
+
+        let xy: PointArray = vec![p0()].into();
+        assert_eq!(xy.value(0).m(), None);
+        assert!(xy.with_m(vec![].into()).is_err());
+    }
+
+    #[test]
+    fn try_from_wrong_type() {
+        let arr = PrimitiveArray::<f64>::from_slice([1.]).boxed();
+        let err = PointArray::try_from(arr).unwrap_err();
+        assert!(matches!(
+            err,
+            GeoArrowError::Downcast {
+                found: DataType::Float64,
+                ..
+            }
+        ));
+    }
 }
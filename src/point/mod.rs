@@ -1,8 +1,8 @@
 //! Helpers for using Point GeoArrow data
 
 pub use array::PointArray;
-pub use mutable::MutablePointArray;
-pub use scalar::Point;
+pub use mutable::{MutablePointArray, ToGeoArrowPoint};
+pub use scalar::{OwnedPoint, Point};
 
 mod array;
 mod iterator;
@@ -1,9 +1,11 @@
+use crate::concat::extend_validity;
 use crate::error::GeoArrowError;
+use crate::geo_traits::PointTrait;
 use crate::trait_::{GeometryArrayTrait, MutableGeometryArray};
 use arrow2::array::StructArray;
 use arrow2::bitmap::{Bitmap, MutableBitmap};
 use geo::Point;
-use geozero::{GeomProcessor, GeozeroGeometry};
+use geozero::{FeatureProcessor, GeomProcessor, GeozeroGeometry, PropertyProcessor};
 
 use super::array::{check, PointArray};
 
@@ -48,13 +50,60 @@ impl MutablePointArray {
         Ok(Self { x, y, validity })
     }
 
+    /// Append the geometries of `other` to this array.
+    ///
+    /// Mutable arrays carry no CRS, so the CRS of `other` is dropped.
+    pub fn extend_from_array(&mut self, other: &PointArray) {
+        extend_validity(
+            &mut self.validity,
+            self.x.len(),
+            other.validity(),
+            other.len(),
+        );
+        self.x.extend_from_slice(other.values_x());
+        self.y.extend_from_slice(other.values_y());
+    }
+
     /// Extract the low-level APIs from the [`MutablePointArray`].
     pub fn into_inner(self) -> (Vec<f64>, Vec<f64>, Option<MutableBitmap>) {
         (self.x, self.y, self.validity)
     }
 
+    /// Reserves capacity for at least `additional` more points.
+    pub fn reserve(&mut self, additional: usize) {
+        self.x.reserve(additional);
+        self.y.reserve(additional);
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional);
+        }
+    }
+
+    /// Reserves capacity for exactly `additional` more points. The validity may still reserve more
+    /// than needed.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.x.reserve_exact(additional);
+        self.y.reserve_exact(additional);
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional);
+        }
+    }
+
+    /// Shrinks the capacity of the buffers of this array to fit their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        self.x.shrink_to_fit();
+        self.y.shrink_to_fit();
+        if let Some(validity) = &mut self.validity {
+            validity.shrink_to_fit();
+        }
+    }
+
     /// Adds a new value to the array.
     pub fn push_geo(&mut self, value: Option<Point>) {
+        self.push_point(value.as_ref())
+    }
+
+    /// Adds a new point to the array, or a null if `value` is `None`.
+    pub fn push_point(&mut self, value: Option<&impl PointTrait>) {
         match value {
             Some(value) => {
                 self.x.push(value.x());
@@ -64,15 +113,18 @@ impl MutablePointArray {
                     None => {}
                 }
             }
+            None => self.push_null(),
+        }
+    }
+
+    /// Adds a new null to the array.
+    pub fn push_null(&mut self) {
+        self.x.push(f64::default());
+        self.y.push(f64::default());
+        match &mut self.validity {
+            Some(validity) => validity.push(false),
             None => {
-                self.x.push(f64::default());
-                self.y.push(f64::default());
-                match &mut self.validity {
-                    Some(validity) => validity.push(false),
-                    None => {
-                        self.init_validity();
-                    }
-                }
+                self.init_validity();
             }
         }
     }
@@ -125,6 +177,10 @@ impl MutableGeometryArray for MutablePointArray {
     fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn push_null(&mut self) {
+        MutablePointArray::push_null(self)
+    }
 }
 
 impl Default for MutablePointArray {
@@ -326,6 +382,11 @@ impl GeomProcessor for MutablePointArray {
     }
 }
 
+impl PropertyProcessor for MutablePointArray {}
+
+/// Features are appended as one geometry each, ignoring their properties.
+impl FeatureProcessor for MutablePointArray {}
+
 #[cfg(test)]
 mod test {
     use super::ToGeoArrowPoint;
@@ -8,9 +8,18 @@ use rstar::{RTreeObject, AABB};
 pub struct Point<'a> {
     pub x: &'a Buffer<f64>,
     pub y: &'a Buffer<f64>,
+    /// Buffer of m values, if the array has them
+    pub m: Option<&'a Buffer<f64>>,
     pub geom_index: usize,
 }
 
+impl Point<'_> {
+    /// The m value (measure) of this point, if its array has m values.
+    pub fn m(&self) -> Option<f64> {
+        self.m.map(|m| m[self.geom_index])
+    }
+}
+
 impl PointTrait for Point<'_> {
     fn x(&self) -> f64 {
         self.x[self.geom_index]
@@ -65,3 +74,80 @@ impl RTreeObject for Point<'_> {
         AABB::from_corners(lower, upper)
     }
 }
+
+/// An owned [`Point`], which can outlive the array it was taken from.
+///
+/// Converting a [`Point`] into an [`OwnedPoint`] clones the reference-counted buffers of its
+/// array without copying their values, so owned scalars can be returned from functions that
+/// build arrays locally or stored in an [`rstar::RTree`]. Each owned scalar converts back into
+/// its borrowed scalar to use the geometry traits.
+#[derive(Debug, Clone)]
+pub struct OwnedPoint {
+    pub x: Buffer<f64>,
+    pub y: Buffer<f64>,
+    pub m: Option<Buffer<f64>>,
+    pub geom_index: usize,
+}
+
+impl OwnedPoint {
+    /// The m value (measure) of this point, if its array has m values.
+    pub fn m(&self) -> Option<f64> {
+        Point::from(self).m()
+    }
+}
+
+impl From<Point<'_>> for OwnedPoint {
+    fn from(value: Point<'_>) -> Self {
+        Self {
+            x: value.x.clone(),
+            y: value.y.clone(),
+            m: value.m.cloned(),
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedPoint> for Point<'a> {
+    fn from(value: &'a OwnedPoint) -> Self {
+        Self {
+            x: &value.x,
+            y: &value.y,
+            m: value.m.as_ref(),
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl PointTrait for OwnedPoint {
+    fn x(&self) -> f64 {
+        self.x[self.geom_index]
+    }
+
+    fn y(&self) -> f64 {
+        self.y[self.geom_index]
+    }
+
+    fn x_y(&self) -> (f64, f64) {
+        (self.x[self.geom_index], self.y[self.geom_index])
+    }
+}
+
+impl From<OwnedPoint> for geo::Point {
+    fn from(value: OwnedPoint) -> Self {
+        Point::from(&value).into()
+    }
+}
+
+impl From<OwnedPoint> for geo::Geometry {
+    fn from(value: OwnedPoint) -> Self {
+        geo::Geometry::Point(value.into())
+    }
+}
+
+impl RTreeObject for OwnedPoint {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        Point::from(self).envelope()
+    }
+}
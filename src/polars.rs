@@ -0,0 +1,97 @@
+//! Convert geometry arrays to and from [Polars](https://pola.rs) [`Series`].
+//!
+//! Series hold the GeoArrow layout of the array as is: structs for points and rects, lists of
+//! structs for the other native types and large binary for WKB. Polars keeps no field metadata,
+//! so the CRS of an array is not kept, and the geometry type of a series is given by the array
+//! type it is read into. Polars has no union type, so mixed geometry arrays must be converted to
+//! WKB first.
+
+use crate::error::GeoArrowError;
+use crate::{
+    GeometryArray, GeometryArrayTrait, LineStringArray, MultiLineStringArray, MultiPointArray,
+    MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+};
+use arrow2::array::Array;
+use polars::prelude::Series;
+
+/// A series named `name` holding `array`.
+fn to_series(name: &str, array: Box<dyn Array>) -> Result<Series, GeoArrowError> {
+    Series::try_from((name, array)).map_err(|err| GeoArrowError::External(err.into()))
+}
+
+macro_rules! impl_polars {
+    ($type:ty) => {
+        impl $type {
+            /// A Polars series named `name`, with one chunk holding the geometries of this array.
+            /// The buffers are not copied, and the CRS is not kept.
+            pub fn to_polars_series(&self, name: &str) -> Result<Series, GeoArrowError> {
+                to_series(name, self.clone().into_arrow().boxed())
+            }
+
+            /// An array holding the geometries of `series`, whose chunks are concatenated.
+            /// # Errors
+            /// Errors if `series` does not have the GeoArrow layout of this array type.
+            pub fn from_polars_series(series: &Series) -> Result<Self, GeoArrowError> {
+                series.rechunk().to_arrow(0).try_into()
+            }
+        }
+    };
+}
+
+impl_polars!(PointArray);
+impl_polars!(LineStringArray);
+impl_polars!(PolygonArray);
+impl_polars!(MultiPointArray);
+impl_polars!(MultiLineStringArray);
+impl_polars!(MultiPolygonArray);
+impl_polars!(WKBArray);
+impl_polars!(RectArray);
+
+impl GeometryArray {
+    /// A Polars series named `name`, with one chunk holding the geometries of this array.
+    /// The buffers are not copied, and the CRS is not kept.
+    /// # Errors
+    /// Errors for mixed geometry arrays, which Polars cannot hold.
+    pub fn to_polars_series(&self, name: &str) -> Result<Series, GeoArrowError> {
+        match self {
+            GeometryArray::Point(arr) => arr.to_polars_series(name),
+            GeometryArray::LineString(arr) => arr.to_polars_series(name),
+            GeometryArray::Polygon(arr) => arr.to_polars_series(name),
+            GeometryArray::MultiPoint(arr) => arr.to_polars_series(name),
+            GeometryArray::MultiLineString(arr) => arr.to_polars_series(name),
+            GeometryArray::MultiPolygon(arr) => arr.to_polars_series(name),
+            GeometryArray::WKB(arr) => arr.to_polars_series(name),
+            GeometryArray::Rect(arr) => arr.to_polars_series(name),
+            GeometryArray::Mixed(_) => Err(GeoArrowError::NotYetImplemented(
+                "Polars series of mixed geometries; convert them to WKB first".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{line_string, point};
+
+    #[test]
+    fn round_trip() {
+        let points: PointArray = vec![Some(point!(x: 0., y: 1.)), None].into();
+        let series = points.to_polars_series("geometry").unwrap();
+        assert_eq!(series.name(), "geometry");
+        assert_eq!(series.len(), 2);
+        let output = PointArray::from_polars_series(&series).unwrap();
+        assert_eq!(
+            output.iter_geo().collect::<Vec<_>>(),
+            points.iter_geo().collect::<Vec<_>>()
+        );
+
+        let lines: LineStringArray = vec![line_string![(x: 0., y: 0.), (x: 1., y: 1.)]].into();
+        let series = GeometryArray::LineString(lines.clone())
+            .to_polars_series("lines")
+            .unwrap();
+        let output = LineStringArray::from_polars_series(&series).unwrap();
+        assert_eq!(output.value_as_geo(0), lines.value_as_geo(0));
+        assert!(PointArray::from_polars_series(&series).is_err());
+    }
+}
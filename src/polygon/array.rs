@@ -1,15 +1,26 @@
+use crate::concat::concat_crs;
+use crate::coords::{concat_m, coord_fields, coords_have_m, impl_coords, values_range};
+use crate::crs::impl_crs;
 use crate::error::GeoArrowError;
+use crate::offsets::widen_list_offsets;
+#[cfg(feature = "rayon")]
+use crate::parallel::MIN_ROWS_PER_TASK;
 use crate::slice::slice_validity_unchecked;
-use crate::{GeometryArrayTrait, MultiLineStringArray};
+use crate::trait_::{Dimension, GeometryArrayType};
+use crate::validity::check_validity_len;
+use crate::{GeometryArrayTrait, MultiLineStringArray, MultiPolygonArray};
 use arrow2::array::Array;
 use arrow2::array::{ListArray, PrimitiveArray, StructArray};
 use arrow2::bitmap::utils::{BitmapIter, ZipValidity};
 use arrow2::bitmap::Bitmap;
 use arrow2::buffer::Buffer;
 use arrow2::datatypes::{DataType, Field};
-use arrow2::offset::OffsetsBuffer;
+use arrow2::offset::{Offsets, OffsetsBuffer};
 use geozero::{GeomProcessor, GeozeroGeometry};
-use rstar::RTree;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::ops::Range;
+use std::sync::Arc;
 
 use super::MutablePolygonArray;
 
@@ -23,6 +34,9 @@ pub struct PolygonArray {
     /// Buffer of y coordinates
     y: Buffer<f64>,
 
+    /// Buffer of m values (measures), one per coordinate, if any
+    m: Option<Buffer<f64>>,
+
     /// Offsets into the ring array where each geometry starts
     geom_offsets: OffsetsBuffer<i64>,
 
@@ -31,6 +45,9 @@ pub struct PolygonArray {
 
     /// Validity bitmap
     validity: Option<Bitmap>,
+
+    /// Coordinate reference system
+    crs: Option<Arc<str>>,
 }
 
 pub(super) fn check(
@@ -69,9 +86,11 @@ impl PolygonArray {
         Self {
             x,
             y,
+            m: None,
             geom_offsets,
             ring_offsets,
             validity,
+            crs: None,
         }
     }
 
@@ -89,13 +108,75 @@ impl PolygonArray {
         Ok(Self {
             x,
             y,
+            m: None,
             geom_offsets,
             ring_offsets,
             validity,
+            crs: None,
         })
     }
+
+    /// Offsets into the ring offsets where each geometry starts.
+    #[inline]
+    pub fn geom_offsets(&self) -> &OffsetsBuffer<i64> {
+        &self.geom_offsets
+    }
+
+    /// Offsets into the coordinate buffers where each ring starts.
+    #[inline]
+    pub fn ring_offsets(&self) -> &OffsetsBuffer<i64> {
+        &self.ring_offsets
+    }
+
+    /// Concatenate `arrays` into one array, copying their coordinates and rebuilding offsets.
+    ///
+    /// Arrays without a CRS are assumed to be in the CRS of the others. m values are kept if
+    /// every array has them.
+    /// # Errors
+    /// Errors if two arrays have different CRSes, or if the offsets of the result would overflow.
+    pub fn concat(arrays: &[&PolygonArray]) -> Result<Self, GeoArrowError> {
+        let crs = concat_crs(arrays.iter().map(|array| array.crs()))?;
+        let mut mutable = MutablePolygonArray::new();
+        for array in arrays {
+            mutable.extend_from_array(array)?;
+        }
+        let array = Self::from(mutable).with_crs(crs);
+        let m = concat_m(arrays.iter().map(|array| {
+            let range = array.coord_range(0..array.len());
+            array.values_m().map(|m| &m[range])
+        }));
+        match m {
+            Some(m) => array.with_m(m),
+            None => Ok(array),
+        }
+    }
+}
+
+impl PolygonArray {
+    /// The number of interior rings (holes) of each polygon, read from the ring offsets. Null
+    /// polygons give null counts.
+    pub fn num_interior_rings(&self) -> PrimitiveArray<u32> {
+        let counts = (0..self.len())
+            .map(|i| {
+                let (start, end) = self.geom_offsets.start_end(i);
+                (end - start).saturating_sub(1) as u32
+            })
+            .collect::<Vec<_>>();
+        PrimitiveArray::new(DataType::UInt32, counts.into(), self.validity.clone())
+    }
+}
+
+impl_crs!(PolygonArray);
+
+impl PolygonArray {
+    /// The range of the coordinate buffers holding the coordinates of the geometries `geoms`.
+    pub(crate) fn coord_range(&self, geoms: Range<usize>) -> Range<usize> {
+        values_range(&self.ring_offsets, values_range(&self.geom_offsets, geoms))
+    }
 }
 
+impl_coords!(PolygonArray);
+
 impl<'a> GeometryArrayTrait<'a> for PolygonArray {
     type Scalar = crate::Polygon<'a>;
     type ScalarGeo = geo::Polygon;
@@ -105,6 +186,7 @@ impl<'a> GeometryArrayTrait<'a> for PolygonArray {
         crate::Polygon {
             x: &self.x,
             y: &self.y,
+            m: self.m.as_ref(),
             geom_offsets: &self.geom_offsets,
             ring_offsets: &self.ring_offsets,
             geom_index: i,
@@ -113,9 +195,8 @@ impl<'a> GeometryArrayTrait<'a> for PolygonArray {
 
     fn into_arrow(self) -> Self::ArrowArray {
         // Data type
-        let coord_field_x = Field::new("x", DataType::Float64, false);
-        let coord_field_y = Field::new("y", DataType::Float64, false);
-        let struct_data_type = DataType::Struct(vec![coord_field_x, coord_field_y]);
+        let (coord_fields, coord_values) = coord_fields(self.x, self.y, self.m);
+        let struct_data_type = DataType::Struct(coord_fields);
         let inner_list_data_type = DataType::LargeList(Box::new(Field::new(
             "vertices",
             struct_data_type.clone(),
@@ -135,10 +216,7 @@ impl<'a> GeometryArrayTrait<'a> for PolygonArray {
         };
 
         // Array data
-        let array_x = PrimitiveArray::new(DataType::Float64, self.x, None).boxed();
-        let array_y = PrimitiveArray::new(DataType::Float64, self.y, None).boxed();
-
-        let coord_array = StructArray::new(struct_data_type, vec![array_x, array_y], None).boxed();
+        let coord_array = StructArray::new(struct_data_type, coord_values, None).boxed();
 
         let inner_list_array =
             ListArray::new(inner_list_data_type, self.ring_offsets, coord_array, None).boxed();
@@ -151,11 +229,12 @@ impl<'a> GeometryArrayTrait<'a> for PolygonArray {
         )
     }
 
-    /// Build a spatial index containing this array's geometries
-    fn rstar_tree(&'a self) -> RTree<Self::Scalar> {
-        let mut tree = RTree::new();
-        self.iter().flatten().for_each(|geom| tree.insert(geom));
-        tree
+    fn geometry_type(&self) -> GeometryArrayType {
+        GeometryArrayType::Polygon
+    }
+
+    fn dimension(&self) -> Dimension {
+        Dimension::new(false, self.m.is_some())
     }
 
     /// Returns the number of geometries in this array
@@ -204,6 +283,14 @@ impl<'a> GeometryArrayTrait<'a> for PolygonArray {
         self.geom_offsets.slice_unchecked(offset, length + 1);
     }
 
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        check_validity_len(validity.as_ref(), self.len());
+        Self {
+            validity,
+            ..self.clone()
+        }
+    }
+
     fn to_boxed(&self) -> Box<Self> {
         Box::new(self.clone())
     }
@@ -223,6 +310,24 @@ impl PolygonArray {
         ZipValidity::new_with_validity(self.iter_geo_values(), self.validity())
     }
 
+    /// Parallel iterator over the scalars of this array, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = Option<crate::Polygon<'_>>> {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get(i))
+    }
+
+    /// Parallel iterator over geo Geometry objects, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_geo(&self) -> impl IndexedParallelIterator<Item = Option<geo::Polygon>> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get_as_geo(i))
+    }
+
     /// Returns the value at slot `i` as a GEOS geometry.
     #[cfg(feature = "geos")]
     pub fn value_as_geos(&self, i: usize) -> geos::Geometry {
@@ -283,21 +388,46 @@ impl TryFrom<ListArray<i64>> for PolygonArray {
             .downcast_ref::<PrimitiveArray<f64>>()
             .unwrap();
 
-        Ok(Self::new(
+        let array = Self::new(
             x_array_values.values().clone(),
             y_array_values.values().clone(),
             geom_offsets.clone(),
             ring_offsets.clone(),
             validity.cloned(),
-        ))
+        );
+        if !coords_have_m(coords_array)? {
+            return Ok(array);
+        }
+        let m_array_values = coords_array.values()[2]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .unwrap();
+        array.with_m(m_array_values.values().clone())
+    }
+}
+
+impl TryFrom<ListArray<i32>> for PolygonArray {
+    type Error = GeoArrowError;
+
+    /// Read a list array with 32-bit offsets, widening them to 64 bits.
+    fn try_from(value: ListArray<i32>) -> Result<Self, Self::Error> {
+        value.boxed().try_into()
     }
 }
 
 impl TryFrom<Box<dyn Array>> for PolygonArray {
     type Error = GeoArrowError;
 
+    /// Both `List` and `LargeList` arrays are accepted.
     fn try_from(value: Box<dyn Array>) -> Result<Self, Self::Error> {
-        let arr = value.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+        let value = widen_list_offsets(value.as_ref());
+        let arr = value
+            .as_any()
+            .downcast_ref::<ListArray<i64>>()
+            .ok_or_else(|| GeoArrowError::Downcast {
+                expected: "ListArray<i64>",
+                found: value.data_type().clone(),
+            })?;
         arr.clone().try_into()
     }
 }
@@ -327,6 +457,27 @@ impl From<PolygonArray> for MultiLineStringArray {
             value.ring_offsets,
             value.validity,
         )
+        .with_crs(value.crs.as_deref())
+        .with_optional_m(value.m)
+    }
+}
+
+/// Wrap each polygon in a multi polygon of length one
+impl From<PolygonArray> for MultiPolygonArray {
+    fn from(value: PolygonArray) -> Self {
+        let geom_offsets = Offsets::<i64>::try_from_lengths((0..value.len()).map(|_| 1))
+            .unwrap()
+            .into();
+        Self::new(
+            value.x,
+            value.y,
+            geom_offsets,
+            value.geom_offsets,
+            value.ring_offsets,
+            value.validity,
+        )
+        .with_crs(value.crs.as_deref())
+        .with_optional_m(value.m)
     }
 }
 
@@ -423,12 +574,34 @@ mod test {
     #[test]
     fn geozero_process_geom() -> geozero::error::Result<()> {
         let arr: PolygonArray = vec![p0(), p1()].into();
-        let wkt = arr.to_wkt()?;
+        let wkt = ToWkt::to_wkt(&arr)?;
         let expected = "GEOMETRYCOLLECTION(POLYGON((-111 45,-111 41,-104 41,-104 45,-111 45)),POLYGON((-111 45,-111 41,-104 41,-104 45,-111 45),(-110 44,-110 42,-105 42,-105 44,-110 44)))";
         assert_eq!(wkt, expected);
         Ok(())
     }
 
+    #[test]
+    fn rings() {
+        let arr: PolygonArray = vec![Some(p0()), None, Some(p1())].into();
+        let counts = arr.num_interior_rings();
+        assert_eq!(counts.values().as_slice(), &[0, 0, 1]);
+        assert!(counts.is_null(1));
+
+        let polygon = arr.value(2);
+        assert_eq!(polygon.num_interior_rings(), 1);
+        assert_eq!(
+            geo::LineString::from(polygon.exterior()),
+            p1().exterior().clone()
+        );
+        assert_eq!(
+            geo::LineString::from(polygon.interior_ring(0).unwrap()),
+            p1().interiors()[0]
+        );
+        assert!(polygon.interior_ring(1).is_none());
+        assert_eq!(polygon.rings().count(), 2);
+        assert_eq!(arr.sliced_range(2..3).num_interior_rings().value(0), 1);
+    }
+
     #[test]
     fn slice() {
         let mut arr: PolygonArray = vec![p0(), p1()].into();
@@ -436,4 +609,51 @@ mod test {
         assert_eq!(arr.len(), 1);
         assert_eq!(arr.get_as_geo(0), Some(p1()));
     }
+
+    #[test]
+    fn concat() {
+        let first: PolygonArray = vec![Some(p0()), None, Some(p1())].into();
+        let second: PolygonArray = vec![p1(), p0()].into();
+        let second = second.sliced_range(1..2).with_crs(Some("EPSG:4326"));
+        let arr = PolygonArray::concat(&[&first.sliced_range(1..3), &second]).unwrap();
+        assert_eq!(arr.len(), 3);
+        assert_eq!(arr.crs(), Some("EPSG:4326"));
+        assert!(arr.is_null(0));
+        assert_eq!(arr.get_as_geo(1), Some(p1()));
+        assert_eq!(arr.get_as_geo(2), Some(p0()));
+
+        let other = first.with_crs(Some("EPSG:3857"));
+        assert!(PolygonArray::concat(&[&other, &second]).is_err());
+    }
+
+    #[test]
+    fn m_values() {
+        let arr: PolygonArray = vec![p0(), p1()].into();
+        let m = (0..15).map(f64::from).collect::<Vec<_>>();
+        let arr = arr.with_m(m.into()).unwrap();
+        assert!(arr.has_m());
+        let polygon = arr.value(1);
+        assert_eq!(
+            polygon.exterior().m_values(),
+            Some(&[5., 6., 7., 8., 9.][..])
+        );
+        assert_eq!(
+            polygon.interior_ring(0).unwrap().m_values(),
+            Some(&[10., 11., 12., 13., 14.][..])
+        );
+
+        let round_trip = PolygonArray::try_from(arr.clone().into_arrow()).unwrap();
+        assert_eq!(round_trip, arr);
+
+        let multi_polygons = MultiPolygonArray::from(arr.clone());
+        assert_eq!(multi_polygons.values_m(), arr.values_m());
+        let multi_line_strings = MultiLineStringArray::from(arr.clone());
+        assert_eq!(PolygonArray::from(multi_line_strings), arr);
+
+        let wkb = crate::WKBArray::from(&arr);
+        assert_eq!(PolygonArray::try_from(wkb).unwrap(), arr);
+
+        let concat = PolygonArray::concat(&[&arr.sliced_range(1..2), &arr]).unwrap();
+        assert_eq!(concat.values_m().unwrap().len(), 25);
+    }
 }
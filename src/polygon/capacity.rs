@@ -0,0 +1,85 @@
+//! Capacities of the buffers of a [`MutablePolygonArray`](super::MutablePolygonArray).
+
+use crate::geo_traits::{LineStringTrait, PolygonTrait};
+use std::ops::Add;
+
+/// The number of coordinates, rings and geometries to allocate in a
+/// [`MutablePolygonArray`](super::MutablePolygonArray).
+///
+/// Counting the geometries to ingest first, with [`from_polygons`](Self::from_polygons),
+/// lets the array allocate its buffers once rather than growing them geometry by geometry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PolygonCapacity {
+    coord_capacity: usize,
+    ring_capacity: usize,
+    geom_capacity: usize,
+}
+
+impl PolygonCapacity {
+    /// Creates a new capacity from its parts.
+    pub fn new(coord_capacity: usize, ring_capacity: usize, geom_capacity: usize) -> Self {
+        Self {
+            coord_capacity,
+            ring_capacity,
+            geom_capacity,
+        }
+    }
+
+    /// Creates a new capacity of zero.
+    pub fn new_empty() -> Self {
+        Self::default()
+    }
+
+    /// The number of coordinates.
+    pub fn coord_capacity(&self) -> usize {
+        self.coord_capacity
+    }
+
+    /// The number of rings.
+    pub fn ring_capacity(&self) -> usize {
+        self.ring_capacity
+    }
+
+    /// The number of geometries.
+    pub fn geom_capacity(&self) -> usize {
+        self.geom_capacity
+    }
+
+    /// Add the capacity of a polygon, or of a null if `value` is `None`.
+    pub fn add_polygon<'a>(&mut self, value: Option<&impl PolygonTrait<'a>>) {
+        self.geom_capacity += 1;
+        if let Some(polygon) = value {
+            self.add_polygon_rings(polygon);
+        }
+    }
+
+    /// Add the rings of `polygon`.
+    fn add_polygon_rings<'a>(&mut self, polygon: &impl PolygonTrait<'a>) {
+        self.ring_capacity += 1 + polygon.num_interiors();
+        self.coord_capacity += polygon.exterior().num_points();
+        for ring in polygon.interiors() {
+            self.coord_capacity += ring.num_points();
+        }
+    }
+
+    /// The capacity needed to hold `geoms`, where `None` is a null.
+    pub fn from_polygons<'a>(geoms: impl Iterator<Item = Option<impl PolygonTrait<'a>>>) -> Self {
+        let mut capacity = Self::new_empty();
+        for geom in geoms {
+            capacity.add_polygon(geom.as_ref());
+        }
+        capacity
+    }
+}
+
+impl Add for PolygonCapacity {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            coord_capacity: self.coord_capacity + rhs.coord_capacity,
+            ring_capacity: self.ring_capacity + rhs.ring_capacity,
+            geom_capacity: self.geom_capacity + rhs.geom_capacity,
+        }
+    }
+}
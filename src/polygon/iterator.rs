@@ -80,16 +80,16 @@ impl<'a> PolygonArray {
 /// Iterator of values of a [`PointArray`]
 #[derive(Clone, Debug)]
 pub struct PolygonInteriorIterator<'a> {
-    geom: &'a Polygon<'a>,
+    geom: Polygon<'a>,
     index: usize,
     end: usize,
 }
 
 impl<'a> PolygonInteriorIterator<'a> {
     #[inline]
-    pub fn new(geom: &'a Polygon<'a>) -> Self {
+    pub fn new(geom: &Polygon<'a>) -> Self {
         Self {
-            geom,
+            geom: geom.clone(),
             index: 0,
             end: geom.num_interiors(),
         }
@@ -1,11 +1,13 @@
 //! Helpers for using Polygon GeoArrow data
 
 pub use array::PolygonArray;
-pub use mutable::MutablePolygonArray;
-pub use scalar::Polygon;
+pub use capacity::PolygonCapacity;
+pub use mutable::{MutablePolygonArray, ToGeoArrowPolygon};
+pub use scalar::{OwnedPolygon, Polygon};
 pub(crate) use util::parse_polygon;
 
 mod array;
+mod capacity;
 mod iterator;
 mod mutable;
 mod scalar;
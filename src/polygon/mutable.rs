@@ -1,13 +1,19 @@
-use crate::trait_::GeometryArrayTrait;
+use crate::trait_::{GeometryArrayTrait, MutableGeometryArray};
 use arrow2::array::ListArray;
 use arrow2::bitmap::{Bitmap, MutableBitmap};
 use arrow2::offset::{Offsets, OffsetsBuffer};
 use arrow2::types::Index;
 use geo::Polygon;
 
+use crate::concat::{extend_offsets, extend_validity, push_validity, try_push_end};
 use crate::error::GeoArrowError;
+use crate::geo_traits::{LineStringTrait, PointTrait, PolygonTrait};
 use crate::multilinestring::MutableMultiLineStringArray;
+use crate::polygon::PolygonCapacity;
+use crate::processor::{push_offset, reject_curves, reject_geometries};
 use crate::PolygonArray;
+use geozero::error::GeozeroError;
+use geozero::{FeatureProcessor, GeomProcessor, GeozeroGeometry, PropertyProcessor};
 
 pub type MutablePolygonParts = (
     Vec<f64>,
@@ -79,6 +85,27 @@ impl MutablePolygonArray {
         })
     }
 
+    /// Append the geometries of `other` to this array.
+    ///
+    /// Mutable arrays carry no CRS, so the CRS of `other` is dropped.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn extend_from_array(&mut self, other: &PolygonArray) -> Result<(), GeoArrowError> {
+        let len = self.geom_offsets.len_proxy();
+        let (ring_start, ring_end) =
+            extend_offsets(&mut self.geom_offsets, other.geom_offsets(), 0, other.len())?;
+        let (start, end) = extend_offsets(
+            &mut self.ring_offsets,
+            other.ring_offsets(),
+            ring_start,
+            ring_end - ring_start,
+        )?;
+        self.x.extend_from_slice(&other.values_x()[start..end]);
+        self.y.extend_from_slice(&other.values_y()[start..end]);
+        extend_validity(&mut self.validity, len, other.validity(), other.len());
+        Ok(())
+    }
+
     /// Extract the low-level APIs from the [`MutableLineStringArray`].
     pub fn into_inner(self) -> MutablePolygonParts {
         (
@@ -94,6 +121,97 @@ impl MutablePolygonArray {
         let polygon_array: PolygonArray = self.into();
         polygon_array.into_arrow()
     }
+
+    /// Creates a new [`MutablePolygonArray`] with the capacity counted by `capacity`.
+    pub fn with_capacity(capacity: PolygonCapacity) -> Self {
+        Self::with_capacities(
+            capacity.coord_capacity(),
+            capacity.geom_capacity(),
+            capacity.ring_capacity(),
+        )
+    }
+
+    /// Reserves capacity for at least `additional` more values.
+    pub fn reserve(&mut self, additional: PolygonCapacity) {
+        self.x.reserve(additional.coord_capacity());
+        self.y.reserve(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        self.ring_offsets.reserve(additional.ring_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Reserves capacity for exactly `additional` more coordinates, so that ingesting a counted
+    /// batch does not over-allocate the coordinate buffers. Offsets and validity may still
+    /// reserve more than needed.
+    pub fn reserve_exact(&mut self, additional: PolygonCapacity) {
+        self.x.reserve_exact(additional.coord_capacity());
+        self.y.reserve_exact(additional.coord_capacity());
+        self.geom_offsets.reserve(additional.geom_capacity());
+        self.ring_offsets.reserve(additional.ring_capacity());
+        if let Some(validity) = &mut self.validity {
+            validity.reserve(additional.geom_capacity());
+        }
+    }
+
+    /// Shrinks the capacity of the buffers of this array to fit their lengths.
+    pub fn shrink_to_fit(&mut self) {
+        self.x.shrink_to_fit();
+        self.y.shrink_to_fit();
+        self.geom_offsets.shrink_to_fit();
+        self.ring_offsets.shrink_to_fit();
+        if let Some(validity) = &mut self.validity {
+            validity.shrink_to_fit();
+        }
+    }
+
+    /// Adds a new polygon to the array, or a null if `value` is `None`.
+    /// # Errors
+    /// Errors if the offsets of this array would overflow.
+    pub fn try_push_polygon<'a>(
+        &mut self,
+        value: Option<&impl PolygonTrait<'a>>,
+    ) -> Result<(), GeoArrowError> {
+        let Some(polygon) = value else {
+            self.push_null();
+            return Ok(());
+        };
+        self.try_push_ring(polygon.exterior())?;
+        for ring in polygon.interiors() {
+            self.try_push_ring(ring)?;
+        }
+        self.try_push_valid()
+    }
+
+    /// Adds a new value to the array.
+    pub fn try_push_geo(&mut self, value: Option<Polygon>) -> Result<(), GeoArrowError> {
+        self.try_push_polygon(value.as_ref().as_ref())
+    }
+
+    /// Adds a new null to the array.
+    pub fn push_null(&mut self) {
+        self.geom_offsets.extend_constant(1);
+        let len = self.geom_offsets.len_proxy() - 1;
+        push_validity(&mut self.validity, len, false);
+    }
+
+    /// Close the current geometry, whose children were pushed, as a valid value.
+    fn try_push_valid(&mut self) -> Result<(), GeoArrowError> {
+        let len = self.geom_offsets.len_proxy();
+        try_push_end(&mut self.geom_offsets, self.ring_offsets.len_proxy())?;
+        push_validity(&mut self.validity, len, true);
+        Ok(())
+    }
+
+    /// Push the coordinates of `ring` and close it.
+    fn try_push_ring<'a>(&mut self, ring: impl LineStringTrait<'a>) -> Result<(), GeoArrowError> {
+        for point in ring.points() {
+            self.x.push(point.x());
+            self.y.push(point.y());
+        }
+        try_push_end(&mut self.ring_offsets, self.x.len())
+    }
 }
 
 impl Default for MutablePolygonArray {
@@ -102,6 +220,28 @@ impl Default for MutablePolygonArray {
     }
 }
 
+impl MutableGeometryArray for MutablePolygonArray {
+    fn len(&self) -> usize {
+        self.geom_offsets.len_proxy()
+    }
+
+    fn validity(&self) -> Option<&MutableBitmap> {
+        self.validity.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn push_null(&mut self) {
+        MutablePolygonArray::push_null(self)
+    }
+}
+
 impl From<MutablePolygonArray> for PolygonArray {
     fn from(other: MutablePolygonArray) -> Self {
         let validity = other.validity.and_then(|x| {
@@ -263,3 +403,130 @@ impl From<MutablePolygonArray> for MutableMultiLineStringArray {
         .unwrap()
     }
 }
+
+/// Convert a geozero geometry, such as a geometry collection of polygons, to a GeoArrow
+/// [`PolygonArray`].
+pub trait ToGeoArrowPolygon {
+    /// Convert to GeoArrow PolygonArray
+    fn to_geoarrow(&self) -> geozero::error::Result<PolygonArray>;
+
+    /// Convert to a GeoArrow MutablePolygonArray
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutablePolygonArray>;
+}
+
+impl<T: GeozeroGeometry> ToGeoArrowPolygon for T {
+    fn to_geoarrow(&self) -> geozero::error::Result<PolygonArray> {
+        Ok(self.to_mutable_geoarrow()?.into())
+    }
+
+    fn to_mutable_geoarrow(&self) -> geozero::error::Result<MutablePolygonArray> {
+        let mut mutable_array = MutablePolygonArray::new();
+        self.process_geom(&mut mutable_array)?;
+        Ok(mutable_array)
+    }
+}
+
+/// Each polygon processed is appended as one geometry.
+impl GeomProcessor for MutablePolygonArray {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> geozero::error::Result<()> {
+        self.x.push(x);
+        self.y.push(y);
+        Ok(())
+    }
+
+    fn linestring_begin(
+        &mut self,
+        tagged: bool,
+        size: usize,
+        _idx: usize,
+    ) -> geozero::error::Result<()> {
+        if tagged {
+            return Err(GeozeroError::Geometry(
+                "Only polygon geometries allowed".to_string(),
+            ));
+        }
+        self.x.reserve(size);
+        self.y.reserve(size);
+        Ok(())
+    }
+
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> geozero::error::Result<()> {
+        push_offset(&mut self.ring_offsets, self.x.len())
+    }
+
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> geozero::error::Result<()> {
+        push_offset(&mut self.geom_offsets, self.ring_offsets.len_proxy())?;
+        if let Some(validity) = &mut self.validity {
+            validity.push(true)
+        }
+        Ok(())
+    }
+
+    reject_geometries!(
+        "Only polygon geometries allowed",
+        [
+            point_begin(_idx: usize),
+            multipoint_begin(_size: usize, _idx: usize),
+            multilinestring_begin(_size: usize, _idx: usize),
+            multipolygon_begin(_size: usize, _idx: usize),
+        ]
+    );
+    reject_curves!("Only polygon geometries allowed");
+}
+
+impl PropertyProcessor for MutablePolygonArray {}
+
+/// Features are appended as one geometry each, ignoring their properties.
+impl FeatureProcessor for MutablePolygonArray {}
+
+#[cfg(test)]
+mod test {
+    use super::{MutablePolygonArray, ToGeoArrowPolygon};
+    use crate::polygon::PolygonCapacity;
+    use crate::trait_::MutableGeometryArray;
+    use crate::{GeometryArrayTrait, PolygonArray};
+    use geo::{line_string, polygon, Geometry, GeometryCollection};
+
+    #[test]
+    fn from_geozero() {
+        let p0 = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 0.)];
+        let p1 = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 0.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 1.)]],
+        );
+        let geo = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::Polygon(p0.clone()),
+            Geometry::Polygon(p1.clone()),
+        ]));
+        let arr = geo.to_geoarrow().unwrap();
+        assert_eq!(arr.len(), 2);
+        assert_eq!(arr.value_as_geo(0), p0);
+        assert_eq!(arr.value_as_geo(1), p1);
+
+        let geo = Geometry::LineString(line_string![(x: 0., y: 1.), (x: 1., y: 2.)]);
+        assert!(geo.to_geoarrow().is_err());
+    }
+
+    #[test]
+    fn push_polygons() {
+        let p0 = polygon!(
+            exterior: [(x: 0., y: 0.), (x: 4., y: 0.), (x: 4., y: 4.), (x: 0., y: 0.)],
+            interiors: [[(x: 1., y: 1.), (x: 2., y: 1.), (x: 2., y: 2.), (x: 1., y: 1.)]],
+        );
+        let source: PolygonArray = vec![p0.clone()].into();
+
+        let capacity = PolygonCapacity::from_polygons(source.iter().chain([None]));
+        assert_eq!(capacity, PolygonCapacity::new(8, 2, 2));
+        let mut builder = MutablePolygonArray::with_capacity(capacity);
+        builder.try_push_polygon(Some(&source.value(0))).unwrap();
+        builder.push_null();
+        builder.reserve_exact(PolygonCapacity::from_polygons([Some(&p0)].into_iter()));
+        builder.try_push_geo(Some(p0.clone())).unwrap();
+        assert_eq!(MutableGeometryArray::len(&builder), 3);
+
+        let arr: PolygonArray = builder.into();
+        assert_eq!(arr.value_as_geo(0), p0);
+        assert!(arr.is_null(1));
+        assert_eq!(arr.value_as_geo(2), p0);
+    }
+}
@@ -16,6 +16,9 @@ pub struct Polygon<'a> {
     /// Buffer of y coordinates
     pub y: &'a Buffer<f64>,
 
+    /// Buffer of m values, if the array has them
+    pub m: Option<&'a Buffer<f64>>,
+
     /// Offsets into the ring array where each geometry starts
     pub geom_offsets: &'a OffsetsBuffer<i64>,
 
@@ -29,40 +32,84 @@ impl<'a> PolygonTrait<'a> for Polygon<'a> {
     type ItemType = LineString<'a>;
     type Iter = PolygonInteriorIterator<'a>;
 
-    fn exterior(&'a self) -> Self::ItemType {
-        let (start, _) = self.geom_offsets.start_end(self.geom_index);
+    fn exterior(&self) -> Self::ItemType {
+        let (start, end) = self.geom_offsets.start_end(self.geom_index);
+        if start == end {
+            // A polygon without rings has an empty exterior. Its own (empty) range of rings
+            // stands in for an empty range of coordinates.
+            return LineString {
+                x: self.x,
+                y: self.y,
+                m: self.m,
+                geom_offsets: self.geom_offsets,
+                geom_index: self.geom_index,
+            };
+        }
+
         LineString {
             x: self.x,
             y: self.y,
+            m: self.m,
             geom_offsets: self.ring_offsets,
             geom_index: start,
         }
     }
 
-    fn interiors(&'a self) -> Self::Iter {
+    fn interiors(&self) -> Self::Iter {
         PolygonInteriorIterator::new(self)
     }
 
-    fn num_interiors(&'a self) -> usize {
+    fn num_interiors(&self) -> usize {
         let (start, end) = self.geom_offsets.start_end(self.geom_index);
-        end - start - 1
+        (end - start).saturating_sub(1)
     }
 
-    fn interior(&'a self, i: usize) -> Option<Self::ItemType> {
+    fn interior(&self, i: usize) -> Option<Self::ItemType> {
         let (start, end) = self.geom_offsets.start_end(self.geom_index);
-        if i > (end - start - 1) {
+        if i + 1 >= end - start {
             return None;
         }
 
         Some(LineString {
             x: self.x,
             y: self.y,
+            m: self.m,
             geom_offsets: self.ring_offsets,
             geom_index: start + 1 + i,
         })
     }
 }
 
+impl<'a> Polygon<'a> {
+    /// The exterior ring of this polygon, empty if the polygon has no rings.
+    pub fn exterior(&self) -> LineString<'a> {
+        PolygonTrait::exterior(self)
+    }
+
+    /// The number of interior rings (holes) of this polygon.
+    pub fn num_interior_rings(&self) -> usize {
+        self.num_interiors()
+    }
+
+    /// The interior ring `i` of this polygon, or `None` if it has no more than `i` holes.
+    pub fn interior_ring(&self, i: usize) -> Option<LineString<'a>> {
+        self.interior(i)
+    }
+
+    /// Iterate over the rings of this polygon: its exterior first, then its interior rings.
+    pub fn rings(&self) -> impl Iterator<Item = LineString<'a>> + 'a {
+        let (start, end) = self.geom_offsets.start_end(self.geom_index);
+        let (x, y, m, ring_offsets) = (self.x, self.y, self.m, self.ring_offsets);
+        (start..end).map(move |ring| LineString {
+            x,
+            y,
+            m,
+            geom_offsets: ring_offsets,
+            geom_index: ring,
+        })
+    }
+}
+
 impl From<Polygon<'_>> for geo::Polygon {
     fn from(value: Polygon<'_>) -> Self {
         (&value).into()
@@ -95,3 +142,70 @@ impl RTreeObject for Polygon<'_> {
         AABB::from_corners(lower, upper)
     }
 }
+
+/// An owned [`Polygon`], holding clones of the buffers of its array.
+#[derive(Debug, Clone)]
+pub struct OwnedPolygon {
+    /// Buffer of x coordinates
+    pub x: Buffer<f64>,
+
+    /// Buffer of y coordinates
+    pub y: Buffer<f64>,
+
+    /// Buffer of m values, if the array has them
+    pub m: Option<Buffer<f64>>,
+
+    /// Offsets into the ring array where each geometry starts
+    pub geom_offsets: OffsetsBuffer<i64>,
+
+    /// Offsets into the coordinate array where each ring starts
+    pub ring_offsets: OffsetsBuffer<i64>,
+
+    pub geom_index: usize,
+}
+
+impl From<Polygon<'_>> for OwnedPolygon {
+    fn from(value: Polygon<'_>) -> Self {
+        Self {
+            x: value.x.clone(),
+            y: value.y.clone(),
+            m: value.m.cloned(),
+            geom_offsets: value.geom_offsets.clone(),
+            ring_offsets: value.ring_offsets.clone(),
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl<'a> From<&'a OwnedPolygon> for Polygon<'a> {
+    fn from(value: &'a OwnedPolygon) -> Self {
+        Self {
+            x: &value.x,
+            y: &value.y,
+            m: value.m.as_ref(),
+            geom_offsets: &value.geom_offsets,
+            ring_offsets: &value.ring_offsets,
+            geom_index: value.geom_index,
+        }
+    }
+}
+
+impl From<OwnedPolygon> for geo::Polygon {
+    fn from(value: OwnedPolygon) -> Self {
+        Polygon::from(&value).into()
+    }
+}
+
+impl From<OwnedPolygon> for geo::Geometry {
+    fn from(value: OwnedPolygon) -> Self {
+        geo::Geometry::Polygon(value.into())
+    }
+}
+
+impl RTreeObject for OwnedPolygon {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        Polygon::from(self).envelope()
+    }
+}
@@ -11,6 +11,11 @@ pub(crate) fn parse_polygon(
     // Start and end indices into the ring_offsets buffer
     let (start_geom_idx, end_geom_idx) = polygon_offsets.start_end(i);
 
+    // Null slots have no rings; the ring offsets may not extend that far
+    if start_geom_idx == end_geom_idx {
+        return geo::Polygon::new(geo::LineString::new(vec![]), vec![]);
+    }
+
     // Parse exterior ring first
     let (start_ext_ring_idx, end_ext_ring_idx) = ring_offsets.start_end(start_geom_idx);
     let mut exterior_coords: Vec<geo::Coord> =
@@ -0,0 +1,55 @@
+//! Helpers for implementing geozero's [`GeomProcessor`](geozero::GeomProcessor) on mutable
+//! arrays, so that any geozero datasource can be streamed into a geometry array.
+
+use crate::error::GeoArrowError;
+use arrow2::offset::Offsets;
+use arrow2::types::Index;
+use geozero::error::GeozeroError;
+
+/// Implement the given `GeomProcessor` methods, which begin geometries of types the array cannot
+/// hold, by erroring with `message`.
+macro_rules! reject_geometries {
+    ($message:expr, [$($method:ident($($arg:ident: $ty:ty),*)),* $(,)?]) => {
+        $(
+            fn $method(&mut self, $($arg: $ty),*) -> geozero::error::Result<()> {
+                Err(geozero::error::GeozeroError::Geometry($message.to_string()))
+            }
+        )*
+    };
+}
+
+/// Reject curved and polyhedral geometries, which no geometry array can hold.
+macro_rules! reject_curves {
+    ($message:expr) => {
+        $crate::processor::reject_geometries!(
+            $message,
+            [
+                circularstring_begin(_size: usize, _idx: usize),
+                compoundcurve_begin(_size: usize, _idx: usize),
+                curvepolygon_begin(_size: usize, _idx: usize),
+                multicurve_begin(_size: usize, _idx: usize),
+                multisurface_begin(_size: usize, _idx: usize),
+                polyhedralsurface_begin(_size: usize, _idx: usize),
+                tin_begin(_size: usize, _idx: usize),
+                triangle_begin(_tagged: bool, _size: usize, _idx: usize),
+            ]
+        );
+    };
+}
+
+pub(crate) use {reject_curves, reject_geometries};
+
+/// Close the current element of `offsets`, whose children end at `end`.
+pub(crate) fn push_offset(offsets: &mut Offsets<i64>, end: usize) -> geozero::error::Result<()> {
+    let length = end
+        .checked_sub(offsets.last().to_usize())
+        .ok_or_else(|| GeozeroError::Geometry(GeoArrowError::Overflow.to_string()))?;
+    offsets.try_push_usize(length).map_err(|_| {
+        GeozeroError::Geometry(
+            GeoArrowError::OffsetOverflow {
+                row: offsets.len_proxy(),
+            }
+            .to_string(),
+        )
+    })
+}
@@ -0,0 +1,372 @@
+use crate::crs::impl_crs;
+use crate::error::GeoArrowError;
+#[cfg(feature = "rayon")]
+use crate::parallel::MIN_ROWS_PER_TASK;
+use crate::slice::slice_validity_unchecked;
+use crate::trait_::GeometryArrayType;
+use crate::validity::check_validity_len;
+use crate::{GeometryArrayTrait, PolygonArray};
+use arrow2::array::{Array, PrimitiveArray, StructArray};
+use arrow2::bitmap::utils::{BitmapIter, ZipValidity};
+use arrow2::bitmap::{Bitmap, MutableBitmap};
+use arrow2::buffer::Buffer;
+use arrow2::datatypes::{DataType, Field};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// An array of axis-aligned bounding boxes, semantically equivalent to `Vec<Option<Rect>>`.
+///
+/// Each box is stored as four parallel coordinate buffers, in the same struct layout as
+/// GeoParquet's bounding box covering columns.
+#[derive(Debug, Clone)]
+pub struct RectArray {
+    minx: Buffer<f64>,
+    miny: Buffer<f64>,
+    maxx: Buffer<f64>,
+    maxy: Buffer<f64>,
+    validity: Option<Bitmap>,
+    crs: Option<Arc<str>>,
+}
+
+pub(super) fn check(
+    minx: &[f64],
+    miny: &[f64],
+    maxx: &[f64],
+    maxy: &[f64],
+    validity_len: Option<usize>,
+) -> Result<(), GeoArrowError> {
+    if validity_len.map_or(false, |len| len != minx.len()) {
+        return Err(GeoArrowError::General(
+            "validity mask length must match the number of values".to_string(),
+        ));
+    }
+
+    if [miny.len(), maxx.len(), maxy.len()]
+        .iter()
+        .any(|len| *len != minx.len())
+    {
+        return Err(GeoArrowError::General(
+            "all coordinate arrays must have the same length".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl RectArray {
+    /// Create a new RectArray from parts
+    /// # Implementation
+    /// This function is `O(1)`.
+    pub fn new(
+        minx: Buffer<f64>,
+        miny: Buffer<f64>,
+        maxx: Buffer<f64>,
+        maxy: Buffer<f64>,
+        validity: Option<Bitmap>,
+    ) -> Self {
+        Self::try_new(minx, miny, maxx, maxy, validity).unwrap()
+    }
+
+    /// Create a new RectArray from parts
+    /// # Implementation
+    /// This function is `O(1)`.
+    pub fn try_new(
+        minx: Buffer<f64>,
+        miny: Buffer<f64>,
+        maxx: Buffer<f64>,
+        maxy: Buffer<f64>,
+        validity: Option<Bitmap>,
+    ) -> Result<Self, GeoArrowError> {
+        check(
+            &minx,
+            &miny,
+            &maxx,
+            &maxy,
+            validity.as_ref().map(|v| v.len()),
+        )?;
+        Ok(Self {
+            minx,
+            miny,
+            maxx,
+            maxy,
+            validity,
+            crs: None,
+        })
+    }
+
+    /// The minimum x [`Buffer`].
+    /// Values on null slots are undetermined (they can be anything).
+    #[inline]
+    pub fn values_minx(&self) -> &Buffer<f64> {
+        &self.minx
+    }
+
+    /// The minimum y [`Buffer`].
+    /// Values on null slots are undetermined (they can be anything).
+    #[inline]
+    pub fn values_miny(&self) -> &Buffer<f64> {
+        &self.miny
+    }
+
+    /// The maximum x [`Buffer`].
+    /// Values on null slots are undetermined (they can be anything).
+    #[inline]
+    pub fn values_maxx(&self) -> &Buffer<f64> {
+        &self.maxx
+    }
+
+    /// The maximum y [`Buffer`].
+    /// Values on null slots are undetermined (they can be anything).
+    #[inline]
+    pub fn values_maxy(&self) -> &Buffer<f64> {
+        &self.maxy
+    }
+
+    /// Iterator over geo Rect objects, not looking at validity
+    pub fn iter_geo_values(&self) -> impl Iterator<Item = geo::Rect> + '_ {
+        (0..self.len()).map(|i| self.value_as_geo(i))
+    }
+
+    /// Iterator over geo Rect objects, taking into account validity
+    pub fn iter_geo(
+        &self,
+    ) -> ZipValidity<geo::Rect, impl Iterator<Item = geo::Rect> + '_, BitmapIter> {
+        ZipValidity::new_with_validity(self.iter_geo_values(), self.validity())
+    }
+
+    /// Parallel iterator over the scalars of this array, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = Option<crate::Rect<'_>>> {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get(i))
+    }
+
+    /// Parallel iterator over geo Geometry objects, taking into account validity
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_geo(&self) -> impl IndexedParallelIterator<Item = Option<geo::Rect>> + '_ {
+        (0..self.len())
+            .into_par_iter()
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .map(|i| self.get_as_geo(i))
+    }
+}
+
+impl_crs!(RectArray);
+
+impl<'a> GeometryArrayTrait<'a> for RectArray {
+    type Scalar = crate::Rect<'a>;
+    type ScalarGeo = geo::Rect;
+    type ArrowArray = StructArray;
+
+    fn value(&'a self, i: usize) -> Self::Scalar {
+        crate::Rect {
+            minx: &self.minx,
+            miny: &self.miny,
+            maxx: &self.maxx,
+            maxy: &self.maxy,
+            geom_index: i,
+        }
+    }
+
+    /// Convert this array into an [`arrow2`] struct array with `minx`, `miny`, `maxx` and `maxy`
+    /// fields.
+    fn into_arrow(self) -> Self::ArrowArray {
+        self.into()
+    }
+
+    fn geometry_type(&self) -> GeometryArrayType {
+        GeometryArrayType::Rect
+    }
+
+    /// Returns the number of boxes in this array
+    #[inline]
+    fn len(&self) -> usize {
+        self.minx.len()
+    }
+
+    /// Returns the optional validity.
+    #[inline]
+    fn validity(&self) -> Option<&Bitmap> {
+        self.validity.as_ref()
+    }
+
+    /// Slices this [`RectArray`] in place.
+    /// # Implementation
+    /// This operation is `O(1)`.
+    /// # Panic
+    /// This function panics iff `offset + length > self.len()`.
+    #[inline]
+    fn slice(&mut self, offset: usize, length: usize) {
+        assert!(
+            offset + length <= self.len(),
+            "offset + length may not exceed length of array"
+        );
+        unsafe { self.slice_unchecked(offset, length) }
+    }
+
+    /// Slices this [`RectArray`] in place.
+    /// # Implementation
+    /// This operation is `O(1)`.
+    /// # Safety
+    /// The caller must ensure that `offset + length <= self.len()`.
+    #[inline]
+    unsafe fn slice_unchecked(&mut self, offset: usize, length: usize) {
+        slice_validity_unchecked(&mut self.validity, offset, length);
+        self.minx.slice_unchecked(offset, length);
+        self.miny.slice_unchecked(offset, length);
+        self.maxx.slice_unchecked(offset, length);
+        self.maxy.slice_unchecked(offset, length);
+    }
+
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self {
+        check_validity_len(validity.as_ref(), self.len());
+        Self {
+            validity,
+            ..self.clone()
+        }
+    }
+
+    fn to_boxed(&self) -> Box<Self> {
+        Box::new(self.clone())
+    }
+}
+
+impl From<RectArray> for StructArray {
+    fn from(value: RectArray) -> Self {
+        let fields = ["minx", "miny", "maxx", "maxy"]
+            .iter()
+            .map(|name| Field::new(*name, DataType::Float64, false))
+            .collect();
+        let values: Vec<Box<dyn Array>> = [value.minx, value.miny, value.maxx, value.maxy]
+            .into_iter()
+            .map(|buffer| PrimitiveArray::new(DataType::Float64, buffer, None).boxed())
+            .collect();
+
+        StructArray::new(DataType::Struct(fields), values, value.validity)
+    }
+}
+
+impl From<Vec<Option<geo::Rect>>> for RectArray {
+    fn from(other: Vec<Option<geo::Rect>>) -> Self {
+        let mut minx = Vec::with_capacity(other.len());
+        let mut miny = Vec::with_capacity(other.len());
+        let mut maxx = Vec::with_capacity(other.len());
+        let mut maxy = Vec::with_capacity(other.len());
+        let mut validity = MutableBitmap::with_capacity(other.len());
+
+        for maybe_rect in &other {
+            let (min, max) = maybe_rect.map_or(([0.0; 2], [0.0; 2]), |rect| {
+                (rect.min().x_y().into(), rect.max().x_y().into())
+            });
+            minx.push(min[0]);
+            miny.push(min[1]);
+            maxx.push(max[0]);
+            maxy.push(max[1]);
+            validity.push(maybe_rect.is_some());
+        }
+
+        Self::new(
+            minx.into(),
+            miny.into(),
+            maxx.into(),
+            maxy.into(),
+            validity.into(),
+        )
+    }
+}
+
+impl From<Vec<geo::Rect>> for RectArray {
+    fn from(other: Vec<geo::Rect>) -> Self {
+        other.into_iter().map(Some).collect::<Vec<_>>().into()
+    }
+}
+
+impl TryFrom<StructArray> for RectArray {
+    type Error = GeoArrowError;
+
+    fn try_from(value: StructArray) -> Result<Self, Self::Error> {
+        let arrays = value.values();
+        if arrays.len() != 4 {
+            return Err(GeoArrowError::General(
+                "Expected four child arrays of this StructArray.".to_string(),
+            ));
+        }
+
+        let mut buffers = Vec::with_capacity(4);
+        for array in arrays {
+            let values = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f64>>()
+                .ok_or_else(|| GeoArrowError::Downcast {
+                    expected: "PrimitiveArray<f64>",
+                    found: array.data_type().clone(),
+                })?;
+            buffers.push(values.values().clone());
+        }
+        let [minx, miny, maxx, maxy]: [Buffer<f64>; 4] = buffers.try_into().unwrap();
+
+        Self::try_new(minx, miny, maxx, maxy, value.validity().cloned())
+    }
+}
+
+impl TryFrom<Box<dyn Array>> for RectArray {
+    type Error = GeoArrowError;
+
+    fn try_from(value: Box<dyn Array>) -> Result<Self, Self::Error> {
+        let arr = value
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .ok_or_else(|| GeoArrowError::Downcast {
+                expected: "StructArray",
+                found: value.data_type().clone(),
+            })?;
+        arr.clone().try_into()
+    }
+}
+
+impl From<RectArray> for PolygonArray {
+    fn from(value: RectArray) -> Self {
+        let polygons: Vec<Option<geo::Polygon>> = value
+            .iter_geo()
+            .map(|maybe_rect| maybe_rect.map(|rect| rect.to_polygon()))
+            .collect();
+        PolygonArray::from(polygons).with_crs(value.crs())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo::{coord, polygon};
+
+    fn rects() -> RectArray {
+        vec![
+            Some(geo::Rect::new(
+                coord! { x: 0., y: 0. },
+                coord! { x: 2., y: 1. },
+            )),
+            None,
+        ]
+        .into()
+    }
+
+    #[test]
+    fn arrow_roundtrip() {
+        let arr = rects();
+        let roundtrip: RectArray = arr.clone().into_arrow().boxed().try_into().unwrap();
+        assert_eq!(roundtrip.get_as_geo(0), arr.get_as_geo(0));
+        assert!(roundtrip.is_null(1));
+    }
+
+    #[test]
+    fn to_polygon_array() {
+        let polygons: PolygonArray = rects().into();
+        assert_eq!(
+            polygons.get_as_geo(0),
+            Some(polygon![(x: 2., y: 0.), (x: 2., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)])
+        );
+        assert!(polygons.is_null(1));
+    }
+}
@@ -0,0 +1,7 @@
+//! Helpers for using axis-aligned bounding box GeoArrow data
+
+pub use array::RectArray;
+pub use scalar::Rect;
+
+mod array;
+mod scalar;
@@ -0,0 +1,74 @@
+use crate::geo_traits::RectTrait;
+use arrow2::buffer::Buffer;
+use geo::{coord, Coord};
+use rstar::{RTreeObject, AABB};
+
+/// An Arrow equivalent of a Rect
+#[derive(Debug, Clone)]
+pub struct Rect<'a> {
+    pub minx: &'a Buffer<f64>,
+    pub miny: &'a Buffer<f64>,
+    pub maxx: &'a Buffer<f64>,
+    pub maxy: &'a Buffer<f64>,
+    pub geom_index: usize,
+}
+
+impl Rect<'_> {
+    /// The lower left corner of this box.
+    pub fn lower(&self) -> [f64; 2] {
+        [self.minx[self.geom_index], self.miny[self.geom_index]]
+    }
+
+    /// The upper right corner of this box.
+    pub fn upper(&self) -> [f64; 2] {
+        [self.maxx[self.geom_index], self.maxy[self.geom_index]]
+    }
+}
+
+impl RectTrait for Rect<'_> {
+    type ItemType = Coord;
+
+    fn min(&self) -> Self::ItemType {
+        let [x, y] = self.lower();
+        coord! { x: x, y: y }
+    }
+
+    fn max(&self) -> Self::ItemType {
+        let [x, y] = self.upper();
+        coord! { x: x, y: y }
+    }
+}
+
+impl From<Rect<'_>> for geo::Rect {
+    fn from(value: Rect<'_>) -> Self {
+        (&value).into()
+    }
+}
+
+impl From<&Rect<'_>> for geo::Rect {
+    fn from(value: &Rect<'_>) -> Self {
+        let [minx, miny] = value.lower();
+        let [maxx, maxy] = value.upper();
+        geo::Rect::new(coord! { x: minx, y: miny }, coord! { x: maxx, y: maxy })
+    }
+}
+
+impl From<Rect<'_>> for geo::Polygon {
+    fn from(value: Rect<'_>) -> Self {
+        geo::Rect::from(value).to_polygon()
+    }
+}
+
+impl From<Rect<'_>> for geo::Geometry {
+    fn from(value: Rect<'_>) -> Self {
+        geo::Geometry::Rect(value.into())
+    }
+}
+
+impl RTreeObject for Rect<'_> {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_corners(self.lower(), self.upper())
+    }
+}
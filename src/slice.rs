@@ -18,3 +18,91 @@ pub(crate) unsafe fn slice_validity_unchecked(
         *validity = None
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        GeometryArrayTrait, LineStringArray, MultiLineStringArray, MultiPointArray,
+        MultiPolygonArray, PointArray, PolygonArray, RectArray, WKBArray,
+    };
+    use arrow2::array::Array;
+    use geo::{
+        line_string, point, polygon, BoundingRect, MultiLineString, MultiPoint, MultiPolygon,
+    };
+
+    /// Check every empty slice and a full-range slice of `array`.
+    fn check_slices<'a, A>(array: &'a A)
+    where
+        A: GeometryArrayTrait<'a> + Clone,
+        A::ArrowArray: Array,
+    {
+        for offset in 0..=array.len() {
+            let empty = array.sliced_range(offset..offset);
+            assert_eq!(empty.len(), 0);
+            assert_eq!(empty.null_count(), 0);
+            assert_eq!(empty.into_arrow().len(), 0);
+        }
+
+        let full = array.sliced_range(0..array.len());
+        assert_eq!(full.len(), array.len());
+        assert_eq!(full.into_arrow().len(), array.len());
+    }
+
+    #[test]
+    fn zero_length_slices() {
+        let line = line_string![(x: 0., y: 0.), (x: 1., y: 1.)];
+        let polygon = polygon![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.)];
+
+        let points: PointArray = vec![Some(point!(x: 0., y: 0.)), None].into();
+        check_slices(&points);
+
+        let lines: LineStringArray = vec![Some(line.clone()), None].into();
+        check_slices(&lines);
+
+        let polygons: PolygonArray = vec![Some(polygon.clone()), None].into();
+        check_slices(&polygons);
+
+        let multi_points: MultiPointArray =
+            vec![Some(MultiPoint::new(vec![point!(x: 0., y: 0.)])), None].into();
+        check_slices(&multi_points);
+
+        let multi_lines: MultiLineStringArray =
+            vec![Some(MultiLineString::new(vec![line])), None].into();
+        check_slices(&multi_lines);
+
+        let multi_polygons: MultiPolygonArray =
+            vec![Some(MultiPolygon::new(vec![polygon.clone()])), None].into();
+        check_slices(&multi_polygons);
+
+        let wkb: WKBArray = vec![Some(geo::Geometry::Polygon(polygon.clone())), None].into();
+        check_slices(&wkb);
+
+        let rects: RectArray = vec![Some(polygon.bounding_rect().unwrap()), None].into();
+        check_slices(&rects);
+
+        // Empty nested arrays still carry a single offset
+        let empty = polygons.sliced_range(1..1).into_arrow();
+        assert_eq!(empty.offsets().len(), 1);
+        assert_eq!(empty.offsets().first(), empty.offsets().last());
+    }
+
+    #[test]
+    fn slice_range() {
+        let mut points: PointArray = vec![
+            point!(x: 0., y: 0.),
+            point!(x: 1., y: 1.),
+            point!(x: 2., y: 2.),
+        ]
+        .into();
+        points.slice_range(1..3);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points.value_as_geo(0), point!(x: 1., y: 1.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_range_out_of_bounds() {
+        let mut points: PointArray = vec![point!(x: 0., y: 0.)].into();
+        points.slice_range(1..2);
+    }
+}
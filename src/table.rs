@@ -0,0 +1,1270 @@
+//! Defines [`GeoTable`], a collection of Arrow chunks with a designated geometry column.
+
+use crate::algorithm::bounds::{bounds_intersect, chunk_bounds, TotalBounds};
+use crate::algorithm::explode::Explode;
+use crate::algorithm::geo::{map_geometries, predicate_mask, spatial_join, SpatialPredicate};
+use crate::algorithm::hilbert::hilbert_keys;
+use crate::coerce::{coerce, replace_geometry};
+use crate::crs::check_crs;
+use crate::enum_::{
+    extension_metadata, extension_name, EXTENSION_METADATA_KEY, EXTENSION_NAME_KEY,
+};
+use crate::error::GeoArrowError;
+use crate::{GeometryArray, GeometryArrayTrait, GeometryArrayType, WKBArray};
+use arrow2::array::growable::make_growable;
+use arrow2::array::{
+    get_display, new_null_array, Array, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array,
+};
+use arrow2::chunk::Chunk;
+use arrow2::compute::filter::filter_chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::types::NativeType;
+use geozero::error::GeozeroError;
+use geozero::{ColumnValue, FeatureProcessor, GeozeroDatasource, PropertyProcessor};
+use rstar::RTreeObject;
+
+/// The position of the geometry column among `fields` when none is named: the first field
+/// carrying a `geoarrow.*` extension name, falling back to a field named `"geometry"`.
+pub(crate) fn default_geometry_column(fields: &[Field]) -> Option<usize> {
+    fields
+        .iter()
+        .position(|field| extension_name(field).map_or(false, |name| name.starts_with("geoarrow.")))
+        .or_else(|| fields.iter().position(|field| field.name == "geometry"))
+}
+
+/// A table of Arrow chunks sharing one schema, one column of which holds geometries.
+///
+/// This is the common container passed between readers, writers and table-level algorithms.
+#[derive(Debug, Clone)]
+pub struct GeoTable {
+    schema: Schema,
+    chunks: Vec<Chunk<Box<dyn Array>>>,
+    geometry_column_index: usize,
+}
+
+impl GeoTable {
+    /// Create a new [`GeoTable`] from parts
+    /// # Implementation
+    /// This function is `O(C)`, where `C` is the number of chunks.
+    /// # Panics
+    /// This function panics if:
+    /// * `geometry_column_index` is out of bounds of the schema
+    /// * any chunk has a different number of columns than the schema
+    /// * the data type of any array differs from its field in the schema
+    pub fn new(
+        schema: Schema,
+        chunks: Vec<Chunk<Box<dyn Array>>>,
+        geometry_column_index: usize,
+    ) -> Self {
+        Self::try_new(schema, chunks, geometry_column_index).unwrap()
+    }
+
+    /// Create a new [`GeoTable`] from parts
+    /// # Implementation
+    /// This function is `O(C)`, where `C` is the number of chunks.
+    /// # Errors
+    /// This function errors iff:
+    /// * `geometry_column_index` is out of bounds of the schema
+    /// * any chunk has a different number of columns than the schema
+    /// * the data type of any array differs from its field in the schema
+    pub fn try_new(
+        schema: Schema,
+        chunks: Vec<Chunk<Box<dyn Array>>>,
+        geometry_column_index: usize,
+    ) -> Result<Self, GeoArrowError> {
+        if geometry_column_index >= schema.fields.len() {
+            return Err(GeoArrowError::General(format!(
+                "geometry column index {} out of bounds for schema with {} fields",
+                geometry_column_index,
+                schema.fields.len()
+            )));
+        }
+
+        for chunk in &chunks {
+            if chunk.arrays().len() != schema.fields.len() {
+                return Err(GeoArrowError::General(
+                    "chunk must have the same number of columns as the schema".to_string(),
+                ));
+            }
+
+            for (array, field) in chunk.arrays().iter().zip(schema.fields.iter()) {
+                if array.data_type() != field.data_type() {
+                    return Err(GeoArrowError::SchemaMismatch {
+                        column: field.name.clone(),
+                        expected: Box::new(field.data_type().clone()),
+                        found: Box::new(array.data_type().clone()),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            schema,
+            chunks,
+            geometry_column_index,
+        })
+    }
+
+    /// Create a [`GeoTable`] from an Arrow schema and chunks.
+    ///
+    /// When `geometry_column` is `None`, the geometry column is the first field carrying a
+    /// `geoarrow.*` extension name, falling back to a field named `"geometry"`.
+    pub fn from_arrow(
+        schema: Schema,
+        chunks: Vec<Chunk<Box<dyn Array>>>,
+        geometry_column: Option<&str>,
+    ) -> Result<Self, GeoArrowError> {
+        let geometry_column_index = match geometry_column {
+            Some(name) => schema.fields.iter().position(|field| field.name == name),
+            None => default_geometry_column(&schema.fields),
+        }
+        .ok_or_else(|| GeoArrowError::General("geometry column not found".to_string()))?;
+
+        Self::try_new(schema, chunks, geometry_column_index)
+    }
+
+    /// Concatenate the rows of `tables` into one table.
+    ///
+    /// Attribute columns are matched by name, in order of first appearance; a column missing
+    /// from some tables is null for their rows. Geometry columns, which must share a name, are
+    /// promoted to a common type: single and multi geometries of the same dimension become
+    /// multi geometries, and any other mix is stored as WKB. Geometries without a CRS are assumed
+    /// to be in the CRS of the others.
+    /// # Errors
+    /// Errors if `tables` is empty, if the geometry columns have different names or CRSes, or if a
+    /// column has different data types in different tables.
+    pub fn concat(tables: &[GeoTable]) -> Result<Self, GeoArrowError> {
+        let first = tables
+            .first()
+            .ok_or_else(|| GeoArrowError::General("cannot concatenate zero tables".to_string()))?;
+        let geometry_name = &first.geometry_field().name;
+
+        let mut geometries = Vec::with_capacity(tables.len());
+        for table in tables {
+            if &table.geometry_field().name != geometry_name {
+                return Err(GeoArrowError::General(format!(
+                    "geometry column {} does not match {}",
+                    table.geometry_field().name,
+                    geometry_name
+                )));
+            }
+            geometries.push(table.geometry()?);
+        }
+        let mut crs = None;
+        for geometry in geometries.iter().flatten() {
+            crs = check_crs(crs, geometry.crs())?;
+        }
+        let crs = crs.map(str::to_string);
+
+        // Attribute columns, in order of first appearance
+        let mut fields: Vec<Field> = vec![];
+        for table in tables {
+            for (idx, field) in table.schema.fields.iter().enumerate() {
+                if idx == table.geometry_column_index {
+                    continue;
+                }
+                match fields
+                    .iter_mut()
+                    .find(|existing| existing.name == field.name)
+                {
+                    Some(existing) if existing.data_type() != field.data_type() => {
+                        return Err(GeoArrowError::SchemaMismatch {
+                            column: field.name.clone(),
+                            expected: Box::new(existing.data_type().clone()),
+                            found: Box::new(field.data_type().clone()),
+                        });
+                    }
+                    Some(existing) => existing.is_nullable |= field.is_nullable,
+                    None => fields.push(field.clone()),
+                }
+            }
+        }
+        for field in fields.iter_mut() {
+            let in_every_table = tables.iter().all(|table| {
+                table
+                    .schema
+                    .fields
+                    .iter()
+                    .any(|table_field| table_field.name == field.name)
+            });
+            field.is_nullable |= !in_every_table;
+        }
+
+        // The narrowest geometry type holding every chunk
+        let kind = GeometryArrayType::common(
+            geometries
+                .iter()
+                .flatten()
+                .map(|geometry| geometry.geometry_type()),
+        );
+
+        let geometry_column_index = first.geometry_column_index.min(fields.len());
+        let mut geometry_field = first.geometry_field().clone();
+        if crs.is_some() {
+            geometry_field.metadata.insert(
+                EXTENSION_METADATA_KEY.to_string(),
+                extension_metadata(crs.as_deref()),
+            );
+        }
+        let mut chunks = vec![];
+        for (table, table_geometries) in tables.iter().zip(geometries) {
+            for (chunk, geometry) in table.chunks.iter().zip(table_geometries) {
+                let geometry = coerce(geometry, kind)?.into_arrow();
+                if geometry.data_type() != geometry_field.data_type() {
+                    geometry_field.data_type = geometry.data_type().clone();
+                    geometry_field.metadata.remove(EXTENSION_NAME_KEY);
+                    if let Some(name) = kind.extension_name() {
+                        geometry_field
+                            .metadata
+                            .insert(EXTENSION_NAME_KEY.to_string(), name.to_string());
+                    }
+                }
+
+                let mut arrays: Vec<Box<dyn Array>> = fields
+                    .iter()
+                    .map(|field| {
+                        match table
+                            .schema
+                            .fields
+                            .iter()
+                            .position(|table_field| table_field.name == field.name)
+                        {
+                            Some(idx) => chunk.arrays()[idx].clone(),
+                            None => new_null_array(field.data_type().clone(), chunk.len()),
+                        }
+                    })
+                    .collect();
+                arrays.insert(geometry_column_index, geometry);
+                chunks.push(Chunk::new(arrays));
+            }
+        }
+
+        fields.insert(geometry_column_index, geometry_field);
+        let schema = Schema::from(fields).with_metadata(first.schema.metadata.clone());
+        Self::try_new(schema, chunks, geometry_column_index)
+    }
+
+    /// Decompose this table into its Arrow schema and chunks.
+    pub fn into_arrow(self) -> (Schema, Vec<Chunk<Box<dyn Array>>>) {
+        (self.schema, self.chunks)
+    }
+
+    /// The schema shared by every chunk.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// The chunks of this table.
+    pub fn chunks(&self) -> &[Chunk<Box<dyn Array>>] {
+        &self.chunks
+    }
+
+    /// The position of the geometry column within the schema.
+    pub fn geometry_column_index(&self) -> usize {
+        self.geometry_column_index
+    }
+
+    /// The field describing the geometry column.
+    pub fn geometry_field(&self) -> &Field {
+        &self.schema.fields[self.geometry_column_index]
+    }
+
+    /// The total number of rows across all chunks.
+    pub fn len(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.len()).sum()
+    }
+
+    /// Returns true if the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of columns, including the geometry column.
+    pub fn num_columns(&self) -> usize {
+        self.schema.fields.len()
+    }
+
+    /// The geometry column of each chunk, as a [`GeometryArray`].
+    ///
+    /// The geometry type is read from the extension name of the geometry field (see
+    /// [`GeometryArray::from_arrow_field`]).
+    /// # Errors
+    /// Errors if the geometry column does not hold geometries of a type this crate can read.
+    pub fn geometry(&self) -> Result<Vec<GeometryArray>, GeoArrowError> {
+        let field = self.geometry_field();
+        self.chunks
+            .iter()
+            .map(|chunk| {
+                GeometryArray::from_arrow_field(
+                    field,
+                    chunk.arrays()[self.geometry_column_index].as_ref(),
+                )
+            })
+            .collect()
+    }
+
+    /// The `[minx, miny, maxx, maxy]` box containing every geometry of the table, or `None` if
+    /// there are no non-null, non-empty geometries.
+    pub fn total_bounds(&self) -> Result<Option<[f64; 4]>, GeoArrowError> {
+        Ok(self.geometry()?.total_bounds())
+    }
+
+    /// The `[minx, miny, maxx, maxy]` box of the geometries of each chunk; see [`chunk_bounds`].
+    pub fn chunk_bounds(&self) -> Result<Vec<Option<[f64; 4]>>, GeoArrowError> {
+        Ok(chunk_bounds(&self.geometry()?))
+    }
+
+    /// Keep only the rows whose geometry's bounding box intersects `bbox`, given as
+    /// `[minx, miny, maxx, maxy]`.
+    ///
+    /// Chunks whose bounds do not intersect `bbox` are dropped without looking at their rows.
+    pub fn filter_bbox(&self, bbox: &[f64; 4]) -> Result<Self, GeoArrowError> {
+        let mut mask = Vec::with_capacity(self.len());
+        for geometry in self.geometry()? {
+            match geometry.total_bounds() {
+                Some(bounds) if bounds_intersect(&bounds, bbox) => {
+                    mask.extend((0..geometry.len()).map(|i| {
+                        geometry.get(i).map_or(false, |geom| {
+                            let envelope = geom.envelope();
+                            let ([minx, miny], [maxx, maxy]) = (envelope.lower(), envelope.upper());
+                            bounds_intersect(&[minx, miny, maxx, maxy], bbox)
+                        })
+                    }))
+                }
+                _ => mask.resize(mask.len() + geometry.len(), false),
+            }
+        }
+        self.filter(&BooleanArray::from_slice(mask))
+    }
+
+    /// Keep only the rows where `mask` is true.
+    ///
+    /// `mask` spans the whole table; null values in the mask drop their row. Chunks left empty
+    /// by the filter are removed.
+    pub fn filter(&self, mask: &BooleanArray) -> Result<Self, GeoArrowError> {
+        if mask.len() != self.len() {
+            return Err(GeoArrowError::General(
+                "mask must have the same length as the table".to_string(),
+            ));
+        }
+
+        let mut offset = 0;
+        let mut chunks = Vec::with_capacity(self.chunks.len());
+        for chunk in &self.chunks {
+            let chunk_mask = mask.clone().sliced(offset, chunk.len());
+            offset += chunk.len();
+
+            let filtered = filter_chunk(chunk, &chunk_mask)
+                .map_err(|err| GeoArrowError::External(err.into()))?;
+            if !filtered.is_empty() {
+                chunks.push(filtered);
+            }
+        }
+
+        Ok(Self {
+            schema: self.schema.clone(),
+            chunks,
+            geometry_column_index: self.geometry_column_index,
+        })
+    }
+
+    /// Keep only the rows whose geometry satisfies `predicate`, evaluated in parallel.
+    ///
+    /// Rows with null geometries are dropped.
+    pub fn filter_by<F>(&self, predicate: F) -> Result<Self, GeoArrowError>
+    where
+        F: Fn(&geo::Geometry) -> bool + Sync,
+    {
+        let mut mask = Vec::with_capacity(self.len());
+        for geometry in self.geometry()? {
+            mask.extend(predicate_mask(&geometry, &predicate).iter());
+        }
+        self.filter(&BooleanArray::from(mask))
+    }
+
+    /// Replace each geometry with the output of `f`, evaluated in parallel.
+    ///
+    /// `f` returning `None` produces a null geometry. The geometry column takes the narrowest
+    /// type able to hold every returned geometry, falling back to WKB.
+    pub fn map_geometries<F>(&self, f: F) -> Result<Self, GeoArrowError>
+    where
+        F: Fn(geo::Geometry) -> Option<geo::Geometry> + Sync,
+    {
+        let outputs = self
+            .geometry()?
+            .iter()
+            .map(|geometry| map_geometries(geometry, &f))
+            .collect();
+        replace_geometry(self, outputs)
+    }
+
+    /// Append a `UInt64` column named `name` holding each row's position in this table.
+    ///
+    /// If the table already has a `UInt64` column with this name it is returned unchanged, so
+    /// the column keeps pointing at the rows of the table it was first added to. Operations
+    /// that carry columns through, such as [`filter`](Self::filter), preserve it; the
+    /// `*_with_row_index` variants call this before running, so results can be traced back to
+    /// source rows across a chain of operations.
+    /// # Errors
+    /// Errors if a column named `name` exists with a data type other than `UInt64`.
+    pub fn with_row_index(&self, name: &str) -> Result<Self, GeoArrowError> {
+        if let Some(field) = self.schema.fields.iter().find(|field| field.name == name) {
+            if field.data_type() != &DataType::UInt64 {
+                return Err(GeoArrowError::SchemaMismatch {
+                    column: name.to_string(),
+                    expected: Box::new(DataType::UInt64),
+                    found: Box::new(field.data_type().clone()),
+                });
+            }
+            return Ok(self.clone());
+        }
+
+        let mut fields = self.schema.fields.clone();
+        fields.push(Field::new(name, DataType::UInt64, false));
+        let schema = Schema::from(fields).with_metadata(self.schema.metadata.clone());
+
+        let mut offset = 0;
+        let chunks = self
+            .chunks
+            .iter()
+            .map(|chunk| {
+                let start = offset as u64;
+                offset += chunk.len();
+                let row_index = PrimitiveArray::<u64>::from_vec((start..offset as u64).collect());
+
+                let mut arrays = chunk.arrays().to_vec();
+                arrays.push(row_index.boxed());
+                Chunk::new(arrays)
+            })
+            .collect();
+
+        Ok(Self {
+            schema,
+            chunks,
+            geometry_column_index: self.geometry_column_index,
+        })
+    }
+
+    /// Like [`filter`](Self::filter), but also emits a row index column named `index_name`.
+    ///
+    /// See [`with_row_index`](Self::with_row_index).
+    pub fn filter_with_row_index(
+        &self,
+        mask: &BooleanArray,
+        index_name: &str,
+    ) -> Result<Self, GeoArrowError> {
+        self.with_row_index(index_name)?.filter(mask)
+    }
+
+    /// A new table holding the row at each of `indices`, in order, as one chunk.
+    ///
+    /// `indices` span the whole table. Null indices produce rows of nulls, and make every column
+    /// nullable. Indices may repeat.
+    /// # Errors
+    /// Errors if an index is negative or out of bounds.
+    pub fn take(&self, indices: &PrimitiveArray<i64>) -> Result<Self, GeoArrowError> {
+        let len = self.len();
+        let mut starts = Vec::with_capacity(self.chunks.len());
+        let mut offset = 0;
+        for chunk in &self.chunks {
+            starts.push(offset);
+            offset += chunk.len();
+        }
+
+        // The chunk and row within it of each index
+        let rows = indices
+            .iter()
+            .map(|index| {
+                index
+                    .map(|index| match usize::try_from(*index) {
+                        Ok(row) if row < len => {
+                            let chunk = starts.partition_point(|start| *start <= row) - 1;
+                            Ok((chunk, row - starts[chunk]))
+                        }
+                        _ => Err(GeoArrowError::General(format!(
+                            "index {} out of bounds for table of length {}",
+                            index, len
+                        ))),
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut schema = self.schema.clone();
+        if rows.is_empty() {
+            return Ok(Self {
+                schema,
+                chunks: vec![],
+                geometry_column_index: self.geometry_column_index,
+            });
+        }
+
+        let has_nulls = rows.iter().any(Option::is_none);
+        if has_nulls {
+            for field in schema.fields.iter_mut() {
+                field.is_nullable = true;
+            }
+        }
+        let arrays = (0..self.num_columns())
+            .map(|column| {
+                let arrays: Vec<&dyn Array> = self
+                    .chunks
+                    .iter()
+                    .map(|chunk| chunk.arrays()[column].as_ref())
+                    .collect();
+                let mut growable = make_growable(&arrays, has_nulls, rows.len());
+                for row in &rows {
+                    match row {
+                        Some((chunk, row)) => growable.extend(*chunk, *row, 1),
+                        None => growable.extend_validity(1),
+                    }
+                }
+                growable.as_box()
+            })
+            .collect();
+
+        Ok(Self {
+            schema,
+            chunks: vec![Chunk::new(arrays)],
+            geometry_column_index: self.geometry_column_index,
+        })
+    }
+
+    /// Split multi geometries into their parts, one row per part; see [`Explode`].
+    ///
+    /// The other columns of each row are repeated for each of its parts. Null geometries keep
+    /// one row, and empty multi geometries produce none. Chunks of other geometry types are
+    /// unchanged.
+    pub fn explode(&self) -> Result<Self, GeoArrowError> {
+        let mut chunks = Vec::with_capacity(self.chunks.len());
+        let mut outputs = Vec::with_capacity(self.chunks.len());
+        for (chunk, geometry) in self.chunks.iter().zip(self.geometry()?) {
+            let (exploded, indices) = match geometry {
+                GeometryArray::MultiPoint(array) => {
+                    let (exploded, indices) = array.explode();
+                    (GeometryArray::Point(exploded), indices)
+                }
+                GeometryArray::MultiLineString(array) => {
+                    let (exploded, indices) = array.explode();
+                    (GeometryArray::LineString(exploded), indices)
+                }
+                GeometryArray::MultiPolygon(array) => {
+                    let (exploded, indices) = array.explode();
+                    (GeometryArray::Polygon(exploded), indices)
+                }
+                geometry => {
+                    chunks.push(chunk.clone());
+                    outputs.push(geometry);
+                    continue;
+                }
+            };
+            if indices.is_empty() {
+                continue;
+            }
+
+            let table = Self {
+                schema: self.schema.clone(),
+                chunks: vec![chunk.clone()],
+                geometry_column_index: self.geometry_column_index,
+            };
+            chunks.extend(table.take(&indices)?.chunks);
+            outputs.push(exploded);
+        }
+
+        let table = Self {
+            schema: self.schema.clone(),
+            chunks,
+            geometry_column_index: self.geometry_column_index,
+        };
+        replace_geometry(&table, outputs)
+    }
+
+    /// Like [`explode`](Self::explode), but also emits a row index column named `index_name`,
+    /// repeated for each part.
+    ///
+    /// See [`with_row_index`](Self::with_row_index).
+    pub fn explode_with_row_index(&self, index_name: &str) -> Result<Self, GeoArrowError> {
+        self.with_row_index(index_name)?.explode()
+    }
+
+    /// Join each row to every row of `right` whose geometry satisfies `predicate`; see
+    /// [`spatial_join`].
+    ///
+    /// The output holds one row per matching pair, ordered by the left row and then by the right
+    /// row, as one chunk. Its columns are those of this table, followed by the attribute columns
+    /// of `right`; a right column whose name is already taken gets a `_right` suffix. The
+    /// geometry column is the one of this table.
+    /// # Errors
+    /// Errors if the geometry columns have different CRSes.
+    pub fn spatial_join(
+        &self,
+        right: &GeoTable,
+        predicate: SpatialPredicate,
+    ) -> Result<Self, GeoArrowError> {
+        let left_geometry = self.geometry()?;
+        let right_geometry = right.geometry()?;
+        let mut crs = None;
+        for geometry in left_geometry.iter().chain(&right_geometry) {
+            crs = check_crs(crs, geometry.crs())?;
+        }
+
+        let mut pairs = vec![];
+        let mut left_offset = 0;
+        for left_chunk in &left_geometry {
+            let mut right_offset = 0;
+            for right_chunk in &right_geometry {
+                let (left_indices, right_indices) =
+                    spatial_join(left_chunk, right_chunk, predicate);
+                pairs.extend(
+                    left_indices
+                        .values()
+                        .iter()
+                        .zip(right_indices.values().iter())
+                        .map(|(left_idx, right_idx)| {
+                            (
+                                left_offset + *left_idx as i64,
+                                right_offset + *right_idx as i64,
+                            )
+                        }),
+                );
+                right_offset += right_chunk.len() as i64;
+            }
+            left_offset += left_chunk.len() as i64;
+        }
+        pairs.sort_unstable();
+        let (left_indices, right_indices): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+        let left = self.take(&PrimitiveArray::from_vec(left_indices))?;
+        let right = right.take(&PrimitiveArray::from_vec(right_indices))?;
+
+        let mut fields = left.schema.fields.clone();
+        let mut right_columns = vec![];
+        for (idx, field) in right.schema.fields.iter().enumerate() {
+            if idx == right.geometry_column_index {
+                continue;
+            }
+            let mut field = field.clone();
+            if fields.iter().any(|existing| existing.name == field.name) {
+                field.name = format!("{}_right", field.name);
+            }
+            fields.push(field);
+            right_columns.push(idx);
+        }
+        let schema = Schema::from(fields).with_metadata(self.schema.metadata.clone());
+
+        let chunks = left
+            .chunks
+            .iter()
+            .zip(&right.chunks)
+            .map(|(left_chunk, right_chunk)| {
+                let mut arrays = left_chunk.arrays().to_vec();
+                arrays.extend(
+                    right_columns
+                        .iter()
+                        .map(|idx| right_chunk.arrays()[*idx].clone()),
+                );
+                Chunk::new(arrays)
+            })
+            .collect();
+
+        Self::try_new(schema, chunks, self.geometry_column_index)
+    }
+
+    /// Like [`spatial_join`](Self::spatial_join), but also emits row index columns: `index_name`
+    /// for the rows of this table and `{index_name}_right` for the rows of `right`.
+    ///
+    /// See [`with_row_index`](Self::with_row_index).
+    pub fn spatial_join_with_row_index(
+        &self,
+        right: &GeoTable,
+        predicate: SpatialPredicate,
+        index_name: &str,
+    ) -> Result<Self, GeoArrowError> {
+        self.with_row_index(index_name)?
+            .spatial_join(&right.with_row_index(index_name)?, predicate)
+    }
+
+    /// Sort the rows along a Hilbert curve by the center of each geometry's bounding box within
+    /// the total bounds of the table, as one chunk; see [`hilbert_sort_indices`].
+    ///
+    /// The sort is stable, and rows with null or empty geometries are placed last. A table with
+    /// no non-empty geometries is returned unchanged.
+    ///
+    /// [`hilbert_sort_indices`]: crate::algorithm::hilbert::hilbert_sort_indices
+    pub fn hilbert_sort(&self) -> Result<Self, GeoArrowError> {
+        let geometry = self.geometry()?;
+        let Some([min_x, min_y, max_x, max_y]) = geometry.total_bounds() else {
+            return Ok(self.clone());
+        };
+        let bounds = geo::Rect::new((min_x, min_y), (max_x, max_y));
+        let mut keys: Vec<Option<u32>> = Vec::with_capacity(self.len());
+        for array in &geometry {
+            keys.extend(hilbert_keys(array, &bounds).iter().map(|key| key.copied()));
+        }
+
+        // `None` sorts before `Some`, so sort on whether the key is null first to place nulls last
+        let mut indices: Vec<i64> = (0..keys.len() as i64).collect();
+        indices.sort_by_key(|&i| (keys[i as usize].is_none(), keys[i as usize]));
+        self.take(&PrimitiveArray::from_vec(indices))
+    }
+
+    /// Like [`hilbert_sort`](Self::hilbert_sort), but also emits a row index column named
+    /// `index_name`.
+    ///
+    /// See [`with_row_index`](Self::with_row_index).
+    pub fn hilbert_sort_with_row_index(&self, index_name: &str) -> Result<Self, GeoArrowError> {
+        self.with_row_index(index_name)?.hilbert_sort()
+    }
+
+    /// Keep only the named columns, in schema order.
+    ///
+    /// The geometry column is always kept, whether or not it is listed.
+    pub fn select(&self, columns: &[&str]) -> Result<Self, GeoArrowError> {
+        for name in columns {
+            if !self.schema.fields.iter().any(|field| field.name == *name) {
+                return Err(GeoArrowError::General(format!(
+                    "column \"{}\" not found",
+                    name
+                )));
+            }
+        }
+
+        let indices: Vec<usize> = self
+            .schema
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(idx, field)| {
+                *idx == self.geometry_column_index || columns.contains(&field.name.as_str())
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let fields = indices
+            .iter()
+            .map(|idx| self.schema.fields[*idx].clone())
+            .collect::<Vec<_>>();
+        let schema = Schema::from(fields).with_metadata(self.schema.metadata.clone());
+
+        let chunks = self
+            .chunks
+            .iter()
+            .map(|chunk| {
+                Chunk::new(
+                    indices
+                        .iter()
+                        .map(|idx| chunk.arrays()[*idx].clone())
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let geometry_column_index = indices
+            .iter()
+            .position(|idx| *idx == self.geometry_column_index)
+            .unwrap();
+
+        Ok(Self {
+            schema,
+            chunks,
+            geometry_column_index,
+        })
+    }
+}
+
+/// Emit the value at `row` of an attribute column as the property `idx` named `name`.
+///
+/// Columns without a matching [`ColumnValue`], such as dates or lists, are emitted as strings
+/// formatted by Arrow.
+fn process_property<P: PropertyProcessor>(
+    processor: &mut P,
+    idx: usize,
+    name: &str,
+    array: &dyn Array,
+    row: usize,
+) -> geozero::error::Result<bool> {
+    fn primitive<T: NativeType>(array: &dyn Array, row: usize) -> T {
+        array
+            .as_any()
+            .downcast_ref::<PrimitiveArray<T>>()
+            .unwrap()
+            .value(row)
+    }
+
+    let value = match array.data_type() {
+        DataType::Boolean => ColumnValue::Bool(
+            array
+                .as_any()
+                .downcast_ref::<BooleanArray>()
+                .unwrap()
+                .value(row),
+        ),
+        DataType::Int8 => ColumnValue::Byte(primitive(array, row)),
+        DataType::UInt8 => ColumnValue::UByte(primitive(array, row)),
+        DataType::Int16 => ColumnValue::Short(primitive(array, row)),
+        DataType::UInt16 => ColumnValue::UShort(primitive(array, row)),
+        DataType::Int32 => ColumnValue::Int(primitive(array, row)),
+        DataType::UInt32 => ColumnValue::UInt(primitive(array, row)),
+        DataType::Int64 => ColumnValue::Long(primitive(array, row)),
+        DataType::UInt64 => ColumnValue::ULong(primitive(array, row)),
+        DataType::Float32 => ColumnValue::Float(primitive(array, row)),
+        DataType::Float64 => ColumnValue::Double(primitive(array, row)),
+        DataType::Utf8 => ColumnValue::String(
+            array
+                .as_any()
+                .downcast_ref::<Utf8Array<i32>>()
+                .unwrap()
+                .value(row),
+        ),
+        DataType::LargeUtf8 => ColumnValue::String(
+            array
+                .as_any()
+                .downcast_ref::<Utf8Array<i64>>()
+                .unwrap()
+                .value(row),
+        ),
+        DataType::Binary => ColumnValue::Binary(
+            array
+                .as_any()
+                .downcast_ref::<BinaryArray<i32>>()
+                .unwrap()
+                .value(row),
+        ),
+        DataType::LargeBinary => ColumnValue::Binary(
+            array
+                .as_any()
+                .downcast_ref::<BinaryArray<i64>>()
+                .unwrap()
+                .value(row),
+        ),
+        _ => {
+            let mut formatted = String::new();
+            get_display(array, "null")(&mut formatted, row)
+                .map_err(|err| GeozeroError::Property(err.to_string()))?;
+            return processor.property(idx, name, &ColumnValue::String(&formatted));
+        }
+    };
+    processor.property(idx, name, &value)
+}
+
+/// Each row is emitted as one feature, in order across chunks. Attribute columns become its
+/// properties, numbered in schema order without the geometry column; null values are omitted.
+/// Rows with a null geometry are emitted without a geometry.
+impl GeozeroDatasource for GeoTable {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> geozero::error::Result<()> {
+        let geometries = self
+            .geometry()
+            .map_err(|err| GeozeroError::Dataset(err.to_string()))?;
+
+        processor.dataset_begin(None)?;
+        let mut feature_idx = 0;
+        for (chunk, geometry) in self.chunks.iter().zip(geometries) {
+            let wkb = WKBArray::from(geometry).into_arrow();
+            for row in 0..chunk.len() {
+                processor.feature_begin(feature_idx)?;
+
+                processor.properties_begin()?;
+                let mut property_idx = 0;
+                for (column_idx, (field, array)) in
+                    self.schema.fields.iter().zip(chunk.arrays()).enumerate()
+                {
+                    if column_idx == self.geometry_column_index {
+                        continue;
+                    }
+                    if array.is_valid(row) {
+                        process_property(
+                            processor,
+                            property_idx,
+                            &field.name,
+                            array.as_ref(),
+                            row,
+                        )?;
+                    }
+                    property_idx += 1;
+                }
+                processor.properties_end()?;
+
+                if wkb.is_valid(row) {
+                    processor.geometry_begin()?;
+                    geozero::wkb::process_wkb_geom(&mut wkb.value(row), processor)?;
+                    processor.geometry_end()?;
+                }
+
+                processor.feature_end(feature_idx)?;
+                feature_idx += 1;
+            }
+        }
+        processor.dataset_end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::hilbert::hilbert_sort_indices;
+    use crate::{MultiPointArray, PointArray};
+    use arrow2::array::{Int32Array, Int64Array, Utf8Array};
+    use arrow2::datatypes::Metadata;
+    use geo::{point, MultiPoint};
+
+    fn table() -> GeoTable {
+        let points: PointArray = vec![
+            point!(x: 0., y: 1.),
+            point!(x: 1., y: 2.),
+            point!(x: 2., y: 3.),
+        ]
+        .into();
+        let points = points.into_arrow();
+        let schema = Schema::from(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("geometry", points.data_type().clone(), true),
+        ]);
+        let chunks = vec![
+            Chunk::new(vec![
+                Int32Array::from_slice([0, 1]).boxed(),
+                points.clone().sliced(0, 2).boxed(),
+            ]),
+            Chunk::new(vec![
+                Int32Array::from_slice([2]).boxed(),
+                points.sliced(2, 1).boxed(),
+            ]),
+        ];
+        GeoTable::from_arrow(schema, chunks, None).unwrap()
+    }
+
+    #[test]
+    fn from_arrow_finds_geometry_column() {
+        let table = table();
+        assert_eq!(table.geometry_column_index(), 1);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.num_columns(), 2);
+
+        let geometry = table.geometry().unwrap();
+        assert_eq!(geometry.len(), 2);
+        assert!(matches!(geometry[0], GeometryArray::Point(_)));
+    }
+
+    #[test]
+    fn try_new_rejects_mismatched_chunks() {
+        let (schema, mut chunks) = table().into_arrow();
+        chunks.push(Chunk::new(vec![Int32Array::from_slice([3]).boxed()]));
+        assert!(GeoTable::try_new(schema.clone(), chunks, 1).is_err());
+        assert!(GeoTable::try_new(schema.clone(), vec![], 2).is_err());
+
+        let chunks = vec![Chunk::new(vec![
+            Int64Array::from_slice([0]).boxed(),
+            Int32Array::from_slice([0]).boxed(),
+        ])];
+        let err = GeoTable::try_new(schema, chunks, 1).unwrap_err();
+        assert!(matches!(
+            err,
+            GeoArrowError::SchemaMismatch { ref column, .. } if column == "id"
+        ));
+    }
+
+    #[test]
+    fn filter_across_chunks() {
+        let mask = BooleanArray::from_slice([true, false, false]);
+        let filtered = table().filter(&mask).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered.chunks().len(), 1);
+
+        let ids = filtered.chunks()[0].arrays()[0]
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(ids.value(0), 0);
+    }
+
+    #[test]
+    fn closures_over_geometries() {
+        let filtered = table()
+            .filter_by(|geom| matches!(geom, geo::Geometry::Point(p) if p.x() > 0.5))
+            .unwrap();
+        assert_eq!(filtered.len(), 2);
+
+        let mapped = table()
+            .map_geometries(|geom| match geom {
+                geo::Geometry::Point(p) if p.x() > 0. => Some(geo::Geometry::MultiPoint(p.into())),
+                _ => None,
+            })
+            .unwrap();
+        let geometry = mapped.geometry().unwrap();
+        assert!(matches!(geometry[0], GeometryArray::MultiPoint(_)));
+        assert!(matches!(geometry[1], GeometryArray::MultiPoint(_)));
+        assert!(mapped.chunks()[0].arrays()[1].is_null(0));
+    }
+
+    #[test]
+    fn row_index_survives_chained_filters() {
+        let table = table()
+            .filter_with_row_index(&BooleanArray::from_slice([false, true, true]), "row")
+            .unwrap();
+        let table = table
+            .filter_with_row_index(&BooleanArray::from_slice([false, true]), "row")
+            .unwrap();
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.num_columns(), 3);
+
+        let row_index = table.chunks()[0].arrays()[2]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<u64>>()
+            .unwrap();
+        assert_eq!(row_index.value(0), 2);
+
+        assert!(table.with_row_index("id").is_err());
+    }
+
+    /// The values of the column `name` across all chunks.
+    fn column<T: NativeType>(table: &GeoTable, name: &str) -> Vec<Option<T>> {
+        let idx = table
+            .schema()
+            .fields
+            .iter()
+            .position(|field| field.name == name)
+            .unwrap();
+        table
+            .chunks()
+            .iter()
+            .flat_map(|chunk| {
+                chunk.arrays()[idx]
+                    .as_any()
+                    .downcast_ref::<PrimitiveArray<T>>()
+                    .unwrap()
+                    .iter()
+                    .map(|value| value.copied())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn take_across_chunks() {
+        let taken = table()
+            .take(&PrimitiveArray::from([Some(2), None, Some(0), Some(2)]))
+            .unwrap();
+        assert_eq!(taken.chunks().len(), 1);
+        assert_eq!(
+            column::<i32>(&taken, "id"),
+            vec![Some(2), None, Some(0), Some(2)]
+        );
+        assert!(taken.schema().fields[0].is_nullable);
+
+        let geometry = taken.geometry().unwrap();
+        assert_eq!(geometry[0].len(), 4);
+        assert!(geometry[0].is_null(1));
+
+        assert!(table().take(&PrimitiveArray::from_slice([3])).is_err());
+        assert!(table().take(&PrimitiveArray::from_slice([-1])).is_err());
+    }
+
+    #[test]
+    fn row_index_survives_explode() {
+        let multi_points: MultiPointArray = vec![
+            MultiPoint::from(vec![(0., 0.), (1., 1.)]),
+            MultiPoint::from(vec![(2., 2.)]),
+            MultiPoint::from(vec![(3., 3.), (4., 4.)]),
+        ]
+        .into();
+        let (schema, chunks) = table().into_arrow();
+        let table = replace_geometry(
+            &GeoTable::try_new(schema, chunks, 1).unwrap(),
+            vec![
+                GeometryArray::MultiPoint(multi_points.sliced_range(0..2)),
+                GeometryArray::MultiPoint(multi_points.sliced_range(2..3)),
+            ],
+        )
+        .unwrap();
+
+        let exploded = table.explode_with_row_index("row").unwrap();
+        assert_eq!(exploded.len(), 5);
+        assert_eq!(
+            column::<u64>(&exploded, "row"),
+            vec![Some(0), Some(0), Some(1), Some(2), Some(2)]
+        );
+        assert_eq!(
+            column::<i32>(&exploded, "id"),
+            vec![Some(0), Some(0), Some(1), Some(2), Some(2)]
+        );
+        let geometry = exploded.geometry().unwrap();
+        assert!(matches!(geometry[0], GeometryArray::Point(_)));
+        assert_eq!(geometry[1].value_as_geo(1), point!(x: 4., y: 4.).into());
+    }
+
+    #[test]
+    fn row_index_survives_spatial_join() {
+        let right = table()
+            .filter_with_row_index(&BooleanArray::from_slice([false, true, true]), "row")
+            .unwrap();
+        let joined = table()
+            .spatial_join_with_row_index(&right, SpatialPredicate::Intersects, "row")
+            .unwrap();
+        assert_eq!(joined.len(), 2);
+        assert_eq!(joined.geometry_column_index(), 1);
+        assert_eq!(column::<u64>(&joined, "row"), vec![Some(1), Some(2)]);
+        assert_eq!(column::<u64>(&joined, "row_right"), vec![Some(1), Some(2)]);
+        assert_eq!(column::<i32>(&joined, "id_right"), vec![Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn row_index_survives_hilbert_sort() {
+        let points: PointArray = vec![
+            point!(x: 0., y: 0.),
+            point!(x: 1., y: 1.),
+            point!(x: 0., y: 1.),
+            point!(x: 1., y: 0.),
+        ]
+        .into();
+        let points = GeometryArray::Point(points);
+        let (schema, chunks) = table().into_arrow();
+        let mut chunks = chunks;
+        chunks.push(chunks[1].clone());
+        let table = replace_geometry(
+            &GeoTable::try_new(schema, chunks, 1).unwrap(),
+            vec![
+                points.sliced_range(0..2),
+                points.sliced_range(2..3),
+                points.sliced_range(3..4),
+            ],
+        )
+        .unwrap();
+
+        let sorted = table.hilbert_sort_with_row_index("row").unwrap();
+        let expected: Vec<_> = hilbert_sort_indices(&points)
+            .values()
+            .iter()
+            .map(|idx| Some(*idx as u64))
+            .collect();
+        assert_eq!(column::<u64>(&sorted, "row"), expected);
+
+        let geometry = sorted.geometry().unwrap();
+        for (i, row) in expected.iter().enumerate() {
+            assert_eq!(
+                geometry[0].value_as_geo(i),
+                points.value_as_geo(row.unwrap() as usize)
+            );
+        }
+    }
+
+    #[test]
+    fn select_keeps_geometry() {
+        let selected = table().select(&[]).unwrap();
+        assert_eq!(selected.num_columns(), 1);
+        assert_eq!(selected.geometry_column_index(), 0);
+        assert_eq!(selected.geometry_field().name, "geometry");
+
+        assert!(table().select(&["missing"]).is_err());
+    }
+
+    #[test]
+    fn concat_promotes_geometries() {
+        let multi_points: MultiPointArray = vec![MultiPoint::new(vec![
+            point!(x: 5., y: 5.),
+            point!(x: 6., y: 6.),
+        ])]
+        .into();
+        let multi_points = multi_points.into_arrow();
+        let mut metadata = Metadata::new();
+        metadata.insert(
+            EXTENSION_NAME_KEY.to_string(),
+            "geoarrow.multipoint".to_string(),
+        );
+        let schema = Schema::from(vec![
+            Field::new("geometry", multi_points.data_type().clone(), true).with_metadata(metadata),
+            Field::new("name", DataType::Utf8, false),
+        ]);
+        let chunks = vec![Chunk::new(vec![
+            multi_points.boxed(),
+            Utf8Array::<i32>::from_slice(["a"]).boxed(),
+        ])];
+        let other = GeoTable::from_arrow(schema, chunks, None).unwrap();
+
+        let concatenated = GeoTable::concat(&[table(), other]).unwrap();
+        assert_eq!(concatenated.len(), 4);
+        assert_eq!(concatenated.chunks().len(), 3);
+        let names: Vec<_> = concatenated
+            .schema()
+            .fields
+            .iter()
+            .map(|f| &f.name)
+            .collect();
+        assert_eq!(names, vec!["id", "geometry", "name"]);
+        assert!(concatenated.schema().fields[0].is_nullable);
+
+        let geometry = concatenated.geometry().unwrap();
+        match &geometry[0] {
+            GeometryArray::MultiPoint(arr) => assert_eq!(
+                arr.value_as_geo(1),
+                MultiPoint::new(vec![point!(x: 1., y: 2.)])
+            ),
+            _ => panic!("expected a multi point array"),
+        }
+        assert!(matches!(geometry[2], GeometryArray::MultiPoint(_)));
+        assert!(concatenated.chunks()[2].arrays()[0].is_null(0));
+        assert!(concatenated.chunks()[0].arrays()[2].is_null(1));
+
+        let (schema, chunks) = concatenated.into_arrow();
+        let id_as_string = Schema::from(vec![
+            Field::new("id", DataType::Utf8, true),
+            schema.fields[1].clone(),
+        ]);
+        let chunks = vec![Chunk::new(vec![
+            Utf8Array::<i32>::from_slice(["0", "1"]).boxed(),
+            chunks[0].arrays()[1].clone(),
+        ])];
+        let mismatched = GeoTable::from_arrow(id_as_string, chunks, None).unwrap();
+        assert!(GeoTable::concat(&[table(), mismatched]).is_err());
+        assert!(GeoTable::concat(&[]).is_err());
+    }
+
+    #[test]
+    fn concat_checks_crs() {
+        let table_in = |crs: &str| {
+            let points: PointArray = vec![point!(x: 0., y: 1.)].into();
+            let (field, array) =
+                GeometryArray::Point(points.with_crs(Some(crs))).into_arrow_field("geometry");
+            GeoTable::from_arrow(
+                Schema::from(vec![field]),
+                vec![Chunk::new(vec![array])],
+                None,
+            )
+            .unwrap()
+        };
+
+        let concatenated = GeoTable::concat(&[table(), table_in("EPSG:3857")]).unwrap();
+        let geometry = concatenated.geometry().unwrap();
+        assert!(geometry.iter().all(|arr| arr.crs() == Some("EPSG:3857")));
+
+        assert!(matches!(
+            GeoTable::concat(&[table_in("EPSG:3857"), table_in("EPSG:4326")]),
+            Err(GeoArrowError::CrsMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn process_features() {
+        let mut out: Vec<u8> = Vec::new();
+        table()
+            .process(&mut geozero::geojson::GeoJsonWriter::new(&mut out))
+            .unwrap();
+        let json = String::from_utf8(out).unwrap();
+        assert!(json.starts_with("{\n\"type\": \"FeatureCollection\""));
+        assert_eq!(json.matches("\"type\": \"Feature\"").count(), 3);
+        assert!(json.contains("\"properties\": {\"id\": 2}"));
+        assert!(json.contains("\"coordinates\": [2,3]"));
+    }
+
+    #[test]
+    fn bounds() {
+        let table = table();
+        assert_eq!(
+            table.chunk_bounds().unwrap(),
+            vec![Some([0., 1., 1., 2.]), Some([2., 3., 2., 3.])]
+        );
+        assert_eq!(table.total_bounds().unwrap(), Some([0., 1., 2., 3.]));
+
+        let filtered = table.filter_bbox(&[0.5, 1.5, 1.5, 5.]).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered.chunks().len(), 1);
+        assert!(table.filter_bbox(&[10., 10., 11., 11.]).unwrap().is_empty());
+    }
+}
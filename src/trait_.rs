@@ -1,6 +1,121 @@
+use crate::indexed::CachedEnvelope;
 use arrow2::bitmap::{Bitmap, MutableBitmap};
 use rstar::{RTree, RTreeObject};
 use std::any::Any;
+use std::ops::Range;
+
+/// The physical layout of a geometry array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GeometryArrayType {
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    Rect,
+    Mixed,
+    GeometryCollection,
+    WKB,
+}
+
+impl GeometryArrayType {
+    /// The type of the native array holding `geom`, or WKB for geometry collections, lines and
+    /// triangles.
+    pub(crate) fn of_geo(geom: &geo::Geometry) -> Self {
+        match geom {
+            geo::Geometry::Point(_) => GeometryArrayType::Point,
+            geo::Geometry::LineString(_) => GeometryArrayType::LineString,
+            geo::Geometry::Polygon(_) => GeometryArrayType::Polygon,
+            geo::Geometry::MultiPoint(_) => GeometryArrayType::MultiPoint,
+            geo::Geometry::MultiLineString(_) => GeometryArrayType::MultiLineString,
+            geo::Geometry::MultiPolygon(_) => GeometryArrayType::MultiPolygon,
+            geo::Geometry::Rect(_) => GeometryArrayType::Rect,
+            _ => GeometryArrayType::WKB,
+        }
+    }
+
+    /// The narrowest type able to hold geometries of both `self` and `other`, which is WKB when
+    /// they have no common native type.
+    pub(crate) fn promote(self, other: Self) -> Self {
+        use GeometryArrayType::*;
+        match (self, other) {
+            (left, right) if left == right => left,
+            (Point, MultiPoint) | (MultiPoint, Point) => MultiPoint,
+            (LineString, MultiLineString) | (MultiLineString, LineString) => MultiLineString,
+            (Polygon, Rect) | (Rect, Polygon) => Polygon,
+            (Polygon | Rect, MultiPolygon) | (MultiPolygon, Polygon | Rect) => MultiPolygon,
+            _ => WKB,
+        }
+    }
+
+    /// The narrowest type able to hold geometries of every type of `types`.
+    ///
+    /// Returns WKB if `types` is empty or its types have no common native type.
+    pub(crate) fn common(mut types: impl Iterator<Item = Self>) -> Self {
+        match types.next() {
+            Some(first) => types.fold(first, Self::promote),
+            None => GeometryArrayType::WKB,
+        }
+    }
+
+    /// The GeoArrow extension name of arrays of this type, if the specification defines one.
+    pub fn extension_name(&self) -> Option<&'static str> {
+        match self {
+            GeometryArrayType::Point => Some("geoarrow.point"),
+            GeometryArrayType::LineString => Some("geoarrow.linestring"),
+            GeometryArrayType::Polygon => Some("geoarrow.polygon"),
+            GeometryArrayType::MultiPoint => Some("geoarrow.multipoint"),
+            GeometryArrayType::MultiLineString => Some("geoarrow.multilinestring"),
+            GeometryArrayType::MultiPolygon => Some("geoarrow.multipolygon"),
+            GeometryArrayType::Rect => None,
+            GeometryArrayType::Mixed => Some("geoarrow.geometry"),
+            GeometryArrayType::GeometryCollection => Some("geoarrow.geometrycollection"),
+            GeometryArrayType::WKB => Some("geoarrow.wkb"),
+        }
+    }
+}
+
+/// The dimensions of the coordinates of a geometry array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    XY,
+    XYZ,
+    /// x and y with a measure, as used for linear referencing.
+    XYM,
+    XYZM,
+}
+
+impl Dimension {
+    /// The dimension of coordinates with or without z and m values.
+    pub fn new(has_z: bool, has_m: bool) -> Self {
+        match (has_z, has_m) {
+            (false, false) => Dimension::XY,
+            (true, false) => Dimension::XYZ,
+            (false, true) => Dimension::XYM,
+            (true, true) => Dimension::XYZM,
+        }
+    }
+
+    /// The number of values in each coordinate.
+    pub fn size(&self) -> usize {
+        match self {
+            Dimension::XY => 2,
+            Dimension::XYZ | Dimension::XYM => 3,
+            Dimension::XYZM => 4,
+        }
+    }
+
+    /// Whether coordinates of this dimension have a z value.
+    pub fn has_z(&self) -> bool {
+        matches!(self, Dimension::XYZ | Dimension::XYZM)
+    }
+
+    /// Whether coordinates of this dimension have an m value.
+    pub fn has_m(&self) -> bool {
+        matches!(self, Dimension::XYM | Dimension::XYZM)
+    }
+}
 
 pub trait GeometryArrayTrait<'a> {
     type Scalar: RTreeObject;
@@ -38,8 +153,37 @@ pub trait GeometryArrayTrait<'a> {
     /// This is `O(1)`.
     fn into_arrow(self) -> Self::ArrowArray;
 
-    /// Build an [`RTree`] spatial index containing this array's geometries.
-    fn rstar_tree(&'a self) -> RTree<Self::Scalar>;
+    /// Build an [`RTree`] spatial index containing this array's non-null geometries.
+    ///
+    /// The tree is bulk loaded, and each entry caches its geometry's envelope and row index so
+    /// that queries don't recompute envelopes.
+    fn rstar_tree(&'a self) -> RTree<CachedEnvelope<Self::Scalar>> {
+        let entries = (0..self.len())
+            .filter_map(|i| self.get(i).map(|geom| CachedEnvelope::new(geom, i)))
+            .collect();
+        RTree::bulk_load(entries)
+    }
+
+    /// The physical layout of this array.
+    fn geometry_type(&self) -> GeometryArrayType;
+
+    /// The dimensions of the coordinates of this array.
+    ///
+    /// Native arrays store x and y coordinates, and point and line string arrays may also store
+    /// m values.
+    fn dimension(&self) -> Dimension {
+        Dimension::XY
+    }
+
+    /// Returns `true` if the coordinates of this array have a z value.
+    fn has_z(&self) -> bool {
+        self.dimension().has_z()
+    }
+
+    /// Returns `true` if the coordinates of this array have an m value.
+    fn has_m(&self) -> bool {
+        self.dimension().has_m()
+    }
 
     /// The number of geometries contained in this array.
     fn len(&self) -> usize;
@@ -99,10 +243,40 @@ pub trait GeometryArrayTrait<'a> {
     /// The caller must ensure that `offset + length <= self.len()`
     unsafe fn slice_unchecked(&mut self, offset: usize, length: usize);
 
-    // /// Clones this [`GeometryArray`] with a new new assigned bitmap.
-    // /// # Panic
-    // /// This function panics iff `validity.len() != self.len()`.
-    // fn with_validity(&self, validity: Option<Bitmap>) -> Box<dyn GeometryArray>;
+    /// Slices the array in place to the geometries in `range`.
+    ///
+    /// Empty ranges are allowed anywhere up to and including `self.len()`, and produce an empty
+    /// array.
+    /// # Panic
+    /// This function panics iff `range.start > range.end` or `range.end > self.len()`.
+    fn slice_range(&mut self, range: Range<usize>) {
+        assert!(
+            range.start <= range.end,
+            "range start may not exceed range end"
+        );
+        self.slice(range.start, range.end - range.start)
+    }
+
+    /// Returns a copy of this array holding only the geometries in `range`.
+    /// # Panic
+    /// This function panics iff `range.start > range.end` or `range.end > self.len()`.
+    fn sliced_range(&self, range: Range<usize>) -> Self
+    where
+        Self: Sized,
+    {
+        let mut array = *self.to_boxed();
+        array.slice_range(range);
+        array
+    }
+
+    /// Clones this array with a new assigned validity, keeping its values.
+    ///
+    /// Values of slots that become valid are those stored under the previous validity.
+    /// # Panic
+    /// This function panics iff `validity.len() != self.len()`.
+    fn with_validity(&self, validity: Option<Bitmap>) -> Self
+    where
+        Self: Sized;
 
     /// Clones this array to an owned, boxed geometry array.
     fn to_boxed(&self) -> Box<Self>;
@@ -141,8 +315,8 @@ pub trait MutableGeometryArray: std::fmt::Debug + Send + Sync {
     /// Convert to mutable `Any`, to enable dynamic casting.
     fn as_mut_any(&mut self) -> &mut dyn Any;
 
-    // /// Adds a new null element to the array.
-    // fn push_null(&mut self);
+    /// Adds a new null element to the array.
+    fn push_null(&mut self);
 
     /// Whether `index` is valid / set.
     /// # Panic
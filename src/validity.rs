@@ -0,0 +1,84 @@
+//! Helpers for propagating nulls between geometry arrays and the outputs of kernels.
+
+use arrow2::bitmap::Bitmap;
+
+/// The validity of values that are null wherever either input is null.
+///
+/// `None` means all values are valid, as in
+/// [`GeometryArrayTrait::validity`](crate::GeometryArrayTrait::validity).
+/// # Panic
+/// This function panics iff both validities are given and have different lengths.
+pub fn combine_validities(left: Option<&Bitmap>, right: Option<&Bitmap>) -> Option<Bitmap> {
+    match (left, right) {
+        (Some(left), Some(right)) => {
+            assert_eq!(
+                left.len(),
+                right.len(),
+                "validities must have the same length"
+            );
+            Some(left & right)
+        }
+        (Some(validity), None) | (None, Some(validity)) => Some(validity.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Panic unless `validity` is `None` or has length `len`.
+pub(crate) fn check_validity_len(validity: Option<&Bitmap>, len: usize) {
+    assert!(
+        validity.map_or(true, |validity| validity.len() == len),
+        "validity mask length must match the number of values"
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{GeometryArray, GeometryArrayTrait, MixedGeometryArray, PointArray};
+    use geo::{line_string, point};
+
+    #[test]
+    fn combine() {
+        let left = Bitmap::from([true, false, true]);
+        let right = Bitmap::from([true, true, false]);
+        let combined = combine_validities(Some(&left), Some(&right)).unwrap();
+        assert_eq!(
+            combined.iter().collect::<Vec<_>>(),
+            vec![true, false, false]
+        );
+        assert_eq!(combine_validities(None, Some(&right)), Some(right));
+        assert_eq!(combine_validities(None, None), None);
+    }
+
+    #[test]
+    fn with_validity() {
+        let points: PointArray = vec![point!(x: 0., y: 1.), point!(x: 2., y: 3.)].into();
+        let nulled = points.with_validity(Some(Bitmap::from([false, true])));
+        assert!(nulled.is_null(0));
+        assert_eq!(nulled.get_as_geo(1), Some(point!(x: 2., y: 3.)));
+        assert!(points.is_valid(0));
+
+        let mixed = MixedGeometryArray::try_from(vec![
+            Some(geo::Geometry::Point(point!(x: 0., y: 1.))),
+            Some(geo::Geometry::LineString(
+                line_string![(x: 0., y: 1.), (x: 2., y: 3.)],
+            )),
+        ])
+        .unwrap();
+        let nulled =
+            GeometryArray::Mixed(Box::new(mixed)).with_validity(Some(Bitmap::from([true, false])));
+        assert!(nulled.is_valid(0));
+        assert!(nulled.is_null(1));
+        match nulled {
+            GeometryArray::Mixed(mixed) => assert!(mixed.line_strings().is_null(0)),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn with_validity_wrong_length() {
+        let points: PointArray = vec![point!(x: 0., y: 1.)].into();
+        points.with_validity(Some(Bitmap::from([true, false])));
+    }
+}